@@ -0,0 +1,46 @@
+//! Compares sequential and parallel parsing of a synthetic clippy JSON stream.
+//!
+//! Run with `cargo bench --bench parse`. Set `PARSE_BENCH_LINES` to change the
+//! workload size (defaults to 200,000 diagnostics).
+
+use std::time::{Duration, Instant};
+use cargo_analyzer::parser::WarningParser;
+use cargo_analyzer::runner::bench::synthetic_workload;
+
+fn time<F: FnMut() -> usize>(mut f: F) -> (Duration, usize) {
+    let start = Instant::now();
+    let count = f();
+    (start.elapsed(), count)
+}
+
+fn main() -> std::io::Result<()> {
+    let lines = std::env::var("PARSE_BENCH_LINES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(200_000);
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("parse_bench.json");
+    std::fs::write(&path, synthetic_workload(lines))?;
+    let input = path.to_str().expect("temp path is valid UTF-8");
+
+    let (baseline, expected) = time(|| WarningParser::parse_file(input).map(|r| r.0.len()).unwrap_or(0));
+    println!("sequential        {:>10.2?}  ({} warnings)", baseline, expected);
+
+    let max_jobs = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4).max(2);
+    let mut jobs = 2;
+    while jobs <= max_jobs {
+        let (elapsed, count) = time(|| {
+            WarningParser::parse_file_parallel(input, jobs).map(|r| r.0.len()).unwrap_or(0)
+        });
+        assert_eq!(count, expected, "parallel parse must produce the same warnings");
+        println!(
+            "parallel (-j {:>2})  {:>10.2?}  speedup {:.2}x",
+            jobs,
+            elapsed,
+            baseline.as_secs_f64() / elapsed.as_secs_f64()
+        );
+        jobs *= 2;
+    }
+
+    Ok(())
+}
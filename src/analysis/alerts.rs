@@ -0,0 +1,205 @@
+//! Rate-of-change alerts.
+//!
+//! An alert rule watches one lint's warning count over time, for lints whose
+//! total is tolerated but whose growth is not:
+//!
+//! ```toml
+//! alert-if = ['"clippy::unwrap_used" increases by > 5 per week']
+//! ```
+//!
+//! The period is `run`, `day`, `week`, or `month`. A rule is evaluated
+//! against the latest recorded run at least one period old, with the change
+//! scaled down to one period; when history is shorter than the period, the
+//! oldest run is used and the change is taken as is, so a young history
+//! never extrapolates a spike into an alert.
+
+use std::fmt;
+use std::str::FromStr;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use crate::analysis::{statistics::warning::WarningStatistics, trends::TrendAnalysis};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertPeriod {
+    Run,
+    Day,
+    Week,
+    Month,
+}
+
+impl AlertPeriod {
+    /// Length in days; `None` for a run, compared with the previous run.
+    pub fn days(&self) -> Option<i64> {
+        match self {
+            AlertPeriod::Run => None,
+            AlertPeriod::Day => Some(1),
+            AlertPeriod::Week => Some(7),
+            AlertPeriod::Month => Some(30),
+        }
+    }
+}
+
+impl fmt::Display for AlertPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlertPeriod::Run => write!(f, "run"),
+            AlertPeriod::Day => write!(f, "day"),
+            AlertPeriod::Week => write!(f, "week"),
+            AlertPeriod::Month => write!(f, "month"),
+        }
+    }
+}
+
+/// `"<lint>" increases by > <count> per <period>`, from the `alert-if`
+/// config list.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct AlertRule {
+    pub lint: String,
+    /// Increase per period that must be exceeded
+    pub threshold: f64,
+    pub period: AlertPeriod,
+}
+
+impl FromStr for AlertRule {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid alert rule `{}`; expected `\"<lint>\" increases by > <count> per <period>`", rule);
+        let words: Vec<&str> = rule.split_whitespace().collect();
+        let [lint, "increases", "by", ">", threshold, "per", period] = words.as_slice() else {
+            return Err(invalid());
+        };
+        let lint = lint.trim_matches('"');
+        let threshold: f64 = threshold.parse().map_err(|_| invalid())?;
+        let period = match *period {
+            "run" => AlertPeriod::Run,
+            "day" => AlertPeriod::Day,
+            "week" => AlertPeriod::Week,
+            "month" => AlertPeriod::Month,
+            other => return Err(format!("unknown alert period `{}`; expected run, day, week, or month", other)),
+        };
+        if lint.is_empty() || threshold < 0.0 {
+            return Err(invalid());
+        }
+        Ok(AlertRule { lint: lint.to_string(), threshold, period })
+    }
+}
+
+impl TryFrom<String> for AlertRule {
+    type Error = String;
+
+    fn try_from(rule: String) -> Result<Self, Self::Error> {
+        rule.parse()
+    }
+}
+
+impl fmt::Display for AlertRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" increases by > {} per {}", self.lint, self.threshold, self.period)
+    }
+}
+
+/// A rule whose lint grew faster than it allows.
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub rule: AlertRule,
+    /// Count in the run compared against
+    pub previous: usize,
+    /// Date of that run
+    pub since: String,
+    pub current: usize,
+    /// Increase per period
+    pub rate: f64,
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} increased by {:.1} per {} ({} on {} → {} now; alert above {})",
+            self.rule.lint, self.rate, self.rule.period, self.previous, self.since, self.current, self.rule.threshold
+        )
+    }
+}
+
+/// Evaluates the rules against the current run and the earlier runs in
+/// `history` (oldest first), as of `today`.
+pub fn evaluate_alerts(
+    rules: &[AlertRule],
+    current: &WarningStatistics,
+    history: &[TrendAnalysis],
+    today: NaiveDate,
+) -> Vec<Alert> {
+    let runs: Vec<(NaiveDate, WarningStatistics)> = history
+        .iter()
+        .filter_map(|trend| {
+            let date = NaiveDate::parse_from_str(trend.dates.first()?, "%Y-%m-%d").ok()?;
+            Some((date, WarningStatistics::from(trend)))
+        })
+        .collect();
+
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let (date, stats, scale) = match rule.period.days() {
+                None => runs.last().map(|(date, stats)| (date, stats, 1.0))?,
+                Some(days) => {
+                    let (date, stats) = runs
+                        .iter()
+                        .rev()
+                        .find(|(date, _)| (today - *date).num_days() >= days)
+                        .or_else(|| runs.first())?;
+                    let elapsed = (today - *date).num_days().max(days);
+                    (date, stats, days as f64 / elapsed as f64)
+                }
+            };
+            let count = |stats: &WarningStatistics| stats.by_lint.get(&rule.lint).copied().unwrap_or(0);
+            let (previous, now) = (count(stats), count(current));
+            let rate = (now as f64 - previous as f64) * scale;
+            (rate > rule.threshold).then(|| Alert {
+                rule: rule.clone(),
+                previous,
+                since: date.format("%Y-%m-%d").to_string(),
+                current: now,
+                rate,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_alert_rates() {
+        let rule: AlertRule = "\"clippy::unwrap_used\" increases by > 5 per week".parse().unwrap();
+        assert_eq!(rule.period, AlertPeriod::Week);
+        assert!("clippy::unwrap_used grows fast".parse::<AlertRule>().is_err());
+
+        let run = |date: &str, count| TrendAnalysis {
+            dates: vec![date.to_string()],
+            recurring_issues: HashMap::from([("clippy::unwrap_used: used `unwrap()`".to_string(), count)]),
+            ..Default::default()
+        };
+        let history = [run("2026-09-01", 2), run("2026-10-02", 4), run("2026-10-12", 10)];
+        let current = WarningStatistics {
+            by_lint: HashMap::from([("clippy::unwrap_used".to_string(), 16)]),
+            ..Default::default()
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+
+        // Compared with 2026-10-02, the latest run a week old: 12 over 14 days
+        let alerts = evaluate_alerts(std::slice::from_ref(&rule), &current, &history, today);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].previous, alerts[0].since.as_str()), (4, "2026-10-02"));
+        assert_eq!(alerts[0].rate, 6.0);
+
+        let per_run: AlertRule = "clippy::unwrap_used increases by > 6 per run".parse().unwrap();
+        assert!(evaluate_alerts(&[per_run], &current, &history, today).is_empty());
+        let young = [run("2026-10-14", 3)];
+        assert_eq!(evaluate_alerts(&[rule], &current, &young, today)[0].rate, 13.0);
+    }
+}
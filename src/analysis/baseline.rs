@@ -0,0 +1,415 @@
+//! Stored analyses of baseline branches.
+//!
+//! With `--auto-baseline <branch>`, the current run is compared against the
+//! analysis stored for the head commit of that branch, so CI can report what
+//! a change adds or fixes without anyone managing baseline files. Analyses
+//! are looked up in a baseline store, a directory holding the warnings of
+//! each analyzed commit as `<commit>.json` (e.g. a cached CI artifact), and
+//! then in the history database.
+//!
+//! Stored analyses are self-describing: each file records the commit and
+//! branch, when and with which analyzer, rustc, and clippy versions it was
+//! made, and the workspace root its paths are relative to. `baseline
+//! export` and `baseline import` copy them between stores, so a developer
+//! machine can compare against the baselines CI produced. Files from before
+//! the metadata, a bare array of warnings, still load.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::types::Warning;
+use super::fingerprint::{FingerprintDiff, diff_fingerprints, fingerprint_counts, follow_renamed};
+use super::renames::RenameMap;
+use super::statistics::{delta::StatsDelta, warning::WarningStatistics};
+
+/// Where a baseline analysis was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BaselineSource {
+    Store(PathBuf),
+    History,
+    /// A JSON or JSONL warnings export
+    File(PathBuf),
+}
+
+/// The analysis of a baseline branch's head commit.
+#[derive(Debug)]
+pub struct Baseline {
+    pub branch: String,
+    pub commit: String,
+    pub source: BaselineSource,
+    pub stats: WarningStatistics,
+    /// Warnings by fingerprint; empty when the baseline was recorded
+    /// without fingerprints
+    pub fingerprints: HashMap<String, usize>,
+    /// The baseline's warnings, when loaded from the baseline store
+    pub warnings: Vec<Warning>,
+    /// Whether `fingerprints` are message fingerprints, for warnings stored
+    /// before fingerprints were assigned during analysis
+    pub by_message: bool,
+}
+
+impl Baseline {
+    /// A baseline from warnings loaded from the store.
+    pub fn from_warnings(branch: &str, commit: &str, source: BaselineSource, warnings: Vec<Warning>) -> Self {
+        let files: HashSet<&str> = warnings.iter().map(|w| w.file.as_str()).collect();
+        let stats = WarningStatistics::from_warnings(&warnings, files.len());
+        let by_message = warnings.iter().any(|w| w.fingerprint.is_empty());
+        let fingerprints = if by_message {
+            message_counts(&warnings)
+        } else {
+            fingerprint_counts(&warnings)
+        };
+        Self {
+            branch: branch.to_string(),
+            commit: commit.to_string(),
+            source,
+            stats,
+            fingerprints,
+            warnings,
+            by_message,
+        }
+    }
+
+    /// Moves the baseline's warnings in files renamed since to their
+    /// current paths and fingerprints, given the fingerprints the current
+    /// warnings had under their earlier paths (see
+    /// [`Fingerprinter::renamed_fingerprints`](super::fingerprint::Fingerprinter::renamed_fingerprints)).
+    pub fn follow_renames(&mut self, renames: &RenameMap, renamed: &HashMap<String, String>) {
+        if renames.is_empty() {
+            return;
+        }
+        self.stats.by_file = renames.follow(&self.stats.by_file);
+        for warning in &mut self.warnings {
+            warning.file = renames.current_path(&warning.file).to_string();
+            if let Some(current) = renamed.get(&warning.fingerprint) {
+                warning.fingerprint.clone_from(current);
+            }
+        }
+        if self.by_message {
+            // Message fingerprints hash the path, now the current one
+            self.fingerprints = message_counts(&self.warnings);
+        } else {
+            self.fingerprints = follow_renamed(&self.fingerprints, renamed);
+        }
+    }
+
+    /// Changes from the baseline to `current`.
+    pub fn delta(&self, current: &WarningStatistics) -> StatsDelta {
+        current.diff(&self.stats)
+    }
+
+    /// The warnings of `current` that are new and the baseline warnings
+    /// that are gone, matched by fingerprint. `None` when the baseline has
+    /// no fingerprints.
+    pub fn warning_diff<'a>(&self, current: &'a [Warning]) -> Option<FingerprintDiff<'a>> {
+        if self.fingerprints.is_empty() && self.stats.total_warnings > 0 {
+            return None;
+        }
+        let key = if self.by_message { Warning::message_fingerprint } else { Warning::fingerprint };
+        Some(diff_fingerprints(&self.fingerprints, current, key))
+    }
+
+    /// The stored baseline warnings among `diff.fixed`.
+    pub fn fixed_warnings(&self, diff: &FingerprintDiff) -> Vec<&Warning> {
+        let mut remaining = diff.fixed.clone();
+        self.warnings
+            .iter()
+            .filter(|warning| {
+                let key = if self.by_message { warning.message_fingerprint() } else { warning.fingerprint() };
+                match remaining.get_mut(&key) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        true
+                    }
+                    _ => false,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Version of the baseline file format; files of later versions are
+/// rejected
+pub const BASELINE_FORMAT_VERSION: u32 = 1;
+
+/// Where, when, and with what a baseline analysis was made.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineMeta {
+    /// 0 for files from before the metadata
+    #[serde(default)]
+    pub format_version: u32,
+    pub commit: String,
+    pub branch: Option<String>,
+    /// RFC 3339 time of the analysis
+    pub created: Option<String>,
+    pub analyzer_version: Option<String>,
+    /// `rustc -V` output
+    pub rustc_version: Option<String>,
+    /// `cargo clippy -V` output
+    pub clippy_version: Option<String>,
+    /// Workspace root the warning paths are relative to; absolute paths
+    /// under it are made relative on import
+    pub root: Option<String>,
+}
+
+impl BaselineMeta {
+    /// Metadata of an analysis of `commit` made now by this analyzer.
+    pub fn new(commit: &str) -> Self {
+        Self {
+            format_version: BASELINE_FORMAT_VERSION,
+            commit: commit.to_string(),
+            created: Some(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+            analyzer_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            ..Self::default()
+        }
+    }
+}
+
+/// A baseline file: the warnings of one commit and how they were produced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineFile {
+    #[serde(flatten)]
+    pub meta: BaselineMeta,
+    pub warnings: Vec<Warning>,
+}
+
+/// The contents of a baseline file, in either format.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredBaseline {
+    File(BaselineFile),
+    Warnings(Vec<Warning>),
+}
+
+impl BaselineFile {
+    pub fn new(meta: BaselineMeta, warnings: Vec<Warning>) -> Self {
+        Self { meta, warnings }
+    }
+
+    /// Reads a baseline file; one without metadata gets `commit`, if known.
+    pub fn read(path: &Path, commit: Option<&str>) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let stored = serde_json::from_reader(reader).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("Invalid baseline {}: {}", path.display(), e))
+        })?;
+        let file = match stored {
+            StoredBaseline::File(file) => file,
+            StoredBaseline::Warnings(warnings) => Self {
+                meta: BaselineMeta { commit: commit.unwrap_or_default().to_string(), ..BaselineMeta::default() },
+                warnings,
+            },
+        };
+        if file.meta.format_version > BASELINE_FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Baseline {} has format version {}, newer than this analyzer supports ({})",
+                    path.display(),
+                    file.meta.format_version,
+                    BASELINE_FORMAT_VERSION
+                ),
+            ));
+        }
+        Ok(file)
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    /// Makes warning paths relative to the workspace root, with forward
+    /// slashes. Warnings whose path changed lose their fingerprint, which
+    /// hashed the old path, and are matched by message instead. Returns how
+    /// many paths changed.
+    pub fn remap_paths(&mut self) -> usize {
+        let root = self.meta.root.as_deref().map(|root| format!("{}/", root.replace('\\', "/").trim_end_matches('/')));
+        let mut remapped = 0;
+        for warning in &mut self.warnings {
+            let mut file = warning.file.replace('\\', "/");
+            if let Some(relative) = root.as_deref().and_then(|root| file.strip_prefix(root)) {
+                file = relative.to_string();
+            }
+            if let Some(relative) = file.strip_prefix("./") {
+                file = relative.to_string();
+            }
+            if file != warning.file {
+                warning.file = file;
+                warning.fingerprint.clear();
+                remapped += 1;
+            }
+        }
+        remapped
+    }
+
+    /// How the toolchain that made this baseline differs from the given
+    /// `rustc -V` and `cargo clippy -V` versions; lints change between
+    /// releases, so a comparison across them may report spurious changes.
+    pub fn toolchain_mismatches(&self, rustc: Option<&str>, clippy: Option<&str>) -> Vec<String> {
+        let mut mismatches = Vec::new();
+        for (tool, theirs, ours) in [("rustc", &self.meta.rustc_version, rustc), ("clippy", &self.meta.clippy_version, clippy)] {
+            if let (Some(theirs), Some(ours)) = (theirs, ours) {
+                if theirs != ours {
+                    mismatches.push(format!("{}: baseline made with {}, local is {}", tool, theirs, ours));
+                }
+            }
+        }
+        mismatches
+    }
+}
+
+/// Directory of analyses keyed by commit.
+#[derive(Debug, Clone)]
+pub struct BaselineStore {
+    dir: PathBuf,
+}
+
+impl BaselineStore {
+    pub fn new(dir: &Path) -> Self {
+        Self { dir: dir.to_path_buf() }
+    }
+
+    pub fn path_for(&self, commit: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", commit))
+    }
+
+    /// Stores the analysis of its commit, replacing any earlier one. The
+    /// commit names the file, so it must be a commit hash.
+    pub fn save(&self, baseline: &BaselineFile) -> io::Result<PathBuf> {
+        if !is_commit_hash(&baseline.meta.commit) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{}' is not a commit hash", baseline.meta.commit),
+            ));
+        }
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.path_for(&baseline.meta.commit);
+        baseline.write(&path)?;
+        Ok(path)
+    }
+
+    /// Loads the analysis stored for `commit`, if any.
+    pub fn load(&self, commit: &str) -> io::Result<Option<BaselineFile>> {
+        let path = self.path_for(commit);
+        if !path.exists() {
+            return Ok(None);
+        }
+        BaselineFile::read(&path, Some(commit)).map(Some)
+    }
+}
+
+/// Whether `commit` is a full or abbreviated commit hash, 7 to 40 hex
+/// digits.
+pub fn is_commit_hash(commit: &str) -> bool {
+    (7..=40).contains(&commit.len()) && commit.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Warnings by message fingerprint.
+fn message_counts(warnings: &[Warning]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for warning in warnings {
+        *counts.entry(warning.message_fingerprint()).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CategoryType, Priority};
+
+    #[test]
+    fn test_store_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let store = BaselineStore::new(dir);
+        assert!(store.load("abc1234").unwrap().is_none());
+
+        let warning = Warning {
+            primary_message: "used `unwrap()`".to_string(),
+            category: CategoryType::Safety,
+            priority: Priority::Critical,
+            ..Warning::for_test("clippy::unwrap_used", "src/lib.rs", 3)
+        };
+        let outside = BaselineMeta::new("../../outside");
+        let error = store.save(&BaselineFile::new(outside, Vec::new())).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        let meta = BaselineMeta::new("abc1234");
+        store.save(&BaselineFile::new(meta.clone(), vec![warning.clone(), warning])).unwrap();
+        let loaded = store.load("abc1234").unwrap().unwrap();
+        assert_eq!(loaded.meta, meta);
+        let loaded = loaded.warnings;
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].lint_code, "clippy::unwrap_used");
+
+        // Warnings stored before the message was split into fields
+        let old: Warning = serde_json::from_value(serde_json::json!({
+            "id": "clippy::unwrap_used",
+            "message": "used `unwrap()`\nLocation: 3:5-3:9-src/lib.rs\nExplanation: \nChild messages: []",
+            "category": "Safety",
+            "priority": "Critical",
+            "file": "src/lib.rs",
+            "line": 3,
+            "suggested_fix": null
+        }))
+        .unwrap();
+        assert_eq!(old.lint_code, "clippy::unwrap_used");
+        assert_eq!(old.primary_message, "used `unwrap()`");
+        assert_eq!(old.fingerprint(), loaded[0].fingerprint());
+
+        // Stored without assigned fingerprints, so matched by message
+        let baseline = Baseline::from_warnings("main", "abc1234", BaselineSource::History, loaded);
+        assert!(baseline.by_message);
+        let mut current = vec![old.clone(), old];
+        current[0].fingerprint = "0123456789abcdef".to_string();
+        current[1].primary_message = "used `expect()`".to_string();
+        let diff = baseline.warning_diff(&current).unwrap();
+        assert_eq!(diff.new.len(), 1);
+        assert_eq!(diff.new[0].primary_message, "used `expect()`");
+        assert_eq!(baseline.fixed_warnings(&diff).len(), 1);
+    }
+
+    #[test]
+    fn test_portable_baseline_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let warning = |file: &str| {
+            let mut warning: Warning = serde_json::from_value(serde_json::json!({
+                "lint_code": "clippy::len_zero",
+                "primary_message": "length comparison to zero",
+                "category": "Style",
+                "priority": "Low",
+                "file": file,
+                "line": 7,
+            }))
+            .unwrap();
+            warning.fingerprint = "0123456789abcdef".to_string();
+            warning
+        };
+
+        // A bare array, as stored before baselines described themselves
+        let old = dir.join("abc123.json");
+        serde_json::to_writer(File::create(&old).unwrap(), &[warning("src/lib.rs")]).unwrap();
+        let file = BaselineFile::read(&old, Some("abc123")).unwrap();
+        assert_eq!((file.meta.format_version, file.meta.commit.as_str()), (0, "abc123"));
+
+        let mut meta = BaselineMeta::new("def456");
+        meta.root = Some("C:\\ci\\work\\".to_string());
+        meta.rustc_version = Some("rustc 1.80.0".to_string());
+        let mut file = BaselineFile::new(meta, vec![warning("C:\\ci\\work\\src\\main.rs"), warning("src/lib.rs")]);
+        assert_eq!(file.remap_paths(), 1);
+        assert_eq!(file.warnings[0].file, "src/main.rs");
+        assert!(file.warnings[0].fingerprint.is_empty() && !file.warnings[1].fingerprint.is_empty());
+        assert_eq!(file.toolchain_mismatches(Some("rustc 1.81.0"), None).len(), 1);
+        assert!(file.toolchain_mismatches(Some("rustc 1.80.0"), Some("clippy 0.1.80")).is_empty());
+
+        let newer = dir.join("newer.json");
+        file.meta.format_version = BASELINE_FORMAT_VERSION + 1;
+        file.write(&newer).unwrap();
+        let error = BaselineFile::read(&newer, None).unwrap_err();
+        assert!(error.to_string().contains("newer than this analyzer supports"));
+    }
+}
@@ -0,0 +1,165 @@
+//! Restricting an analysis to the lines a change touches.
+//!
+//! With `--base <ref>`, the working tree is diffed against the merge base
+//! with that ref, and only warnings on added or modified lines are
+//! reported: what a reviewer needs for incremental enforcement. Warnings
+//! elsewhere in the touched files are kept aside as pre-existing, and
+//! warnings in untouched files are dropped. With `--changed-only`, every
+//! line of a touched file counts as changed, so all of its warnings are
+//! kept: less precise, but robust to code moving within a file.
+
+use std::collections::{HashMap, HashSet};
+use crate::types::{FileWarnings, Warning};
+
+/// Lines added or modified since a base commit, by file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangedLines {
+    /// The ref compared against, for display
+    pub base: String,
+    /// Whether whole files are compared rather than lines
+    pub whole_files: bool,
+    /// Inclusive line ranges on the new side of the diff
+    files: HashMap<String, Vec<(u32, u32)>>,
+}
+
+impl ChangedLines {
+    pub fn new(base: &str) -> Self {
+        Self { base: base.to_string(), whole_files: false, files: HashMap::new() }
+    }
+
+    /// Every line of `files` as changed, e.g. from `git diff --name-only`.
+    pub fn from_files<'a>(base: &str, files: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut changed = Self { whole_files: true, ..Self::new(base) };
+        for file in files {
+            changed.add_file(file);
+        }
+        changed
+    }
+
+    /// Reads the hunks of a `git diff --unified=0` against `base`.
+    pub fn from_diff(base: &str, diff: &str) -> Self {
+        let mut changed = Self::new(base);
+        let mut file: Option<String> = None;
+        for line in diff.lines() {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                file = path.strip_prefix("b/").map(str::to_string);
+            } else if let (Some(hunk), Some(file)) = (line.strip_prefix("@@ "), &file) {
+                // @@ -a,b +c,d @@: d lines from c, a pure deletion when d is 0
+                let Some(new) = hunk.split_whitespace().find_map(|part| part.strip_prefix('+')) else { continue };
+                let mut numbers = new.split(',').map(|n| n.parse::<u32>().ok());
+                let (Some(Some(start)), count) = (numbers.next(), numbers.next().flatten().unwrap_or(1)) else { continue };
+                if count > 0 {
+                    changed.files.entry(file.clone()).or_default().push((start, start + count - 1));
+                }
+            }
+        }
+        changed
+    }
+
+    /// Marks every line of `file` as changed, e.g. for an untracked file.
+    pub fn add_file(&mut self, file: &str) {
+        self.files.insert(file.to_string(), vec![(1, u32::MAX)]);
+    }
+
+    /// Whether the change touches `file`.
+    pub fn touches(&self, file: &str) -> bool {
+        self.files.contains_key(file.strip_prefix("./").unwrap_or(file))
+    }
+
+    /// Whether `warning`'s lines overlap a changed line.
+    pub fn contains(&self, warning: &Warning) -> bool {
+        let Some(ranges) = self.files.get(warning.file.strip_prefix("./").unwrap_or(&warning.file)) else { return false };
+        let (start, end) = match warning.span {
+            Some(span) => (span.line_start, span.line_end.max(span.line_start)),
+            None => (warning.line, warning.line),
+        };
+        ranges.iter().any(|(from, to)| start <= *to && end >= *from)
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Keeps the warnings on changed lines and returns the other warnings
+    /// of touched files, plus how many warnings were in untouched files.
+    pub fn retain(
+        &self,
+        warnings: &mut Vec<Warning>,
+        file_warnings: &mut HashMap<String, FileWarnings>,
+    ) -> (Vec<Warning>, usize) {
+        let mut pre_existing = Vec::new();
+        let mut untouched = 0;
+        let mut kept = Vec::with_capacity(warnings.len());
+        for warning in warnings.drain(..) {
+            if self.contains(&warning) {
+                kept.push(warning);
+            } else if self.touches(&warning.file) {
+                pre_existing.push(warning);
+            } else {
+                untouched += 1;
+            }
+        }
+        *warnings = kept;
+        let kept: HashSet<String> = warnings.iter().map(|w| w.fingerprint()).collect();
+        for fw in file_warnings.values_mut() {
+            fw.warnings.retain(|w| kept.contains(&w.fingerprint()));
+        }
+        file_warnings.retain(|_, fw| !fw.warnings.is_empty());
+        (pre_existing, untouched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Priority, Span};
+
+    #[test]
+    fn test_changed_lines_from_diff() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3,0 +4,2 @@ fn main() {
++    let x = 1;
++    let y = 2;
+@@ -20 +22 @@
+-old
++new
+@@ -30,2 +31,0 @@
+diff --git a/src/gone.rs b/src/gone.rs
+--- a/src/gone.rs
++++ /dev/null
+@@ -1,3 +0,0 @@
+";
+        let mut changed = ChangedLines::from_diff("main", diff);
+        changed.add_file("src/new.rs");
+        let warning = |file: &str, line: u32, span: Option<Span>| Warning {
+            primary_message: format!("{}:{}", file, line),
+            span,
+            priority: Priority::Low,
+            fingerprint: format!("{}:{}", file, line),
+            ..Warning::for_test("clippy::len_zero", file, line)
+        };
+        let multiline = Span { line_start: 20, line_end: 23, ..Span::at(20, 1) };
+        let mut warnings = vec![
+            warning("src/lib.rs", 5, None),
+            warning("src/lib.rs", 20, Some(multiline)),
+            warning("src/lib.rs", 31, None),
+            warning("src/new.rs", 100, None),
+            warning("src/main.rs", 4, None),
+        ];
+        let (pre_existing, untouched) = changed.retain(&mut warnings, &mut HashMap::new());
+        let lines: Vec<(&str, u32)> = warnings.iter().map(|w| (w.file.as_str(), w.line)).collect();
+        assert_eq!(lines, [("src/lib.rs", 5), ("src/lib.rs", 20), ("src/new.rs", 100)]);
+        assert_eq!(pre_existing.len(), 1);
+        assert_eq!(pre_existing[0].line, 31);
+        assert_eq!(untouched, 1);
+        assert!(!changed.touches("src/gone.rs"));
+
+        let changed = ChangedLines::from_files("main", ["src/lib.rs"]);
+        let mut warnings = vec![warning("src/lib.rs", 31, None), warning("src/main.rs", 4, None)];
+        let (pre_existing, untouched) = changed.retain(&mut warnings, &mut HashMap::new());
+        assert_eq!((warnings.len(), pre_existing.len(), untouched), (1, 0, 1));
+    }
+}
@@ -1,5 +1,3 @@
-use termcolor;
-
 #[derive(Debug)]
 pub enum ChartStyle {
     Basic,    // █
@@ -11,11 +9,27 @@ pub enum ChartStyle {
 #[derive(Debug)]
 pub struct ChartConfig {
     pub style: ChartStyle,
+    /// Never read: charts are plain text, colored by their writer
+    #[cfg(feature = "terminal")]
+    #[deprecated(note = "charts are plain text; color them when writing them")]
     pub color: Option<termcolor::Color>,
     pub width: usize,
     pub show_percentage: bool,
 }
 
+impl ChartConfig {
+    pub fn new(style: ChartStyle, width: usize, show_percentage: bool) -> Self {
+        #[allow(deprecated)]
+        Self {
+            style,
+            #[cfg(feature = "terminal")]
+            color: None,
+            width,
+            show_percentage,
+        }
+    }
+}
+
 pub fn create_enhanced_chart(data: &[(String, usize)], config: ChartConfig) -> String {
     let total_value: usize = data.iter().map(|(_, v)| *v).sum();
     let mut chart = String::new();
@@ -67,3 +81,84 @@ pub fn create_enhanced_chart(data: &[(String, usize)], config: ChartConfig) -> S
 
     chart
 }
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Fill characters for the series of a stacked bar, in series order
+pub const STACK_FILLS: [char; 4] = ['█', '▓', '▒', '░'];
+
+/// Renders a series as a one-line sparkline scaled between its minimum and
+/// maximum. A flat series renders at the lowest level.
+pub fn sparkline(values: &[usize]) -> String {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = (max - min) as f64;
+    values
+        .iter()
+        .map(|&value| {
+            if range == 0.0 {
+                SPARK_LEVELS[0]
+            } else {
+                let level = ((value - min) as f64 / range * (SPARK_LEVELS.len() - 1) as f64).round();
+                SPARK_LEVELS[level as usize]
+            }
+        })
+        .collect()
+}
+
+/// Describes the change between two values with an arrow, e.g. `▲ +3`.
+pub fn delta_arrow(previous: usize, current: usize) -> String {
+    let change = current as i64 - previous as i64;
+    match change {
+        0 => "▶ 0".to_string(),
+        c if c > 0 => format!("▲ +{}", c),
+        c => format!("▼ {}", c),
+    }
+}
+
+/// Renders one stacked horizontal bar per row. Each row holds one value per
+/// series, drawn with the matching `STACK_FILLS` character; bars are scaled
+/// so the largest row total spans `width` characters.
+pub fn create_stacked_chart(rows: &[(String, Vec<usize>)], series: &[&str], width: usize) -> String {
+    let max_total = rows.iter().map(|(_, values)| values.iter().sum::<usize>()).max().unwrap_or(0);
+    let mut chart = String::new();
+
+    for (label, values) in rows {
+        let mut bar = String::new();
+        for (i, value) in values.iter().enumerate() {
+            let segment = if max_total == 0 {
+                0
+            } else {
+                (*value as f64 / max_total as f64 * width as f64).round() as usize
+            };
+            let fill = STACK_FILLS[i % STACK_FILLS.len()];
+            bar.extend(std::iter::repeat_n(fill, segment));
+        }
+        let total: usize = values.iter().sum();
+        chart.push_str(&format!("{:<20} {} {}\n", label, bar, total));
+    }
+
+    let legend: Vec<String> = series
+        .iter()
+        .enumerate()
+        .map(|(i, name)| format!("{} {}", STACK_FILLS[i % STACK_FILLS.len()], name))
+        .collect();
+    chart.push_str(&format!("\n{}\n", legend.join("  ")));
+    chart
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline_and_delta() {
+        assert_eq!(sparkline(&[0, 7, 14]), "▁▅█");
+        assert_eq!(sparkline(&[3, 3]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(delta_arrow(5, 8), "▲ +3");
+        assert_eq!(delta_arrow(8, 5), "▼ -3");
+        assert_eq!(delta_arrow(4, 4), "▶ 0");
+    }
+}
@@ -0,0 +1,123 @@
+//! Recurring warning patterns.
+//!
+//! Groups warnings by lint and normalized message (see
+//! [`normalize_message`]), so "unused variable: `x`" and "unused variable:
+//! `y`" count as one pattern.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use crate::types::Warning;
+use super::normalize::normalize_message;
+
+/// Share of a cluster's warnings a directory needs to be called out
+const DOMINANT_DIR_SHARE: f64 = 0.5;
+
+#[derive(Debug, Clone)]
+pub struct WarningCluster {
+    pub lint: String,
+    pub template: String,
+    pub count: usize,
+    pub files: usize,
+    /// Directory holding most of the cluster's warnings, with their count
+    pub dominant_dir: Option<(String, usize)>,
+}
+
+impl WarningCluster {
+    /// One-line description, e.g. "clippy::needless_borrow occurs 143 times
+    /// across 27 files, mostly under src/proto/".
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} occurs {} time{} across {} file{}",
+            self.lint,
+            self.count,
+            if self.count == 1 { "" } else { "s" },
+            self.files,
+            if self.files == 1 { "" } else { "s" },
+        );
+        if let Some((dir, count)) = &self.dominant_dir {
+            summary.push_str(&format!(", mostly under {} ({} of {})", dir, count, self.count));
+        }
+        summary
+    }
+}
+
+/// Clusters `warnings` by lint and message template, largest first.
+pub fn cluster_warnings(warnings: &[Warning]) -> Vec<WarningCluster> {
+    let mut groups: HashMap<(&str, String), Vec<&Warning>> = HashMap::new();
+    for warning in warnings {
+        groups
+            .entry((warning.lint_code.as_str(), normalize_message(&warning.primary_message)))
+            .or_default()
+            .push(warning);
+    }
+
+    let mut clusters: Vec<WarningCluster> = groups
+        .into_iter()
+        .map(|((lint, template), members)| {
+            let files: HashSet<&str> = members.iter().map(|w| w.file.as_str()).collect();
+            WarningCluster {
+                lint: lint.to_string(),
+                template,
+                count: members.len(),
+                files: files.len(),
+                dominant_dir: dominant_dir(&members),
+            }
+        })
+        .collect();
+    clusters.sort_by(|a, b| {
+        b.count.cmp(&a.count)
+            .then_with(|| a.lint.cmp(&b.lint))
+            .then_with(|| a.template.cmp(&b.template))
+    });
+    clusters
+}
+
+fn dominant_dir(members: &[&Warning]) -> Option<(String, usize)> {
+    let mut by_dir: HashMap<String, usize> = HashMap::new();
+    for warning in members {
+        let Some(dir) = Path::new(&warning.file)
+            .parent()
+            .map(|dir| format!("{}/", dir.display()))
+            .filter(|dir| dir != "/")
+        else {
+            continue;
+        };
+        *by_dir.entry(dir).or_insert(0) += 1;
+    }
+    by_dir
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+        .filter(|(_, count)| members.len() > 1 && *count as f64 >= members.len() as f64 * DOMINANT_DIR_SHARE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    fn warning(id: &str, message: &str, file: &str) -> Warning {
+        Warning {
+            primary_message: message.to_string(),
+            priority: Priority::Low,
+            ..Warning::for_test(id, file, 1)
+        }
+    }
+
+    #[test]
+    fn test_cluster_warnings() {
+        let warnings = vec![
+            warning("unused_variables", "unused variable: `a`", "src/proto/a.rs"),
+            warning("unused_variables", "unused variable: `b`", "src/proto/b.rs"),
+            warning("unused_variables", "unused variable: `c`", "src/main.rs"),
+            warning("clippy::needless_borrow", "this expression creates a reference", "src/main.rs"),
+        ];
+
+        let clusters = cluster_warnings(&warnings);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(
+            clusters[0].summary(),
+            "unused_variables occurs 3 times across 3 files, mostly under src/proto/ (2 of 3)"
+        );
+        assert_eq!(clusters[1].summary(), "clippy::needless_borrow occurs 1 time across 1 file");
+    }
+}
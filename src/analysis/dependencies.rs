@@ -0,0 +1,168 @@
+//! Unused dependency detection.
+//!
+//! `cargo +nightly udeps` gives the most precise answer, including dev and
+//! build dependencies, but needs a nightly toolchain and the cargo-udeps
+//! binary. Without it, rustc's `unused_crate_dependencies` lint (allowed by
+//! default) is used when the analyzed crates enable it.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use serde::Deserialize;
+use crate::types::Warning;
+
+/// rustc lint reporting dependencies a crate never uses
+pub const UNUSED_CRATE_DEPENDENCIES: &str = "unused_crate_dependencies";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DependencyKind {
+    Normal,
+    Dev,
+    Build,
+}
+
+impl DependencyKind {
+    /// Manifest table the dependency is declared in.
+    pub fn table(&self) -> &'static str {
+        match self {
+            DependencyKind::Normal => "[dependencies]",
+            DependencyKind::Dev => "[dev-dependencies]",
+            DependencyKind::Build => "[build-dependencies]",
+        }
+    }
+}
+
+/// How the unused dependencies were found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyCheck {
+    Udeps,
+    Lint,
+}
+
+impl fmt::Display for DependencyCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencyCheck::Udeps => write!(f, "cargo-udeps"),
+            DependencyCheck::Lint => write!(f, "the `{}` lint", UNUSED_CRATE_DEPENDENCIES),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnusedDependency {
+    /// Package declaring the dependency
+    pub package: String,
+    pub dependency: String,
+    pub kind: DependencyKind,
+}
+
+impl UnusedDependency {
+    /// `cargo remove` invocation that drops the dependency.
+    pub fn removal_command(&self) -> String {
+        let kind = match self.kind {
+            DependencyKind::Normal => "",
+            DependencyKind::Dev => " --dev",
+            DependencyKind::Build => " --build",
+        };
+        format!("cargo remove -p {}{} {}", self.package, kind, self.dependency)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnusedDependencies {
+    pub check: DependencyCheck,
+    /// Sorted by package, then dependency
+    pub dependencies: Vec<UnusedDependency>,
+}
+
+#[derive(Deserialize)]
+struct UdepsOutput {
+    #[serde(default)]
+    unused_deps: BTreeMap<String, UdepsPackage>,
+}
+
+#[derive(Deserialize)]
+struct UdepsPackage {
+    #[serde(default)]
+    normal: Vec<String>,
+    #[serde(default)]
+    development: Vec<String>,
+    #[serde(default)]
+    build: Vec<String>,
+}
+
+impl UnusedDependencies {
+    /// Parses the output of `cargo udeps --output json`.
+    pub fn from_udeps_json(json: &str) -> serde_json::Result<Self> {
+        let output: UdepsOutput = serde_json::from_str(json)?;
+        let mut dependencies = Vec::new();
+        for (package_id, package) in output.unused_deps {
+            // Package ids look like `name 0.1.0 (path+file:///...)`
+            let name = package_id.split_whitespace().next().unwrap_or(&package_id);
+            let kinds = [
+                (DependencyKind::Normal, package.normal),
+                (DependencyKind::Dev, package.development),
+                (DependencyKind::Build, package.build),
+            ];
+            for (kind, names) in kinds {
+                dependencies.extend(names.into_iter().map(|dependency| UnusedDependency {
+                    package: name.to_string(),
+                    dependency,
+                    kind,
+                }));
+            }
+        }
+        dependencies.sort();
+        Ok(Self { check: DependencyCheck::Udeps, dependencies })
+    }
+
+    /// Collects dependencies reported by the `unused_crate_dependencies`
+    /// lint. The lint fires once per target, so duplicates are merged.
+    pub fn from_lints(warnings: &[Warning]) -> Self {
+        let dependencies: BTreeSet<UnusedDependency> = warnings
+            .iter()
+            .filter(|w| w.lint_code == UNUSED_CRATE_DEPENDENCIES)
+            .filter_map(|w| {
+                // "external crate `foo` unused in `bar`: remove the dependency ..."
+                let dependency = w.primary_message.split('`').nth(1)?;
+                Some(UnusedDependency {
+                    package: w.crate_name.clone().unwrap_or_else(|| "<package>".to_string()),
+                    dependency: dependency.to_string(),
+                    kind: DependencyKind::Normal,
+                })
+            })
+            .collect();
+        Self { check: DependencyCheck::Lint, dependencies: dependencies.into_iter().collect() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dependencies.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_udeps_json() {
+        let json = r#"{
+            "success": false,
+            "unused_deps": {
+                "demo 0.1.0 (path+file:///work/demo)": {
+                    "manifest_path": "/work/demo/Cargo.toml",
+                    "normal": ["regex"],
+                    "development": ["tempfile"],
+                    "build": []
+                }
+            },
+            "note": "Note: They might be false-positive."
+        }"#;
+        let unused = UnusedDependencies::from_udeps_json(json).unwrap();
+        assert_eq!(unused.check, DependencyCheck::Udeps);
+        let commands: Vec<String> = unused.dependencies.iter().map(|d| d.removal_command()).collect();
+        assert_eq!(commands, vec![
+            "cargo remove -p demo regex",
+            "cargo remove -p demo --dev tempfile",
+        ]);
+    }
+}
@@ -0,0 +1,140 @@
+//! Suspected false positives.
+//!
+//! Warnings a reviewer believes clippy got wrong are marked by fingerprint
+//! (see [`Warning::fingerprint`]), either in the config:
+//!
+//! ```toml
+//! [[false-positive]]
+//! fingerprint = "3f2a9c1e8b7d6054"
+//! reason = "the clone is needed to release the lock early"
+//! ```
+//!
+//! or in a triage file of `<fingerprint> [reason]` lines, with `#` comments,
+//! and with `--false-positive`. Marked warnings are taken out of the totals
+//! and gates, counted separately, and can be compiled into a report to file
+//! upstream with clippy.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use serde::Deserialize;
+use crate::types::Warning;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FalsePositive {
+    pub fingerprint: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// A warning marked as a suspected false positive.
+#[derive(Debug, Clone)]
+pub struct FlaggedWarning {
+    pub warning: Warning,
+    pub reason: Option<String>,
+}
+
+/// Fingerprints of suspected false positives, with the reason given.
+#[derive(Debug, Clone, Default)]
+pub struct FalsePositiveSet {
+    reasons: BTreeMap<String, Option<String>>,
+}
+
+impl FalsePositiveSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks `fingerprint`; a later reason replaces an earlier one.
+    pub fn add(&mut self, fingerprint: &str, reason: Option<String>) {
+        let entry = self.reasons.entry(fingerprint.trim().to_lowercase()).or_default();
+        if reason.is_some() {
+            *entry = reason;
+        }
+    }
+
+    pub fn extend(&mut self, entries: &[FalsePositive]) {
+        for entry in entries {
+            self.add(&entry.fingerprint, entry.reason.clone());
+        }
+    }
+
+    /// Adds the entries of a triage file.
+    pub fn load_triage_file(&mut self, path: &Path) -> io::Result<()> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            io::Error::new(e.kind(), format!("Cannot read triage file {}: {}", path.display(), e))
+        })?;
+        self.parse_triage(&contents);
+        Ok(())
+    }
+
+    fn parse_triage(&mut self, contents: &str) {
+        for line in contents.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (fingerprint, reason) = match line.split_once(char::is_whitespace) {
+                Some((fingerprint, reason)) => (fingerprint, Some(reason.trim().to_string())),
+                None => (line, None),
+            };
+            self.add(fingerprint, reason);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.reasons.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reasons.is_empty()
+    }
+
+    /// Splits warnings into unmarked ones and suspected false positives.
+    pub fn partition(&self, warnings: Vec<Warning>) -> (Vec<Warning>, Vec<FlaggedWarning>) {
+        if self.is_empty() {
+            return (warnings, Vec::new());
+        }
+        let mut kept = Vec::with_capacity(warnings.len());
+        let mut flagged = Vec::new();
+        for warning in warnings {
+            let reason = self.reasons
+                .get(&warning.fingerprint())
+                .or_else(|| self.reasons.get(&warning.message_fingerprint()));
+            match reason {
+                Some(reason) => flagged.push(FlaggedWarning { reason: reason.clone(), warning }),
+                None => kept.push(warning),
+            }
+        }
+        (kept, flagged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CategoryType, Priority, Span};
+
+    #[test]
+    fn test_partition_by_fingerprint() {
+        let warning = |line: u32, message: &str| Warning {
+            primary_message: message.to_string(),
+            span: Some(Span::new(line, 5, line, 9)),
+            category: CategoryType::Performance,
+            priority: Priority::High,
+            ..Warning::for_test("clippy::redundant_clone", "src/lib.rs", line)
+        };
+        let flagged_warning = warning(10, "redundant clone");
+        let fingerprint = flagged_warning.fingerprint();
+        assert_eq!(fingerprint.len(), 16);
+        // Moving the warning keeps its fingerprint
+        assert_eq!(warning(42, "redundant clone").fingerprint(), fingerprint);
+
+        let mut set = FalsePositiveSet::new();
+        set.parse_triage(&format!("# triaged upstream\n{} clone releases the lock\n\n", fingerprint.to_uppercase()));
+        let (kept, flagged) = set.partition(vec![flagged_warning, warning(12, "another clone")]);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].reason.as_deref(), Some("clone releases the lock"));
+    }
+}
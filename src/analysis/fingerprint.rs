@@ -0,0 +1,255 @@
+//! Stable warning fingerprints.
+//!
+//! A warning's fingerprint identifies "the same warning" across commits, for
+//! baseline comparisons, trend tracking, and triage. It hashes the lint, the
+//! file path relative to the workspace root, and the flagged code with
+//! whitespace collapsed, so edits elsewhere in the file, reformatting, and
+//! line shifts keep it. Warnings of one lint on identical code in one file
+//! are told apart by their order in the file; the same site reported more
+//! than once, as with `--all-targets`, keeps one fingerprint.
+//!
+//! When the source is not available (no source root, or the file is gone)
+//! the message fingerprint of the lint, path, and message is used instead.
+//!
+//! A renamed file changes the path hashed; comparisons with an earlier run
+//! translate its fingerprints with [`Fingerprinter::renamed_fingerprints`].
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::types::{Warning, fingerprint_hash};
+use super::renames::RenameMap;
+
+/// Lines of a multi-line span included in the fingerprint; enough to pin
+/// down the construct without tying it to the whole body of an item
+const MAX_FINGERPRINT_LINES: usize = 3;
+
+/// Assigns fingerprints, reading each source file once.
+#[derive(Debug, Default)]
+pub struct Fingerprinter {
+    root: Option<PathBuf>,
+    sources: HashMap<String, Option<Vec<String>>>,
+}
+
+impl Fingerprinter {
+    /// Reads sources relative to `root`; without one, every warning gets
+    /// its message fingerprint.
+    pub fn new(root: Option<&Path>) -> Self {
+        Self { root: root.map(Path::to_path_buf), sources: HashMap::new() }
+    }
+
+    /// Sets the fingerprint of every warning without one; warnings loaded
+    /// from an export keep theirs, whatever the source looks like now.
+    pub fn assign(&mut self, warnings: &mut [Warning]) {
+        let fingerprints = self.fingerprints(warnings, |warning| warning.file.as_str());
+        for (warning, fingerprint) in warnings.iter_mut().zip(fingerprints) {
+            if warning.fingerprint.is_empty() {
+                warning.fingerprint = fingerprint;
+            }
+        }
+    }
+
+    /// The fingerprints the warnings in renamed files had under their
+    /// earlier paths, mapped to their current fingerprints.
+    pub fn renamed_fingerprints(&mut self, warnings: &[Warning], renames: &RenameMap) -> HashMap<String, String> {
+        if renames.is_empty() {
+            return HashMap::new();
+        }
+        let renamed: Vec<Warning> = warnings
+            .iter()
+            .filter(|warning| renames.previous_path(&warning.file).is_some())
+            .cloned()
+            .collect();
+        let earlier = self.fingerprints(&renamed, |warning| renames.previous_path(&warning.file).unwrap_or(&warning.file));
+        earlier.into_iter().zip(renamed.iter().map(Warning::fingerprint)).collect()
+    }
+
+    /// The fingerprint of each warning, hashing the path given by `path`.
+    fn fingerprints<'w>(&mut self, warnings: &'w [Warning], path: impl Fn(&'w Warning) -> &'w str) -> Vec<String> {
+        // Ordinals count identical code in file order, whatever the order
+        // warnings were reported in
+        let mut order: Vec<usize> = (0..warnings.len()).collect();
+        order.sort_by_key(|&i| {
+            let (lint, file, line, column) = warnings[i].site_key();
+            (file.to_string(), line, column, lint.to_string())
+        });
+
+        let mut fingerprints = vec![String::new(); warnings.len()];
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let mut previous: Option<usize> = None;
+        for i in order {
+            let warning = &warnings[i];
+            // Repeats of a site are adjacent after sorting
+            if let Some(p) = previous.filter(|&p| warnings[p].site_key() == warning.site_key()) {
+                fingerprints[i] = fingerprints[p].clone();
+                continue;
+            }
+            previous = Some(i);
+            let path = path(warning);
+            fingerprints[i] = match self.flagged_code(warning) {
+                Some(code) => {
+                    let base = fingerprint_hash(&[&warning.lint_code, &path.replace('\\', "/"), &code]);
+                    let ordinal = seen.entry(base.clone()).or_insert(0);
+                    *ordinal += 1;
+                    match *ordinal {
+                        1 => base,
+                        n => fingerprint_hash(&[&base, &n.to_string()]),
+                    }
+                }
+                // The message fingerprint, under `path`
+                None => fingerprint_hash(&[&warning.lint_code, path, &warning.primary_message]),
+            };
+        }
+        fingerprints
+    }
+
+    /// The warning's lines with whitespace collapsed, if the file is
+    /// readable and the span lies within it.
+    fn flagged_code(&mut self, warning: &Warning) -> Option<String> {
+        let root = self.root.as_ref()?;
+        let lines = self
+            .sources
+            .entry(warning.file.clone())
+            .or_insert_with(|| {
+                std::fs::read_to_string(root.join(&warning.file))
+                    .ok()
+                    .map(|source| source.lines().map(str::to_string).collect())
+            })
+            .as_ref()?;
+
+        let (start, end) = warning.span.map_or((warning.line, warning.line), |span| (span.line_start, span.line_end));
+        let start = start as usize;
+        if start == 0 || start > lines.len() {
+            return None;
+        }
+        let end = (end as usize).clamp(start, lines.len()).min(start + MAX_FINGERPRINT_LINES - 1);
+        Some(normalize_code(&lines[start - 1..end]))
+    }
+}
+
+/// Warnings by fingerprint, as stored for comparisons with later runs.
+pub fn fingerprint_counts(warnings: &[Warning]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for warning in warnings {
+        *counts.entry(warning.fingerprint()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Fingerprint counts of an earlier run with the fingerprints of renamed
+/// files replaced by their current ones (see
+/// [`Fingerprinter::renamed_fingerprints`]).
+pub fn follow_renamed(counts: &HashMap<String, usize>, renamed: &HashMap<String, String>) -> HashMap<String, usize> {
+    let mut followed = HashMap::new();
+    for (fingerprint, count) in counts {
+        *followed.entry(renamed.get(fingerprint).unwrap_or(fingerprint).clone()).or_insert(0) += count;
+    }
+    followed
+}
+
+/// Warnings new in and gone from `current`, compared with `previous`, both
+/// as fingerprint counts.
+pub fn fingerprint_churn(previous: &HashMap<String, usize>, current: &HashMap<String, usize>) -> (usize, usize) {
+    let count = |counts: &HashMap<String, usize>, key: &String| counts.get(key).copied().unwrap_or(0);
+    let new = current.iter().map(|(key, &n)| n.saturating_sub(count(previous, key))).sum();
+    let fixed = previous.iter().map(|(key, &n)| n.saturating_sub(count(current, key))).sum();
+    (new, fixed)
+}
+
+/// A run's warnings matched against an earlier run by fingerprint.
+#[derive(Debug, Default)]
+pub struct FingerprintDiff<'a> {
+    /// Warnings the earlier run did not have
+    pub new: Vec<&'a Warning>,
+    /// Fingerprints of the earlier run's warnings that are gone, with counts
+    pub fixed: HashMap<String, usize>,
+}
+
+impl FingerprintDiff<'_> {
+    pub fn fixed_count(&self) -> usize {
+        self.fixed.values().sum()
+    }
+}
+
+/// Matches `current` against the fingerprint counts of an earlier run,
+/// identifying each warning by `key` (normally [`Warning::fingerprint`]).
+pub fn diff_fingerprints<'a>(
+    previous: &HashMap<String, usize>,
+    current: &'a [Warning],
+    key: fn(&Warning) -> String,
+) -> FingerprintDiff<'a> {
+    let mut remaining = previous.clone();
+    let mut new = Vec::new();
+    for warning in current {
+        match remaining.get_mut(&key(warning)) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => new.push(warning),
+        }
+    }
+    remaining.retain(|_, count| *count > 0);
+    FingerprintDiff { new, fixed: remaining }
+}
+
+/// Joins the lines with whitespace runs collapsed to single spaces.
+fn normalize_code(lines: &[String]) -> String {
+    lines
+        .iter()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CategoryType, Priority, Span};
+
+    #[test]
+    fn test_fingerprint_survives_line_shifts() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        let warning = |line| Warning {
+            primary_message: "used `unwrap()` on an `Option` value".to_string(),
+            span: Some(Span::new(line, 13, line, 23)),
+            category: CategoryType::Safety,
+            priority: Priority::Critical,
+            ..Warning::for_test("clippy::unwrap_used", "src/lib.rs", line)
+        };
+
+        std::fs::write(dir.join("src/lib.rs"), "fn f() {\n    let a = x.unwrap();\n    let b = y.unwrap();\n}\n").unwrap();
+        let mut before = vec![warning(3), warning(2)];
+        Fingerprinter::new(Some(dir)).assign(&mut before);
+
+        std::fs::write(
+            dir.join("src/lib.rs"),
+            "// header\n\nfn f() {\n    let a  =  x.unwrap();\n    let b = y.unwrap();\n}\n",
+        )
+        .unwrap();
+        let mut after = vec![warning(4), warning(5)];
+        Fingerprinter::new(Some(dir)).assign(&mut after);
+        // One site reported by two targets
+        let mut repeated = vec![warning(4), warning(5), warning(4)];
+        Fingerprinter::new(Some(dir)).assign(&mut repeated);
+        assert_eq!(repeated[0].fingerprint, repeated[2].fingerprint);
+        assert_eq!(repeated[1].fingerprint, after[1].fingerprint);
+
+        std::fs::rename(dir.join("src/lib.rs"), dir.join("src/moved.rs")).unwrap();
+        let mut moved = vec![warning(4), warning(5)];
+        moved.iter_mut().for_each(|warning| warning.file = "src/moved.rs".to_string());
+        let mut fingerprinter = Fingerprinter::new(Some(dir));
+        fingerprinter.assign(&mut moved);
+        let renames = RenameMap::from_renames([("src/lib.rs".to_string(), "src/moved.rs".to_string())]);
+        let renamed = fingerprinter.renamed_fingerprints(&moved, &renames);
+        assert_ne!(moved[0].fingerprint, after[0].fingerprint);
+        let previous = fingerprint_counts(&before);
+        assert_eq!(fingerprint_churn(&follow_renamed(&previous, &renamed), &fingerprint_counts(&moved)), (0, 0));
+
+        assert_eq!(before[1].fingerprint, after[0].fingerprint);
+        assert_eq!(before[0].fingerprint, after[1].fingerprint);
+        assert_ne!(after[0].fingerprint, after[1].fingerprint);
+
+        let mut unreadable = vec![warning(2)];
+        Fingerprinter::new(None).assign(&mut unreadable);
+        assert_eq!(unreadable[0].fingerprint, unreadable[0].message_fingerprint());
+    }
+}
@@ -0,0 +1,314 @@
+//! Per-function warning counts.
+//!
+//! Clippy's diagnostic spans do not name the enclosing item, so the source
+//! around each warning is re-read: a lightweight scan of each file finds
+//! `fn` bodies (qualified by their `impl` or `trait`) and their line ranges,
+//! and every warning is attributed to the innermost function containing it.
+
+use std::collections::HashMap;
+use std::path::Path;
+use serde::Serialize;
+use crate::types::Warning;
+
+/// A function body and the lines it spans, 1-based and inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemSpan {
+    /// Function name, prefixed with its impl or trait type, e.g. `Parser::parse`
+    pub name: String,
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FunctionOffender {
+    pub file: String,
+    pub function: String,
+    pub warnings: usize,
+    /// Sum of the severity scores of the function's warnings
+    pub severity: usize,
+}
+
+impl FunctionOffender {
+    /// `file::function`, as used in statistics keys.
+    pub fn qualified_name(&self) -> String {
+        format!("{}::{}", self.file, self.function)
+    }
+}
+
+/// Counts warnings per enclosing function, worst first.
+///
+/// Files are read relative to `root`; warnings in files that cannot be read,
+/// or outside any function, are not counted.
+pub fn rank_functions(warnings: &[Warning], root: &Path) -> Vec<FunctionOffender> {
+    let mut items_by_file: HashMap<&str, Vec<ItemSpan>> = HashMap::new();
+    let mut counts: HashMap<(&str, String), (usize, usize)> = HashMap::new();
+
+    for warning in warnings {
+        let items = items_by_file.entry(&warning.file).or_insert_with(|| {
+            std::fs::read_to_string(root.join(&warning.file))
+                .map(|source| function_spans(&source))
+                .unwrap_or_default()
+        });
+        if let Some(item) = innermost_item(items, warning.line) {
+            let entry = counts.entry((&warning.file, item.name.clone())).or_default();
+            entry.0 += 1;
+            entry.1 += warning.priority.severity_score() as usize;
+        }
+    }
+
+    let mut offenders: Vec<FunctionOffender> = counts
+        .into_iter()
+        .map(|((file, function), (warnings, severity))| FunctionOffender {
+            file: file.to_string(),
+            function,
+            warnings,
+            severity,
+        })
+        .collect();
+    offenders.sort_by(|a, b| {
+        b.warnings.cmp(&a.warnings)
+            .then(b.severity.cmp(&a.severity))
+            .then_with(|| a.qualified_name().cmp(&b.qualified_name()))
+    });
+    offenders
+}
+
+/// The smallest function span containing `line`.
+pub fn innermost_item(items: &[ItemSpan], line: u32) -> Option<&ItemSpan> {
+    items
+        .iter()
+        .filter(|item| item.start <= line && line <= item.end)
+        .min_by_key(|item| item.end - item.start)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    Ident(String),
+    Punct(char),
+}
+
+/// What a `{` opens, while scanning.
+enum Block {
+    Function(String, u32),
+    Container(String),
+    Other,
+}
+
+enum Pending {
+    None,
+    Function(String, u32),
+    Impl { names: Vec<String>, angle_depth: usize, in_where: bool },
+    Trait(Option<String>),
+}
+
+/// Finds the function bodies in Rust source.
+pub fn function_spans(source: &str) -> Vec<ItemSpan> {
+    let mut spans = Vec::new();
+    let mut stack: Vec<Block> = Vec::new();
+    let mut pending = Pending::None;
+    let mut previous: Option<Token> = None;
+
+    for (token, line) in tokenize(source) {
+        match (&mut pending, &token) {
+            (_, Token::Punct('{')) => {
+                let block = match std::mem::replace(&mut pending, Pending::None) {
+                    Pending::Function(name, start) => {
+                        let container = stack.iter().rev().find_map(|block| match block {
+                            Block::Container(name) => Some(name.as_str()),
+                            _ => None,
+                        });
+                        let name = match container {
+                            Some(container) => format!("{}::{}", container, name),
+                            None => name,
+                        };
+                        Block::Function(name, start)
+                    }
+                    Pending::Impl { names, .. } => Block::Container(names.last().cloned().unwrap_or_default()),
+                    Pending::Trait(name) => Block::Container(name.unwrap_or_default()),
+                    Pending::None => Block::Other,
+                };
+                stack.push(block);
+            }
+            (_, Token::Punct('}')) => {
+                pending = Pending::None;
+                if let Some(Block::Function(name, start)) = stack.pop() {
+                    spans.push(ItemSpan { name, start, end: line });
+                }
+            }
+            (_, Token::Punct(';')) => pending = Pending::None,
+            (Pending::Impl { in_where: true, .. }, _) => {}
+            (Pending::Impl { names, angle_depth, in_where }, token) => match token {
+                Token::Punct('<') => *angle_depth += 1,
+                Token::Punct('>') => *angle_depth = angle_depth.saturating_sub(1),
+                Token::Ident(ident) if ident == "where" => *in_where = true,
+                Token::Ident(ident) if ident == "for" => names.clear(),
+                Token::Ident(ident) if *angle_depth == 0 => names.push(ident.clone()),
+                _ => {}
+            },
+            (Pending::Trait(name @ None), Token::Ident(ident)) => *name = Some(ident.clone()),
+            (Pending::Function(..) | Pending::Trait(_), _) => {}
+            (Pending::None, Token::Ident(ident)) => match (&previous, ident.as_str()) {
+                (Some(Token::Ident(keyword)), name) if keyword == "fn" => {
+                    pending = Pending::Function(name.to_string(), line);
+                }
+                (_, "impl") => {
+                    pending = Pending::Impl { names: Vec::new(), angle_depth: 0, in_where: false };
+                }
+                (_, "trait") => pending = Pending::Trait(None),
+                _ => {}
+            },
+            (Pending::None, _) => {}
+        }
+        previous = Some(token);
+    }
+    spans
+}
+
+/// Splits source into identifiers and the punctuation the scans need, with
+/// their line numbers, skipping comments, strings, character literals, and
+/// lifetimes.
+pub(crate) fn tokenize(source: &str) -> Vec<(Token, u32)> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+            }
+            '/' if next == Some('/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if next == Some('*') => {
+                let mut depth = 0;
+                while i < chars.len() {
+                    match (chars[i], chars.get(i + 1)) {
+                        ('/', Some('*')) => { depth += 1; i += 2; }
+                        ('*', Some('/')) => {
+                            depth -= 1;
+                            i += 2;
+                            if depth == 0 { break; }
+                        }
+                        ('\n', _) => { line += 1; i += 1; }
+                        _ => i += 1,
+                    }
+                }
+            }
+            '"' => i = skip_string(&chars, i + 1, None, &mut line),
+            '\'' => {
+                // A character literal, unless it is a lifetime like `'a`
+                if next == Some('\\') {
+                    i += 2;
+                    while i < chars.len() && chars[i] != '\'' {
+                        i += 1;
+                    }
+                    i += 1;
+                } else if chars.get(i + 2) == Some(&'\'') {
+                    i += 3;
+                } else {
+                    i += 1;
+                    while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                        i += 1;
+                    }
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let ident: String = chars[start..i].iter().collect();
+                let mut hashes = 0;
+                while matches!(ident.as_str(), "r" | "br") && chars.get(i + hashes) == Some(&'#') {
+                    hashes += 1;
+                }
+                if matches!(ident.as_str(), "r" | "br" | "b") && chars.get(i + hashes) == Some(&'"') {
+                    let raw = (ident != "b").then_some(hashes);
+                    i = skip_string(&chars, i + hashes + 1, raw, &mut line);
+                } else if ident == "b" && chars.get(i) == Some(&'\'') {
+                    continue;
+                } else {
+                    tokens.push((Token::Ident(ident), line));
+                }
+            }
+            '{' | '}' | ';' | '<' | '>' | '(' | '*' => {
+                tokens.push((Token::Punct(c), line));
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    tokens
+}
+
+/// Skips a string body starting at `i`, returning the index after its close.
+/// `raw` holds the number of `#`s of a raw string, which has no escapes.
+fn skip_string(chars: &[char], mut i: usize, raw: Option<usize>, line: &mut u32) -> usize {
+    let hashes = raw.unwrap_or(0);
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if raw.is_none() => i += 2,
+            '"' if chars[i + 1..].iter().take(hashes).filter(|&&c| c == '#').count() == hashes => {
+                return i + 1 + hashes;
+            }
+            '\n' => {
+                *line += 1;
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function_spans() {
+        let source = r#####"
+/// Docs with a { brace
+pub fn free() {
+    let s = "}";
+    let c = '{';
+}
+
+impl<'a, T: Clone> Parser<'a, T> {
+    fn parse(&self) -> Vec<T> {
+        let raw = r#"{"#;
+        fn helper() {}
+        Vec::new()
+    }
+}
+
+impl<T> std::fmt::Display for Warning<T> where T: Clone {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { Ok(()) }
+}
+
+trait Check {
+    fn required(&self);
+    fn provided(&self) {}
+}
+"#####;
+        let spans = function_spans(source);
+        let names: Vec<(&str, u32, u32)> = spans.iter().map(|s| (s.name.as_str(), s.start, s.end)).collect();
+        assert_eq!(names, vec![
+            ("free", 3, 6),
+            ("Parser::helper", 11, 11),
+            ("Parser::parse", 9, 13),
+            ("Warning::fmt", 17, 17),
+            ("Check::provided", 22, 22),
+        ]);
+        assert_eq!(innermost_item(&spans, 11).map(|s| s.name.as_str()), Some("Parser::helper"));
+        assert_eq!(innermost_item(&spans, 12).map(|s| s.name.as_str()), Some("Parser::parse"));
+        assert_eq!(innermost_item(&spans, 7), None);
+    }
+}
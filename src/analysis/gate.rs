@@ -0,0 +1,164 @@
+//! Quality gates.
+//!
+//! A gate is a set of thresholds from the `[gate]` config table. Evaluating it
+//! against the statistics of a run yields a pass/fail verdict plus the list of
+//! rules that were exceeded.
+
+use std::fmt;
+use serde::{Serialize, Deserialize};
+use crate::analysis::statistics::warning::WarningStatistics;
+use crate::types::{Warning, Priority};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct GateConfig {
+    /// Maximum number of Critical priority warnings
+    pub max_critical: Option<usize>,
+    /// Maximum number of High priority warnings
+    pub max_high: Option<usize>,
+    /// Maximum number of warnings overall
+    pub max_total: Option<usize>,
+}
+
+impl GateConfig {
+    pub fn is_empty(&self) -> bool {
+        self.max_critical.is_none() && self.max_high.is_none() && self.max_total.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GateRule {
+    MaxCritical,
+    MaxHigh,
+    MaxTotal,
+}
+
+impl GateRule {
+    /// Whether the warning counts towards this rule.
+    pub fn matches(&self, warning: &Warning) -> bool {
+        match self {
+            GateRule::MaxCritical => warning.priority == Priority::Critical,
+            GateRule::MaxHigh => warning.priority == Priority::High,
+            GateRule::MaxTotal => true,
+        }
+    }
+}
+
+impl fmt::Display for GateRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GateRule::MaxCritical => write!(f, "max-critical"),
+            GateRule::MaxHigh => write!(f, "max-high"),
+            GateRule::MaxTotal => write!(f, "max-total"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GateViolation {
+    pub rule: GateRule,
+    pub limit: usize,
+    pub actual: usize,
+}
+
+impl fmt::Display for GateViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} warnings (limit {})", self.rule, self.actual, self.limit)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GateVerdict {
+    pub violations: Vec<GateViolation>,
+}
+
+impl GateVerdict {
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Warnings that contributed to a violated rule, most severe first.
+    pub fn blocking_warnings<'a>(&self, warnings: &'a [Warning], limit: usize) -> Vec<&'a Warning> {
+        let mut blocking: Vec<&Warning> = warnings.iter()
+            .filter(|w| self.violations.iter().any(|v| v.rule.matches(w)))
+            .collect();
+        blocking.sort_by_key(|w| std::cmp::Reverse(w.priority.severity_score()));
+        blocking.truncate(limit);
+        blocking
+    }
+}
+
+pub fn evaluate_gate(config: &GateConfig, stats: &WarningStatistics) -> GateVerdict {
+    let count = |priority: Priority| *stats.by_priority.get(&priority).unwrap_or(&0);
+    let checks = [
+        (GateRule::MaxCritical, config.max_critical, count(Priority::Critical)),
+        (GateRule::MaxHigh, config.max_high, count(Priority::High)),
+        (GateRule::MaxTotal, config.max_total, stats.total_warnings),
+    ];
+
+    let violations = checks.into_iter()
+        .filter_map(|(rule, limit, actual)| {
+            let limit = limit?;
+            (actual > limit).then_some(GateViolation { rule, limit, actual })
+        })
+        .collect();
+
+    GateVerdict { violations }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(critical: usize, high: usize, total: usize) -> WarningStatistics {
+        let mut stats = WarningStatistics { total_warnings: total, ..Default::default() };
+        stats.by_priority.insert(Priority::Critical, critical);
+        stats.by_priority.insert(Priority::High, high);
+        stats
+    }
+
+    #[test]
+    fn test_gate_config_from_toml() {
+        let config: GateConfig = toml::from_str("max-critical = 0\nmax-total = 50").unwrap();
+        assert_eq!(config.max_critical, Some(0));
+        assert_eq!(config.max_high, None);
+        assert_eq!(config.max_total, Some(50));
+        assert!(!config.is_empty());
+        assert!(GateConfig::default().is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_gate() {
+        let config = GateConfig { max_critical: Some(0), max_high: Some(2), max_total: Some(10) };
+
+        assert!(evaluate_gate(&config, &stats(0, 2, 10)).passed());
+        assert!(evaluate_gate(&GateConfig::default(), &stats(5, 5, 100)).passed());
+
+        let verdict = evaluate_gate(&config, &stats(1, 3, 11));
+        assert!(!verdict.passed());
+        let rules: Vec<GateRule> = verdict.violations.iter().map(|v| v.rule).collect();
+        assert_eq!(rules, [GateRule::MaxCritical, GateRule::MaxHigh, GateRule::MaxTotal]);
+        assert_eq!(verdict.violations[1].to_string(), "max-high: 3 warnings (limit 2)");
+    }
+
+    #[test]
+    fn test_blocking_warnings() {
+        let warnings = [
+            Warning { priority: Priority::Low, ..Warning::for_test("clippy::needless_return", "src/a.rs", 1) },
+            Warning { priority: Priority::High, ..Warning::for_test("clippy::unwrap_used", "src/a.rs", 2) },
+            Warning { priority: Priority::Critical, ..Warning::for_test("clippy::mem_forget", "src/b.rs", 3) },
+        ];
+        let verdict = GateVerdict {
+            violations: vec![
+                GateViolation { rule: GateRule::MaxHigh, limit: 0, actual: 1 },
+                GateViolation { rule: GateRule::MaxCritical, limit: 0, actual: 1 },
+            ],
+        };
+
+        let blocking = verdict.blocking_warnings(&warnings, 10);
+        let lines: Vec<u32> = blocking.iter().map(|w| w.line).collect();
+        assert_eq!(lines, [3, 2]);
+        assert_eq!(verdict.blocking_warnings(&warnings, 1).len(), 1);
+    }
+}
@@ -0,0 +1,184 @@
+//! Gate tuning by replaying history.
+//!
+//! Evaluates a proposed `[gate]` table and `alert-if` rules against each
+//! recorded run as if they had been enforced at the time, counting how often
+//! each rule would have failed. Alert rules see only the runs recorded before
+//! the one they are evaluated for.
+
+use std::fmt;
+use chrono::NaiveDate;
+use crate::analysis::{
+    alerts::{evaluate_alerts, AlertRule},
+    gate::{evaluate_gate, GateConfig, GateRule},
+    statistics::warning::WarningStatistics,
+    trends::TrendAnalysis,
+};
+
+/// How one rule fared over the replayed runs.
+#[derive(Debug, Clone)]
+pub struct RuleSimulation {
+    /// The rule as configured, e.g. `max-critical = 0`
+    pub rule: String,
+    /// Runs the rule would have failed
+    pub failures: usize,
+    /// Worst value among the failures: the count for gate rules, the
+    /// increase per period for alerts
+    pub worst: Option<f64>,
+    /// Date of the latest failing run
+    pub last_failure: Option<String>,
+}
+
+impl RuleSimulation {
+    fn new(rule: String) -> Self {
+        Self { rule, failures: 0, worst: None, last_failure: None }
+    }
+
+    fn fail(&mut self, value: f64, date: Option<&str>) {
+        self.failures += 1;
+        self.worst = Some(self.worst.map_or(value, |worst| worst.max(value)));
+        if let Some(date) = date {
+            self.last_failure = Some(date.to_string());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GateSimulation {
+    pub runs: usize,
+    /// Runs at least one rule would have failed
+    pub failed_runs: usize,
+    pub first_date: Option<String>,
+    pub last_date: Option<String>,
+    /// Gate rules in `[gate]` order, then alert rules
+    pub rules: Vec<RuleSimulation>,
+}
+
+/// Replays `history` (oldest first) against the proposed gate and alerts.
+pub fn simulate_gates(gate: &GateConfig, alerts: &[AlertRule], history: &[TrendAnalysis]) -> GateSimulation {
+    let gate_rules: Vec<(GateRule, usize)> = [
+        (GateRule::MaxCritical, gate.max_critical),
+        (GateRule::MaxHigh, gate.max_high),
+        (GateRule::MaxTotal, gate.max_total),
+    ]
+    .into_iter()
+    .filter_map(|(rule, limit)| Some((rule, limit?)))
+    .collect();
+
+    let mut rules: Vec<RuleSimulation> = gate_rules
+        .iter()
+        .map(|(rule, limit)| RuleSimulation::new(format!("{} = {}", rule, limit)))
+        .chain(alerts.iter().map(|rule| RuleSimulation::new(rule.to_string())))
+        .collect();
+
+    let date = |trend: &TrendAnalysis| trend.dates.first().cloned();
+    let mut simulation = GateSimulation {
+        runs: history.len(),
+        first_date: history.first().and_then(date),
+        last_date: history.last().and_then(date),
+        ..Default::default()
+    };
+
+    for (i, trend) in history.iter().enumerate() {
+        let stats = WarningStatistics::from(trend);
+        let date = trend.dates.first().map(String::as_str);
+        let mut failed = false;
+
+        let verdict = evaluate_gate(gate, &stats);
+        for (sim, (rule, _)) in rules.iter_mut().zip(&gate_rules) {
+            if let Some(violation) = verdict.violations.iter().find(|v| v.rule == *rule) {
+                sim.fail(violation.actual as f64, date);
+                failed = true;
+            }
+        }
+
+        // Alerts compare against earlier runs, so they need the run's date
+        let today = date.and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+        if let Some(today) = today {
+            for alert in evaluate_alerts(alerts, &stats, &history[..i], today) {
+                let index = alerts.iter().position(|rule| *rule == alert.rule).unwrap_or_default();
+                rules[gate_rules.len() + index].fail(alert.rate, date);
+                failed = true;
+            }
+        }
+
+        if failed {
+            simulation.failed_runs += 1;
+        }
+    }
+
+    simulation.rules = rules;
+    simulation
+}
+
+impl fmt::Display for GateSimulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Replayed {} runs from {} to {}; {} would have failed\n",
+            self.runs,
+            self.first_date.as_deref().unwrap_or("-"),
+            self.last_date.as_deref().unwrap_or("-"),
+            self.failed_runs
+        )?;
+
+        let width = self.rules.iter().map(|r| r.rule.len()).max().unwrap_or(0).max("rule".len());
+        writeln!(f, "{:<width$}  {:>8}  {:>6}  {:>8}  last failure", "rule", "failures", "rate", "worst")?;
+        for rule in &self.rules {
+            let rate = if self.runs == 0 { 0.0 } else { rule.failures as f64 / self.runs as f64 * 100.0 };
+            let worst = rule.worst.map_or_else(|| "-".to_string(), |worst| format!("{}", (worst * 10.0).round() / 10.0));
+            writeln!(
+                f,
+                "{:<width$}  {:>8}  {:>5.0}%  {:>8}  {}",
+                rule.rule,
+                rule.failures,
+                rate,
+                worst,
+                rule.last_failure.as_deref().unwrap_or("-")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::types::Priority;
+
+    fn run(date: &str, critical: usize, unwraps: usize) -> TrendAnalysis {
+        TrendAnalysis {
+            dates: vec![date.to_string()],
+            total_warnings: critical + unwraps,
+            by_priority: HashMap::from([(Priority::Critical, critical)]),
+            recurring_issues: HashMap::from([("clippy::unwrap_used: used `unwrap()`".to_string(), unwraps)]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_simulate_gates() {
+        let gate = GateConfig { max_critical: Some(1), max_total: Some(100), ..Default::default() };
+        let alerts: Vec<AlertRule> = vec!["clippy::unwrap_used increases by > 3 per run".parse().unwrap()];
+        let history = [
+            run("2026-10-01", 0, 2),
+            run("2026-10-02", 3, 8),
+            run("2026-10-03", 2, 9),
+            run("2026-10-04", 0, 9),
+        ];
+
+        let simulation = simulate_gates(&gate, &alerts, &history);
+        assert_eq!((simulation.runs, simulation.failed_runs), (4, 2));
+        let summary: Vec<(&str, usize, Option<f64>)> = simulation.rules
+            .iter()
+            .map(|rule| (rule.rule.as_str(), rule.failures, rule.worst))
+            .collect();
+        assert_eq!(summary, [
+            ("max-critical = 1", 2, Some(3.0)),
+            ("max-total = 100", 0, None),
+            ("\"clippy::unwrap_used\" increases by > 3 per run", 1, Some(6.0)),
+        ]);
+        assert_eq!(simulation.rules[0].last_failure.as_deref(), Some("2026-10-03"));
+        assert!(simulation.to_string().contains("Replayed 4 runs from 2026-10-01 to 2026-10-04; 2 would have failed"));
+    }
+}
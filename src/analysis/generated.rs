@@ -0,0 +1,186 @@
+//! Exclusion of generated and vendored code.
+//!
+//! Warnings in code the workspace does not write by hand are left out of the
+//! analysis, so that a large generated file does not dominate the statistics.
+//! Built-in detection covers:
+//!
+//! - build script output, `target/<profile>/build/<package>-<hash>/out/`,
+//!   wherever the target directory is
+//! - anything else in the target directory
+//! - protobuf bindings, `*.pb.rs`
+//! - vendored crates, the `vendor/` directory of the workspace root
+//!
+//! Warning paths are relative to the workspace root, so a `target` or
+//! `vendor` directory deeper in the workspace is first-party code. Absolute
+//! paths count as build output only under the target directory set with
+//! [`GeneratedCodeFilter::set_target_dir`].
+//!
+//! The `generated` config key and `--generated` flags add globs, and
+//! `--include-generated` turns the built-in detection off. Excluded warnings
+//! are counted and listed in an appendix of the detailed report.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use glob::{MatchOptions, Pattern};
+use crate::types::Warning;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GeneratedReason {
+    BuildOutput,
+    TargetDir,
+    Protobuf,
+    Vendored,
+    /// Matched by a configured glob
+    Configured,
+}
+
+impl fmt::Display for GeneratedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneratedReason::BuildOutput => write!(f, "build script output (OUT_DIR)"),
+            GeneratedReason::TargetDir => write!(f, "target directory"),
+            GeneratedReason::Protobuf => write!(f, "protobuf bindings"),
+            GeneratedReason::Vendored => write!(f, "vendored code"),
+            GeneratedReason::Configured => write!(f, "configured exclude"),
+        }
+    }
+}
+
+/// The built-in reason `file` is generated or vendored, if it is.
+/// `target_dir` is the absolute target directory of the workspace.
+pub fn builtin_reason(file: &str, target_dir: Option<&Path>) -> Option<GeneratedReason> {
+    let path = file.replace('\\', "/");
+    let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty() && *c != ".").collect();
+    let in_target: Option<Vec<&str>> = if Path::new(&path).is_absolute() {
+        let relative = target_dir.and_then(|dir| Path::new(&path).strip_prefix(dir).ok());
+        relative.map(|relative| relative.iter().filter_map(|c| c.to_str()).collect())
+    } else {
+        components.split_first().filter(|(first, _)| **first == "target").map(|(_, rest)| rest.to_vec())
+    };
+    // `<profile>/build/<package>-<hash>/out`, with `<triple>/` first when
+    // cross-compiling
+    let build_output = in_target.as_ref().is_some_and(|rest| {
+        [1, 2].iter().any(|&start| rest.get(start..start + 3).is_some_and(|w| w[0] == "build" && w[2] == "out"))
+    });
+
+    if build_output {
+        Some(GeneratedReason::BuildOutput)
+    } else if path.ends_with(".pb.rs") {
+        Some(GeneratedReason::Protobuf)
+    } else if components.first() == Some(&"vendor") && !Path::new(&path).is_absolute() {
+        Some(GeneratedReason::Vendored)
+    } else if in_target.is_some() {
+        Some(GeneratedReason::TargetDir)
+    } else {
+        None
+    }
+}
+
+/// Detects warnings in generated and vendored files.
+#[derive(Debug, Clone)]
+pub struct GeneratedCodeFilter {
+    builtin: bool,
+    excludes: Vec<Pattern>,
+    target_dir: Option<PathBuf>,
+}
+
+impl Default for GeneratedCodeFilter {
+    fn default() -> Self {
+        Self { builtin: true, excludes: Vec::new(), target_dir: None }
+    }
+}
+
+impl GeneratedCodeFilter {
+    /// A filter with the built-in detection, unless `include_generated`,
+    /// and the given exclude globs.
+    pub fn new<S: AsRef<str>>(globs: &[S], include_generated: bool) -> io::Result<Self> {
+        let excludes = globs
+            .iter()
+            .map(|glob| {
+                Pattern::new(glob.as_ref()).map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("Invalid generated code glob '{}': {}", glob.as_ref(), e),
+                    )
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(Self { builtin: !include_generated, excludes, target_dir: None })
+    }
+
+    /// Recognizes build output by absolute path under `dir`, the
+    /// workspace's target directory.
+    pub fn set_target_dir(&mut self, dir: PathBuf) {
+        self.target_dir = Some(dir);
+    }
+
+    /// Why `file` is excluded, if it is.
+    pub fn reason(&self, file: &str) -> Option<GeneratedReason> {
+        let options = MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        if self.excludes.iter().any(|pattern| pattern.matches_with(file, options)) {
+            return Some(GeneratedReason::Configured);
+        }
+        self.builtin.then(|| builtin_reason(file, self.target_dir.as_deref())).flatten()
+    }
+
+    pub fn is_generated(&self, file: &str) -> bool {
+        self.reason(file).is_some()
+    }
+
+    /// Splits warnings into `(kept, excluded)`, preserving input order.
+    pub fn partition(&self, warnings: Vec<Warning>) -> (Vec<Warning>, Vec<Warning>) {
+        warnings.into_iter().partition(|w| !self.is_generated(&w.file))
+    }
+
+    /// Excluded warnings counted by reason, then by file.
+    pub fn breakdown<'a>(&self, excluded: &'a [Warning]) -> BTreeMap<GeneratedReason, BTreeMap<&'a str, usize>> {
+        let mut breakdown: BTreeMap<GeneratedReason, BTreeMap<&str, usize>> = BTreeMap::new();
+        for warning in excluded {
+            if let Some(reason) = self.reason(&warning.file) {
+                *breakdown.entry(reason).or_default().entry(warning.file.as_str()).or_default() += 1;
+            }
+        }
+        breakdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_reasons() {
+        let mut filter = GeneratedCodeFilter::new(&["src/bindings/**"], false).unwrap();
+        filter.set_target_dir(PathBuf::from("/work/demo/target"));
+        let reason = |file: &str| filter.reason(file);
+        assert_eq!(
+            reason("/work/demo/target/debug/build/demo-3f2a9c1e/out/schema.rs"),
+            Some(GeneratedReason::BuildOutput)
+        );
+        assert_eq!(
+            reason("target/x86_64-unknown-linux-gnu/release/build/demo-3f2a9c1e/out/schema.rs"),
+            Some(GeneratedReason::BuildOutput)
+        );
+        assert_eq!(reason("/ci/cache/debug/build/demo-3f2a9c1e/out/schema.rs"), None);
+        assert_eq!(reason("target/doc/demo.rs"), Some(GeneratedReason::TargetDir));
+        assert_eq!(reason("src/proto/events.pb.rs"), Some(GeneratedReason::Protobuf));
+        assert_eq!(reason("vendor/left-pad/src/lib.rs"), Some(GeneratedReason::Vendored));
+        assert_eq!(reason("src/bindings/ffi.rs"), Some(GeneratedReason::Configured));
+        assert_eq!(reason("src/build/output.rs"), None);
+        assert_eq!(reason("src/vendor/mod.rs"), None);
+        assert_eq!(reason("crates/target/src/lib.rs"), None);
+        assert_eq!(reason("crates/demo/build/gen/out/lib.rs"), None);
+        assert_eq!(reason("src/lib.rs"), None);
+
+        let configured_only = GeneratedCodeFilter::new(&["src/bindings/**"], true).unwrap();
+        assert!(!configured_only.is_generated("vendor/left-pad/src/lib.rs"));
+        assert!(configured_only.is_generated("src/bindings/ffi.rs"));
+        assert!(GeneratedCodeFilter::new(&["src/[bad"], false).is_err());
+    }
+}
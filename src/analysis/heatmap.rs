@@ -0,0 +1,105 @@
+//! Crate × category severity heat table.
+//!
+//! Each cell holds the warning count for one crate and category together with
+//! a heat level derived from the count weighted by the category's priority.
+
+use crate::analysis::statistics::warning::WarningStatistics;
+use crate::types::CategoryType;
+
+/// Number of distinct heat levels (0 = no warnings, `HEAT_LEVELS - 1` = hottest)
+pub const HEAT_LEVELS: u8 = 5;
+
+/// Categories in column order, most severe first
+pub const HEAT_COLUMNS: [CategoryType; 5] = [
+    CategoryType::Safety,
+    CategoryType::Performance,
+    CategoryType::Documentation,
+    CategoryType::Style,
+    CategoryType::Uncategorized,
+];
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeatCell {
+    pub count: usize,
+    pub score: usize,
+    pub level: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct HeatRow {
+    pub crate_name: String,
+    pub cells: [HeatCell; HEAT_COLUMNS.len()],
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HeatTable {
+    pub rows: Vec<HeatRow>,
+}
+
+impl HeatTable {
+    /// Builds the table from per-crate statistics, hottest crates first.
+    pub fn from_stats(stats: &WarningStatistics) -> Self {
+        let mut rows: Vec<HeatRow> = stats.by_crate_category
+            .iter()
+            .map(|(crate_name, by_category)| {
+                let mut cells = [HeatCell::default(); HEAT_COLUMNS.len()];
+                for (cell, category) in cells.iter_mut().zip(HEAT_COLUMNS) {
+                    cell.count = *by_category.get(&category).unwrap_or(&0);
+                    // Uncategorized warnings weigh as little as documentation ones, but still show heat
+                    cell.score = cell.count * category.priority_level().max(1) as usize;
+                }
+                HeatRow {
+                    crate_name: crate_name.clone(),
+                    total: cells.iter().map(|c| c.count).sum(),
+                    cells,
+                }
+            })
+            .collect();
+
+        let max_score = rows.iter()
+            .flat_map(|row| row.cells.iter().map(|c| c.score))
+            .max()
+            .unwrap_or(0);
+
+        for row in &mut rows {
+            for cell in &mut row.cells {
+                cell.level = heat_level(cell.score, max_score);
+            }
+        }
+
+        rows.sort_by(|a, b| {
+            let score = |row: &HeatRow| row.cells.iter().map(|c| c.score).sum::<usize>();
+            score(b).cmp(&score(a)).then_with(|| a.crate_name.cmp(&b.crate_name))
+        });
+
+        Self { rows }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+pub(crate) fn heat_level(score: usize, max_score: usize) -> u8 {
+    if score == 0 || max_score == 0 {
+        return 0;
+    }
+    let steps = (HEAT_LEVELS - 1) as usize;
+    // Any non-zero cell is at least level 1
+    (1 + (score * (steps - 1)) / max_score) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heat_level() {
+        assert_eq!(heat_level(0, 10), 0);
+        assert_eq!(heat_level(5, 0), 0);
+        assert_eq!(heat_level(1, 100), 1);
+        assert_eq!(heat_level(50, 100), 2);
+        assert_eq!(heat_level(100, 100), HEAT_LEVELS - 1);
+    }
+}
@@ -0,0 +1,375 @@
+//! Code clippy cannot see.
+//!
+//! Clippy only reports on what it compiles and rustc on what runs:
+//! `#[ignore]`d tests compile but never run, and items behind a
+//! `#[cfg(feature = ...)]` the analyzed feature set leaves off are not
+//! compiled at all, so both can rot unnoticed. Sources are scanned line by
+//! line for them. Only `feature` predicates are decided; `test`, `unix`,
+//! and the rest are unknown, and an item is reported only when its cfg is
+//! false whatever they evaluate to.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Features selected on the cargo command line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeatureSelection {
+    pub all_features: bool,
+    pub no_default_features: bool,
+    /// `--features` values, with any `package/` prefix removed
+    pub features: Vec<String>,
+}
+
+impl FeatureSelection {
+    /// The selection made by `--all-features`, `--no-default-features`, and
+    /// `--features` (`-F`) in `cargo_args`.
+    pub fn from_cargo_args(cargo_args: &[String]) -> Self {
+        let mut selection = Self::default();
+        let mut args = cargo_args.iter();
+        while let Some(arg) = args.next() {
+            let list = match arg.as_str() {
+                "--all-features" => {
+                    selection.all_features = true;
+                    continue;
+                }
+                "--no-default-features" => {
+                    selection.no_default_features = true;
+                    continue;
+                }
+                "--features" | "-F" => args.next().map(String::as_str),
+                _ => arg.strip_prefix("--features="),
+            };
+            let features = list
+                .into_iter()
+                .flat_map(|list| list.split([',', ' ']))
+                .filter(|feature| !feature.is_empty())
+                .map(|feature| feature.rsplit('/').next().unwrap_or(feature).to_string());
+            selection.features.extend(features);
+        }
+        selection
+    }
+}
+
+/// The features one package declares and those a selection enables.
+#[derive(Debug, Clone, Default)]
+pub struct FeatureSet {
+    declared: BTreeSet<String>,
+    enabled: BTreeSet<String>,
+}
+
+impl FeatureSet {
+    /// Resolves `selection` against a manifest's `[features]` table,
+    /// following the features each enabled feature turns on.
+    pub fn resolve(features: &BTreeMap<String, Vec<String>>, selection: &FeatureSelection) -> Self {
+        let declared: BTreeSet<String> = features.keys().cloned().collect();
+        let mut pending: Vec<String> = if selection.all_features {
+            declared.iter().cloned().collect()
+        } else {
+            let default = (!selection.no_default_features).then(|| "default".to_string());
+            default.into_iter().chain(selection.features.iter().cloned()).collect()
+        };
+        let mut enabled = BTreeSet::new();
+        while let Some(feature) = pending.pop() {
+            if !declared.contains(&feature) || !enabled.insert(feature.clone()) {
+                continue;
+            }
+            // `dep:name` and `name/feature` entries concern dependencies
+            let implied = features[&feature].iter().filter(|entry| !entry.contains(':') && !entry.contains('/'));
+            pending.extend(implied.cloned());
+        }
+        Self { declared, enabled }
+    }
+
+    pub fn is_declared(&self, feature: &str) -> bool {
+        self.declared.contains(feature)
+    }
+
+    pub fn is_enabled(&self, feature: &str) -> bool {
+        self.enabled.contains(feature)
+    }
+}
+
+/// Why an item goes unseen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HiddenReason {
+    /// An `#[ignore]`d test, with the reason given in the attribute
+    IgnoredTest(Option<String>),
+    /// A cfg that is false under the analyzed features, and the features
+    /// it names that the package does not declare
+    InactiveCfg { cfg: String, undeclared: Vec<String> },
+}
+
+impl fmt::Display for HiddenReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HiddenReason::IgnoredTest(None) => write!(f, "ignored test"),
+            HiddenReason::IgnoredTest(Some(reason)) => write!(f, "ignored test ({})", reason),
+            HiddenReason::InactiveCfg { cfg, undeclared } if undeclared.is_empty() => {
+                write!(f, "`cfg({})` is off under the analyzed features", cfg)
+            }
+            HiddenReason::InactiveCfg { cfg, undeclared } => write!(
+                f,
+                "`cfg({})` can never be on: {} not declared",
+                cfg,
+                undeclared.iter().map(|feature| format!("`{}`", feature)).collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+}
+
+/// An item clippy cannot see.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HiddenCode {
+    pub file: String,
+    /// Line of the attribute hiding the item
+    pub line: usize,
+    /// The item's first line, e.g. `fn slow_roundtrip()`, or `(whole file)`
+    /// for an inner `#![cfg]`
+    pub item: String,
+    pub reason: HiddenReason,
+}
+
+/// The unseen code of a workspace.
+#[derive(Debug, Clone, Default)]
+pub struct HiddenCodeReport {
+    pub items: Vec<HiddenCode>,
+    pub files_scanned: usize,
+}
+
+impl HiddenCodeReport {
+    pub fn ignored_tests(&self) -> usize {
+        self.items.iter().filter(|item| matches!(item.reason, HiddenReason::IgnoredTest(_))).count()
+    }
+
+    pub fn inactive_items(&self) -> usize {
+        self.items.len() - self.ignored_tests()
+    }
+}
+
+/// Finds the ignored tests and inactive cfg items of one source file.
+pub fn scan_hidden_code(source: &str, file: &str, features: &FeatureSet) -> Vec<HiddenCode> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut hidden = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        let (inner, attribute) = if let Some(rest) = line.strip_prefix("#![") {
+            (true, rest)
+        } else if let Some(rest) = line.strip_prefix("#[") {
+            (false, rest)
+        } else {
+            continue;
+        };
+
+        let reason = if let Some(rest) = attribute.strip_prefix("ignore") {
+            let rest = rest.trim_start();
+            if rest.starts_with(']') {
+                HiddenReason::IgnoredTest(None)
+            } else if let Some(reason) = rest.strip_prefix('=') {
+                let reason = reason.trim().trim_end_matches(']').trim().trim_matches('"');
+                HiddenReason::IgnoredTest(Some(reason.to_string()))
+            } else {
+                continue;
+            }
+        } else if let Some(rest) = attribute.strip_prefix("cfg(") {
+            // Multi-line cfgs are skipped
+            let Some(end) = rest.rfind(")]") else { continue };
+            let cfg = rest[..end].trim();
+            let Some(predicate) = Cfg::parse(cfg) else { continue };
+            if predicate.eval(features) != Some(false) {
+                continue;
+            }
+            let mut undeclared = Vec::new();
+            predicate.collect_features(&mut |feature| {
+                if !features.is_declared(feature) && !undeclared.iter().any(|f| f == feature) {
+                    undeclared.push(feature.to_string());
+                }
+            });
+            HiddenReason::InactiveCfg { cfg: cfg.to_string(), undeclared }
+        } else {
+            continue;
+        };
+
+        let item = if inner {
+            "(whole file)".to_string()
+        } else {
+            let same_line = line.split_once(']').map_or("", |(_, rest)| rest.trim());
+            let next = lines[i + 1..]
+                .iter()
+                .map(|line| line.trim())
+                .find(|line| !line.is_empty() && !line.starts_with("#[") && !line.starts_with("//"));
+            item_signature(if same_line.is_empty() { next.unwrap_or("") } else { same_line })
+        };
+        hidden.push(HiddenCode { file: file.to_string(), line: i + 1, item, reason });
+    }
+    hidden
+}
+
+/// The declaration part of an item's first line.
+fn item_signature(line: &str) -> String {
+    line.split('{').next().unwrap_or(line).trim().trim_end_matches(';').trim().to_string()
+}
+
+/// A cfg predicate; everything but `feature` is unknown.
+#[derive(Debug)]
+enum Cfg {
+    Feature(String),
+    Not(Box<Cfg>),
+    All(Vec<Cfg>),
+    Any(Vec<Cfg>),
+    Unknown,
+}
+
+impl Cfg {
+    fn parse(text: &str) -> Option<Cfg> {
+        let mut parser = CfgParser { rest: text };
+        let cfg = parser.predicate()?;
+        parser.rest.trim().is_empty().then_some(cfg)
+    }
+
+    /// `None` when the result depends on an unknown predicate.
+    fn eval(&self, features: &FeatureSet) -> Option<bool> {
+        match self {
+            Cfg::Feature(feature) => Some(features.is_enabled(feature)),
+            Cfg::Not(cfg) => cfg.eval(features).map(|value| !value),
+            Cfg::All(cfgs) => {
+                let values: Vec<Option<bool>> = cfgs.iter().map(|cfg| cfg.eval(features)).collect();
+                if values.contains(&Some(false)) {
+                    Some(false)
+                } else {
+                    values.into_iter().collect::<Option<Vec<_>>>().map(|_| true)
+                }
+            }
+            Cfg::Any(cfgs) => {
+                let values: Vec<Option<bool>> = cfgs.iter().map(|cfg| cfg.eval(features)).collect();
+                if values.contains(&Some(true)) {
+                    Some(true)
+                } else {
+                    values.into_iter().collect::<Option<Vec<_>>>().map(|_| false)
+                }
+            }
+            Cfg::Unknown => None,
+        }
+    }
+
+    fn collect_features(&self, f: &mut dyn FnMut(&str)) {
+        match self {
+            Cfg::Feature(feature) => f(feature),
+            Cfg::Not(cfg) => cfg.collect_features(f),
+            Cfg::All(cfgs) | Cfg::Any(cfgs) => cfgs.iter().for_each(|cfg| cfg.collect_features(f)),
+            Cfg::Unknown => {}
+        }
+    }
+}
+
+struct CfgParser<'a> {
+    rest: &'a str,
+}
+
+impl CfgParser<'_> {
+    fn eat(&mut self, token: char) -> bool {
+        self.rest = self.rest.trim_start();
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn ident(&mut self) -> Option<&str> {
+        self.rest = self.rest.trim_start();
+        let end = self.rest.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(self.rest.len());
+        let (ident, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        (!ident.is_empty()).then_some(ident)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.rest = self.rest.trim_start().strip_prefix('"')?;
+        let (value, rest) = self.rest.split_once('"')?;
+        self.rest = rest;
+        Some(value.to_string())
+    }
+
+    fn predicate(&mut self) -> Option<Cfg> {
+        let ident = self.ident()?.to_string();
+        if self.eat('=') {
+            let value = self.string()?;
+            return Some(if ident == "feature" { Cfg::Feature(value) } else { Cfg::Unknown });
+        }
+        if !self.eat('(') {
+            return Some(Cfg::Unknown);
+        }
+        let mut args = Vec::new();
+        while !self.eat(')') {
+            args.push(self.predicate()?);
+            if !self.eat(',') && !self.rest.trim_start().starts_with(')') {
+                return None;
+            }
+        }
+        Some(match ident.as_str() {
+            "not" if args.len() == 1 => Cfg::Not(Box::new(args.pop()?)),
+            "all" => Cfg::All(args),
+            "any" => Cfg::Any(args),
+            _ => Cfg::Unknown,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_hidden_code() {
+        let features = BTreeMap::from([
+            ("default".to_string(), vec!["json".to_string()]),
+            ("json".to_string(), vec!["dep:serde_json".to_string()]),
+            ("sqlite".to_string(), vec!["dep:rusqlite".to_string()]),
+        ]);
+        let args = ["--features".to_string(), "demo/extra".to_string()];
+        let selection = FeatureSelection::from_cargo_args(&args);
+        assert_eq!(selection.features, ["extra"]);
+        let set = FeatureSet::resolve(&features, &selection);
+        assert!(set.is_enabled("json") && !set.is_enabled("sqlite") && !set.is_enabled("extra"));
+
+        let source = r#"
+#[cfg(feature = "json")]
+fn to_json() {}
+
+#[cfg(feature = "sqlite")]
+pub mod history;
+
+#[cfg(all(test, feature = "sqlite"))]
+mod db_tests {}
+
+#[cfg(any(unix, feature = "sqlite"))]
+fn maybe() {}
+
+#[cfg(not(feature = "json"))] fn fallback() {}
+
+#[cfg(feature = "legacy")]
+#[allow(dead_code)]
+fn old() {}
+
+#[test]
+#[ignore = "needs network"]
+fn downloads() {}
+"#;
+        let hidden = scan_hidden_code(source, "src/lib.rs", &set);
+        let items: Vec<(&str, usize)> = hidden.iter().map(|h| (h.item.as_str(), h.line)).collect();
+        assert_eq!(
+            items,
+            [("pub mod history", 5), ("mod db_tests", 8), ("fn fallback()", 14), ("fn old()", 16), ("fn downloads()", 21)]
+        );
+        assert_eq!(
+            hidden[3].reason,
+            HiddenReason::InactiveCfg { cfg: "feature = \"legacy\"".to_string(), undeclared: vec!["legacy".to_string()] }
+        );
+        assert_eq!(hidden[4].reason, HiddenReason::IgnoredTest(Some("needs network".to_string())));
+
+        let all = FeatureSet::resolve(&features, &FeatureSelection { all_features: true, ..Default::default() });
+        assert_eq!(scan_hidden_code(source, "src/lib.rs", &all).len(), 3);
+    }
+}
@@ -0,0 +1,410 @@
+//! SQLite-backed run history, available with the `sqlite` feature.
+//!
+//! Every analysis run is recorded with its timestamp, git commit and branch,
+//! per-category, per-priority, per-lint, and per-file counts, and warning
+//! fingerprints. Trend data
+//! is read back from here instead of `clippy_historical.json` when a history
+//! database is configured, and single lints or files can be tracked over time.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use crate::{
+    analysis::{
+        fingerprint::fingerprint_counts,
+        renames::RenameMap,
+        statistics::warning::WarningStatistics,
+        trends::{TrendAnalysis, recurring_issues},
+    },
+    types::{CategoryType, Priority, Warning},
+};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    git_commit TEXT,
+    branch TEXT,
+    total_warnings INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS runs_timestamp ON runs(timestamp);
+CREATE TABLE IF NOT EXISTS run_counts (
+    run_id INTEGER NOT NULL REFERENCES runs(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    key TEXT NOT NULL,
+    count INTEGER NOT NULL,
+    PRIMARY KEY (run_id, kind, key)
+);
+CREATE INDEX IF NOT EXISTS run_counts_key ON run_counts(kind, key);
+";
+
+const KIND_CATEGORY: &str = "category";
+const KIND_PRIORITY: &str = "priority";
+const KIND_LINT: &str = "lint";
+const KIND_FILE: &str = "file";
+const KIND_ISSUE: &str = "issue";
+const KIND_FINGERPRINT: &str = "fingerprint";
+const KIND_CHURN: &str = "churn";
+
+/// One analysis run as stored in the history database.
+#[derive(Debug, Clone)]
+pub struct RunRecord {
+    pub timestamp: DateTime<Utc>,
+    pub git_commit: Option<String>,
+    pub branch: Option<String>,
+    pub total_warnings: usize,
+    pub by_category: HashMap<CategoryType, usize>,
+    pub by_priority: HashMap<Priority, usize>,
+    pub by_lint: HashMap<String, usize>,
+    pub by_file: HashMap<String, usize>,
+    /// Warnings by recurrence key
+    pub by_issue: HashMap<String, usize>,
+    /// Warnings by fingerprint
+    pub fingerprints: HashMap<String, usize>,
+    /// Warnings new and fixed since the previous run, as counted with files
+    /// renamed in between followed; without it, trends compare the stored
+    /// fingerprints of both runs
+    pub churn: Option<(usize, usize)>,
+}
+
+impl RunRecord {
+    /// Builds a record for the current time from a run's warnings.
+    pub fn new(warnings: &[Warning], stats: &WarningStatistics) -> Self {
+        let mut by_lint = HashMap::new();
+        let mut by_file = HashMap::new();
+        for warning in warnings {
+            *by_lint.entry(warning.lint_code.clone()).or_insert(0) += 1;
+            *by_file.entry(warning.file.clone()).or_insert(0) += 1;
+        }
+
+        Self {
+            timestamp: Utc::now(),
+            git_commit: None,
+            branch: None,
+            total_warnings: stats.total_warnings,
+            by_category: stats.by_category.clone(),
+            by_priority: stats.by_priority.clone(),
+            by_lint,
+            by_file,
+            by_issue: recurring_issues(warnings),
+            fingerprints: fingerprint_counts(warnings),
+            churn: None,
+        }
+    }
+}
+
+/// A single point of a lint or file trend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrendPoint {
+    pub timestamp: String,
+    pub git_commit: Option<String>,
+    pub count: usize,
+}
+
+pub struct HistoryDb {
+    conn: Connection,
+}
+
+impl HistoryDb {
+    /// Opens (or creates) the history database at `path`.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let conn = Connection::open(path).map_err(sql_error)?;
+        conn.execute_batch("PRAGMA foreign_keys = ON;").map_err(sql_error)?;
+        conn.execute_batch(SCHEMA).map_err(sql_error)?;
+        Ok(Self { conn })
+    }
+
+    /// Stores a run and returns its id.
+    pub fn record_run(&mut self, run: &RunRecord) -> io::Result<i64> {
+        let tx = self.conn.transaction().map_err(sql_error)?;
+        tx.execute(
+            "INSERT INTO runs (timestamp, git_commit, branch, total_warnings) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                format_timestamp(run.timestamp),
+                run.git_commit,
+                run.branch,
+                run.total_warnings as i64,
+            ],
+        )
+        .map_err(sql_error)?;
+        let run_id = tx.last_insert_rowid();
+
+        {
+            let mut insert = tx
+                .prepare("INSERT INTO run_counts (run_id, kind, key, count) VALUES (?1, ?2, ?3, ?4)")
+                .map_err(sql_error)?;
+            let counts = run.by_category.iter().map(|(k, v)| (KIND_CATEGORY, k.to_string(), *v))
+                .chain(run.by_priority.iter().map(|(k, v)| (KIND_PRIORITY, k.to_string(), *v)))
+                .chain(run.by_lint.iter().map(|(k, v)| (KIND_LINT, k.clone(), *v)))
+                .chain(run.by_file.iter().map(|(k, v)| (KIND_FILE, k.clone(), *v)))
+                .chain(run.by_issue.iter().map(|(k, v)| (KIND_ISSUE, k.clone(), *v)))
+                .chain(run.fingerprints.iter().map(|(k, v)| (KIND_FINGERPRINT, k.clone(), *v)))
+                .chain(run.churn.into_iter().flat_map(|(new, fixed)| {
+                    [(KIND_CHURN, "new".to_string(), new), (KIND_CHURN, "fixed".to_string(), fixed)]
+                }));
+            for (kind, key, count) in counts {
+                insert.execute(params![run_id, kind, key, count as i64]).map_err(sql_error)?;
+            }
+        }
+
+        tx.commit().map_err(sql_error)?;
+        Ok(run_id)
+    }
+
+    /// Occurrences of `lint` (e.g. `clippy::unwrap_used`) in each run of the
+    /// last `days` days, oldest first. Runs without the lint count as zero.
+    pub fn lint_trend(&self, lint: &str, days: i64) -> io::Result<Vec<TrendPoint>> {
+        self.count_trend(KIND_LINT, lint, days)
+    }
+
+    /// Warnings in `file` for each run of the last `days` days, oldest
+    /// first, counting runs from before the file was renamed under its
+    /// earlier paths.
+    pub fn file_trend(&self, file: &str, renames: &RenameMap, days: i64) -> io::Result<Vec<TrendPoint>> {
+        let mut points = self.count_trend(KIND_FILE, file, days)?;
+        for earlier in renames.earlier_paths(file) {
+            for (point, earlier) in points.iter_mut().zip(self.count_trend(KIND_FILE, earlier, days)?) {
+                point.count += earlier.count;
+            }
+        }
+        Ok(points)
+    }
+
+    /// Trend summaries of the most recent `limit` runs, oldest first.
+    pub fn recent_trends(&self, limit: usize) -> io::Result<Vec<TrendAnalysis>> {
+        let mut runs = self.conn
+            .prepare(
+                "SELECT id, timestamp, total_warnings FROM runs
+                 ORDER BY timestamp DESC, id DESC LIMIT ?1",
+            )
+            .map_err(sql_error)?;
+        let rows = runs
+            .query_map(params![limit as i64], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)?))
+            })
+            .map_err(sql_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql_error)?;
+
+        let mut trends = Vec::with_capacity(rows.len());
+        let mut previous_fingerprints: HashMap<String, usize> = HashMap::new();
+        for (run_id, timestamp, total) in rows.into_iter().rev() {
+            let by_category = self.run_counts(run_id, KIND_CATEGORY)?
+                .into_iter()
+                .filter_map(|(key, count)| key.parse::<CategoryType>().ok().map(|c| (c, count)))
+                .collect();
+            let by_priority = self.run_counts(run_id, KIND_PRIORITY)?
+                .into_iter()
+                .filter_map(|(key, count)| key.parse::<Priority>().ok().map(|p| (p, count)))
+                .collect();
+            // Runs recorded before recurrence keys only have lint counts
+            let mut recurring_issues: HashMap<String, usize> = self.run_counts(run_id, KIND_ISSUE)?.into_iter().collect();
+            if recurring_issues.is_empty() {
+                recurring_issues = self.run_counts(run_id, KIND_LINT)?.into_iter().collect();
+            }
+
+            let mut trend = TrendAnalysis::new(total as usize, by_category, by_priority, recurring_issues);
+            trend.dates = vec![timestamp.chars().take(10).collect()];
+            trend.by_file = self.run_counts(run_id, KIND_FILE)?.into_iter().collect();
+            let previous: Vec<usize> = trends.iter().map(|t: &TrendAnalysis| t.total_warnings).collect();
+            trend.calculate_improvement_rate(&previous);
+            trend.calculate_weighted_improvement(&trends);
+            // Runs recorded before fingerprints have none to compare
+            let fingerprints: HashMap<String, usize> = self.run_counts(run_id, KIND_FINGERPRINT)?.into_iter().collect();
+            let churn: HashMap<String, usize> = self.run_counts(run_id, KIND_CHURN)?.into_iter().collect();
+            if let (Some(&new), Some(&fixed)) = (churn.get("new"), churn.get("fixed")) {
+                trend.new_warnings = Some(new);
+                trend.fixed_warnings = Some(fixed);
+            } else if !previous_fingerprints.is_empty() && (total == 0 || !fingerprints.is_empty()) {
+                trend.set_fingerprint_churn(&previous_fingerprints, &fingerprints);
+            }
+            previous_fingerprints = fingerprints;
+            trends.push(trend);
+        }
+        Ok(trends)
+    }
+
+    /// Fingerprint counts of the most recent run, empty if there is none
+    /// or it was recorded without fingerprints.
+    pub fn latest_fingerprints(&self) -> io::Result<HashMap<String, usize>> {
+        let run_id = self.conn
+            .query_row("SELECT id FROM runs ORDER BY timestamp DESC, id DESC LIMIT 1", [], |row| row.get::<_, i64>(0))
+            .optional()
+            .map_err(sql_error)?;
+        match run_id {
+            Some(run_id) => Ok(self.run_counts(run_id, KIND_FINGERPRINT)?.into_iter().collect()),
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    /// Commit of the most recent run, if it recorded one.
+    pub fn latest_commit(&self) -> io::Result<Option<String>> {
+        let commit = self.conn
+            .query_row("SELECT git_commit FROM runs ORDER BY timestamp DESC, id DESC LIMIT 1", [], |row| {
+                row.get::<_, Option<String>>(0)
+            })
+            .optional()
+            .map_err(sql_error)?;
+        Ok(commit.flatten())
+    }
+
+    /// Whether a run was recorded at `timestamp`, to the second.
+    pub fn has_run_at(&self, timestamp: DateTime<Utc>) -> io::Result<bool> {
+        self.conn
+            .query_row("SELECT 1 FROM runs WHERE timestamp = ?1 LIMIT 1", params![format_timestamp(timestamp)], |_| Ok(()))
+            .optional()
+            .map(|run| run.is_some())
+            .map_err(sql_error)
+    }
+
+    /// Fingerprint counts of the latest run recorded for `commit`.
+    pub fn fingerprints_for_commit(&self, commit: &str) -> io::Result<HashMap<String, usize>> {
+        match self.latest_run_for_commit(commit)? {
+            Some((run_id, _)) => Ok(self.run_counts(run_id, KIND_FINGERPRINT)?.into_iter().collect()),
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    /// Statistics of the latest run recorded for `commit`.
+    pub fn stats_for_commit(&self, commit: &str) -> io::Result<Option<WarningStatistics>> {
+        let Some((run_id, total)) = self.latest_run_for_commit(commit)? else { return Ok(None) };
+
+        let by_file: HashMap<String, usize> = self.run_counts(run_id, KIND_FILE)?.into_iter().collect();
+        Ok(Some(WarningStatistics {
+            total_warnings: total as usize,
+            total_input_warnings: total as usize,
+            files_affected: by_file.len(),
+            by_category: self.run_counts(run_id, KIND_CATEGORY)?
+                .into_iter()
+                .filter_map(|(key, count)| key.parse::<CategoryType>().ok().map(|c| (c, count)))
+                .collect(),
+            by_priority: self.run_counts(run_id, KIND_PRIORITY)?
+                .into_iter()
+                .filter_map(|(key, count)| key.parse::<Priority>().ok().map(|p| (p, count)))
+                .collect(),
+            by_lint: self.run_counts(run_id, KIND_LINT)?.into_iter().collect(),
+            by_file,
+            ..Default::default()
+        }))
+    }
+
+    fn latest_run_for_commit(&self, commit: &str) -> io::Result<Option<(i64, i64)>> {
+        self.conn
+            .query_row(
+                "SELECT id, total_warnings FROM runs WHERE git_commit = ?1
+                 ORDER BY timestamp DESC, id DESC LIMIT 1",
+                params![commit],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .optional()
+            .map_err(sql_error)
+    }
+
+    fn count_trend(&self, kind: &str, key: &str, days: i64) -> io::Result<Vec<TrendPoint>> {
+        let since = format_timestamp(Utc::now() - Duration::days(days));
+        let mut query = self.conn
+            .prepare(
+                "SELECT r.timestamp, r.git_commit, COALESCE(c.count, 0) FROM runs r
+                 LEFT JOIN run_counts c ON c.run_id = r.id AND c.kind = ?1 AND c.key = ?2
+                 WHERE r.timestamp >= ?3
+                 ORDER BY r.timestamp, r.id",
+            )
+            .map_err(sql_error)?;
+        let points = query
+            .query_map(params![kind, key, since], |row| {
+                Ok(TrendPoint {
+                    timestamp: row.get(0)?,
+                    git_commit: row.get(1)?,
+                    count: row.get::<_, i64>(2)? as usize,
+                })
+            })
+            .map_err(sql_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql_error)?;
+        Ok(points)
+    }
+
+    fn run_counts(&self, run_id: i64, kind: &str) -> io::Result<Vec<(String, usize)>> {
+        let mut query = self.conn
+            .prepare("SELECT key, count FROM run_counts WHERE run_id = ?1 AND kind = ?2")
+            .map_err(sql_error)?;
+        let counts = query
+            .query_map(params![run_id, kind], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+            })
+            .map_err(sql_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql_error)?;
+        Ok(counts)
+    }
+}
+
+/// UTC timestamps in a fixed format sort chronologically as text.
+fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn sql_error(e: rusqlite::Error) -> io::Error {
+    io::Error::other(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(lints: &[(&str, usize)], age_days: i64) -> RunRecord {
+        let by_lint: HashMap<String, usize> = lints.iter().map(|(l, c)| (l.to_string(), *c)).collect();
+        RunRecord {
+            timestamp: Utc::now() - Duration::days(age_days),
+            git_commit: Some(format!("commit-{}", age_days)),
+            branch: Some("main".to_string()),
+            total_warnings: by_lint.values().sum(),
+            by_category: HashMap::from([(CategoryType::Safety, by_lint.values().sum())]),
+            by_priority: HashMap::new(),
+            by_lint,
+            by_file: HashMap::new(),
+            by_issue: HashMap::new(),
+            fingerprints: lints.iter().map(|(l, c)| (format!("{}@{}", l, age_days), *c)).collect(),
+            churn: None,
+        }
+    }
+
+    #[test]
+    fn test_lint_trend_over_window() {
+        let mut db = HistoryDb::open(Path::new(":memory:")).unwrap();
+        db.record_run(&run(&[("clippy::unwrap_used", 9)], 120)).unwrap();
+        db.record_run(&run(&[("clippy::unwrap_used", 5)], 30)).unwrap();
+        db.record_run(&run(&[("clippy::needless_return", 2)], 1)).unwrap();
+
+        let trend = db.lint_trend("clippy::unwrap_used", 90).unwrap();
+        let counts: Vec<usize> = trend.iter().map(|p| p.count).collect();
+        assert_eq!(counts, vec![5, 0]);
+
+        let trends = db.recent_trends(10).unwrap();
+        assert_eq!(trends.len(), 3);
+        assert_eq!(trends[0].total_warnings, 9);
+        assert_eq!(trends[2].by_category.get(&CategoryType::Safety), Some(&2));
+        assert_eq!(trends[0].new_warnings, None);
+        assert_eq!((trends[2].new_warnings, trends[2].fixed_warnings), (Some(2), Some(5)));
+        assert_eq!(db.fingerprints_for_commit("commit-30").unwrap().get("clippy::unwrap_used@30"), Some(&5));
+
+        let stats = db.stats_for_commit("commit-30").unwrap().unwrap();
+        assert_eq!(stats.by_lint.get("clippy::unwrap_used"), Some(&5));
+        assert!(db.stats_for_commit("unknown").unwrap().is_none());
+
+        // A file renamed between runs, with the churn counted at record time
+        let mut before = run(&[("clippy::needless_return", 2)], 0);
+        before.by_file = HashMap::from([("src/old.rs".to_string(), 2)]);
+        db.record_run(&before).unwrap();
+        let mut after = run(&[("clippy::needless_return", 2)], 0);
+        after.by_file = HashMap::from([("src/new.rs".to_string(), 2)]);
+        after.churn = Some((1, 0));
+        db.record_run(&after).unwrap();
+        let renames = RenameMap::from_renames([("src/old.rs".to_string(), "src/new.rs".to_string())]);
+        let counts: Vec<usize> = db.file_trend("src/new.rs", &renames, 90).unwrap().iter().map(|p| p.count).collect();
+        assert_eq!(counts, vec![0, 0, 2, 2]);
+        assert_eq!(db.recent_trends(1).unwrap()[0].new_warnings, Some(1));
+    }
+}
@@ -0,0 +1,130 @@
+//! Hotspot detection.
+//!
+//! Ranks files by a weighted score combining how many warnings they have, how
+//! severe those warnings are, and how often the file changed recently. Files
+//! that are both noisy and actively edited are the best candidates to fix
+//! first, since the debt there is paid on every change.
+
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use crate::types::Warning;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct HotspotConfig {
+    /// Months of git history counted as churn
+    pub months: u32,
+    /// Number of hotspots listed in the fix plan
+    pub limit: usize,
+    pub warning_weight: f64,
+    pub severity_weight: f64,
+    pub churn_weight: f64,
+}
+
+impl Default for HotspotConfig {
+    fn default() -> Self {
+        Self {
+            months: 6,
+            limit: 10,
+            warning_weight: 1.0,
+            severity_weight: 1.0,
+            churn_weight: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Hotspot {
+    pub file: String,
+    pub warnings: usize,
+    /// Sum of the severity scores of the file's warnings
+    pub severity: usize,
+    /// Commits touching the file within the configured window
+    pub churn: usize,
+    pub score: f64,
+}
+
+/// Ranks files with warnings, highest score first.
+///
+/// Each factor is normalized by its maximum across files before weighting,
+/// so the score ranges from 0 to the sum of the weights.
+pub fn rank_hotspots(
+    warnings: &[Warning],
+    churn: &HashMap<String, usize>,
+    config: &HotspotConfig,
+) -> Vec<Hotspot> {
+    let mut by_file: HashMap<&str, (usize, usize)> = HashMap::new();
+    for warning in warnings {
+        let entry = by_file.entry(&warning.file).or_default();
+        entry.0 += 1;
+        entry.1 += warning.priority.severity_score() as usize;
+    }
+
+    let mut hotspots: Vec<Hotspot> = by_file
+        .into_iter()
+        .map(|(file, (warnings, severity))| Hotspot {
+            file: file.to_string(),
+            warnings,
+            severity,
+            churn: churn.get(file).copied().unwrap_or(0),
+            score: 0.0,
+        })
+        .collect();
+
+    let max = |f: fn(&Hotspot) -> usize| hotspots.iter().map(f).max().unwrap_or(0).max(1) as f64;
+    let (max_warnings, max_severity, max_churn) = (max(|h| h.warnings), max(|h| h.severity), max(|h| h.churn));
+    for hotspot in &mut hotspots {
+        hotspot.score = config.warning_weight * hotspot.warnings as f64 / max_warnings
+            + config.severity_weight * hotspot.severity as f64 / max_severity
+            + config.churn_weight * hotspot.churn as f64 / max_churn;
+    }
+
+    hotspots.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.file.cmp(&b.file)));
+    hotspots.truncate(config.limit);
+    hotspots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    fn warning(file: &str, priority: Priority) -> Warning {
+        Warning { priority, ..Warning::for_test("clippy::unwrap_used", file, 1) }
+    }
+
+    fn files(hotspots: &[Hotspot]) -> Vec<&str> {
+        hotspots.iter().map(|h| h.file.as_str()).collect()
+    }
+
+    #[test]
+    fn test_rank_hotspots() {
+        let warnings = [
+            warning("src/a.rs", Priority::Medium),
+            warning("src/a.rs", Priority::Medium),
+            warning("src/b.rs", Priority::Critical),
+            warning("src/c.rs", Priority::Low),
+        ];
+        let churn = HashMap::from([("src/b.rs".to_string(), 4), ("src/c.rs".to_string(), 4)]);
+
+        let hotspots = rank_hotspots(&warnings, &churn, &HotspotConfig::default());
+        assert_eq!(files(&hotspots), ["src/b.rs", "src/a.rs", "src/c.rs"]);
+        assert_eq!(
+            hotspots[1],
+            Hotspot { file: "src/a.rs".to_string(), warnings: 2, severity: 6, churn: 0, score: 2.0 }
+        );
+        assert!((hotspots[0].score - (0.5 + 5.0 / 6.0 + 1.0)).abs() < 1e-9);
+
+        // Without churn, the file with the most and most severe warnings leads
+        let config = HotspotConfig { churn_weight: 0.0, limit: 2, ..HotspotConfig::default() };
+        assert_eq!(files(&rank_hotspots(&warnings, &churn, &config)), ["src/a.rs", "src/b.rs"]);
+    }
+
+    #[test]
+    fn test_equal_scores_rank_by_file() {
+        let warnings = [warning("src/z.rs", Priority::High), warning("src/m.rs", Priority::High)];
+        let hotspots = rank_hotspots(&warnings, &HashMap::new(), &HotspotConfig::default());
+        assert_eq!(files(&hotspots), ["src/m.rs", "src/z.rs"]);
+        assert!(rank_hotspots(&[], &HashMap::new(), &HotspotConfig::default()).is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+//! Size and compile-time impact of performance warnings.
+//!
+//! A performance lint in a function that is never called costs nothing,
+//! while one in the largest function of the binary, or in a crate that
+//! dominates the build, is worth fixing first. Function and crate sizes come
+//! from `cargo bloat --message-format json -n 0`, compile times per crate
+//! from the `timing-info` messages of `cargo build --timings=json`.
+//! Performance warnings are attributed to their enclosing function, found
+//! by scanning the source as for the per-function ranking, and matched to
+//! the measured functions of their crate by name.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io;
+use std::path::Path;
+use serde::Deserialize;
+use crate::types::{CategoryType, Warning};
+use super::functions::{function_spans, innermost_item, ItemSpan};
+
+/// Size of one function of the binary, as measured by cargo-bloat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionSize {
+    /// Crate name with underscores, e.g. `serde_json`
+    pub crate_name: String,
+    /// Demangled path without hash, e.g. `demo::parser::Parser::parse`
+    pub name: String,
+    pub bytes: u64,
+}
+
+/// Binary size and compile time measurements of the workspace.
+#[derive(Debug, Clone, Default)]
+pub struct BuildImpact {
+    /// Size of the analyzed binary
+    pub file_size: Option<u64>,
+    pub functions: Vec<FunctionSize>,
+    /// Code size per crate, summed over its functions
+    pub crate_sizes: BTreeMap<String, u64>,
+    /// Compile time per crate in seconds, summed over its targets
+    pub compile_seconds: BTreeMap<String, f64>,
+}
+
+#[derive(Deserialize)]
+struct BloatOutput {
+    #[serde(rename = "file-size")]
+    file_size: Option<u64>,
+    #[serde(default)]
+    functions: Vec<BloatFunction>,
+}
+
+#[derive(Deserialize)]
+struct BloatFunction {
+    #[serde(rename = "crate")]
+    crate_name: Option<String>,
+    name: String,
+    size: u64,
+}
+
+#[derive(Deserialize)]
+struct TimingInfo {
+    reason: String,
+    target: TimingTarget,
+    duration: f64,
+}
+
+#[derive(Deserialize)]
+struct TimingTarget {
+    name: String,
+}
+
+impl BuildImpact {
+    /// Reads the output of `cargo bloat --message-format json`.
+    pub fn from_bloat_json(json: &str) -> io::Result<Self> {
+        let output: BloatOutput = serde_json::from_str(json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid cargo-bloat output: {}", e)))?;
+        let mut impact = Self { file_size: output.file_size, ..Self::default() };
+        for function in output.functions {
+            let Some(crate_name) = function.crate_name else { continue };
+            let crate_name = crate_key(&crate_name);
+            *impact.crate_sizes.entry(crate_name.clone()).or_default() += function.size;
+            impact.functions.push(FunctionSize { crate_name, name: function.name, bytes: function.size });
+        }
+        Ok(impact)
+    }
+
+    /// Adds the compile times of the `timing-info` lines of a cargo build's
+    /// JSON output, returning how many targets were timed. Other lines are
+    /// skipped.
+    pub fn add_timings(&mut self, output: &str) -> usize {
+        let mut timed = 0;
+        for line in output.lines().filter(|line| line.contains("\"timing-info\"")) {
+            let Ok(info) = serde_json::from_str::<TimingInfo>(line) else { continue };
+            if info.reason == "timing-info" {
+                *self.compile_seconds.entry(crate_key(&info.target.name)).or_default() += info.duration;
+                timed += 1;
+            }
+        }
+        timed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty() && self.compile_seconds.is_empty()
+    }
+
+    /// Total size of the functions of `crate_name` named `function`, over
+    /// all their monomorphizations. `function` is a name as found in the
+    /// source, e.g. `Parser::parse`.
+    pub fn function_bytes(&self, crate_name: &str, function: &str) -> Option<u64> {
+        let crate_name = crate_key(crate_name);
+        let suffix = format!("::{}", function);
+        let matching: Vec<u64> = self
+            .functions
+            .iter()
+            .filter(|f| f.crate_name == crate_name)
+            .filter(|f| {
+                let name = plain_path(&f.name);
+                name == function || name.ends_with(&suffix)
+            })
+            .map(|f| f.bytes)
+            .collect();
+        (!matching.is_empty()).then(|| matching.iter().sum())
+    }
+
+    /// The performance warnings among `warnings` whose code has a measured
+    /// size or compile time, most impactful first. Sources are read
+    /// relative to `root` to find the enclosing functions.
+    pub fn annotate<'a>(&self, warnings: &'a [Warning], root: &Path) -> Vec<PerformanceImpact<'a>> {
+        let mut items_by_file: HashMap<&str, Vec<ItemSpan>> = HashMap::new();
+        let mut impacts: Vec<PerformanceImpact> = warnings
+            .iter()
+            .filter(|w| w.category == CategoryType::Performance)
+            .filter_map(|warning| {
+                let crate_name = warning.crate_name.as_deref()?;
+                let items = items_by_file.entry(&warning.file).or_insert_with(|| {
+                    std::fs::read_to_string(root.join(&warning.file))
+                        .map(|source| function_spans(&source))
+                        .unwrap_or_default()
+                });
+                let function = innermost_item(items, warning.line).map(|item| item.name.clone());
+                let impact = PerformanceImpact {
+                    warning,
+                    function_bytes: function.as_deref().and_then(|f| self.function_bytes(crate_name, f)),
+                    function,
+                    crate_bytes: self.crate_sizes.get(&crate_key(crate_name)).copied(),
+                    compile_seconds: self.compile_seconds.get(&crate_key(crate_name)).copied(),
+                };
+                impact.is_measured().then_some(impact)
+            })
+            .collect();
+        impacts.sort_by(|a, b| {
+            b.function_bytes
+                .cmp(&a.function_bytes)
+                .then(b.crate_bytes.cmp(&a.crate_bytes))
+                .then(b.compile_seconds.partial_cmp(&a.compile_seconds).unwrap_or(Ordering::Equal))
+                .then_with(|| (&a.warning.file, a.warning.line).cmp(&(&b.warning.file, b.warning.line)))
+        });
+        impacts
+    }
+}
+
+/// A performance warning with the measured size and compile time of the
+/// code it is in.
+#[derive(Debug, Clone)]
+pub struct PerformanceImpact<'a> {
+    pub warning: &'a Warning,
+    /// Enclosing function, e.g. `Parser::parse`
+    pub function: Option<String>,
+    pub function_bytes: Option<u64>,
+    pub crate_bytes: Option<u64>,
+    pub compile_seconds: Option<f64>,
+}
+
+impl PerformanceImpact<'_> {
+    pub fn is_measured(&self) -> bool {
+        self.function_bytes.is_some() || self.crate_bytes.is_some() || self.compile_seconds.is_some()
+    }
+}
+
+/// A size in bytes, displayed as `512 B`, `12.3 KiB`, or `4.1 MiB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bytes(pub u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            bytes if bytes < 1024 => write!(f, "{} B", bytes),
+            bytes if bytes < 1024 * 1024 => write!(f, "{:.1} KiB", bytes as f64 / 1024.0),
+            bytes => write!(f, "{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+        }
+    }
+}
+
+/// A demangled function path as the source names it: `<Foo as Trait>::f`
+/// becomes `Foo::f`, and generic arguments are dropped.
+fn plain_path(name: &str) -> String {
+    let name = match name.strip_prefix('<').and_then(|name| name.split_once(">::")) {
+        Some((ty, rest)) => format!("{}::{}", ty.split(" as ").next().unwrap_or(ty), rest),
+        None => name.to_string(),
+    };
+    let mut depth = 0usize;
+    name.chars()
+        .filter(|c| match c {
+            '<' => {
+                depth += 1;
+                false
+            }
+            '>' => {
+                depth = depth.saturating_sub(1);
+                false
+            }
+            _ => depth == 0,
+        })
+        .collect()
+}
+
+/// Crate names as the compiler spells them, with underscores.
+fn crate_key(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotate_performance_warnings() {
+        let mut impact = BuildImpact::from_bloat_json(
+            r#"{"file-size": 4194304, "text-section-size": 1048576, "functions": [
+                {"crate": "demo", "name": "demo::parser::Parser::parse", "size": 6144},
+                {"crate": "demo", "name": "<demo::parser::Parser>::parse", "size": 2048},
+                {"crate": "demo", "name": "demo::main", "size": 512},
+                {"crate": "std", "name": "std::rt::lang_start_internal", "size": 900}
+            ]}"#,
+        )
+        .unwrap();
+        let timings = [
+            r#"{"reason":"compiler-artifact","target":{"name":"demo"}}"#,
+            r#"{"reason":"timing-info","package_id":"demo 0.1.0","target":{"name":"demo"},"mode":"build","duration":4.5,"rmeta_time":2.0}"#,
+        ];
+        assert_eq!(impact.add_timings(&timings.join("\n")), 1);
+        assert_eq!(impact.function_bytes("demo", "Parser::parse"), Some(8192));
+        assert_eq!(impact.crate_sizes["demo"], 8704);
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("src/parser.rs"),
+            "impl Parser {\n    pub fn parse(&self) {\n        let v = self.input.clone();\n    }\n}\n\nfn unused() {\n    let x = 1;\n}\n",
+        )
+        .unwrap();
+        let warning = |line, category| Warning {
+            primary_message: "redundant clone".to_string(),
+            category,
+            crate_name: Some("demo".to_string()),
+            ..Warning::for_test("clippy::redundant_clone", "src/parser.rs", line)
+        };
+        let warnings = [
+            warning(8, CategoryType::Performance),
+            warning(3, CategoryType::Performance),
+            warning(3, CategoryType::Style),
+        ];
+        let impacts = impact.annotate(&warnings, root);
+
+        assert_eq!(impacts.len(), 2);
+        assert_eq!(impacts[0].warning.line, 3);
+        assert_eq!(impacts[0].function.as_deref(), Some("Parser::parse"));
+        assert_eq!(impacts[0].function_bytes, Some(8192));
+        assert_eq!(impacts[1].function_bytes, None);
+        assert_eq!(impacts[1].compile_seconds, Some(4.5));
+        assert_eq!(Bytes(8192).to_string(), "8.0 KiB");
+    }
+}
@@ -1,7 +1,63 @@
 pub mod trends;
+pub mod alerts;
+pub mod baseline;
+pub mod changed_lines;
 pub mod charts;
+pub mod clusters;
+pub mod dependencies;
+pub mod false_positives;
+pub mod fingerprint;
+pub mod functions;
+pub mod gate;
+pub mod gate_simulation;
+pub mod generated;
+pub mod heatmap;
+pub mod hidden_code;
+#[cfg(feature = "sqlite")]
+pub mod history;
+pub mod hotspots;
+pub mod impact;
+pub mod module_tree;
+pub mod normalize;
+pub mod pipeline;
+pub mod plugin;
+pub mod ranking;
+pub mod renames;
+pub mod scope;
+pub mod severity;
 pub mod statistics;
+pub mod suppression;
+pub mod third_party;
+pub mod unsafe_usage;
 
 pub use trends::*;
+pub use alerts::*;
+pub use baseline::*;
+pub use changed_lines::*;
 pub use charts::*;
+pub use clusters::*;
+pub use dependencies::*;
+pub use false_positives::*;
+pub use fingerprint::*;
+pub use functions::*;
+pub use gate::*;
+pub use gate_simulation::*;
+pub use generated::*;
+pub use heatmap::*;
+pub use hidden_code::*;
+#[cfg(feature = "sqlite")]
+pub use history::*;
+pub use hotspots::*;
+pub use impact::*;
+pub use module_tree::*;
+pub use normalize::*;
+pub use pipeline::*;
+pub use plugin::*;
+pub use ranking::*;
+pub use renames::*;
+pub use scope::*;
+pub use severity::*;
 pub use statistics::*;
+pub use suppression::*;
+pub use third_party::*;
+pub use unsafe_usage::*;
@@ -0,0 +1,118 @@
+//! Module hierarchy with warning density.
+//!
+//! Derives a module tree from the paths of files with warnings: directories
+//! and files become nodes, with `mod.rs`, `lib.rs`, and `main.rs` counted
+//! toward their directory. Each node carries the warnings of its subtree and
+//! a heat level based on warnings per affected file.
+
+use std::collections::BTreeMap;
+use crate::analysis::{
+    heatmap::heat_level,
+    statistics::warning::WarningStatistics,
+};
+
+/// Path components kept before deeper modules are folded into their ancestor
+pub const MAX_MODULE_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, Default)]
+pub struct ModuleNode {
+    pub name: String,
+    pub warnings: usize,
+    pub files: usize,
+    pub level: u8,
+    pub children: Vec<ModuleNode>,
+}
+
+impl ModuleNode {
+    /// Warnings per affected file in this subtree.
+    pub fn density(&self) -> f64 {
+        if self.files == 0 {
+            0.0
+        } else {
+            self.warnings as f64 / self.files as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ModuleTree {
+    /// Top-level nodes, most warnings first
+    pub roots: Vec<ModuleNode>,
+}
+
+#[derive(Default)]
+struct Builder {
+    warnings: usize,
+    files: usize,
+    children: BTreeMap<String, Builder>,
+}
+
+impl ModuleTree {
+    pub fn from_stats(stats: &WarningStatistics) -> Self {
+        let mut root = Builder::default();
+        for (file, &count) in &stats.by_file {
+            let mut node = &mut root;
+            for component in module_path(file) {
+                node = node.children.entry(component).or_default();
+                node.warnings += count;
+                node.files += 1;
+            }
+        }
+
+        let mut roots: Vec<ModuleNode> = root.children.into_iter().map(|(name, b)| build(name, b)).collect();
+        roots.sort_by(|a, b| b.warnings.cmp(&a.warnings).then_with(|| a.name.cmp(&b.name)));
+
+        // Densities scaled to integers so the shared heat scale applies
+        let max_density = roots.iter().map(max_scaled_density).max().unwrap_or(0);
+        for node in &mut roots {
+            assign_levels(node, max_density);
+        }
+        Self { roots }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+}
+
+/// Module path of `file`, outermost first and at most `MAX_MODULE_DEPTH`
+/// long: `src/network/mod.rs` and `src/network.rs` both give `src`,
+/// `network`.
+pub(crate) fn module_path(file: &str) -> Vec<String> {
+    let mut components: Vec<&str> = file.split('/').filter(|c| !c.is_empty() && *c != ".").collect();
+    if let Some(last) = components.pop() {
+        let stem = last.strip_suffix(".rs").unwrap_or(last);
+        if !matches!(stem, "mod" | "lib" | "main") || components.is_empty() {
+            components.push(stem);
+        }
+    }
+    components.truncate(MAX_MODULE_DEPTH);
+    components.into_iter().map(str::to_string).collect()
+}
+
+fn build(name: String, builder: Builder) -> ModuleNode {
+    let mut children: Vec<ModuleNode> = builder.children.into_iter().map(|(n, b)| build(n, b)).collect();
+    children.sort_by(|a, b| b.warnings.cmp(&a.warnings).then_with(|| a.name.cmp(&b.name)));
+    ModuleNode {
+        name,
+        warnings: builder.warnings,
+        files: builder.files,
+        level: 0,
+        children,
+    }
+}
+
+fn scaled_density(node: &ModuleNode) -> usize {
+    (node.density() * 100.0).round() as usize
+}
+
+fn max_scaled_density(node: &ModuleNode) -> usize {
+    node.children.iter().map(max_scaled_density).fold(scaled_density(node), usize::max)
+}
+
+fn assign_levels(node: &mut ModuleNode, max_density: usize) {
+    node.level = heat_level(scaled_density(node), max_density);
+    for child in &mut node.children {
+        assign_levels(child, max_density);
+    }
+}
@@ -0,0 +1,142 @@
+//! Message normalization.
+//!
+//! Warnings of one lint often differ only in the values their message
+//! embeds: "this vector has 37 elements", "unused import: `HashMap`".
+//! Grouping by raw message gives each value its own group, so statistics,
+//! clusters, the fix plan, and recurring-issue tracking group by the
+//! normalized message instead, where
+//!
+//! - numbers (`37`, `3.5`, `0x1f`, `1_000`) become `_`,
+//! - file paths become `<path>`,
+//! - quoted identifiers and literals become `` `_` ``, as do quoted names
+//!   after an item kind or a colon (``struct `Foo` ``, ``unused import: `HashMap` ``).
+//!
+//! Quoted code naming a construct, like `` `unwrap()` `` or `` `Some` ``,
+//! is kept.
+
+use crate::types::Warning;
+
+/// Placeholder for stripped identifiers and literals
+const VALUE_PLACEHOLDER: &str = "_";
+/// Placeholder for stripped file paths
+const PATH_PLACEHOLDER: &str = "<path>";
+
+/// Words after which quoted code names a user item
+const ITEM_KINDS: [&str; 16] = [
+    "constant", "crate", "enum", "field", "fields", "function", "import", "label", "lifetime",
+    "method", "module", "static", "struct", "trait", "type", "variant",
+];
+
+/// Normalizes the first line of a warning message into a template shared
+/// by warnings that differ only in identifiers, literals, or paths.
+pub fn normalize_message(message: &str) -> String {
+    let first_line = message.lines().next().unwrap_or("").trim();
+
+    let mut template = String::with_capacity(first_line.len());
+    let mut rest = first_line;
+    while let Some(start) = rest.find('`') {
+        let Some(len) = rest[start + 1..].find('`') else { break };
+        let before = &rest[..start];
+        template.push_str(&normalize_words(before));
+        let code = &rest[start + 1..start + 1 + len];
+        template.push('`');
+        template.push_str(if names_item(before) || is_variable_code(code) { VALUE_PLACEHOLDER } else { code });
+        template.push('`');
+        rest = &rest[start + len + 2..];
+    }
+    template.push_str(&normalize_words(rest));
+    template
+}
+
+/// Whether quoted code following `before` names a user item, as in
+/// ``struct `Foo` `` or ``unused import: `HashMap` ``.
+fn names_item(before: &str) -> bool {
+    let before = before.trim_end();
+    before.ends_with(':')
+        || before
+            .rsplit(' ')
+            .next()
+            .is_some_and(|word| ITEM_KINDS.contains(&word.to_ascii_lowercase().as_str()))
+}
+
+/// The subcategory `warning` is grouped under: its safety subcategory when
+/// the diagnostic determined one, otherwise its normalized message.
+pub fn subcategory(warning: &Warning) -> String {
+    match &warning.safety_subcategory {
+        Some(subcategory) => subcategory.to_string(),
+        None => normalize_message(&warning.primary_message),
+    }
+}
+
+/// Whether code quoted in a message names something specific to this
+/// warning (an identifier, literal, or path) rather than a construct.
+fn is_variable_code(code: &str) -> bool {
+    let is_identifier = !code.is_empty()
+        && code.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    let is_literal = code.starts_with(|c: char| c.is_ascii_digit() || c == '"' || c == '\'' || c == '-');
+    is_identifier || is_literal || is_path(code)
+}
+
+fn is_path(word: &str) -> bool {
+    word.contains('/') || word.ends_with(".rs")
+}
+
+fn normalize_words(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            let trimmed = word.trim_matches(|c: char| matches!(c, ',' | ';' | ':' | '(' | ')'));
+            if is_path(trimmed) {
+                word.replace(trimmed, PATH_PLACEHOLDER)
+            } else {
+                mask_numbers(word)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Replaces numbers starting at a word boundary, with any suffix, digit
+/// separators, or fraction (`37`, `37th`, `1_000`, `3.5`, `0x1f`), by `_`.
+fn mask_numbers(word: &str) -> String {
+    let chars: Vec<char> = word.chars().collect();
+    let mut masked = String::with_capacity(word.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let at_boundary = i == 0 || !(chars[i - 1].is_alphanumeric() || chars[i - 1] == '_');
+        if chars[i].is_ascii_digit() && at_boundary {
+            while i < chars.len()
+                && (chars[i].is_alphanumeric()
+                    || chars[i] == '_'
+                    || (chars[i] == '.' && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit())))
+            {
+                i += 1;
+            }
+            masked.push_str(VALUE_PLACEHOLDER);
+        } else {
+            masked.push(chars[i]);
+            i += 1;
+        }
+    }
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_message() {
+        assert_eq!(normalize_message("unused variable: `y`"), "unused variable: `_`");
+        assert_eq!(normalize_message("used `unwrap()` on `Some` value"), "used `unwrap()` on `Some` value");
+        assert_eq!(normalize_message("casting `42` to `u8` may truncate"), "casting `_` to `_` may truncate");
+        assert_eq!(normalize_message("file src/lib.rs has 3 items"), "file <path> has _ items");
+        assert_eq!(normalize_message("this vector has 37 elements"), "this vector has _ elements");
+        assert_eq!(
+            normalize_message("large size difference (1_000 bytes, 3.5x) between variants"),
+            "large size difference (_ bytes, _) between variants"
+        );
+        assert_eq!(normalize_message("unused import: `HashMap`"), "unused import: `_`");
+        assert_eq!(normalize_message("struct `Config` is never constructed"), "struct `_` is never constructed");
+        assert_eq!(normalize_message("item `u32` at position 2nd"), "item `_` at position _");
+    }
+}
@@ -0,0 +1,258 @@
+//! In-memory analysis of parsed warnings.
+//!
+//! [`analyze`] turns a parse result into the filtered warnings and statistics
+//! every report is built from. It writes nothing: report directories, log
+//! files, and the terminal belong to the command line layer, so other tools
+//! can embed the analysis as is. Source-based metrics (the per-function
+//! ranking and unsafe counts) read source files only when a source root is
+//! given.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::PathBuf;
+use crate::config::AnalyzerConfig;
+use crate::parser::{AnalysisContext, ParseResult};
+use crate::types::{FileWarnings, Warning};
+use super::changed_lines::ChangedLines;
+use super::false_positives::{FalsePositiveSet, FlaggedWarning};
+use super::fingerprint::Fingerprinter;
+use super::functions::rank_functions;
+use super::generated::GeneratedCodeFilter;
+use super::scope::PathScope;
+use super::severity::SeverityOverrides;
+use super::statistics::warning::WarningStatistics;
+use super::suppression::SuppressionFilter;
+use super::third_party::{is_third_party, partition_third_party};
+use super::unsafe_usage::{GeigerReport, UnsafeMetrics};
+
+/// Filters and metrics applied by [`analyze`].
+#[derive(Debug, Clone, Default)]
+pub struct AnalysisOptions {
+    /// Only analyze warnings within this file or directory
+    pub scope: Option<PathScope>,
+    /// Only analyze warnings on lines changed since a base ref
+    pub changed_lines: Option<ChangedLines>,
+    pub ignore: SuppressionFilter,
+    /// Generated and vendored files left out of the analysis
+    pub generated: GeneratedCodeFilter,
+    pub severity: SeverityOverrides,
+    pub false_positives: FalsePositiveSet,
+    /// Workspace root that warning paths are relative to; without it,
+    /// fingerprints fall back to messages and function rankings and unsafe
+    /// metrics are skipped
+    pub source_root: Option<PathBuf>,
+    pub unsafe_metrics: bool,
+    /// cargo-geiger counts merged into the unsafe metrics
+    pub geiger: Option<GeigerReport>,
+    /// Keep every report of a warning clippy emits once per target
+    pub keep_duplicates: bool,
+    /// Count warnings outside the workspace like the workspace's own
+    pub include_third_party: bool,
+}
+
+impl AnalysisOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Options with the config's ignore rules, severity overrides, inline false positives, and
+    /// unsafe metrics setting. The false positive triage file is not read.
+    pub fn from_config(config: &AnalyzerConfig) -> io::Result<Self> {
+        let mut false_positives = FalsePositiveSet::new();
+        false_positives.extend(&config.false_positive);
+        Ok(Self {
+            ignore: SuppressionFilter::from_specs(&config.ignore)?,
+            generated: GeneratedCodeFilter::new(&config.generated, config.include_generated)?,
+            severity: SeverityOverrides::from_config(&config.severity)?,
+            false_positives,
+            unsafe_metrics: config.unsafe_metrics,
+            keep_duplicates: config.keep_duplicates,
+            include_third_party: config.include_third_party,
+            ..Self::default()
+        })
+    }
+}
+
+/// The analyzed warnings of one run.
+#[derive(Debug)]
+pub struct Analysis {
+    /// Warnings left after scoping, ignore rules, and false positives
+    pub warnings: Vec<Warning>,
+    pub file_warnings: HashMap<String, FileWarnings>,
+    pub context: Vec<AnalysisContext>,
+    /// Repeated reports of one warning dropped, e.g. from the lib and test
+    /// targets of `--all-targets`
+    pub duplicates: usize,
+    /// Warnings in generated or vendored files
+    pub generated: Vec<Warning>,
+    /// Warnings in dependencies and files outside the workspace, left out
+    /// of the totals
+    pub third_party: Vec<Warning>,
+    /// Warnings dropped for being outside the scope
+    pub out_of_scope: usize,
+    /// Warnings of files the change touches, but not on changed lines
+    pub pre_existing: Vec<Warning>,
+    /// Warnings dropped for being in files the change does not touch
+    pub untouched: usize,
+    /// Warnings whose priority a severity override changed
+    pub overridden: usize,
+    /// Warnings matched by an ignore rule
+    pub suppressed: Vec<Warning>,
+    pub false_positives: Vec<FlaggedWarning>,
+    pub stats: WarningStatistics,
+}
+
+/// Fingerprints and deduplicates `parsed`, drops generated code, sets third
+/// party code apart, scopes and filters it, applies severity overrides, then
+/// computes its statistics.
+pub fn analyze(parsed: ParseResult, options: &AnalysisOptions) -> Analysis {
+    let (mut warnings, mut file_warnings, context) = parsed;
+    let input_warnings = warnings.len();
+    let mut fingerprinter = Fingerprinter::new(options.source_root.as_deref());
+    fingerprinter.assign(&mut warnings);
+    for fw in file_warnings.values_mut() {
+        fingerprinter.assign(&mut fw.warnings);
+    }
+    let duplicates = if options.keep_duplicates {
+        0
+    } else {
+        for fw in file_warnings.values_mut() {
+            dedupe(&mut fw.warnings);
+        }
+        dedupe(&mut warnings)
+    };
+    let (warnings, generated) = options.generated.partition(warnings);
+    file_warnings.retain(|file, _| !options.generated.is_generated(file));
+    let root = options.source_root.as_deref();
+    let (mut warnings, third_party) = if options.include_third_party {
+        (warnings, Vec::new())
+    } else {
+        file_warnings.retain(|file, _| !is_third_party(file, root));
+        partition_third_party(warnings, root)
+    };
+    let overridden = options.severity.apply(&mut warnings);
+    options.severity.apply_to_files(&mut file_warnings);
+    let out_of_scope = match &options.scope {
+        Some(scope) => scope.retain(&mut warnings, &mut file_warnings),
+        None => 0,
+    };
+
+    let (warnings, suppressed) = options.ignore.partition(warnings);
+    let (mut warnings, false_positives) = options.false_positives.partition(warnings);
+    if !suppressed.is_empty() || !false_positives.is_empty() {
+        let removed: HashSet<_> = suppressed
+            .iter()
+            .chain(false_positives.iter().map(|f| &f.warning))
+            .map(Warning::site_key)
+            .collect();
+        for fw in file_warnings.values_mut() {
+            fw.warnings.retain(|w| !removed.contains(&w.site_key()));
+        }
+        file_warnings.retain(|_, fw| !fw.warnings.is_empty());
+    }
+    let (pre_existing, untouched) = match &options.changed_lines {
+        Some(changed) => changed.retain(&mut warnings, &mut file_warnings),
+        None => (Vec::new(), 0),
+    };
+
+    let mut stats = WarningStatistics::from_warnings(&warnings, file_warnings.len());
+    stats.total_input_warnings = input_warnings;
+    stats.suppressed_warnings = suppressed.len();
+    stats.false_positives = false_positives.len();
+    stats.duplicates_removed = duplicates;
+    stats.generated_excluded = generated.len();
+    stats.third_party_warnings = third_party.len();
+    if let Some(root) = &options.source_root {
+        stats.by_function = rank_functions(&warnings, root);
+        if options.unsafe_metrics {
+            let mut metrics = UnsafeMetrics::scan(&warnings, root);
+            if let Some(geiger) = &options.geiger {
+                metrics.merge_geiger(geiger);
+            }
+            stats.safety_details.set_unsafe_metrics(metrics);
+        }
+    }
+
+    Analysis {
+        warnings,
+        file_warnings,
+        context,
+        duplicates,
+        generated,
+        third_party,
+        out_of_scope,
+        pre_existing,
+        untouched,
+        overridden,
+        suppressed,
+        false_positives,
+        stats,
+    }
+}
+
+/// Drops all but the first report of each warning, returning how many were
+/// dropped. Warnings match on fingerprint and location, since message
+/// fingerprints do not tell apart sites with the same message.
+fn dedupe(warnings: &mut Vec<Warning>) -> usize {
+    let before = warnings.len();
+    let mut seen = HashSet::new();
+    warnings.retain(|w| {
+        let (_, _, line, column) = w.site_key();
+        seen.insert((w.fingerprint(), line, column))
+    });
+    before - warnings.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::WarningParser;
+
+    #[test]
+    fn test_analyze_in_memory() {
+        let line = |code: &str, file: &str| {
+            serde_json::json!({
+                "reason": "compiler-message",
+                "package_id": "demo 0.1.0 (path+file:///work/demo)",
+                "message": {
+                    "code": { "code": code },
+                    "level": "warning",
+                    "message": "message",
+                    "spans": [{ "file_name": file, "line_start": 1, "line_end": 1, "column_start": 1, "column_end": 2 }],
+                    "children": [],
+                    "rendered": null
+                }
+            })
+            .to_string()
+        };
+        let output = [
+            line("clippy::len_zero", "src/lib.rs"),
+            line("clippy::needless_return", "src/lib.rs"),
+            line("dead_code", "tests/it.rs"),
+            line("clippy::unreadable_literal", "target/debug/build/demo-3f2a9c1e/out/schema.rs"),
+            line("clippy::unwrap_used", "/home/ci/.cargo/registry/src/index.crates.io-6f17d22bba15001f/serde-1.0.210/src/de/mod.rs"),
+            // Reported again by the test target
+            line("clippy::len_zero", "src/lib.rs"),
+        ]
+        .join("\n");
+
+        let parsed = WarningParser::new().parse_str(&output, 1);
+        let mut options = AnalysisOptions::new();
+        options.ignore = SuppressionFilter::from_specs(&["tests/**"]).unwrap();
+        let analysis = analyze(parsed, &options);
+        assert_eq!(analysis.warnings.len(), 2);
+        assert_eq!(analysis.duplicates, 1);
+        assert_eq!(analysis.stats.duplicates_removed, 1);
+        assert_eq!(analysis.stats.generated_excluded, 1);
+        assert_eq!(analysis.stats.third_party_warnings, 1);
+        assert_eq!(analysis.suppressed.len(), 1);
+        assert_eq!(analysis.stats.total_warnings, 2);
+        assert_eq!(analysis.stats.total_input_warnings, 6);
+        assert_eq!(analysis.stats.suppressed_warnings, 1);
+        assert_eq!(analysis.file_warnings.len(), 1);
+
+        let parsed = WarningParser::new().parse_str(&output, 1);
+        options.keep_duplicates = true;
+        assert_eq!(analyze(parsed, &options).warnings.len(), 3);
+    }
+}
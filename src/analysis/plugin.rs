@@ -0,0 +1,131 @@
+//! Pluggable warning sources.
+//!
+//! Clippy is one [`WarningSource`] among others: rustfmt's check mode is
+//! another, and users can register their own (miri, custom lint drivers,
+//! proprietary scanners) on the workflow. Warnings from every source are
+//! merged into one parse result, so they flow through the same statistics,
+//! ignore rules, and reports.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use crate::output::progress::ProgressReporter;
+use crate::parser::{AnalysisContext, ParseResult};
+use crate::types::{Completeness, FileWarnings, Warning};
+
+/// Produces warnings about the analyzed workspace.
+pub trait WarningSource {
+    /// Short name used in logs, e.g. `clippy`.
+    fn name(&self) -> &str;
+
+    /// Collects this source's warnings. Build context and an early stop are
+    /// reported through `ctx`.
+    fn collect(&self, ctx: &mut SourceContext<'_>) -> io::Result<Vec<Warning>>;
+
+    /// Whether a failure of this source fails the run. Other sources are
+    /// skipped with a log message when they fail.
+    fn is_required(&self) -> bool {
+        false
+    }
+}
+
+/// State shared by the sources of one run.
+pub struct SourceContext<'a> {
+    /// Workspace root; warning file paths are relative to it
+    pub root: &'a Path,
+    /// Cargo flags selecting packages, features, and targets
+    pub cargo_args: &'a [String],
+    /// Attach each warning's original diagnostic as `Warning::raw`
+    pub keep_raw: bool,
+    pub progress: &'a mut dyn ProgressReporter,
+    /// Set by a source that was stopped before it finished
+    pub completeness: Completeness,
+    /// Build and build script information, in the order reported
+    pub build_context: Vec<AnalysisContext>,
+}
+
+impl<'a> SourceContext<'a> {
+    pub fn new(root: &'a Path, cargo_args: &'a [String], progress: &'a mut dyn ProgressReporter) -> Self {
+        Self {
+            root,
+            cargo_args,
+            keep_raw: false,
+            progress,
+            completeness: Completeness::Complete,
+            build_context: Vec::new(),
+        }
+    }
+
+    /// Merges the warnings of all sources into one parse result.
+    ///
+    /// Sources that do not know which package a file belongs to, like
+    /// rustfmt, get the package of another warning in the same file.
+    pub fn finish(self, mut warnings: Vec<Warning>) -> (ParseResult, Completeness) {
+        let packages: HashMap<String, String> = warnings
+            .iter()
+            .filter_map(|w| Some((w.file.clone(), w.crate_name.clone()?)))
+            .collect();
+        for warning in warnings.iter_mut().filter(|w| w.crate_name.is_none()) {
+            warning.crate_name = packages.get(&warning.file).cloned();
+        }
+
+        let mut files: HashMap<String, FileWarnings> = HashMap::new();
+        for warning in &warnings {
+            files
+                .entry(warning.file.clone())
+                .or_insert_with(|| FileWarnings::new(warning.file.clone()))
+                .add_warning(warning.clone());
+        }
+        let mut context = self.build_context;
+        context.extend(warnings.iter().cloned().map(AnalysisContext::Warning));
+        ((warnings, files, context), self.completeness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::progress::NoProgress;
+    use crate::types::{Priority, Source};
+
+    struct FixedSource(Vec<Warning>);
+
+    impl WarningSource for FixedSource {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn collect(&self, _ctx: &mut SourceContext<'_>) -> io::Result<Vec<Warning>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn warning(id: &str, crate_name: Option<&str>) -> Warning {
+        Warning {
+            primary_message: "message".to_string(),
+            priority: Priority::Low,
+            crate_name: crate_name.map(str::to_string),
+            source: Source::from_code(id),
+            ..Warning::for_test(id, "src/lib.rs", 1)
+        }
+    }
+
+    #[test]
+    fn test_sources_merge() {
+        let clippy = FixedSource(vec![warning("clippy::len_zero", Some("demo"))]);
+        let rustfmt = FixedSource(vec![warning("rustfmt::formatting", None)]);
+        let mut progress = NoProgress;
+        let mut ctx = SourceContext::new(Path::new("."), &[], &mut progress);
+
+        let mut warnings = Vec::new();
+        for source in [&clippy, &rustfmt] {
+            warnings.extend(source.collect(&mut ctx).unwrap());
+        }
+        let ((warnings, files, context), completeness) = ctx.finish(warnings);
+        assert!(completeness.is_complete());
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[1].crate_name.as_deref(), Some("demo"));
+        assert_eq!(files["src/lib.rs"].warnings.len(), 2);
+        assert_eq!(context.len(), 2);
+    }
+}
@@ -0,0 +1,194 @@
+//! Stability of the worst-file ranking across runs.
+//!
+//! A file at the top of this run's ranking may be a chronic problem or a
+//! one-off spike, e.g. from a large merge. Consecutive runs are compared by
+//! Spearman rank correlation over the files in either run's top N: close to
+//! 1 means the same files stay worst, close to 0 or below means the
+//! ranking reshuffles from run to run. Each current top file is also
+//! classified by how many of the runs it ranked in the top N.
+
+use std::collections::{HashMap, HashSet};
+use crate::analysis::trends::TrendAnalysis;
+
+/// Files compared per run
+pub const RANKING_TOP_FILES: usize = 10;
+
+/// How persistently a file ranks among the worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankPattern {
+    /// In the top N in at least three quarters of the runs
+    Chronic,
+    /// In the top N in several runs, but not most
+    Recurring,
+    /// In the top N in this run only
+    Spike,
+}
+
+impl std::fmt::Display for RankPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RankPattern::Chronic => write!(f, "chronic"),
+            RankPattern::Recurring => write!(f, "recurring"),
+            RankPattern::Spike => write!(f, "spike"),
+        }
+    }
+}
+
+/// A file of the current top N and its ranking history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedFile {
+    pub file: String,
+    /// Rank in the current run, 1 being the worst
+    pub rank: usize,
+    pub warnings: usize,
+    /// Runs in which the file was in the top N
+    pub top_runs: usize,
+    pub pattern: RankPattern,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankingStability {
+    /// Runs with per-file counts that were compared, current included
+    pub runs: usize,
+    /// Rank correlation of each run with the one before, oldest first
+    pub correlations: Vec<f64>,
+    pub files: Vec<RankedFile>,
+}
+
+impl RankingStability {
+    /// Compares the rankings of `historical` and `current`, skipping runs
+    /// recorded without per-file counts. `None` with fewer than two such
+    /// runs.
+    pub fn compute(current: &TrendAnalysis, historical: &[TrendAnalysis], top: usize) -> Option<Self> {
+        let runs: Vec<&HashMap<String, usize>> = historical
+            .iter()
+            .chain(std::iter::once(current))
+            .map(|run| &run.by_file)
+            .filter(|by_file| !by_file.is_empty())
+            .collect();
+        if runs.len() < 2 || current.by_file.is_empty() {
+            return None;
+        }
+
+        let tops: Vec<Vec<&str>> = runs.iter().map(|by_file| top_files(by_file, top)).collect();
+        let correlations = runs
+            .windows(2)
+            .zip(tops.windows(2))
+            .map(|(pair, top_pair)| {
+                let files: Vec<&str> = top_pair[0]
+                    .iter()
+                    .chain(&top_pair[1])
+                    .copied()
+                    .collect::<HashSet<_>>()
+                    .into_iter()
+                    .collect();
+                let counts = |by_file: &HashMap<String, usize>| -> Vec<f64> {
+                    files.iter().map(|file| by_file.get(*file).copied().unwrap_or(0) as f64).collect()
+                };
+                spearman(&counts(pair[0]), &counts(pair[1]))
+            })
+            .collect();
+
+        let current_top = tops.last().cloned().unwrap_or_default();
+        let files = current_top
+            .iter()
+            .enumerate()
+            .map(|(i, file)| {
+                let top_runs = tops.iter().filter(|top| top.contains(file)).count();
+                let pattern = if top_runs * 4 >= runs.len() * 3 {
+                    RankPattern::Chronic
+                } else if top_runs > 1 {
+                    RankPattern::Recurring
+                } else {
+                    RankPattern::Spike
+                };
+                RankedFile {
+                    file: file.to_string(),
+                    rank: i + 1,
+                    warnings: current.by_file.get(*file).copied().unwrap_or(0),
+                    top_runs,
+                    pattern,
+                }
+            })
+            .collect();
+
+        Some(Self { runs: runs.len(), correlations, files })
+    }
+
+    /// Mean rank correlation between consecutive runs.
+    pub fn mean_correlation(&self) -> f64 {
+        if self.correlations.is_empty() {
+            return 0.0;
+        }
+        self.correlations.iter().sum::<f64>() / self.correlations.len() as f64
+    }
+}
+
+/// The `top` files with the most warnings, ties broken by path.
+fn top_files(by_file: &HashMap<String, usize>, top: usize) -> Vec<&str> {
+    let mut files: Vec<(&str, usize)> = by_file.iter().map(|(file, count)| (file.as_str(), *count)).collect();
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    files.into_iter().take(top).map(|(file, _)| file).collect()
+}
+
+/// Ranks of `values`, largest first, ties sharing their average rank.
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[b].total_cmp(&values[a]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        let average = (start + end + 1) as f64 / 2.0;
+        for &index in &order[start..end] {
+            ranks[index] = average;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Spearman's rank correlation: Pearson correlation of the ranks. 1 when
+/// either side has no variation and both rank alike, 0 when only one does.
+fn spearman(a: &[f64], b: &[f64]) -> f64 {
+    let (a, b) = (ranks(a), ranks(b));
+    let n = a.len() as f64;
+    let mean = (n + 1.0) / 2.0;
+    let covariance: f64 = a.iter().zip(&b).map(|(x, y)| (x - mean) * (y - mean)).sum();
+    let spread = |ranks: &[f64]| ranks.iter().map(|x| (x - mean).powi(2)).sum::<f64>().sqrt();
+    let (spread_a, spread_b) = (spread(&a), spread(&b));
+    if spread_a == 0.0 || spread_b == 0.0 {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+    covariance / (spread_a * spread_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranking_stability() {
+        let run = |files: &[(&str, usize)]| TrendAnalysis {
+            by_file: files.iter().map(|(file, count)| (file.to_string(), *count)).collect(),
+            ..TrendAnalysis::default()
+        };
+        let historical = [
+            run(&[("a.rs", 30), ("b.rs", 20), ("c.rs", 10)]),
+            TrendAnalysis::default(),
+            run(&[("a.rs", 28), ("b.rs", 21), ("c.rs", 9)]),
+        ];
+        let current = run(&[("a.rs", 25), ("b.rs", 19), ("d.rs", 40)]);
+
+        let stability = RankingStability::compute(&current, &historical, 3).unwrap();
+        assert_eq!(stability.runs, 3);
+        assert!((stability.correlations[0] - 1.0).abs() < 1e-9);
+        assert!(stability.correlations[1] < 1.0);
+        let patterns: Vec<(&str, RankPattern)> = stability.files.iter().map(|f| (f.file.as_str(), f.pattern)).collect();
+        assert_eq!(patterns, [("d.rs", RankPattern::Spike), ("a.rs", RankPattern::Chronic), ("b.rs", RankPattern::Chronic)]);
+        assert!(RankingStability::compute(&current, &[], 3).is_none());
+    }
+}
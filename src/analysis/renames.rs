@@ -0,0 +1,113 @@
+//! File rename tracking.
+//!
+//! Fingerprints and per-file counts are keyed by path, so a moved file
+//! would look like all of its warnings were fixed and as many new ones
+//! appeared. A [`RenameMap`] holds the renames git detected since an
+//! earlier run (by content similarity, as `git diff -M` does), and the
+//! comparisons with that run look up warnings under their earlier paths.
+
+use std::collections::HashMap;
+
+/// Renames between an earlier commit and the working tree.
+#[derive(Debug, Clone, Default)]
+pub struct RenameMap {
+    /// First path by current path
+    previous: HashMap<String, String>,
+    /// Current path by every earlier path
+    current: HashMap<String, String>,
+}
+
+impl RenameMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A map of `(old, new)` path pairs, oldest rename first. Chained
+    /// renames resolve to the first path.
+    pub fn from_renames(renames: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut previous: HashMap<String, String> = HashMap::new();
+        let mut current: HashMap<String, String> = HashMap::new();
+        for (old, new) in renames {
+            for path in current.values_mut().filter(|path| **path == old) {
+                path.clone_from(&new);
+            }
+            current.remove(&new);
+            current.insert(old.clone(), new.clone());
+            let first = previous.remove(&old).unwrap_or(old);
+            if first != new {
+                previous.insert(new, first);
+            }
+        }
+        current.retain(|earlier, path| earlier != path);
+        Self { previous, current }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.previous.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.previous.len()
+    }
+
+    /// The path `path` had before it was renamed, if it was.
+    pub fn previous_path(&self, path: &str) -> Option<&str> {
+        self.previous.get(path).map(String::as_str)
+    }
+
+    /// The current path of a file known by `path` earlier: the renamed
+    /// path, or `path` itself.
+    pub fn current_path<'a>(&'a self, path: &'a str) -> &'a str {
+        self.current.get(path).map_or(path, String::as_str)
+    }
+
+    /// Every earlier path of the file now at `path`.
+    pub fn earlier_paths(&self, path: &str) -> Vec<&str> {
+        let mut earlier: Vec<&str> = self
+            .current
+            .iter()
+            .filter(|(_, current)| *current == path)
+            .map(|(earlier, _)| earlier.as_str())
+            .collect();
+        earlier.sort_unstable();
+        earlier
+    }
+
+    /// Rekeys counts by earlier path, e.g. per-file counts of an earlier
+    /// run, to current paths, merging counts that land on one path.
+    pub fn follow(&self, counts: &HashMap<String, usize>) -> HashMap<String, usize> {
+        let mut followed = HashMap::new();
+        for (path, count) in counts {
+            *followed.entry(self.current_path(path).to_string()).or_insert(0) += count;
+        }
+        followed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chained_renames() {
+        let renames = RenameMap::from_renames([
+            ("src/util.rs".to_string(), "src/helpers.rs".to_string()),
+            ("src/helpers.rs".to_string(), "src/support/helpers.rs".to_string()),
+            ("src/a.rs".to_string(), "src/b.rs".to_string()),
+            ("src/b.rs".to_string(), "src/a.rs".to_string()),
+        ]);
+        assert_eq!(renames.len(), 1);
+        assert_eq!(renames.previous_path("src/support/helpers.rs"), Some("src/util.rs"));
+        assert_eq!(renames.previous_path("src/helpers.rs"), None);
+        assert_eq!(renames.current_path("src/util.rs"), "src/support/helpers.rs");
+        assert_eq!(renames.current_path("src/helpers.rs"), "src/support/helpers.rs");
+        assert_eq!(renames.current_path("src/b.rs"), "src/a.rs");
+        assert_eq!(renames.current_path("src/lib.rs"), "src/lib.rs");
+        assert_eq!(renames.earlier_paths("src/support/helpers.rs"), ["src/helpers.rs", "src/util.rs"]);
+
+        let counts = HashMap::from([("src/util.rs".to_string(), 3), ("src/lib.rs".to_string(), 1)]);
+        let followed = renames.follow(&counts);
+        assert_eq!(followed.get("src/support/helpers.rs"), Some(&3));
+        assert_eq!(followed.get("src/lib.rs"), Some(&1));
+    }
+}
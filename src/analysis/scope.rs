@@ -0,0 +1,74 @@
+//! Restricting an analysis to one file or directory.
+//!
+//! Clippy always checks whole crates, so `cargo analyzer file <PATH>` scopes
+//! the parsed output instead: warnings outside the path are dropped before
+//! anything is counted, and every report covers only what remains.
+
+use std::collections::HashMap;
+use std::path::Path;
+use crate::types::{FileWarnings, Warning};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathScope {
+    /// Path relative to the workspace root, without `./` or a trailing `/`
+    path: String,
+}
+
+impl PathScope {
+    /// Creates a scope for `path`; absolute paths are made relative to `root`,
+    /// the directory clippy reports paths relative to.
+    pub fn new(path: &Path, root: &Path) -> Self {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let mut path = relative.to_string_lossy().replace('\\', "/");
+        while let Some(rest) = path.strip_prefix("./") {
+            path = rest.to_string();
+        }
+        let path = path.trim_end_matches('/').to_string();
+        Self { path }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn contains(&self, file: &str) -> bool {
+        let file = file.strip_prefix("./").unwrap_or(file);
+        if self.path.is_empty() || self.path == "." {
+            return true;
+        }
+        file == self.path
+            || file.strip_prefix(&self.path).is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// Drops warnings outside the scope, returning how many were dropped.
+    pub fn retain(
+        &self,
+        warnings: &mut Vec<Warning>,
+        file_warnings: &mut HashMap<String, FileWarnings>,
+    ) -> usize {
+        let before = warnings.len();
+        warnings.retain(|w| self.contains(&w.file));
+        file_warnings.retain(|file, _| self.contains(file));
+        before - warnings.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let root = Path::new("/work/project");
+        let dir = PathScope::new(Path::new("./src/parser/"), root);
+        assert_eq!(dir.path(), "src/parser");
+        assert!(dir.contains("src/parser/warning_parser.rs"));
+        assert!(!dir.contains("src/parser_utils.rs"));
+        assert!(!dir.contains("src/main.rs"));
+
+        let file = PathScope::new(Path::new("/work/project/src/main.rs"), root);
+        assert_eq!(file.path(), "src/main.rs");
+        assert!(file.contains("src/main.rs"));
+        assert!(!file.contains("src/main.rs.bak"));
+    }
+}
@@ -0,0 +1,99 @@
+//! Per-lint severity overrides.
+//!
+//! The `[severity]` config table maps lint names to priorities, replacing
+//! the priority the parser derived from the diagnostic:
+//!
+//! ```toml
+//! [severity]
+//! "clippy::unwrap_used" = "critical"
+//! "clippy::module_name_repetitions" = "trivial"
+//! ```
+//!
+//! Overrides are applied before statistics are computed, so totals, gates,
+//! and report ordering all see the overridden priority.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use crate::types::{FileWarnings, Priority, Warning};
+
+#[derive(Debug, Clone, Default)]
+pub struct SeverityOverrides {
+    /// Priorities by lint name, without the `clippy::` prefix
+    priorities: BTreeMap<String, Priority>,
+}
+
+impl SeverityOverrides {
+    /// Parses the `[severity]` table. Lint names match with or without the
+    /// `clippy::` prefix.
+    pub fn from_config(table: &BTreeMap<String, String>) -> io::Result<Self> {
+        let mut priorities = BTreeMap::new();
+        for (lint, priority) in table {
+            let priority = priority.parse().map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidInput, format!("Severity override for {}: {}", lint, e))
+            })?;
+            priorities.insert(strip_tool(lint).to_string(), priority);
+        }
+        Ok(Self { priorities })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.priorities.is_empty()
+    }
+
+    /// The configured priority for the warning's lint.
+    pub fn get(&self, warning: &Warning) -> Option<Priority> {
+        self.priorities.get(strip_tool(&warning.lint_code)).copied()
+    }
+
+    /// Overrides the priority of matching warnings, returning how many
+    /// changed.
+    pub fn apply(&self, warnings: &mut [Warning]) -> usize {
+        let mut changed = 0;
+        for warning in warnings {
+            if let Some(priority) = self.get(warning) {
+                if warning.priority != priority {
+                    warning.priority = priority;
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Applies the overrides to warnings grouped by file.
+    pub fn apply_to_files(&self, file_warnings: &mut HashMap<String, FileWarnings>) {
+        for fw in file_warnings.values_mut() {
+            self.apply(&mut fw.warnings);
+        }
+    }
+}
+
+fn strip_tool(lint: &str) -> &str {
+    lint.strip_prefix("clippy::").unwrap_or(lint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_overrides() {
+        let table = BTreeMap::from([
+            ("clippy::unwrap_used".to_string(), "critical".to_string()),
+            ("dead_code".to_string(), "Trivial".to_string()),
+        ]);
+        let overrides = SeverityOverrides::from_config(&table).unwrap();
+        let warning = |id: &str| Warning {
+            primary_message: "message".to_string(),
+            ..Warning::for_test(id, "src/lib.rs", 1)
+        };
+        let mut warnings = vec![warning("clippy::unwrap_used"), warning("dead_code"), warning("clippy::len_zero")];
+        assert_eq!(overrides.apply(&mut warnings), 2);
+        assert_eq!(warnings[0].priority, Priority::Critical);
+        assert_eq!(warnings[1].priority, Priority::Trivial);
+        assert_eq!(warnings[2].priority, Priority::Medium);
+
+        let invalid = BTreeMap::from([("dead_code".to_string(), "urgent".to_string())]);
+        assert!(SeverityOverrides::from_config(&invalid).is_err());
+    }
+}
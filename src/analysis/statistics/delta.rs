@@ -0,0 +1,139 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use serde::Serialize;
+use crate::types::{CategoryType, Priority};
+use super::warning::WarningStatistics;
+
+/// A count in two runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CountDelta {
+    pub previous: usize,
+    pub current: usize,
+}
+
+impl CountDelta {
+    pub fn new(previous: usize, current: usize) -> Self {
+        Self { previous, current }
+    }
+
+    /// Signed change from the previous run; positive means more warnings.
+    pub fn change(&self) -> i64 {
+        self.current as i64 - self.previous as i64
+    }
+
+    pub fn is_unchanged(&self) -> bool {
+        self.previous == self.current
+    }
+}
+
+/// Differences between two sets of warning statistics, produced by
+/// [`WarningStatistics::diff`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StatsDelta {
+    pub total: CountDelta,
+    pub files_affected: CountDelta,
+    pub by_category: HashMap<CategoryType, CountDelta>,
+    pub by_priority: HashMap<Priority, CountDelta>,
+    pub by_lint: HashMap<String, CountDelta>,
+}
+
+impl StatsDelta {
+    pub fn category(&self, category: &CategoryType) -> CountDelta {
+        self.by_category.get(category).copied().unwrap_or_default()
+    }
+
+    pub fn priority(&self, priority: &Priority) -> CountDelta {
+        self.by_priority.get(priority).copied().unwrap_or_default()
+    }
+
+    /// Lints absent from the previous run, by current count.
+    pub fn new_lints(&self) -> Vec<(&str, usize)> {
+        self.sorted_lints(|delta| delta.previous == 0, |delta| delta.current)
+    }
+
+    /// Lints that no longer occur, by previous count.
+    pub fn fixed_lints(&self) -> Vec<(&str, usize)> {
+        self.sorted_lints(|delta| delta.current == 0, |delta| delta.previous)
+    }
+
+    /// Lints with more warnings than before, by increase.
+    pub fn regressed_lints(&self) -> Vec<(&str, usize)> {
+        self.sorted_lints(|delta| delta.change() > 0, |delta| delta.change() as usize)
+    }
+
+    fn sorted_lints(
+        &self,
+        keep: impl Fn(&CountDelta) -> bool,
+        key: impl Fn(&CountDelta) -> usize,
+    ) -> Vec<(&str, usize)> {
+        let mut lints: Vec<(&str, usize)> = self.by_lint
+            .iter()
+            .filter(|(_, delta)| keep(delta))
+            .map(|(lint, delta)| (lint.as_str(), key(delta)))
+            .collect();
+        lints.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        lints
+    }
+}
+
+impl WarningStatistics {
+    /// Computes the changes from `previous` to these statistics.
+    pub fn diff(&self, previous: &WarningStatistics) -> StatsDelta {
+        StatsDelta {
+            total: CountDelta::new(previous.total_warnings, self.total_warnings),
+            files_affected: CountDelta::new(previous.files_affected, self.files_affected),
+            by_category: diff_counts(&previous.by_category, &self.by_category),
+            by_priority: diff_counts(&previous.by_priority, &self.by_priority),
+            by_lint: diff_counts(&previous.by_lint, &self.by_lint),
+        }
+    }
+}
+
+fn diff_counts<K: Clone + Eq + Hash>(
+    previous: &HashMap<K, usize>,
+    current: &HashMap<K, usize>,
+) -> HashMap<K, CountDelta> {
+    let keys: HashSet<&K> = previous.keys().chain(current.keys()).collect();
+    keys.into_iter()
+        .map(|key| {
+            let delta = CountDelta::new(
+                previous.get(key).copied().unwrap_or(0),
+                current.get(key).copied().unwrap_or(0),
+            );
+            (key.clone(), delta)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Warning;
+
+    fn warning(id: &str, category: CategoryType, file: &str) -> Warning {
+        Warning { category, ..Warning::for_test(id, file, 1) }
+    }
+
+    #[test]
+    fn test_diff() {
+        let previous = WarningStatistics::from_warnings(&[
+            warning("clippy::unwrap_used", CategoryType::Safety, "src/a.rs"),
+            warning("clippy::needless_borrow", CategoryType::Style, "src/a.rs"),
+        ], 1);
+        let current = WarningStatistics::from_warnings(&[
+            warning("clippy::unwrap_used", CategoryType::Safety, "src/a.rs"),
+            warning("clippy::unwrap_used", CategoryType::Safety, "src/b.rs"),
+            warning("clippy::missing_docs", CategoryType::Documentation, "src/b.rs"),
+        ], 2);
+
+        let delta = current.diff(&previous);
+        assert_eq!(delta.total.change(), 1);
+        assert_eq!(delta.category(&CategoryType::Safety), CountDelta::new(1, 2));
+        assert_eq!(delta.category(&CategoryType::Style).change(), -1);
+        assert!(delta.category(&CategoryType::Performance).is_unchanged());
+        assert_eq!(delta.priority(&Priority::Medium).change(), 1);
+        assert_eq!(delta.new_lints(), vec![("clippy::missing_docs", 1)]);
+        assert_eq!(delta.fixed_lints(), vec![("clippy::needless_borrow", 1)]);
+        assert_eq!(delta.regressed_lints(), vec![("clippy::missing_docs", 1), ("clippy::unwrap_used", 1)]);
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::{BTreeMap, HashMap};
+use crate::analysis::module_tree::module_path;
+use crate::types::{CategoryType, Warning};
+
+/// Modules listed in the module breakdown
+pub const MODULE_BREAKDOWN_ROWS: usize = 15;
+
+/// Warnings of one module and everything below it.
+#[derive(Debug, Clone, Default)]
+pub struct DirectoryStatistics {
+    /// Components of the module path, 1 for `src`
+    pub depth: usize,
+    pub warnings: usize,
+    /// Warnings weighted by `Priority::severity_score`
+    pub score: usize,
+    pub by_category: HashMap<CategoryType, usize>,
+}
+
+impl DirectoryStatistics {
+    /// Percentage of the `total` warnings of `category` that fall in this
+    /// module.
+    pub fn category_share(&self, category: CategoryType, total: usize) -> f64 {
+        if total == 0 {
+            0.0
+        } else {
+            *self.by_category.get(&category).unwrap_or(&0) as f64 / total as f64 * 100.0
+        }
+    }
+}
+
+/// Per-module totals, keyed by module path with `/` separators. Modules
+/// follow the rules of the module tree, so both reports agree: deeper
+/// modules count toward their ancestor at `MAX_MODULE_DEPTH`, and
+/// `mod.rs`, `lib.rs`, and `main.rs` toward their directory.
+#[derive(Debug, Clone, Default)]
+pub struct DirectoryTree {
+    pub directories: BTreeMap<String, DirectoryStatistics>,
+}
+
+impl DirectoryTree {
+    pub fn add(&mut self, warning: &Warning) {
+        for (depth, module) in module_prefixes(&warning.file).into_iter().enumerate() {
+            let stats = self.directories.entry(module).or_default();
+            stats.depth = depth + 1;
+            stats.warnings += 1;
+            stats.score += warning.priority.severity_score() as usize;
+            *stats.by_category.entry(warning.category).or_insert(0) += 1;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.directories.is_empty()
+    }
+
+    /// The `limit` modules with the highest severity-weighted score, in
+    /// path order so that submodules follow their parent.
+    pub fn top(&self, limit: usize) -> Vec<(&str, &DirectoryStatistics)> {
+        let mut rows: Vec<(&str, &DirectoryStatistics)> = self.directories
+            .iter()
+            .map(|(dir, stats)| (dir.as_str(), stats))
+            .collect();
+        rows.sort_by(|a, b| b.1.score.cmp(&a.1.score).then_with(|| a.0.cmp(b.0)));
+        rows.truncate(limit);
+        rows.sort_by(|a, b| a.0.cmp(b.0));
+        rows
+    }
+}
+
+/// Modules containing `file`, outermost first, as in [`ModuleTree`]:
+/// `src/network/tcp.rs` gives `src`, `src/network`, and `src/network/tcp`.
+///
+/// [`ModuleTree`]: crate::analysis::module_tree::ModuleTree
+pub fn module_prefixes(file: &str) -> Vec<String> {
+    let mut prefixes: Vec<String> = Vec::new();
+    for component in module_path(file) {
+        let prefix = match prefixes.last() {
+            Some(parent) => format!("{}/{}", parent, component),
+            None => component,
+        };
+        prefixes.push(prefix);
+    }
+    prefixes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    fn warning(file: &str, category: CategoryType, priority: Priority) -> Warning {
+        Warning { category, priority, ..Warning::for_test("clippy::unwrap_used", file, 1) }
+    }
+
+    #[test]
+    fn test_directory_rollup() {
+        assert_eq!(module_prefixes("./src/network/tcp/conn.rs"), ["src", "src/network", "src/network/tcp", "src/network/tcp/conn"]);
+        assert_eq!(module_prefixes("src/network/mod.rs"), ["src", "src/network"]);
+        assert_eq!(module_prefixes("build.rs"), ["build"]);
+
+        let mut tree = DirectoryTree::default();
+        tree.add(&warning("src/network/tcp.rs", CategoryType::Safety, Priority::Critical));
+        tree.add(&warning("src/network/udp.rs", CategoryType::Safety, Priority::Critical));
+        tree.add(&warning("src/lib.rs", CategoryType::Style, Priority::Low));
+
+        let src = &tree.directories["src"];
+        assert_eq!((src.depth, src.warnings, src.score), (1, 3, 12));
+        let network = &tree.directories["src/network"];
+        assert_eq!((network.depth, network.score), (2, 10));
+        assert_eq!(network.category_share(CategoryType::Safety, 4), 50.0);
+        let top: Vec<&str> = tree.top(2).into_iter().map(|(dir, _)| dir).collect();
+        assert_eq!(top, ["src", "src/network"]);
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use crate::types::Warning;
+use crate::analysis::normalize::normalize_message;
 
 #[derive(Debug, Default)]
 pub struct DocStatistics {
@@ -13,21 +14,21 @@ impl DocStatistics {
     pub fn update(&mut self, warning: &Warning) {
         self.total_issues += 1;
         
-        if warning.message.contains("missing") {
+        if warning.primary_message.contains("missing") {
             self.missing_docs
-                .entry(warning.message.clone())
+                .entry(normalize_message(&warning.primary_message))
                 .and_modify(|e| *e += 1)
                 .or_insert(1);
         }
         
-        if warning.message.contains("quality") {
+        if warning.primary_message.contains("quality") {
             self.quality_issues
-                .entry(warning.message.clone())
+                .entry(normalize_message(&warning.primary_message))
                 .and_modify(|e| *e += 1)
                 .or_insert(1);
         }
         
-        if warning.message.contains("link") {
+        if warning.primary_message.contains("link") {
             self.link_issues += 1;
         }
     }
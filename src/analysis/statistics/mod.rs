@@ -3,9 +3,13 @@ pub mod performance;
 pub mod style;
 pub mod documentation;
 pub mod warning;
+pub mod delta;
+pub mod directory;
 
 pub use safety::*;
 pub use performance::*;
 pub use style::*;
 pub use documentation::*;
-pub use warning::*; 
\ No newline at end of file
+pub use warning::*;
+pub use delta::*;
+pub use directory::*; 
\ No newline at end of file
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use crate::types::Warning;
+use crate::analysis::normalize::normalize_message;
 
 #[derive(Debug, Default)]
 pub struct PerformanceStatistics {
@@ -13,23 +14,23 @@ impl PerformanceStatistics {
     pub fn update(&mut self, warning: &Warning) {
         self.total_issues += 1;
         
-        if warning.message.contains("allocation") {
+        if warning.primary_message.contains("allocation") {
             self.allocation_patterns
-                .entry(warning.message.clone())
+                .entry(normalize_message(&warning.primary_message))
                 .and_modify(|e| *e += 1)
                 .or_insert(1);
         }
         
-        if warning.message.contains("clone") {
+        if warning.primary_message.contains("clone") {
             self.clone_patterns
-                .entry(warning.message.clone())
+                .entry(normalize_message(&warning.primary_message))
                 .and_modify(|e| *e += 1)
                 .or_insert(1);
         }
         
-        if warning.message.contains("lock") {
+        if warning.primary_message.contains("lock") {
             self.lock_patterns
-                .entry(warning.message.clone())
+                .entry(normalize_message(&warning.primary_message))
                 .and_modify(|e| *e += 1)
                 .or_insert(1);
         }
@@ -1,5 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use crate::types::Warning;
+use crate::analysis::normalize::normalize_message;
+use crate::analysis::unsafe_usage::{UnsafeMetrics, UnsafeMetricsSource, UnsafeUsage};
 
 #[derive(Debug, Default)]
 pub struct SafetyStatistics {
@@ -22,6 +24,9 @@ pub struct UnsafeStatistics {
     pub raw_pointers: usize,
     pub ffi_calls: usize,
     pub mutable_statics: usize,
+    /// Measured usage per crate, empty unless unsafe metrics were collected
+    pub by_crate: BTreeMap<String, UnsafeUsage>,
+    pub measured_by: Option<UnsafeMetricsSource>,
 }
 
 #[derive(Debug, Default)]
@@ -33,37 +38,51 @@ pub struct ThreadSafetyStatistics {
 }
 
 impl SafetyStatistics {
+    /// Replaces the unsafe counts guessed from warning messages with
+    /// measured ones.
+    pub fn set_unsafe_metrics(&mut self, metrics: UnsafeMetrics) {
+        let totals = metrics.totals();
+        self.unsafe_details = UnsafeStatistics {
+            total_unsafe: totals.total(),
+            raw_pointers: totals.raw_pointers,
+            ffi_calls: totals.ffi_functions,
+            mutable_statics: totals.mutable_statics,
+            by_crate: metrics.by_crate,
+            measured_by: Some(metrics.source),
+        };
+    }
+
     pub fn update(&mut self, warning: &Warning) {
         self.total_issues += 1;
-        match warning.message.split_whitespace().next().unwrap_or("") {
+        match warning.primary_message.split_whitespace().next().unwrap_or("") {
             "Type" => {
                 self.casting_details.total_casts += 1;
                 self.casting_details.by_type
-                    .entry(warning.message.clone())
+                    .entry(normalize_message(&warning.primary_message))
                     .and_modify(|e| *e += 1)
                     .or_insert(1);
             },
             "Unsafe" => {
                 self.unsafe_details.total_unsafe += 1;
-                if warning.message.contains("raw pointer") {
+                if warning.primary_message.contains("raw pointer") {
                     self.unsafe_details.raw_pointers += 1;
                 }
-                if warning.message.contains("FFI") {
+                if warning.primary_message.contains("FFI") {
                     self.unsafe_details.ffi_calls += 1;
                 }
-                if warning.message.contains("static mut") {
+                if warning.primary_message.contains("static mut") {
                     self.unsafe_details.mutable_statics += 1;
                 }
             },
             "Thread" => {
                 self.thread_safety_details.total_issues += 1;
-                if warning.message.contains("Send") || warning.message.contains("Sync") {
+                if warning.primary_message.contains("Send") || warning.primary_message.contains("Sync") {
                     self.thread_safety_details.send_sync_violations += 1;
                 }
-                if warning.message.contains("data race") {
+                if warning.primary_message.contains("data race") {
                     self.thread_safety_details.data_races += 1;
                 }
-                if warning.message.contains("lock") {
+                if warning.primary_message.contains("lock") {
                     self.thread_safety_details.lock_issues += 1;
                 }
             },
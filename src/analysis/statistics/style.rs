@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use crate::types::Warning;
+use crate::analysis::normalize::normalize_message;
 
 #[derive(Debug, Default)]
 pub struct StyleStatistics {
@@ -13,23 +14,23 @@ impl StyleStatistics {
     pub fn update(&mut self, warning: &Warning) {
         self.total_issues += 1;
         
-        if warning.message.contains("naming") {
+        if warning.primary_message.contains("naming") {
             self.naming_issues
-                .entry(warning.message.clone())
+                .entry(normalize_message(&warning.primary_message))
                 .and_modify(|e| *e += 1)
                 .or_insert(1);
         }
         
-        if warning.message.contains("unused") {
+        if warning.primary_message.contains("unused") {
             self.unused_patterns
-                .entry(warning.message.clone())
+                .entry(normalize_message(&warning.primary_message))
                 .and_modify(|e| *e += 1)
                 .or_insert(1);
         }
         
-        if warning.message.contains("complex") {
+        if warning.primary_message.contains("complex") {
             self.complexity_issues
-                .entry(warning.message.clone())
+                .entry(normalize_message(&warning.primary_message))
                 .and_modify(|e| *e += 1)
                 .or_insert(1);
         }
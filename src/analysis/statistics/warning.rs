@@ -1,20 +1,56 @@
 use std::collections::HashMap;
-use crate::types::{Warning, CategoryType, Priority};
+use crate::types::{Warning, CategoryType, Priority, Source};
+use crate::analysis::{normalize::subcategory, functions::FunctionOffender};
 use super::{
     safety::SafetyStatistics,
     performance::PerformanceStatistics,
     style::StyleStatistics,
     documentation::DocStatistics,
+    directory::DirectoryTree,
 };
 
+/// Crate label used for warnings whose package could not be determined
+pub const UNKNOWN_CRATE: &str = "(unknown)";
+
+/// Number of low-confidence lints named in summaries
+pub const LOW_CONFIDENCE_LINTS_SHOWN: usize = 5;
+
 #[derive(Debug, Default)]
 pub struct WarningStatistics {
     pub total_warnings: usize,
+    /// Warnings in the clippy output before deduplication and filtering;
+    /// `from_warnings` counts the warnings it is given
     pub total_input_warnings: usize,
+    pub suppressed_warnings: usize,
+    /// Warnings marked as suspected false positives, excluded from the totals
+    pub false_positives: usize,
+    /// Repeated reports of one warning dropped before counting, e.g. one per
+    /// target with `--all-targets`
+    pub duplicates_removed: usize,
+    /// Warnings in generated or vendored files, excluded before counting
+    pub generated_excluded: usize,
+    /// Warnings in dependencies and files outside the workspace, reported
+    /// separately
+    pub third_party_warnings: usize,
     pub files_affected: usize,
     pub by_priority: HashMap<Priority, usize>,
     pub by_category: HashMap<CategoryType, usize>,
     pub by_subcategory: HashMap<String, usize>,
+    pub by_crate_category: HashMap<String, HashMap<CategoryType, usize>>,
+    pub by_file: HashMap<String, usize>,
+    pub by_lint: HashMap<String, usize>,
+    pub by_source: HashMap<Source, usize>,
+    /// Warnings and severity-weighted scores rolled up per module
+    pub by_directory: DirectoryTree,
+    /// Hard compile errors among the warnings
+    pub errors: usize,
+    /// Warnings categorized with less than `LOW_CONFIDENCE`
+    pub low_confidence: usize,
+    /// Lints of the low-confidence warnings, with their counts
+    pub low_confidence_lints: HashMap<String, usize>,
+    /// Warnings per enclosing function, worst first. Empty unless filled in
+    /// with `rank_functions`, which reads the source files.
+    pub by_function: Vec<FunctionOffender>,
     pub safety_details: SafetyStatistics,
     pub performance_details: PerformanceStatistics,
     pub style_details: StyleStatistics,
@@ -26,10 +62,24 @@ impl WarningStatistics {
         let mut stats = WarningStatistics {
             total_warnings: warnings.len(),
             total_input_warnings: warnings.len(),
+            suppressed_warnings: 0,
+            false_positives: 0,
+            duplicates_removed: 0,
+            generated_excluded: 0,
+            third_party_warnings: 0,
             files_affected: total_files,
             by_category: HashMap::new(),
             by_priority: HashMap::new(),
             by_subcategory: HashMap::new(),
+            by_crate_category: HashMap::new(),
+            by_file: HashMap::new(),
+            by_lint: HashMap::new(),
+            by_source: HashMap::new(),
+            by_directory: DirectoryTree::default(),
+            errors: warnings.iter().filter(|w| w.is_error).count(),
+            low_confidence: 0,
+            low_confidence_lints: HashMap::new(),
+            by_function: Vec::new(),
             safety_details: SafetyStatistics::default(),
             performance_details: PerformanceStatistics::default(),
             style_details: StyleStatistics::default(),
@@ -38,7 +88,7 @@ impl WarningStatistics {
 
         for warning in warnings {
             *stats.by_category
-                .entry(warning.category.clone())
+                .entry(warning.category)
                 .or_insert(0) += 1;
 
             *stats.by_priority
@@ -46,13 +96,52 @@ impl WarningStatistics {
                 .or_insert(0) += 1;
 
             *stats.by_subcategory
-                .entry(warning.message.clone())
+                .entry(subcategory(warning))
+                .or_insert(0) += 1;
+
+            *stats.by_file
+                .entry(warning.file.clone())
+                .or_insert(0) += 1;
+
+            *stats.by_lint
+                .entry(warning.lint_code.clone())
+                .or_insert(0) += 1;
+
+            *stats.by_source
+                .entry(warning.source)
+                .or_insert(0) += 1;
+
+            stats.by_directory.add(warning);
+
+            if warning.is_low_confidence() {
+                stats.low_confidence += 1;
+                *stats.low_confidence_lints
+                    .entry(warning.lint_code.clone())
+                    .or_insert(0) += 1;
+            }
+
+            let crate_name = warning.crate_name.clone().unwrap_or_else(|| UNKNOWN_CRATE.to_string());
+            *stats.by_crate_category
+                .entry(crate_name)
+                .or_default()
+                .entry(warning.category)
                 .or_insert(0) += 1;
         }
 
         stats
     }
 
+    /// Low-confidence lints, most frequent first.
+    pub fn top_low_confidence_lints(&self, limit: usize) -> Vec<(&str, usize)> {
+        let mut lints: Vec<(&str, usize)> = self.low_confidence_lints
+            .iter()
+            .map(|(lint, &count)| (lint.as_str(), count))
+            .collect();
+        lints.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        lints.truncate(limit);
+        lints
+    }
+
     pub fn get_detailed_stats(&self) -> (
         &SafetyStatistics,
         &PerformanceStatistics,
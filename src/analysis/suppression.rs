@@ -0,0 +1,157 @@
+//! Warning suppression via ignore rules.
+//!
+//! Rules come from the `ignore` config key and `--ignore` flags and take one
+//! of these forms:
+//!
+//! - a lint name, e.g. `clippy::module_name_repetitions` or `dead_code`
+//! - a glob on the file path, e.g. `tests/**` or `src/generated/*.rs`
+//! - a category, e.g. `category:documentation`
+//! - a warning fingerprint, e.g. `fingerprint:3f2a9c1e8b7d6054`
+//! - warnings from macros: `macro:external` for macros defined outside the
+//!   workspace, or `macro:<name>` for one macro, e.g. `macro:println`
+
+use std::io;
+use glob::{MatchOptions, Pattern};
+use crate::types::{Warning, CategoryType};
+
+#[derive(Debug, Clone)]
+pub enum IgnoreRule {
+    Lint(String),
+    Path(Pattern),
+    Category(CategoryType),
+    Fingerprint(String),
+    /// Warnings inside expansions of macros defined outside the workspace
+    ExternalMacro,
+    Macro(String),
+}
+
+impl IgnoreRule {
+    pub fn parse(spec: &str) -> io::Result<Self> {
+        let spec = spec.trim();
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+
+        if let Some(fingerprint) = spec.strip_prefix("fingerprint:") {
+            return Ok(IgnoreRule::Fingerprint(fingerprint.trim().to_lowercase()));
+        }
+        if let Some(name) = spec.strip_prefix("macro:") {
+            return match name.trim() {
+                "external" => Ok(IgnoreRule::ExternalMacro),
+                "" => Err(invalid("Missing macro name in 'macro:'".to_string())),
+                name => Ok(IgnoreRule::Macro(name.trim_end_matches('!').to_string())),
+            };
+        }
+        if let Some(category) = spec.strip_prefix("category:") {
+            return category.parse()
+                .map(IgnoreRule::Category)
+                .map_err(invalid);
+        }
+
+        let looks_like_path = spec.contains('/')
+            || spec.contains('*')
+            || spec.contains('?')
+            || spec.ends_with(".rs");
+
+        if looks_like_path && !spec.contains("::") {
+            Pattern::new(spec)
+                .map(IgnoreRule::Path)
+                .map_err(|e| invalid(format!("Invalid ignore glob '{}': {}", spec, e)))
+        } else {
+            Ok(IgnoreRule::Lint(spec.to_string()))
+        }
+    }
+
+    pub fn matches(&self, warning: &Warning) -> bool {
+        match self {
+            IgnoreRule::Lint(name) => strip_tool(name) == strip_tool(&warning.lint_code),
+            IgnoreRule::Path(pattern) => pattern.matches_with(
+                &warning.file,
+                MatchOptions {
+                    case_sensitive: true,
+                    require_literal_separator: true,
+                    require_literal_leading_dot: false,
+                },
+            ),
+            IgnoreRule::Category(category) => warning.category == *category,
+            IgnoreRule::Fingerprint(fingerprint) => warning.matches_fingerprint(fingerprint),
+            IgnoreRule::ExternalMacro => warning.macro_origin.as_ref().is_some_and(|origin| origin.external),
+            IgnoreRule::Macro(name) => warning.macro_origin
+                .as_ref()
+                .is_some_and(|origin| origin.name.trim_end_matches('!') == name),
+        }
+    }
+}
+
+fn strip_tool(lint: &str) -> &str {
+    lint.strip_prefix("clippy::").unwrap_or(lint)
+}
+
+/// A set of ignore rules applied to parsed warnings before statistics are computed.
+#[derive(Debug, Clone, Default)]
+pub struct SuppressionFilter {
+    rules: Vec<IgnoreRule>,
+}
+
+impl SuppressionFilter {
+    pub fn from_specs<S: AsRef<str>>(specs: &[S]) -> io::Result<Self> {
+        let rules = specs.iter()
+            .map(|spec| IgnoreRule::parse(spec.as_ref()))
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn is_suppressed(&self, warning: &Warning) -> bool {
+        self.rules.iter().any(|rule| rule.matches(warning))
+    }
+
+    /// Splits warnings into `(kept, suppressed)`, preserving input order.
+    pub fn partition(&self, warnings: Vec<Warning>) -> (Vec<Warning>, Vec<Warning>) {
+        warnings.into_iter().partition(|w| !self.is_suppressed(w))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MacroOrigin;
+
+    fn warning(id: &str, file: &str, category: CategoryType) -> Warning {
+        Warning { category, ..Warning::for_test(id, file, 1) }
+    }
+
+    #[test]
+    fn test_rule_kinds() {
+        let filter = SuppressionFilter::from_specs(&[
+            "clippy::module_name_repetitions",
+            "tests/**",
+            "category:documentation",
+        ]).unwrap();
+
+        assert!(filter.is_suppressed(&warning("clippy::module_name_repetitions", "src/lib.rs", CategoryType::Style)));
+        assert!(filter.is_suppressed(&warning("clippy::unwrap_used", "tests/it/main.rs", CategoryType::Safety)));
+        assert!(filter.is_suppressed(&warning("clippy::missing_errors_doc", "src/lib.rs", CategoryType::Documentation)));
+        assert!(!filter.is_suppressed(&warning("clippy::unwrap_used", "src/lib.rs", CategoryType::Safety)));
+    }
+
+    #[test]
+    fn test_macro_rules() {
+        let mut from_macro = warning("clippy::unwrap_used", "src/lib.rs", CategoryType::Safety);
+        from_macro.macro_origin = Some(MacroOrigin { name: "serde_json::json!".to_string(), definition: None, external: true });
+        let filter = SuppressionFilter::from_specs(&["macro:external"]).unwrap();
+        assert!(filter.is_suppressed(&from_macro));
+        assert!(!filter.is_suppressed(&warning("clippy::unwrap_used", "src/lib.rs", CategoryType::Safety)));
+
+        from_macro.macro_origin.as_mut().unwrap().external = false;
+        assert!(!filter.is_suppressed(&from_macro));
+        assert!(SuppressionFilter::from_specs(&["macro:serde_json::json!"]).unwrap().is_suppressed(&from_macro));
+        assert!(IgnoreRule::parse("macro:").is_err());
+    }
+
+    #[test]
+    fn test_invalid_category() {
+        assert!(IgnoreRule::parse("category:nonsense").is_err());
+    }
+}
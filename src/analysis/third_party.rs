@@ -0,0 +1,85 @@
+//! Separation of third-party code from the workspace's own.
+//!
+//! Rustc reports files of workspace crates relative to the workspace root,
+//! and files of dependencies by absolute path, usually under
+//! `~/.cargo/registry` or `~/.cargo/git`. Dependency diagnostics appear when
+//! a warning is attributed to a macro's definition or a dependency is built
+//! with lints on, and are nothing the project can fix, so they are set apart
+//! from the workspace's warnings: reported in their own section and left out
+//! of the totals and gates unless `include-third-party` is set.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use crate::parser::is_workspace_file;
+use crate::types::Warning;
+
+/// Label of third-party files that are neither in the registry nor in a git
+/// checkout
+pub const OUTSIDE_WORKSPACE: &str = "(outside the workspace)";
+
+/// Whether `file`, as reported by rustc, is outside the workspace at `root`:
+/// not a workspace file by the parser's rules, nor an absolute path into
+/// `root`, as other tools may report workspace files.
+pub fn is_third_party(file: &str, root: Option<&Path>) -> bool {
+    let file = file.replace('\\', "/");
+    if file.contains("/.cargo/registry/") || file.contains("/.cargo/git/") {
+        return true;
+    }
+    !is_workspace_file(&file) && !root.is_some_and(|root| Path::new(&file).starts_with(root))
+}
+
+/// The package a third-party file belongs to, e.g. `serde-1.0.210` for a
+/// registry file, or [`OUTSIDE_WORKSPACE`].
+pub fn third_party_origin(file: &str) -> String {
+    let file = file.replace('\\', "/");
+    let components: Vec<&str> = file.split('/').collect();
+    // registry/src/<index>/<package>-<version>/..., git/checkouts/<repo>-<hash>/<rev>/...
+    let package = components.windows(4).find_map(|w| match w {
+        ["registry", "src", _, package] => Some(*package),
+        ["git", "checkouts", repository, _] => Some(*repository),
+        _ => None,
+    });
+    package.map_or_else(|| OUTSIDE_WORKSPACE.to_string(), str::to_string)
+}
+
+/// Splits warnings into `(workspace, third_party)`, preserving input order.
+pub fn partition_third_party(warnings: Vec<Warning>, root: Option<&Path>) -> (Vec<Warning>, Vec<Warning>) {
+    warnings.into_iter().partition(|w| !is_third_party(&w.file, root))
+}
+
+/// Third-party warnings counted by origin, then by lint.
+pub fn third_party_breakdown(warnings: &[Warning]) -> BTreeMap<String, BTreeMap<&str, usize>> {
+    let mut breakdown: BTreeMap<String, BTreeMap<&str, usize>> = BTreeMap::new();
+    for warning in warnings {
+        *breakdown
+            .entry(third_party_origin(&warning.file))
+            .or_default()
+            .entry(warning.lint_code.as_str())
+            .or_default() += 1;
+    }
+    breakdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_third_party_files() {
+        let root = Path::new("/work/demo");
+        let registry = "/home/ci/.cargo/registry/src/index.crates.io-6f17d22bba15001f/serde-1.0.210/src/de/mod.rs";
+        let checkout = "/home/ci/.cargo/git/checkouts/tokio-3a1b2c3d4e5f6a7b/8c9d0e1/tokio/src/lib.rs";
+        assert!(is_third_party(registry, Some(root)));
+        assert!(is_third_party(checkout, Some(root)));
+        assert!(is_third_party("/work/shared/src/lib.rs", Some(root)));
+        assert!(is_third_party("../shared/src/lib.rs", Some(root)));
+        assert!(is_third_party("<::core::macros::panic macros>", Some(root)));
+        assert!(!is_third_party("/work/demo/src/lib.rs", Some(root)));
+        assert!(!is_third_party("src/lib.rs", Some(root)));
+        assert!(is_third_party("/work/demo/src/lib.rs", None));
+
+        assert_eq!(third_party_origin(registry), "serde-1.0.210");
+        assert_eq!(third_party_origin(checkout), "tokio-3a1b2c3d4e5f6a7b");
+        assert_eq!(third_party_origin("/work/shared/src/lib.rs"), OUTSIDE_WORKSPACE);
+    }
+}
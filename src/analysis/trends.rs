@@ -1,6 +1,13 @@
 use std::collections::HashMap;
-use crate::types::{CategoryType, Priority};
+use crate::types::{CategoryType, Priority, Warning};
+use super::fingerprint::fingerprint_churn;
+use super::normalize::normalize_message;
+use super::statistics::{delta::StatsDelta, warning::WarningStatistics};
 use serde::{Serialize, Deserialize};
+
+/// Recurring issues listed in the trend section
+pub const MAX_RECURRING_ISSUES: usize = 10;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrendAnalysis {
     pub dates: Vec<String>,
@@ -8,7 +15,26 @@ pub struct TrendAnalysis {
     pub by_category: HashMap<CategoryType, usize>,
     pub by_priority: HashMap<Priority, usize>,
     pub improvement_rate: f64,
+    /// Improvement of the severity-weighted warning load (see
+    /// [`TrendAnalysis::weighted_total`])
+    #[serde(default)]
+    pub weighted_improvement_rate: f64,
+    /// Improvement rate of each category seen in the historical runs
+    #[serde(default)]
+    pub category_improvement: HashMap<CategoryType, f64>,
+    /// Warnings by recurrence key (see [`recurrence_key`])
     pub recurring_issues: HashMap<String, usize>,
+    /// Warnings whose fingerprint the previous run did not have; `None`
+    /// when that run was recorded without fingerprints
+    #[serde(default)]
+    pub new_warnings: Option<usize>,
+    /// Warnings of the previous run whose fingerprint is gone
+    #[serde(default)]
+    pub fixed_warnings: Option<usize>,
+    /// Warnings by file, for ranking stability (see
+    /// [`RankingStability`](super::ranking::RankingStability))
+    #[serde(default)]
+    pub by_file: HashMap<String, usize>,
 }
 
 impl Default for TrendAnalysis {
@@ -19,7 +45,12 @@ impl Default for TrendAnalysis {
             by_category: HashMap::new(),
             by_priority: HashMap::new(),
             improvement_rate: 0.0,
+            weighted_improvement_rate: 0.0,
+            category_improvement: HashMap::new(),
             recurring_issues: HashMap::new(),
+            new_warnings: None,
+            fixed_warnings: None,
+            by_file: HashMap::new(),
         }
     }
 }
@@ -37,10 +68,23 @@ impl TrendAnalysis {
             by_category,
             by_priority,
             improvement_rate: 0.0, // This would need historical data to calculate
+            weighted_improvement_rate: 0.0,
+            category_improvement: HashMap::new(),
             recurring_issues,
+            new_warnings: None,
+            fixed_warnings: None,
+            by_file: HashMap::new(),
         }
     }
 
+    /// Counts the warnings new and fixed since the previous run, from the
+    /// fingerprint counts of both runs.
+    pub fn set_fingerprint_churn(&mut self, previous: &HashMap<String, usize>, current: &HashMap<String, usize>) {
+        let (new, fixed) = fingerprint_churn(previous, current);
+        self.new_warnings = Some(new);
+        self.fixed_warnings = Some(fixed);
+    }
+
     pub fn calculate_improvement_rate(&mut self, historical_warnings: &[usize]) -> f64 {
         if historical_warnings.is_empty() {
             return 0.0;
@@ -58,6 +102,41 @@ impl TrendAnalysis {
         improvement
     }
 
+    /// Warnings weighted by their priority's severity score, so one Critical
+    /// warning counts as much as five Trivial ones.
+    pub fn weighted_total(&self) -> usize {
+        self.by_priority
+            .iter()
+            .map(|(priority, count)| count * priority.severity_score() as usize)
+            .sum()
+    }
+
+    /// Computes the severity-weighted and per-category improvement rates
+    /// against the average of `historical`. Unlike
+    /// [`calculate_improvement_rate`](Self::calculate_improvement_rate), fixing
+    /// style nits while Critical issues grow shows up as a regression.
+    pub fn calculate_weighted_improvement(&mut self, historical: &[TrendAnalysis]) -> f64 {
+        self.weighted_improvement_rate =
+            improvement(historical.iter().map(TrendAnalysis::weighted_total), self.weighted_total());
+
+        self.category_improvement.clear();
+        let categories: std::collections::HashSet<CategoryType> =
+            historical.iter().flat_map(|t| t.by_category.keys().copied()).collect();
+        for category in categories {
+            let count = |t: &TrendAnalysis| t.by_category.get(&category).copied().unwrap_or(0);
+            let past: Vec<usize> = historical.iter().map(count).collect();
+            if past.iter().any(|&c| c > 0) {
+                self.category_improvement.insert(category, improvement(past.into_iter(), count(self)));
+            }
+        }
+        self.weighted_improvement_rate
+    }
+
+    /// Computes the changes from `previous` to this run.
+    pub fn diff(&self, previous: &TrendAnalysis) -> StatsDelta {
+        WarningStatistics::from(self).diff(&WarningStatistics::from(previous))
+    }
+
     pub fn get_top_issues(&self, limit: usize) -> Vec<(&String, &usize)> {
         let mut issues: Vec<(&String, &usize)> = self.recurring_issues.iter().collect();
         issues.sort_by(|a, b| b.1.cmp(a.1));
@@ -82,6 +161,88 @@ impl TrendAnalysis {
     }
 }
 
+/// Relative drop of `current` below the average of `past`; negative when it grew.
+fn improvement(past: impl Iterator<Item = usize>, current: usize) -> f64 {
+    let (sum, runs) = past.fold((0, 0), |(sum, runs), value| (sum + value, runs + 1));
+    if runs == 0 || sum == 0 {
+        return 0.0;
+    }
+    let average = sum as f64 / runs as f64;
+    (average - current as f64) / average
+}
+
+/// Identifies the same issue across runs: the lint and its message with
+/// identifiers, literals, and paths replaced by placeholders, e.g.
+/// ``clippy::needless_return: unneeded `return` statement``.
+pub fn recurrence_key(warning: &Warning) -> String {
+    format!("{}: {}", warning.lint_code, normalize_message(&warning.primary_message))
+}
+
+/// Counts warnings by recurrence key.
+pub fn recurring_issues(warnings: &[Warning]) -> HashMap<String, usize> {
+    let mut issues = HashMap::new();
+    for warning in warnings {
+        *issues.entry(recurrence_key(warning)).or_insert(0) += 1;
+    }
+    issues
+}
+
+/// The lint of a recurrence key. Trends recorded before keys included the
+/// message are keyed by lint alone.
+pub fn recurrence_lint(key: &str) -> &str {
+    key.split_once(": ").map_or(key, |(lint, _)| lint)
+}
+
+/// An issue seen across several runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurringIssue {
+    pub key: String,
+    /// Runs the issue appeared in
+    pub runs: usize,
+    /// Occurrences summed over those runs
+    pub occurrences: usize,
+}
+
+/// The issues present in the most runs of `historical` and `current`, ties
+/// broken by total occurrences.
+pub fn top_recurring(current: &TrendAnalysis, historical: &[TrendAnalysis], limit: usize) -> Vec<RecurringIssue> {
+    let mut issues: HashMap<&str, RecurringIssue> = HashMap::new();
+    for run in historical.iter().chain(std::iter::once(current)) {
+        for (key, count) in &run.recurring_issues {
+            let issue = issues.entry(key).or_insert_with(|| RecurringIssue { key: key.clone(), runs: 0, occurrences: 0 });
+            issue.runs += 1;
+            issue.occurrences += count;
+        }
+    }
+    let mut issues: Vec<RecurringIssue> = issues.into_values().collect();
+    issues.sort_by(|a, b| {
+        b.runs.cmp(&a.runs)
+            .then(b.occurrences.cmp(&a.occurrences))
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    issues.truncate(limit);
+    issues
+}
+
+impl From<&TrendAnalysis> for WarningStatistics {
+    /// Statistics with the counts a trend records; `by_lint` is summed from
+    /// the recurring issues.
+    fn from(trend: &TrendAnalysis) -> Self {
+        let mut by_lint = HashMap::new();
+        for (key, count) in &trend.recurring_issues {
+            *by_lint.entry(recurrence_lint(key).to_string()).or_insert(0) += count;
+        }
+        WarningStatistics {
+            total_warnings: trend.total_warnings,
+            total_input_warnings: trend.total_warnings,
+            by_category: trend.by_category.clone(),
+            by_priority: trend.by_priority.clone(),
+            by_lint,
+            ..Default::default()
+        }
+    }
+}
+
 pub fn analyze_trends(
     current: &TrendAnalysis,
     historical: &[TrendAnalysis]
@@ -102,6 +263,13 @@ pub fn analyze_trends(
         }
     }
 
+    if current.improvement_rate > 0.0 && current.weighted_improvement_rate < 0.0 {
+        insights.push(format!(
+            "Fewer warnings overall, but the severity-weighted load grew by {:.1}%",
+            -current.weighted_improvement_rate * 100.0
+        ));
+    }
+
     // Analyze category trends
     let category_dist = current.get_category_distribution();
     for (category, percentage) in category_dist {
@@ -135,3 +303,52 @@ pub fn analyze_trends(
 
     insights
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_improvement_flags_critical_growth() {
+        let trend = |style: usize, critical: usize| {
+            TrendAnalysis::new(
+                style + critical,
+                HashMap::from([(CategoryType::Style, style), (CategoryType::Safety, critical)]),
+                HashMap::from([(Priority::Trivial, style), (Priority::Critical, critical)]),
+                HashMap::new(),
+            )
+        };
+        let previous = vec![trend(20, 1)];
+        let mut current = trend(10, 4);
+        current.calculate_improvement_rate(&[21]);
+        current.calculate_weighted_improvement(&previous);
+
+        assert!(current.improvement_rate > 0.0);
+        // 20 + 5 = 25 before, 10 + 20 = 30 now
+        assert!((current.weighted_improvement_rate - -0.2).abs() < 1e-9);
+        assert_eq!(current.category_improvement[&CategoryType::Style], 0.5);
+        assert_eq!(current.category_improvement[&CategoryType::Safety], -3.0);
+        assert!(analyze_trends(&current, &previous).iter().any(|i| i.contains("severity-weighted load grew by 20.0%")));
+    }
+
+    #[test]
+    fn test_recurrence_keys_match_across_runs() {
+        let warning = |message: &str, file: &str| Warning {
+            primary_message: message.to_string(),
+            priority: Priority::Low,
+            ..Warning::for_test("clippy::needless_return", file, 3)
+        };
+        let run = |warnings: &[Warning]| {
+            TrendAnalysis::new(warnings.len(), HashMap::new(), HashMap::new(), recurring_issues(warnings))
+        };
+        let first = run(&[warning("unneeded `return` statement", "src/a.rs")]);
+        let second = run(&[warning("unneeded `return` statement", "src/b.rs")]);
+        assert_eq!(first.recurring_issues, second.recurring_issues);
+
+        let top = top_recurring(&second, &[first], MAX_RECURRING_ISSUES);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].runs, 2);
+        assert_eq!(recurrence_lint(&top[0].key), "clippy::needless_return");
+        assert_eq!(WarningStatistics::from(&second).by_lint["clippy::needless_return"], 1);
+    }
+}
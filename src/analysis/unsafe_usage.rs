@@ -0,0 +1,240 @@
+//! Unsafe code metrics.
+//!
+//! The source files diagnostics point into are scanned for unsafe blocks,
+//! functions, and impls, raw pointer types, foreign functions, and mutable
+//! statics. When cargo-geiger is available its counts of unsafe blocks,
+//! functions, and impls replace the scanned ones, since geiger covers every
+//! file of a crate and its dependencies.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use serde::Deserialize;
+use crate::types::Warning;
+use super::functions::{Token, tokenize};
+use super::statistics::warning::UNKNOWN_CRATE;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsafeMetricsSource {
+    /// Source files referenced by diagnostics
+    SourceScan,
+    /// cargo-geiger, with pointer, FFI, and static counts from the scan
+    Geiger,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnsafeUsage {
+    pub unsafe_blocks: usize,
+    pub unsafe_functions: usize,
+    pub unsafe_impls: usize,
+    /// `*const T` and `*mut T` types
+    pub raw_pointers: usize,
+    /// Functions declared in `extern` blocks
+    pub ffi_functions: usize,
+    pub mutable_statics: usize,
+}
+
+impl UnsafeUsage {
+    /// Unsafe blocks, functions, and impls.
+    pub fn total(&self) -> usize {
+        self.unsafe_blocks + self.unsafe_functions + self.unsafe_impls
+    }
+
+    fn add(&mut self, other: &UnsafeUsage) {
+        self.unsafe_blocks += other.unsafe_blocks;
+        self.unsafe_functions += other.unsafe_functions;
+        self.unsafe_impls += other.unsafe_impls;
+        self.raw_pointers += other.raw_pointers;
+        self.ffi_functions += other.ffi_functions;
+        self.mutable_statics += other.mutable_statics;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnsafeMetrics {
+    pub source: UnsafeMetricsSource,
+    pub by_crate: BTreeMap<String, UnsafeUsage>,
+}
+
+impl UnsafeMetrics {
+    /// Scans the files `warnings` point into, read relative to `root`, and
+    /// attributes them to the crate of their warnings. Unreadable files are
+    /// skipped.
+    pub fn scan(warnings: &[Warning], root: &Path) -> Self {
+        let files: BTreeSet<(&str, &str)> = warnings
+            .iter()
+            .map(|w| (w.crate_name.as_deref().unwrap_or(UNKNOWN_CRATE), w.file.as_str()))
+            .collect();
+
+        let mut by_crate: BTreeMap<String, UnsafeUsage> = BTreeMap::new();
+        for (crate_name, file) in files {
+            if let Ok(source) = std::fs::read_to_string(root.join(file)) {
+                by_crate.entry(crate_name.to_string()).or_default().add(&scan_source(&source));
+            }
+        }
+        Self { source: UnsafeMetricsSource::SourceScan, by_crate }
+    }
+
+    /// Replaces the unsafe block, function, and impl counts with those of a
+    /// cargo-geiger report, adding the crates only geiger saw.
+    pub fn merge_geiger(&mut self, geiger: &GeigerReport) {
+        for (name, usage) in &geiger.by_crate {
+            let entry = self.by_crate.entry(name.clone()).or_default();
+            entry.unsafe_blocks = usage.unsafe_blocks;
+            entry.unsafe_functions = usage.unsafe_functions;
+            entry.unsafe_impls = usage.unsafe_impls;
+        }
+        self.source = UnsafeMetricsSource::Geiger;
+    }
+
+    pub fn totals(&self) -> UnsafeUsage {
+        let mut totals = UnsafeUsage::default();
+        for usage in self.by_crate.values() {
+            totals.add(usage);
+        }
+        totals
+    }
+}
+
+/// Counts unsafe code in Rust source.
+pub fn scan_source(source: &str) -> UnsafeUsage {
+    let tokens: Vec<Token> = tokenize(source).into_iter().map(|(token, _)| token).collect();
+    let mut usage = UnsafeUsage::default();
+    // Brace depth, and the depth inside an `extern` block if in one
+    let mut depth = 0usize;
+    let mut extern_block: Option<usize> = None;
+
+    for (i, token) in tokens.iter().enumerate() {
+        let next = tokens.get(i + 1);
+        let is_ident = |token: Option<&Token>, name: &str| matches!(token, Some(Token::Ident(ident)) if ident == name);
+        match token {
+            Token::Punct('{') => depth += 1,
+            Token::Punct('}') => {
+                depth = depth.saturating_sub(1);
+                if extern_block.is_some_and(|block| depth < block) {
+                    extern_block = None;
+                }
+            }
+            Token::Punct('*') if is_ident(next, "const") || is_ident(next, "mut") => usage.raw_pointers += 1,
+            Token::Ident(ident) => match ident.as_str() {
+                "unsafe" => match next {
+                    Some(Token::Punct('{')) => usage.unsafe_blocks += 1,
+                    Some(Token::Ident(next)) if next == "fn" => usage.unsafe_functions += 1,
+                    Some(Token::Ident(next)) if next == "impl" => usage.unsafe_impls += 1,
+                    _ => {}
+                },
+                // The ABI string is skipped by the tokenizer
+                "extern" if matches!(next, Some(Token::Punct('{'))) => extern_block = Some(depth + 1),
+                "fn" if extern_block == Some(depth) => usage.ffi_functions += 1,
+                "static" if is_ident(next, "mut") => usage.mutable_statics += 1,
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    usage
+}
+
+/// Per-package unsafe counts from `cargo geiger --output-format Json`.
+#[derive(Debug, Clone, Default)]
+pub struct GeigerReport {
+    pub by_crate: BTreeMap<String, UnsafeUsage>,
+}
+
+#[derive(Deserialize)]
+struct GeigerOutput {
+    packages: Vec<GeigerPackage>,
+}
+
+#[derive(Deserialize)]
+struct GeigerPackage {
+    package: GeigerPackageInfo,
+    unsafety: GeigerUnsafety,
+}
+
+#[derive(Deserialize)]
+struct GeigerPackageInfo {
+    id: GeigerPackageId,
+}
+
+#[derive(Deserialize)]
+struct GeigerPackageId {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GeigerUnsafety {
+    used: GeigerCounters,
+}
+
+#[derive(Deserialize)]
+struct GeigerCounters {
+    functions: GeigerCount,
+    exprs: GeigerCount,
+    item_impls: GeigerCount,
+    item_traits: GeigerCount,
+    methods: GeigerCount,
+}
+
+#[derive(Deserialize)]
+struct GeigerCount {
+    unsafe_: usize,
+}
+
+impl GeigerReport {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let output: GeigerOutput = serde_json::from_str(json)?;
+        let by_crate = output
+            .packages
+            .into_iter()
+            .map(|package| {
+                let used = package.unsafety.used;
+                let usage = UnsafeUsage {
+                    unsafe_blocks: used.exprs.unsafe_,
+                    unsafe_functions: used.functions.unsafe_ + used.methods.unsafe_,
+                    unsafe_impls: used.item_impls.unsafe_ + used.item_traits.unsafe_,
+                    ..UnsafeUsage::default()
+                };
+                (package.package.id.name, usage)
+            })
+            .collect();
+        Ok(Self { by_crate })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_source() {
+        let source = r#"
+static mut COUNTER: u32 = 0;
+static NAME: &'static str = "unsafe { not code }";
+
+extern "C" {
+    fn abs(input: i32) -> i32;
+    fn strlen(s: *const u8) -> usize;
+}
+
+unsafe impl Send for Handle {}
+
+pub unsafe fn bump(ptr: *mut u32) {
+    // unsafe { in a comment }
+    unsafe { *ptr += 1 };
+}
+
+fn longest<'a>(a: &'a str) -> &'a str {
+    let _n = unsafe { abs(-1) };
+    a
+}
+"#;
+        assert_eq!(scan_source(source), UnsafeUsage {
+            unsafe_blocks: 2,
+            unsafe_functions: 1,
+            unsafe_impls: 1,
+            raw_pointers: 2,
+            ffi_functions: 2,
+            mutable_statics: 1,
+        });
+    }
+}
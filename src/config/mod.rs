@@ -0,0 +1,225 @@
+//! Analyzer configuration.
+//!
+//! Settings are read from `cargo-analyzer.toml` in the working directory (or
+//! the file passed via `--config`) and merged with command line flags by the
+//! workflow before analysis starts.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use crate::{
+    analysis::{alerts::AlertRule, false_positives::FalsePositive, gate::GateConfig, hotspots::HotspotConfig},
+    fixes::{apply::DEFAULT_DECISIONS_FILE, post_process::SuggestionConfig, registry::FixTemplateConfig},
+    output::{bundle::Artifact, csv_export::CsvColumn, diff::DiffLayout, formats::ReportFormat, html::HtmlTheme, notification::WebhookConfig, pr_summary::PrCommentConfig, template::TemplateConfig},
+    types::RiskLevel,
+};
+
+/// Name of the configuration file looked up in the working directory.
+pub const DEFAULT_CONFIG_FILE: &str = "cargo-analyzer.toml";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct AnalyzerConfig {
+    /// Ignore rules: lint names, path globs, `category:<name>`, `macro:external`, or `macro:<name>`
+    pub ignore: Vec<String>,
+    /// Priorities by lint name, replacing the ones derived from diagnostics
+    pub severity: BTreeMap<String, String>,
+    /// List suppressed warnings in an appendix of the detailed report
+    pub list_suppressed: bool,
+    /// Globs of generated or vendored files excluded from the analysis, in
+    /// addition to the built-in ones (see `analysis::generated`)
+    pub generated: Vec<String>,
+    /// Analyze build script output, `target/`, `*.pb.rs`, and `vendor/`
+    /// like hand-written code
+    pub include_generated: bool,
+    /// Count warnings in dependencies and files outside the workspace in
+    /// the totals and gates instead of reporting them separately
+    pub include_third_party: bool,
+    /// Suspected false positives by fingerprint, left out of totals and gates
+    pub false_positive: Vec<FalsePositive>,
+    /// Triage file of `<fingerprint> [reason]` lines marking false positives
+    pub false_positives_file: Option<PathBuf>,
+    /// Write the suspected false positives as a report to file upstream
+    pub false_positive_report: bool,
+    /// Keep the repeated reports of a warning clippy emits for several
+    /// targets instead of counting it once
+    pub keep_duplicates: bool,
+    /// Gate thresholds evaluated after each run
+    pub gate: GateConfig,
+    /// Rate-of-change alerts evaluated against history, e.g.
+    /// `"clippy::unwrap_used" increases by > 5 per week`
+    pub alert_if: Vec<AlertRule>,
+    /// Webhooks notified of regressions, as `[[notify]]` tables (see
+    /// `output::notification`)
+    pub notify: Vec<WebhookConfig>,
+    /// Write a merge queue check status payload
+    pub merge_queue_status: bool,
+    /// Write a fix playbook per category
+    pub playbooks: bool,
+    /// Write per-crate safety findings as supply-chain audit notes
+    pub audit_notes: bool,
+    /// Write warnings as compact protobuf (see `proto/warnings.proto`)
+    pub binary_export: bool,
+    /// Also write warnings as JSONL (see `schema/warning.schema.json`)
+    pub jsonl: bool,
+    /// Write an appendix of every diagnostic as clippy rendered it
+    pub rendered_appendix: bool,
+    /// Theme, accent color, logo, and header text of the HTML report
+    pub html: HtmlTheme,
+    /// Handlebars templates replacing the layout of the markdown and HTML
+    /// reports (requires the `templates` feature)
+    pub templates: TemplateConfig,
+    /// Write an SVG badge and a shields.io endpoint JSON file with the
+    /// warning count
+    pub badge: bool,
+    /// Columns of the CSV export, in order (see `output::csv_export`)
+    pub csv_columns: Vec<CsvColumn>,
+    /// Additional report formats (see `output::formats`)
+    pub formats: Vec<ReportFormat>,
+    /// Size limits of the pull request comment body
+    pub pr_comment: PrCommentConfig,
+    /// Named artifact lists selectable with `--bundle` (see
+    /// `output::bundle`)
+    pub bundles: BTreeMap<String, Vec<Artifact>>,
+    /// Artifacts to write; every core artifact and the enabled optional
+    /// ones when unset
+    pub artifacts: Option<Vec<Artifact>>,
+    /// Include each warning's original JSON diagnostic in the reports
+    pub embed_raw: bool,
+    /// Preview matching fix examples in the terminal with this diff layout
+    pub preview_fixes: Option<DiffLayout>,
+    /// Where terminal warning locations link to: `file`, `vscode`, `idea`,
+    /// `none`, or a URL template (see `output::hyperlink`)
+    pub editor_link: Option<String>,
+    /// Show the code of the top warnings in the terminal
+    pub snippets: bool,
+    /// File watch mode rewrites after each run with every warning in
+    /// quickfix format, relative to the workspace root
+    pub problems_file: Option<PathBuf>,
+    /// Context lines quoted around each warning in the reports (default 2)
+    pub snippet_context: Option<usize>,
+    /// Look for unused dependencies with `cargo +nightly udeps`
+    pub udeps: bool,
+    /// Report code rustfmt would reformat as Style warnings
+    pub fmt_check: bool,
+    /// Report the future incompatibilities of dependencies from
+    /// `cargo report future-incompatibilities`
+    pub future_incompat: bool,
+    /// External tools reporting warnings as line JSON, by name and command
+    pub sources: BTreeMap<String, String>,
+    /// Measure unsafe code, with cargo-geiger when it is installed
+    pub unsafe_metrics: bool,
+    /// Annotate performance warnings with the binary size (cargo-bloat) and
+    /// compile time (nightly build timings) of their code
+    pub performance_impact: bool,
+    /// Report `#[ignore]`d tests and items behind features the analyzed
+    /// feature set leaves off
+    pub hidden_code: bool,
+    /// Branch whose stored analysis this run is compared against
+    pub auto_baseline: Option<String>,
+    /// Ref whose merge base the reports are restricted to the changed lines
+    /// of (see `analysis::changed_lines`)
+    pub base: Option<String>,
+    /// Keep every warning of the files changed since `base` instead of only
+    /// those on changed lines
+    pub changed_only: bool,
+    /// Directory storing each analyzed commit's warnings for baseline
+    /// comparisons, e.g. a cached CI artifact
+    pub baseline_store: Option<PathBuf>,
+    /// SQLite database recording every run (requires the `sqlite` feature)
+    pub history_db: Option<PathBuf>,
+    /// Fix plan risk levels; priorities not listed keep their defaults
+    pub risk_levels: Vec<RiskLevel>,
+    /// Weights and git history window for the fix plan's hotspot ranking
+    pub hotspots: HotspotConfig,
+    /// Built-in post-processors applied to fix suggestions
+    pub suggestions: SuggestionConfig,
+    /// User fix examples by lint, shown instead of the built-in ones
+    pub fix_templates: FixTemplateConfig,
+    /// File recording `fix` review decisions; suppressed warnings are ignored
+    pub fix_decisions: Option<PathBuf>,
+}
+
+impl AnalyzerConfig {
+    /// Loads configuration from the given TOML file.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        toml::from_str(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid config {}: {}", path.display(), e),
+            )
+        })
+    }
+
+    /// Selects the artifacts of bundle `name`.
+    pub fn select_bundle(&mut self, name: &str) -> io::Result<()> {
+        let Some(artifacts) = self.bundles.get(name) else {
+            let known: Vec<&str> = self.bundles.keys().map(String::as_str).collect();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Unknown bundle `{}`; configured bundles: {}",
+                    name,
+                    if known.is_empty() { "none".to_string() } else { known.join(", ") }
+                ),
+            ));
+        };
+        self.artifacts = Some(artifacts.clone());
+        Ok(())
+    }
+
+    /// Enables the optional artifacts of the selection, if any.
+    pub fn enable_selected_artifacts(&mut self) {
+        for artifact in self.artifacts.clone().unwrap_or_default() {
+            match artifact {
+                Artifact::Jsonl => self.jsonl = true,
+                Artifact::RenderedDiagnostics => self.rendered_appendix = true,
+                Artifact::Protobuf => self.binary_export = true,
+                Artifact::MergeStatus => self.merge_queue_status = true,
+                Artifact::FalsePositives => self.false_positive_report = true,
+                Artifact::AuditNotes => self.audit_notes = true,
+                Artifact::Playbooks => self.playbooks = true,
+                Artifact::Badge => self.badge = true,
+                Artifact::Prometheus if !self.formats.contains(&ReportFormat::Prometheus) => {
+                    self.formats.push(ReportFormat::Prometheus)
+                }
+                Artifact::PrComment if !self.formats.contains(&ReportFormat::PrComment) => {
+                    self.formats.push(ReportFormat::PrComment)
+                }
+                Artifact::Sarif if !self.formats.contains(&ReportFormat::Sarif) => {
+                    self.formats.push(ReportFormat::Sarif)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether `artifact` is written, when enabled: always without a
+    /// selection.
+    pub fn writes(&self, artifact: Artifact) -> bool {
+        self.artifacts.as_ref().is_none_or(|artifacts| artifacts.contains(&artifact))
+    }
+
+    /// Where `fix` review decisions are recorded.
+    pub fn fix_decisions_path(&self) -> PathBuf {
+        self.fix_decisions.clone().unwrap_or_else(|| PathBuf::from(DEFAULT_DECISIONS_FILE))
+    }
+
+    /// Loads the explicitly requested config file, or `cargo-analyzer.toml`
+    /// if present, falling back to defaults otherwise.
+    pub fn load_or_default(path: Option<&Path>) -> io::Result<Self> {
+        match path {
+            Some(path) => Self::load(path),
+            None => {
+                let default_path = Path::new(DEFAULT_CONFIG_FILE);
+                if default_path.exists() {
+                    Self::load(default_path)
+                } else {
+                    Ok(Self::default())
+                }
+            }
+        }
+    }
+}
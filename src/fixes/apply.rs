@@ -0,0 +1,312 @@
+//! Applying clippy's suggested replacements to source files.
+//!
+//! Replacements are read from a warning's raw diagnostic (see
+//! [`Warning::raw`]): every child span carrying a `suggested_replacement`.
+//! Review decisions are recorded by fingerprint so later runs skip warnings
+//! that were already handled, and suppressed ones are ignored altogether.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::ops::Range;
+use crate::types::{Span, Warning};
+use super::examples::Applicability;
+
+/// File recording review decisions when none is configured
+pub const DEFAULT_DECISIONS_FILE: &str = "fix_decisions.json";
+
+/// Replaces the bytes of a span in a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    pub file: String,
+    /// Always has byte offsets when read from a diagnostic
+    pub span: Span,
+    pub text: String,
+}
+
+/// The replacements making up a warning's fix.
+#[derive(Debug, Clone)]
+pub struct SuggestedEdit {
+    pub replacements: Vec<Replacement>,
+    pub applicability: Applicability,
+}
+
+impl SuggestedEdit {
+    /// Reads the fix from the warning's raw diagnostic. Suggestions with
+    /// placeholders or unknown applicability are left out.
+    pub fn from_warning(warning: &Warning) -> Option<Self> {
+        let raw: Value = serde_json::from_str(warning.raw.as_deref()?).ok()?;
+        let mut replacements = Vec::new();
+        let mut applicability = Applicability::MachineApplicable;
+        for child in raw.get("children")?.as_array()? {
+            for span in child.get("spans").and_then(Value::as_array).into_iter().flatten() {
+                let Some(text) = span.get("suggested_replacement").and_then(Value::as_str) else {
+                    continue;
+                };
+                match span.get("suggestion_applicability").and_then(Value::as_str) {
+                    Some("MachineApplicable") => {}
+                    Some("MaybeIncorrect") => applicability = Applicability::MaybeIncorrect,
+                    _ => return None,
+                }
+                let file = span.get("file_name")?.as_str()?.to_string();
+                let span: Span = serde_json::from_value(span.clone()).ok()?;
+                span.byte_range()?;
+                replacements.push(Replacement { file, span, text: text.to_string() });
+            }
+        }
+        if replacements.is_empty() {
+            return None;
+        }
+        replacements.sort_by_key(|r| (r.file.clone(), r.span.byte_start));
+        Some(Self { replacements, applicability })
+    }
+
+    /// Files touched by the edit.
+    pub fn files(&self) -> Vec<&str> {
+        let mut files: Vec<&str> = self.replacements.iter().map(|r| r.file.as_str()).collect();
+        files.dedup();
+        files
+    }
+
+    /// First byte changed in `file`, used to apply edits back to front.
+    pub fn first_byte(&self) -> usize {
+        self.replacements.first().and_then(|r| r.span.byte_start).unwrap_or(0)
+    }
+
+    /// Applies the replacements for `file` to its contents. Returns `None`
+    /// when a range is missing, out of bounds, or overlapping, which means
+    /// the file changed since clippy ran.
+    pub fn apply_to(&self, file: &str, source: &str) -> Option<String> {
+        let mut result = source.to_string();
+        let mut end = usize::MAX;
+        for replacement in self.replacements.iter().rev().filter(|r| r.file == file) {
+            let Range { start, end: stop } = replacement.span.byte_range()?;
+            if stop > end || !source.is_char_boundary(start) || !source.is_char_boundary(stop) {
+                return None;
+            }
+            result.replace_range(start..stop, &replacement.text);
+            end = start;
+        }
+        Some(result)
+    }
+
+    /// Writes the edit to the files under `root`.
+    pub fn apply(&self, root: &Path) -> io::Result<()> {
+        for file in self.files() {
+            let path = root.join(file);
+            let source = std::fs::read_to_string(&path)?;
+            let fixed = self.apply_to(file, &source).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} changed since clippy ran; rerun the analysis", file),
+                )
+            })?;
+            std::fs::write(&path, fixed)?;
+        }
+        Ok(())
+    }
+}
+
+/// What the reviewer chose for a fix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FixDecision {
+    Applied,
+    Skipped,
+    /// Not to be fixed; the warning is ignored from now on
+    Suppressed,
+}
+
+impl fmt::Display for FixDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixDecision::Applied => write!(f, "applied"),
+            FixDecision::Skipped => write!(f, "skipped"),
+            FixDecision::Suppressed => write!(f, "suppressed"),
+        }
+    }
+}
+
+/// Review decisions by warning fingerprint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FixDecisions {
+    decisions: BTreeMap<String, FixDecision>,
+}
+
+impl FixDecisions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads recorded decisions, or none if the file does not exist.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid fix decisions {}: {}", path.display(), e),
+            )
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn record(&mut self, warning: &Warning, decision: FixDecision) {
+        self.decisions.insert(warning.fingerprint(), decision);
+    }
+
+    pub fn get(&self, warning: &Warning) -> Option<FixDecision> {
+        self.decisions
+            .get(&warning.fingerprint())
+            .or_else(|| self.decisions.get(&warning.message_fingerprint()))
+            .copied()
+    }
+
+    /// Fingerprints of suppressed warnings.
+    pub fn suppressed(&self) -> impl Iterator<Item = &str> {
+        self.decisions
+            .iter()
+            .filter(|(_, decision)| **decision == FixDecision::Suppressed)
+            .map(|(fingerprint, _)| fingerprint.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.decisions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.decisions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    #[test]
+    fn test_edit_from_raw_diagnostic() {
+        let source = "fn main() {\n    let v = vec![1];\n    if v.len() == 0 {}\n}\n";
+        let start = source.find("v.len() == 0").unwrap();
+        let raw = serde_json::json!({
+            "message": "length comparison to zero",
+            "children": [
+                { "message": "for further information visit ...", "spans": [] },
+                { "message": "using `is_empty` is clearer", "spans": [{
+                    "file_name": "src/main.rs",
+                    "byte_start": start,
+                    "byte_end": start + 12,
+                    "line_start": 3,
+                    "line_end": 3,
+                    "column_start": 8,
+                    "column_end": 20,
+                    "suggested_replacement": "v.is_empty()",
+                    "suggestion_applicability": "MachineApplicable"
+                }]}
+            ]
+        });
+        let mut warning = Warning {
+            primary_message: "length comparison to zero".to_string(),
+            priority: Priority::Low,
+            raw: Some(raw.to_string()),
+            ..Warning::for_test("clippy::len_zero", "src/main.rs", 3)
+        };
+        let edit = SuggestedEdit::from_warning(&warning).unwrap();
+        assert_eq!(edit.applicability, Applicability::MachineApplicable);
+        assert_eq!(
+            edit.apply_to("src/main.rs", source).unwrap(),
+            "fn main() {\n    let v = vec![1];\n    if v.is_empty() {}\n}\n"
+        );
+        // The file shrank since clippy ran
+        assert!(edit.apply_to("src/main.rs", "fn main() {}").is_none());
+
+        let mut decisions = FixDecisions::new();
+        decisions.record(&warning, FixDecision::Suppressed);
+        assert_eq!(decisions.suppressed().collect::<Vec<_>>(), vec![warning.fingerprint()]);
+
+        warning.raw = None;
+        assert!(SuggestedEdit::from_warning(&warning).is_none());
+    }
+
+    /// A suggestion span replacing `start..end` of `file` with `text`.
+    fn suggestion(file: &str, start: usize, end: usize, text: &str) -> serde_json::Value {
+        serde_json::json!({
+            "file_name": file,
+            "byte_start": start,
+            "byte_end": end,
+            "line_start": 1,
+            "line_end": 1,
+            "column_start": start + 1,
+            "column_end": end + 1,
+            "suggested_replacement": text,
+            "suggestion_applicability": "MachineApplicable"
+        })
+    }
+
+    fn edit_warning(spans: Vec<serde_json::Value>) -> Warning {
+        let raw = serde_json::json!({ "children": [{ "message": "try", "spans": spans }] });
+        Warning { raw: Some(raw.to_string()), ..Warning::for_test("clippy::redundant_clone", "src/a.rs", 1) }
+    }
+
+    #[test]
+    fn test_edit_spanning_several_files() {
+        let warning = edit_warning(vec![
+            suggestion("src/b.rs", 4, 5, "y"),
+            suggestion("src/a.rs", 8, 11, "two"),
+            suggestion("src/a.rs", 0, 3, "one"),
+        ]);
+        let edit = SuggestedEdit::from_warning(&warning).unwrap();
+
+        let starts: Vec<(&str, Option<usize>)> =
+            edit.replacements.iter().map(|r| (r.file.as_str(), r.span.byte_start)).collect();
+        assert_eq!(starts, [("src/a.rs", Some(0)), ("src/a.rs", Some(8)), ("src/b.rs", Some(4))]);
+        assert_eq!(edit.files(), ["src/a.rs", "src/b.rs"]);
+        assert_eq!(edit.first_byte(), 0);
+        assert_eq!(edit.replacements[1].span, Span { byte_start: Some(8), byte_end: Some(11), ..Span::new(1, 9, 1, 12) });
+
+        // Applied back to front, so earlier offsets stay valid
+        assert_eq!(edit.apply_to("src/a.rs", "1 + 2 + 3 + 4").unwrap(), "one 2 + two 4");
+        assert_eq!(edit.apply_to("src/b.rs", "let x = 1;").unwrap(), "let y = 1;");
+        assert_eq!(edit.apply_to("src/c.rs", "untouched").unwrap(), "untouched");
+    }
+
+    #[test]
+    fn test_edit_rejects_unusable_spans() {
+        let overlapping = edit_warning(vec![suggestion("src/a.rs", 0, 6, "a"), suggestion("src/a.rs", 4, 8, "b")]);
+        let edit = SuggestedEdit::from_warning(&overlapping).unwrap();
+        assert!(edit.apply_to("src/a.rs", "0123456789").is_none());
+
+        // Offsets inside a multi-byte character
+        let split = edit_warning(vec![suggestion("src/a.rs", 1, 2, "x")]);
+        let edit = SuggestedEdit::from_warning(&split).unwrap();
+        assert!(edit.apply_to("src/a.rs", "é").is_none());
+
+        let mut no_offsets = suggestion("src/a.rs", 0, 1, "x");
+        no_offsets.as_object_mut().unwrap().remove("byte_end");
+        assert!(SuggestedEdit::from_warning(&edit_warning(vec![no_offsets])).is_none());
+
+        let mut placeholder = suggestion("src/a.rs", 0, 1, "x");
+        placeholder["suggestion_applicability"] = "HasPlaceholders".into();
+        assert!(SuggestedEdit::from_warning(&edit_warning(vec![placeholder])).is_none());
+    }
+
+    #[test]
+    fn test_site_key_tells_columns_apart() {
+        let at = |column| Warning {
+            span: Some(Span::new(3, column, 3, column + 4)),
+            ..Warning::for_test("clippy::redundant_clone", "src/a.rs", 3)
+        };
+        assert_eq!(at(5).site_key(), ("clippy::redundant_clone", "src/a.rs", 3, 5));
+        assert_ne!(at(5).site_key(), at(9).site_key());
+        assert_eq!(Warning::for_test("clippy::redundant_clone", "src/a.rs", 3).site_key().3, 0);
+    }
+}
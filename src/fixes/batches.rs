@@ -0,0 +1,116 @@
+//! Batching of suggested fixes into reviewable commits.
+//!
+//! Warnings that come with a concrete fix are grouped by the top-level module
+//! of their file, so each batch can be applied and reviewed as one commit
+//! touching a single area of the code.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use crate::types::Warning;
+
+/// Most lints named in a batch's commit message before they are counted
+const MAX_LINTS_IN_MESSAGE: usize = 3;
+
+/// Fixable warnings in one module.
+#[derive(Debug)]
+pub struct FixBatch<'a> {
+    pub module: String,
+    /// Warnings by lint
+    pub fixes: BTreeMap<&'a str, Vec<&'a Warning>>,
+}
+
+impl FixBatch<'_> {
+    /// Conventional commit message for the batch, e.g.
+    /// `fix(parser): apply clippy::needless_borrow fixes`.
+    pub fn commit_message(&self) -> String {
+        let lints: Vec<&str> = self.fixes.keys().copied().collect();
+        let applied = if lints.len() <= MAX_LINTS_IN_MESSAGE {
+            lints.join(", ")
+        } else {
+            format!("{} lint", lints.len())
+        };
+        format!("fix({}): apply {} fixes", self.module, applied)
+    }
+
+    pub fn files(&self) -> BTreeSet<&str> {
+        self.fixes.values().flatten().map(|w| w.file.as_str()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fixes.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fixes.is_empty()
+    }
+}
+
+/// Groups warnings with a suggested fix into batches, one per module, in
+/// module order.
+pub fn batch_fixes<'a>(warnings: impl IntoIterator<Item = &'a Warning>) -> Vec<FixBatch<'a>> {
+    let mut batches: BTreeMap<String, BTreeMap<&str, Vec<&Warning>>> = BTreeMap::new();
+    for warning in warnings.into_iter().filter(|w| w.suggested_fix.is_some() && !w.is_error) {
+        let module = module_of(&warning.file, warning.crate_name.as_deref());
+        batches.entry(module).or_default().entry(&warning.lint_code).or_default().push(warning);
+    }
+    batches.into_iter().map(|(module, fixes)| FixBatch { module, fixes }).collect()
+}
+
+/// Top-level module of a source file: `src/parser/warning_parser.rs` is in
+/// `parser`, and crate roots are named after their crate. Files outside
+/// `src/`, like integration tests, are named after their directory.
+pub fn module_of(file: &str, crate_name: Option<&str>) -> String {
+    let path = Path::new(file);
+    let components: Vec<&str> = path.iter().filter_map(|c| c.to_str()).collect();
+    let within_src = components.iter().rposition(|c| *c == "src").map(|i| &components[i + 1..]);
+    let crate_root = || crate_name.unwrap_or("crate").to_string();
+
+    match within_src {
+        Some([single]) => match single.strip_suffix(".rs") {
+            Some("lib" | "main") | None => crate_root(),
+            Some(stem) => stem.to_string(),
+        },
+        Some([first, ..]) => first.to_string(),
+        _ => match components.as_slice() {
+            [.., dir, _] => dir.to_string(),
+            _ => crate_root(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    fn warning(id: &str, file: &str, fix: Option<&str>) -> Warning {
+        Warning {
+            primary_message: "message".to_string(),
+            priority: Priority::Low,
+            suggested_fix: fix.map(str::to_string),
+            crate_name: Some("demo".to_string()),
+            ..Warning::for_test(id, file, 1)
+        }
+    }
+
+    #[test]
+    fn test_batch_fixes() {
+        assert_eq!(module_of("src/parser/warning_parser.rs", None), "parser");
+        assert_eq!(module_of("crates/core/src/lib.rs", Some("core")), "core");
+        assert_eq!(module_of("src/config.rs", None), "config");
+        assert_eq!(module_of("tests/cli.rs", None), "tests");
+
+        let warnings = vec![
+            warning("clippy::needless_borrow", "src/parser/mod.rs", Some("&x")),
+            warning("clippy::needless_borrow", "src/parser/lexer.rs", Some("&y")),
+            warning("clippy::len_zero", "src/main.rs", Some("v.is_empty()")),
+            warning("clippy::too_many_lines", "src/main.rs", None),
+        ];
+        let batches = batch_fixes(&warnings);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].commit_message(), "fix(demo): apply clippy::len_zero fixes");
+        assert_eq!(batches[1].commit_message(), "fix(parser): apply clippy::needless_borrow fixes");
+        assert_eq!(batches[1].len(), 2);
+        assert_eq!(batches[1].files().len(), 2);
+    }
+}
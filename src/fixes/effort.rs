@@ -0,0 +1,132 @@
+//! Fix effort estimates.
+//!
+//! Each warning is put in an effort bucket by its lint and by how its fix can
+//! be applied: fixes `cargo clippy --fix` applies on its own take a minute,
+//! fixes that need review a few minutes, and manual fixes as long as their
+//! lint usually takes, falling back to a per-category default. The estimates
+//! are rough and meant for comparing areas of work, not for scheduling.
+
+use std::fmt;
+use crate::types::{CategoryType, Warning};
+use super::{apply::SuggestedEdit, examples::Applicability, registry::FixExampleRegistry};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EffortBucket {
+    Trivial,
+    Small,
+    Medium,
+    Large,
+    Huge,
+}
+
+impl EffortBucket {
+    pub fn minutes(&self) -> f64 {
+        match self {
+            EffortBucket::Trivial => 1.0,
+            EffortBucket::Small => 5.0,
+            EffortBucket::Medium => 15.0,
+            EffortBucket::Large => 30.0,
+            EffortBucket::Huge => 60.0,
+        }
+    }
+}
+
+/// Lints whose manual fix takes noticeably more or less than their
+/// category's default, without the `clippy::` prefix
+const LINT_BUCKETS: &[(&str, EffortBucket)] = &[
+    ("too_many_lines", EffortBucket::Huge),
+    ("cognitive_complexity", EffortBucket::Huge),
+    ("large_enum_variant", EffortBucket::Large),
+    ("too_many_arguments", EffortBucket::Large),
+    ("await_holding_lock", EffortBucket::Large),
+    ("type_complexity", EffortBucket::Medium),
+    ("result_large_err", EffortBucket::Medium),
+    ("unwrap_used", EffortBucket::Small),
+    ("expect_used", EffortBucket::Small),
+    ("missing_errors_doc", EffortBucket::Small),
+    ("missing_panics_doc", EffortBucket::Small),
+    ("missing_safety_doc", EffortBucket::Small),
+    ("module_name_repetitions", EffortBucket::Small),
+    ("doc_markdown", EffortBucket::Trivial),
+];
+
+/// Bucket of a manual fix for warnings of `category` whose lint is not
+/// listed.
+fn category_bucket(category: CategoryType) -> EffortBucket {
+    match category {
+        CategoryType::Safety => EffortBucket::Medium,
+        CategoryType::Performance => EffortBucket::Medium,
+        CategoryType::Documentation => EffortBucket::Small,
+        CategoryType::Style => EffortBucket::Small,
+        CategoryType::Uncategorized => EffortBucket::Medium,
+    }
+}
+
+/// The effort bucket of fixing `warning` given how its fix applies.
+pub fn effort_bucket(warning: &Warning, applicability: Option<Applicability>) -> EffortBucket {
+    let lint = warning.lint_code.strip_prefix("clippy::").unwrap_or(&warning.lint_code);
+    let manual = LINT_BUCKETS
+        .iter()
+        .find(|(name, _)| *name == lint)
+        .map(|(_, bucket)| *bucket)
+        .unwrap_or_else(|| if warning.is_error { EffortBucket::Medium } else { category_bucket(warning.category) });
+
+    match applicability {
+        Some(Applicability::MachineApplicable) => EffortBucket::Trivial,
+        Some(Applicability::MaybeIncorrect) => manual.min(EffortBucket::Small),
+        Some(Applicability::Manual) | None => manual,
+    }
+}
+
+/// How the warning's fix applies: from the suggestion in its diagnostic,
+/// else from its fix example.
+pub fn applicability(warning: &Warning, examples: &FixExampleRegistry) -> Option<Applicability> {
+    SuggestedEdit::from_warning(warning)
+        .map(|edit| edit.applicability)
+        .or_else(|| examples.get(warning).and_then(|example| example.applicability))
+}
+
+/// Estimated minutes to fix `warning`.
+pub fn estimate_minutes(warning: &Warning, examples: &FixExampleRegistry) -> f64 {
+    effort_bucket(warning, applicability(warning, examples)).minutes()
+}
+
+/// An amount of work in minutes, displayed as `~45 min` or `~3.5 hours`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Effort(pub f64);
+
+impl fmt::Display for Effort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 < 60.0 {
+            write!(f, "~{:.0} min", self.0)
+        } else {
+            write!(f, "~{:.1} hours", self.0 / 60.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warning(id: &str, category: CategoryType) -> Warning {
+        Warning { category, ..Warning::for_test(id, "src/lib.rs", 1) }
+    }
+
+    #[test]
+    fn test_effort_buckets() {
+        let long = warning("clippy::too_many_lines", CategoryType::Style);
+        assert_eq!(effort_bucket(&long, None), EffortBucket::Huge);
+        assert_eq!(effort_bucket(&long, Some(Applicability::MaybeIncorrect)), EffortBucket::Small);
+        assert_eq!(effort_bucket(&long, Some(Applicability::MachineApplicable)), EffortBucket::Trivial);
+        assert_eq!(effort_bucket(&warning("clippy::doc_markdown", CategoryType::Documentation), None), EffortBucket::Trivial);
+        assert_eq!(effort_bucket(&warning("clippy::mem_forget", CategoryType::Safety), None), EffortBucket::Medium);
+
+        // needless_borrow's built-in example is machine-applicable
+        let examples = FixExampleRegistry::default();
+        assert_eq!(estimate_minutes(&warning("clippy::needless_borrow", CategoryType::Style), &examples), 1.0);
+
+        assert_eq!(Effort(45.0).to_string(), "~45 min");
+        assert_eq!(Effort(210.0).to_string(), "~3.5 hours");
+    }
+}
@@ -1,4 +1,12 @@
-use crate::types::{Warning, CategoryType};
+//! Built-in fix examples for common lints.
+//!
+//! Examples are keyed by lint name (without the `clippy::` prefix) and cover
+//! the lints most often seen in real projects. Each one shows the pattern
+//! the lint flags, the fixed code, and whether `cargo clippy --fix` can apply
+//! the change by itself.
+
+use std::fmt;
+use crate::types::Warning;
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -8,81 +16,567 @@ pub struct FixExample {
     pub after: String,
     pub explanation: String,
     pub additional_notes: Vec<String>,
+    /// How safely the fix can be applied automatically, when known
+    #[serde(default)]
+    pub applicability: Option<Applicability>,
 }
 
-pub fn get_fix_example(warning: &Warning) -> Option<FixExample> {
-    let subcategory = warning.message.split_whitespace().next().unwrap_or("");
-    
-    match (warning.category, subcategory) {
-        (CategoryType::Performance, "Locking") => Some(FixExample {
-            description: "Efficient locking patterns".to_string(),
-            before: r#"
-// ❌ Inefficient: Long-held locks
-fn process_data(data: &Arc<Mutex<Vec<String>>>) {
-    let mut locked = data.lock().unwrap();
-    for item in locked.iter_mut() {
-        expensive_operation(item);  // Lock held during expensive operation
-    }
-}"#.to_string(),
-            after: r#"
-// ✅ Efficient: Minimize lock duration
-fn process_data(data: &Arc<Mutex<Vec<String>>>) {
-    // Option 1: Clone data under lock
-    let items = {
-        let locked = data.lock().unwrap();
-        locked.clone()
-    };
-    for item in &items {
-        expensive_operation(item);
+/// Whether a fix can be applied without review, following rustc's
+/// suggestion applicability levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Applicability {
+    /// `cargo clippy --fix` applies it and the result is correct
+    MachineApplicable,
+    /// `cargo clippy --fix` can apply it, but the result needs review
+    MaybeIncorrect,
+    /// The fix needs a human decision
+    Manual,
+}
+
+impl fmt::Display for Applicability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Applicability::MachineApplicable => write!(f, "machine-applicable: `cargo clippy --fix` applies it"),
+            Applicability::MaybeIncorrect => write!(f, "maybe incorrect: review the suggested fix and apply it by hand"),
+            Applicability::Manual => write!(f, "manual: needs a human decision"),
+        }
     }
+}
 
-    // Option 2: Process items individually
-    for i in 0..data.lock().unwrap().len() {
-        let item = {
-            let locked = data.lock().unwrap();
-            locked[i].clone()
-        };
-        expensive_operation(&item);
+/// A fix example as stored in the built-in table.
+struct LintExample {
+    lint: &'static str,
+    description: &'static str,
+    before: &'static str,
+    after: &'static str,
+    explanation: &'static str,
+    notes: &'static [&'static str],
+    applicability: Applicability,
+}
+
+use Applicability::{Manual, MachineApplicable, MaybeIncorrect};
+
+const EXAMPLES: &[LintExample] = &[
+    // Safety
+    LintExample {
+        lint: "unwrap_used",
+        description: "Propagate errors instead of unwrapping",
+        before: "let config = std::fs::read_to_string(path).unwrap();",
+        after: "let config = std::fs::read_to_string(path)?;",
+        explanation: "`unwrap` panics on error; `?` hands the error to the caller",
+        notes: &["Use `expect` with an invariant when failure is truly impossible"],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "expect_used",
+        description: "Handle the failure case explicitly",
+        before: "let port: u16 = env::var(\"PORT\").expect(\"PORT set\").parse().expect(\"valid port\");",
+        after: "let port: u16 = match env::var(\"PORT\").ok().and_then(|p| p.parse().ok()) {\n    Some(port) => port,\n    None => DEFAULT_PORT,\n};",
+        explanation: "`expect` still panics; fall back or return an error instead",
+        notes: &["Allow the lint in tests, where panicking is the point"],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "panic",
+        description: "Return an error instead of panicking",
+        before: "if len > MAX {\n    panic!(\"input too long\");\n}",
+        after: "if len > MAX {\n    return Err(Error::TooLong(len));\n}",
+        explanation: "Library code should let callers decide how to handle bad input",
+        notes: &[],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "indexing_slicing",
+        description: "Use checked indexing",
+        before: "let first = items[0];",
+        after: "let Some(&first) = items.first() else {\n    return Err(Error::Empty);\n};",
+        explanation: "Indexing panics when out of bounds; `get` and `first` return an `Option`",
+        notes: &[],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "unnecessary_unwrap",
+        description: "Bind the value with `if let`",
+        before: "if opt.is_some() {\n    use_value(opt.unwrap());\n}",
+        after: "if let Some(value) = opt {\n    use_value(value);\n}",
+        explanation: "The check and the unwrap can drift apart; pattern matching does both at once",
+        notes: &[],
+        applicability: MaybeIncorrect,
+    },
+    LintExample {
+        lint: "unnecessary_literal_unwrap",
+        description: "Use the literal value directly",
+        before: "let x = Some(1).unwrap();",
+        after: "let x = 1;",
+        explanation: "Unwrapping a freshly built `Some` or `Ok` can never fail",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "await_holding_lock",
+        description: "Release locks before awaiting",
+        before: "let mut guard = state.lock().unwrap();\nguard.count += 1;\nsave(&guard).await;",
+        after: "let snapshot = {\n    let mut guard = state.lock().unwrap();\n    guard.count += 1;\n    guard.clone()\n};\nsave(&snapshot).await;",
+        explanation: "A std mutex guard held across `.await` can deadlock the executor",
+        notes: &[
+            "Use an async-aware mutex when the lock must be held across awaits",
+            "Minimize critical section size",
+        ],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "unused_io_amount",
+        description: "Write or read the whole buffer",
+        before: "file.write(&buf)?;",
+        after: "file.write_all(&buf)?;",
+        explanation: "`write` and `read` may handle only part of the buffer",
+        notes: &[],
+        applicability: Manual,
+    },
+    // Performance
+    LintExample {
+        lint: "redundant_clone",
+        description: "Move the value instead of cloning it",
+        before: "let name = config.name.clone();\nregister(name.clone());",
+        after: "let name = config.name.clone();\nregister(name);",
+        explanation: "The original is not used after the clone, so it can be moved",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "clone_on_copy",
+        description: "Copy instead of cloning",
+        before: "let id: u64 = *user_id;\nlet copy = id.clone();",
+        after: "let id: u64 = *user_id;\nlet copy = id;",
+        explanation: "`Copy` types are copied implicitly; `clone` only adds noise",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "unnecessary_to_owned",
+        description: "Borrow instead of allocating",
+        before: "if names.contains(&name.to_string()) {}",
+        after: "if names.iter().any(|n| n == name) {}",
+        explanation: "Creating an owned value just to borrow it allocates for nothing",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "inefficient_to_string",
+        description: "Dereference before calling `to_string`",
+        before: "fn label(name: &&str) -> String {\n    name.to_string()\n}",
+        after: "fn label(name: &&str) -> String {\n    (*name).to_string()\n}",
+        explanation: "`to_string` on `&&str` goes through the slow `Display` path",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "single_char_pattern",
+        description: "Use a char pattern",
+        before: "let parts: Vec<&str> = line.split(\",\").collect();",
+        after: "let parts: Vec<&str> = line.split(',').collect();",
+        explanation: "Char patterns are matched faster than single-character strings",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "needless_collect",
+        description: "Iterate without collecting",
+        before: "let evens: Vec<_> = items.iter().filter(|x| *x % 2 == 0).collect();\nlet count = evens.len();",
+        after: "let count = items.iter().filter(|x| *x % 2 == 0).count();",
+        explanation: "Collecting into a temporary vector allocates only to be consumed right away",
+        notes: &[],
+        applicability: MaybeIncorrect,
+    },
+    LintExample {
+        lint: "large_enum_variant",
+        description: "Box the large variant",
+        before: "enum Message {\n    Ping,\n    Payload([u8; 4096]),\n}",
+        after: "enum Message {\n    Ping,\n    Payload(Box<[u8; 4096]>),\n}",
+        explanation: "Every value takes the size of the largest variant",
+        notes: &["Measure before and after: boxing adds an allocation per large value"],
+        applicability: MaybeIncorrect,
+    },
+    LintExample {
+        lint: "box_collection",
+        description: "Drop the box around a collection",
+        before: "struct Cache {\n    entries: Box<Vec<Entry>>,\n}",
+        after: "struct Cache {\n    entries: Vec<Entry>,\n}",
+        explanation: "Collections already store their contents on the heap",
+        notes: &[],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "vec_box",
+        description: "Store values directly in the vector",
+        before: "let shapes: Vec<Box<Circle>> = Vec::new();",
+        after: "let shapes: Vec<Circle> = Vec::new();",
+        explanation: "Boxing sized values inside a `Vec` adds an allocation and an indirection per element",
+        notes: &["Keep the box for trait objects (`Vec<Box<dyn Shape>>`)"],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "or_fun_call",
+        description: "Compute the fallback lazily",
+        before: "let name = cached.unwrap_or(String::from(\"anonymous\"));",
+        after: "let name = cached.unwrap_or_else(|| String::from(\"anonymous\"));",
+        explanation: "`unwrap_or` evaluates its argument even when it is not needed",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "expect_fun_call",
+        description: "Format the panic message lazily",
+        before: "let file = open(path).expect(&format!(\"cannot open {}\", path));",
+        after: "let file = open(path).unwrap_or_else(|_| panic!(\"cannot open {}\", path));",
+        explanation: "The message is formatted on every call, even when nothing fails",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "iter_nth_zero",
+        description: "Use `next` for the first element",
+        before: "let first = iter.nth(0);",
+        after: "let first = iter.next();",
+        explanation: "`next` states the intent directly",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "useless_vec",
+        description: "Use an array instead of a vector",
+        before: "for x in vec![1, 2, 3] {\n    total += x;\n}",
+        after: "for x in [1, 2, 3] {\n    total += x;\n}",
+        explanation: "A fixed set of values does not need a heap allocation",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "to_string_in_format_args",
+        description: "Let the formatter convert the value",
+        before: "println!(\"{}\", value.to_string());",
+        after: "println!(\"{}\", value);",
+        explanation: "`to_string` allocates a string the formatter then copies",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    // Style
+    LintExample {
+        lint: "redundant_closure",
+        description: "Pass the function directly",
+        before: "let lengths: Vec<usize> = words.iter().map(|w| str::len(w)).collect();",
+        after: "let lengths: Vec<usize> = words.iter().map(|w| w.len()).collect();\nlet parsed: Vec<u32> = inputs.iter().map(parse).collect();",
+        explanation: "A closure that only forwards its argument can be replaced by the function",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "too_many_arguments",
+        description: "Group related arguments into a struct",
+        before: "fn render(title: &str, width: u32, height: u32, x: i32, y: i32, visible: bool, z: u8, alpha: f32) {}",
+        after: "struct Layout {\n    width: u32,\n    height: u32,\n    x: i32,\n    y: i32,\n    z: u8,\n}\n\nfn render(title: &str, layout: &Layout, visible: bool, alpha: f32) {}",
+        explanation: "Long parameter lists are easy to call with arguments in the wrong order",
+        notes: &["A builder works well when most arguments have defaults"],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "needless_borrow",
+        description: "Remove the extra reference",
+        before: "let n = count_words(&text);  // text: &str",
+        after: "let n = count_words(text);",
+        explanation: "The value is already a reference; borrowing again is auto-dereferenced away",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "needless_return",
+        description: "Use the final expression as the return value",
+        before: "fn double(x: i32) -> i32 {\n    return x * 2;\n}",
+        after: "fn double(x: i32) -> i32 {\n    x * 2\n}",
+        explanation: "A block evaluates to its last expression",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "len_zero",
+        description: "Use `is_empty`",
+        before: "if items.len() == 0 {\n    return;\n}",
+        after: "if items.is_empty() {\n    return;\n}",
+        explanation: "`is_empty` states the intent and is constant time for every collection",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "collapsible_if",
+        description: "Merge the nested conditions",
+        before: "if ready {\n    if count > 0 {\n        flush();\n    }\n}",
+        after: "if ready && count > 0 {\n    flush();\n}",
+        explanation: "One condition is easier to read than two levels of nesting",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "collapsible_else_if",
+        description: "Use `else if`",
+        before: "if a {\n    first();\n} else {\n    if b {\n        second();\n    }\n}",
+        after: "if a {\n    first();\n} else if b {\n    second();\n}",
+        explanation: "An `else` block holding only an `if` reads better as `else if`",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "single_match",
+        description: "Use `if let` for a single pattern",
+        before: "match result {\n    Some(value) => process(value),\n    _ => {}\n}",
+        after: "if let Some(value) = result {\n    process(value);\n}",
+        explanation: "A `match` with one interesting arm and an empty fallback is an `if let`",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "new_without_default",
+        description: "Implement `Default` alongside `new`",
+        before: "impl Registry {\n    pub fn new() -> Self {\n        Self { entries: Vec::new() }\n    }\n}",
+        after: "impl Default for Registry {\n    fn default() -> Self {\n        Self::new()\n    }\n}",
+        explanation: "Types with an argument-free constructor are expected to implement `Default`",
+        notes: &["`#[derive(Default)]` works when every field has a default"],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "redundant_field_names",
+        description: "Use field init shorthand",
+        before: "let point = Point { x: x, y: y };",
+        after: "let point = Point { x, y };",
+        explanation: "When the variable and the field share a name, the name is enough",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "needless_range_loop",
+        description: "Iterate over the elements",
+        before: "for i in 0..items.len() {\n    println!(\"{}\", items[i]);\n}",
+        after: "for item in &items {\n    println!(\"{}\", item);\n}",
+        explanation: "Iterators avoid bounds checks and off-by-one mistakes",
+        notes: &["Use `.iter().enumerate()` when the index is needed too"],
+        applicability: MaybeIncorrect,
+    },
+    LintExample {
+        lint: "manual_map",
+        description: "Use `Option::map`",
+        before: "let len = match name {\n    Some(n) => Some(n.len()),\n    None => None,\n};",
+        after: "let len = name.map(|n| n.len());",
+        explanation: "Mapping the inner value is exactly what `map` does",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "map_unwrap_or",
+        description: "Use `map_or` or `map_or_else`",
+        before: "let len = name.map(|n| n.len()).unwrap_or(0);",
+        after: "let len = name.map_or(0, |n| n.len());",
+        explanation: "One combinator replaces the chain",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "type_complexity",
+        description: "Name the complex type",
+        before: "fn handlers() -> HashMap<String, Vec<Box<dyn Fn(&Request) -> Result<Response, Error>>>> {}",
+        after: "type Handler = Box<dyn Fn(&Request) -> Result<Response, Error>>;\n\nfn handlers() -> HashMap<String, Vec<Handler>> {}",
+        explanation: "A type alias documents what the type is for",
+        notes: &[],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "use_self",
+        description: "Refer to the type as `Self`",
+        before: "impl Point {\n    fn origin() -> Point {\n        Point { x: 0, y: 0 }\n    }\n}",
+        after: "impl Point {\n    fn origin() -> Self {\n        Self { x: 0, y: 0 }\n    }\n}",
+        explanation: "`Self` keeps working when the type is renamed",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "manual_strip",
+        description: "Use `strip_prefix`",
+        before: "if line.starts_with(\"# \") {\n    let title = &line[2..];\n}",
+        after: "if let Some(title) = line.strip_prefix(\"# \") {\n}",
+        explanation: "`strip_prefix` checks and slices in one step, without a magic length",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "match_like_matches_macro",
+        description: "Use `matches!`",
+        before: "let is_digit = match c {\n    '0'..='9' => true,\n    _ => false,\n};",
+        after: "let is_digit = matches!(c, '0'..='9');",
+        explanation: "A match that only returns booleans is what `matches!` expresses",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "question_mark",
+        description: "Use the `?` operator",
+        before: "let value = match lookup(key) {\n    Some(value) => value,\n    None => return None,\n};",
+        after: "let value = lookup(key)?;",
+        explanation: "`?` is the idiomatic early return for `Option` and `Result`",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "let_and_return",
+        description: "Return the expression directly",
+        before: "fn area(w: u32, h: u32) -> u32 {\n    let area = w * h;\n    area\n}",
+        after: "fn area(w: u32, h: u32) -> u32 {\n    w * h\n}",
+        explanation: "A binding used only as the return value adds nothing",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "bool_comparison",
+        description: "Use the boolean directly",
+        before: "if enabled == true {\n    start();\n}",
+        after: "if enabled {\n    start();\n}",
+        explanation: "Comparing a boolean to a literal is redundant",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "comparison_chain",
+        description: "Match on `cmp`",
+        before: "if a > b {\n    greater()\n} else if a < b {\n    less()\n} else {\n    equal()\n}",
+        after: "match a.cmp(&b) {\n    Ordering::Greater => greater(),\n    Ordering::Less => less(),\n    Ordering::Equal => equal(),\n}",
+        explanation: "The compiler checks that every ordering is handled",
+        notes: &[],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "ptr_arg",
+        description: "Take a slice instead of a `Vec` or `String` reference",
+        before: "fn total(values: &Vec<u32>) -> u32 {\n    values.iter().sum()\n}",
+        after: "fn total(values: &[u32]) -> u32 {\n    values.iter().sum()\n}",
+        explanation: "Slices accept more callers: arrays, vectors, and sub-slices",
+        notes: &["Likewise `&str` instead of `&String` and `&Path` instead of `&PathBuf`"],
+        applicability: MaybeIncorrect,
+    },
+    LintExample {
+        lint: "useless_conversion",
+        description: "Remove the identity conversion",
+        before: "let name: String = name.into();  // name: String",
+        after: "let name: String = name;",
+        explanation: "Converting a value into its own type does nothing",
+        notes: &[],
+        applicability: MachineApplicable,
+    },
+    LintExample {
+        lint: "uninlined_format_args",
+        description: "Inline variables into the format string",
+        before: "println!(\"{} has {} items\", name, count);",
+        after: "println!(\"{name} has {count} items\");",
+        explanation: "Inline arguments keep each value next to its placeholder",
+        notes: &["Only plain identifiers can be inlined, not expressions"],
+        applicability: MachineApplicable,
+    },
+    // Documentation
+    LintExample {
+        lint: "missing_errors_doc",
+        description: "Document when the function returns an error",
+        before: "pub fn load(path: &Path) -> io::Result<Config> {}",
+        after: "/// Loads the configuration at `path`.\n///\n/// # Errors\n///\n/// Returns an error if the file cannot be read or is not valid TOML.\npub fn load(path: &Path) -> io::Result<Config> {}",
+        explanation: "Callers need to know which failures to expect",
+        notes: &[],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "missing_panics_doc",
+        description: "Document when the function panics",
+        before: "pub fn nth_prime(n: usize) -> u64 {\n    assert!(n > 0);\n}",
+        after: "/// Returns the `n`th prime, counting from 1.\n///\n/// # Panics\n///\n/// Panics if `n` is zero.\npub fn nth_prime(n: usize) -> u64 {\n    assert!(n > 0);\n}",
+        explanation: "Undocumented panics surprise callers",
+        notes: &[],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "doc_markdown",
+        description: "Put code in backticks",
+        before: "/// Parses the output of cargo_metadata into a HashMap.",
+        after: "/// Parses the output of `cargo_metadata` into a `HashMap`.",
+        explanation: "Backticks render identifiers as code and keep rustdoc links working",
+        notes: &[],
+        applicability: MaybeIncorrect,
+    },
+    LintExample {
+        lint: "missing_docs_in_private_items",
+        description: "Document private items",
+        before: "struct Configuration {\n    timeout: Duration,\n    retries: u32,\n}",
+        after: "/// Configuration for network operations\nstruct Configuration {\n    /// Time allowed for each attempt\n    timeout: Duration,\n    /// Attempts after the first failure\n    retries: u32,\n}",
+        explanation: "Internal docs help the next maintainer",
+        notes: &["Include practical usage examples", "Demonstrate common use cases"],
+        applicability: Manual,
+    },
+    LintExample {
+        lint: "missing_docs",
+        description: "Document public items",
+        before: "pub struct Configuration {\n    pub timeout: Duration,\n}",
+        after: "/// Configuration for network operations\n///\n/// # Examples\n///\n/// ```\n/// let config = Configuration { timeout: Duration::from_secs(30) };\n/// ```\npub struct Configuration {\n    /// Time allowed for each attempt\n    pub timeout: Duration,\n}",
+        explanation: "Add comprehensive examples to documentation",
+        notes: &["Include practical usage examples", "Show error handling"],
+        applicability: Manual,
+    },
+];
+
+impl LintExample {
+    fn to_example(&self) -> FixExample {
+        FixExample {
+            description: self.description.to_string(),
+            before: self.before.to_string(),
+            after: self.after.to_string(),
+            explanation: self.explanation.to_string(),
+            additional_notes: self.notes.iter().map(|note| note.to_string()).collect(),
+            applicability: Some(self.applicability),
+        }
     }
-}"#.to_string(),
-            explanation: "Minimize lock duration and consider alternative synchronization primitives".to_string(),
-            additional_notes: vec![
-                "Use RwLock for read-heavy workloads".to_string(),
-                "Consider lock-free data structures".to_string(),
-                "Minimize critical section size".to_string(),
-            ],
-        }),
+}
+
+/// Lints with a built-in example, without the `clippy::` prefix.
+pub fn example_lints() -> impl Iterator<Item = &'static str> {
+    EXAMPLES.iter().map(|example| example.lint)
+}
+
+/// The built-in example for the warning's lint.
+pub fn get_fix_example(warning: &Warning) -> Option<FixExample> {
+    let lint = warning.lint_code.strip_prefix("clippy::").unwrap_or(&warning.lint_code);
+    EXAMPLES
+        .iter()
+        .find(|example| example.lint == lint)
+        .map(LintExample::to_example)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use crate::types::Priority;
 
-        (CategoryType::Documentation, "Missing") => Some(FixExample {
-            description: "Documentation examples".to_string(),
-            before: r#"
-pub struct Configuration {
-    timeout: Duration,
-    retries: u32,
-}"#.to_string(),
-            after: r#"/// Configuration for network operations
-/// 
-/// # Examples
-/// 
-/// ```
-/// let config = Configuration::new(
-///     Duration::from_secs(30),
-///     3
-/// )?;
-/// ```
-pub struct Configuration {
-    timeout: Duration,
-    retries: u32,
-}"#.to_string(),
-            explanation: "Add comprehensive examples to documentation".to_string(),
-            additional_notes: vec![
-                "Include practical usage examples".to_string(),
-                "Show error handling".to_string(),
-                "Demonstrate common use cases".to_string(),
-            ],
-        }),
+    #[test]
+    fn test_examples_keyed_by_lint() {
+        let lints: HashSet<&str> = example_lints().collect();
+        assert_eq!(lints.len(), EXAMPLES.len(), "duplicate lint in the example table");
+        assert!(lints.len() >= 50);
+
+        let mut warning = Warning {
+            primary_message: "length comparison to zero".to_string(),
+            priority: Priority::Low,
+            ..Warning::for_test("clippy::len_zero", "src/lib.rs", 1)
+        };
+        let example = get_fix_example(&warning).unwrap();
+        assert!(example.after.contains("is_empty()"));
+        assert_eq!(example.applicability, Some(Applicability::MachineApplicable));
 
-        // Add more examples as needed...
-        _ => None,
+        warning.lint_code = "missing_docs".to_string();
+        assert!(get_fix_example(&warning).is_some());
+        warning.lint_code = "clippy::no_such_lint".to_string();
+        assert!(get_fix_example(&warning).is_none());
     }
 }
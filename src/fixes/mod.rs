@@ -1,7 +1,19 @@
+pub mod apply;
+pub mod batches;
+pub mod effort;
+pub mod sprints;
 pub mod examples;
 pub mod templates;
 pub mod suggestions;
+pub mod post_process;
+pub mod registry;
 
-pub use examples::{FixExample, get_fix_example};
+pub use apply::{FixDecision, FixDecisions, SuggestedEdit};
+pub use batches::{FixBatch, batch_fixes};
+pub use effort::{Effort, EffortBucket, estimate_minutes};
+pub use sprints::{SprintAction, SprintBatch, plan_sprints};
+pub use examples::{Applicability, FixExample, get_fix_example};
 pub use templates::write_fix_template;
 pub use suggestions::generate_fix_suggestion;
+pub use registry::{FixExampleRegistry, FixTemplate, FixTemplateConfig};
+pub use post_process::{SuggestionConfig, SuggestionPipeline, SuggestionPostProcessor};
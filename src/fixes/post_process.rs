@@ -0,0 +1,162 @@
+//! Suggestion post-processing.
+//!
+//! Post-processors rewrite or augment fix suggestions before they are
+//! rendered. Built-ins are enabled from the `[suggestions]` config table;
+//! library users can register their own with
+//! `AnalysisRunner::add_suggestion_post_processor`.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use serde::Deserialize;
+use crate::types::Warning;
+use super::suggestions::FixSuggestion;
+
+pub trait SuggestionPostProcessor: Send + Sync {
+    /// Short name, shown in debug output.
+    fn name(&self) -> &str;
+
+    /// Returns the suggestion to render for `warning` in place of `suggestion`.
+    fn process(&self, warning: &Warning, suggestion: FixSuggestion) -> FixSuggestion;
+}
+
+/// Built-in post-processors enabled from configuration.
+///
+/// ```toml
+/// [suggestions]
+/// rename = { "MyType" = "Widget" }
+/// feature-gate = "clippy-fixes"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct SuggestionConfig {
+    /// Identifiers in suggested code replaced with project names
+    pub rename: BTreeMap<String, String>,
+    /// Feature that suggested code is gated behind
+    pub feature_gate: Option<String>,
+}
+
+/// Replaces whole identifiers in suggested code, e.g. placeholder type names
+/// with ones following the project's naming conventions.
+#[derive(Debug, Clone)]
+pub struct RenameIdentifiers {
+    renames: BTreeMap<String, String>,
+}
+
+impl RenameIdentifiers {
+    pub fn new(renames: BTreeMap<String, String>) -> Self {
+        Self { renames }
+    }
+}
+
+impl SuggestionPostProcessor for RenameIdentifiers {
+    fn name(&self) -> &str {
+        "rename"
+    }
+
+    fn process(&self, _warning: &Warning, mut suggestion: FixSuggestion) -> FixSuggestion {
+        let mut code = String::with_capacity(suggestion.code.len());
+        let mut word = String::new();
+        for c in suggestion.code.chars() {
+            if c.is_alphanumeric() || c == '_' {
+                word.push(c);
+            } else {
+                code.push_str(self.renames.get(&word).unwrap_or(&word));
+                word.clear();
+                code.push(c);
+            }
+        }
+        code.push_str(self.renames.get(&word).unwrap_or(&word));
+        suggestion.code = code;
+        suggestion
+    }
+}
+
+/// Gates suggested code behind a cargo feature, for projects that roll out
+/// lint fixes incrementally.
+#[derive(Debug, Clone)]
+pub struct FeatureGate {
+    feature: String,
+}
+
+impl FeatureGate {
+    pub fn new(feature: &str) -> Self {
+        Self { feature: feature.to_string() }
+    }
+}
+
+impl SuggestionPostProcessor for FeatureGate {
+    fn name(&self) -> &str {
+        "feature-gate"
+    }
+
+    fn process(&self, _warning: &Warning, mut suggestion: FixSuggestion) -> FixSuggestion {
+        suggestion.code = format!("#[cfg(feature = \"{}\")]\n{}", self.feature, suggestion.code);
+        suggestion.explanation = format!(
+            "{} (gated behind the `{}` feature)",
+            suggestion.explanation, self.feature
+        );
+        suggestion
+    }
+}
+
+/// Post-processors applied in registration order.
+#[derive(Clone, Default)]
+pub struct SuggestionPipeline {
+    processors: Vec<Arc<dyn SuggestionPostProcessor>>,
+}
+
+impl SuggestionPipeline {
+    pub fn from_config(config: &SuggestionConfig) -> Self {
+        let mut pipeline = Self::default();
+        if !config.rename.is_empty() {
+            pipeline.register(Arc::new(RenameIdentifiers::new(config.rename.clone())));
+        }
+        if let Some(feature) = &config.feature_gate {
+            pipeline.register(Arc::new(FeatureGate::new(feature)));
+        }
+        pipeline
+    }
+
+    pub fn register(&mut self, processor: Arc<dyn SuggestionPostProcessor>) {
+        self.processors.push(processor);
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.processors.iter().map(|processor| processor.name()).collect()
+    }
+
+    pub fn apply(&self, warning: &Warning, suggestion: FixSuggestion) -> FixSuggestion {
+        self.processors
+            .iter()
+            .fold(suggestion, |suggestion, processor| processor.process(warning, suggestion))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    #[test]
+    fn test_pipeline_from_config() {
+        let config: SuggestionConfig = toml::from_str(
+            "rename = { MyType = \"Widget\" }\nfeature-gate = \"clippy-fixes\"",
+        ).unwrap();
+        let pipeline = SuggestionPipeline::from_config(&config);
+        assert_eq!(pipeline.names(), vec!["rename", "feature-gate"]);
+
+        let warning = Warning {
+            priority: Priority::Low,
+            ..Warning::for_test("clippy::use_self", "src/lib.rs", 1)
+        };
+        let suggestion = FixSuggestion {
+            code: "pub struct MyType {}\nlet x: MyTypeRef;".to_string(),
+            explanation: "Use conventional names".to_string(),
+            confidence: 0.9,
+        };
+
+        let processed = pipeline.apply(&warning, suggestion);
+        assert_eq!(processed.code, "#[cfg(feature = \"clippy-fixes\")]\npub struct Widget {}\nlet x: MyTypeRef;");
+        assert_eq!(processed.explanation, "Use conventional names (gated behind the `clippy-fixes` feature)");
+    }
+}
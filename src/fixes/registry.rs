@@ -0,0 +1,205 @@
+//! User-defined fix examples.
+//!
+//! Projects can describe how they want specific lints fixed, keyed by lint
+//! name, in TOML or YAML files under `fix_templates/` (or the directory set
+//! in the config) and inline in the config file:
+//!
+//! ```toml
+//! [[template]]
+//! lint = "clippy::needless_borrow"
+//! description = "Pass references through"
+//! before = "takes_ref(&name)"
+//! after = "takes_ref(name)"
+//! explanation = "`name` is already a reference"
+//! notes = ["Applies to all `&&str` call sites"]
+//! applicability = "machine-applicable"
+//! ```
+//!
+//! Lints without a user template fall back to the built-in examples.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use crate::types::Warning;
+use super::examples::{Applicability, FixExample, get_fix_example};
+
+/// Directory searched for template files when none is configured
+pub const DEFAULT_TEMPLATES_DIR: &str = "fix_templates";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FixTemplate {
+    /// Lint name, with or without the `clippy::` prefix
+    pub lint: String,
+    pub description: Option<String>,
+    pub before: String,
+    pub after: String,
+    #[serde(default)]
+    pub explanation: String,
+    #[serde(default)]
+    pub notes: Vec<String>,
+    #[serde(default)]
+    pub applicability: Option<Applicability>,
+}
+
+impl FixTemplate {
+    fn to_example(&self) -> FixExample {
+        FixExample {
+            description: self.description.clone().unwrap_or_else(|| format!("Fixing `{}`", self.lint)),
+            before: self.before.clone(),
+            after: self.after.clone(),
+            explanation: self.explanation.clone(),
+            additional_notes: self.notes.clone(),
+            applicability: self.applicability,
+        }
+    }
+}
+
+/// Where user templates come from.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct FixTemplateConfig {
+    /// Directory of template files; `fix_templates/` when it exists
+    pub dir: Option<PathBuf>,
+    /// Templates defined inline, taking precedence over files
+    pub template: Vec<FixTemplate>,
+}
+
+#[derive(Deserialize)]
+struct TemplateFile {
+    #[serde(default)]
+    template: Vec<FixTemplate>,
+}
+
+/// Fix examples by lint, backed by the built-in examples.
+#[derive(Debug, Clone, Default)]
+pub struct FixExampleRegistry {
+    templates: HashMap<String, FixTemplate>,
+}
+
+impl FixExampleRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the configured template directory, then the inline templates.
+    /// A configured directory must exist; the default one is optional.
+    pub fn from_config(config: &FixTemplateConfig) -> io::Result<Self> {
+        let mut registry = Self::new();
+        match &config.dir {
+            Some(dir) => {
+                registry.load_dir(dir)?;
+            }
+            None if Path::new(DEFAULT_TEMPLATES_DIR).is_dir() => {
+                registry.load_dir(Path::new(DEFAULT_TEMPLATES_DIR))?;
+            }
+            None => {}
+        }
+        for template in &config.template {
+            registry.add(template.clone());
+        }
+        Ok(registry)
+    }
+
+    /// Adds a template, replacing any earlier one for the same lint.
+    pub fn add(&mut self, template: FixTemplate) {
+        let lint = template.lint.strip_prefix("clippy::").unwrap_or(&template.lint).to_string();
+        self.templates.insert(lint, template);
+    }
+
+    /// Loads every `.toml`, `.yaml`, and `.yml` file in `dir`, in name order.
+    /// Returns the number of templates loaded.
+    pub fn load_dir(&mut self, dir: &Path) -> io::Result<usize> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                matches!(path.extension().and_then(|ext| ext.to_str()), Some("toml" | "yaml" | "yml"))
+            })
+            .collect();
+        paths.sort();
+
+        let mut loaded = 0;
+        for path in paths {
+            loaded += self.load_file(&path)?;
+        }
+        Ok(loaded)
+    }
+
+    /// Loads the templates of one TOML or YAML file.
+    pub fn load_file(&mut self, path: &Path) -> io::Result<usize> {
+        let contents = std::fs::read_to_string(path)?;
+        let parsed = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str::<TemplateFile>(&contents).map_err(|e| e.to_string()),
+            _ => serde_yaml::from_str::<TemplateFile>(&contents).map_err(|e| e.to_string()),
+        };
+        let file = parsed.map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid fix template {}: {}", path.display(), e),
+            )
+        })?;
+        let loaded = file.template.len();
+        for template in file.template {
+            self.add(template);
+        }
+        Ok(loaded)
+    }
+
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// The user template for the warning's lint, or else a built-in example.
+    pub fn get(&self, warning: &Warning) -> Option<FixExample> {
+        let lint = warning.lint_code.strip_prefix("clippy::").unwrap_or(&warning.lint_code);
+        match self.templates.get(lint) {
+            Some(template) => Some(template.to_example()),
+            None => get_fix_example(warning),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    #[test]
+    fn test_registry_loads_toml_and_yaml() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("borrow.toml"),
+            "[[template]]\nlint = \"clippy::needless_borrow\"\nbefore = \"f(&x)\"\nafter = \"f(x)\"\n",
+        ).unwrap();
+        std::fs::write(
+            dir.join("len.yaml"),
+            "template:\n  - lint: len_zero\n    description: Use is_empty\n    before: v.len() == 0\n    after: v.is_empty()\n    notes: [Clearer intent]\n",
+        ).unwrap();
+
+        let mut registry = FixExampleRegistry::new();
+        assert_eq!(registry.load_dir(dir).unwrap(), 2);
+
+        let mut warning = Warning {
+            primary_message: "length comparison to zero".to_string(),
+            priority: Priority::Low,
+            ..Warning::for_test("clippy::len_zero", "src/lib.rs", 1)
+        };
+        let example = registry.get(&warning).unwrap();
+        assert_eq!(example.description, "Use is_empty");
+        assert_eq!(example.additional_notes, vec!["Clearer intent"]);
+
+        warning.lint_code = "clippy::needless_borrow".to_string();
+        assert_eq!(registry.get(&warning).unwrap().description, "Fixing `clippy::needless_borrow`");
+
+        warning.lint_code = "clippy::redundant_clone".to_string();
+        assert_eq!(registry.get(&warning).unwrap().description, "Move the value instead of cloning it");
+
+        warning.lint_code = "clippy::no_such_lint".to_string();
+        assert!(registry.get(&warning).is_none());
+    }
+}
@@ -0,0 +1,157 @@
+//! Sprint batches: the fix plan as a sequence of pull requests.
+//!
+//! Warnings are grouped by the action that resolves them: lints whose fixes
+//! `cargo clippy --fix` applies on its own, suggestions that may be wrong
+//! and are applied by hand after review, and manual fixes by category. Each
+//! group is split into batches of at most `MAX_BATCH_FILES` files, so every
+//! batch stays small enough to review as a single pull request.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use crate::types::{CategoryType, Warning};
+use super::{
+    effort::{Effort, applicability, estimate_minutes},
+    examples::Applicability,
+    registry::FixExampleRegistry,
+};
+
+/// Most files a batch touches
+pub const MAX_BATCH_FILES: usize = 10;
+
+/// Manual batches in this order, most severe first
+const MANUAL_ORDER: [CategoryType; 5] = [
+    CategoryType::Safety,
+    CategoryType::Performance,
+    CategoryType::Documentation,
+    CategoryType::Style,
+    CategoryType::Uncategorized,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SprintAction {
+    /// `cargo clippy --fix` applies every fix correctly
+    AutoFix,
+    /// The suggested fixes may be incorrect; `cargo clippy --fix` skips
+    /// them, so they are reviewed and applied by hand
+    ReviewFix,
+    /// Fixed by hand
+    Manual(CategoryType),
+}
+
+impl fmt::Display for SprintAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SprintAction::AutoFix => write!(f, "run `cargo clippy --fix`"),
+            SprintAction::ReviewFix => write!(f, "review and apply the suggested fixes"),
+            SprintAction::Manual(category) => write!(f, "manual review of {} warnings", category.to_string().to_lowercase()),
+        }
+    }
+}
+
+/// Warnings resolved together in one pull request.
+#[derive(Debug)]
+pub struct SprintBatch<'a> {
+    pub action: SprintAction,
+    /// Warnings by lint
+    pub warnings: BTreeMap<&'a str, Vec<&'a Warning>>,
+    pub effort: Effort,
+}
+
+impl SprintBatch<'_> {
+    pub fn len(&self) -> usize {
+        self.warnings.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    pub fn files(&self) -> BTreeSet<&str> {
+        self.warnings.values().flatten().map(|w| w.file.as_str()).collect()
+    }
+
+    /// The command applying the batch's fixes, for [`SprintAction::AutoFix`]:
+    /// every clippy lint is allowed except the batch's. Command line flags
+    /// cannot switch off rustc's lints or lints enabled by attributes or the
+    /// `[lints]` table, so their machine-applicable fixes are applied too.
+    pub fn command(&self) -> Option<String> {
+        if self.action != SprintAction::AutoFix {
+            return None;
+        }
+        let lints: Vec<String> = self.warnings.keys().map(|lint| format!("-W {}", lint)).collect();
+        Some(format!("cargo clippy --fix --allow-dirty -- -A clippy::all {}", lints.join(" ")))
+    }
+}
+
+/// Splits `warnings` into sprint batches: the automatic fixes, then the
+/// fixes needing review, then manual fixes by category. Compile errors are
+/// left out; they come before any batch.
+pub fn plan_sprints<'a>(warnings: &'a [Warning], examples: &FixExampleRegistry) -> Vec<SprintBatch<'a>> {
+    let mut by_action: BTreeMap<SprintAction, BTreeMap<&str, Vec<&Warning>>> = BTreeMap::new();
+    for warning in warnings.iter().filter(|w| !w.is_error) {
+        let action = match applicability(warning, examples) {
+            Some(Applicability::MachineApplicable) => SprintAction::AutoFix,
+            Some(Applicability::MaybeIncorrect) => SprintAction::ReviewFix,
+            Some(Applicability::Manual) | None => SprintAction::Manual(warning.category),
+        };
+        by_action.entry(action).or_default().entry(warning.file.as_str()).or_default().push(warning);
+    }
+
+    let order = [SprintAction::AutoFix, SprintAction::ReviewFix]
+        .into_iter()
+        .chain(MANUAL_ORDER.map(SprintAction::Manual));
+    let mut batches = Vec::new();
+    for action in order {
+        let Some(by_file) = by_action.remove(&action) else { continue };
+        let files: Vec<Vec<&Warning>> = by_file.into_values().collect();
+        for chunk in files.chunks(MAX_BATCH_FILES) {
+            let mut by_lint: BTreeMap<&str, Vec<&Warning>> = BTreeMap::new();
+            for warning in chunk.iter().flatten() {
+                by_lint.entry(warning.lint_code.as_str()).or_default().push(warning);
+            }
+            let effort = Effort(chunk.iter().flatten().map(|w| estimate_minutes(w, examples)).sum());
+            batches.push(SprintBatch { action, warnings: by_lint, effort });
+        }
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warning(id: &str, category: CategoryType, file: &str) -> Warning {
+        Warning { category, ..Warning::for_test(id, file, 1) }
+    }
+
+    #[test]
+    fn test_plan_sprints() {
+        let mut warnings: Vec<Warning> = (0..12)
+            .map(|i| warning("clippy::unwrap_used", CategoryType::Safety, &format!("src/m{:02}.rs", i)))
+            .collect();
+        warnings.push(warning("clippy::needless_borrow", CategoryType::Style, "src/a.rs"));
+        warnings.push(warning("clippy::len_zero", CategoryType::Style, "src/b.rs"));
+        warnings.push(warning("clippy::missing_errors_doc", CategoryType::Documentation, "src/a.rs"));
+
+        let batches = plan_sprints(&warnings, &FixExampleRegistry::default());
+        let summary: Vec<(SprintAction, usize, usize)> = batches.iter().map(|b| (b.action, b.len(), b.files().len())).collect();
+        assert_eq!(summary, [
+            (SprintAction::AutoFix, 2, 2),
+            (SprintAction::Manual(CategoryType::Safety), 10, 10),
+            (SprintAction::Manual(CategoryType::Safety), 2, 2),
+            (SprintAction::Manual(CategoryType::Documentation), 1, 1),
+        ]);
+        assert_eq!(
+            batches[0].command().unwrap(),
+            "cargo clippy --fix --allow-dirty -- -A clippy::all -W clippy::len_zero -W clippy::needless_borrow"
+        );
+        assert!(batches[1].command().is_none());
+
+        let many: Vec<Warning> = (0..12)
+            .map(|i| warning("clippy::len_zero", CategoryType::Style, &format!("src/m{:02}.rs", i)))
+            .collect();
+        let batches = plan_sprints(&many, &FixExampleRegistry::default());
+        let files: Vec<usize> = batches.iter().map(|b| b.files().len()).collect();
+        assert_eq!(files, [MAX_BATCH_FILES, 2]);
+    }
+}
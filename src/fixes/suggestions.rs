@@ -8,7 +8,7 @@ pub struct FixSuggestion {
 }
 
 pub fn generate_fix_suggestion(warning: &Warning) -> Option<FixSuggestion> {
-    let subcategory = warning.message.split_whitespace().next().unwrap_or("");
+    let subcategory = warning.primary_message.split_whitespace().next().unwrap_or("");
     
     // First try to get a specific fix based on the message
     if let Some(fix) = get_specific_fix(subcategory) {
@@ -21,6 +21,7 @@ pub fn generate_fix_suggestion(warning: &Warning) -> Option<FixSuggestion> {
         CategoryType::Safety => generate_safety_suggestion(warning),
         CategoryType::Performance => generate_performance_suggestion(warning),
         CategoryType::Documentation => generate_documentation_suggestion(warning),
+        CategoryType::Uncategorized => None,
     }
 }
 
@@ -45,7 +46,7 @@ fn get_specific_fix(clippy_code: &str) -> Option<FixSuggestion> {
 }
 
 fn generate_performance_suggestion(warning: &Warning) -> Option<FixSuggestion> {
-    let subcategory = warning.message.split_whitespace().next().unwrap_or("");
+    let subcategory = warning.primary_message.split_whitespace().next().unwrap_or("");
     match subcategory {
         "Allocation" => Some(FixSuggestion {
             code: "// Consider using a pre-allocated buffer\nlet mut buffer = Vec::with_capacity(size);".to_string(),
@@ -62,7 +63,7 @@ fn generate_performance_suggestion(warning: &Warning) -> Option<FixSuggestion> {
 }
 
 fn generate_safety_suggestion(warning: &Warning) -> Option<FixSuggestion> {
-    let subcategory = warning.message.split_whitespace().next().unwrap_or("");
+    let subcategory = warning.primary_message.split_whitespace().next().unwrap_or("");
     match subcategory {
         "UnsafeCode" => Some(FixSuggestion {
             code: "// Consider using safe alternatives\nslice.get(index).copied()".to_string(),
@@ -79,7 +80,7 @@ fn generate_safety_suggestion(warning: &Warning) -> Option<FixSuggestion> {
 }
 
 fn generate_style_suggestion(warning: &Warning) -> Option<FixSuggestion> {
-    let subcategory = warning.message.split_whitespace().next().unwrap_or("");
+    let subcategory = warning.primary_message.split_whitespace().next().unwrap_or("");
     match subcategory {
         "NamingConvention" => Some(FixSuggestion {
             code: "// Follow Rust naming conventions\npub struct MyType {}".to_string(),
@@ -96,7 +97,7 @@ fn generate_style_suggestion(warning: &Warning) -> Option<FixSuggestion> {
 }
 
 fn generate_documentation_suggestion(warning: &Warning) -> Option<FixSuggestion> {
-    let subcategory = warning.message.split_whitespace().next().unwrap_or("");
+    let subcategory = warning.primary_message.split_whitespace().next().unwrap_or("");
     match subcategory {
         "MissingDocs" => Some(FixSuggestion {
             code: "/// Brief description of the item\n/// \n/// # Examples\n/// ```\n/// // Add example here\n/// ```".to_string(),
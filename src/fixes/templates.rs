@@ -23,6 +23,7 @@ pub fn write_category_templates(file: &mut File, category: CategoryType) -> std:
         CategoryType::Performance => "Performance Improvements",
         CategoryType::Style => "Style Guidelines",
         CategoryType::Documentation => "Documentation Guidelines",
+        CategoryType::Uncategorized => "General Guidelines",
     };
 
     writeln!(file, "\n=== {} ===\n", header)?;
@@ -33,6 +34,7 @@ pub fn write_category_templates(file: &mut File, category: CategoryType) -> std:
         CategoryType::Performance => write_performance_templates(file)?,
         CategoryType::Style => write_style_templates(file)?,
         CategoryType::Documentation => write_documentation_templates(file)?,
+        CategoryType::Uncategorized => writeln!(file, "// See the lint's documentation for how to fix it")?,
     }
 
     Ok(())
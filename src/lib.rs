@@ -13,24 +13,49 @@
 //! 
 //! ## Module Structure
 //! 
-//! - `analysis`: Statistical analysis and trend tracking
+//! - `analysis`: Statistical analysis and trend tracking, with the in-memory
+//!   pipeline in `analysis::pipeline`
+//! - `config`: Configuration file loading
 //! - `fixes`: Fix suggestions and example generation
 //! - `output`: Report generation and formatting
 //! - `parser`: Warning parsing and categorization
-//! - `runner`: Analysis execution and workflow management
+//! - `runner`: Analysis execution and workflow management (requires the
+//!   `terminal` feature)
 //! - `types`: Core type definitions and enums
+//!
+//! ## Features
+//!
+//! - `terminal` (default): the command line workflow, colored terminal
+//!   output, and fix previews. Embedders that only parse and analyze can
+//!   depend on the crate with `default-features = false`.
+//! - `sqlite`: a history database of runs
+//! - `tui`: the full-screen `--tui` dashboard (implies `terminal`)
+//! - `templates`: Handlebars templates for the markdown and HTML reports
 //! 
+//! ## Library and CLI
+//!
+//! The library works on in-memory data: the parser reads clippy output from a
+//! string or reader, and the analysis pipeline returns warnings and
+//! statistics without writing anything. Running clippy, report directories,
+//! and log files are the business of the `runner` module behind the
+//! `cargo-analyzer` binary.
+//!
 //! ## Usage Example
-//! 
+//!
 //! ```rust
-//! use cargo_analyzer::runner::workflow::ClippyWorkflow;
-//! 
-//! let workflow = ClippyWorkflow::new();
-//! workflow.run().expect("Failed to run analysis");
+//! use cargo_analyzer::analysis::pipeline::{AnalysisOptions, analyze};
+//! use cargo_analyzer::parser::WarningParser;
+//!
+//! let clippy_output = std::fs::read_to_string("clippy.json").unwrap_or_default();
+//! let parsed = WarningParser::new().parse_str(&clippy_output, 1);
+//! let analysis = analyze(parsed, &AnalysisOptions::new());
+//! println!("{} warnings", analysis.stats.total_warnings);
 //! ```
 
 pub mod analysis;
+pub mod config;
 pub mod output;
+#[cfg(feature = "terminal")]
 pub mod runner;
 pub mod parser;
 pub mod types;
@@ -39,7 +64,9 @@ pub mod fixes;
 // Re-export commonly used items
 pub use types::*;
 pub use analysis::*;
+pub use config::*;
 pub use output::*;
 pub use fixes::*;
 pub use parser::*;
+#[cfg(feature = "terminal")]
 pub use runner::*;
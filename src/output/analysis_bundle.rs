@@ -0,0 +1,273 @@
+//! Analysis bundle: the versioned output contract for tools built on the
+//! analyzer.
+//!
+//! `cargo analyzer bundle [DIR]` (also spelled `export-bundle`) writes one
+//! run's analysis as a directory of JSON files that editor extensions and
+//! dashboards read instead of parsing the human-oriented reports:
+//!
+//! ```text
+//! analysis_bundle/
+//! ├── manifest.json   format, version, generator, time, completeness, file list
+//! ├── warnings.json   array of warnings, schema/warning.schema.json
+//! ├── stats.json      totals by category, priority, lint, file, and source
+//! └── fixes.json      fixes index: one entry per warning with a known fix
+//! ```
+//!
+//! `manifest.json` carries `"format": "cargo-analyzer-bundle"` and a
+//! `version`, [`BUNDLE_VERSION`]. Within a version, fields are only ever
+//! added; removing or changing the meaning of one bumps the version, so
+//! readers should check it and ignore fields they do not know. Warnings and
+//! fixes refer to each other by warning `fingerprint`. Map keys and list
+//! entries are sorted. The manifest, statistics, and fixes index follow the
+//! `bundle-*.schema.json` schemas shipped with the crate.
+//!
+//! `fixes.json` lists, for each warning whose lint has a suggested fix, a fix
+//! example, or a suggestion in its diagnostic: its fingerprint, location,
+//! lint, `applicability` (`machine-applicable`, `maybe-incorrect`,
+//! `manual`, or null when unknown), `effort_minutes` (see
+//! `fixes::effort`), the `suggested_fix` text, and the fix `example` with
+//! `description`, `before`, and `after`.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use serde::Serialize;
+use crate::{
+    analysis::statistics::warning::WarningStatistics,
+    fixes::{
+        effort::{applicability, estimate_minutes},
+        examples::Applicability,
+        registry::FixExampleRegistry,
+    },
+    output::json_export::{JSON_SCHEMA_VERSION, write_json},
+    types::{Completeness, Warning},
+};
+
+/// Value of `format` in `manifest.json`
+pub const BUNDLE_FORMAT: &str = "cargo-analyzer-bundle";
+
+/// Version of the bundle layout
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// The JSON schema of `manifest.json`
+pub const BUNDLE_MANIFEST_SCHEMA: &str = include_str!("../../schema/bundle-manifest.schema.json");
+
+/// The JSON schema of `stats.json`
+pub const BUNDLE_STATS_SCHEMA: &str = include_str!("../../schema/bundle-stats.schema.json");
+
+/// The JSON schema of `fixes.json`
+pub const BUNDLE_FIXES_SCHEMA: &str = include_str!("../../schema/bundle-fixes.schema.json");
+
+/// Directory written when none is given
+pub const DEFAULT_BUNDLE_DIR: &str = "analysis_bundle";
+
+const MANIFEST_FILE: &str = "manifest.json";
+const WARNINGS_FILE: &str = "warnings.json";
+const STATS_FILE: &str = "stats.json";
+const FIXES_FILE: &str = "fixes.json";
+
+#[derive(Debug, Serialize)]
+pub struct BundleManifest {
+    pub format: &'static str,
+    pub version: u32,
+    /// Version of `schema/warning.schema.json` the warnings follow
+    pub warning_schema_version: u32,
+    /// `cargo-analyzer <version>`
+    pub generator: String,
+    /// RFC 3339 time of the analysis
+    pub generated_at: String,
+    /// Whether clippy finished on every target
+    pub complete: bool,
+    /// Why the analysis is incomplete, absent when it is complete
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incomplete_reason: Option<String>,
+    pub total_warnings: usize,
+    pub total_fixes: usize,
+    /// Files of the bundle by role: `warnings`, `stats`, `fixes`
+    pub files: BTreeMap<&'static str, &'static str>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleStats {
+    pub total_warnings: usize,
+    pub files_affected: usize,
+    pub errors: usize,
+    pub suppressed_warnings: usize,
+    pub false_positives: usize,
+    pub duplicates_removed: usize,
+    pub generated_excluded: usize,
+    pub third_party_warnings: usize,
+    pub by_category: BTreeMap<String, usize>,
+    pub by_priority: BTreeMap<String, usize>,
+    pub by_lint: BTreeMap<String, usize>,
+    pub by_file: BTreeMap<String, usize>,
+    pub by_source: BTreeMap<String, usize>,
+}
+
+impl From<&WarningStatistics> for BundleStats {
+    fn from(stats: &WarningStatistics) -> Self {
+        fn sorted<K: ToString>(counts: &std::collections::HashMap<K, usize>) -> BTreeMap<String, usize> {
+            counts.iter().map(|(key, count)| (key.to_string(), *count)).collect()
+        }
+        Self {
+            total_warnings: stats.total_warnings,
+            files_affected: stats.files_affected,
+            errors: stats.errors,
+            suppressed_warnings: stats.suppressed_warnings,
+            false_positives: stats.false_positives,
+            duplicates_removed: stats.duplicates_removed,
+            generated_excluded: stats.generated_excluded,
+            third_party_warnings: stats.third_party_warnings,
+            by_category: sorted(&stats.by_category),
+            by_priority: sorted(&stats.by_priority),
+            by_lint: sorted(&stats.by_lint),
+            by_file: sorted(&stats.by_file),
+            by_source: sorted(&stats.by_source),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FixExampleEntry {
+    pub description: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FixEntry {
+    pub fingerprint: String,
+    pub file: String,
+    pub line: u32,
+    pub lint_code: String,
+    pub applicability: Option<Applicability>,
+    pub effort_minutes: f64,
+    pub suggested_fix: Option<String>,
+    pub example: Option<FixExampleEntry>,
+}
+
+/// Entries of the fixes index, in file and line order.
+pub fn fix_index(warnings: &[Warning], examples: &FixExampleRegistry) -> Vec<FixEntry> {
+    let mut entries: Vec<FixEntry> = warnings
+        .iter()
+        .filter_map(|warning| {
+            let example = examples.get(warning);
+            let applicability = applicability(warning, examples);
+            if warning.suggested_fix.is_none() && example.is_none() && applicability.is_none() {
+                return None;
+            }
+            Some(FixEntry {
+                fingerprint: warning.fingerprint(),
+                file: warning.file.clone(),
+                line: warning.line,
+                lint_code: warning.lint_code.clone(),
+                applicability,
+                effort_minutes: estimate_minutes(warning, examples),
+                suggested_fix: warning.suggested_fix.clone(),
+                example: example.map(|example| FixExampleEntry {
+                    description: example.description,
+                    before: example.before,
+                    after: example.after,
+                }),
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| (&a.file, a.line, &a.lint_code).cmp(&(&b.file, b.line, &b.lint_code)));
+    entries
+}
+
+/// Writes the bundle of one run into `dir`, creating it if needed and
+/// replacing the bundle files already there.
+pub fn write_analysis_bundle(
+    dir: &Path,
+    warnings: &[Warning],
+    stats: &WarningStatistics,
+    completeness: &Completeness,
+    examples: &FixExampleRegistry,
+) -> io::Result<BundleManifest> {
+    std::fs::create_dir_all(dir)?;
+    // Written last, so a bundle with a manifest is whole
+    match std::fs::remove_file(dir.join(MANIFEST_FILE)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    let fixes = fix_index(warnings, examples);
+
+    let mut writer = BufWriter::new(File::create(dir.join(WARNINGS_FILE))?);
+    write_json(&mut writer, warnings)?;
+    writer.flush()?;
+    write_pretty(&dir.join(STATS_FILE), &BundleStats::from(stats))?;
+    write_pretty(&dir.join(FIXES_FILE), &fixes)?;
+
+    let manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        version: BUNDLE_VERSION,
+        warning_schema_version: JSON_SCHEMA_VERSION,
+        generator: format!("cargo-analyzer {}", env!("CARGO_PKG_VERSION")),
+        generated_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        complete: completeness.is_complete(),
+        incomplete_reason: (!completeness.is_complete()).then(|| completeness.to_string()),
+        total_warnings: warnings.len(),
+        total_fixes: fixes.len(),
+        files: BTreeMap::from([("warnings", WARNINGS_FILE), ("stats", STATS_FILE), ("fixes", FIXES_FILE)]),
+    };
+    write_pretty(&dir.join(MANIFEST_FILE), &manifest)?;
+    Ok(manifest)
+}
+
+fn write_pretty<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut writer, value)?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    #[test]
+    fn test_write_analysis_bundle() {
+        let warning = |lint: &str, line| Warning {
+            primary_message: "message".to_string(),
+            priority: Priority::Low,
+            fingerprint: format!("fp{}", line),
+            ..Warning::for_test(lint, "src/lib.rs", line)
+        };
+        let warnings = [warning("clippy::needless_borrow", 7), warning("clippy::some_unknown_lint", 3)];
+        let stats = WarningStatistics::from_warnings(&warnings, 1);
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let manifest =
+            write_analysis_bundle(dir, &warnings, &stats, &Completeness::Complete, &FixExampleRegistry::default()).unwrap();
+        assert_eq!((manifest.total_warnings, manifest.total_fixes), (2, 1));
+
+        let read = |name: &str| -> serde_json::Value {
+            serde_json::from_str(&std::fs::read_to_string(dir.join(name)).unwrap()).unwrap()
+        };
+        assert_eq!(read(MANIFEST_FILE)["format"], BUNDLE_FORMAT);
+        assert_eq!(read(STATS_FILE)["by_category"]["Style"], 2);
+        let fixes = read(FIXES_FILE);
+        assert_eq!(fixes[0]["fingerprint"], "fp7");
+        assert_eq!(fixes[0]["applicability"], "machine-applicable");
+        assert_eq!(read(WARNINGS_FILE).as_array().unwrap().len(), 2);
+
+        let stats = read(STATS_FILE);
+        for (schema, written) in [
+            (BUNDLE_MANIFEST_SCHEMA, &read(MANIFEST_FILE)),
+            (BUNDLE_STATS_SCHEMA, &stats),
+            (BUNDLE_FIXES_SCHEMA, &fixes[0]),
+        ] {
+            let schema: serde_json::Value = serde_json::from_str(schema).unwrap();
+            let schema = schema.get("items").unwrap_or(&schema);
+            let properties = schema["properties"].as_object().unwrap();
+            let written = written.as_object().unwrap();
+            for required in schema["required"].as_array().unwrap() {
+                assert!(written.contains_key(required.as_str().unwrap()), "missing {}", required);
+            }
+            assert!(written.keys().all(|key| properties.contains_key(key)), "undocumented field in {:?}", written);
+        }
+    }
+}
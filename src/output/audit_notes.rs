@@ -0,0 +1,136 @@
+//! Supply-chain audit notes.
+//!
+//! Summarizes Safety findings per crate as TOML, one table per crate with a
+//! `notes` string that can be pasted into a cargo-vet audit entry (or any
+//! other dependency review record). Crates that produced warnings but no
+//! safety findings are listed too, so the absence of findings is recorded.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use serde::Serialize;
+use crate::{
+    analysis::statistics::warning::UNKNOWN_CRATE,
+    types::{CategoryType, Warning},
+};
+
+/// Locations listed per lint before the rest are summarized as a count
+const MAX_LOCATIONS_PER_LINT: usize = 5;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CrateAuditNote {
+    pub safety_findings: usize,
+    pub lints: Vec<String>,
+    pub notes: String,
+}
+
+/// Audit notes keyed by crate name.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct AuditNotes {
+    pub crates: BTreeMap<String, CrateAuditNote>,
+}
+
+impl AuditNotes {
+    pub fn from_warnings(warnings: &[Warning], date: &str) -> Self {
+        let mut by_crate: BTreeMap<String, BTreeMap<&str, Vec<&Warning>>> = BTreeMap::new();
+        for warning in warnings {
+            let crate_name = warning.crate_name.clone().unwrap_or_else(|| UNKNOWN_CRATE.to_string());
+            let lints = by_crate.entry(crate_name).or_default();
+            if warning.category == CategoryType::Safety {
+                lints.entry(warning.lint_code.as_str()).or_default().push(warning);
+            }
+        }
+
+        let crates = by_crate
+            .into_iter()
+            .map(|(crate_name, lints)| {
+                let safety_findings = lints.values().map(Vec::len).sum();
+                let note = CrateAuditNote {
+                    safety_findings,
+                    lints: lints.keys().map(|lint| lint.to_string()).collect(),
+                    notes: render_notes(&lints, safety_findings, date),
+                };
+                (crate_name, note)
+            })
+            .collect();
+
+        Self { crates }
+    }
+}
+
+fn render_notes(lints: &BTreeMap<&str, Vec<&Warning>>, total: usize, date: &str) -> String {
+    if total == 0 {
+        return format!("cargo-analyzer ({}): clippy reported no safety findings.\n", date);
+    }
+
+    let mut notes = format!("cargo-analyzer ({}): {} clippy safety finding(s).\n", date, total);
+    for (lint, warnings) in lints {
+        let mut locations: Vec<String> = warnings
+            .iter()
+            .take(MAX_LOCATIONS_PER_LINT)
+            .map(|w| format!("{}:{}", w.file, w.line))
+            .collect();
+        if warnings.len() > MAX_LOCATIONS_PER_LINT {
+            locations.push(format!("and {} more", warnings.len() - MAX_LOCATIONS_PER_LINT));
+        }
+        notes.push_str(&format!("- {} ({}): {}\n", lint, warnings.len(), locations.join(", ")));
+    }
+    notes
+}
+
+pub fn write_audit_notes<W: Write>(mut writer: W, notes: &AuditNotes) -> io::Result<()> {
+    writeln!(writer, "# Safety findings per crate from cargo-analyzer.")?;
+    writeln!(writer, "# Copy a crate's `notes` into its cargo-vet audit entry after review.\n")?;
+    let toml = toml::to_string_pretty(notes)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    writer.write_all(toml.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warning(crate_name: &str, lint_code: &str, category: CategoryType, line: u32) -> Warning {
+        Warning {
+            category,
+            crate_name: Some(crate_name.to_string()),
+            ..Warning::for_test(lint_code, "src/lib.rs", line)
+        }
+    }
+
+    #[test]
+    fn test_audit_notes() {
+        let mut warnings: Vec<Warning> = (1..=7)
+            .map(|line| warning("core", "clippy::undocumented_unsafe_blocks", CategoryType::Safety, line))
+            .collect();
+        warnings.push(warning("core", "clippy::mem_forget", CategoryType::Safety, 20));
+        warnings.push(warning("core", "clippy::len_zero", CategoryType::Style, 30));
+        warnings.push(warning("cli", "clippy::len_zero", CategoryType::Style, 4));
+
+        let notes = AuditNotes::from_warnings(&warnings, "2024-01-01");
+        assert_eq!(notes.crates.keys().collect::<Vec<_>>(), ["cli", "core"]);
+
+        let cli = &notes.crates["cli"];
+        assert_eq!(cli.safety_findings, 0);
+        assert!(cli.lints.is_empty());
+        assert_eq!(cli.notes, "cargo-analyzer (2024-01-01): clippy reported no safety findings.\n");
+
+        let core = &notes.crates["core"];
+        assert_eq!(core.safety_findings, 8);
+        assert_eq!(core.lints, ["clippy::mem_forget", "clippy::undocumented_unsafe_blocks"]);
+        assert_eq!(
+            core.notes,
+            "cargo-analyzer (2024-01-01): 8 clippy safety finding(s).\n\
+             - clippy::mem_forget (1): src/lib.rs:20\n\
+             - clippy::undocumented_unsafe_blocks (7): src/lib.rs:1, src/lib.rs:2, src/lib.rs:3, src/lib.rs:4, src/lib.rs:5, and 2 more\n"
+        );
+
+        let mut out = Vec::new();
+        write_audit_notes(&mut out, &notes).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let parsed: toml::Table = toml::from_str(&text).unwrap();
+        assert_eq!(parsed["core"]["safety-findings"].as_integer(), Some(8));
+        assert_eq!(parsed["core"]["notes"].as_str(), Some(core.notes.as_str()));
+    }
+}
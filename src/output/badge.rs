@@ -0,0 +1,138 @@
+//! Code-health badges.
+//!
+//! Each run can write an SVG badge, e.g. "clippy | 42 warnings", colored by
+//! the worst severity found, and the same badge as a shields.io endpoint
+//! JSON file. Both go to fixed file names in the reports directory, so a
+//! README can embed the one published by CI at a stable URL:
+//!
+//! ```markdown
+//! ![clippy](https://img.shields.io/endpoint?url=https://example.com/reports/clippy_badge.json)
+//! ```
+
+use std::io::{self, Write};
+use crate::{
+    analysis::statistics::warning::WarningStatistics,
+    output::html::escape_html,
+    types::{Completeness, Priority},
+};
+
+/// File name of the SVG badge
+pub const BADGE_SVG_FILE: &str = "clippy_badge.svg";
+/// File name of the shields.io endpoint JSON
+pub const BADGE_ENDPOINT_FILE: &str = "clippy_badge.json";
+
+/// Approximate width of a character of 11px Verdana, the badge font
+const CHAR_WIDTH: usize = 7;
+const PADDING: usize = 10;
+
+/// A badge's text and color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Badge {
+    pub label: String,
+    pub message: String,
+    /// shields.io color name
+    pub color: &'static str,
+}
+
+impl Badge {
+    /// `clippy | N warnings`, colored by the worst priority present; gray
+    /// when clippy did not finish, since the count may be too low.
+    pub fn from_stats(stats: &WarningStatistics, completeness: &Completeness) -> Self {
+        let count = stats.total_warnings;
+        let mut message = format!("{} warning{}", count, if count == 1 { "" } else { "s" });
+        let worst = [Priority::Critical, Priority::High, Priority::Medium, Priority::Low, Priority::Trivial]
+            .into_iter()
+            .find(|priority| stats.by_priority.get(priority).is_some_and(|count| *count > 0));
+        let color = if !completeness.is_complete() {
+            message.push_str(" (incomplete)");
+            "lightgrey"
+        } else {
+            match worst {
+                Some(Priority::Critical) => "red",
+                Some(Priority::High) => "orange",
+                Some(Priority::Medium) => "yellow",
+                Some(Priority::Low | Priority::Trivial) => "yellowgreen",
+                None => "brightgreen",
+            }
+        };
+        Self { label: "clippy".to_string(), message, color }
+    }
+
+    /// The hex value of the color, as shields.io renders it.
+    pub fn hex_color(&self) -> &'static str {
+        match self.color {
+            "red" => "#e05d44",
+            "orange" => "#fe7d37",
+            "yellow" => "#dfb317",
+            "yellowgreen" => "#a4a61d",
+            "brightgreen" => "#4c1",
+            _ => "#9f9f9f",
+        }
+    }
+}
+
+/// Writes `badge` as a flat-style SVG.
+pub fn write_badge_svg<W: Write>(mut writer: W, badge: &Badge) -> io::Result<()> {
+    let label_width = badge.label.chars().count() * CHAR_WIDTH + PADDING;
+    let message_width = badge.message.chars().count() * CHAR_WIDTH + PADDING;
+    let width = label_width + message_width;
+    let label = escape_html(&badge.label);
+    let message = escape_html(&badge.message);
+    writeln!(
+        writer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"20\" role=\"img\" aria-label=\"{label}: {message}\">\n\
+         <title>{label}: {message}</title>\n\
+         <linearGradient id=\"s\" x2=\"0\" y2=\"100%\"><stop offset=\"0\" stop-color=\"#bbb\" stop-opacity=\".1\"/><stop offset=\"1\" stop-opacity=\".1\"/></linearGradient>\n\
+         <clipPath id=\"r\"><rect width=\"{width}\" height=\"20\" rx=\"3\" fill=\"#fff\"/></clipPath>\n\
+         <g clip-path=\"url(#r)\"><rect width=\"{label_width}\" height=\"20\" fill=\"#555\"/><rect x=\"{label_width}\" width=\"{message_width}\" height=\"20\" fill=\"{color}\"/><rect width=\"{width}\" height=\"20\" fill=\"url(#s)\"/></g>\n\
+         <g fill=\"#fff\" text-anchor=\"middle\" font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"11\">\n\
+         <text x=\"{label_x}\" y=\"15\" fill=\"#010101\" fill-opacity=\".3\">{label}</text><text x=\"{label_x}\" y=\"14\">{label}</text>\n\
+         <text x=\"{message_x}\" y=\"15\" fill=\"#010101\" fill-opacity=\".3\">{message}</text><text x=\"{message_x}\" y=\"14\">{message}</text>\n\
+         </g>\n</svg>",
+        color = badge.hex_color(),
+        label_x = label_width / 2,
+        message_x = label_width + message_width / 2,
+    )
+}
+
+/// Writes `badge` in the shields.io endpoint schema.
+pub fn write_shields_endpoint<W: Write>(writer: W, badge: &Badge) -> io::Result<()> {
+    let endpoint = serde_json::json!({
+        "schemaVersion": 1,
+        "label": badge.label,
+        "message": badge.message,
+        "color": badge.color,
+    });
+    serde_json::to_writer_pretty(writer, &endpoint).map_err(io::Error::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_badge_color_follows_worst_priority() {
+        let stats = WarningStatistics {
+            total_warnings: 42,
+            by_priority: [(Priority::Low, 40), (Priority::High, 2)].into(),
+            ..WarningStatistics::default()
+        };
+        let badge = Badge::from_stats(&stats, &Completeness::Complete);
+        assert_eq!((badge.message.as_str(), badge.color), ("42 warnings", "orange"));
+
+        let clean = Badge::from_stats(&WarningStatistics::default(), &Completeness::Complete);
+        assert_eq!((clean.message.as_str(), clean.color), ("0 warnings", "brightgreen"));
+
+        let mut svg = Vec::new();
+        write_badge_svg(&mut svg, &badge).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains("aria-label=\"clippy: 42 warnings\""));
+        assert!(svg.contains("fill=\"#fe7d37\""));
+
+        let mut json = Vec::new();
+        write_shields_endpoint(&mut json, &badge).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json["schemaVersion"], 1);
+        assert_eq!(json["color"], "orange");
+    }
+}
@@ -0,0 +1,111 @@
+use std::io::{self, Write};
+use crate::{
+    analysis::{
+        baseline::{Baseline, BaselineSource},
+        statistics::delta::{CountDelta, StatsDelta},
+    },
+    types::{CategoryType, Priority, Warning},
+};
+
+/// Lints listed per section of the baseline report
+const MAX_LISTED_LINTS: usize = 20;
+/// Warnings listed per section of the baseline report
+const MAX_LISTED_WARNINGS: usize = 50;
+
+/// Writes the comparison of this run's `warnings` with a baseline branch.
+pub fn write_baseline_report<W: Write>(
+    mut writer: W,
+    baseline: &Baseline,
+    delta: &StatsDelta,
+    warnings: &[Warning],
+) -> io::Result<()> {
+    writeln!(writer, "# Baseline Comparison\n")?;
+    let branch = format!("`{}` at `{}`", baseline.branch, short_commit(&baseline.commit));
+    let compared = match &baseline.source {
+        BaselineSource::Store(path) => format!("{} (from the baseline store `{}`)", branch, path.display()),
+        BaselineSource::History => format!("{} (from the history database)", branch),
+        BaselineSource::File(path) => format!("the warnings saved in `{}`", path.display()),
+    };
+    writeln!(writer, "Compared with {}.\n", compared)?;
+
+    writeln!(writer, "| | Baseline | Current | Change |")?;
+    writeln!(writer, "|---|---:|---:|---:|")?;
+    write_row(&mut writer, "**Total warnings**", delta.total)?;
+    write_row(&mut writer, "Files affected", delta.files_affected)?;
+    for category in [
+        CategoryType::Safety,
+        CategoryType::Performance,
+        CategoryType::Documentation,
+        CategoryType::Style,
+        CategoryType::Uncategorized,
+    ] {
+        write_row(&mut writer, &category.to_string(), delta.category(&category))?;
+    }
+    for priority in [Priority::Critical, Priority::High, Priority::Medium, Priority::Low, Priority::Trivial] {
+        write_row(&mut writer, &format!("{} priority", priority), delta.priority(&priority))?;
+    }
+    writeln!(writer)?;
+
+    write_lints(&mut writer, "New Lints", &delta.new_lints())?;
+    write_lints(&mut writer, "Regressed Lints", &delta.regressed_lints())?;
+    write_lints(&mut writer, "Fixed Lints", &delta.fixed_lints())?;
+
+    match baseline.warning_diff(warnings) {
+        Some(diff) => {
+            write_warnings(&mut writer, "New Warnings", &diff.new, diff.new.len())?;
+            let fixed = baseline.fixed_warnings(&diff);
+            if fixed.is_empty() && diff.fixed_count() > 0 {
+                writeln!(writer, "## Fixed Warnings\n\n{} warnings of the baseline are gone.\n", diff.fixed_count())?;
+            } else {
+                write_warnings(&mut writer, "Fixed Warnings", &fixed, diff.fixed_count())?;
+            }
+        }
+        None => writeln!(writer, "_The baseline was recorded without fingerprints; warnings are compared by count only._")?,
+    }
+    Ok(())
+}
+
+fn write_warnings<W: Write>(writer: &mut W, title: &str, warnings: &[&Warning], total: usize) -> io::Result<()> {
+    if total == 0 {
+        return Ok(());
+    }
+    writeln!(writer, "## {}\n", title)?;
+    for warning in warnings.iter().take(MAX_LISTED_WARNINGS) {
+        writeln!(
+            writer,
+            "- `{}:{}` `{}` {} (`{}`)",
+            warning.file,
+            warning.line,
+            warning.lint_code,
+            warning.primary_message,
+            warning.fingerprint()
+        )?;
+    }
+    if total > MAX_LISTED_WARNINGS {
+        writeln!(writer, "- ... and {} more", total - MAX_LISTED_WARNINGS)?;
+    }
+    writeln!(writer)
+}
+
+/// First 12 characters of a commit hash.
+pub fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
+
+fn write_row<W: Write>(writer: &mut W, label: &str, delta: CountDelta) -> io::Result<()> {
+    writeln!(writer, "| {} | {} | {} | {:+} |", label, delta.previous, delta.current, delta.change())
+}
+
+fn write_lints<W: Write>(writer: &mut W, title: &str, lints: &[(&str, usize)]) -> io::Result<()> {
+    if lints.is_empty() {
+        return Ok(());
+    }
+    writeln!(writer, "## {}\n", title)?;
+    for (lint, count) in lints.iter().take(MAX_LISTED_LINTS) {
+        writeln!(writer, "- `{}`: {}", lint, count)?;
+    }
+    if lints.len() > MAX_LISTED_LINTS {
+        writeln!(writer, "- ... and {} more", lints.len() - MAX_LISTED_LINTS)?;
+    }
+    writeln!(writer)
+}
@@ -0,0 +1,320 @@
+//! Compact protobuf export of warnings.
+//!
+//! Encodes a run's warnings following `proto/warnings.proto`, shipped with
+//! the crate, with file paths and crate names interned. Intended for very
+//! large runs where pretty JSON is slow to write and ingest.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use prost::Message;
+use crate::types::{CategoryType, Priority, Source, Span, Warning};
+
+/// The protobuf schema the export follows
+pub const PROTO_SCHEMA: &str = include_str!("../../proto/warnings.proto");
+
+/// Current `WarningSet.version`
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum ProtoCategory {
+    Unspecified = 0,
+    Safety = 1,
+    Performance = 2,
+    Style = 3,
+    Documentation = 4,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum ProtoPriority {
+    Unspecified = 0,
+    Critical = 1,
+    High = 2,
+    Medium = 3,
+    Low = 4,
+    Trivial = 5,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum ProtoSource {
+    Clippy = 0,
+    Rustc = 1,
+    Rustfmt = 2,
+    External = 3,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ProtoWarning {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub message: String,
+    #[prost(enumeration = "ProtoCategory", tag = "3")]
+    pub category: i32,
+    #[prost(enumeration = "ProtoPriority", tag = "4")]
+    pub priority: i32,
+    #[prost(uint32, tag = "5")]
+    pub file_index: u32,
+    #[prost(uint32, tag = "6")]
+    pub line: u32,
+    #[prost(string, optional, tag = "7")]
+    pub suggested_fix: Option<String>,
+    #[prost(uint32, optional, tag = "8")]
+    pub crate_index: Option<u32>,
+    #[prost(enumeration = "ProtoSource", tag = "9")]
+    pub source: i32,
+    #[prost(bool, tag = "10")]
+    pub is_error: bool,
+    #[prost(float, optional, tag = "11")]
+    pub category_confidence: Option<f32>,
+    #[prost(message, optional, tag = "12")]
+    pub span: Option<ProtoSpan>,
+}
+
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct ProtoSpan {
+    #[prost(uint32, tag = "1")]
+    pub line_start: u32,
+    #[prost(uint32, tag = "2")]
+    pub line_end: u32,
+    #[prost(uint32, tag = "3")]
+    pub column_start: u32,
+    #[prost(uint32, tag = "4")]
+    pub column_end: u32,
+    #[prost(uint64, optional, tag = "5")]
+    pub byte_start: Option<u64>,
+    #[prost(uint64, optional, tag = "6")]
+    pub byte_end: Option<u64>,
+}
+
+impl From<Span> for ProtoSpan {
+    fn from(span: Span) -> Self {
+        Self {
+            line_start: span.line_start,
+            line_end: span.line_end,
+            column_start: span.column_start,
+            column_end: span.column_end,
+            byte_start: span.byte_start.map(|b| b as u64),
+            byte_end: span.byte_end.map(|b| b as u64),
+        }
+    }
+}
+
+impl From<ProtoSpan> for Span {
+    fn from(span: ProtoSpan) -> Self {
+        Self {
+            line_start: span.line_start,
+            line_end: span.line_end,
+            column_start: span.column_start,
+            column_end: span.column_end,
+            byte_start: span.byte_start.map(|b| b as usize),
+            byte_end: span.byte_end.map(|b| b as usize),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct WarningSet {
+    #[prost(uint32, tag = "1")]
+    pub version: u32,
+    #[prost(string, tag = "2")]
+    pub timestamp: String,
+    #[prost(string, repeated, tag = "3")]
+    pub files: Vec<String>,
+    #[prost(string, repeated, tag = "4")]
+    pub crates: Vec<String>,
+    #[prost(message, repeated, tag = "5")]
+    pub warnings: Vec<ProtoWarning>,
+}
+
+impl WarningSet {
+    pub fn from_warnings(warnings: &[Warning], timestamp: &str) -> Self {
+        let mut files = Interner::default();
+        let mut crates = Interner::default();
+        let warnings = warnings
+            .iter()
+            .map(|w| ProtoWarning {
+                id: w.lint_code.clone(),
+                message: w.primary_message.clone(),
+                category: ProtoCategory::from(w.category) as i32,
+                priority: ProtoPriority::from(w.priority) as i32,
+                file_index: files.intern(&w.file),
+                line: w.line,
+                suggested_fix: w.suggested_fix.clone(),
+                crate_index: w.crate_name.as_deref().map(|name| crates.intern(name)),
+                source: match w.source {
+                    Source::Clippy => ProtoSource::Clippy,
+                    Source::Rustc => ProtoSource::Rustc,
+                    Source::Rustfmt => ProtoSource::Rustfmt,
+                    Source::External => ProtoSource::External,
+                } as i32,
+                is_error: w.is_error,
+                category_confidence: (w.category_confidence < 1.0).then_some(w.category_confidence),
+                span: w.span.map(ProtoSpan::from),
+            })
+            .collect();
+
+        Self {
+            version: SCHEMA_VERSION,
+            timestamp: timestamp.to_string(),
+            files: files.values,
+            crates: crates.values,
+            warnings,
+        }
+    }
+
+    /// Decodes an export produced by [`write_binary_export`].
+    pub fn decode_from(bytes: &[u8]) -> io::Result<Self> {
+        Self::decode(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Expands the set back into warnings.
+    pub fn to_warnings(&self) -> io::Result<Vec<Warning>> {
+        let lookup = |values: &[String], index: u32| {
+            values.get(index as usize).cloned().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("Index {} out of range", index))
+            })
+        };
+
+        self.warnings
+            .iter()
+            .map(|w| {
+                Ok(Warning {
+                    lint_code: w.id.clone(),
+                    primary_message: w.message.clone(),
+                    span: w.span.map(Span::from),
+                    explanations: Vec::new(),
+                    children: Vec::new(),
+                    rendered: None,
+                    category: category_from_proto(w.category)?,
+                    priority: priority_from_proto(w.priority)?,
+                    file: lookup(&self.files, w.file_index)?,
+                    line: w.line,
+                    suggested_fix: w.suggested_fix.clone(),
+                    crate_name: w.crate_index.map(|i| lookup(&self.crates, i)).transpose()?,
+                    source: match ProtoSource::try_from(w.source) {
+                        Ok(ProtoSource::Rustc) => Source::Rustc,
+                        Ok(ProtoSource::Rustfmt) => Source::Rustfmt,
+                        Ok(ProtoSource::External) => Source::External,
+                        _ => Source::Clippy,
+                    },
+                    is_error: w.is_error,
+                    category_confidence: w.category_confidence.unwrap_or(1.0),
+                    raw: None,
+                    fingerprint: String::new(),
+                    macro_origin: None,
+                    safety_subcategory: None,
+                })
+            })
+            .collect()
+    }
+}
+
+pub fn write_binary_export<W: Write>(mut writer: W, set: &WarningSet) -> io::Result<()> {
+    writer.write_all(&set.encode_to_vec())
+}
+
+#[derive(Default)]
+struct Interner {
+    values: Vec<String>,
+    indices: HashMap<String, u32>,
+}
+
+impl Interner {
+    fn intern(&mut self, value: &str) -> u32 {
+        if let Some(&index) = self.indices.get(value) {
+            return index;
+        }
+        let index = self.values.len() as u32;
+        self.values.push(value.to_string());
+        self.indices.insert(value.to_string(), index);
+        index
+    }
+}
+
+impl From<CategoryType> for ProtoCategory {
+    fn from(category: CategoryType) -> Self {
+        match category {
+            CategoryType::Safety => ProtoCategory::Safety,
+            CategoryType::Performance => ProtoCategory::Performance,
+            CategoryType::Style => ProtoCategory::Style,
+            CategoryType::Documentation => ProtoCategory::Documentation,
+            CategoryType::Uncategorized => ProtoCategory::Unspecified,
+        }
+    }
+}
+
+impl From<Priority> for ProtoPriority {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Critical => ProtoPriority::Critical,
+            Priority::High => ProtoPriority::High,
+            Priority::Medium => ProtoPriority::Medium,
+            Priority::Low => ProtoPriority::Low,
+            Priority::Trivial => ProtoPriority::Trivial,
+        }
+    }
+}
+
+fn category_from_proto(value: i32) -> io::Result<CategoryType> {
+    match ProtoCategory::try_from(value) {
+        Ok(ProtoCategory::Safety) => Ok(CategoryType::Safety),
+        Ok(ProtoCategory::Performance) => Ok(CategoryType::Performance),
+        Ok(ProtoCategory::Style) => Ok(CategoryType::Style),
+        Ok(ProtoCategory::Documentation) => Ok(CategoryType::Documentation),
+        Ok(ProtoCategory::Unspecified) => Ok(CategoryType::Uncategorized),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, format!("Invalid category {}", value))),
+    }
+}
+
+fn priority_from_proto(value: i32) -> io::Result<Priority> {
+    match ProtoPriority::try_from(value) {
+        Ok(ProtoPriority::Critical) => Ok(Priority::Critical),
+        Ok(ProtoPriority::High) => Ok(Priority::High),
+        Ok(ProtoPriority::Medium) => Ok(Priority::Medium),
+        Ok(ProtoPriority::Low) => Ok(Priority::Low),
+        Ok(ProtoPriority::Trivial) => Ok(Priority::Trivial),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, format!("Invalid priority {}", value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let warning = |file: &str, crate_name: Option<&str>| Warning {
+            primary_message: "used `unwrap()`".to_string(),
+            span: Some(Span::new(7, 5, 7, 13)),
+            category: CategoryType::Safety,
+            priority: Priority::Critical,
+            crate_name: crate_name.map(str::to_string),
+            source: Source::Rustc,
+            category_confidence: 0.6,
+            ..Warning::for_test("clippy::unwrap_used", file, 7)
+        };
+        let warnings = vec![
+            warning("src/a.rs", Some("demo")),
+            warning("src/b.rs", None),
+            warning("src/a.rs", Some("demo")),
+        ];
+
+        let mut bytes = Vec::new();
+        write_binary_export(&mut bytes, &WarningSet::from_warnings(&warnings, "20240101_000000")).unwrap();
+        let set = WarningSet::decode_from(&bytes).unwrap();
+
+        assert_eq!(set.files, vec!["src/a.rs", "src/b.rs"]);
+        let decoded = set.to_warnings().unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[1].file, "src/b.rs");
+        assert_eq!(decoded[1].crate_name, None);
+        assert_eq!(decoded[2].crate_name.as_deref(), Some("demo"));
+        assert_eq!(decoded[0].priority, Priority::Critical);
+        assert_eq!(decoded[0].source, Source::Rustc);
+        assert_eq!(decoded[0].category_confidence, 0.6);
+        assert_eq!(decoded[0].span, Some(Span::new(7, 5, 7, 13)));
+    }
+}
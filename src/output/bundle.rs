@@ -0,0 +1,91 @@
+//! Report artifacts and named bundles of them.
+//!
+//! By default every core artifact is written, plus the optional ones
+//! enabled by flags. A bundle names the artifacts a pipeline needs, so it
+//! can ask for them with `--bundle NAME` instead of a list of flags:
+//!
+//! ```toml
+//! [bundles]
+//! release-report = ["summary", "trend", "fix-plan"]
+//! pr-report = ["delta", "gate"]
+//! ```
+//!
+//! With a bundle (or an `artifacts = [...]` list), only the listed
+//! artifacts are written, optional ones included, plus any requested by
+//! flags on the command line. `trend` is an alias of `analysis`, which
+//! holds the trend analysis, `delta` of `baseline`, and `gate` of
+//! `merge-status`. `baseline` still needs `auto-baseline` to be set.
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Artifact {
+    /// File-by-file markdown report
+    Report,
+    /// HTML summary
+    Summary,
+    /// Markdown analysis with charts and trends
+    #[serde(alias = "trend", alias = "trends")]
+    Analysis,
+    FixPlan,
+    Json,
+    Csv,
+    /// SVG charts
+    Charts,
+    Jsonl,
+    Protobuf,
+    #[serde(alias = "gate")]
+    MergeStatus,
+    /// Comparison with the baseline branch
+    #[serde(alias = "delta")]
+    Baseline,
+    FalsePositives,
+    AuditNotes,
+    Playbooks,
+    Badge,
+    Prometheus,
+    PrComment,
+    /// Every diagnostic's rendered text, grouped by file
+    #[serde(alias = "rendered")]
+    RenderedDiagnostics,
+    Sarif,
+}
+
+impl Artifact {
+    /// Artifacts written on every run unless a selection leaves them out
+    pub const CORE: [Artifact; 7] = [
+        Artifact::Report,
+        Artifact::Summary,
+        Artifact::Analysis,
+        Artifact::FixPlan,
+        Artifact::Json,
+        Artifact::Csv,
+        Artifact::Charts,
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AnalyzerConfig;
+
+    #[test]
+    fn test_bundle_selects_artifacts() {
+        let mut config: AnalyzerConfig = toml::from_str(
+            "[bundles]\nrelease-report = [\"summary\", \"trend\", \"fix-plan\"]\npr-report = [\"delta\", \"gate\", \"badge\"]\n",
+        )
+        .unwrap();
+        assert!(config.writes(Artifact::Report));
+        assert!(config.select_bundle("nightly").is_err());
+
+        config.select_bundle("pr-report").unwrap();
+        config.enable_selected_artifacts();
+        assert_eq!(config.artifacts.as_deref(), Some(&[Artifact::Baseline, Artifact::MergeStatus, Artifact::Badge][..]));
+        assert!(config.merge_queue_status && config.badge);
+        assert!(!config.writes(Artifact::Summary));
+
+        config.select_bundle("release-report").unwrap();
+        assert!(config.writes(Artifact::Analysis) && !config.writes(Artifact::Csv));
+    }
+}
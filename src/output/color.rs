@@ -1,19 +1,37 @@
 use std::io::Write;
-use termcolor::{ColorChoice, ColorSpec, StandardStream, WriteColor};
+use termcolor::{ColorChoice, ColorSpec, NoColor, StandardStream, WriteColor};
 
 pub struct ColorWriter {
-    stdout: StandardStream,
+    stdout: Box<dyn WriteColor>,
+}
+
+impl Default for ColorWriter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ColorWriter {
     pub fn new() -> Self {
+        Self::with_choice(ColorChoice::Auto)
+    }
+
+    /// Creates a stdout writer that colors according to `choice`.
+    pub fn with_choice(choice: ColorChoice) -> Self {
+        ColorWriter {
+            stdout: Box::new(StandardStream::stdout(choice)),
+        }
+    }
+
+    /// Creates a writer that discards all output, for embedding without a terminal.
+    pub fn silent() -> Self {
         ColorWriter {
-            stdout: StandardStream::stdout(ColorChoice::Auto),
+            stdout: Box::new(NoColor::new(std::io::sink())),
         }
     }
 
-    pub fn writer(&mut self) -> &mut StandardStream {
-        &mut self.stdout
+    pub fn writer(&mut self) -> &mut dyn WriteColor {
+        self.stdout.as_mut()
     }
 
     pub fn write_colored(&mut self, text: &str, color: termcolor::Color) -> std::io::Result<()> {
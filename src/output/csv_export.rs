@@ -0,0 +1,164 @@
+//! CSV export of warnings.
+//!
+//! Fields are quoted as needed, so messages keep their commas, quotes, and
+//! newlines. The columns, in their default order, are:
+//!
+//! | Column | Header | Value |
+//! |---|---|---|
+//! | `file` | File | Path relative to the workspace root |
+//! | `line` | Line | First line of the primary span |
+//! | `column` | Column | First column of the primary span, 0 if unknown |
+//! | `end-line` | End Line | Last line of the primary span |
+//! | `end-column` | End Column | Column after the span's end, 0 if unknown |
+//! | `category` | Category | `Safety`, `Performance`, `Style`, ... |
+//! | `message` | Message | Primary diagnostic message |
+//! | `priority` | Priority | `Critical`, `High`, `Medium`, `Low`, or `Trivial` |
+//! | `suggested-fix` | Suggested Fix | Empty when there is none |
+//!
+//! Not exported by default:
+//!
+//! | Column | Header | Value |
+//! |---|---|---|
+//! | `lint` | Lint | Lint code, e.g. `clippy::needless_borrow` |
+//! | `crate` | Crate | Crate the warning was reported for |
+//! | `source` | Source | Tool that reported the warning |
+//! | `fingerprint` | Fingerprint | Identifier used by baselines and triage |
+//! | `byte-start` | Byte Start | Byte offset of the span's start, empty if unknown |
+//! | `byte-end` | Byte End | Byte offset just past the span's end, empty if unknown |
+//!
+//! Column names and headers are stable; new columns are only ever added
+//! outside the default set.
+
+use std::io::{self, Write};
+#[cfg(feature = "terminal")]
+use clap::ValueEnum;
+use serde::Deserialize;
+use crate::types::{Span, Warning};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "terminal", derive(ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum CsvColumn {
+    File,
+    Line,
+    Column,
+    EndLine,
+    EndColumn,
+    Category,
+    Message,
+    Priority,
+    SuggestedFix,
+    Lint,
+    Crate,
+    Source,
+    Fingerprint,
+    ByteStart,
+    ByteEnd,
+}
+
+impl CsvColumn {
+    /// Columns exported when none are configured
+    pub const DEFAULT: [CsvColumn; 9] = [
+        CsvColumn::File,
+        CsvColumn::Line,
+        CsvColumn::Column,
+        CsvColumn::EndLine,
+        CsvColumn::EndColumn,
+        CsvColumn::Category,
+        CsvColumn::Message,
+        CsvColumn::Priority,
+        CsvColumn::SuggestedFix,
+    ];
+
+    pub fn header(self) -> &'static str {
+        match self {
+            CsvColumn::File => "File",
+            CsvColumn::Line => "Line",
+            CsvColumn::Column => "Column",
+            CsvColumn::EndLine => "End Line",
+            CsvColumn::EndColumn => "End Column",
+            CsvColumn::Category => "Category",
+            CsvColumn::Message => "Message",
+            CsvColumn::Priority => "Priority",
+            CsvColumn::SuggestedFix => "Suggested Fix",
+            CsvColumn::Lint => "Lint",
+            CsvColumn::Crate => "Crate",
+            CsvColumn::Source => "Source",
+            CsvColumn::Fingerprint => "Fingerprint",
+            CsvColumn::ByteStart => "Byte Start",
+            CsvColumn::ByteEnd => "Byte End",
+        }
+    }
+
+    fn value(self, warning: &Warning) -> String {
+        let span = warning.span.unwrap_or(Span::at(warning.line, 0));
+        match self {
+            CsvColumn::File => warning.file.clone(),
+            CsvColumn::Line => span.line_start.to_string(),
+            CsvColumn::Column => span.column_start.to_string(),
+            CsvColumn::EndLine => span.line_end.to_string(),
+            CsvColumn::EndColumn => span.column_end.to_string(),
+            CsvColumn::Category => format!("{:?}", warning.category),
+            CsvColumn::Message => warning.primary_message.clone(),
+            CsvColumn::Priority => format!("{:?}", warning.priority),
+            CsvColumn::SuggestedFix => warning.suggested_fix.clone().unwrap_or_default(),
+            CsvColumn::Lint => warning.lint_code.clone(),
+            CsvColumn::Crate => warning.crate_name.clone().unwrap_or_default(),
+            CsvColumn::Source => format!("{:?}", warning.source),
+            CsvColumn::Fingerprint => warning.fingerprint(),
+            CsvColumn::ByteStart => span.byte_start.map(|b| b.to_string()).unwrap_or_default(),
+            CsvColumn::ByteEnd => span.byte_end.map(|b| b.to_string()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Writes the warnings as CSV with a header row. An empty column list
+/// selects [`CsvColumn::DEFAULT`].
+pub fn write_csv<W: Write>(writer: W, warnings: &[Warning], columns: &[CsvColumn]) -> io::Result<()> {
+    let columns = if columns.is_empty() { &CsvColumn::DEFAULT[..] } else { columns };
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record(columns.iter().map(|column| column.header()))?;
+    for warning in warnings {
+        csv.write_record(columns.iter().map(|column| column.value(warning)))?;
+    }
+    csv.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    #[test]
+    fn test_csv_quoting_and_columns() {
+        let warning = Warning {
+            primary_message: "this, \"that\"\nand more".to_string(),
+            span: Some(Span::new(3, 5, 3, 9)),
+            priority: Priority::Low,
+            ..Warning::for_test("clippy::needless_borrow", "src/lib.rs", 3)
+        };
+
+        let mut output = Vec::new();
+        write_csv(&mut output, std::slice::from_ref(&warning), &[]).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("File,Line,Column,End Line,End Column,Category,Message,Priority,Suggested Fix\n"));
+        assert!(output.contains("src/lib.rs,3,5,3,9,Style,\"this, \"\"that\"\"\nand more\",Low,\n"));
+
+        let mut output = Vec::new();
+        write_csv(&mut output, &[warning], &[CsvColumn::Lint, CsvColumn::Line]).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "Lint,Line\nclippy::needless_borrow,3\n");
+    }
+
+    #[test]
+    fn test_csv_byte_offsets() {
+        let span = Span { byte_start: Some(40), byte_end: Some(52), ..Span::new(3, 8, 3, 20) };
+        let warnings = [
+            Warning { span: Some(span), ..Warning::for_test("clippy::len_zero", "src/lib.rs", 3) },
+            Warning::for_test("clippy::len_zero", "src/lib.rs", 7),
+        ];
+
+        let mut output = Vec::new();
+        write_csv(&mut output, &warnings, &[CsvColumn::Line, CsvColumn::ByteStart, CsvColumn::ByteEnd]).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "Line,Byte Start,Byte End\n3,40,52\n7,,\n");
+    }
+}
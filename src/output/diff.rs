@@ -0,0 +1,56 @@
+//! Line diffs of code snippets.
+
+#[cfg(feature = "terminal")]
+use clap::ValueEnum;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "terminal", derive(ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum DiffLayout {
+    #[default]
+    Unified,
+    SideBySide,
+}
+
+/// A line of a diff between two texts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Line `before[i]` and `after[j]` are identical
+    Same(usize, usize),
+    Removed(usize),
+    Added(usize),
+}
+
+/// Diffs two texts line by line, via their longest common subsequence.
+pub fn diff_lines(before: &[&str], after: &[&str]) -> Vec<DiffLine> {
+    let (n, m) = (before.len(), after.len());
+    // lcs[i][j] is the LCS length of before[i..] and after[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if before[i] == after[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && before[i] == after[j] {
+            lines.push(DiffLine::Same(i, j));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(DiffLine::Removed(i));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(j));
+            j += 1;
+        }
+    }
+    lines
+}
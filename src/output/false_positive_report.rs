@@ -0,0 +1,47 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use crate::{
+    analysis::false_positives::FlaggedWarning,
+    types::Source,
+};
+
+/// Issue form for reporting clippy false positives
+const CLIPPY_FALSE_POSITIVE_ISSUE: &str =
+    "https://github.com/rust-lang/rust-clippy/issues/new?template=false_positive.yml";
+
+/// Writes the suspected false positives grouped by lint, ready to be filed
+/// with clippy.
+pub fn write_false_positive_report<W: Write>(mut writer: W, flagged: &[FlaggedWarning]) -> io::Result<()> {
+    writeln!(writer, "# Suspected False Positives\n")?;
+    if flagged.is_empty() {
+        return writeln!(writer, "No warnings were marked as false positives.");
+    }
+
+    let mut by_lint: BTreeMap<&str, Vec<&FlaggedWarning>> = BTreeMap::new();
+    for entry in flagged {
+        by_lint.entry(&entry.warning.lint_code).or_default().push(entry);
+    }
+    writeln!(
+        writer,
+        "{} warnings across {} lints were marked during triage and excluded from the totals and gates.\n",
+        flagged.len(),
+        by_lint.len()
+    )?;
+
+    for (lint, entries) in by_lint {
+        writeln!(writer, "## `{}` ({})\n", lint, entries.len())?;
+        if entries[0].warning.source == Source::Clippy {
+            writeln!(writer, "Report upstream: {}\n", CLIPPY_FALSE_POSITIVE_ISSUE)?;
+        }
+        for entry in entries {
+            let warning = &entry.warning;
+            write!(writer, "- `{}:{}` {} (fingerprint `{}`)", warning.file, warning.line, warning.primary_message, warning.fingerprint())?;
+            match &entry.reason {
+                Some(reason) => writeln!(writer, "\n  - Why it is wrong: {}", reason)?,
+                None => writeln!(writer)?,
+            }
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
@@ -1,16 +1,38 @@
 use std::io::Write;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use crate::{
-    types::{Warning, CategoryType, Priority},
-    fixes::{examples::get_fix_example, suggestions::generate_fix_suggestion},
+    types::{Warning, CategoryType, Priority, RiskLevel},
+    fixes::{
+        batches::{FixBatch, batch_fixes},
+        effort::{Effort, estimate_minutes},
+        sprints::{MAX_BATCH_FILES, plan_sprints},
+        post_process::SuggestionPipeline,
+        registry::FixExampleRegistry,
+        suggestions::generate_fix_suggestion,
+    },
     analysis::{
         statistics::warning::WarningStatistics,
+        hotspots::Hotspot,
+        dependencies::UnusedDependencies,
+        functions::FunctionOffender,
+        normalize::subcategory,
         charts::{ChartConfig, ChartStyle, create_enhanced_chart},
     },
+    output::snippet::SnippetReader,
 };
 
+/// Functions listed in the worst functions section
+const WORST_FUNCTIONS: usize = 10;
+
 pub struct FixPlanGenerator<W: Write> {
     writer: W,
+    risk_levels: Vec<RiskLevel>,
+    hotspots: Vec<Hotspot>,
+    worst_functions: Vec<FunctionOffender>,
+    unused_deps: Option<UnusedDependencies>,
+    suggestion_pipeline: SuggestionPipeline,
+    fix_examples: FixExampleRegistry,
+    snippets: Option<SnippetReader>,
 }
 
 #[derive(Default)]
@@ -22,7 +44,67 @@ struct CategoryStats<'a> {
 
 impl<W: Write> FixPlanGenerator<W> {
     pub fn new(writer: W) -> Self {
-        Self { writer }
+        Self {
+            writer,
+            risk_levels: RiskLevel::defaults(),
+            hotspots: Vec::new(),
+            worst_functions: Vec::new(),
+            unused_deps: None,
+            suggestion_pipeline: SuggestionPipeline::default(),
+            fix_examples: FixExampleRegistry::default(),
+            snippets: None,
+        }
+    }
+
+    /// Uses custom risk levels; priorities they do not cover keep the defaults.
+    pub fn set_risk_levels(&mut self, risk_levels: &[RiskLevel]) {
+        self.risk_levels = RiskLevel::merge_with_defaults(risk_levels);
+    }
+
+    /// Lists these files in a "Fix These First" section.
+    pub fn set_hotspots(&mut self, hotspots: Vec<Hotspot>) {
+        self.hotspots = hotspots;
+    }
+
+    /// Lists the worst of these functions after the hotspots.
+    pub fn set_worst_functions(&mut self, functions: &[FunctionOffender]) {
+        self.worst_functions = functions.iter().take(WORST_FUNCTIONS).cloned().collect();
+    }
+
+    /// Lists removal steps for these dependencies.
+    pub fn set_unused_dependencies(&mut self, unused: UnusedDependencies) {
+        self.unused_deps = Some(unused);
+    }
+
+    /// Fix examples including user templates; built-ins only by default.
+    pub fn set_fix_examples(&mut self, fix_examples: FixExampleRegistry) {
+        self.fix_examples = fix_examples;
+    }
+
+    /// Passes fix suggestions through `pipeline` before rendering them.
+    pub fn set_suggestion_pipeline(&mut self, pipeline: SuggestionPipeline) {
+        self.suggestion_pipeline = pipeline;
+    }
+
+    /// Quotes the code of each group's first warning.
+    pub fn set_snippet_reader(&mut self, snippets: SnippetReader) {
+        self.snippets = Some(snippets);
+    }
+
+    fn risk_level(&self, priority: Priority) -> Option<&RiskLevel> {
+        self.risk_levels.iter().find(|level| level.priority == priority)
+    }
+
+    /// Estimated work to fix all of `warnings`.
+    fn effort<'a>(&self, warnings: impl IntoIterator<Item = &'a Warning>) -> Effort {
+        Effort(warnings.into_iter().map(|w| estimate_minutes(w, &self.fix_examples)).sum())
+    }
+
+    /// Severity points a batch removes per minute of estimated work.
+    fn batch_roi(&self, batch: &FixBatch) -> f64 {
+        let warnings = || batch.fixes.values().flatten().copied();
+        let severity: usize = warnings().map(|w| w.priority.severity_score() as usize).sum();
+        severity as f64 / self.effort(warnings()).0.max(1.0)
     }
 
     pub fn generate_plan(&mut self, warnings: &[Warning]) -> std::io::Result<()> {
@@ -35,8 +117,14 @@ impl<W: Write> FixPlanGenerator<W> {
             .collect::<HashSet<_>>()
             .len());
         self.write_statistics(&stats)?;
+        writeln!(self.writer, "Estimated effort: {} of work\n", self.effort(warnings))?;
         
+        self.write_compile_errors(warnings)?;
         self.write_risk_levels()?;
+        self.write_hotspots()?;
+        self.write_worst_functions()?;
+        self.write_unused_dependencies()?;
+        self.write_fix_batches(warnings)?;
 
         // Group warnings by priority and category
         let mut priority_groups: HashMap<Priority, HashMap<CategoryType, CategoryStats>> = HashMap::new();
@@ -45,7 +133,7 @@ impl<W: Write> FixPlanGenerator<W> {
             let priority = match warning.category {
                 CategoryType::Safety => Priority::Critical,
                 CategoryType::Performance => Priority::High,
-                CategoryType::Style => Priority::Low,
+                CategoryType::Style | CategoryType::Uncategorized => Priority::Low,
                 CategoryType::Documentation => Priority::Medium,
             };
 
@@ -58,7 +146,7 @@ impl<W: Write> FixPlanGenerator<W> {
             stats.count += 1;
             stats.files.insert(warning.file.clone());
             stats.subcategories
-                .entry(warning.message.clone())
+                .entry(subcategory(warning))
                 .or_default()
                 .push(warning);
         }
@@ -86,12 +174,7 @@ impl<W: Write> FixPlanGenerator<W> {
             .map(|(k, v)| (format!("{:?}", k), *v))
             .collect();
 
-        let chart_config = ChartConfig {
-            style: ChartStyle::Blocks,
-            color: None,
-            width: 50,
-            show_percentage: true,
-        };
+        let chart_config = ChartConfig::new(ChartStyle::Blocks, 50, true);
 
         let chart = create_enhanced_chart(&warning_counts, chart_config);
         
@@ -114,10 +197,13 @@ impl<W: Write> FixPlanGenerator<W> {
         priority: Priority,
         categories: &HashMap<CategoryType, CategoryStats>
     ) -> std::io::Result<()> {
-        writeln!(self.writer, "\n# {} Priority Warnings (Risk Level: {})\n", 
-            priority, 
-            priority.severity_score()
-        )?;
+        let (label, score) = match self.risk_level(priority) {
+            Some(level) => (level.label.clone(), level.score),
+            None => (priority.to_string(), priority.severity_score()),
+        };
+        writeln!(self.writer, "\n# {} Priority Warnings (Risk Level: {})\n", label, score)?;
+        let effort = self.effort(categories.values().flat_map(|stats| stats.subcategories.values().flatten().copied()));
+        writeln!(self.writer, "{} section: {} of work\n", label, effort)?;
 
         for (category, stats) in categories {
             self.write_category_section(category, stats)?;
@@ -153,18 +239,29 @@ impl<W: Write> FixPlanGenerator<W> {
         // Write impact assessment
         self.write_impact_assessment(category, warnings)?;
 
+        if let Some(snippet) = self.snippets.as_mut().and_then(|reader| reader.read(warnings[0])) {
+            writeln!(self.writer, "#### Code\n")?;
+            writeln!(self.writer, "`{}:{}`\n", snippet.file, warnings[0].line)?;
+            writeln!(self.writer, "{}", snippet.to_markdown())?;
+        }
+
         // Get both example and specific fix
-        if let Some(example) = get_fix_example(&warnings[0]) {
+        if let Some(example) = self.fix_examples.get(warnings[0]) {
             writeln!(self.writer, "#### Fix Template\n")?;
             writeln!(self.writer, "```rust")?;
             writeln!(self.writer, "{}", example.before)?;
             writeln!(self.writer, "\n// After applying fix:\n")?;
             writeln!(self.writer, "{}", example.after)?;
             writeln!(self.writer, "```\n")?;
+            if let Some(applicability) = example.applicability {
+                writeln!(self.writer, "Applicability: {}\n", applicability)?;
+            }
         }
 
         // Add specific fix suggestion if available
-        if let Some(fix) = generate_fix_suggestion(&warnings[0]) {
+        let fix = generate_fix_suggestion(warnings[0])
+            .map(|fix| self.suggestion_pipeline.apply(warnings[0], fix));
+        if let Some(fix) = fix {
             writeln!(self.writer, "#### Specific Fix\n")?;
             writeln!(self.writer, "```rust")?;
             writeln!(self.writer, "{}", fix.code)?;
@@ -172,35 +269,196 @@ impl<W: Write> FixPlanGenerator<W> {
             writeln!(self.writer, "Confidence: {:.0}%\n", fix.confidence * 100.0)?;
         }
 
-        // List all occurrences with more detail
+        // Identical messages share one block followed by their locations
         writeln!(self.writer, "#### All Occurrences\n")?;
-        for warning in warnings {
+        if let [warning] = warnings {
             writeln!(self.writer, "**{}:{}**", warning.file, warning.line)?;
-            writeln!(self.writer, "```")?;
-            writeln!(self.writer, "Message: {}", warning.message)?;
-            
-            // Format child messages properly
-            let child_messages = warning.message.lines()
-                .find(|line| line.contains("Child messages:"))
-                .map(|line| line.to_string());  // Create owned String
-
-            if let Some(msg) = child_messages {
-                let msg_content = msg.replace("Child messages: ", "")
-                    .trim_matches(|c| c == '[' || c == ']' || c == '"')
-                    .to_string();
-                let messages = msg_content.split("\", \"").collect::<Vec<_>>();
-                
-                writeln!(self.writer, "\nChild Messages:")?;
-                for msg in messages {
-                    writeln!(self.writer, "- {}", msg)?;
-                }
+        } else {
+            writeln!(self.writer, "**{} occurrences**\n", warnings.len())?;
+            let mut lines_by_file: BTreeMap<&str, Vec<u32>> = BTreeMap::new();
+            for warning in warnings {
+                lines_by_file.entry(&warning.file).or_default().push(warning.line);
             }
-            writeln!(self.writer, "```\n")?;
+            for (file, mut lines) in lines_by_file {
+                lines.sort_unstable();
+                let lines: Vec<String> = lines.iter().map(u32::to_string).collect();
+                writeln!(self.writer, "- `{}`: {}", file, lines.join(", "))?;
+            }
+            writeln!(self.writer)?;
+        }
+        self.write_message_block(warnings[0])?;
+
+        Ok(())
+    }
+
+    fn write_message_block(&mut self, warning: &Warning) -> std::io::Result<()> {
+        writeln!(self.writer, "```")?;
+        writeln!(self.writer, "Message: {}", warning.primary_message)?;
+        for explanation in &warning.explanations {
+            writeln!(self.writer, "Explanation: {}", explanation)?;
+        }
+
+        if !warning.children.is_empty() {
+            writeln!(self.writer, "\nChild Messages:")?;
+            for child in &warning.children {
+                writeln!(self.writer, "- {}", child)?;
+            }
+        }
+        writeln!(self.writer, "```\n")
+    }
+
+    fn write_hotspots(&mut self) -> std::io::Result<()> {
+        if self.hotspots.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(self.writer, "## Fix These First\n")?;
+        writeln!(self.writer, "Files ranked by warning count, severity, and recent git changes.\n")?;
+        writeln!(self.writer, "| # | File | Warnings | Severity | Commits | Score |")?;
+        writeln!(self.writer, "|---|------|---------:|---------:|--------:|------:|")?;
+        for (rank, hotspot) in self.hotspots.iter().enumerate() {
+            writeln!(
+                self.writer,
+                "| {} | `{}` | {} | {} | {} | {:.2} |",
+                rank + 1,
+                hotspot.file,
+                hotspot.warnings,
+                hotspot.severity,
+                hotspot.churn,
+                hotspot.score
+            )?;
+        }
+        writeln!(self.writer)
+    }
+
+    fn write_unused_dependencies(&mut self) -> std::io::Result<()> {
+        let Some(unused) = self.unused_deps.as_ref().filter(|unused| !unused.is_empty()) else {
+            return Ok(());
+        };
+
+        writeln!(self.writer, "## Unused Dependencies\n")?;
+        writeln!(
+            self.writer,
+            "Found by {}. Removing them shortens builds and shrinks the supply chain:\n",
+            unused.check
+        )?;
+        writeln!(self.writer, "```sh")?;
+        for dependency in &unused.dependencies {
+            writeln!(self.writer, "{}", dependency.removal_command())?;
+        }
+        writeln!(self.writer, "```\n")
+    }
+
+    /// Every warning in pull-request-sized sprint batches. Within a batch,
+    /// suggested fixes are grouped by module into commits, those removing
+    /// the most severity per minute of work first.
+    fn write_fix_batches(&mut self, warnings: &[Warning]) -> std::io::Result<()> {
+        let batches = plan_sprints(warnings, &self.fix_examples);
+        if batches.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(self.writer, "## Fix Batches\n")?;
+        writeln!(
+            self.writer,
+            "Every warning in pull-request-sized batches of at most {} files: automatic fixes first, \
+             then fixes to review, then manual work by category. Suggested fixes are grouped by \
+             module so each group can land as one commit.\n",
+            MAX_BATCH_FILES
+        )?;
+        for (number, batch) in batches.iter().enumerate() {
+            let files = batch.files();
+            writeln!(
+                self.writer,
+                "### Batch {}: {} — {} warnings of {} lints in {} files ({})\n",
+                number + 1,
+                batch.action,
+                batch.len(),
+                batch.warnings.len(),
+                files.len(),
+                batch.effort
+            )?;
+            if let Some(command) = batch.command() {
+                writeln!(self.writer, "```sh\n{}\n```\n", command)?;
+            }
+            for (lint, lint_warnings) in &batch.warnings {
+                writeln!(self.writer, "- `{}`: {}", lint, lint_warnings.len())?;
+            }
+            let files: Vec<String> = files.iter().map(|file| format!("`{}`", file)).collect();
+            writeln!(self.writer, "\nFiles: {}\n", files.join(", "))?;
+            self.write_fix_commits(batch.warnings.values().flatten().copied())?;
         }
+        Ok(())
+    }
 
+    /// The commits applying the suggested fixes of one batch, by module.
+    fn write_fix_commits<'a>(&mut self, warnings: impl IntoIterator<Item = &'a Warning>) -> std::io::Result<()> {
+        let mut commits: Vec<(FixBatch, f64)> = batch_fixes(warnings)
+            .into_iter()
+            .map(|commit| {
+                let roi = self.batch_roi(&commit);
+                (commit, roi)
+            })
+            .collect();
+        commits.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.module.cmp(&b.0.module)));
+        for (commit, roi) in &commits {
+            let effort = self.effort(commit.fixes.values().flatten().copied());
+            writeln!(
+                self.writer,
+                "#### `{}` ({} fixes, {}, {:.1} severity points per minute)\n",
+                commit.module,
+                commit.len(),
+                effort,
+                roi
+            )?;
+            writeln!(self.writer, "```")?;
+            writeln!(self.writer, "{}", commit.commit_message())?;
+            writeln!(self.writer, "```\n")?;
+            for (lint, fixes) in &commit.fixes {
+                let locations: Vec<String> = fixes.iter().map(|w| format!("{}:{}", w.file, w.line)).collect();
+                writeln!(self.writer, "- `{}`: {}", lint, locations.join(", "))?;
+            }
+            writeln!(self.writer)?;
+        }
         Ok(())
     }
 
+    fn write_compile_errors(&mut self, warnings: &[Warning]) -> std::io::Result<()> {
+        let errors: Vec<&Warning> = warnings.iter().filter(|w| w.is_error).collect();
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(self.writer, "## Fix Compile Errors First\n")?;
+        writeln!(self.writer, "The build failed; clippy cannot lint code that does not compile.\n")?;
+        for error in errors {
+            writeln!(self.writer, "- `{}` **{}:{}** {}", error.lint_code, error.file, error.line, error.primary_message)?;
+        }
+        writeln!(self.writer)
+    }
+
+    fn write_worst_functions(&mut self) -> std::io::Result<()> {
+        if self.worst_functions.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(self.writer, "## Worst Functions\n")?;
+        writeln!(self.writer, "| # | Function | File | Warnings | Severity |")?;
+        writeln!(self.writer, "|---|----------|------|---------:|---------:|")?;
+        for (rank, function) in self.worst_functions.iter().enumerate() {
+            writeln!(
+                self.writer,
+                "| {} | `{}` | `{}` | {} | {} |",
+                rank + 1,
+                function.function,
+                function.file,
+                function.warnings,
+                function.severity
+            )?;
+        }
+        writeln!(self.writer)
+    }
+
     fn write_header(&mut self) -> std::io::Result<()> {
         writeln!(self.writer, "# Comprehensive Fix Priority Plan\n")?;
         writeln!(self.writer, "This plan outlines all detected issues, prioritized by risk level and impact.\n")
@@ -213,11 +471,10 @@ impl<W: Write> FixPlanGenerator<W> {
 
     fn write_risk_levels(&mut self) -> std::io::Result<()> {
         writeln!(self.writer, "## Risk Level Definitions\n")?;
-        writeln!(self.writer, "- 5: Critical - Immediate action required (safety issues, potential bugs)")?;
-        writeln!(self.writer, "- 4: High - Should be fixed soon (correctness issues, performance problems)")?;
-        writeln!(self.writer, "- 3: Medium - Plan to fix (maintainability issues)")?;
-        writeln!(self.writer, "- 2: Low - Fix when convenient (style issues)")?;
-        writeln!(self.writer, "- 1: Trivial - Optional fixes\n")
+        for level in &self.risk_levels {
+            writeln!(self.writer, "- {}: {} - {}", level.score, level.label, level.description)?;
+        }
+        writeln!(self.writer)
     }
 
     fn write_impact_assessment(
@@ -239,6 +496,8 @@ impl<W: Write> FixPlanGenerator<W> {
                 ("MAINTAINABILITY CONCERN", "Affects code readability and maintenance."),
             (CategoryType::Documentation, _) => 
                 ("DOCUMENTATION GAP", "Impacts code understanding and usability."),
+            (CategoryType::Uncategorized, _) =>
+                ("UNCATEGORIZED", "Impact unknown; review the lint documentation."),
         };
 
         let pattern = if warnings.len() > 5 {
@@ -247,8 +506,15 @@ impl<W: Write> FixPlanGenerator<W> {
             "Isolated occurrences - can be fixed individually."
         };
 
-        writeln!(self.writer, "**Risk Assessment**: {}: {}", severity, warnings[0].message)?;
+        writeln!(self.writer, "**Risk Assessment**: {}: {}", severity, warnings[0].primary_message)?;
         writeln!(self.writer, "**Impact**: {}", impact)?;
-        writeln!(self.writer, "**Pattern**: {}\n", pattern)
+        writeln!(self.writer, "**Pattern**: {}", pattern)?;
+        let effort = self.effort(warnings.iter().copied());
+        if let [_] = warnings {
+            writeln!(self.writer, "**Estimated Effort**: {}\n", effort)
+        } else {
+            let each = Effort(effort.0 / warnings.len() as f64);
+            writeln!(self.writer, "**Estimated Effort**: {} ({} each)\n", effort, each)
+        }
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,22 @@
+//! Additional report formats.
+//!
+//! The markdown, HTML, CSV, and JSON reports are always written; each
+//! `--format` (or `formats = [...]` in the config) adds another one.
+
+#[cfg(feature = "terminal")]
+use clap::ValueEnum;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "terminal", derive(ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum ReportFormat {
+    /// Prometheus text exposition for the node_exporter textfile collector
+    /// (see `output::prometheus`)
+    Prometheus,
+    /// Compact markdown body for a pull request comment (see
+    /// `output::pr_summary`)
+    PrComment,
+    /// SARIF 2.1.0 log for code scanning (see `output::sarif`)
+    Sarif,
+}
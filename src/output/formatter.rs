@@ -1,4 +1,5 @@
 use crate::types::{Warning, CategoryType};
+#[cfg(feature = "terminal")]
 use termcolor::Color;
 
 pub struct WarningFormatter<'a> {
@@ -10,26 +11,31 @@ impl<'a> WarningFormatter<'a> {
         Self { warning }
     }
 
+    #[cfg(feature = "terminal")]
     pub fn format(&self) -> (String, Color) {
-        let color = self.get_category_color();
-        let priority_marker = self.get_priority_marker();
-        let formatted = format!(
+        (self.text(), self.get_category_color())
+    }
+
+    /// The formatted warning without a terminal color.
+    pub fn text(&self) -> String {
+        format!(
             "{} {} in {} (line {})\n    {}\n",
-            priority_marker,
+            self.get_priority_marker(),
             self.warning.category,
             self.warning.file,
             self.warning.line,
-            self.warning.message
-        );
-        (formatted, color)
+            self.warning.details().replace('\n', "\n    ")
+        )
     }
 
+    #[cfg(feature = "terminal")]
     fn get_category_color(&self) -> Color {
         match self.warning.category {
             CategoryType::Safety => Color::Red,
             CategoryType::Performance => Color::Yellow,
             CategoryType::Style => Color::Blue,
             CategoryType::Documentation => Color::Cyan,
+            CategoryType::Uncategorized => Color::White,
         }
     }
 
@@ -39,10 +45,12 @@ impl<'a> WarningFormatter<'a> {
             CategoryType::Performance => "🟡",
             CategoryType::Documentation => "🟢",
             CategoryType::Style => "⚪",
+            CategoryType::Uncategorized => "⚫",
         }
     }
 }
 
+#[cfg(feature = "terminal")]
 pub fn format_warning(warning: &Warning) -> (String, Color) {
     let formatter = WarningFormatter::new(warning);
     formatter.format()
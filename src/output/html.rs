@@ -0,0 +1,863 @@
+//! HTML summary report generation.
+//!
+//! Produces a single self-contained HTML page with the analysis overview,
+//! distribution tables, and the crate × category heat table. Colors come
+//! from CSS variables, so the `[html]` config table can switch the page to a
+//! dark palette and replace the accent color without touching the sections.
+//!
+//! Tables use header cells with scopes, each chart is followed by its data as
+//! a table, and both palettes keep text at WCAG AA contrast. The `a11y` mode
+//! adds the heat level to every heat table cell as text, so severity is not
+//! conveyed by color alone.
+//!
+//! Warnings are listed per file in collapsed sections, each opening with a
+//! sparkline of the file's warnings over the recorded runs and a bar of its
+//! category mix. Past the `[html]`
+//! `lazy-threshold`, the sections are empty placeholders and their warnings
+//! go to a companion JSONL file with one line per file; a small script
+//! fetches a file's line, by byte range where the server supports it, when
+//! the section is expanded, so the page stays light with 100k warnings. The
+//! page must then be served over HTTP, as browsers refuse `fetch` on
+//! `file://` pages.
+
+use crate::analysis::{
+    heatmap::{HeatTable, HEAT_COLUMNS, HEAT_LEVELS},
+    statistics::{
+        directory::{DirectoryTree, MODULE_BREAKDOWN_ROWS},
+        warning::WarningStatistics,
+    },
+    trends::TrendAnalysis,
+};
+use crate::{
+    output::{snippet::Snippet, svg_charts::{SvgCharts, category_mix_svg, sparkline_svg}},
+    types::{CategoryType, Completeness, Warning},
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use serde::Deserialize;
+
+/// Warnings above which per-file sections are loaded on demand
+pub const DEFAULT_LAZY_THRESHOLD: usize = 5000;
+
+/// Most recent runs charted in each file's warning history
+pub const FILE_HISTORY_RUNS: usize = 20;
+
+/// Palette of the light theme; `--heat-0` (none) through `--heat-4`
+/// (hottest) color the heat table cells
+const LIGHT_PALETTE: &str = "--bg: #ffffff; --fg: #222222; --border: #cccccc; --code-bg: #f5f5f5; \
+--mark: #fff59d; --notice-border: #e57373; --notice-bg: #ffebee; --accent: #1565c0; \
+--heat-0: #ffffff; --heat-1: #e8f5e9; --heat-2: #fff59d; --heat-3: #ffb74d; --heat-4: #e57373;";
+
+const DARK_PALETTE: &str = "--bg: #121212; --fg: #e0e0e0; --border: #444444; --code-bg: #1e1e1e; \
+--mark: #5d4c1a; --notice-border: #c62828; --notice-bg: #3b1d1d; --accent: #64b5f6; \
+--heat-0: #121212; --heat-1: #1e3a26; --heat-2: #5a5220; --heat-3: #7a4a12; --heat-4: #8e2a2a;";
+
+/// Heat levels 0 through 4 in words, for the `a11y` mode
+const HEAT_LABELS: [&str; HEAT_LEVELS as usize] = ["none", "low", "moderate", "high", "severe"];
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: var(--fg); background: var(--bg); }
+h1, h2 { color: var(--accent); }
+a { color: var(--accent); }
+header.brand { display: flex; align-items: center; gap: 0.8em; border-bottom: 3px solid var(--accent); padding-bottom: 0.5em; }
+header.brand img { max-height: 3em; }
+table { border-collapse: collapse; margin-bottom: 1.5em; }
+th, td { border: 1px solid var(--border); padding: 0.3em 0.8em; text-align: right; }
+th:first-child, td:first-child { text-align: left; }
+caption { text-align: left; font-weight: bold; padding: 0.3em 0; }
+figure { margin: 0 0 1.5em; }
+.heat-0 { background: var(--heat-0); } .heat-1 { background: var(--heat-1); } .heat-2 { background: var(--heat-2); }
+.heat-3 { background: var(--heat-3); } .heat-4 { background: var(--heat-4); }
+.notice { border: 1px solid var(--notice-border); background: var(--notice-bg); padding: 0.5em 1em; }
+.clean { font-size: 1.3em; }
+details pre, pre.snippet { background: var(--code-bg); padding: 0.5em; overflow-x: auto; }
+pre.snippet mark { background: var(--mark); color: inherit; display: block; }
+svg > rect:first-of-type { fill: var(--bg); }
+svg text { fill: var(--fg); }
+svg polyline { stroke: var(--accent); }
+svg circle { fill: var(--accent); }
+.file-charts { display: flex; align-items: center; gap: 1em; flex-wrap: wrap; }";
+
+/// Light or dark page colors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeMode {
+    #[default]
+    Light,
+    Dark,
+    /// Follows the reader's system setting (`prefers-color-scheme`)
+    Auto,
+}
+
+/// A CSS color: `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, or a named color.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CssColor(String);
+
+impl TryFrom<String> for CssColor {
+    type Error = String;
+
+    fn try_from(color: String) -> Result<Self, Self::Error> {
+        let valid = match color.strip_prefix('#') {
+            Some(hex) => matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()),
+            None => !color.is_empty() && color.chars().all(|c| c.is_ascii_alphabetic()),
+        };
+        if valid {
+            Ok(Self(color))
+        } else {
+            Err(format!("invalid color `{}`, expected e.g. `#7b1fa2` or `teal`", color))
+        }
+    }
+}
+
+impl fmt::Display for CssColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Appearance of the HTML report, from the `[html]` config table:
+///
+/// ```toml
+/// [html]
+/// theme = "auto"
+/// accent = "#7b1fa2"
+/// logo = "docs/logo.svg"
+/// header = "Platform Team · Rust Quality"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct HtmlTheme {
+    pub theme: ThemeMode,
+    /// Color of headings, links, and chart lines
+    pub accent: Option<CssColor>,
+    /// Image shown next to the header: a URL, or a file relative to the
+    /// workspace root that is embedded so the page stays self-contained
+    pub logo: Option<String>,
+    /// Text shown above the title, e.g. a team or product name
+    pub header: Option<String>,
+    /// Spell out heat levels instead of conveying them by color alone
+    pub a11y: bool,
+    /// Warnings above which per-file sections load from a companion file
+    /// when expanded (default 5000)
+    pub lazy_threshold: Option<usize>,
+}
+
+impl HtmlTheme {
+    /// Whether a run with `warnings` gets lazily loaded per-file sections.
+    pub fn is_lazy(&self, warnings: usize) -> bool {
+        warnings > self.lazy_threshold.unwrap_or(DEFAULT_LAZY_THRESHOLD)
+    }
+
+    /// Replaces a logo file path with a `data:` URI of its contents.
+    pub fn embed_logo(&mut self, root: &Path) -> io::Result<()> {
+        let Some(logo) = &self.logo else { return Ok(()) };
+        if ["http://", "https://", "data:"].iter().any(|scheme| logo.starts_with(scheme)) {
+            return Ok(());
+        }
+        let path = root.join(logo);
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default().to_ascii_lowercase();
+        let mime = match extension.as_str() {
+            "svg" => "image/svg+xml",
+            "png" => "image/png",
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "webp" => "image/webp",
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Unsupported logo format {}, expected svg, png, jpeg, gif, or webp", path.display()),
+                ))
+            }
+        };
+        let contents = std::fs::read(&path)
+            .map_err(|e| io::Error::new(e.kind(), format!("Cannot read logo {}: {}", path.display(), e)))?;
+        self.logo = Some(format!("data:{};base64,{}", mime, base64(&contents)));
+        Ok(())
+    }
+
+    /// The page's CSS: palette, accent color, and layout.
+    pub fn style(&self) -> String {
+        let mut style = match self.theme {
+            ThemeMode::Light => format!(":root {{ color-scheme: light; {} }}\n", LIGHT_PALETTE),
+            ThemeMode::Dark => format!(":root {{ color-scheme: dark; {} }}\n", DARK_PALETTE),
+            ThemeMode::Auto => format!(
+                ":root {{ color-scheme: light dark; {} }}\n@media (prefers-color-scheme: dark) {{ :root {{ {} }} }}\n",
+                LIGHT_PALETTE, DARK_PALETTE
+            ),
+        };
+        if let Some(accent) = &self.accent {
+            style.push_str(&format!(":root {{ --accent: {}; }}\n", accent));
+        }
+        style.push_str(STYLE);
+        style
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, byte)| n | (*byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Where a file's warnings are in the companion JSONL file: bytes
+/// `start..end`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LazySection {
+    pub file: String,
+    pub warnings: usize,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Per-file sections loaded from the companion file at `href`.
+#[derive(Debug, Clone)]
+struct LazySections {
+    href: String,
+    sections: Vec<LazySection>,
+}
+
+/// Fetches and renders a lazy section's warnings when it is first opened.
+const LAZY_SCRIPT: &str = r#"<script>
+document.querySelectorAll("details.lazy").forEach(function (section) {
+  section.addEventListener("toggle", function () {
+    if (!section.open || section.dataset.loaded) return;
+    section.dataset.loaded = "1";
+    var src = document.body.dataset.sections, start = +section.dataset.start, end = +section.dataset.end;
+    var status = section.querySelector(".loading");
+    fetch(src, { headers: { Range: "bytes=" + start + "-" + (end - 1) } })
+      .then(function (response) {
+        if (!response.ok) throw new Error(response.status + " " + response.statusText);
+        return response.arrayBuffer().then(function (bytes) {
+          return new TextDecoder().decode(response.status === 206 ? bytes : bytes.slice(start, end));
+        });
+      })
+      .then(function (line) {
+        var table = document.createElement("table"), head = table.createTHead().insertRow();
+        ["Line", "Lint", "Priority", "Message"].forEach(function (label) {
+          var th = document.createElement("th");
+          th.scope = "col";
+          th.textContent = label;
+          head.appendChild(th);
+        });
+        var body = table.createTBody();
+        JSON.parse(line).warnings.forEach(function (w) {
+          var row = body.insertRow();
+          [w.line, w.lint_code, w.priority, w.message].forEach(function (value) {
+            row.insertCell().textContent = value;
+          });
+        });
+        status.replaceWith(table);
+      })
+      .catch(function (error) {
+        status.textContent = "Could not load " + src + " (" + error.message + "). Serve the reports directory over HTTP to browse warnings by file.";
+      });
+  });
+});
+</script>"#;
+
+/// Writes HTML report sections to any `Write` implementation.
+#[derive(Debug)]
+pub struct HtmlWriter<W: Write> {
+    writer: W,
+    theme: HtmlTheme,
+    lazy: Option<LazySections>,
+    /// Per-file warning counts of the recorded runs, oldest first
+    file_history: Vec<HashMap<String, usize>>,
+}
+
+impl<W: Write> HtmlWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer, theme: HtmlTheme::default(), lazy: None, file_history: Vec::new() }
+    }
+
+    pub fn set_theme(&mut self, theme: HtmlTheme) {
+        self.theme = theme;
+    }
+
+    /// Loads per-file sections from the companion file written by
+    /// [`write_file_sections_jsonl`], at `href` relative to the page.
+    pub fn set_lazy_sections(&mut self, href: String, sections: Vec<LazySection>) {
+        self.lazy = Some(LazySections { href, sections });
+    }
+
+    /// Charts each file's warnings over the last `FILE_HISTORY_RUNS` of
+    /// `historical` (oldest first) in its section. Runs recorded without
+    /// per-file counts are skipped.
+    pub fn set_file_history(&mut self, historical: &[TrendAnalysis]) {
+        let runs: Vec<&TrendAnalysis> = historical.iter().filter(|run| !run.by_file.is_empty()).collect();
+        self.file_history = runs[runs.len().saturating_sub(FILE_HISTORY_RUNS)..]
+            .iter()
+            .map(|run| run.by_file.clone())
+            .collect();
+    }
+
+    pub fn write_document_start(&mut self, title: &str) -> io::Result<()> {
+        writeln!(self.writer, "<!DOCTYPE html>")?;
+        writeln!(self.writer, "<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">")?;
+        writeln!(self.writer, "<title>{}</title>", escape_html(title))?;
+        writeln!(self.writer, "<style>\n{}\n</style>", self.theme.style())?;
+        match &self.lazy {
+            Some(lazy) => writeln!(self.writer, "</head>\n<body data-sections=\"{}\">", escape_html(&lazy.href))?,
+            None => writeln!(self.writer, "</head>\n<body>")?,
+        }
+        if self.theme.logo.is_some() || self.theme.header.is_some() {
+            write!(self.writer, "<header class=\"brand\">")?;
+            if let Some(logo) = &self.theme.logo {
+                write!(self.writer, "<img src=\"{}\" alt=\"\">", escape_html(logo))?;
+            }
+            if let Some(header) = &self.theme.header {
+                write!(self.writer, "<strong>{}</strong>", escape_html(header))?;
+            }
+            writeln!(self.writer, "</header>")?;
+        }
+        writeln!(self.writer, "<main>\n<h1>{}</h1>", escape_html(title))
+    }
+
+    pub fn write_document_end(&mut self) -> io::Result<()> {
+        writeln!(self.writer, "</main>")?;
+        if self.lazy.is_some() {
+            writeln!(self.writer, "{}", LAZY_SCRIPT)?;
+        }
+        writeln!(self.writer, "</body>\n</html>")
+    }
+
+    fn write_table_head(&mut self, columns: &[&str]) -> io::Result<()> {
+        write!(self.writer, "<thead><tr>")?;
+        for column in columns {
+            write!(self.writer, "<th scope=\"col\">{}</th>", escape_html(column))?;
+        }
+        writeln!(self.writer, "</tr></thead>")
+    }
+
+    /// Writes a highlighted notice, e.g. that the analysis is incomplete.
+    pub fn write_notice(&mut self, text: &str) -> io::Result<()> {
+        writeln!(self.writer, "<p class=\"notice\"><strong>{}</strong></p>", escape_html(text))
+    }
+
+    /// Writes the summary of a run without warnings, with the warning count
+    /// of the previous run, if any.
+    pub fn write_clean_summary(&mut self, previous: Option<usize>) -> io::Result<()> {
+        writeln!(self.writer, "<h2>Summary</h2>")?;
+        writeln!(self.writer, "<p class=\"clean\">🎉 <strong>No warnings.</strong> Clippy found nothing to fix in this workspace.</p>")?;
+        match previous {
+            Some(0) => writeln!(self.writer, "<p>The previous run was clean too.</p>"),
+            Some(count) => writeln!(self.writer, "<p>Down from {} warnings in the previous run.</p>", count),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes totals and the category, priority, and subcategory distributions.
+    pub fn write_summary(&mut self, stats: &WarningStatistics) -> io::Result<()> {
+        writeln!(self.writer, "<h2>Summary</h2>")?;
+        writeln!(self.writer, "<p>Total warnings: {}<br>Files affected: {}</p>",
+            stats.total_warnings, stats.files_affected)?;
+
+        let mut categories: Vec<(String, usize)> = stats.by_category.iter()
+            .map(|(category, count)| (category.to_string(), *count))
+            .collect();
+        categories.sort_by_key(|row| std::cmp::Reverse(row.1));
+        self.write_distribution_table("Category Distribution", "Category", &categories, stats.total_warnings)?;
+
+        let mut priorities: Vec<(String, usize)> = stats.by_priority.iter()
+            .map(|(priority, count)| (priority.to_string(), *count))
+            .collect();
+        priorities.sort_by_key(|row| std::cmp::Reverse(row.1));
+        self.write_distribution_table("Priority Distribution", "Priority", &priorities, stats.total_warnings)?;
+
+        let mut subcategories: Vec<(String, usize)> = stats.by_subcategory.iter()
+            .map(|(subcategory, count)| (subcategory.clone(), *count))
+            .collect();
+        subcategories.sort_by_key(|row| std::cmp::Reverse(row.1));
+        self.write_distribution_table("Subcategory Distribution", "Subcategory", &subcategories, stats.total_warnings)
+    }
+
+    fn write_distribution_table(
+        &mut self,
+        title: &str,
+        label: &str,
+        rows: &[(String, usize)],
+        total: usize,
+    ) -> io::Result<()> {
+        let id = anchor(title);
+        writeln!(self.writer, "<h2 id=\"{}\">{}</h2>", id, escape_html(title))?;
+        writeln!(self.writer, "<table aria-labelledby=\"{}\">", id)?;
+        self.write_table_head(&[label, "Count", "Share"])?;
+        writeln!(self.writer, "<tbody>")?;
+        for (name, count) in rows {
+            let percentage = if total > 0 { *count as f64 / total as f64 * 100.0 } else { 0.0 };
+            writeln!(self.writer, "<tr><th scope=\"row\">{}</th><td>{}</td><td>{:.1}%</td></tr>",
+                escape_html(name), count, percentage)?;
+        }
+        writeln!(self.writer, "</tbody>\n</table>")
+    }
+
+    /// Embeds the SVG charts inline, each followed by its data as a
+    /// collapsed table for screen readers and text browsers.
+    pub fn write_charts(&mut self, charts: &SvgCharts) -> io::Result<()> {
+        writeln!(self.writer, "<h2>Charts</h2>")?;
+        self.write_chart(&charts.category, "Category Distribution", "Category", &charts.data.category)?;
+        self.write_chart(&charts.severity, "Severity Distribution", "Priority", &charts.data.severity)?;
+        if let Some(trend) = &charts.trend {
+            self.write_chart(trend, "Warning Trend", "Run", &charts.data.trend)?;
+        }
+        Ok(())
+    }
+
+    fn write_chart(&mut self, svg: &str, title: &str, label: &str, rows: &[(String, usize)]) -> io::Result<()> {
+        writeln!(self.writer, "<figure>\n{}", svg.trim_end())?;
+        writeln!(self.writer, "<figcaption><details><summary>{} data</summary>", escape_html(title))?;
+        writeln!(self.writer, "<table>\n<caption>{}</caption>", escape_html(title))?;
+        self.write_table_head(&[label, "Warnings"])?;
+        writeln!(self.writer, "<tbody>")?;
+        for (name, value) in rows {
+            writeln!(self.writer, "<tr><th scope=\"row\">{}</th><td>{}</td></tr>", escape_html(name), value)?;
+        }
+        writeln!(self.writer, "</tbody>\n</table>\n</details></figcaption>\n</figure>")
+    }
+
+    /// Writes the crate × category heat table with cells colored by heat level.
+    pub fn write_heat_table(&mut self, table: &HeatTable) -> io::Result<()> {
+        writeln!(self.writer, "<h2 id=\"severity-heat-table\">Severity Heat Table</h2>")?;
+        if table.is_empty() {
+            return writeln!(self.writer, "<p>No warnings to tabulate.</p>");
+        }
+
+        writeln!(self.writer, "<table aria-labelledby=\"severity-heat-table\" aria-describedby=\"heat-legend\">")?;
+        let columns: Vec<String> = HEAT_COLUMNS.iter().map(ToString::to_string).collect();
+        let mut head = vec!["Crate"];
+        head.extend(columns.iter().map(String::as_str));
+        head.push("Total");
+        self.write_table_head(&head)?;
+        writeln!(self.writer, "<tbody>")?;
+
+        for row in &table.rows {
+            write!(self.writer, "<tr><th scope=\"row\">{}</th>", escape_html(&row.crate_name))?;
+            for cell in &row.cells {
+                if self.theme.a11y {
+                    let label = HEAT_LABELS[cell.level as usize];
+                    write!(self.writer, "<td class=\"heat-{}\">{} ({})</td>", cell.level, cell.count, label)?;
+                } else {
+                    write!(self.writer, "<td class=\"heat-{}\">{}</td>", cell.level, cell.count)?;
+                }
+            }
+            writeln!(self.writer, "<td>{}</td></tr>", row.total)?;
+        }
+        writeln!(self.writer, "</tbody>\n</table>")?;
+        if self.theme.a11y {
+            writeln!(self.writer, "<p id=\"heat-legend\">Each cell gives the warning count and, in parentheses, its heat level \
+                (none, low, moderate, high, severe), weighted by category severity.</p>")
+        } else {
+            writeln!(self.writer, "<p id=\"heat-legend\">Cell color reflects warning count weighted by category severity.</p>")
+        }
+    }
+
+    /// Writes the modules with the highest severity-weighted scores and
+    /// their share of each category's warnings.
+    pub fn write_module_breakdown(&mut self, tree: &DirectoryTree, stats: &WarningStatistics) -> io::Result<()> {
+        writeln!(self.writer, "<h2 id=\"module-breakdown\">Module Breakdown</h2>")?;
+        if tree.is_empty() {
+            return writeln!(self.writer, "<p>No warnings to break down.</p>");
+        }
+
+        writeln!(self.writer, "<table aria-labelledby=\"module-breakdown\" aria-describedby=\"module-breakdown-legend\">")?;
+        let columns: Vec<String> = HEAT_COLUMNS.iter().map(ToString::to_string).collect();
+        let mut head = vec!["Module", "Warnings", "Score"];
+        head.extend(columns.iter().map(String::as_str));
+        self.write_table_head(&head)?;
+        writeln!(self.writer, "<tbody>")?;
+
+        for (module, dir_stats) in tree.top(MODULE_BREAKDOWN_ROWS) {
+            write!(
+                self.writer,
+                "<tr><th scope=\"row\"><code>{}</code></th><td>{}</td><td>{}</td>",
+                escape_html(module),
+                dir_stats.warnings,
+                dir_stats.score
+            )?;
+            for category in HEAT_COLUMNS {
+                let count = *dir_stats.by_category.get(&category).unwrap_or(&0);
+                let total = *stats.by_category.get(&category).unwrap_or(&0);
+                write!(self.writer, "<td>{} ({:.0}%)</td>", count, dir_stats.category_share(category, total))?;
+            }
+            writeln!(self.writer, "</tr>")?;
+        }
+        writeln!(self.writer, "</tbody>\n</table>")?;
+        writeln!(self.writer, "<p id=\"module-breakdown-legend\">Score weights each warning by priority, from Critical (5) to Trivial (1). \
+            Category cells give the module's share of all warnings in that category.</p>")
+    }
+
+    /// Shows the code of the given warnings, most important first.
+    pub fn write_snippets(&mut self, snippets: &[(&Warning, Snippet)]) -> io::Result<()> {
+        if snippets.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.writer, "<h2>Top Warnings</h2>")?;
+        for (warning, snippet) in snippets {
+            writeln!(
+                self.writer,
+                "<h3><code>{}</code> {}:{} ({})</h3>\n<p>{}</p>\n{}",
+                escape_html(&warning.lint_code),
+                escape_html(&warning.file),
+                warning.line,
+                warning.priority,
+                escape_html(&warning.primary_message),
+                snippet.to_html()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Lists the warnings of each file in a collapsed section, or writes
+    /// placeholders for the lazily loaded sections.
+    pub fn write_file_sections(&mut self, warnings: &[Warning]) -> io::Result<()> {
+        if warnings.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.writer, "<h2>Warnings by File</h2>")?;
+        let by_file = group_by_file(warnings);
+        if let Some(lazy) = self.lazy.take() {
+            for section in &lazy.sections {
+                writeln!(
+                    self.writer,
+                    "<details class=\"lazy\" data-start=\"{}\" data-end=\"{}\"><summary><code>{}</code> ({} warnings)</summary>",
+                    section.start,
+                    section.end,
+                    escape_html(&section.file),
+                    section.warnings
+                )?;
+                self.write_file_charts(&section.file, by_file.get(section.file.as_str()).map(Vec::as_slice).unwrap_or_default())?;
+                writeln!(self.writer, "<p class=\"loading\">Loading…</p></details>")?;
+            }
+            self.lazy = Some(lazy);
+            return Ok(());
+        }
+        for (file, file_warnings) in by_file {
+            writeln!(
+                self.writer,
+                "<details><summary><code>{}</code> ({} warnings)</summary>",
+                escape_html(file),
+                file_warnings.len()
+            )?;
+            self.write_file_charts(file, &file_warnings)?;
+            writeln!(self.writer, "<table>")?;
+            self.write_table_head(&["Line", "Lint", "Priority", "Message"])?;
+            writeln!(self.writer, "<tbody>")?;
+            for warning in file_warnings {
+                writeln!(
+                    self.writer,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    warning.line,
+                    escape_html(&warning.lint_code),
+                    warning.priority,
+                    escape_html(&table_message(warning))
+                )?;
+            }
+            writeln!(self.writer, "</tbody>\n</table>\n</details>")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the file's warning history, when recorded, and its category
+    /// mix, each with a text equivalent.
+    fn write_file_charts(&mut self, file: &str, warnings: &[&Warning]) -> io::Result<()> {
+        let mut mix: HashMap<CategoryType, usize> = HashMap::new();
+        for warning in warnings {
+            *mix.entry(warning.category).or_insert(0) += 1;
+        }
+        let mix_text: Vec<String> = HEAT_COLUMNS
+            .iter()
+            .filter_map(|category| mix.get(category).map(|count| format!("{} {}", count, category)))
+            .collect();
+
+        writeln!(self.writer, "<div class=\"file-charts\">")?;
+        if !self.file_history.is_empty() {
+            let mut counts: Vec<usize> = self.file_history.iter().map(|run| run.get(file).copied().unwrap_or(0)).collect();
+            let previous = counts.last().copied().unwrap_or(0);
+            let trend = match warnings.len().cmp(&previous) {
+                std::cmp::Ordering::Less => "improving",
+                std::cmp::Ordering::Greater => "worsening",
+                std::cmp::Ordering::Equal => "unchanged",
+            };
+            counts.push(warnings.len());
+            let text = format!(
+                "Warnings over the last {} runs: {} ({})",
+                counts.len(),
+                counts.iter().map(usize::to_string).collect::<Vec<_>>().join(", "),
+                trend
+            );
+            writeln!(self.writer, "{}<span>{}</span>", sparkline_svg(&text, &counts).trim_end(), escape_html(&text))?;
+        }
+        let text = format!("Categories: {}", mix_text.join(", "));
+        writeln!(self.writer, "{}<span>{}</span>", category_mix_svg(&text, &mix).trim_end(), escape_html(&text))?;
+        writeln!(self.writer, "</div>")
+    }
+
+    /// Lists the original JSON diagnostic of each warning that kept one,
+    /// collapsed by default.
+    pub fn write_raw_diagnostics(&mut self, warnings: &[Warning]) -> io::Result<()> {
+        let with_raw: Vec<(&Warning, &String)> = warnings
+            .iter()
+            .filter_map(|warning| warning.raw.as_ref().map(|raw| (warning, raw)))
+            .collect();
+        if with_raw.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(self.writer, "<h2>Raw Diagnostics</h2>")?;
+        for (warning, raw) in with_raw {
+            writeln!(
+                self.writer,
+                "<details><summary><code>{}</code> {}:{} ({}, {})</summary><pre>{}</pre></details>",
+                escape_html(&warning.lint_code),
+                escape_html(&warning.file),
+                warning.line,
+                warning.category,
+                warning.priority,
+                escape_html(raw)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Warnings by file path, each file's in line order.
+fn group_by_file(warnings: &[Warning]) -> BTreeMap<&str, Vec<&Warning>> {
+    let mut by_file: BTreeMap<&str, Vec<&Warning>> = BTreeMap::new();
+    for warning in warnings {
+        by_file.entry(warning.file.as_str()).or_default().push(warning);
+    }
+    for file_warnings in by_file.values_mut() {
+        file_warnings.sort_by_key(|warning| warning.line);
+    }
+    by_file
+}
+
+/// Writes the companion file of lazily loaded sections: one JSON line per
+/// file, `{"file": ..., "warnings": [{"line", "lint_code", "priority",
+/// "message", "raw"}]}`, returning where each line is.
+pub fn write_file_sections_jsonl<W: Write>(mut writer: W, warnings: &[Warning]) -> io::Result<Vec<LazySection>> {
+    let mut sections = Vec::new();
+    let mut offset = 0u64;
+    for (file, file_warnings) in group_by_file(warnings) {
+        let rows: Vec<serde_json::Value> = file_warnings
+            .iter()
+            .map(|warning| {
+                serde_json::json!({
+                    "line": warning.line,
+                    "lint_code": warning.lint_code,
+                    "priority": warning.priority.to_string(),
+                    "message": table_message(warning),
+                    "raw": warning.raw,
+                })
+            })
+            .collect();
+        let mut line = serde_json::to_vec(&serde_json::json!({ "file": file, "warnings": rows }))?;
+        line.push(b'\n');
+        writer.write_all(&line)?;
+        let end = offset + line.len() as u64;
+        sections.push(LazySection { file: file.to_string(), warnings: file_warnings.len(), start: offset, end });
+        offset = end;
+    }
+    writer.flush()?;
+    Ok(sections)
+}
+
+/// The message shown in a file's table, noting the macro the warning came
+/// from since its line is then the macro's call site.
+fn table_message(warning: &Warning) -> String {
+    match &warning.macro_origin {
+        Some(origin) => format!("{} (from macro `{}`)", warning.primary_message, origin.name),
+        None => warning.primary_message.clone(),
+    }
+}
+
+/// `Category Distribution` → `category-distribution`
+fn anchor(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Escapes text for safe inclusion in HTML element content and attributes.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes the summary page with `html_writer`, configured with a theme and,
+/// for large runs, lazy per-file sections.
+pub fn generate_html_report<W: Write>(
+    mut html_writer: HtmlWriter<W>,
+    stats: &WarningStatistics,
+    warnings: &[Warning],
+    completeness: &Completeness,
+    charts: &SvgCharts,
+    snippets: &[(&Warning, Snippet)],
+) -> io::Result<()> {
+    html_writer.write_document_start("Clippy Analysis Summary")?;
+    if !completeness.is_complete() {
+        html_writer.write_notice(&completeness.to_string())?;
+    }
+    html_writer.write_summary(stats)?;
+    html_writer.write_charts(charts)?;
+    html_writer.write_heat_table(&HeatTable::from_stats(stats))?;
+    html_writer.write_module_breakdown(&stats.by_directory, stats)?;
+    html_writer.write_snippets(snippets)?;
+    html_writer.write_file_sections(warnings)?;
+    // Lazy sections carry the raw diagnostics in the companion file
+    if html_writer.lazy.is_none() {
+        html_writer.write_raw_diagnostics(warnings)?;
+    }
+    html_writer.write_document_end()
+}
+
+/// Writes the summary page of a run without warnings: the clean summary and
+/// the warning trend.
+pub fn generate_clean_html_report<W: Write>(
+    mut html_writer: HtmlWriter<W>,
+    completeness: &Completeness,
+    charts: &SvgCharts,
+    previous: Option<usize>,
+) -> io::Result<()> {
+    html_writer.write_document_start("Clippy Analysis Summary")?;
+    if !completeness.is_complete() {
+        html_writer.write_notice(&completeness.to_string())?;
+    }
+    html_writer.write_clean_summary(previous)?;
+    if let Some(trend) = &charts.trend {
+        html_writer.write_chart(trend, "Warning Trend", "Run", &charts.data.trend)?;
+    }
+    html_writer.write_document_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_themed_document_start() {
+        let mut theme: HtmlTheme = toml::from_str(
+            "theme = \"dark\"\naccent = \"#7b1fa2\"\nheader = \"Platform <Team>\"\nlogo = \"https://example.com/logo.png\"",
+        )
+        .unwrap();
+        theme.embed_logo(Path::new("/nonexistent")).unwrap();
+        let mut writer = HtmlWriter::new(Vec::new());
+        writer.set_theme(theme);
+        writer.write_document_start("Summary").unwrap();
+        let html = String::from_utf8(writer.writer).unwrap();
+        assert!(html.contains("color-scheme: dark; --bg: #121212"));
+        assert!(html.contains("--accent: #7b1fa2;"));
+        assert!(html.contains("<img src=\"https://example.com/logo.png\" alt=\"\"><strong>Platform &lt;Team&gt;</strong>"));
+
+        assert!(toml::from_str::<HtmlTheme>("accent = \"red; } body { display: none\"").is_err());
+        assert_eq!(base64(b"logo"), "bG9nbw==");
+    }
+
+    #[test]
+    fn test_lazy_file_sections() {
+        let warning = |file: &str, line: u32| Warning {
+            primary_message: "length comparison to zero".to_string(),
+            priority: crate::types::Priority::Low,
+            ..Warning::for_test("clippy::len_zero", file, line)
+        };
+        let warnings = [warning("src/b.rs", 9), warning("src/a.rs", 4), warning("src/b.rs", 2)];
+        let mut jsonl = Vec::new();
+        let sections = write_file_sections_jsonl(&mut jsonl, &warnings).unwrap();
+        assert_eq!(sections.iter().map(|s| (s.file.as_str(), s.warnings)).collect::<Vec<_>>(), [("src/a.rs", 1), ("src/b.rs", 2)]);
+        let b: serde_json::Value = serde_json::from_slice(&jsonl[sections[1].start as usize..sections[1].end as usize]).unwrap();
+        assert_eq!(b["file"], "src/b.rs");
+        assert_eq!(b["warnings"][0]["line"], 2);
+
+        let mut writer = HtmlWriter::new(Vec::new());
+        writer.set_lazy_sections("files.jsonl".to_string(), sections);
+        writer.write_file_sections(&warnings).unwrap();
+        writer.write_document_end().unwrap();
+        let html = String::from_utf8(writer.writer).unwrap();
+        assert!(html.contains("data-start=\"0\""));
+        assert!(html.contains("<script>"));
+
+        let run = |count| TrendAnalysis { by_file: HashMap::from([("src/b.rs".to_string(), count)]), ..Default::default() };
+        let mut writer = HtmlWriter::new(Vec::new());
+        writer.set_file_history(&[TrendAnalysis::default(), run(1), run(3)]);
+        writer.write_file_sections(&warnings).unwrap();
+        let html = String::from_utf8(writer.writer).unwrap();
+        assert!(html.contains("<span>Warnings over the last 3 runs: 0, 0, 1 (worsening)</span>"));
+        assert!(html.contains("<span>Warnings over the last 3 runs: 1, 3, 2 (improving)</span>"));
+        assert!(html.contains("<span>Categories: 2 Style</span>"));
+    }
+
+    /// WCAG contrast ratio of two `#rrggbb` colors
+    fn contrast(a: &str, b: &str) -> f64 {
+        let luminance = |hex: &str| {
+            let channel = |i: usize| {
+                let c = u8::from_str_radix(&hex[1 + 2 * i..3 + 2 * i], 16).unwrap() as f64 / 255.0;
+                if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+            };
+            0.2126 * channel(0) + 0.7152 * channel(1) + 0.0722 * channel(2)
+        };
+        let (a, b) = (luminance(a), luminance(b));
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    #[test]
+    fn test_palette_contrast() {
+        for palette in [LIGHT_PALETTE, DARK_PALETTE] {
+            let color = |name: &str| {
+                let start = palette.find(&format!("--{}: ", name)).unwrap() + name.len() + 4;
+                &palette[start..start + 7]
+            };
+            for background in ["bg", "code-bg", "mark", "notice-bg", "heat-0", "heat-1", "heat-2", "heat-3", "heat-4"] {
+                assert!(contrast(color("fg"), color(background)) >= 4.5, "fg on {} in {}", background, palette);
+            }
+            assert!(contrast(color("accent"), color("bg")) >= 4.5, "accent in {}", palette);
+        }
+
+        let mut heat = HeatTable::default();
+        let mut cells = [crate::analysis::heatmap::HeatCell::default(); HEAT_COLUMNS.len()];
+        cells[0] = crate::analysis::heatmap::HeatCell { count: 3, score: 12, level: 4 };
+        heat.rows.push(crate::analysis::heatmap::HeatRow { crate_name: "core".to_string(), cells, total: 3 });
+        let mut writer = HtmlWriter::new(Vec::new());
+        writer.set_theme(HtmlTheme { a11y: true, ..HtmlTheme::default() });
+        writer.write_heat_table(&heat).unwrap();
+        let html = String::from_utf8(writer.writer).unwrap();
+        assert!(html.contains("<th scope=\"col\">Crate</th>"));
+        assert!(html.contains("<th scope=\"row\">core</th><td class=\"heat-4\">3 (severe)</td>"));
+    }
+
+    #[test]
+    fn test_clean_report() {
+        let history = [TrendAnalysis { dates: vec!["2026-10-01".to_string()], total_warnings: 12, ..Default::default() }];
+        let charts = SvgCharts::new(&WarningStatistics::default(), &history);
+        let mut out = Vec::new();
+        generate_clean_html_report(HtmlWriter::new(&mut out), &Completeness::Complete, &charts, Some(12)).unwrap();
+        let html = String::from_utf8(out).unwrap();
+        assert!(html.contains("<strong>No warnings.</strong>"));
+        assert!(html.contains("Down from 12 warnings in the previous run."));
+        assert!(html.ends_with("</html>\n"));
+    }
+}
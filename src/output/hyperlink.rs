@@ -0,0 +1,129 @@
+//! Terminal hyperlinks to warning locations.
+//!
+//! Locations printed in the terminal are wrapped in OSC 8 escape sequences,
+//! so clicking one in a supporting terminal opens the file at the warning.
+//! The link target is a URL template with `{path}`, `{line}`, and `{col}`
+//! placeholders, or the name of a preset. `{path}` is absolute and starts
+//! with `/`:
+//!
+//! | Name     | Template                                      |
+//! |----------|-----------------------------------------------|
+//! | `file`   | `file://{path}`                               |
+//! | `vscode` | `vscode://file{path}:{line}:{col}`            |
+//! | `idea`   | `idea://open?file={path}&line={line}&column={col}` |
+//! | `none`   | no links                                      |
+
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use crate::types::Warning;
+
+/// Link presets by name
+const PRESETS: [(&str, &str); 3] = [
+    ("file", "file://{path}"),
+    ("vscode", "vscode://file{path}:{line}:{col}"),
+    ("idea", "idea://open?file={path}&line={line}&column={col}"),
+];
+
+/// Wraps `text` in an OSC 8 hyperlink to `url`.
+pub fn osc8(url: &str, text: &str) -> String {
+    format!("\x1B]8;;{}\x1B\\{}\x1B]8;;\x1B\\", url, text)
+}
+
+/// Builds editor links for warnings in the workspace at `root`.
+#[derive(Debug, Clone, Default)]
+pub struct EditorLinks {
+    template: Option<String>,
+    root: PathBuf,
+}
+
+impl EditorLinks {
+    /// Links using `scheme`, a preset name or a template containing
+    /// `{path}`.
+    pub fn new(scheme: &str, root: &Path) -> io::Result<Self> {
+        let template = match PRESETS.iter().find(|(name, _)| *name == scheme) {
+            Some((_, template)) => Some(template.to_string()),
+            None if scheme == "none" => None,
+            None if scheme.contains("{path}") => Some(scheme.to_string()),
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Unknown editor link `{}`: expected file, vscode, idea, none, or a template with {{path}}", scheme),
+                ))
+            }
+        };
+        Ok(Self { template, root: root.to_path_buf() })
+    }
+
+    /// Links for standard output: `scheme`, `file` by default, when it is a
+    /// terminal and none otherwise, so piped output stays plain text.
+    pub fn for_stdout(scheme: Option<&str>, root: &Path) -> io::Result<Self> {
+        if !io::stdout().is_terminal() {
+            return Ok(Self::disabled());
+        }
+        Self::new(scheme.unwrap_or("file"), root)
+    }
+
+    /// No links; text is printed unchanged.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// The link target of `warning`'s location.
+    pub fn url(&self, warning: &Warning) -> Option<String> {
+        let template = self.template.as_ref()?;
+        let path = self.root.join(&warning.file);
+        let column = warning.span.map_or(1, |span| span.column_start.max(1));
+        Some(
+            template
+                .replace("{path}", &encode_path(&path))
+                .replace("{line}", &warning.line.to_string())
+                .replace("{col}", &column.to_string()),
+        )
+    }
+
+    /// `text` linked to `warning`'s location, or unchanged when links are
+    /// disabled.
+    pub fn link(&self, text: &str, warning: &Warning) -> String {
+        match self.url(warning) {
+            Some(url) => osc8(&url, text),
+            None => text.to_string(),
+        }
+    }
+}
+
+/// Percent-encodes the characters of `path` that are not allowed unescaped
+/// in a URL path.
+fn encode_path(path: &Path) -> String {
+    let mut encoded = String::new();
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Priority, Span};
+
+    #[test]
+    fn test_editor_link_templates() {
+        let warning = Warning {
+            primary_message: "length comparison to zero".to_string(),
+            span: Some(Span::at(12, 8)),
+            priority: Priority::Low,
+            ..Warning::for_test("clippy::len_zero", "src/my lib.rs", 12)
+        };
+        let root = Path::new("/work");
+        let url = |scheme: &str| EditorLinks::new(scheme, root).unwrap().url(&warning);
+        assert_eq!(url("vscode").unwrap(), "vscode://file/work/src/my%20lib.rs:12:8");
+        assert_eq!(url("file").unwrap(), "file:///work/src/my%20lib.rs");
+        assert_eq!(url("subl://open?url=file://{path}&line={line}").unwrap(), "subl://open?url=file:///work/src/my%20lib.rs&line=12");
+        assert_eq!(url("none"), None);
+        assert_eq!(EditorLinks::disabled().link("src/lib.rs:12", &warning), "src/lib.rs:12");
+        assert!(EditorLinks::new("emacs", root).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! JSON and JSONL export of warnings.
+//!
+//! Both follow `schema/warning.schema.json`, shipped with the crate: the
+//! JSON export is an array of warnings, the JSONL export one warning per
+//! line for streaming into data pipelines. Warnings are exported as
+//! analyzed, so each carries its fingerprint. [`read_warnings`] loads
+//! either export back, for re-rendering reports and comparing runs.
+
+use std::io::{self, Write};
+use std::path::Path;
+use crate::types::Warning;
+
+/// The JSON schema of an exported warning
+pub const WARNING_SCHEMA: &str = include_str!("../../schema/warning.schema.json");
+
+/// Version of [`WARNING_SCHEMA`], also the last segment of its `$id`
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// Writes the warnings as a pretty-printed JSON array.
+pub fn write_json<W: Write>(writer: W, warnings: &[Warning]) -> io::Result<()> {
+    serde_json::to_writer_pretty(writer, warnings)?;
+    Ok(())
+}
+
+/// Writes one compact JSON warning per line.
+pub fn write_jsonl<W: Write>(mut writer: W, warnings: &[Warning]) -> io::Result<()> {
+    for warning in warnings {
+        serde_json::to_writer(&mut writer, warning)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
+
+/// Reads warnings from a JSON or JSONL export.
+pub fn read_warnings(path: &Path) -> io::Result<Vec<Warning>> {
+    let content = std::fs::read_to_string(path)?;
+    let invalid = |e: serde_json::Error| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("{} is not a warnings export: {}", path.display(), e))
+    };
+    if content.trim_start().starts_with('[') {
+        return serde_json::from_str(&content).map_err(invalid);
+    }
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(invalid))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::fingerprint::Fingerprinter;
+    use crate::types::{Priority, Span};
+
+    #[test]
+    fn test_jsonl_follows_schema() {
+        let warning = |line| Warning {
+            primary_message: "this expression creates a reference".to_string(),
+            span: Some(Span::at(line, 5)),
+            priority: Priority::Low,
+            ..Warning::for_test("clippy::needless_borrow", "src/lib.rs", line)
+        };
+        let mut warnings = [warning(3), warning(8)];
+        Fingerprinter::new(None).assign(&mut warnings);
+        let mut output = Vec::new();
+        write_jsonl(&mut output, &warnings).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 2);
+
+        let schema: serde_json::Value = serde_json::from_str(WARNING_SCHEMA).unwrap();
+        assert!(schema["$id"].as_str().unwrap().ends_with(&format!("/v{}", JSON_SCHEMA_VERSION)));
+        let properties = schema["properties"].as_object().unwrap();
+        for line in output.lines() {
+            let exported: serde_json::Value = serde_json::from_str(line).unwrap();
+            let exported = exported.as_object().unwrap();
+            for required in schema["required"].as_array().unwrap() {
+                assert!(exported.contains_key(required.as_str().unwrap()), "missing {}", required);
+            }
+            assert!(exported.keys().all(|key| properties.contains_key(key)), "undocumented field in {}", line);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Verbosity levels and diagnostic logging.
+//!
+//! Diagnostics never go to the working directory unless asked for: `-v` shows
+//! informational messages on stderr, `-vv` adds debug messages, and
+//! `--log-file` captures everything in a file regardless of verbosity.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// How much the analyzer writes to the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    /// Only the final summary line.
+    Quiet,
+    /// Terminal summary, progress, and report list.
+    #[default]
+    Normal,
+    /// Normal output plus informational messages on stderr.
+    Verbose,
+    /// Verbose output plus debug messages on stderr.
+    Debug,
+}
+
+impl Verbosity {
+    /// Maps `-q` and the number of `-v` flags to a level.
+    pub fn from_flags(quiet: bool, verbose: u8) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::Debug,
+        }
+    }
+
+    pub fn is_quiet(self) -> bool {
+        self == Verbosity::Quiet
+    }
+}
+
+/// Writes timestamped diagnostics to stderr and an optional log file.
+#[derive(Default)]
+pub struct Logger {
+    verbosity: Verbosity,
+    file: Option<BufWriter<File>>,
+}
+
+impl Logger {
+    pub fn new(verbosity: Verbosity) -> Self {
+        Self { verbosity, file: None }
+    }
+
+    /// Also appends every message to `path`, whatever the verbosity.
+    pub fn with_log_file(mut self, path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        self.file = Some(BufWriter::new(file));
+        Ok(self)
+    }
+
+    pub fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
+    /// Logs a message shown on stderr with `-v`.
+    pub fn info(&mut self, message: &str) -> io::Result<()> {
+        self.log(Verbosity::Verbose, message)
+    }
+
+    /// Logs a message shown on stderr with `-vv`.
+    pub fn debug(&mut self, message: &str) -> io::Result<()> {
+        self.log(Verbosity::Debug, message)
+    }
+
+    fn log(&mut self, level: Verbosity, message: &str) -> io::Result<()> {
+        let timestamp = chrono::Local::now().format("%H:%M:%S%.3f");
+        if self.verbosity >= level {
+            writeln!(io::stderr(), "[{}] {}", timestamp, message)?;
+        }
+        if let Some(file) = &mut self.file {
+            writeln!(file, "[{}] {}", timestamp, message)?;
+            file.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_from_flags() {
+        assert_eq!(Verbosity::from_flags(false, 0), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(false, 1), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(false, 3), Verbosity::Debug);
+        // -q wins over -v
+        assert_eq!(Verbosity::from_flags(true, 2), Verbosity::Quiet);
+        assert!(Verbosity::Quiet.is_quiet() && !Verbosity::default().is_quiet());
+    }
+
+    #[test]
+    fn test_log_file_captures_every_level() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("analyzer.log");
+        std::fs::write(&path, "earlier run\n").unwrap();
+
+        let mut logger = Logger::new(Verbosity::Quiet).with_log_file(&path).unwrap();
+        logger.info("parsing").unwrap();
+        logger.debug("3 warnings").unwrap();
+        drop(logger);
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "earlier run");
+        assert!(lines[1].starts_with('[') && lines[1].ends_with("] parsing"));
+        assert!(lines[2].ends_with("] 3 warnings"));
+    }
+}
@@ -5,16 +5,35 @@
 
 use crate::{
     analysis::{
-        charts::{ChartConfig, ChartStyle, create_enhanced_chart},
-        trends::{TrendAnalysis, analyze_trends},
-        statistics::warning::WarningStatistics,
+        alerts::Alert,
+        clusters::WarningCluster,
+        dependencies::{DependencyCheck, UnusedDependencies},
+        charts::{ChartConfig, ChartStyle, create_enhanced_chart, create_stacked_chart, delta_arrow, sparkline},
+        heatmap::{HeatTable, HEAT_COLUMNS},
+        hidden_code::HiddenCodeReport,
+        impact::{Bytes, PerformanceImpact},
+        module_tree::{ModuleNode, ModuleTree},
+        ranking::{RankingStability, RANKING_TOP_FILES},
+        trends::{MAX_RECURRING_ISSUES, TrendAnalysis, analyze_trends, top_recurring},
+        third_party::third_party_breakdown,
+        statistics::{
+            directory::{DirectoryTree, MODULE_BREAKDOWN_ROWS},
+            safety::UnsafeStatistics,
+            warning::{WarningStatistics, LOW_CONFIDENCE_LINTS_SHOWN},
+        },
+        unsafe_usage::UnsafeMetricsSource,
     },
     parser::AnalysisContext, 
-    types::CategoryType,
+    types::{CategoryType, Warning},
 };
 use std::io::{self, Write};
 use std::collections::HashMap;
 
+/// Patterns listed in the recurring patterns section
+const MAX_RECURRING_PATTERNS: usize = 10;
+/// Pre-existing warnings listed in full
+const MAX_PRE_EXISTING_LISTED: usize = 100;
+
 /// Handles the generation and writing of Markdown format reports.
 /// 
 /// # Fields
@@ -37,6 +56,18 @@ impl<W: Write> MarkdownWriter<W> {
         Self { writer }
     }
 
+    /// Writes the summary of a run without warnings, with the warning count
+    /// of the previous run, if any.
+    pub fn write_clean_summary(&mut self, previous: Option<usize>) -> io::Result<()> {
+        writeln!(self.writer, "## Analysis Summary\n")?;
+        writeln!(self.writer, "🎉 **No warnings.** Clippy found nothing to fix in this workspace.\n")?;
+        match previous {
+            Some(0) => writeln!(self.writer, "The previous run was clean too.\n"),
+            Some(count) => writeln!(self.writer, "Down from {} warnings in the previous run.\n", count),
+            None => Ok(()),
+        }
+    }
+
     /// Writes the analysis summary section of the report.
     /// 
     /// # Arguments
@@ -47,6 +78,31 @@ impl<W: Write> MarkdownWriter<W> {
         writeln!(self.writer, "## Analysis Summary\n")?;
         writeln!(self.writer, "Total warnings: {}", stats.total_warnings)?;
         writeln!(self.writer, "Files affected: {}\n", stats.files_affected)?;
+        if stats.duplicates_removed > 0 {
+            writeln!(self.writer, "Duplicates removed: {} (warnings reported by more than one target are counted once)\n", stats.duplicates_removed)?;
+        }
+        if stats.third_party_warnings > 0 {
+            writeln!(self.writer, "Third-party code: {} warnings (reported separately, excluded from the totals)\n", stats.third_party_warnings)?;
+        }
+        if stats.generated_excluded > 0 {
+            writeln!(self.writer, "Generated and vendored code: {} warnings excluded (listed in the appendix of the detailed report)\n", stats.generated_excluded)?;
+        }
+        if stats.errors > 0 {
+            writeln!(self.writer, "**Compile errors: {}** (the build failed; see the detailed report)\n", stats.errors)?;
+        }
+        if stats.low_confidence > 0 {
+            let lints: Vec<String> = stats.top_low_confidence_lints(LOW_CONFIDENCE_LINTS_SHOWN)
+                .iter()
+                .map(|(lint, count)| format!("`{}` ({})", lint, count))
+                .collect();
+            writeln!(
+                self.writer,
+                "Low-confidence categorizations: {} ({:.1}%), mostly {}\n",
+                stats.low_confidence,
+                stats.low_confidence as f64 / stats.total_warnings as f64 * 100.0,
+                lints.join(", ")
+            )?;
+        }
         
         // Map categories to severity levels based on our definitions
         let mut severity_counts: HashMap<&str, usize> = HashMap::new();
@@ -55,7 +111,7 @@ impl<W: Write> MarkdownWriter<W> {
                 CategoryType::Safety => "Critical",
                 CategoryType::Performance => "High",
                 CategoryType::Documentation => "Medium",
-                CategoryType::Style => "Low",
+                CategoryType::Style | CategoryType::Uncategorized => "Low",
             };
             *severity_counts.entry(severity).or_default() += count;
         }
@@ -76,7 +132,7 @@ impl<W: Write> MarkdownWriter<W> {
             .iter()
             .map(|(k, v)| (format!("{:?}", k), *v))
             .collect();
-        category_data.sort_by(|a, b| b.1.cmp(&a.1));  // Sort by count in descending order
+        category_data.sort_by_key(|&(_, count)| std::cmp::Reverse(count));  // Sort by count in descending order
         self.write_chart("Category Distribution", &category_data)?;
 
         // Add subcategory insights (already sorted)
@@ -91,13 +147,20 @@ impl<W: Write> MarkdownWriter<W> {
         Ok(())
     }
 
+    /// Links chart images (alt text, relative path) rendered next to the report.
+    pub fn write_chart_images(&mut self, images: &[(&str, String)]) -> io::Result<()> {
+        if images.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.writer, "### Charts\n")?;
+        for (alt, path) in images {
+            writeln!(self.writer, "![{}]({})\n", alt, path)?;
+        }
+        Ok(())
+    }
+
     fn write_chart(&mut self, title: &str, data: &[(String, usize)]) -> io::Result<()> {
-        let chart_config = ChartConfig {
-            style: ChartStyle::Blocks,
-            color: None,
-            width: 60,
-            show_percentage: true,
-        };
+        let chart_config = ChartConfig::new(ChartStyle::Blocks, 60, true);
 
         let chart = create_enhanced_chart(data, chart_config);
         writeln!(self.writer, "#### {}\n", title)?;
@@ -114,6 +177,330 @@ impl<W: Write> MarkdownWriter<W> {
         Ok(())
     }
 
+    /// Lists the most frequent warning patterns.
+    pub fn write_recurring_patterns(&mut self, clusters: &[WarningCluster]) -> io::Result<()> {
+        let recurring: Vec<&WarningCluster> = clusters
+            .iter()
+            .filter(|cluster| cluster.count > 1)
+            .take(MAX_RECURRING_PATTERNS)
+            .collect();
+        if recurring.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(self.writer, "## Recurring Patterns\n")?;
+        for cluster in recurring {
+            writeln!(self.writer, "- {}: \"{}\"", cluster.summary(), cluster.template)?;
+        }
+        writeln!(self.writer)
+    }
+
+    /// Tabulates measured unsafe code per crate; writes nothing unless unsafe
+    /// metrics were collected.
+    pub fn write_unsafe_usage(&mut self, stats: &UnsafeStatistics) -> io::Result<()> {
+        let Some(source) = stats.measured_by else { return Ok(()) };
+
+        writeln!(self.writer, "## Unsafe Code\n")?;
+        match source {
+            UnsafeMetricsSource::Geiger => writeln!(
+                self.writer,
+                "Unsafe blocks, functions, and impls counted by cargo-geiger; pointers, FFI, and statics scanned from files with warnings.\n"
+            )?,
+            UnsafeMetricsSource::SourceScan => writeln!(self.writer, "Scanned from files with warnings.\n")?,
+        }
+        writeln!(self.writer, "| Crate | Unsafe blocks | Unsafe fns | Unsafe impls | Raw pointers | FFI fns | `static mut` |")?;
+        writeln!(self.writer, "|---|---:|---:|---:|---:|---:|---:|")?;
+        for (crate_name, usage) in &stats.by_crate {
+            writeln!(
+                self.writer,
+                "| {} | {} | {} | {} | {} | {} | {} |",
+                crate_name,
+                usage.unsafe_blocks,
+                usage.unsafe_functions,
+                usage.unsafe_impls,
+                usage.raw_pointers,
+                usage.ffi_functions,
+                usage.mutable_statics
+            )?;
+        }
+        writeln!(
+            self.writer,
+            "\nTotal: {} unsafe items, {} raw pointers, {} FFI functions, {} mutable statics\n",
+            stats.total_unsafe, stats.raw_pointers, stats.ffi_calls, stats.mutable_statics
+        )
+    }
+
+    /// Lists the alert rules that fired, with the counts that triggered them.
+    pub fn write_alerts(&mut self, alerts: &[Alert]) -> io::Result<()> {
+        writeln!(self.writer, "## Alerts\n")?;
+        writeln!(self.writer, "| Lint | Since | Then | Now | Increase | Rule |")?;
+        writeln!(self.writer, "|---|---|---|---|---|---|")?;
+        for alert in alerts {
+            writeln!(
+                self.writer,
+                "| `{}` | {} | {} | {} | {:.1} per {} | `{}` |",
+                alert.rule.lint,
+                alert.since,
+                alert.previous,
+                alert.current,
+                alert.rate,
+                alert.rule.period,
+                alert.rule
+            )?;
+        }
+        writeln!(self.writer)
+    }
+
+    /// Lists the warnings of touched files that are not on changed lines.
+    pub fn write_pre_existing(&mut self, base: &str, warnings: &[Warning]) -> io::Result<()> {
+        writeln!(self.writer, "## Pre-existing Warnings in Touched Files\n")?;
+        writeln!(
+            self.writer,
+            "{} warnings in files changed since `{}`, on lines the change leaves alone.\n",
+            warnings.len(),
+            base
+        )?;
+        let mut sorted: Vec<&Warning> = warnings.iter().collect();
+        sorted.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        writeln!(self.writer, "| Location | Priority | Lint | Message |")?;
+        writeln!(self.writer, "|---|---|---|---|")?;
+        for warning in sorted.iter().take(MAX_PRE_EXISTING_LISTED) {
+            writeln!(
+                self.writer,
+                "| `{}:{}` | {} | `{}` | {} |",
+                warning.file,
+                warning.line,
+                warning.priority,
+                warning.lint_code,
+                warning.primary_message.lines().next().unwrap_or_default().replace('|', "\\|")
+            )?;
+        }
+        if sorted.len() > MAX_PRE_EXISTING_LISTED {
+            writeln!(self.writer, "\n…and {} more.", sorted.len() - MAX_PRE_EXISTING_LISTED)?;
+        }
+        writeln!(self.writer)
+    }
+
+    /// Lists unused dependencies with the command that removes each.
+    pub fn write_unused_dependencies(&mut self, unused: &UnusedDependencies) -> io::Result<()> {
+        writeln!(self.writer, "## Unused Dependencies\n")?;
+        if unused.is_empty() {
+            writeln!(self.writer, "No unused dependencies found by {}.\n", unused.check)?;
+            return Ok(());
+        }
+
+        writeln!(self.writer, "Found by {}.\n", unused.check)?;
+        writeln!(self.writer, "| Package | Dependency | Table | Suggested removal |")?;
+        writeln!(self.writer, "|---|---|---|---|")?;
+        for dependency in &unused.dependencies {
+            writeln!(
+                self.writer,
+                "| {} | `{}` | `{}` | `{}` |",
+                dependency.package,
+                dependency.dependency,
+                dependency.kind.table(),
+                dependency.removal_command()
+            )?;
+        }
+        if unused.check == DependencyCheck::Lint {
+            writeln!(self.writer, "\nThe lint cannot tell dev and build dependencies apart; check the table before removing.")?;
+        }
+        writeln!(self.writer)
+    }
+
+    pub fn write_hidden_code(&mut self, report: &HiddenCodeReport) -> io::Result<()> {
+        writeln!(self.writer, "## Code Clippy Cannot See\n")?;
+        if report.items.is_empty() {
+            writeln!(self.writer, "No ignored tests or inactive cfg items in {} files.\n", report.files_scanned)?;
+            return Ok(());
+        }
+
+        writeln!(
+            self.writer,
+            "{} ignored tests and {} items behind features the analyzed feature set leaves off, in {} files. \
+             Neither is linted or run; check that they still build and are still needed.\n",
+            report.ignored_tests(),
+            report.inactive_items(),
+            report.files_scanned
+        )?;
+        writeln!(self.writer, "| Location | Item | Reason |")?;
+        writeln!(self.writer, "|---|---|---|")?;
+        for hidden in &report.items {
+            writeln!(
+                self.writer,
+                "| {}:{} | `{}` | {} |",
+                hidden.file,
+                hidden.line,
+                hidden.item.replace('|', "\\|"),
+                hidden.reason.to_string().replace('|', "\\|")
+            )?;
+        }
+        writeln!(self.writer)
+    }
+
+    pub fn write_third_party(&mut self, warnings: &[Warning]) -> io::Result<()> {
+        writeln!(self.writer, "## Third-Party Code\n")?;
+        writeln!(
+            self.writer,
+            "{} warnings in dependencies and files outside the workspace, left out of the totals and gates. \
+             Set `include-third-party` to count them.\n",
+            warnings.len()
+        )?;
+        writeln!(self.writer, "| Source | Warnings | Lints |")?;
+        writeln!(self.writer, "|---|---:|---|")?;
+        for (origin, lints) in third_party_breakdown(warnings) {
+            let mut lints: Vec<(&str, usize)> = lints.into_iter().collect();
+            lints.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+            let listed: Vec<String> = lints.iter().map(|(lint, count)| format!("`{}` ({})", lint, count)).collect();
+            writeln!(
+                self.writer,
+                "| {} | {} | {} |",
+                origin,
+                lints.iter().map(|(_, count)| count).sum::<usize>(),
+                listed.join(", ")
+            )?;
+        }
+        writeln!(self.writer)
+    }
+
+    pub fn write_performance_impact(&mut self, impacts: &[PerformanceImpact]) -> io::Result<()> {
+        writeln!(self.writer, "## Performance Impact\n")?;
+        if impacts.is_empty() {
+            writeln!(self.writer, "No performance warnings in code cargo-bloat or the build timings measured.\n")?;
+            return Ok(());
+        }
+
+        writeln!(
+            self.writer,
+            "Performance warnings in code that measurably adds to binary size or compile time, largest first; \
+             fixing these first has the most effect. Sizes are of the release build, summed over monomorphizations.\n"
+        )?;
+        writeln!(self.writer, "| Location | Lint | Function | Function size | Crate size | Crate compile time |")?;
+        writeln!(self.writer, "|---|---|---|---:|---:|---:|")?;
+        let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        for impact in impacts {
+            writeln!(
+                self.writer,
+                "| {}:{} | `{}` | {} | {} | {} | {} |",
+                impact.warning.file,
+                impact.warning.line,
+                impact.warning.lint_code,
+                or_dash(impact.function.as_ref().map(|function| format!("`{}`", function))),
+                or_dash(impact.function_bytes.map(|bytes| Bytes(bytes).to_string())),
+                or_dash(impact.crate_bytes.map(|bytes| Bytes(bytes).to_string())),
+                or_dash(impact.compile_seconds.map(|seconds| format!("{:.1} s", seconds)))
+            )?;
+        }
+        writeln!(self.writer)
+    }
+
+    /// Writes the crate × category heat table.
+    ///
+    /// Markdown has no cell colors, so heat levels are shown as colored squares.
+    pub fn write_heat_table(&mut self, table: &HeatTable) -> io::Result<()> {
+        const MARKERS: [&str; 5] = ["", "🟩 ", "🟨 ", "🟧 ", "🟥 "];
+
+        writeln!(self.writer, "## Severity Heat Table\n")?;
+        if table.is_empty() {
+            writeln!(self.writer, "No warnings to tabulate.\n")?;
+            return Ok(());
+        }
+
+        write!(self.writer, "| Crate |")?;
+        for category in HEAT_COLUMNS {
+            write!(self.writer, " {} |", category)?;
+        }
+        writeln!(self.writer, " Total |")?;
+        writeln!(self.writer, "|---|{}---|", "---:|".repeat(HEAT_COLUMNS.len()))?;
+
+        for row in &table.rows {
+            write!(self.writer, "| {} |", row.crate_name.replace('|', "\\|"))?;
+            for cell in &row.cells {
+                let marker = MARKERS[cell.level as usize % MARKERS.len()];
+                write!(self.writer, " {}{} |", marker, cell.count)?;
+            }
+            writeln!(self.writer, " {} |", row.total)?;
+        }
+
+        writeln!(self.writer, "\nHeat reflects warning count weighted by category severity: 🟩 low → 🟥 high.\n")?;
+        Ok(())
+    }
+
+    /// Writes the module hierarchy as a Mermaid graph colored by warning density.
+    pub fn write_module_heatmap(&mut self, tree: &ModuleTree) -> io::Result<()> {
+        const FILLS: [&str; 5] = ["#ffffff", "#e8f5e9", "#fff59d", "#ffb74d", "#e57373"];
+
+        writeln!(self.writer, "## Module Heatmap\n")?;
+        if tree.is_empty() {
+            writeln!(self.writer, "No warnings to map.\n")?;
+            return Ok(());
+        }
+
+        writeln!(self.writer, "```mermaid")?;
+        writeln!(self.writer, "graph LR")?;
+        let mut next_id = 0;
+        for node in &tree.roots {
+            self.write_module_node(node, None, &mut next_id)?;
+        }
+        for (level, fill) in FILLS.iter().enumerate() {
+            writeln!(self.writer, "  classDef heat{} fill:{},stroke:#999,color:#222", level, fill)?;
+        }
+        writeln!(self.writer, "```\n")?;
+        writeln!(self.writer, "Color reflects warnings per affected file: 🟩 low → 🟥 high.\n")
+    }
+
+    /// Writes the modules with the highest severity-weighted scores and
+    /// their share of each category's warnings.
+    pub fn write_module_breakdown(&mut self, tree: &DirectoryTree, stats: &WarningStatistics) -> io::Result<()> {
+        writeln!(self.writer, "## Module Breakdown\n")?;
+        if tree.is_empty() {
+            writeln!(self.writer, "No warnings to break down.\n")?;
+            return Ok(());
+        }
+
+        write!(self.writer, "| Module | Warnings | Score |")?;
+        for category in HEAT_COLUMNS {
+            write!(self.writer, " {} |", category)?;
+        }
+        writeln!(self.writer)?;
+        writeln!(self.writer, "|---|---:|---:|{}", "---:|".repeat(HEAT_COLUMNS.len()))?;
+
+        for (module, dir_stats) in tree.top(MODULE_BREAKDOWN_ROWS) {
+            write!(self.writer, "| `{}` | {} | {} |", module, dir_stats.warnings, dir_stats.score)?;
+            for category in HEAT_COLUMNS {
+                let count = *dir_stats.by_category.get(&category).unwrap_or(&0);
+                let total = *stats.by_category.get(&category).unwrap_or(&0);
+                write!(self.writer, " {} ({:.0}%) |", count, dir_stats.category_share(category, total))?;
+            }
+            writeln!(self.writer)?;
+        }
+
+        writeln!(self.writer, "\nScore weights each warning by priority, from Critical (5) to Trivial (1). \
+            Category cells give the module's share of all warnings in that category.\n")?;
+        Ok(())
+    }
+
+    fn write_module_node(&mut self, node: &ModuleNode, parent: Option<usize>, next_id: &mut usize) -> io::Result<()> {
+        let id = *next_id;
+        *next_id += 1;
+        writeln!(
+            self.writer,
+            "  m{}[\"{}<br/>{} warnings · {} files\"]:::heat{}",
+            id,
+            mermaid_label(&node.name),
+            node.warnings,
+            node.files,
+            node.level
+        )?;
+        if let Some(parent) = parent {
+            writeln!(self.writer, "  m{} --> m{}", parent, id)?;
+        }
+        for child in &node.children {
+            self.write_module_node(child, Some(id), next_id)?;
+        }
+        Ok(())
+    }
+
     pub fn write_trend_analysis(&mut self, trends: &TrendAnalysis, historical: &[TrendAnalysis]) -> io::Result<()> {
         writeln!(self.writer, "## Trend Analysis\n")?;
         
@@ -129,6 +516,7 @@ impl<W: Write> MarkdownWriter<W> {
                 (CategoryType::Performance, "HIGH"),
                 (CategoryType::Documentation, "MEDIUM"),
                 (CategoryType::Style, "LOW"),
+                (CategoryType::Uncategorized, "UNKNOWN"),
             ];
             
             for (category, severity) in ordered_categories {
@@ -145,6 +533,22 @@ impl<W: Write> MarkdownWriter<W> {
             
             writeln!(self.writer, "### Warning Count Trends\n")?;
             self.write_chart("Historical Trends", &trend_data)?;
+            self.write_category_sparklines(trends, historical)?;
+
+            writeln!(self.writer, "### Improvement Rate\n")?;
+            writeln!(self.writer, "| Measure | Improvement |")?;
+            writeln!(self.writer, "|---|---|")?;
+            writeln!(self.writer, "| All warnings | {:+.1}% |", trends.improvement_rate * 100.0)?;
+            writeln!(self.writer, "| Severity-weighted | {:+.1}% |", trends.weighted_improvement_rate * 100.0)?;
+            for category in [CategoryType::Safety, CategoryType::Performance, CategoryType::Documentation, CategoryType::Style, CategoryType::Uncategorized] {
+                if let Some(rate) = trends.category_improvement.get(&category) {
+                    writeln!(self.writer, "| {} | {:+.1}% |", category, rate * 100.0)?;
+                }
+            }
+            writeln!(self.writer)?;
+            if let (Some(new), Some(fixed)) = (trends.new_warnings, trends.fixed_warnings) {
+                writeln!(self.writer, "Since the previous run: **{}** new warnings, **{}** fixed.\n", new, fixed)?;
+            }
 
             // Add category trend analysis
             writeln!(self.writer, "### Category Trends\n")?;
@@ -153,15 +557,16 @@ impl<W: Write> MarkdownWriter<W> {
                 writeln!(self.writer, "- {}", insight)?;
             }
 
+            self.write_top_recurring(trends, historical)?;
+            if let Some(stability) = RankingStability::compute(trends, historical, RANKING_TOP_FILES) {
+                self.write_ranking_stability(&stability)?;
+            }
+
             // Add risk level changes
             writeln!(self.writer, "\n### Risk Level Changes\n")?;
+            let delta = historical.last().map(|previous| trends.diff(previous)).unwrap_or_default();
             for category in [CategoryType::Safety, CategoryType::Performance, CategoryType::Documentation, CategoryType::Style] {
-                let current = trends.by_category.get(&category).unwrap_or(&0);
-                let previous = historical.last()
-                    .and_then(|h| h.by_category.get(&category))
-                    .unwrap_or(&0);
-                
-                let change = *current as i64 - *previous as i64;
+                let change = delta.category(&category).change();
                 let direction = if change > 0 { "increased" } else if change < 0 { "decreased" } else { "unchanged" };
                 
                 writeln!(self.writer, "- {:?} issues have {} ({:+})", category, direction, change)?;
@@ -171,6 +576,97 @@ impl<W: Write> MarkdownWriter<W> {
         Ok(())
     }
 
+    /// Shows how much the worst-file ranking moves between runs, and which
+    /// of the current worst files are chronic.
+    fn write_ranking_stability(&mut self, stability: &RankingStability) -> io::Result<()> {
+        writeln!(self.writer, "\n### Ranking Stability\n")?;
+        let mean = stability.mean_correlation();
+        let verdict = if mean >= 0.7 { "stable" } else if mean >= 0.3 { "shifting" } else { "volatile" };
+        writeln!(
+            self.writer,
+            "Rank correlation of the top {} files between consecutive runs: **{:.2}** ({}, over {} runs).\n",
+            RANKING_TOP_FILES, mean, verdict, stability.runs
+        )?;
+        writeln!(self.writer, "| Rank | File | Warnings | Runs in top {} | Pattern |", RANKING_TOP_FILES)?;
+        writeln!(self.writer, "|---|---|---|---|---|")?;
+        for file in &stability.files {
+            writeln!(
+                self.writer,
+                "| {} | `{}` | {} | {}/{} | {} |",
+                file.rank, file.file, file.warnings, file.top_runs, stability.runs, file.pattern
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Lists the issues seen in the most runs of the history window.
+    fn write_top_recurring(&mut self, current: &TrendAnalysis, historical: &[TrendAnalysis]) -> io::Result<()> {
+        let issues = top_recurring(current, historical, MAX_RECURRING_ISSUES);
+        if issues.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.writer, "\n### Recurring Issues\n")?;
+        writeln!(self.writer, "| Issue | Runs | Occurrences |")?;
+        writeln!(self.writer, "|---|---|---|")?;
+        for issue in issues {
+            writeln!(
+                self.writer,
+                "| {} | {}/{} | {} |",
+                issue.key.replace('|', "\\|"),
+                issue.runs,
+                historical.len() + 1,
+                issue.occurrences
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes a sparkline per category across all runs, and a stacked bar of
+    /// the category mix of each run.
+    fn write_category_sparklines(&mut self, current: &TrendAnalysis, historical: &[TrendAnalysis]) -> io::Result<()> {
+        let runs: Vec<&TrendAnalysis> = historical.iter().chain(std::iter::once(current)).collect();
+        let count = |run: &TrendAnalysis, category: &CategoryType| run.by_category.get(category).copied().unwrap_or(0);
+
+        writeln!(self.writer, "### Category Sparklines\n")?;
+        writeln!(self.writer, "| Category | Trend | Previous | Current | Change |")?;
+        writeln!(self.writer, "|----------|-------|----------|---------|--------|")?;
+        let delta = historical.last().map(|previous| current.diff(previous)).unwrap_or_default();
+        for category in &HEAT_COLUMNS {
+            let series: Vec<usize> = runs.iter().map(|run| count(run, category)).collect();
+            let change = delta.category(category);
+            writeln!(
+                self.writer,
+                "| {} | `{}` | {} | {} | {} |",
+                category,
+                sparkline(&series),
+                change.previous,
+                change.current,
+                delta_arrow(change.previous, change.current)
+            )?;
+        }
+        writeln!(self.writer)?;
+
+        let rows: Vec<(String, Vec<usize>)> = runs
+            .iter()
+            .enumerate()
+            .map(|(i, run)| {
+                let label = if i + 1 == runs.len() {
+                    "Current".to_string()
+                } else {
+                    run.dates.first().cloned().unwrap_or_else(|| format!("Analysis {}", i + 1))
+                };
+                (label, HEAT_COLUMNS.iter().map(|category| count(run, category)).collect())
+            })
+            .collect();
+        let series: Vec<String> = HEAT_COLUMNS.iter().map(|category| category.to_string()).collect();
+        let series: Vec<&str> = series.iter().map(String::as_str).collect();
+
+        writeln!(self.writer, "#### Category Mix per Run\n")?;
+        writeln!(self.writer, "```")?;
+        write!(self.writer, "{}", create_stacked_chart(&rows, &series, 50))?;
+        writeln!(self.writer, "```\n")
+    }
+
     pub fn write_build_info(&mut self, context: &[AnalysisContext]) -> io::Result<()> {
         writeln!(self.writer, "## Build Configuration Analysis\n")?;
         
@@ -216,12 +712,16 @@ impl<W: Write> MarkdownWriter<W> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn generate_markdown_report<W: Write>(
     writer: W,
     stats: &WarningStatistics,
     trends: &TrendAnalysis,
     historical: &[TrendAnalysis],
     context: &[AnalysisContext],
+    chart_images: &[(&str, String)],
+    clusters: &[WarningCluster],
+    unused_deps: Option<&UnusedDependencies>,
 ) -> io::Result<()> {
     let mut md_writer = MarkdownWriter::new(writer);
     
@@ -229,6 +729,18 @@ pub fn generate_markdown_report<W: Write>(
     md_writer.write_header("Clippy Analysis Report")?;
     md_writer.write_summary(stats, &chrono::Local::now().format("%Y%m%d_%H%M%S").to_string())?;
 
+    md_writer.write_chart_images(chart_images)?;
+
+    // Write where each kind of debt lives
+    md_writer.write_heat_table(&HeatTable::from_stats(stats))?;
+    md_writer.write_module_heatmap(&ModuleTree::from_stats(stats))?;
+    md_writer.write_module_breakdown(&stats.by_directory, stats)?;
+    md_writer.write_recurring_patterns(clusters)?;
+    md_writer.write_unsafe_usage(&stats.safety_details.unsafe_details)?;
+    if let Some(unused) = unused_deps {
+        md_writer.write_unused_dependencies(unused)?;
+    }
+
     // Write build configuration
     md_writer.write_build_info(context)?;
 
@@ -236,4 +748,73 @@ pub fn generate_markdown_report<W: Write>(
     md_writer.write_trend_analysis(trends, historical)?;
 
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Escapes text for a quoted Mermaid node label.
+///
+/// Mermaid reads `#code;` as an entity, so `#` itself is escaped along with
+/// the characters that would end the label or the statement.
+fn mermaid_label(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '"' | '[' | ']' | '<' | '>' | '#' | ';' => format!("#{};", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Writes the analysis report of a run without warnings: the clean summary,
+/// build configuration, and trends.
+pub fn generate_clean_markdown_report<W: Write>(
+    writer: W,
+    trends: &TrendAnalysis,
+    historical: &[TrendAnalysis],
+    context: &[AnalysisContext],
+) -> io::Result<()> {
+    let mut md_writer = MarkdownWriter::new(writer);
+    md_writer.write_header("Clippy Analysis Report")?;
+    md_writer.write_clean_summary(historical.last().map(|run| run.total_warnings))?;
+    md_writer.write_build_info(context)?;
+    md_writer.write_trend_analysis(trends, historical)
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(write: impl FnOnce(&mut MarkdownWriter<&mut Vec<u8>>) -> io::Result<()>) -> String {
+        let mut out = Vec::new();
+        write(&mut MarkdownWriter::new(&mut out)).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_heat_table_escapes_crate_names() {
+        let mut stats = WarningStatistics::default();
+        stats.by_crate_category.insert(
+            "a|b".to_string(),
+            HashMap::from([(CategoryType::Safety, 2), (CategoryType::Style, 1)]),
+        );
+        let table = HeatTable::from_stats(&stats);
+
+        let output = render(|md| md.write_heat_table(&table));
+        assert!(output.contains("| a\\|b | 🟥 2 | 0 | 0 | 🟩 1 | 0 | 3 |"));
+    }
+
+    #[test]
+    fn test_module_heatmap_escapes_labels() {
+        let tree = ModuleTree {
+            roots: vec![ModuleNode {
+                name: "a\"b[c]<d>#e;f".to_string(),
+                warnings: 3,
+                files: 1,
+                level: 2,
+                children: Vec::new(),
+            }],
+        };
+
+        let output = render(|md| md.write_module_heatmap(&tree));
+        assert!(output.contains(
+            "  m0[\"a#34;b#91;c#93;#60;d#62;#35;e#59;f<br/>3 warnings · 1 files\"]:::heat2"
+        ));
+    }
+}
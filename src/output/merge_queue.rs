@@ -0,0 +1,164 @@
+//! Merge queue status payload.
+//!
+//! The payload follows the shape of a GitHub check run (`name`, `head_sha`,
+//! `status`, `conclusion`, `output`) so merge automation such as bors or
+//! Mergify can treat the analyzer as a required check. The top blocking
+//! findings are included both in the rendered text and as structured data.
+
+use std::io::{self, Write};
+use serde::Serialize;
+use crate::{
+    analysis::{gate::GateVerdict, statistics::warning::WarningStatistics},
+    types::Warning,
+};
+
+/// Number of blocking findings listed in the payload
+pub const MAX_BLOCKING_FINDINGS: usize = 10;
+
+#[derive(Debug, Serialize)]
+pub struct MergeQueueStatus {
+    pub name: String,
+    pub head_sha: Option<String>,
+    pub status: String,
+    pub conclusion: String,
+    pub output: CheckOutput,
+    pub blocking_findings: Vec<BlockingFinding>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckOutput {
+    pub title: String,
+    pub summary: String,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlockingFinding {
+    pub lint: String,
+    pub priority: String,
+    pub file: String,
+    pub line: u32,
+    pub message: String,
+}
+
+impl MergeQueueStatus {
+    pub fn new(
+        stats: &WarningStatistics,
+        verdict: &GateVerdict,
+        warnings: &[Warning],
+        head_sha: Option<String>,
+    ) -> Self {
+        let blocking_findings: Vec<BlockingFinding> = verdict
+            .blocking_warnings(warnings, MAX_BLOCKING_FINDINGS)
+            .into_iter()
+            .map(|w| BlockingFinding {
+                lint: w.lint_code.clone(),
+                priority: w.priority.to_string(),
+                file: w.file.clone(),
+                line: w.line,
+                message: w.primary_message.clone(),
+            })
+            .collect();
+
+        let (conclusion, title) = if verdict.passed() {
+            ("success", format!("Gate passed: {} warnings", stats.total_warnings))
+        } else {
+            ("failure", format!("Gate failed: {} rule(s) exceeded", verdict.violations.len()))
+        };
+
+        let mut summary = format!(
+            "{} warnings in {} files.",
+            stats.total_warnings, stats.files_affected
+        );
+        for violation in &verdict.violations {
+            summary.push_str(&format!("\n- {}", violation));
+        }
+
+        let mut text = String::new();
+        if !blocking_findings.is_empty() {
+            text.push_str("### Top blocking findings\n\n");
+            for finding in &blocking_findings {
+                text.push_str(&format!(
+                    "- **{}** `{}` at {}:{} — {}\n",
+                    finding.priority, finding.lint, finding.file, finding.line, finding.message
+                ));
+            }
+        }
+
+        Self {
+            name: "cargo-analyzer".to_string(),
+            head_sha,
+            status: "completed".to_string(),
+            conclusion: conclusion.to_string(),
+            output: CheckOutput { title, summary, text },
+            blocking_findings,
+        }
+    }
+}
+
+pub fn write_merge_queue_status<W: Write>(writer: W, status: &MergeQueueStatus) -> io::Result<()> {
+    serde_json::to_writer_pretty(writer, status)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::gate::{GateRule, GateViolation};
+    use crate::types::Priority;
+
+    fn stats(total: usize) -> WarningStatistics {
+        WarningStatistics { total_warnings: total, files_affected: 1, ..Default::default() }
+    }
+
+    #[test]
+    fn test_passing_status() {
+        let status = MergeQueueStatus::new(&stats(2), &GateVerdict::default(), &[], Some("abc123".to_string()));
+        let mut out = Vec::new();
+        write_merge_queue_status(&mut out, &status).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+        assert_eq!(json["name"], "cargo-analyzer");
+        assert_eq!(json["head_sha"], "abc123");
+        assert_eq!(json["status"], "completed");
+        assert_eq!(json["conclusion"], "success");
+        assert_eq!(json["output"]["title"], "Gate passed: 2 warnings");
+        assert_eq!(json["output"]["text"], "");
+        assert_eq!(json["blocking_findings"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_failing_status() {
+        let warnings = [
+            Warning { priority: Priority::Low, ..Warning::for_test("clippy::needless_return", "src/a.rs", 1) },
+            Warning {
+                priority: Priority::Critical,
+                primary_message: "usage of `mem::forget`".to_string(),
+                ..Warning::for_test("clippy::mem_forget", "src/b.rs", 7)
+            },
+        ];
+        let verdict = GateVerdict {
+            violations: vec![GateViolation { rule: GateRule::MaxCritical, limit: 0, actual: 1 }],
+        };
+        let status = MergeQueueStatus::new(&stats(2), &verdict, &warnings, None);
+        let mut out = Vec::new();
+        write_merge_queue_status(&mut out, &status).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+        assert_eq!(json["head_sha"], serde_json::Value::Null);
+        assert_eq!(json["conclusion"], "failure");
+        assert_eq!(json["output"]["title"], "Gate failed: 1 rule(s) exceeded");
+        assert_eq!(json["output"]["summary"], "2 warnings in 1 files.\n- max-critical: 1 warnings (limit 0)");
+        assert_eq!(
+            json["blocking_findings"],
+            serde_json::json!([{
+                "lint": "clippy::mem_forget",
+                "priority": "Critical",
+                "file": "src/b.rs",
+                "line": 7,
+                "message": "usage of `mem::forget`",
+            }])
+        );
+        assert!(json["output"]["text"].as_str().unwrap().contains("- **Critical** `clippy::mem_forget` at src/b.rs:7"));
+    }
+}
@@ -1,11 +1,74 @@
+#[cfg(feature = "terminal")]
 pub mod color;
 pub mod report;
 pub mod formatter;
 pub mod markdown;
 pub mod fix_plan;
+pub mod html;
+pub mod merge_queue;
+pub mod playbook;
+pub mod audit_notes;
+pub mod binary_export;
+pub mod csv_export;
+pub mod json_export;
+pub mod svg_charts;
+pub mod baseline_report;
+pub mod false_positive_report;
+pub mod diff;
+#[cfg(feature = "terminal")]
+pub mod preview;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod progress;
+pub mod snippet;
+pub mod hyperlink;
+pub mod template;
+pub mod badge;
+pub mod bundle;
+pub mod analysis_bundle;
+pub mod formats;
+pub mod prometheus;
+pub mod notification;
+pub mod pr_summary;
+pub mod rendered_appendix;
+pub mod run_manifest;
+pub mod sarif;
+pub mod log;
 
+#[cfg(feature = "terminal")]
 pub use color::ColorWriter;
-pub use report::{write_trend_analysis, write_colored_section};
+pub use report::write_trend_analysis;
+#[cfg(feature = "terminal")]
+pub use report::write_colored_section;
+#[cfg(feature = "terminal")]
 pub use formatter::format_warning;
 pub use markdown::{MarkdownWriter, generate_markdown_report};
-pub use fix_plan::FixPlanGenerator;
\ No newline at end of file
+pub use fix_plan::FixPlanGenerator;
+pub use html::{HtmlWriter, generate_html_report};
+pub use playbook::{Playbook, write_playbook};
+pub use merge_queue::{MergeQueueStatus, write_merge_queue_status};
+pub use audit_notes::{AuditNotes, write_audit_notes};
+pub use csv_export::{CsvColumn, write_csv};
+pub use json_export::{WARNING_SCHEMA, write_json, write_jsonl};
+pub use binary_export::{PROTO_SCHEMA, WarningSet, write_binary_export};
+pub use svg_charts::SvgCharts;
+pub use baseline_report::write_baseline_report;
+pub use false_positive_report::write_false_positive_report;
+pub use diff::DiffLayout;
+#[cfg(feature = "terminal")]
+pub use preview::FixPreview;
+pub use log::{Logger, Verbosity};
+pub use snippet::{Snippet, SnippetReader};
+pub use hyperlink::EditorLinks;
+pub use template::{TemplateConfig, TemplateContext, render_template};
+pub use badge::{Badge, write_badge_svg, write_shields_endpoint};
+pub use analysis_bundle::{BUNDLE_VERSION, BundleManifest, write_analysis_bundle};
+pub use bundle::Artifact;
+pub use formats::ReportFormat;
+pub use notification::{RunSummary, WebhookConfig};
+pub use pr_summary::{PR_COMMENT_FILE, PrComment, write_pr_comment};
+pub use prometheus::{PROMETHEUS_FILE, write_prometheus, write_prometheus_file};
+pub use rendered_appendix::write_rendered_appendix;
+pub use run_manifest::{RUN_MANIFEST_FILE, RunManifest, write_run_manifest};
+pub use sarif::{SarifLog, write_sarif};
+pub use progress::{NoProgress, ProgressReporter, Stage, default_progress_reporter};
\ No newline at end of file
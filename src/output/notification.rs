@@ -0,0 +1,275 @@
+//! Regression notifications.
+//!
+//! Each `[[notify]]` table in the config posts a summary of the run to a
+//! webhook when one of its triggers fires:
+//!
+//! ```toml
+//! [[notify]]
+//! kind = "slack"                  # slack, teams, or generic (default)
+//! url-env = "SLACK_WEBHOOK_URL"   # or `url = "https://..."`
+//! on = ["new-critical", "increase", "alert", "gate-failed"]
+//! report-url = "https://ci.example.com/artifacts/clippy_summary.html"
+//! ```
+//!
+//! Triggers compare against the previous recorded run, so `increase` and
+//! `new-critical` need history (`history-db` or `clippy_historical.json`).
+//! Without `on`, a webhook fires on `increase` and `new-critical`. Generic
+//! webhooks receive the fields of [`RunSummary`] plus `text` and
+//! `report_url`.
+
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use crate::{
+    analysis::{alerts::Alert, gate::GateVerdict, statistics::warning::WarningStatistics, trends::TrendAnalysis},
+    types::{Completeness, Priority},
+};
+
+/// Message format of a webhook.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookKind {
+    /// Slack incoming webhook
+    Slack,
+    /// Microsoft Teams incoming webhook (MessageCard)
+    Teams,
+    /// Plain JSON with the run's numbers
+    #[default]
+    Generic,
+}
+
+impl fmt::Display for WebhookKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookKind::Slack => write!(f, "Slack"),
+            WebhookKind::Teams => write!(f, "Teams"),
+            WebhookKind::Generic => write!(f, "webhook"),
+        }
+    }
+}
+
+/// Condition under which a webhook is notified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotifyTrigger {
+    /// More warnings than the previous run
+    Increase,
+    /// More critical warnings than the previous run
+    NewCritical,
+    /// An `alert-if` rule matched
+    Alert,
+    /// The gate failed
+    GateFailed,
+    /// Every run
+    Always,
+}
+
+impl fmt::Display for NotifyTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotifyTrigger::Increase => write!(f, "warnings increased"),
+            NotifyTrigger::NewCritical => write!(f, "new critical warnings"),
+            NotifyTrigger::Alert => write!(f, "alert"),
+            NotifyTrigger::GateFailed => write!(f, "gate failed"),
+            NotifyTrigger::Always => write!(f, "run finished"),
+        }
+    }
+}
+
+fn default_triggers() -> Vec<NotifyTrigger> {
+    vec![NotifyTrigger::Increase, NotifyTrigger::NewCritical]
+}
+
+/// One `[[notify]]` table.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WebhookConfig {
+    /// Webhook URL; prefer `url-env` for URLs that embed a secret
+    pub url: Option<String>,
+    /// Environment variable holding the webhook URL
+    pub url_env: Option<String>,
+    #[serde(default)]
+    pub kind: WebhookKind,
+    #[serde(default = "default_triggers")]
+    pub on: Vec<NotifyTrigger>,
+    /// Link to the published report, included in the message
+    pub report_url: Option<String>,
+}
+
+impl WebhookConfig {
+    /// The webhook URL, from `url` or the `url-env` variable; `None` when
+    /// neither is set, e.g. outside CI.
+    pub fn resolve_url(&self) -> Option<String> {
+        self.url
+            .clone()
+            .or_else(|| self.url_env.as_ref().and_then(|name| std::env::var(name).ok()))
+            .filter(|url| !url.trim().is_empty())
+    }
+}
+
+/// The numbers a notification reports.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RunSummary {
+    pub total_warnings: usize,
+    /// Total of the previous recorded run
+    pub previous_total: Option<usize>,
+    pub critical: usize,
+    pub previous_critical: Option<usize>,
+    /// Warnings new and fixed since the previous run, by fingerprint
+    pub new_warnings: Option<usize>,
+    pub fixed_warnings: Option<usize>,
+    /// Matched `alert-if` rules, as displayed
+    pub alerts: Vec<String>,
+    pub gate_failed: bool,
+    pub complete: bool,
+}
+
+impl RunSummary {
+    pub fn new(
+        stats: &WarningStatistics,
+        trend: &TrendAnalysis,
+        historical: &[TrendAnalysis],
+        alerts: &[Alert],
+        verdict: &GateVerdict,
+        completeness: &Completeness,
+    ) -> Self {
+        let previous = historical.last();
+        let critical = |by_priority: &std::collections::HashMap<Priority, usize>| {
+            by_priority.get(&Priority::Critical).copied().unwrap_or(0)
+        };
+        Self {
+            total_warnings: stats.total_warnings,
+            previous_total: previous.map(|run| run.total_warnings),
+            critical: critical(&stats.by_priority),
+            previous_critical: previous.map(|run| critical(&run.by_priority)),
+            new_warnings: trend.new_warnings,
+            fixed_warnings: trend.fixed_warnings,
+            alerts: alerts.iter().map(|alert| alert.to_string()).collect(),
+            gate_failed: !verdict.passed(),
+            complete: completeness.is_complete(),
+        }
+    }
+
+    /// Change in total warnings since the previous run.
+    pub fn change(&self) -> Option<i64> {
+        self.previous_total.map(|previous| self.total_warnings as i64 - previous as i64)
+    }
+
+    /// Critical warnings added since the previous run.
+    pub fn new_critical(&self) -> Option<usize> {
+        self.previous_critical.map(|previous| self.critical.saturating_sub(previous))
+    }
+
+    /// The triggers of `on` that fired.
+    pub fn fired(&self, on: &[NotifyTrigger]) -> Vec<NotifyTrigger> {
+        on.iter()
+            .copied()
+            .filter(|trigger| match trigger {
+                NotifyTrigger::Increase => self.change().is_some_and(|change| change > 0),
+                NotifyTrigger::NewCritical => self.new_critical().is_some_and(|new| new > 0),
+                NotifyTrigger::Alert => !self.alerts.is_empty(),
+                NotifyTrigger::GateFailed => self.gate_failed,
+                NotifyTrigger::Always => true,
+            })
+            .collect()
+    }
+
+    /// Summary lines in Slack/Teams markdown.
+    fn lines(&self, fired: &[NotifyTrigger]) -> Vec<String> {
+        let mut lines = Vec::new();
+        let reasons: Vec<String> = fired.iter().map(|trigger| trigger.to_string()).collect();
+        lines.push(format!("*Clippy analysis: {}*", reasons.join(", ")));
+        let mut total = format!("{} warnings", self.total_warnings);
+        if let Some(change) = self.change() {
+            total.push_str(&format!(" ({:+} since the previous run)", change));
+        }
+        lines.push(total);
+        let mut critical = format!("{} critical", self.critical);
+        if let Some(new) = self.new_critical().filter(|new| *new > 0) {
+            critical.push_str(&format!(" ({} new)", new));
+        }
+        lines.push(critical);
+        if let (Some(new), Some(fixed)) = (self.new_warnings, self.fixed_warnings) {
+            lines.push(format!("{} new, {} fixed", new, fixed));
+        }
+        lines.extend(self.alerts.iter().map(|alert| format!("Alert: {}", alert)));
+        if self.gate_failed {
+            lines.push("Gate failed".to_string());
+        }
+        if !self.complete {
+            lines.push("Incomplete run: counts may be too low".to_string());
+        }
+        lines
+    }
+
+    /// The JSON body posted to a webhook of `kind`.
+    pub fn payload(&self, kind: WebhookKind, fired: &[NotifyTrigger], report_url: Option<&str>) -> serde_json::Value {
+        let mut lines = self.lines(fired);
+        match kind {
+            WebhookKind::Slack => {
+                if let Some(url) = report_url {
+                    lines.push(format!("<{}|View report>", url));
+                }
+                serde_json::json!({ "text": lines.join("\n") })
+            }
+            WebhookKind::Teams => {
+                let title = lines.remove(0).trim_matches('*').to_string();
+                let mut card = serde_json::json!({
+                    "@type": "MessageCard",
+                    "@context": "https://schema.org/extensions",
+                    "summary": title,
+                    "title": title,
+                    "themeColor": if self.new_critical().is_some_and(|new| new > 0) || self.gate_failed { "E05D44" } else { "DFB317" },
+                    "text": lines.join("\n\n"),
+                });
+                if let Some(url) = report_url {
+                    card["potentialAction"] = serde_json::json!([{
+                        "@type": "OpenUri",
+                        "name": "View report",
+                        "targets": [{ "os": "default", "uri": url }],
+                    }]);
+                }
+                card
+            }
+            WebhookKind::Generic => {
+                let mut body = serde_json::to_value(self).unwrap_or_default();
+                body["triggers"] = fired.iter().map(|trigger| trigger.to_string()).collect();
+                body["text"] = lines.join("\n").into();
+                body["report_url"] = report_url.into();
+                body
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regression_triggers_and_payloads() {
+        let summary = RunSummary {
+            total_warnings: 12,
+            previous_total: Some(10),
+            critical: 3,
+            previous_critical: Some(3),
+            new_warnings: Some(4),
+            fixed_warnings: Some(2),
+            complete: true,
+            ..RunSummary::default()
+        };
+        let fired = summary.fired(&default_triggers());
+        assert_eq!(fired, [NotifyTrigger::Increase]);
+        assert!(RunSummary { previous_total: None, ..summary.clone() }.fired(&default_triggers()).is_empty());
+
+        let slack = summary.payload(WebhookKind::Slack, &fired, Some("https://ci/report.html"));
+        assert_eq!(
+            slack["text"],
+            "*Clippy analysis: warnings increased*\n12 warnings (+2 since the previous run)\n3 critical\n4 new, 2 fixed\n<https://ci/report.html|View report>"
+        );
+        let teams = summary.payload(WebhookKind::Teams, &fired, None);
+        assert_eq!(teams["title"], "Clippy analysis: warnings increased");
+        let generic = summary.payload(WebhookKind::Generic, &fired, None);
+        assert_eq!(generic["previous_total"], 10);
+        assert_eq!(generic["triggers"][0], "warnings increased");
+    }
+}
@@ -0,0 +1,206 @@
+//! Per-category fix playbooks.
+//!
+//! A playbook is a standalone markdown document covering one category, for
+//! cleanup weeks where each engineer takes a category: a checklist of its
+//! lints, then per lint the fix template, the affected files, and the
+//! commands that apply and verify the fixes.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use crate::{
+    fixes::{examples::Applicability, registry::FixExampleRegistry},
+    types::{CategoryType, Source, Warning},
+};
+
+/// Affected files listed per lint before the rest are summarized as a count
+const MAX_FILES_PER_LINT: usize = 20;
+/// Line numbers listed per file
+const MAX_LINES_PER_FILE: usize = 10;
+
+/// The warnings of one category, by lint.
+#[derive(Debug)]
+pub struct Playbook<'a> {
+    pub category: CategoryType,
+    pub lints: BTreeMap<&'a str, Vec<&'a Warning>>,
+}
+
+impl<'a> Playbook<'a> {
+    /// One playbook per category with warnings, most important category
+    /// first.
+    pub fn from_warnings(warnings: &'a [Warning]) -> Vec<Self> {
+        let mut by_category: BTreeMap<u8, Playbook<'a>> = BTreeMap::new();
+        for warning in warnings {
+            by_category
+                .entry(warning.category.priority_level())
+                .or_insert_with(|| Playbook { category: warning.category, lints: BTreeMap::new() })
+                .lints
+                .entry(warning.lint_code.as_str())
+                .or_default()
+                .push(warning);
+        }
+        by_category.into_values().rev().collect()
+    }
+
+    /// File name component, e.g. `safety`.
+    pub fn slug(&self) -> String {
+        format!("{:?}", self.category).to_lowercase()
+    }
+
+    pub fn total(&self) -> usize {
+        self.lints.values().map(Vec::len).sum()
+    }
+
+    /// Lints by warning count, most frequent first.
+    fn lints_by_count(&self) -> Vec<(&'a str, &[&'a Warning])> {
+        let mut lints: Vec<_> = self.lints.iter().map(|(lint, warnings)| (*lint, warnings.as_slice())).collect();
+        lints.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+        lints
+    }
+}
+
+/// Writes the playbook as markdown, with fix templates from `examples`.
+pub fn write_playbook<W: Write>(
+    mut writer: W,
+    playbook: &Playbook,
+    examples: &FixExampleRegistry,
+) -> io::Result<()> {
+    let files = files_by_count(playbook.lints.values().flatten().copied());
+    writeln!(writer, "# {:?} Playbook\n", playbook.category)?;
+    writeln!(writer, "{}.\n", playbook.category.description())?;
+    writeln!(
+        writer,
+        "{} from {} in {}.\n",
+        count(playbook.total(), "warning"),
+        count(playbook.lints.len(), "lint"),
+        count(files.len(), "file")
+    )?;
+
+    writeln!(writer, "## Checklist\n")?;
+    let lints = playbook.lints_by_count();
+    for (lint, warnings) in &lints {
+        writeln!(writer, "- [ ] `{}` ({})", lint, warnings.len())?;
+    }
+    writeln!(writer)?;
+
+    for (lint, warnings) in &lints {
+        let files = files_by_count(warnings.iter().copied());
+        writeln!(writer, "## `{}`\n", lint)?;
+        writeln!(writer, "{} in {}.\n", count(warnings.len(), "warning"), count(files.len(), "file"))?;
+
+        let example = examples.get(warnings[0]);
+        if let Some(example) = &example {
+            writeln!(writer, "### Fix Template\n")?;
+            writeln!(writer, "{}\n", example.description)?;
+            writeln!(writer, "```rust\n{}\n\n// After applying fix:\n\n{}\n```\n", example.before, example.after)?;
+            if !example.explanation.is_empty() {
+                writeln!(writer, "{}\n", example.explanation)?;
+            }
+            for note in &example.additional_notes {
+                writeln!(writer, "- {}", note)?;
+            }
+            if !example.additional_notes.is_empty() {
+                writeln!(writer)?;
+            }
+        }
+
+        writeln!(writer, "### Affected Files\n")?;
+        writeln!(writer, "| File | Warnings | Lines |")?;
+        writeln!(writer, "|------|----------|-------|")?;
+        for (file, lines) in files.iter().take(MAX_FILES_PER_LINT) {
+            let mut shown: Vec<String> = lines.iter().take(MAX_LINES_PER_FILE).map(u32::to_string).collect();
+            if lines.len() > MAX_LINES_PER_FILE {
+                shown.push("…".to_string());
+            }
+            writeln!(writer, "| `{}` | {} | {} |", file, lines.len(), shown.join(", "))?;
+        }
+        if files.len() > MAX_FILES_PER_LINT {
+            writeln!(writer, "\n…and {} more files.", files.len() - MAX_FILES_PER_LINT)?;
+        }
+        writeln!(writer)?;
+
+        writeln!(writer, "### Commands\n")?;
+        let applicability = example.and_then(|example| example.applicability);
+        match fix_command(lint, warnings[0].source) {
+            Some(command) if applicability != Some(Applicability::Manual) => {
+                writeln!(writer, "Apply the automatic fixes, then review the diff:\n")?;
+                writeln!(writer, "```sh\n{}\ngit diff --stat\n```\n", command)?;
+            }
+            _ => writeln!(writer, "No automatic fix; edit the files above by hand.\n")?,
+        }
+    }
+
+    writeln!(writer, "## Verify\n")?;
+    writeln!(writer, "Re-run the analysis on each touched file:\n")?;
+    writeln!(writer, "```sh")?;
+    for (file, _) in files.iter().take(MAX_FILES_PER_LINT) {
+        writeln!(writer, "cargo analyzer file {}", file)?;
+    }
+    writeln!(writer, "```")?;
+    Ok(())
+}
+
+/// Command applying the fixes of only this lint, where the tool has one.
+fn fix_command(lint: &str, source: Source) -> Option<String> {
+    match source {
+        Source::Clippy => Some(format!("cargo clippy --fix --allow-dirty -- -A warnings -W {}", lint)),
+        Source::Rustc => Some(format!("RUSTFLAGS=\"-A warnings -W {}\" cargo fix --allow-dirty", lint)),
+        Source::Rustfmt => Some("cargo fmt".to_string()),
+        Source::External => None,
+    }
+}
+
+fn count(n: usize, noun: &str) -> String {
+    format!("{} {}{}", n, noun, if n == 1 { "" } else { "s" })
+}
+
+/// Line numbers by file, files with the most warnings first.
+fn files_by_count<'a>(warnings: impl Iterator<Item = &'a Warning>) -> Vec<(&'a str, Vec<u32>)> {
+    let mut by_file: BTreeMap<&str, Vec<u32>> = BTreeMap::new();
+    for warning in warnings {
+        by_file.entry(warning.file.as_str()).or_default().push(warning.line);
+    }
+    let mut files: Vec<_> = by_file.into_iter().collect();
+    for (_, lines) in &mut files {
+        lines.sort_unstable();
+    }
+    files.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_playbooks_by_category() {
+        let warning = |lint: &str, category, file: &str, line| Warning {
+            primary_message: "message".to_string(),
+            category,
+            ..Warning::for_test(lint, file, line)
+        };
+        let warnings = vec![
+            warning("clippy::needless_borrow", CategoryType::Style, "src/a.rs", 3),
+            warning("clippy::unwrap_used", CategoryType::Safety, "src/a.rs", 7),
+            warning("clippy::unwrap_used", CategoryType::Safety, "src/b.rs", 2),
+            warning("clippy::unwrap_used", CategoryType::Safety, "src/a.rs", 1),
+        ];
+        let playbooks = Playbook::from_warnings(&warnings);
+        assert_eq!(playbooks.len(), 2);
+        assert_eq!(playbooks[0].slug(), "safety");
+        assert_eq!(playbooks[0].total(), 3);
+
+        let mut output = Vec::new();
+        write_playbook(&mut output, &playbooks[0], &FixExampleRegistry::new()).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("# Safety Playbook\n"));
+        assert!(output.contains("- [ ] `clippy::unwrap_used` (3)"));
+        assert!(output.contains("| `src/a.rs` | 2 | 1, 7 |"));
+        assert!(output.contains("### Fix Template"));
+        assert!(output.contains("No automatic fix"));
+        assert!(output.contains("cargo analyzer file src/b.rs"));
+
+        let mut output = Vec::new();
+        write_playbook(&mut output, &playbooks[1], &FixExampleRegistry::new()).unwrap();
+        assert!(String::from_utf8(output).unwrap().contains("cargo clippy --fix --allow-dirty -- -A warnings -W clippy::needless_borrow"));
+    }
+}
@@ -0,0 +1,227 @@
+//! Pull request comment bodies.
+//!
+//! `--format pr-comment` writes a compact markdown summary for a CI step to
+//! post, e.g. with `cargo analyzer comment --pr N`: the totals, the change
+//! against the baseline branch (or the previous run), and the top new
+//! warnings grouped by file in collapsible sections. The body is cut to fit
+//! the comment size limit, GitHub's being the smallest, with a note on how
+//! many warnings were left out:
+//!
+//! ```toml
+//! [pr-comment]
+//! max-warnings = 30
+//! max-length = 60000
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use serde::Deserialize;
+use crate::{
+    analysis::statistics::{delta::CountDelta, warning::WarningStatistics},
+    types::{Completeness, Priority, Warning},
+};
+
+/// File name of the comment body in the reports directory
+pub const PR_COMMENT_FILE: &str = "clippy_pr_comment.md";
+
+/// Characters of a message shown in the warning tables
+const MAX_MESSAGE_CHARS: usize = 120;
+
+fn default_max_warnings() -> usize {
+    30
+}
+
+/// GitHub allows 65536 characters; the rest is headroom for text a CI step
+/// adds around the body.
+fn default_max_length() -> usize {
+    60_000
+}
+
+/// The `[pr-comment]` config table.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PrCommentConfig {
+    /// Warnings listed at most
+    #[serde(default = "default_max_warnings")]
+    pub max_warnings: usize,
+    /// Length of the body in bytes at most
+    #[serde(default = "default_max_length")]
+    pub max_length: usize,
+}
+
+impl Default for PrCommentConfig {
+    fn default() -> Self {
+        Self { max_warnings: default_max_warnings(), max_length: default_max_length() }
+    }
+}
+
+/// What the listed warnings are compared against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommentBase {
+    /// A baseline branch at a commit, e.g. `main` at `1a2b3c4`
+    Branch { branch: String, commit: String },
+    PreviousRun,
+    /// Nothing to compare with; the top warnings are listed
+    None,
+}
+
+/// The data of a comment body.
+#[derive(Debug, Clone)]
+pub struct PrComment<'a> {
+    pub base: CommentBase,
+    /// Total warnings against the base
+    pub total: Option<CountDelta>,
+    /// Warnings new against the base, or all warnings without one
+    pub warnings: Vec<&'a Warning>,
+    /// Base warnings gone
+    pub fixed: Option<usize>,
+}
+
+/// Writes `comment` as markdown within the limits of `config`.
+pub fn write_pr_comment<W: Write>(
+    mut writer: W,
+    comment: &PrComment,
+    stats: &WarningStatistics,
+    completeness: &Completeness,
+    config: &PrCommentConfig,
+) -> io::Result<()> {
+    let mut body = String::from("## Clippy analysis\n\n");
+    let priority = |priority| stats.by_priority.get(&priority).copied().unwrap_or(0);
+    body.push_str(&format!(
+        "**{} warnings** in {} files · {} critical · {} high\n",
+        stats.total_warnings,
+        stats.files_affected,
+        priority(Priority::Critical),
+        priority(Priority::High)
+    ));
+    let base = match &comment.base {
+        CommentBase::Branch { branch, commit } => Some(format!("`{}` (`{}`)", branch, commit)),
+        CommentBase::PreviousRun => Some("the previous run".to_string()),
+        CommentBase::None => None,
+    };
+    if let Some(base) = &base {
+        let mut line = match comment.total {
+            Some(total) => format!("\n**{:+}** vs {}", total.change(), base),
+            None => format!("\nCompared with {}", base),
+        };
+        line.push_str(&format!(": {} new", comment.warnings.len()));
+        if let Some(fixed) = comment.fixed {
+            line.push_str(&format!(", {} fixed", fixed));
+        }
+        body.push_str(&line);
+        body.push('\n');
+    }
+    if !completeness.is_complete() {
+        body.push_str(&format!("\n> ⚠️ {}\n", completeness));
+    }
+
+    let mut listed: Vec<&Warning> = comment.warnings.clone();
+    listed.sort_by(|a, b| {
+        b.priority.severity_score().cmp(&a.priority.severity_score()).then_with(|| (&a.file, a.line).cmp(&(&b.file, b.line)))
+    });
+    listed.truncate(config.max_warnings);
+    if listed.is_empty() {
+        body.push_str(if base.is_some() { "\nNo new warnings. 🎉\n" } else { "\nNo warnings. 🎉\n" });
+        return writer.write_all(body.as_bytes());
+    }
+    body.push_str(&format!("\n### {} ({})\n\n", if base.is_some() { "New warnings" } else { "Top warnings" }, comment.warnings.len()));
+
+    // Files in the order of their most severe listed warning
+    let mut by_file: BTreeMap<&str, Vec<&Warning>> = BTreeMap::new();
+    let mut order: Vec<&str> = Vec::new();
+    for warning in &listed {
+        if !by_file.contains_key(warning.file.as_str()) {
+            order.push(&warning.file);
+        }
+        by_file.entry(&warning.file).or_default().push(warning);
+    }
+
+    let mut shown = 0;
+    for file in order {
+        let warnings = &by_file[file];
+        let mut section = format!(
+            "<details><summary><code>{}</code>: {} warning{}</summary>\n\n| Line | Priority | Lint | Message |\n|---|---|---|---|\n",
+            file,
+            warnings.len(),
+            if warnings.len() == 1 { "" } else { "s" }
+        );
+        for warning in warnings {
+            section.push_str(&format!(
+                "| {} | {} | `{}` | {} |\n",
+                warning.line,
+                warning.priority,
+                warning.lint_code,
+                table_cell(&warning.primary_message)
+            ));
+        }
+        section.push_str("\n</details>\n");
+        // Room for the note on omitted warnings
+        if body.len() + section.len() + 100 > config.max_length {
+            break;
+        }
+        body.push_str(&section);
+        shown += warnings.len();
+    }
+    let omitted = comment.warnings.len() - shown;
+    if omitted > 0 {
+        body.push_str(&format!("\n…and {} more not shown; see the full report.\n", omitted));
+    }
+    writer.write_all(body.as_bytes())
+}
+
+/// `message` on one line, shortened, with table pipes escaped.
+fn table_cell(message: &str) -> String {
+    let line = message.lines().next().unwrap_or_default();
+    let mut cell: String = line.chars().take(MAX_MESSAGE_CHARS).collect();
+    if line.chars().count() > MAX_MESSAGE_CHARS {
+        cell.push('…');
+    }
+    cell.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CategoryType;
+
+    #[test]
+    fn test_pr_comment_groups_and_limits() {
+        let warning = |file: &str, line: u32, priority| Warning {
+            primary_message: "used `unwrap()` on a `Result` | value".to_string(),
+            category: CategoryType::Safety,
+            priority,
+            ..Warning::for_test("clippy::unwrap_used", file, line)
+        };
+        let warnings = [
+            warning("src/a.rs", 3, Priority::Low),
+            warning("src/b.rs", 9, Priority::Critical),
+            warning("src/a.rs", 1, Priority::High),
+        ];
+        let comment = PrComment {
+            base: CommentBase::Branch { branch: "main".to_string(), commit: "1a2b3c4".to_string() },
+            total: Some(CountDelta::new(4, 6)),
+            warnings: warnings.iter().collect(),
+            fixed: Some(1),
+        };
+        let stats = WarningStatistics { total_warnings: 6, files_affected: 2, ..WarningStatistics::default() };
+        let render = |config: &PrCommentConfig| {
+            let mut out = Vec::new();
+            write_pr_comment(&mut out, &comment, &stats, &Completeness::Complete, config).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let body = render(&PrCommentConfig::default());
+        assert!(body.contains("**+2** vs `main` (`1a2b3c4`): 3 new, 1 fixed"));
+        let b = body.find("<code>src/b.rs</code>: 1 warning<").unwrap();
+        let a = body.find("<code>src/a.rs</code>: 2 warnings<").unwrap();
+        assert!(b < a);
+        assert!(body.contains("| 1 | High | `clippy::unwrap_used` | used `unwrap()` on a `Result` \\| value |"));
+
+        let short = render(&PrCommentConfig { max_warnings: 2, ..PrCommentConfig::default() });
+        assert!(!short.contains("| 3 | Low"));
+        assert!(short.contains("…and 1 more not shown"));
+        let tiny = render(&PrCommentConfig { max_length: 300, ..PrCommentConfig::default() });
+        assert!(tiny.len() <= 300);
+        assert!(tiny.contains("…and 3 more not shown"));
+    }
+}
@@ -0,0 +1,248 @@
+//! Terminal previews of fix examples.
+//!
+//! Renders the before and after code of a fix as a line diff, either unified
+//! (`-`/`+` lines) or side by side, with Rust syntax highlighting when the
+//! terminal supports color.
+
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Style, Theme, ThemeSet},
+    parsing::SyntaxSet,
+    util::as_24_bit_terminal_escaped,
+};
+use super::diff::{DiffLayout, DiffLine, diff_lines};
+use super::snippet::Snippet;
+
+/// Theme from syntect's bundled set used for highlighting
+const THEME: &str = "base16-ocean.dark";
+/// Terminal width assumed when `COLUMNS` is not set
+const DEFAULT_WIDTH: usize = 100;
+/// Separator between the columns of a side-by-side diff
+const COLUMN_SEPARATOR: &str = " │ ";
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+pub struct FixPreview {
+    syntaxes: SyntaxSet,
+    theme: Theme,
+    layout: DiffLayout,
+    width: usize,
+    color: bool,
+}
+
+impl FixPreview {
+    pub fn new(layout: DiffLayout, color: bool) -> Self {
+        let width = std::env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.parse().ok())
+            .unwrap_or(DEFAULT_WIDTH);
+        let mut themes = ThemeSet::load_defaults();
+        Self {
+            syntaxes: SyntaxSet::load_defaults_newlines(),
+            theme: themes.themes.remove(THEME).unwrap_or_default(),
+            layout,
+            width,
+            color,
+        }
+    }
+
+    pub fn set_width(&mut self, width: usize) {
+        self.width = width;
+    }
+
+    /// Renders the change from `before` to `after`.
+    pub fn render(&self, before: &str, after: &str) -> String {
+        let before_lines: Vec<&str> = before.trim_matches('\n').lines().collect();
+        let after_lines: Vec<&str> = after.trim_matches('\n').lines().collect();
+        let diff = diff_lines(&before_lines, &after_lines);
+        let before_styled = self.highlight(&before_lines);
+        let after_styled = self.highlight(&after_lines);
+
+        match self.layout {
+            DiffLayout::Unified => self.render_unified(&diff, &before_styled, &after_styled),
+            DiffLayout::SideBySide => self.render_side_by_side(&diff, &before_styled, &after_styled),
+        }
+    }
+
+    fn render_unified(&self, diff: &[DiffLine], before: &[StyledLine], after: &[StyledLine]) -> String {
+        let mut out = String::new();
+        for line in diff {
+            let (marker, color, styled) = match *line {
+                DiffLine::Same(i, _) => (' ', "", &before[i]),
+                DiffLine::Removed(i) => ('-', RED, &before[i]),
+                DiffLine::Added(j) => ('+', GREEN, &after[j]),
+            };
+            out.push_str(&self.marker(marker, color));
+            out.push_str(&self.paint(styled, usize::MAX));
+            out.push('\n');
+        }
+        out
+    }
+
+    fn render_side_by_side(&self, diff: &[DiffLine], before: &[StyledLine], after: &[StyledLine]) -> String {
+        // Each column holds a two-character marker and the code
+        let column = self.width.saturating_sub(COLUMN_SEPARATOR.chars().count()) / 2;
+        let code_width = column.saturating_sub(2);
+        let mut out = String::new();
+        let mut pending_removed: Vec<usize> = Vec::new();
+
+        let mut rows: Vec<(Option<usize>, Option<usize>)> = Vec::new();
+        for line in diff {
+            match *line {
+                DiffLine::Removed(i) => pending_removed.push(i),
+                DiffLine::Added(j) => {
+                    // Pair additions with the removals they replace
+                    if pending_removed.is_empty() {
+                        rows.push((None, Some(j)));
+                    } else {
+                        rows.push((Some(pending_removed.remove(0)), Some(j)));
+                    }
+                }
+                DiffLine::Same(i, j) => {
+                    rows.extend(pending_removed.drain(..).map(|i| (Some(i), None)));
+                    rows.push((Some(i), Some(j)));
+                }
+            }
+        }
+        rows.extend(pending_removed.drain(..).map(|i| (Some(i), None)));
+
+        for (left, right) in rows {
+            let changed = !matches!(
+                (left, right),
+                (Some(i), Some(j)) if before[i].text() == after[j].text()
+            );
+            match left {
+                Some(i) => {
+                    let (marker, color) = if changed { ('-', RED) } else { (' ', "") };
+                    out.push_str(&self.marker(marker, color));
+                    out.push_str(&self.paint(&before[i], code_width));
+                    out.push_str(&" ".repeat(code_width.saturating_sub(before[i].width())));
+                }
+                None => out.push_str(&" ".repeat(column)),
+            }
+            out.push_str(COLUMN_SEPARATOR);
+            if let Some(j) = right {
+                let (marker, color) = if changed { ('+', GREEN) } else { (' ', "") };
+                out.push_str(&self.marker(marker, color));
+                out.push_str(&self.paint(&after[j], code_width));
+            }
+            let trimmed = out.trim_end_matches(' ').len();
+            out.truncate(trimmed);
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Renders a snippet with a line number gutter, highlighting the code
+    /// and marking the flagged lines.
+    pub fn render_snippet(&self, snippet: &Snippet) -> String {
+        let lines: Vec<&str> = snippet.lines.iter().map(String::as_str).collect();
+        let styled = self.highlight(&lines);
+        let width = (snippet.first_line as usize + lines.len()).to_string().len();
+        let mut out = String::new();
+        for ((number, _), styled) in snippet.numbered().zip(&styled) {
+            let (marker, color) = if snippet.is_flagged(number) { ('>', RED) } else { (' ', "") };
+            out.push_str(&self.marker(marker, color));
+            out.push_str(&format!("{:>width$} | ", number, width = width));
+            out.push_str(&self.paint(styled, usize::MAX));
+            out.push('\n');
+        }
+        out
+    }
+
+    fn marker(&self, marker: char, color: &str) -> String {
+        if self.color && !color.is_empty() {
+            format!("{}{}{} ", color, marker, RESET)
+        } else {
+            format!("{} ", marker)
+        }
+    }
+
+    /// The line's code, cut to `width` characters.
+    fn paint(&self, line: &StyledLine, width: usize) -> String {
+        let mut remaining = width;
+        let mut ranges: Vec<(Style, &str)> = Vec::new();
+        for (style, text) in &line.0 {
+            if remaining == 0 {
+                break;
+            }
+            let end = text.char_indices().nth(remaining).map_or(text.len(), |(end, _)| end);
+            remaining -= text[..end].chars().count();
+            ranges.push((*style, &text[..end]));
+        }
+        if self.color {
+            format!("{}{}", as_24_bit_terminal_escaped(&ranges, false), RESET)
+        } else {
+            ranges.iter().map(|(_, text)| *text).collect()
+        }
+    }
+
+    fn highlight(&self, lines: &[&str]) -> Vec<StyledLine> {
+        let syntax = self.syntaxes
+            .find_syntax_by_extension("rs")
+            .unwrap_or_else(|| self.syntaxes.find_syntax_plain_text());
+        let mut highlighter = HighlightLines::new(syntax, &self.theme);
+        lines
+            .iter()
+            .map(|line| {
+                let ranges = if self.color {
+                    highlighter
+                        .highlight_line(&format!("{}\n", line), &self.syntaxes)
+                        .map(|ranges| {
+                            ranges
+                                .into_iter()
+                                .map(|(style, text)| (style, text.trim_end_matches('\n').to_string()))
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+                if ranges.is_empty() {
+                    StyledLine(vec![(Style::default(), line.to_string())])
+                } else {
+                    StyledLine(ranges)
+                }
+            })
+            .collect()
+    }
+}
+
+/// A highlighted line as styled runs of text.
+struct StyledLine(Vec<(Style, String)>);
+
+impl StyledLine {
+    fn text(&self) -> String {
+        self.0.iter().map(|(_, text)| text.as_str()).collect()
+    }
+
+    fn width(&self) -> usize {
+        self.0.iter().map(|(_, text)| text.chars().count()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_without_color() {
+        let before = "\nfn main() {\n    let x = v.len() == 0;\n}";
+        let after = "fn main() {\n    let x = v.is_empty();\n}\n";
+
+        let unified = FixPreview::new(DiffLayout::Unified, false).render(before, after);
+        assert_eq!(
+            unified,
+            "  fn main() {\n-     let x = v.len() == 0;\n+     let x = v.is_empty();\n  }\n"
+        );
+
+        let mut preview = FixPreview::new(DiffLayout::SideBySide, false);
+        preview.set_width(63);
+        let side_by_side = preview.render(before, after);
+        let lines: Vec<&str> = side_by_side.lines().collect();
+        assert_eq!(lines[0], format!("  {:<28} │   fn main() {{", "fn main() {"));
+        assert_eq!(lines[1], format!("-     {:<24} │ +     let x = v.is_empty();", "let x = v.len() == 0;"));
+    }
+}
@@ -0,0 +1,299 @@
+//! Progress reporting for long-running analyses.
+//!
+//! Progress goes to stderr so it never mixes with report output. On a terminal
+//! a single spinner line is redrawn in place; otherwise each stage is logged as
+//! plain timestamped lines, with in-stage updates rate limited.
+
+use std::fmt;
+use std::io::{self, Write};
+#[cfg(feature = "terminal")]
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+#[cfg(feature = "terminal")]
+use std::{
+    sync::{Arc, Mutex, mpsc::{self, RecvTimeoutError, Sender}},
+    thread::{self, JoinHandle},
+};
+
+/// Major phases of an analysis run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Compiling,
+    Parsing,
+    Analyzing,
+    GeneratingReports,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Compiling => write!(f, "Running clippy"),
+            Stage::Parsing => write!(f, "Parsing output"),
+            Stage::Analyzing => write!(f, "Analyzing warnings"),
+            Stage::GeneratingReports => write!(f, "Generating reports"),
+        }
+    }
+}
+
+/// Receives progress notifications from the workflow and runner.
+///
+/// Reporting is best effort: implementations swallow their own I/O errors so
+/// a broken progress display never aborts an analysis.
+pub trait ProgressReporter {
+    fn stage_started(&mut self, stage: Stage);
+    fn stage_progress(&mut self, stage: Stage, message: &str);
+    fn stage_finished(&mut self, stage: Stage, summary: &str);
+}
+
+/// Returns a spinner reporter when stderr is a terminal, plain log lines otherwise.
+#[cfg(feature = "terminal")]
+pub fn default_progress_reporter() -> Box<dyn ProgressReporter> {
+    if io::stderr().is_terminal() {
+        Box::new(SpinnerProgress::new())
+    } else {
+        Box::new(LogProgress::new())
+    }
+}
+
+/// Returns a plain log line reporter; the spinner needs the `terminal`
+/// feature.
+#[cfg(not(feature = "terminal"))]
+pub fn default_progress_reporter() -> Box<dyn ProgressReporter> {
+    Box::new(LogProgress::new())
+}
+
+/// Discards all progress notifications.
+#[derive(Debug, Default)]
+pub struct NoProgress;
+
+impl ProgressReporter for NoProgress {
+    fn stage_started(&mut self, _stage: Stage) {}
+    fn stage_progress(&mut self, _stage: Stage, _message: &str) {}
+    fn stage_finished(&mut self, _stage: Stage, _summary: &str) {}
+}
+
+#[cfg(feature = "terminal")]
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+#[cfg(feature = "terminal")]
+const SPINNER_INTERVAL: Duration = Duration::from_millis(80);
+const LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The status line drawn by [`SpinnerProgress`] while a stage runs.
+#[cfg(feature = "terminal")]
+struct SpinnerLine {
+    stage: Stage,
+    message: String,
+    stage_start: Instant,
+    last_render: Instant,
+    frame: usize,
+}
+
+#[cfg(feature = "terminal")]
+impl SpinnerLine {
+    fn render(&mut self) {
+        self.last_render = Instant::now();
+        self.frame = (self.frame + 1) % SPINNER_FRAMES.len();
+
+        let separator = if self.message.is_empty() { "" } else { ": " };
+        let mut stderr = io::stderr().lock();
+        let _ = write!(
+            stderr,
+            "\r\x1B[2K{} {}{}{} ({:.1}s)",
+            SPINNER_FRAMES[self.frame],
+            self.stage,
+            separator,
+            self.message,
+            self.stage_start.elapsed().as_secs_f64()
+        );
+        let _ = stderr.flush();
+    }
+}
+
+/// Redraws a single status line with a spinner and elapsed time.
+///
+/// While a stage runs, a background thread redraws the line every
+/// [`SPINNER_INTERVAL`], so the spinner keeps turning through long steps
+/// that report no progress, such as compiling.
+#[cfg(feature = "terminal")]
+pub struct SpinnerProgress {
+    line: Arc<Mutex<Option<SpinnerLine>>>,
+    ticker: Option<(Sender<()>, JoinHandle<()>)>,
+}
+
+#[cfg(feature = "terminal")]
+impl SpinnerProgress {
+    pub fn new() -> Self {
+        Self {
+            line: Arc::new(Mutex::new(None)),
+            ticker: None,
+        }
+    }
+
+    fn start_ticker(&mut self) {
+        if self.ticker.is_some() {
+            return;
+        }
+        let (stop, stopped) = mpsc::channel();
+        let line = Arc::clone(&self.line);
+        let handle = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(SPINNER_INTERVAL) {
+                if let Ok(mut line) = line.lock() {
+                    if let Some(line) = line.as_mut().filter(|line| line.last_render.elapsed() >= SPINNER_INTERVAL) {
+                        line.render();
+                    }
+                }
+            }
+        });
+        self.ticker = Some((stop, handle));
+    }
+
+    fn stop_ticker(&mut self) {
+        if let Some((stop, handle)) = self.ticker.take() {
+            let _ = stop.send(());
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(feature = "terminal")]
+impl Default for SpinnerProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "terminal")]
+impl Drop for SpinnerProgress {
+    fn drop(&mut self) {
+        self.stop_ticker();
+    }
+}
+
+#[cfg(feature = "terminal")]
+impl ProgressReporter for SpinnerProgress {
+    fn stage_started(&mut self, stage: Stage) {
+        if let Ok(mut line) = self.line.lock() {
+            let now = Instant::now();
+            line.insert(SpinnerLine {
+                stage,
+                message: String::new(),
+                stage_start: now,
+                last_render: now,
+                frame: 0,
+            })
+            .render();
+        }
+        self.start_ticker();
+    }
+
+    fn stage_progress(&mut self, stage: Stage, message: &str) {
+        let Ok(mut line) = self.line.lock() else {
+            return;
+        };
+        let Some(line) = line.as_mut() else {
+            drop(line);
+            return self.stage_started(stage);
+        };
+        line.stage = stage;
+        line.message.clear();
+        line.message.push_str(message);
+        if line.last_render.elapsed() >= SPINNER_INTERVAL {
+            line.render();
+        }
+    }
+
+    fn stage_finished(&mut self, stage: Stage, summary: &str) {
+        self.stop_ticker();
+        let elapsed = self.line
+            .lock()
+            .ok()
+            .and_then(|mut line| line.take())
+            .map_or(0.0, |line| line.stage_start.elapsed().as_secs_f64());
+        let separator = if summary.is_empty() { "" } else { ": " };
+        let _ = writeln!(
+            io::stderr(),
+            "\r\x1B[2K✔ {}{}{} ({:.1}s)",
+            stage,
+            separator,
+            summary,
+            elapsed
+        );
+    }
+}
+
+/// Plain line-oriented progress for CI logs and other non-terminal output.
+pub struct LogProgress {
+    run_start: Instant,
+    stage_start: Instant,
+    last_log: Instant,
+}
+
+impl LogProgress {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            run_start: now,
+            stage_start: now,
+            last_log: now,
+        }
+    }
+
+    fn log(&self, line: fmt::Arguments<'_>) {
+        let _ = writeln!(
+            io::stderr(),
+            "[{:>7.1}s] {}",
+            self.run_start.elapsed().as_secs_f64(),
+            line
+        );
+    }
+}
+
+impl Default for LogProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressReporter for LogProgress {
+    fn stage_started(&mut self, stage: Stage) {
+        self.stage_start = Instant::now();
+        self.last_log = self.stage_start;
+        self.log(format_args!("{}...", stage));
+    }
+
+    fn stage_progress(&mut self, stage: Stage, message: &str) {
+        if message.is_empty() || self.last_log.elapsed() < LOG_INTERVAL {
+            return;
+        }
+        self.last_log = Instant::now();
+        self.log(format_args!("{}: {}", stage, message));
+    }
+
+    fn stage_finished(&mut self, stage: Stage, summary: &str) {
+        let elapsed = self.stage_start.elapsed().as_secs_f64();
+        if summary.is_empty() {
+            self.log(format_args!("{} done in {:.1}s", stage, elapsed));
+        } else {
+            self.log(format_args!("{} done in {:.1}s: {}", stage, elapsed, summary));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "terminal"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spinner_ticks_without_progress() {
+        let mut spinner = SpinnerProgress::new();
+        spinner.stage_started(Stage::Compiling);
+        let frame = |spinner: &SpinnerProgress| spinner.line.lock().unwrap().as_ref().unwrap().frame;
+        let first = frame(&spinner);
+        thread::sleep(SPINNER_INTERVAL * 4);
+        assert_ne!(frame(&spinner), first);
+
+        spinner.stage_finished(Stage::Compiling, "");
+        assert!(spinner.ticker.is_none());
+        assert!(spinner.line.lock().unwrap().is_none());
+    }
+}
@@ -0,0 +1,148 @@
+//! Prometheus metrics.
+//!
+//! `--format prometheus` writes the run's warning counts as gauges in the
+//! Prometheus text exposition format, to a fixed file name in the reports
+//! directory. Pointing the node_exporter textfile collector at that
+//! directory (`--collector.textfile.directory`) makes them available to
+//! Prometheus and Grafana:
+//!
+//! | Metric | Labels |
+//! |--------|--------|
+//! | `clippy_warnings_total` | `category`, `priority` |
+//! | `clippy_warnings_by_crate` | `crate` |
+//! | `clippy_warnings_by_lint` | `lint` |
+//! | `clippy_files_affected` | |
+//! | `clippy_analysis_complete` | |
+//! | `clippy_last_run_timestamp_seconds` | |
+//!
+//! The file is replaced atomically, so the collector never reads a
+//! partial file.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use crate::{
+    analysis::statistics::warning::WarningStatistics,
+    types::{Completeness, Warning},
+};
+
+/// File name of the metrics, with the `.prom` extension the textfile
+/// collector requires
+pub const PROMETHEUS_FILE: &str = "clippy_metrics.prom";
+
+/// Escapes a label value: backslash, double quote, and newline.
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Writes one gauge with a sample per label set, in label order.
+fn write_gauge<W: Write>(
+    writer: &mut W,
+    name: &str,
+    help: &str,
+    samples: &BTreeMap<Vec<(&str, String)>, usize>,
+) -> io::Result<()> {
+    writeln!(writer, "# HELP {} {}", name, help)?;
+    writeln!(writer, "# TYPE {} gauge", name)?;
+    for (labels, value) in samples {
+        if labels.is_empty() {
+            writeln!(writer, "{} {}", name, value)?;
+            continue;
+        }
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+            .collect();
+        writeln!(writer, "{}{{{}}} {}", name, labels.join(","), value)?;
+    }
+    Ok(())
+}
+
+/// Writes the metrics of a run finished at `timestamp` (Unix seconds).
+pub fn write_prometheus<W: Write>(
+    mut writer: W,
+    warnings: &[Warning],
+    stats: &WarningStatistics,
+    completeness: &Completeness,
+    timestamp: i64,
+) -> io::Result<()> {
+    let mut by_severity = BTreeMap::new();
+    let mut by_crate = BTreeMap::new();
+    for warning in warnings {
+        let key = vec![
+            ("category", warning.category.to_string().to_lowercase()),
+            ("priority", warning.priority.to_string().to_lowercase()),
+        ];
+        *by_severity.entry(key).or_insert(0) += 1;
+        if let Some(name) = &warning.crate_name {
+            *by_crate.entry(vec![("crate", name.clone())]).or_insert(0) += 1;
+        }
+    }
+    let by_lint = stats.by_lint.iter().map(|(lint, count)| (vec![("lint", lint.clone())], *count)).collect();
+    let single = |value: usize| BTreeMap::from([(Vec::new(), value)]);
+
+    write_gauge(&mut writer, "clippy_warnings_total", "Warnings by category and priority.", &by_severity)?;
+    write_gauge(&mut writer, "clippy_warnings_by_crate", "Warnings by workspace crate.", &by_crate)?;
+    write_gauge(&mut writer, "clippy_warnings_by_lint", "Warnings by lint.", &by_lint)?;
+    write_gauge(&mut writer, "clippy_files_affected", "Files with at least one warning.", &single(stats.files_affected))?;
+    write_gauge(
+        &mut writer,
+        "clippy_analysis_complete",
+        "1 if clippy finished on every target, 0 if the counts may be too low.",
+        &single(usize::from(completeness.is_complete())),
+    )?;
+    writeln!(writer, "# HELP clippy_last_run_timestamp_seconds Time the analysis finished.")?;
+    writeln!(writer, "# TYPE clippy_last_run_timestamp_seconds gauge")?;
+    writeln!(writer, "clippy_last_run_timestamp_seconds {}", timestamp)
+}
+
+/// Writes the metrics to `path` through a temporary file renamed into
+/// place.
+pub fn write_prometheus_file(
+    path: &Path,
+    warnings: &[Warning],
+    stats: &WarningStatistics,
+    completeness: &Completeness,
+) -> io::Result<()> {
+    let temp = path.with_extension("prom.tmp");
+    let mut file = io::BufWriter::new(File::create(&temp)?);
+    write_prometheus(&mut file, warnings, stats, completeness, chrono::Utc::now().timestamp())?;
+    file.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    fs::rename(&temp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CategoryType, Priority};
+
+    #[test]
+    fn test_prometheus_exposition() {
+        let warning = |category, priority, lint: &str| Warning {
+            category,
+            priority,
+            crate_name: Some("my \"crate\"".to_string()),
+            ..Warning::for_test(lint, "src/lib.rs", 1)
+        };
+        let warnings = [
+            warning(CategoryType::Safety, Priority::Critical, "clippy::unwrap_used"),
+            warning(CategoryType::Safety, Priority::Critical, "clippy::unwrap_used"),
+            warning(CategoryType::Style, Priority::Low, "clippy::len_zero"),
+        ];
+        let stats = WarningStatistics {
+            files_affected: 1,
+            by_lint: [("clippy::unwrap_used".to_string(), 2), ("clippy::len_zero".to_string(), 1)].into(),
+            ..WarningStatistics::default()
+        };
+        let mut out = Vec::new();
+        write_prometheus(&mut out, &warnings, &stats, &Completeness::Complete, 1_700_000_000).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("# TYPE clippy_warnings_total gauge\n"));
+        assert!(text.contains("clippy_warnings_total{category=\"safety\",priority=\"critical\"} 2\n"));
+        assert!(text.contains("clippy_warnings_by_crate{crate=\"my \\\"crate\\\"\"} 3\n"));
+        assert!(text.contains("clippy_warnings_by_lint{lint=\"clippy::len_zero\"} 1\n"));
+        assert!(text.contains("clippy_analysis_complete 1\n"));
+        assert!(text.ends_with("clippy_last_run_timestamp_seconds 1700000000\n"));
+    }
+}
@@ -0,0 +1,78 @@
+//! Appendix of the diagnostics as the compiler rendered them.
+//!
+//! The reports restructure each diagnostic into lint, message, location, and
+//! suggestion. For readers who prefer clippy's own layout, this appendix
+//! holds every diagnostic's `rendered` text verbatim, grouped by file in line
+//! order, in one markdown file.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use crate::types::Warning;
+
+/// Writes the appendix, returning how many diagnostics had rendered text.
+pub fn write_rendered_appendix<W: Write>(mut writer: W, warnings: &[Warning]) -> io::Result<usize> {
+    let mut by_file: BTreeMap<&str, Vec<(&Warning, &str)>> = BTreeMap::new();
+    for warning in warnings {
+        if let Some(rendered) = warning.rendered.as_deref() {
+            by_file.entry(warning.file.as_str()).or_default().push((warning, rendered));
+        }
+    }
+    let rendered: usize = by_file.values().map(Vec::len).sum();
+
+    writeln!(writer, "# Rendered Diagnostics\n")?;
+    writeln!(
+        writer,
+        "{} diagnostics in {} files, as clippy and rustc rendered them.",
+        rendered,
+        by_file.len()
+    )?;
+    if rendered < warnings.len() {
+        writeln!(writer, "{} diagnostics had no rendered text and are left out.", warnings.len() - rendered)?;
+    }
+    writeln!(writer)?;
+
+    for (file, diagnostics) in &mut by_file {
+        diagnostics.sort_by_key(|(warning, _)| warning.line);
+        writeln!(writer, "## `{}`\n", file)?;
+        for (_, text) in diagnostics.iter() {
+            let fence = fence_for(text);
+            writeln!(writer, "{}text\n{}\n{}\n", fence, text.trim_end(), fence)?;
+        }
+    }
+    writer.flush()?;
+    Ok(rendered)
+}
+
+/// A code fence longer than any run of backticks in `text`.
+fn fence_for(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    #[test]
+    fn test_rendered_appendix() {
+        let warning = |file: &str, line, rendered: Option<&str>| Warning {
+            primary_message: "length comparison to zero".to_string(),
+            rendered: rendered.map(str::to_string),
+            priority: Priority::Low,
+            ..Warning::for_test("clippy::len_zero", file, line)
+        };
+        let warnings = [
+            warning("src/lib.rs", 9, Some("warning: second\n")),
+            warning("src/lib.rs", 3, Some("warning: first\n  = help: use ```is_empty```\n")),
+            warning("src/main.rs", 1, None),
+        ];
+        let mut out = Vec::new();
+        assert_eq!(write_rendered_appendix(&mut out, &warnings).unwrap(), 2);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("2 diagnostics in 1 files"));
+        assert!(out.contains("1 diagnostics had no rendered text"));
+        assert!(out.find("warning: first").unwrap() < out.find("warning: second").unwrap());
+        assert!(out.contains("````text\nwarning: first"));
+    }
+}
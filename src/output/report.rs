@@ -1,9 +1,10 @@
-use std::fs::File;
 use std::io::Write;
+#[cfg(feature = "terminal")]
 use termcolor::Color;
-use crate::analysis::TrendAnalysis;
+use crate::analysis::{GeneratedCodeFilter, TrendAnalysis};
+use super::snippet::SnippetReader;
 
-pub fn write_trend_analysis(file: &mut File, trends: &TrendAnalysis) -> std::io::Result<()> {
+pub fn write_trend_analysis(file: &mut impl Write, trends: &TrendAnalysis) -> std::io::Result<()> {
     writeln!(file, "\n=== Trend Analysis ===")?;
     
     // Write total warnings
@@ -29,20 +30,33 @@ pub fn write_trend_analysis(file: &mut File, trends: &TrendAnalysis) -> std::io:
 
     // Write improvement rate
     writeln!(file, "\nImprovement Rate: {:.1}%", trends.improvement_rate * 100.0)?;
+    writeln!(file, "Severity-Weighted Improvement Rate: {:.1}%", trends.weighted_improvement_rate * 100.0)?;
+    let mut by_category: Vec<_> = trends.category_improvement.iter().collect();
+    by_category.sort_by_key(|(category, _)| category.to_string());
+    for (category, rate) in by_category {
+        writeln!(file, "  {} - {:.1}%", category, rate * 100.0)?;
+    }
+    if let (Some(new), Some(fixed)) = (trends.new_warnings, trends.fixed_warnings) {
+        writeln!(file, "\nSince the previous run: {} new, {} fixed", new, fixed)?;
+    }
 
     Ok(())
 }
 
-pub fn write_colored_section(file: &mut File, title: &str, content: &str, _color: Color) -> std::io::Result<()> {
+#[cfg(feature = "terminal")]
+pub fn write_colored_section(file: &mut impl Write, title: &str, content: &str, _color: Color) -> std::io::Result<()> {
     writeln!(file, "=== {} ===\n", title)?;
     writeln!(file, "{}", content)?;
     Ok(())
 }
 
+/// Lists every warning; with a snippet reader, each is followed by the code
+/// it points at.
 pub fn write_warning_report(
-    file: &mut File,
+    file: &mut impl Write,
     warnings: &[crate::types::Warning],
-    show_snippets: bool
+    show_snippets: bool,
+    mut code: Option<&mut SnippetReader>,
 ) -> std::io::Result<()> {
     writeln!(file, "Warning Report\n")?;
 
@@ -53,12 +67,71 @@ pub fn write_warning_report(
             writeln!(file, "\nFile: {}", current_file)?;
         }
 
-        let (formatted, _) = super::formatter::format_warning(warning);
-        writeln!(file, "{}", formatted)?;
+        writeln!(file, "{}", super::formatter::WarningFormatter::new(warning).text())?;
+        writeln!(file, "Fingerprint: {}", warning.fingerprint())?;
+        if let Some(snippet) = code.as_mut().and_then(|reader| reader.read(warning)) {
+            writeln!(file, "{}", snippet.to_markdown())?;
+        }
+
+        if let Some(fix) = warning.suggested_fix.as_ref().filter(|_| show_snippets) {
+            writeln!(file, "Suggested fix:\n{}\n", fix)?;
+        }
+
+        if let Some(raw) = &warning.raw {
+            writeln!(file, "Raw diagnostic:\n```json\n{}\n```\n", raw)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists hard compile errors, which must be fixed before the lint counts
+/// are complete.
+pub fn write_error_section(
+    file: &mut impl Write,
+    warnings: &[crate::types::Warning],
+) -> std::io::Result<()> {
+    let errors: Vec<_> = warnings.iter().filter(|w| w.is_error).collect();
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(file, "Compile Errors\n")?;
+    writeln!(file, "The build failed with {} errors; lints in code that did not compile are missing.\n", errors.len())?;
+    for error in errors {
+        writeln!(file, "- [{}] {}:{}: {}", error.lint_code, error.file, error.line, error.primary_message)?;
+    }
+    writeln!(file)
+}
+
+pub fn write_suppressed_appendix(
+    file: &mut impl Write,
+    suppressed: &[crate::types::Warning],
+) -> std::io::Result<()> {
+    writeln!(file, "\nAppendix: Suppressed Warnings\n")?;
+    writeln!(file, "{} warnings were excluded by ignore rules.\n", suppressed.len())?;
+
+    for warning in suppressed {
+        writeln!(file, "- {}:{} [{}] {}", warning.file, warning.line, warning.lint_code, warning.primary_message)?;
+    }
+
+    Ok(())
+}
+
+pub fn write_generated_appendix(
+    file: &mut impl Write,
+    generated: &[crate::types::Warning],
+    filter: &GeneratedCodeFilter,
+) -> std::io::Result<()> {
+    writeln!(file, "\nAppendix: Generated and Vendored Code\n")?;
+    writeln!(file, "{} warnings in generated or vendored files were excluded from the analysis.\n", generated.len())?;
 
-        if show_snippets && warning.suggested_fix.is_some() {
-            writeln!(file, "Suggested fix:\n{}\n", warning.suggested_fix.as_ref().unwrap())?;
+    for (reason, files) in filter.breakdown(generated) {
+        writeln!(file, "{} ({} warnings):", reason, files.values().sum::<usize>())?;
+        for (path, count) in files {
+            writeln!(file, "- {}: {} warnings", path, count)?;
         }
+        writeln!(file)?;
     }
 
     Ok(())
@@ -0,0 +1,181 @@
+//! Run manifest: the index of one run's output in the reports directory.
+//!
+//! Report files carry the run's timestamp in their names, so scripts would
+//! otherwise have to glob and sort to find the latest summary. After every
+//! run the analyzer replaces `manifest.json` in the reports directory with
+//! the files that run wrote and what produced them:
+//!
+//! ```json
+//! {
+//!   "format": "cargo-analyzer-run",
+//!   "version": 1,
+//!   "generator": "cargo-analyzer 0.1.0",
+//!   "timestamp": "20240101_120000",
+//!   "generated_at": "2024-01-01T11:00:00Z",
+//!   "git_commit": "4f1c2e0...",
+//!   "rustc_version": "rustc 1.82.0 (f6e511eec 2024-10-15)",
+//!   "args": ["analyzer", "--jsonl"],
+//!   "complete": true,
+//!   "total_warnings": 42,
+//!   "artifacts": [
+//!     {"type": "summary", "format": "html", "path": "clippy_summary_20240101_120000.html",
+//!      "bytes": 48213, "sha256": "9f2c..."}
+//!   ]
+//! }
+//! ```
+//!
+//! Artifact paths are relative to the reports directory when the file is in
+//! it. As with the analysis bundle, fields are only ever added within a
+//! `version`.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use crate::types::Completeness;
+
+/// Name of the manifest in the reports directory
+pub const RUN_MANIFEST_FILE: &str = "manifest.json";
+
+/// Value of `format` in the manifest
+pub const RUN_MANIFEST_FORMAT: &str = "cargo-analyzer-run";
+
+/// Version of the manifest layout
+pub const RUN_MANIFEST_VERSION: u32 = 1;
+
+/// One file written by the run.
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestArtifact {
+    /// Report name, e.g. `summary` or `warnings_json`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// File extension, e.g. `html`
+    pub format: String,
+    pub path: String,
+    pub bytes: u64,
+    /// Hex SHA-256 of the contents
+    pub sha256: String,
+}
+
+/// What produced the run's output.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RunMetadata {
+    pub git_commit: Option<String>,
+    pub git_branch: Option<String>,
+    /// `rustc -V`
+    pub rustc_version: Option<String>,
+    /// `cargo -V`
+    pub cargo_version: Option<String>,
+    /// `cargo clippy -V`
+    pub clippy_version: Option<String>,
+    /// Command line arguments of the analyzer, without the program
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RunManifest {
+    pub format: &'static str,
+    pub version: u32,
+    /// `cargo-analyzer <version>`
+    pub generator: String,
+    /// Timestamp in the report file names
+    pub timestamp: String,
+    /// RFC 3339 time the manifest was written
+    pub generated_at: String,
+    #[serde(flatten)]
+    pub run: RunMetadata,
+    /// Whether clippy finished on every target
+    pub complete: bool,
+    /// Why the analysis is incomplete, absent when it is complete
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incomplete_reason: Option<String>,
+    pub total_warnings: usize,
+    pub artifacts: Vec<ManifestArtifact>,
+}
+
+impl RunManifest {
+    pub fn new(timestamp: &str, run: RunMetadata, completeness: &Completeness, total_warnings: usize) -> Self {
+        Self {
+            format: RUN_MANIFEST_FORMAT,
+            version: RUN_MANIFEST_VERSION,
+            generator: format!("cargo-analyzer {}", env!("CARGO_PKG_VERSION")),
+            timestamp: timestamp.to_string(),
+            generated_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            run,
+            complete: completeness.is_complete(),
+            incomplete_reason: (!completeness.is_complete()).then(|| completeness.to_string()),
+            total_warnings,
+            artifacts: Vec::new(),
+        }
+    }
+
+    /// Adds the file at `path`, written as report `kind`, reading it back
+    /// for its size and checksum.
+    pub fn add_artifact(&mut self, kind: &str, path: &Path, reports_dir: &Path) -> io::Result<()> {
+        let contents = std::fs::read(path)?;
+        self.add_contents(kind, path, &contents, reports_dir);
+        Ok(())
+    }
+
+    /// Adds report `kind` with `contents`, which belongs at `path`.
+    pub fn add_contents(&mut self, kind: &str, path: &Path, contents: &[u8], reports_dir: &Path) {
+        let relative = path.strip_prefix(reports_dir).unwrap_or(path);
+        self.artifacts.push(ManifestArtifact {
+            kind: kind.to_string(),
+            format: path.extension().map(|ext| ext.to_string_lossy().into_owned()).unwrap_or_default(),
+            path: relative.to_string_lossy().replace('\\', "/"),
+            bytes: contents.len() as u64,
+            sha256: format!("{:x}", Sha256::digest(contents)),
+        });
+    }
+}
+
+/// Writes `manifest` as JSON to `writer`.
+pub fn write_manifest<W: Write>(mut writer: W, manifest: &RunManifest) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut writer, manifest)?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
+
+/// Writes `manifest` into `reports_dir`, replacing the previous run's, and
+/// returns its path.
+pub fn write_run_manifest(reports_dir: &Path, manifest: &RunManifest) -> io::Result<PathBuf> {
+    std::fs::create_dir_all(reports_dir)?;
+    let path = reports_dir.join(RUN_MANIFEST_FILE);
+    // Renamed into place, so readers never see half a manifest
+    let partial = reports_dir.join(format!("{}.tmp", RUN_MANIFEST_FILE));
+    write_manifest(BufWriter::new(File::create(&partial)?), manifest)?;
+    std::fs::rename(&partial, &path)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_run_manifest() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let summary = dir.join("clippy_summary_20240101_120000.html");
+        std::fs::write(&summary, "abc").unwrap();
+
+        let run = RunMetadata { git_commit: Some("4f1c2e0".to_string()), args: vec!["--jsonl".to_string()], ..RunMetadata::default() };
+        let mut manifest = RunManifest::new("20240101_120000", run, &Completeness::Complete, 3);
+        manifest.add_artifact("summary", &summary, dir).unwrap();
+        let path = write_run_manifest(dir, &manifest).unwrap();
+
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["format"], RUN_MANIFEST_FORMAT);
+        assert_eq!(written["git_commit"], "4f1c2e0");
+        assert_eq!(written["args"][0], "--jsonl");
+        assert!(written.get("incomplete_reason").is_none());
+        let artifact = &written["artifacts"][0];
+        assert_eq!(artifact["type"], "summary");
+        assert_eq!(artifact["format"], "html");
+        assert_eq!(artifact["path"], "clippy_summary_20240101_120000.html");
+        assert_eq!(artifact["bytes"], 3);
+        assert_eq!(artifact["sha256"], "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+}
@@ -0,0 +1,332 @@
+//! SARIF export of warnings.
+//!
+//! `--format sarif` writes a SARIF 2.1.0 log, the format code scanning
+//! services such as GitHub ingest. Each warning becomes a result with its
+//! full span as the region, including byte offsets when rustc reported
+//! them, and its fingerprint as a partial fingerprint so results are
+//! tracked across runs. With `--embed-raw`, clippy's suggested replacements
+//! become SARIF fixes.
+//!
+//! Levels follow the analyzer's priorities: errors and Critical warnings
+//! are `error`, High and Medium ones `warning`, and the rest `note`.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use serde::Serialize;
+use crate::{
+    fixes::apply::SuggestedEdit,
+    types::{Priority, Span, Warning},
+};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Base of relative result paths, resolved by the consumer to the checkout
+const SOURCE_ROOT: &str = "%SRCROOT%";
+
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    pub tool: Tool,
+    /// rustc counts columns in characters
+    pub column_kind: &'static str,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Tool {
+    pub driver: Driver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Driver {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help_uri: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: &'static str,
+    pub message: Message,
+    pub locations: Vec<Location>,
+    pub partial_fingerprints: BTreeMap<&'static str, String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<Fix>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Message {
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub physical_location: PhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+    pub region: Region,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactLocation {
+    pub uri: String,
+    pub uri_base_id: &'static str,
+}
+
+/// A span of a file. Columns are 1-based with an exclusive end, as rustc
+/// reports them; 0 (unknown) columns are left out.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub byte_offset: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub byte_length: Option<usize>,
+}
+
+impl Region {
+    fn from_span(span: &Span) -> Self {
+        let column = |column: u32| (column > 0).then_some(column);
+        let bytes = span.byte_range();
+        Self {
+            start_line: Some(span.line_start),
+            start_column: column(span.column_start),
+            end_line: Some(span.line_end.max(span.line_start)),
+            end_column: column(span.column_end),
+            byte_offset: bytes.as_ref().map(|range| range.start),
+            byte_length: bytes.map(|range| range.len()),
+        }
+    }
+
+    /// A region known only by its bytes, as fixes address them.
+    fn bytes(span: &Span) -> Option<Self> {
+        let range = span.byte_range()?;
+        Some(Self { byte_offset: Some(range.start), byte_length: Some(range.len()), ..Self::default() })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fix {
+    pub description: Message,
+    pub artifact_changes: Vec<ArtifactChange>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactChange {
+    pub artifact_location: ArtifactLocation,
+    pub replacements: Vec<SarifReplacement>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifReplacement {
+    pub deleted_region: Region,
+    pub inserted_content: Message,
+}
+
+impl SarifLog {
+    pub fn from_warnings(warnings: &[Warning]) -> Self {
+        let mut rules: Vec<Rule> = Vec::new();
+        let mut rule_indices: BTreeMap<&str, usize> = BTreeMap::new();
+        let results = warnings
+            .iter()
+            .map(|warning| {
+                let rule_index = *rule_indices.entry(&warning.lint_code).or_insert_with(|| {
+                    rules.push(Rule { id: warning.lint_code.clone(), help_uri: help_uri(&warning.lint_code) });
+                    rules.len() - 1
+                });
+                let span = warning.span.unwrap_or(Span::at(warning.line, 0));
+                SarifResult {
+                    rule_id: warning.lint_code.clone(),
+                    rule_index,
+                    level: level(warning),
+                    message: Message { text: warning.primary_message.clone() },
+                    locations: vec![Location {
+                        physical_location: PhysicalLocation {
+                            artifact_location: artifact_location(&warning.file),
+                            region: Region::from_span(&span),
+                        },
+                    }],
+                    partial_fingerprints: BTreeMap::from([("cargoAnalyzer/v1", warning.fingerprint())]),
+                    fixes: SuggestedEdit::from_warning(warning).and_then(fix).into_iter().collect(),
+                }
+            })
+            .collect();
+
+        Self {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![SarifRun {
+                tool: Tool { driver: Driver { name: "cargo-analyzer", version: env!("CARGO_PKG_VERSION"), rules } },
+                column_kind: "unicodeCodePoints",
+                results,
+            }],
+        }
+    }
+}
+
+fn level(warning: &Warning) -> &'static str {
+    if warning.is_error {
+        return "error";
+    }
+    match warning.priority {
+        Priority::Critical => "error",
+        Priority::High | Priority::Medium => "warning",
+        Priority::Low | Priority::Trivial => "note",
+    }
+}
+
+/// Documentation of clippy lints; other lints have no stable page.
+fn help_uri(lint_code: &str) -> Option<String> {
+    let name = lint_code.strip_prefix("clippy::")?;
+    Some(format!("https://rust-lang.github.io/rust-clippy/master/index.html#{}", name))
+}
+
+fn artifact_location(file: &str) -> ArtifactLocation {
+    ArtifactLocation { uri: file.replace('\\', "/"), uri_base_id: SOURCE_ROOT }
+}
+
+fn fix(edit: SuggestedEdit) -> Option<Fix> {
+    let artifact_changes = edit
+        .files()
+        .into_iter()
+        .map(|file| {
+            let replacements = edit
+                .replacements
+                .iter()
+                .filter(|r| r.file == file)
+                .map(|r| {
+                    Some(SarifReplacement {
+                        deleted_region: Region::bytes(&r.span)?,
+                        inserted_content: Message { text: r.text.clone() },
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(ArtifactChange { artifact_location: artifact_location(file), replacements })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(Fix { description: Message { text: "Apply the suggested replacement".to_string() }, artifact_changes })
+}
+
+pub fn write_sarif<W: Write>(writer: W, warnings: &[Warning]) -> io::Result<()> {
+    serde_json::to_writer_pretty(writer, &SarifLog::from_warnings(warnings))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    #[test]
+    fn test_sarif_regions_and_fixes() {
+        let source = "fn main() {\n    let v = vec![1];\n    if v.len() == 0 {}\n}\n";
+        let start = source.find("v.len() == 0").unwrap();
+        let raw = serde_json::json!({
+            "children": [{ "message": "using `is_empty` is clearer", "spans": [{
+                "file_name": "src/main.rs",
+                "byte_start": start,
+                "byte_end": start + 12,
+                "line_start": 3,
+                "line_end": 3,
+                "column_start": 8,
+                "column_end": 20,
+                "suggested_replacement": "v.is_empty()",
+                "suggestion_applicability": "MachineApplicable"
+            }]}]
+        });
+        let span = Span { byte_start: Some(start), byte_end: Some(start + 12), ..Span::new(3, 8, 3, 20) };
+        let warnings = [
+            Warning {
+                primary_message: "length comparison to zero".to_string(),
+                span: Some(span),
+                priority: Priority::Low,
+                raw: Some(raw.to_string()),
+                ..Warning::for_test("clippy::len_zero", "src/main.rs", 3)
+            },
+            Warning { is_error: true, ..Warning::for_test("E0308", "src\\lib.rs", 9) },
+            Warning { priority: Priority::High, ..Warning::for_test("clippy::len_zero", "src/lib.rs", 12) },
+        ];
+
+        let mut out = Vec::new();
+        write_sarif(&mut out, &warnings).unwrap();
+        let log: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"],
+            serde_json::json!([
+                { "id": "clippy::len_zero", "helpUri": "https://rust-lang.github.io/rust-clippy/master/index.html#len_zero" },
+                { "id": "E0308" },
+            ])
+        );
+
+        let results = run["results"].as_array().unwrap();
+        let levels: Vec<&str> = results.iter().map(|r| r["level"].as_str().unwrap()).collect();
+        assert_eq!(levels, ["note", "error", "warning"]);
+        assert_eq!(results[2]["ruleIndex"], 0);
+        assert_eq!(results[0]["partialFingerprints"]["cargoAnalyzer/v1"], warnings[0].fingerprint());
+
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(
+            location["region"],
+            serde_json::json!({
+                "startLine": 3, "startColumn": 8, "endLine": 3, "endColumn": 20,
+                "byteOffset": start, "byteLength": 12,
+            })
+        );
+        // Without a span only the line is known
+        let location = &results[1]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+        assert_eq!(location["region"], serde_json::json!({ "startLine": 9, "endLine": 9 }));
+
+        let change = &results[0]["fixes"][0]["artifactChanges"][0];
+        assert_eq!(change["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(
+            change["replacements"],
+            serde_json::json!([{
+                "deletedRegion": { "byteOffset": start, "byteLength": 12 },
+                "insertedContent": { "text": "v.is_empty()" },
+            }])
+        );
+        assert!(results[1].get("fixes").is_none());
+    }
+}
@@ -0,0 +1,153 @@
+//! Source code around warnings.
+//!
+//! Reports quote the lines a warning points at, with a few lines of context
+//! on each side and line numbers. The flagged lines are marked with `>` in
+//! text and markdown and highlighted in HTML; the terminal adds syntax
+//! highlighting (see `FixPreview::render_snippet`).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::types::Warning;
+use super::html::escape_html;
+
+/// Context lines shown on each side of a warning when none are configured
+pub const DEFAULT_CONTEXT_LINES: usize = 2;
+
+/// Lines of a source file around a warning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub file: String,
+    /// Line number of `lines[0]`
+    pub first_line: u32,
+    pub lines: Vec<String>,
+    /// First and last line the warning points at
+    pub span: (u32, u32),
+}
+
+impl Snippet {
+    /// Line numbers paired with the lines.
+    pub fn numbered(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.lines.iter().enumerate().map(|(i, line)| (self.first_line + i as u32, line.as_str()))
+    }
+
+    pub fn is_flagged(&self, line: u32) -> bool {
+        (self.span.0..=self.span.1).contains(&line)
+    }
+
+    fn number_width(&self) -> usize {
+        (self.first_line as usize + self.lines.len()).to_string().len()
+    }
+
+    /// Plain text with a line number gutter and `>` on the flagged lines.
+    pub fn to_text(&self) -> String {
+        let width = self.number_width();
+        self.numbered()
+            .map(|(number, line)| {
+                let marker = if self.is_flagged(number) { '>' } else { ' ' };
+                format!("{} {:>width$} | {}\n", marker, number, line, width = width)
+            })
+            .collect()
+    }
+
+    /// The text form in a fenced code block.
+    pub fn to_markdown(&self) -> String {
+        format!("```rust\n{}```\n", self.to_text())
+    }
+
+    /// A `<pre>` block with the flagged lines in `<mark>`.
+    pub fn to_html(&self) -> String {
+        let width = self.number_width();
+        let mut html = String::from("<pre class=\"snippet\">");
+        for (number, line) in self.numbered() {
+            let text = format!("{:>width$} | {}", number, escape_html(line), width = width);
+            if self.is_flagged(number) {
+                html.push_str(&format!("<mark>{}</mark>\n", text));
+            } else {
+                html.push_str(&format!("{}\n", text));
+            }
+        }
+        html.push_str("</pre>");
+        html
+    }
+}
+
+/// Reads snippets for warnings, loading each source file once.
+#[derive(Debug)]
+pub struct SnippetReader {
+    root: PathBuf,
+    context: usize,
+    files: HashMap<String, Option<Vec<String>>>,
+}
+
+impl SnippetReader {
+    /// Reads files relative to `root`, with `context` lines around each warning.
+    pub fn new(root: &Path, context: usize) -> Self {
+        Self { root: root.to_path_buf(), context, files: HashMap::new() }
+    }
+
+    /// The lines around `warning`, or `None` when its file cannot be read
+    /// or is shorter than the warning's line.
+    pub fn read(&mut self, warning: &Warning) -> Option<Snippet> {
+        let (start, end) = span(warning);
+        let root = &self.root;
+        let lines = self
+            .files
+            .entry(warning.file.clone())
+            .or_insert_with(|| {
+                std::fs::read_to_string(root.join(&warning.file))
+                    .ok()
+                    .map(|source| source.lines().map(str::to_string).collect())
+            })
+            .as_ref()?;
+        if start == 0 || start as usize > lines.len() {
+            return None;
+        }
+
+        let first = (start as usize).saturating_sub(self.context).max(1);
+        let last = (end as usize + self.context).min(lines.len());
+        Some(Snippet {
+            file: warning.file.clone(),
+            first_line: first as u32,
+            lines: lines[first - 1..last].to_vec(),
+            span: (start, end.max(start)),
+        })
+    }
+}
+
+/// First and last line of the warning.
+fn span(warning: &Warning) -> (u32, u32) {
+    warning
+        .span
+        .map_or((warning.line, warning.line), |span| (span.line_start, span.line_end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Priority, Span};
+
+    #[test]
+    fn test_read_snippet() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        let source: String = (1..=12).map(|i| format!("line {}\n", i)).collect();
+        std::fs::write(dir.join("src/lib.rs"), source).unwrap();
+
+        let warning = Warning {
+            primary_message: "length comparison to zero".to_string(),
+            span: Some(Span::new(9, 5, 10, 8)),
+            priority: Priority::Low,
+            ..Warning::for_test("clippy::len_zero", "src/lib.rs", 9)
+        };
+        let snippet = SnippetReader::new(dir, 2).read(&warning).unwrap();
+
+        assert_eq!(snippet.first_line, 7);
+        assert_eq!(snippet.lines.len(), 6);
+        assert_eq!(
+            snippet.to_text(),
+            "   7 | line 7\n   8 | line 8\n>  9 | line 9\n> 10 | line 10\n  11 | line 11\n  12 | line 12\n"
+        );
+        assert!(snippet.to_html().contains("<mark> 9 | line 9</mark>"));
+    }
+}
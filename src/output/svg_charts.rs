@@ -0,0 +1,317 @@
+//! SVG chart rendering.
+//!
+//! Produces standalone SVG documents for the category distribution, severity
+//! distribution, and historical warning trend. The HTML summary embeds them
+//! inline; the markdown analysis links to copies written next to it, so the
+//! charts stay readable where the Unicode block charts do not render.
+//!
+//! Per-file sections get two small inline charts: a sparkline of the file's
+//! warning count over recorded runs and a stacked bar of its category mix.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use crate::{
+    analysis::{statistics::warning::WarningStatistics, trends::TrendAnalysis},
+    output::html::escape_html,
+    types::{CategoryType, Priority},
+};
+
+const CHART_WIDTH: usize = 640;
+const LABEL_WIDTH: usize = 170;
+const BAR_HEIGHT: usize = 22;
+const BAR_GAP: usize = 8;
+const TITLE_HEIGHT: usize = 36;
+const TREND_HEIGHT: usize = 260;
+const TREND_PADDING: usize = 40;
+const SMALL_WIDTH: usize = 160;
+const SPARKLINE_HEIGHT: usize = 32;
+const MIX_HEIGHT: usize = 14;
+const FONT: &str = "font-family=\"sans-serif\" font-size=\"13\"";
+
+const CATEGORY_COLORS: [(CategoryType, &str); 5] = [
+    (CategoryType::Safety, "#e57373"),
+    (CategoryType::Performance, "#ffb74d"),
+    (CategoryType::Documentation, "#81c784"),
+    (CategoryType::Style, "#64b5f6"),
+    (CategoryType::Uncategorized, "#bdbdbd"),
+];
+
+const PRIORITY_COLORS: [(Priority, &str); 5] = [
+    (Priority::Critical, "#c62828"),
+    (Priority::High, "#ef6c00"),
+    (Priority::Medium, "#f9a825"),
+    (Priority::Low, "#1565c0"),
+    (Priority::Trivial, "#90a4ae"),
+];
+
+/// The report charts rendered as SVG documents.
+#[derive(Debug, Clone)]
+pub struct SvgCharts {
+    pub category: String,
+    pub severity: String,
+    /// Only present when there is at least one earlier run to compare with.
+    pub trend: Option<String>,
+    /// The charted values, for text alternatives to the images
+    pub data: ChartData,
+}
+
+/// `(label, value)` rows of each chart, in chart order.
+#[derive(Debug, Clone, Default)]
+pub struct ChartData {
+    pub category: Vec<(String, usize)>,
+    pub severity: Vec<(String, usize)>,
+    /// Empty when there is no trend chart
+    pub trend: Vec<(String, usize)>,
+}
+
+impl SvgCharts {
+    /// Renders all charts. `historical` holds earlier runs, oldest first; the
+    /// current run is appended to the trend from `stats`.
+    pub fn new(stats: &WarningStatistics, historical: &[TrendAnalysis]) -> Self {
+        let category: Vec<(String, usize, &str)> = CATEGORY_COLORS
+            .iter()
+            .map(|(category, color)| {
+                (category.to_string(), stats.by_category.get(category).copied().unwrap_or(0), *color)
+            })
+            .collect();
+        let severity: Vec<(String, usize, &str)> = PRIORITY_COLORS
+            .iter()
+            .map(|(priority, color)| {
+                (priority.to_string(), stats.by_priority.get(priority).copied().unwrap_or(0), *color)
+            })
+            .collect();
+
+        let mut data = ChartData {
+            category: category.iter().map(|(label, value, _)| (label.clone(), *value)).collect(),
+            severity: severity.iter().map(|(label, value, _)| (label.clone(), *value)).collect(),
+            trend: Vec::new(),
+        };
+        let trend = (!historical.is_empty()).then(|| {
+            let points: Vec<(String, usize)> = historical
+                .iter()
+                .enumerate()
+                .map(|(i, run)| {
+                    let label = run.dates.first().cloned().unwrap_or_else(|| format!("#{}", i + 1));
+                    (label, run.total_warnings)
+                })
+                .chain(std::iter::once(("Current".to_string(), stats.total_warnings)))
+                .collect();
+            let svg = line_chart_svg("Warning Trend", &points);
+            data.trend = points;
+            svg
+        });
+
+        Self {
+            category: bar_chart_svg("Category Distribution", &category),
+            severity: bar_chart_svg("Severity Distribution", &severity),
+            trend,
+            data,
+        }
+    }
+}
+
+/// Renders a horizontal bar chart of `(label, value, color)` rows.
+pub fn bar_chart_svg(title: &str, rows: &[(String, usize, &str)]) -> String {
+    let height = TITLE_HEIGHT + rows.len() * (BAR_HEIGHT + BAR_GAP) + BAR_GAP;
+    let max = rows.iter().map(|(_, value, _)| *value).max().unwrap_or(0).max(1);
+    let total: usize = rows.iter().map(|(_, value, _)| *value).sum();
+    let bar_area = CHART_WIDTH - LABEL_WIDTH - 90;
+
+    let mut svg = svg_start(title, height);
+    for (i, (label, value, color)) in rows.iter().enumerate() {
+        let y = TITLE_HEIGHT + i * (BAR_HEIGHT + BAR_GAP);
+        let width = value * bar_area / max;
+        let share = if total > 0 { *value as f64 / total as f64 * 100.0 } else { 0.0 };
+        let _ = writeln!(
+            svg,
+            "<text x=\"8\" y=\"{}\" {}>{}</text>",
+            y + BAR_HEIGHT * 2 / 3, FONT, escape_html(label)
+        );
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"><title>{}: {}</title></rect>",
+            LABEL_WIDTH, y, width, BAR_HEIGHT, color, escape_html(label), value
+        );
+        let _ = writeln!(
+            svg,
+            "<text x=\"{}\" y=\"{}\" {}>{} ({:.1}%)</text>",
+            LABEL_WIDTH + width + 6, y + BAR_HEIGHT * 2 / 3, FONT, value, share
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders a line chart of `(label, value)` points in order.
+pub fn line_chart_svg(title: &str, points: &[(String, usize)]) -> String {
+    let plot_width = CHART_WIDTH - 2 * TREND_PADDING;
+    let plot_height = TREND_HEIGHT - TITLE_HEIGHT - 2 * TREND_PADDING;
+    let bottom = TITLE_HEIGHT + TREND_PADDING + plot_height;
+    let max = points.iter().map(|(_, value)| *value).max().unwrap_or(0).max(1);
+    let step = if points.len() > 1 { plot_width / (points.len() - 1) } else { 0 };
+    // Label every point on short series, otherwise about eight of them
+    let label_every = points.len().div_ceil(8).max(1);
+
+    let coords: Vec<(usize, usize)> = points
+        .iter()
+        .enumerate()
+        .map(|(i, (_, value))| (TREND_PADDING + i * step, bottom - value * plot_height / max))
+        .collect();
+
+    let mut svg = svg_start(title, TREND_HEIGHT);
+    let _ = writeln!(
+        svg,
+        "<line x1=\"{0}\" y1=\"{1}\" x2=\"{2}\" y2=\"{1}\" stroke=\"#999\"/>",
+        TREND_PADDING, bottom, TREND_PADDING + plot_width
+    );
+    let polyline: Vec<String> = coords.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+    let _ = writeln!(
+        svg,
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"#1565c0\" stroke-width=\"2\"/>",
+        polyline.join(" ")
+    );
+    for (i, ((label, value), (x, y))) in points.iter().zip(&coords).enumerate() {
+        let _ = writeln!(
+            svg,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"4\" fill=\"#1565c0\"><title>{}: {}</title></circle>",
+            x, y, escape_html(label), value
+        );
+        if i % label_every == 0 || i + 1 == points.len() {
+            let _ = writeln!(svg, "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" {}>{}</text>", x, y.saturating_sub(8), FONT, value);
+            let _ = writeln!(
+                svg,
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" {}>{}</text>",
+                x, bottom + 18, FONT, escape_html(label)
+            );
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders a small line of `values` in order, without labels. `label`
+/// describes the chart to assistive technology.
+pub fn sparkline_svg(label: &str, values: &[usize]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    let step = if values.len() > 1 { (SMALL_WIDTH - 8) / (values.len() - 1) } else { 0 };
+    let plot_height = SPARKLINE_HEIGHT - 8;
+    let coords: Vec<(usize, usize)> = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| (4 + i * step, 4 + plot_height - value * plot_height / max))
+        .collect();
+
+    let mut svg = small_svg_start(label, SPARKLINE_HEIGHT);
+    let polyline: Vec<String> = coords.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+    let _ = writeln!(
+        svg,
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"#1565c0\" stroke-width=\"2\"/>",
+        polyline.join(" ")
+    );
+    if let Some((x, y)) = coords.last() {
+        let _ = writeln!(svg, "<circle cx=\"{}\" cy=\"{}\" r=\"3\" fill=\"#1565c0\"/>", x, y);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders a single bar split by category in `CATEGORY_COLORS` order.
+pub fn category_mix_svg(label: &str, counts: &HashMap<CategoryType, usize>) -> String {
+    let total: usize = counts.values().sum();
+    let mut svg = small_svg_start(label, MIX_HEIGHT);
+    let mut x = 0;
+    for (category, color) in CATEGORY_COLORS {
+        let count = counts.get(&category).copied().unwrap_or(0);
+        if count == 0 {
+            continue;
+        }
+        let width = count * SMALL_WIDTH / total.max(1);
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{}\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"{}\"><title>{}: {}</title></rect>",
+            x, width.max(1), MIX_HEIGHT, color, category, count
+        );
+        x += width;
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Start of an untitled inline chart, with the background rect the page
+/// styles to its palette.
+fn small_svg_start(label: &str, height: usize) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" role=\"img\" aria-label=\"{2}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>\n",
+        SMALL_WIDTH,
+        height,
+        escape_html(label)
+    )
+}
+
+fn svg_start(title: &str, height: usize) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" role=\"img\" aria-label=\"{2}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>\n\
+         <text x=\"8\" y=\"22\" font-family=\"sans-serif\" font-size=\"16\" font-weight=\"bold\">{2}</text>\n",
+        CHART_WIDTH,
+        height,
+        escape_html(title)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charts_from_stats() {
+        let mut stats = WarningStatistics { total_warnings: 5, ..Default::default() };
+        stats.by_category.insert(CategoryType::Safety, 5);
+        stats.by_priority.insert(Priority::High, 5);
+
+        let charts = SvgCharts::new(&stats, &[]);
+        assert!(charts.trend.is_none() && charts.data.trend.is_empty());
+        assert_eq!(charts.data.category[0], ("Safety".to_string(), 5));
+        assert_eq!(charts.data.severity[1], ("High".to_string(), 5));
+        assert!(charts.category.starts_with("<svg") && charts.category.contains("aria-label=\"Category Distribution\""));
+
+        let history = [
+            TrendAnalysis { dates: vec!["2026-10-01".to_string()], total_warnings: 12, ..Default::default() },
+            TrendAnalysis { total_warnings: 8, ..Default::default() },
+        ];
+        let charts = SvgCharts::new(&stats, &history);
+        assert_eq!(
+            charts.data.trend,
+            [("2026-10-01".to_string(), 12), ("#2".to_string(), 8), ("Current".to_string(), 5)]
+        );
+        assert!(charts.trend.unwrap().contains("<title>Current: 5</title>"));
+    }
+
+    #[test]
+    fn test_bar_chart_scales_to_largest_value() {
+        let rows = [("<Safety>".to_string(), 4, "#e57373"), ("Style".to_string(), 2, "#64b5f6")];
+        let svg = bar_chart_svg("Categories", &rows);
+        assert!(svg.contains(">&lt;Safety&gt;</text>"));
+        assert!(svg.contains("width=\"380\" height=\"22\" fill=\"#e57373\""));
+        assert!(svg.contains("width=\"190\" height=\"22\" fill=\"#64b5f6\""));
+        assert!(svg.contains(">4 (66.7%)</text>") && svg.contains(">2 (33.3%)</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_small_charts() {
+        let sparkline = sparkline_svg("src/lib.rs history", &[0, 10]);
+        assert!(sparkline.contains("points=\"4,28 156,4\""));
+        assert!(sparkline.contains("<circle cx=\"156\" cy=\"4\""));
+        assert!(!sparkline_svg("empty", &[]).contains("<circle"));
+
+        let counts = HashMap::from([(CategoryType::Style, 3), (CategoryType::Safety, 1)]);
+        let mix = category_mix_svg("src/lib.rs categories", &counts);
+        let safety = mix.find("<title>Safety: 1</title>").unwrap();
+        let style = mix.find("<title>Style: 3</title>").unwrap();
+        assert!(safety < style);
+        assert!(mix.contains("<rect x=\"0\" y=\"0\" width=\"40\""));
+        assert!(mix.contains("<rect x=\"40\" y=\"0\" width=\"120\""));
+    }
+}
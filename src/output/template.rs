@@ -0,0 +1,190 @@
+//! User-supplied report templates.
+//!
+//! With a `[templates]` config table, the markdown analysis and the HTML
+//! summary are rendered from Handlebars templates instead of the built-in
+//! layout (requires the `templates` feature):
+//!
+//! ```toml
+//! [templates]
+//! markdown = "templates/analysis.md.hbs"
+//! html = "templates/summary.html.hbs"
+//! ```
+//!
+//! Templates receive a [`TemplateContext`]. Each built-in section is
+//! available pre-rendered under `sections`, so a template can reorder,
+//! drop, or wrap them and add its own branding:
+//!
+//! ```handlebars
+//! # {{title}}
+//!
+//! {{total_warnings}} warnings in {{files_affected}} files.
+//!
+//! {{{sections.heat_table}}}
+//! {{#each categories}}
+//! - {{name}}: {{count}} ({{percent}}%)
+//! {{/each}}
+//! ```
+//!
+//! HTML templates escape `{{value}}` as HTML; use `{{{value}}}` for the
+//! sections. Markdown templates never escape.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::{
+    analysis::statistics::warning::WarningStatistics,
+    types::{Completeness, Warning},
+};
+
+/// Report templates, relative to the workspace root.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct TemplateConfig {
+    /// Template of the markdown analysis report
+    pub markdown: Option<PathBuf>,
+    /// Template of the HTML summary
+    pub html: Option<PathBuf>,
+}
+
+/// One row of a distribution.
+#[derive(Debug, Clone, Serialize)]
+pub struct Share {
+    pub name: String,
+    pub count: usize,
+    /// Share of all warnings, rounded to one decimal
+    pub percent: f64,
+}
+
+/// The data a report template is rendered with.
+#[derive(Debug, Serialize)]
+pub struct TemplateContext<'a> {
+    /// Report title, e.g. `Clippy Analysis Report`
+    pub title: &'a str,
+    /// Local time of the analysis, `YYYY-MM-DD HH:MM:SS`
+    pub generated_at: String,
+    /// Whether clippy finished on every target
+    pub complete: bool,
+    /// Why the analysis is incomplete, empty when it is complete
+    pub incomplete_reason: String,
+    pub total_warnings: usize,
+    pub files_affected: usize,
+    /// Distributions, largest first
+    pub categories: Vec<Share>,
+    pub priorities: Vec<Share>,
+    pub subcategories: Vec<Share>,
+    /// Every warning, with the fields of `schema/warning.schema.json`
+    pub warnings: &'a [Warning],
+    /// Built-in sections rendered in the report's format, by name. Markdown:
+    /// `summary`, `charts`, `heat_table`, `module_heatmap`,
+    /// `module_breakdown`, `recurring_patterns`, `unsafe_usage`,
+    /// `build_info`, `trends`, `unused_dependencies`, `hidden_code`,
+    /// `performance_impact`. HTML:
+    /// `style`, `notice`, `summary`, `charts`, `heat_table`,
+    /// `module_breakdown`, `snippets`, `file_sections`, `raw_diagnostics`.
+    /// Sections with nothing to show are empty.
+    pub sections: BTreeMap<&'static str, String>,
+}
+
+impl<'a> TemplateContext<'a> {
+    pub fn new(
+        title: &'a str,
+        stats: &WarningStatistics,
+        warnings: &'a [Warning],
+        completeness: &Completeness,
+    ) -> Self {
+        let shares = |rows: Vec<(String, usize)>| {
+            let mut shares: Vec<Share> = rows
+                .into_iter()
+                .map(|(name, count)| {
+                    let percent = if stats.total_warnings > 0 { count as f64 / stats.total_warnings as f64 * 100.0 } else { 0.0 };
+                    Share { name, count, percent: (percent * 10.0).round() / 10.0 }
+                })
+                .collect();
+            shares.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+            shares
+        };
+        Self {
+            title,
+            generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            complete: completeness.is_complete(),
+            incomplete_reason: if completeness.is_complete() { String::new() } else { completeness.to_string() },
+            total_warnings: stats.total_warnings,
+            files_affected: stats.files_affected,
+            categories: shares(stats.by_category.iter().map(|(k, v)| (k.to_string(), *v)).collect()),
+            priorities: shares(stats.by_priority.iter().map(|(k, v)| (k.to_string(), *v)).collect()),
+            subcategories: shares(stats.by_subcategory.iter().map(|(k, v)| (k.clone(), *v)).collect()),
+            warnings,
+            sections: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the section `name`, written by `write`.
+    pub fn add_section(
+        &mut self,
+        name: &'static str,
+        write: impl FnOnce(&mut Vec<u8>) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let mut buffer = Vec::new();
+        write(&mut buffer)?;
+        self.sections.insert(name, String::from_utf8_lossy(&buffer).into_owned());
+        Ok(())
+    }
+}
+
+/// Output format of a template, deciding how values are escaped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateFormat {
+    Markdown,
+    Html,
+}
+
+/// Renders the template at `path` with `context`.
+#[cfg(feature = "templates")]
+pub fn render_template(path: &Path, format: TemplateFormat, context: &TemplateContext) -> io::Result<String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("Cannot read template {}: {}", path.display(), e)))?;
+    let invalid = |e: &dyn std::fmt::Display| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("Invalid template {}: {}", path.display(), e))
+    };
+    let mut handlebars = handlebars::Handlebars::new();
+    if format == TemplateFormat::Markdown {
+        handlebars.register_escape_fn(handlebars::no_escape);
+    }
+    handlebars.register_template_string("report", source).map_err(|e| invalid(&e))?;
+    handlebars.render("report", context).map_err(|e| invalid(&e))
+}
+
+#[cfg(not(feature = "templates"))]
+pub fn render_template(path: &Path, _format: TemplateFormat, _context: &TemplateContext) -> io::Result<String> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("Report template {} requires cargo-analyzer built with the `templates` feature", path.display()),
+    ))
+}
+
+#[cfg(all(test, feature = "templates"))]
+mod tests {
+    use super::*;
+    use crate::types::CategoryType;
+
+    #[test]
+    fn test_render_markdown_template() {
+        let stats = WarningStatistics {
+            total_warnings: 4,
+            files_affected: 2,
+            by_category: [(CategoryType::Style, 3), (CategoryType::Safety, 1)].into(),
+            ..WarningStatistics::default()
+        };
+        let mut context = TemplateContext::new("Team <Report>", &stats, &[], &Completeness::Complete);
+        context.add_section("heat_table", |out| io::Write::write_all(out, b"| heat |")).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("template_test.md.hbs");
+        std::fs::write(&path, "# {{title}}\n{{#each categories}}{{name}}={{count}} ({{percent}}%)\n{{/each}}{{{sections.heat_table}}}").unwrap();
+        let markdown = render_template(&path, TemplateFormat::Markdown, &context).unwrap();
+        let html = render_template(&path, TemplateFormat::Html, &context).unwrap();
+        assert_eq!(markdown, "# Team <Report>\nStyle=3 (75.0%)\nSafety=1 (25.0%)\n| heat |");
+        assert!(html.starts_with("# Team &lt;Report&gt;"));
+    }
+}
@@ -0,0 +1,398 @@
+//! Full-screen terminal dashboard (`--tui`).
+//!
+//! Four panes: the category distribution, the files with the most warnings,
+//! the warning list, and the selected warning's code and suggested fix. The
+//! list can be searched by lint, file, and message and filtered by category.
+//!
+//! Keys: `↑`/`↓` or `j`/`k` move, `PgUp`/`PgDn`, `g`/`G` jump, `/` searches,
+//! `c` cycles the category filter, `Esc` clears the filters, `o` opens the
+//! selected warning in `$EDITOR`, and `q` quits.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+use crate::types::{CategoryType, Warning};
+use super::snippet::SnippetReader;
+
+/// Files listed in the worst files pane
+const WORST_FILES: usize = 8;
+/// Width of the longest bar in the category pane
+const BAR_WIDTH: usize = 20;
+/// Rows moved by `PgUp` and `PgDn`
+const PAGE: usize = 10;
+
+/// What a key press asks of the event loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DashboardAction {
+    None,
+    Open,
+    Quit,
+}
+
+/// Warnings, filters, and selection of the dashboard.
+pub struct DashboardState {
+    warnings: Vec<Warning>,
+    /// Categories by count, largest first
+    categories: Vec<(CategoryType, usize)>,
+    worst_files: Vec<(String, usize)>,
+    query: String,
+    searching: bool,
+    category: Option<CategoryType>,
+    /// Indices of the warnings passing the filters
+    visible: Vec<usize>,
+    list: ListState,
+}
+
+impl DashboardState {
+    /// A dashboard over `warnings`, listed by priority, then file and line.
+    pub fn new(mut warnings: Vec<Warning>) -> Self {
+        warnings.sort_by(|a, b| {
+            b.priority
+                .severity_score()
+                .cmp(&a.priority.severity_score())
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+        let mut by_category: HashMap<CategoryType, usize> = HashMap::new();
+        let mut by_file: HashMap<&str, usize> = HashMap::new();
+        for warning in &warnings {
+            *by_category.entry(warning.category).or_insert(0) += 1;
+            *by_file.entry(&warning.file).or_insert(0) += 1;
+        }
+        let mut categories: Vec<(CategoryType, usize)> = by_category.into_iter().collect();
+        categories.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let mut worst_files: Vec<(String, usize)> =
+            by_file.into_iter().map(|(file, count)| (file.to_string(), count)).collect();
+        worst_files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        worst_files.truncate(WORST_FILES);
+
+        let mut state = Self {
+            warnings,
+            categories,
+            worst_files,
+            query: String::new(),
+            searching: false,
+            category: None,
+            visible: Vec::new(),
+            list: ListState::default(),
+        };
+        state.refilter();
+        state
+    }
+
+    /// Warnings passing the search and category filter, in list order.
+    pub fn visible(&self) -> impl Iterator<Item = &Warning> {
+        self.visible.iter().map(|&i| &self.warnings[i])
+    }
+
+    pub fn selected(&self) -> Option<&Warning> {
+        self.list.selected().and_then(|i| self.visible.get(i)).map(|&i| &self.warnings[i])
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) -> DashboardAction {
+        if self.searching {
+            match key {
+                KeyCode::Enter => self.searching = false,
+                KeyCode::Esc => {
+                    self.searching = false;
+                    self.query.clear();
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                }
+                KeyCode::Char(c) => self.query.push(c),
+                _ => return DashboardAction::None,
+            }
+            self.refilter();
+            return DashboardAction::None;
+        }
+
+        match key {
+            KeyCode::Char('q') => return DashboardAction::Quit,
+            KeyCode::Char('o') if self.selected().is_some() => return DashboardAction::Open,
+            KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+            KeyCode::PageDown => self.move_by(PAGE as isize),
+            KeyCode::PageUp => self.move_by(-(PAGE as isize)),
+            KeyCode::Home | KeyCode::Char('g') => self.move_by(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => self.move_by(isize::MAX),
+            KeyCode::Char('/') => self.searching = true,
+            KeyCode::Char('c') => {
+                let next = match self.category {
+                    None => 0,
+                    Some(current) => self.categories.iter().position(|(c, _)| *c == current).map_or(0, |i| i + 1),
+                };
+                self.category = self.categories.get(next).map(|(category, _)| *category);
+                self.refilter();
+            }
+            KeyCode::Esc => {
+                self.query.clear();
+                self.category = None;
+                self.refilter();
+            }
+            _ => {}
+        }
+        DashboardAction::None
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let Some(last) = self.visible.len().checked_sub(1) else { return };
+        let current = self.list.selected().unwrap_or(0) as isize;
+        self.list.select(Some(current.saturating_add(delta).clamp(0, last as isize) as usize));
+    }
+
+    fn refilter(&mut self) {
+        let query = self.query.to_lowercase();
+        self.visible = self
+            .warnings
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| self.category.is_none_or(|category| w.category == category))
+            .filter(|(_, w)| {
+                query.is_empty()
+                    || [&w.lint_code, &w.file, &w.primary_message].iter().any(|field| field.to_lowercase().contains(&query))
+            })
+            .map(|(i, _)| i)
+            .collect();
+        self.list.select((!self.visible.is_empty()).then_some(0));
+    }
+}
+
+/// Runs the dashboard until `q`, reading code relative to `root`.
+pub fn run_dashboard(warnings: Vec<Warning>, root: &Path, context: usize) -> io::Result<()> {
+    let mut state = DashboardState::new(warnings);
+    let mut snippets = SnippetReader::new(root, context);
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut state, &mut snippets, root);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    state: &mut DashboardState,
+    snippets: &mut SnippetReader,
+    root: &Path,
+) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| draw(frame, state, snippets))?;
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match state.handle_key(key.code) {
+            DashboardAction::Quit => return Ok(()),
+            DashboardAction::Open => {
+                let Some(warning) = state.selected() else { continue };
+                let mut command = editor_command(&editor(), &root.join(&warning.file), warning.line);
+                ratatui::restore();
+                let status = command.status();
+                *terminal = ratatui::init();
+                status?;
+            }
+            DashboardAction::None => {}
+        }
+    }
+}
+
+/// `$VISUAL`, `$EDITOR`, or `vi`.
+fn editor() -> String {
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Opens `file` at `line` in `editor`, a command line that may carry its
+/// own arguments. VS Code style editors take `-g file:line`; the rest
+/// `+line file`, which vi, Emacs, nano, and Helix understand.
+pub fn editor_command(editor: &str, file: &Path, line: u32) -> Command {
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let mut command = Command::new(program);
+    command.args(words);
+    let name = Path::new(program).file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    if matches!(name.as_str(), "code" | "codium" | "cursor" | "windsurf") {
+        command.arg("-g").arg(format!("{}:{}", file.display(), line));
+    } else {
+        command.arg(format!("+{}", line)).arg(PathBuf::from(file));
+    }
+    command
+}
+
+fn draw(frame: &mut Frame, state: &mut DashboardState, snippets: &mut SnippetReader) {
+    let [header, top, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(WORST_FILES as u16 + 2),
+        Constraint::Min(5),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [categories, files] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(top);
+    let [list, detail] = Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(body);
+
+    let filter = match (state.category, state.query.is_empty()) {
+        (None, true) => String::new(),
+        (category, _) => format!(
+            "  filter: {}{}",
+            category.map_or_else(String::new, |c| format!("{} ", c)),
+            if state.query.is_empty() { String::new() } else { format!("\"{}\"", state.query) }
+        ),
+    };
+    frame.render_widget(
+        Paragraph::new(format!(
+            " cargo-analyzer  {} warnings, {} shown{}",
+            state.warnings.len(),
+            state.visible.len(),
+            filter
+        ))
+        .style(Style::new().add_modifier(Modifier::BOLD)),
+        header,
+    );
+
+    draw_categories(frame, state, categories);
+    let max_file = state.worst_files.first().map_or(1, |(_, count)| *count);
+    let file_lines: Vec<Line> = state
+        .worst_files
+        .iter()
+        .map(|(file, count)| Line::from(format!("{:>4} {} {}", count, bar(*count, max_file, BAR_WIDTH / 2), file)))
+        .collect();
+    frame.render_widget(Paragraph::new(file_lines).block(titled(" Worst files ")), files);
+
+    let items: Vec<ListItem> = state
+        .visible()
+        .map(|w| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<8} ", w.priority), Style::new().fg(priority_color(w))),
+                Span::raw(format!("{}:{} ", w.file, w.line)),
+                Span::styled(w.lint_code.clone(), Style::new().fg(Color::Cyan)),
+            ]))
+        })
+        .collect();
+    let warnings = List::new(items)
+        .block(titled(" Warnings "))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(warnings, list, &mut state.list);
+
+    draw_detail(frame, state.selected(), snippets, detail);
+
+    let help = if state.searching {
+        format!(" /{}_  (Enter to keep, Esc to clear)", state.query)
+    } else {
+        " ↑↓/jk move  / search  c category  Esc clear  o open in $EDITOR  q quit".to_string()
+    };
+    frame.render_widget(Paragraph::new(help).style(Style::new().fg(Color::DarkGray)), footer);
+}
+
+fn draw_categories(frame: &mut Frame, state: &DashboardState, area: Rect) {
+    let max = state.categories.first().map_or(1, |(_, count)| *count);
+    let lines: Vec<Line> = state
+        .categories
+        .iter()
+        .map(|(category, count)| {
+            let style = if state.category == Some(*category) {
+                Style::new().add_modifier(Modifier::BOLD)
+            } else {
+                Style::new()
+            };
+            Line::styled(format!("{:<14} {:<width$} {}", category.to_string(), bar(*count, max, BAR_WIDTH), count, width = BAR_WIDTH), style)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).block(titled(" Categories ")), area);
+}
+
+fn draw_detail(frame: &mut Frame, warning: Option<&Warning>, snippets: &mut SnippetReader, area: Rect) {
+    let Some(warning) = warning else {
+        frame.render_widget(Paragraph::new("No warnings match the filters.").block(titled(" Detail ")), area);
+        return;
+    };
+    let mut lines = vec![
+        Line::styled(warning.lint_code.clone(), Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Line::from(warning.primary_message.clone()),
+        Line::from(format!("{}:{}  {} · {}", warning.file, warning.line, warning.category, warning.priority)),
+        Line::default(),
+    ];
+    match snippets.read(warning) {
+        Some(snippet) => {
+            for (number, text) in snippet.numbered() {
+                let style = if snippet.is_flagged(number) { Style::new().fg(Color::Yellow) } else { Style::new() };
+                lines.push(Line::styled(format!("{:>5} │ {}", number, text), style));
+            }
+        }
+        None => lines.push(Line::styled("(source unavailable)", Style::new().fg(Color::DarkGray))),
+    }
+    if let Some(fix) = &warning.suggested_fix {
+        lines.push(Line::default());
+        lines.push(Line::styled("Suggested fix", Style::new().add_modifier(Modifier::BOLD)));
+        lines.extend(fix.lines().map(|line| Line::styled(line.to_string(), Style::new().fg(Color::Green))));
+    }
+    frame.render_widget(Paragraph::new(lines).block(titled(" Detail ")).wrap(Wrap { trim: false }), area);
+}
+
+fn titled(title: &str) -> Block<'_> {
+    Block::new().borders(Borders::ALL).title(title)
+}
+
+fn bar(count: usize, max: usize, width: usize) -> String {
+    "█".repeat((count * width).div_ceil(max.max(1)))
+}
+
+fn priority_color(warning: &Warning) -> Color {
+    match warning.priority.severity_score() {
+        5 => Color::Red,
+        4 => Color::LightRed,
+        3 => Color::Yellow,
+        _ => Color::Gray,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    #[test]
+    fn test_dashboard_filters() {
+        let warning = |lint: &str, file: &str, category, priority| Warning {
+            primary_message: format!("{} here", lint),
+            category,
+            priority,
+            ..Warning::for_test(lint, file, 3)
+        };
+        let mut state = DashboardState::new(vec![
+            warning("clippy::needless_return", "src/a.rs", CategoryType::Style, Priority::Low),
+            warning("clippy::unwrap_used", "src/b.rs", CategoryType::Safety, Priority::Critical),
+            warning("clippy::len_zero", "src/a.rs", CategoryType::Style, Priority::Low),
+        ]);
+        assert_eq!(state.selected().unwrap().lint_code, "clippy::unwrap_used");
+        assert_eq!(state.worst_files[0], ("src/a.rs".to_string(), 2));
+
+        // Style has the most warnings, so it is the first category filter
+        state.handle_key(KeyCode::Char('c'));
+        assert_eq!(state.visible().count(), 2);
+        for c in "/len".chars() {
+            state.handle_key(KeyCode::Char(c));
+        }
+        state.handle_key(KeyCode::Enter);
+        assert_eq!(state.visible().map(|w| w.lint_code.as_str()).collect::<Vec<_>>(), ["clippy::len_zero"]);
+        assert_eq!(state.handle_key(KeyCode::Char('o')), DashboardAction::Open);
+        state.handle_key(KeyCode::Esc);
+        state.handle_key(KeyCode::Char('G'));
+        assert_eq!(state.selected().unwrap().file, "src/a.rs");
+        assert_eq!(state.handle_key(KeyCode::Char('q')), DashboardAction::Quit);
+
+        let command = editor_command("code --wait", Path::new("src/a.rs"), 3);
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["--wait", "-g", "src/a.rs:3"]);
+        assert_eq!(editor_command("vim", Path::new("src/a.rs"), 3).get_args().collect::<Vec<_>>(), ["+3", "src/a.rs"]);
+    }
+}
@@ -0,0 +1,145 @@
+//! Warnings reported by external tools.
+//!
+//! Any executable can feed warnings into the analysis by printing one JSON
+//! object per line:
+//!
+//! ```json
+//! {"lint": "todo_comment", "message": "TODO left in code", "file": "src/lib.rs", "line": 12}
+//! ```
+//!
+//! `lint`, `message`, `file`, and `line` are required. Optional fields are
+//! `column`, `level` (`error`, `warning`, or `note`), `category`,
+//! `priority`, `suggestion`, and `crate`. Lints are namespaced by the tool's
+//! name (`mytool::todo_comment`) unless they already contain `::`. Without
+//! a category, lints are categorized by name like unknown clippy lints; the
+//! priority defaults from the level. Lines that do not start with `{` are
+//! ignored, so tools can mix in human-readable output.
+
+use std::io;
+use std::path::Path;
+use serde::Deserialize;
+use crate::types::{Priority, Source, Span, Warning};
+use super::warning_parser::categorize_by_name;
+
+#[derive(Debug, Deserialize)]
+struct ExternalDiagnostic {
+    lint: String,
+    message: String,
+    file: String,
+    line: u32,
+    #[serde(default = "first_column")]
+    column: u32,
+    #[serde(default)]
+    level: Option<String>,
+    #[serde(default)]
+    category: Option<String>,
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default)]
+    suggestion: Option<String>,
+    #[serde(default, rename = "crate")]
+    crate_name: Option<String>,
+}
+
+fn first_column() -> u32 {
+    1
+}
+
+/// Parses the line-JSON output of the external tool `tool`. File paths are
+/// made relative to `root` when inside it.
+pub fn parse_external(tool: &str, output: &str, root: &Path) -> io::Result<Vec<Warning>> {
+    let mut warnings = Vec::new();
+    for (index, line) in output.lines().enumerate() {
+        let line = line.trim();
+        if !line.starts_with('{') {
+            continue;
+        }
+        let diagnostic: ExternalDiagnostic = serde_json::from_str(line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} output line {}: {}", tool, index + 1, e),
+            )
+        })?;
+        warnings.push(diagnostic.into_warning(tool, root)?);
+    }
+    Ok(warnings)
+}
+
+impl ExternalDiagnostic {
+    fn into_warning(self, tool: &str, root: &Path) -> io::Result<Warning> {
+        let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", tool, e));
+        let lint_code = if self.lint.contains("::") {
+            self.lint
+        } else {
+            format!("{}::{}", tool, self.lint)
+        };
+        let is_error = self.level.as_deref() == Some("error");
+        let (category, category_confidence) = match &self.category {
+            Some(category) => (category.parse().map_err(invalid)?, 1.0),
+            None => categorize_by_name(lint_code.rsplit("::").next().unwrap_or(&lint_code)),
+        };
+        let priority = match &self.priority {
+            Some(priority) => priority.parse().map_err(invalid)?,
+            None => match self.level.as_deref() {
+                Some("error") => Priority::Critical,
+                Some("note") | Some("help") => Priority::Low,
+                _ => Priority::Medium,
+            },
+        };
+        let file = Path::new(&self.file)
+            .strip_prefix(root)
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or(self.file);
+
+        Ok(Warning {
+            lint_code,
+            primary_message: self.message,
+            span: Some(Span::at(self.line, self.column)),
+            explanations: Vec::new(),
+            children: Vec::new(),
+            rendered: None,
+            category,
+            priority,
+            file,
+            line: self.line,
+            suggested_fix: self.suggestion,
+            crate_name: self.crate_name,
+            source: Source::External,
+            is_error,
+            category_confidence,
+            raw: None,
+            fingerprint: String::new(),
+            macro_origin: None,
+            safety_subcategory: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CategoryType;
+
+    #[test]
+    fn test_parse_external() {
+        let output = "\
+mytool 1.0 scanning...
+{\"lint\": \"todo_comment\", \"message\": \"TODO left\", \"file\": \"/work/demo/src/lib.rs\", \"line\": 12}
+{\"lint\": \"secrets::api_key\", \"message\": \"key in source\", \"file\": \"src/config.rs\", \"line\": 3, \"level\": \"error\", \"category\": \"safety\"}
+";
+        let warnings = parse_external("mytool", output, Path::new("/work/demo")).unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].lint_code, "mytool::todo_comment");
+        assert_eq!(warnings[0].file, "src/lib.rs");
+        assert_eq!(warnings[0].priority, Priority::Medium);
+        assert_eq!(warnings[0].source, Source::External);
+        assert_eq!(warnings[0].span, Some(Span::at(12, 1)));
+        assert_eq!(warnings[1].lint_code, "secrets::api_key");
+        assert_eq!(warnings[1].category, CategoryType::Safety);
+        assert_eq!(warnings[1].priority, Priority::Critical);
+        assert!(warnings[1].is_error);
+
+        let error = parse_external("mytool", "{\"lint\": \"x\"}", Path::new(".")).unwrap_err();
+        assert!(error.to_string().starts_with("mytool output line 1:"));
+    }
+}
@@ -0,0 +1,163 @@
+//! Future incompatibilities: code a future Rust release will reject.
+//!
+//! rustc flags such code in the workspace with a note that it "was
+//! previously accepted by the compiler but is being phased out"; those
+//! diagnostics are marked as they are parsed. In dependencies, where lints
+//! are capped, cargo only prints a summary after the build and keeps the
+//! details for `cargo report future-incompatibilities`, whose output lists
+//! each affected package followed by its diagnostics, quoted with `> `:
+//!
+//! ```text
+//! The package `rental v0.5.5` currently triggers the following future incompatibility lints:
+//! > warning: using `procedural-masquerade` crate
+//! >   --> /home/user/.cargo/registry/src/index/rental-0.5.5/src/lib.rs:1:1
+//! >    = note: `#[warn(proc_macro_back_compat)]` on by default
+//! ```
+//!
+//! Either way they become Critical Safety warnings with their own
+//! subcategory, so upcoming breakage is tracked with the rest.
+
+use std::path::Path;
+use crate::types::{CategoryType, Priority, SafetySubcategory, Source, Span, Warning};
+use super::rustfmt::relative_path;
+
+/// Lint id of report diagnostics that name no lint
+pub const FUTURE_INCOMPAT_LINT: &str = "future_incompatible";
+
+/// Whether a diagnostic's child messages say a future release will reject it.
+pub fn is_future_incompat(children: &[String]) -> bool {
+    children.iter().any(|child| {
+        child.contains("is being phased out") || child.contains("will become a hard error in a future release")
+    })
+}
+
+/// Marks `warning` as a future incompatibility.
+pub fn mark_future_incompat(warning: &mut Warning) {
+    warning.safety_subcategory = Some(SafetySubcategory::FutureIncompat);
+    warning.category = CategoryType::Safety;
+    warning.category_confidence = 1.0;
+    warning.priority = Priority::Critical;
+}
+
+/// Parses the output of `cargo report future-incompatibilities`. File paths
+/// are made relative to `root` when inside it.
+pub fn parse_future_incompat_report(report: &str, root: &Path) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    let mut package: Option<String> = None;
+    let mut current: Option<Warning> = None;
+    for line in report.lines() {
+        if let Some(rest) = line.strip_prefix("The package `") {
+            warnings.extend(current.take());
+            package = rest.split('`').next().map(|name| name.split(' ').next().unwrap_or(name).to_string());
+            continue;
+        }
+        let Some(quoted) = line.strip_prefix('>') else { continue };
+        let quoted = quoted.trim();
+        let message = quoted.strip_prefix("warning: ").or_else(|| quoted.strip_prefix("error: "));
+        if let Some(message) = message {
+            warnings.extend(current.take());
+            current = Some(report_warning(message, package.clone()));
+        } else if let Some(warning) = current.as_mut() {
+            if let Some(location) = quoted.strip_prefix("--> ").filter(|_| warning.file.is_empty()) {
+                let mut parts = location.rsplitn(3, ':');
+                let (column, line, file) = (parts.next(), parts.next(), parts.next());
+                if let (Some(column), Some(line), Some(file)) = (column, line, file) {
+                    let (line, column) = (line.parse().unwrap_or(1), column.parse().unwrap_or(1));
+                    warning.file = relative_path(file, root);
+                    warning.line = line;
+                    warning.span = Some(Span::at(line, column));
+                }
+            } else if let Some(note) = quoted.strip_prefix("= ") {
+                let note = note.split_once(": ").map_or(note, |(_, text)| text);
+                if let Some(lint) = lint_attribute(note) {
+                    warning.lint_code = lint;
+                } else {
+                    warning.children.push(note.to_string());
+                }
+            }
+        }
+    }
+    warnings.extend(current);
+    warnings
+}
+
+/// The lint of a ``#[warn(lint)]` on by default`` note.
+fn lint_attribute(note: &str) -> Option<String> {
+    let attribute = note.strip_prefix("`#[")?;
+    let (_, lint) = attribute.split_once('(')?;
+    let lint = lint.split(')').next()?;
+    Some(lint.to_string()).filter(|lint| !lint.is_empty() && !lint.contains(' '))
+}
+
+fn report_warning(message: &str, crate_name: Option<String>) -> Warning {
+    let mut warning = Warning {
+        lint_code: FUTURE_INCOMPAT_LINT.to_string(),
+        primary_message: message.to_string(),
+        span: None,
+        explanations: Vec::new(),
+        children: Vec::new(),
+        rendered: None,
+        category: CategoryType::Safety,
+        priority: Priority::Critical,
+        file: String::new(),
+        line: 0,
+        suggested_fix: None,
+        crate_name,
+        source: Source::Rustc,
+        is_error: false,
+        category_confidence: 1.0,
+        raw: None,
+        fingerprint: String::new(),
+        macro_origin: None,
+        safety_subcategory: None,
+    };
+    mark_future_incompat(&mut warning);
+    warning
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_future_incompat_report() {
+        let report = "\
+The following warnings were discovered during the build. These warnings are an
+indication that the packages contain code that will become an error in a
+future release of Rust.
+
+The package `rental v0.5.5` currently triggers the following future incompatibility lints:
+> warning: using `procedural-masquerade` crate
+>   --> /home/user/.cargo/registry/src/index/rental-0.5.5/src/lib.rs:12:5
+>    |
+> 12 |     enum ProceduralMasqueradeDummyType {
+>    |
+>    = note: `#[warn(proc_macro_back_compat)]` on by default
+>    = warning: this was previously accepted by the compiler but is being phased out; it will become a hard error in a future release!
+>    = note: for more information, see issue #83125 <https://github.com/rust-lang/rust/issues/83125>
+>
+
+The package `local v0.1.0` currently triggers the following future incompatibility lints:
+> warning: never type fallback affects this call
+>   --> /work/local/src/main.rs:3:1
+";
+        let warnings = parse_future_incompat_report(report, Path::new("/work/local"));
+        assert_eq!(warnings.len(), 2);
+        let rental = &warnings[0];
+        assert_eq!(rental.lint_code, "proc_macro_back_compat");
+        assert_eq!(rental.crate_name.as_deref(), Some("rental"));
+        assert_eq!((rental.line, rental.priority, rental.category), (12, Priority::Critical, CategoryType::Safety));
+        assert_eq!(rental.primary_message, "using `procedural-masquerade` crate");
+        assert_eq!(rental.safety_subcategory, Some(SafetySubcategory::FutureIncompat));
+        assert!(is_future_incompat(&rental.children));
+        assert_eq!(rental.children.len(), 2);
+        assert_eq!((warnings[1].file.as_str(), warnings[1].lint_code.as_str()), ("src/main.rs", FUTURE_INCOMPAT_LINT));
+
+        // The same lint in the workspace, as clippy reports it
+        let message = r#"{"reason":"compiler-message","message":{"code":{"code":"proc_macro_back_compat"},"level":"warning","message":"using `procedural-masquerade` crate","spans":[{"file_name":"src/lib.rs","line_start":12,"line_end":12,"column_start":5,"column_end":9,"is_primary":true}],"children":[{"message":"this was previously accepted by the compiler but is being phased out; it will become a hard error in a future release!","level":"warning","spans":[],"children":[]}],"rendered":null}}"#;
+        let (parsed, _, _) = crate::parser::WarningParser::new().parse_str(message, 1);
+        assert_eq!(parsed[0].priority, Priority::Critical);
+        assert_eq!(parsed[0].primary_message, rental.primary_message);
+        assert_eq!(parsed[0].safety_subcategory, rental.safety_subcategory);
+    }
+}
@@ -1,3 +1,9 @@
+mod external;
+mod future_incompat;
+mod rustfmt;
 mod warning_parser;
 
-pub use warning_parser::{WarningParser, AnalysisContext}; 
\ No newline at end of file
+pub use external::parse_external;
+pub use future_incompat::{FUTURE_INCOMPAT_LINT, is_future_incompat, mark_future_incompat, parse_future_incompat_report};
+pub use rustfmt::{RUSTFMT_LINT, parse_fmt_check, parse_fmt_json};
+pub use warning_parser::{WarningParser, AnalysisContext, ParseResult, is_workspace_file, read_line_lossy};
\ No newline at end of file
@@ -0,0 +1,210 @@
+//! Formatting deviations reported by rustfmt.
+//!
+//! Nightly rustfmt can describe each mismatch as JSON (`--emit json`); stable
+//! rustfmt only prints `--check` diffs, with hunks headed by
+//! `Diff in <file>:<line>:` (or `Diff in <file> at line <line>:` in older
+//! versions). Both become one Style warning per mismatch, pointing at its
+//! first changed line and suggesting the formatted code.
+
+use std::path::Path;
+use serde::Deserialize;
+use crate::types::{CategoryType, Priority, Source, Span, StyleSubcategory, Warning};
+
+/// Lint id given to formatting deviations
+pub const RUSTFMT_LINT: &str = "rustfmt::formatting";
+
+#[derive(Deserialize)]
+struct FmtFile {
+    name: String,
+    mismatches: Vec<FmtMismatch>,
+}
+
+#[derive(Deserialize)]
+struct FmtMismatch {
+    original_begin_line: u32,
+    original_end_line: u32,
+    expected: String,
+}
+
+/// Parses the output of `rustfmt --emit json`. File paths are made relative
+/// to `root` when inside it.
+pub fn parse_fmt_json(json: &str, root: &Path) -> serde_json::Result<Vec<Warning>> {
+    let files: Vec<FmtFile> = serde_json::from_str(json)?;
+    Ok(files
+        .into_iter()
+        .flat_map(|file| {
+            let path = relative_path(&file.name, root);
+            file.mismatches.into_iter().map(move |mismatch| {
+                formatting_warning(
+                    &path,
+                    mismatch.original_begin_line,
+                    mismatch.original_end_line,
+                    mismatch.expected.trim_end_matches('\n'),
+                )
+            })
+        })
+        .collect())
+}
+
+/// Parses the diff printed by `rustfmt --check`. File paths are made
+/// relative to `root` when inside it.
+pub fn parse_fmt_check(output: &str, root: &Path) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    let mut hunk: Option<Hunk> = None;
+    for line in output.lines() {
+        if let Some((file, start)) = parse_hunk_header(line) {
+            warnings.extend(hunk.take().and_then(|hunk| hunk.into_warning()));
+            hunk = Some(Hunk::new(relative_path(file, root), start));
+        } else if let Some(hunk) = hunk.as_mut() {
+            hunk.push(line);
+        }
+    }
+    warnings.extend(hunk.and_then(|hunk| hunk.into_warning()));
+    warnings
+}
+
+/// File and starting line of a `Diff in` header.
+fn parse_hunk_header(line: &str) -> Option<(&str, u32)> {
+    let header = line.strip_prefix("Diff in ")?.strip_suffix(':')?;
+    if let Some((file, start)) = header.rsplit_once(" at line ") {
+        return Some((file, start.parse().ok()?));
+    }
+    let (file, start) = header.rsplit_once(':')?;
+    Some((file, start.parse().ok()?))
+}
+
+/// One `--check` diff hunk.
+struct Hunk {
+    file: String,
+    /// Line of the hunk's first original line
+    start: u32,
+    /// Original lines seen so far
+    original_lines: u32,
+    /// Original line range of the changes
+    changed: Option<(u32, u32)>,
+    /// Formatted lines from the first change to the last
+    expected: Vec<String>,
+    /// Context lines seen since the last change
+    pending_context: Vec<String>,
+}
+
+impl Hunk {
+    fn new(file: String, start: u32) -> Self {
+        Self {
+            file,
+            start,
+            original_lines: 0,
+            changed: None,
+            expected: Vec::new(),
+            pending_context: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, line: &str) {
+        let current = self.start + self.original_lines;
+        match line.chars().next() {
+            Some('-') => {
+                self.mark_changed(current);
+                self.original_lines += 1;
+            }
+            Some('+') => {
+                // Insertions belong before the current original line
+                self.mark_changed(current.saturating_sub(1).max(self.start));
+                self.expected.push(line[1..].to_string());
+            }
+            _ => {
+                self.original_lines += 1;
+                if self.changed.is_some() {
+                    self.pending_context.push(line.get(1..).unwrap_or("").to_string());
+                }
+            }
+        }
+    }
+
+    fn mark_changed(&mut self, line: u32) {
+        self.changed = Some(match self.changed {
+            Some((first, last)) => (first.min(line), last.max(line)),
+            None => (line, line),
+        });
+        self.expected.append(&mut self.pending_context);
+    }
+
+    fn into_warning(self) -> Option<Warning> {
+        let (first, last) = self.changed?;
+        Some(formatting_warning(&self.file, first, last, &self.expected.join("\n")))
+    }
+}
+
+fn formatting_warning(file: &str, first: u32, last: u32, expected: &str) -> Warning {
+    Warning {
+        lint_code: RUSTFMT_LINT.to_string(),
+        primary_message: format!(
+            "{}: code differs from rustfmt output; run `cargo fmt`",
+            StyleSubcategory::Formatting.description(),
+        ),
+        span: Some(Span::new(first, 1, last, 1)),
+        explanations: Vec::new(),
+        children: Vec::new(),
+        rendered: None,
+        category: CategoryType::Style,
+        priority: Priority::Trivial,
+        file: file.to_string(),
+        line: first,
+        suggested_fix: Some(expected.to_string()),
+        crate_name: None,
+        source: Source::Rustfmt,
+        is_error: false,
+        category_confidence: 1.0,
+        raw: None,
+        fingerprint: String::new(),
+        macro_origin: None,
+        safety_subcategory: None,
+    }
+}
+
+pub(super) fn relative_path(file: &str, root: &Path) -> String {
+    Path::new(file)
+        .strip_prefix(root)
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| file.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fmt_check() {
+        let output = "\
+Diff in /work/demo/src/main.rs:1:
+ mod util;
+ struct Foo;
+-impl Foo { fn new() -> Foo { Foo } }
++impl Foo {
++    fn new() -> Foo {
++        Foo
++    }
++}
+ fn main() {
+Diff in /work/demo/src/main.rs at line 9:
+     let y = 5;
+-    for i in 0..v.len() { println!(\"{}\", v[i]); }
++    for i in 0..v.len() {
++        println!(\"{}\", v[i]);
++    }
+ }
+";
+        let warnings = parse_fmt_check(output, Path::new("/work/demo"));
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].lint_code, RUSTFMT_LINT);
+        assert_eq!(warnings[0].file, "src/main.rs");
+        assert_eq!(warnings[0].line, 3);
+        assert_eq!(warnings[0].source, Source::Rustfmt);
+        assert_eq!(
+            warnings[0].suggested_fix.as_deref(),
+            Some("impl Foo {\n    fn new() -> Foo {\n        Foo\n    }\n}")
+        );
+        assert_eq!(warnings[1].line, 10);
+        assert_eq!(warnings[1].span, Some(Span::at(10, 1)));
+    }
+}
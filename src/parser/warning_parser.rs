@@ -1,12 +1,14 @@
 use std::io::{BufRead, BufReader};
 use std::fs::File;
 use std::collections::HashMap;
+use rayon::prelude::*;
 use serde::Deserialize;
 use crate::types::{
-    Warning, FileWarnings,
+    Warning, FileWarnings, MacroOrigin, Source, Span,
     categories::CategoryType,
     priorities::Priority,
 };
+use super::future_incompat::{is_future_incompat, mark_future_incompat};
 
 #[derive(Debug, Deserialize)]
 struct CompilerMessage {
@@ -25,6 +27,7 @@ struct CompilerMessage {
 #[derive(Debug, Deserialize)]
 struct CompilerTarget {
     kind: Vec<String>,
+    #[serde(default)]
     crate_types: Vec<String>,
     name: String,
     src_path: String,
@@ -52,10 +55,55 @@ struct DiagnosticCode {
 #[derive(Debug, Deserialize)]
 struct DiagnosticSpan {
     file_name: String,
-    line_start: u32,
-    line_end: u32,
-    column_start: u32,
-    column_end: u32,
+    #[serde(default)]
+    is_primary: bool,
+    #[serde(flatten)]
+    span: Span,
+    /// The macro invocation the span was expanded from
+    #[serde(default)]
+    expansion: Option<Box<DiagnosticSpanMacroExpansion>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiagnosticSpanMacroExpansion {
+    /// The invocation, itself possibly inside another expansion
+    span: DiagnosticSpan,
+    macro_decl_name: String,
+    def_site_span: Option<DiagnosticSpan>,
+}
+
+impl DiagnosticSpan {
+    /// The span to report and the macro the flagged code came from. Spans
+    /// inside a macro expansion point into the macro's definition, often
+    /// in another crate; they are moved to the outermost call site, where
+    /// the user's code invoked the macro.
+    fn resolve_macro_call_site(&self) -> (&DiagnosticSpan, Option<MacroOrigin>) {
+        let Some(expansion) = &self.expansion else { return (self, None) };
+        let definition = expansion.def_site_span.as_ref().filter(|span| !span.file_name.is_empty());
+        let origin = MacroOrigin {
+            name: expansion.macro_decl_name.clone(),
+            definition: definition.map(|span| format!("{}:{}", span.file_name, span.span.line_start)),
+            external: !is_workspace_file(&self.file_name)
+                || definition.is_some_and(|span| !is_workspace_file(&span.file_name)),
+        };
+
+        let mut call_site = &expansion.span;
+        while let Some(outer) = &call_site.expansion {
+            call_site = &outer.span;
+        }
+        let span = if is_workspace_file(&call_site.file_name) { call_site } else { self };
+        (span, Some(origin))
+    }
+}
+
+/// Whether a span's file is workspace code: rustc gives those relative to
+/// the workspace root, and dependencies and the standard library as
+/// absolute paths, paths leaving the root, or `<...>` placeholders.
+pub fn is_workspace_file(file_name: &str) -> bool {
+    let path = std::path::Path::new(file_name);
+    !file_name.starts_with('<')
+        && path.is_relative()
+        && path.components().next() != Some(std::path::Component::ParentDir)
 }
 
 #[derive(Debug)]
@@ -117,47 +165,180 @@ struct BuildScriptInfo {
     output: Option<String>,
 }
 
+/// Parsed warnings, warnings grouped by file, and all analysis context in input order
+pub type ParseResult = (Vec<Warning>, HashMap<String, FileWarnings>, Vec<AnalysisContext>);
+
+/// Minimum number of lines handed to a single worker by the parallel parser
+const PARALLEL_CHUNK_MIN: usize = 1024;
+
+/// Common clippy lints with a known category, by name without the
+/// `clippy::` prefix
+const KNOWN_CLIPPY_LINTS: &[(&str, CategoryType)] = &[
+    ("unwrap_used", CategoryType::Safety),
+    ("expect_used", CategoryType::Safety),
+    ("panic", CategoryType::Safety),
+    ("indexing_slicing", CategoryType::Safety),
+    ("mut_from_ref", CategoryType::Safety),
+    ("not_unsafe_ptr_arg_deref", CategoryType::Safety),
+    ("undocumented_unsafe_blocks", CategoryType::Safety),
+    ("redundant_clone", CategoryType::Performance),
+    ("large_enum_variant", CategoryType::Performance),
+    ("box_collection", CategoryType::Performance),
+    ("vec_box", CategoryType::Performance),
+    ("inefficient_to_string", CategoryType::Performance),
+    ("unnecessary_to_owned", CategoryType::Performance),
+    ("needless_collect", CategoryType::Performance),
+    ("single_char_pattern", CategoryType::Performance),
+    ("needless_borrow", CategoryType::Style),
+    ("needless_return", CategoryType::Style),
+    ("redundant_field_names", CategoryType::Style),
+    ("len_zero", CategoryType::Style),
+    ("new_without_default", CategoryType::Style),
+    ("collapsible_if", CategoryType::Style),
+    ("collapsible_else_if", CategoryType::Style),
+    ("single_match", CategoryType::Style),
+    ("too_many_arguments", CategoryType::Style),
+    ("type_complexity", CategoryType::Style),
+    ("use_self", CategoryType::Style),
+    ("missing_docs_in_private_items", CategoryType::Documentation),
+    ("missing_errors_doc", CategoryType::Documentation),
+    ("missing_panics_doc", CategoryType::Documentation),
+    ("doc_markdown", CategoryType::Documentation),
+];
+
 pub struct WarningParser {
     files: HashMap<String, FileWarnings>,
+    warnings: Vec<Warning>,
+    context: Vec<AnalysisContext>,
+    keep_raw: bool,
+}
+
+impl Default for WarningParser {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl WarningParser {
     pub fn new() -> Self {
         Self {
             files: HashMap::new(),
+            warnings: Vec::new(),
+            context: Vec::new(),
+            keep_raw: false,
         }
     }
 
-    pub fn parse_file(input_path: &str) 
-        -> std::io::Result<(Vec<Warning>, HashMap<String, FileWarnings>, Vec<AnalysisContext>)> 
-    {
-        let file = File::open(input_path)?;
-        let reader = BufReader::new(file);
-        let mut parser = Self::new();
-        let mut warnings = Vec::new();
-        let mut context = Vec::new();
-
-        for line in reader.lines() {
-            if let Ok(line) = line {
-                if let Ok(message) = serde_json::from_str::<CompilerMessage>(&line) {
-                    if let Some(ctx) = parser.parse_compiler_message(message) {
-                        match &ctx {
-                            AnalysisContext::Warning(warning) => {
-                                parser.files
-                                    .entry(warning.file.clone())
-                                    .or_insert_with(|| FileWarnings::new(warning.file.clone()))
-                                    .add_warning(warning.clone());
-                                warnings.push(warning.clone());
-                            }
-                            _ => {}
-                        }
-                        context.push(ctx);
-                    }
-                }
+    /// Attaches each warning's original JSON diagnostic, pretty-printed, as
+    /// `Warning::raw`.
+    pub fn set_keep_raw(&mut self, keep_raw: bool) {
+        self.keep_raw = keep_raw;
+    }
+
+    pub fn parse_file(input_path: &str) -> std::io::Result<ParseResult> {
+        Self::new().parse_path(input_path, 1)
+    }
+
+    /// Parses the clippy JSON stream on `jobs` worker threads.
+    ///
+    /// Lines are split into contiguous chunks that are parsed independently and
+    /// merged back in input order, so the result is identical to `parse_file`.
+    pub fn parse_file_parallel(input_path: &str, jobs: usize) -> std::io::Result<ParseResult> {
+        Self::new().parse_path(input_path, jobs)
+    }
+
+    /// Parses the clippy JSON stream at `input_path` on `jobs` worker threads
+    /// with this parser's settings.
+    pub fn parse_path(self, input_path: &str, jobs: usize) -> std::io::Result<ParseResult> {
+        if jobs <= 1 {
+            return self.parse_reader(BufReader::new(File::open(input_path)?));
+        }
+        let contents = std::fs::read(input_path)?;
+        Ok(self.parse_str(&String::from_utf8_lossy(&contents), jobs))
+    }
+
+    /// Parses clippy JSON output from any reader, e.g. a child's stdout.
+    /// Invalid UTF-8, e.g. in a path printed by a build script, is replaced
+    /// rather than failing the whole parse. A read error is returned, never
+    /// a result cut short at it.
+    pub fn parse_reader<R: BufRead>(mut self, mut reader: R) -> std::io::Result<ParseResult> {
+        let mut line = Vec::new();
+        while read_line_lossy(&mut reader, &mut line)? {
+            self.feed_line(&String::from_utf8_lossy(&line));
+        }
+        Ok(self.finish())
+    }
+
+    /// Parses clippy JSON output held in memory on `jobs` worker threads.
+    pub fn parse_str(mut self, output: &str, jobs: usize) -> ParseResult {
+        let lines: Vec<&str> = output.lines().collect();
+        let pool = (jobs > 1)
+            .then(|| rayon::ThreadPoolBuilder::new().num_threads(jobs).build().ok())
+            .flatten();
+        let Some(pool) = pool else {
+            for line in lines {
+                self.feed_line(line);
             }
+            return self.finish();
+        };
+        let chunk_size = (lines.len() / (jobs * 4)).max(PARALLEL_CHUNK_MIN);
+
+        let chunks: Vec<Vec<AnalysisContext>> = pool.install(|| {
+            lines.par_chunks(chunk_size)
+                .map(|chunk| {
+                    let mut chunk_parser = Self::new();
+                    chunk_parser.keep_raw = self.keep_raw;
+                    chunk.iter()
+                        .filter_map(|line| chunk_parser.parse_line(line))
+                        .collect()
+                })
+                .collect()
+        });
+
+        for ctx in chunks.into_iter().flatten() {
+            self.record(ctx);
+        }
+        self.finish()
+    }
+
+    /// Parses a single line of clippy JSON output as it arrives.
+    ///
+    /// Returns the analysis context produced by the line, if any. Lines that
+    /// are not compiler messages (or not JSON at all) are ignored.
+    pub fn feed_line(&mut self, line: &str) -> Option<&AnalysisContext> {
+        let ctx = self.parse_line(line)?;
+        self.record(ctx);
+        self.context.last()
+    }
+
+    fn parse_line(&mut self, line: &str) -> Option<AnalysisContext> {
+        let message = serde_json::from_str::<CompilerMessage>(line).ok()?;
+        let mut ctx = self.parse_compiler_message(message)?;
+        if let (true, AnalysisContext::Warning(warning)) = (self.keep_raw, &mut ctx) {
+            warning.raw = raw_diagnostic(line);
         }
+        Some(ctx)
+    }
 
-        Ok((warnings, parser.files, context))
+    /// Number of warnings parsed so far.
+    pub fn warning_count(&self) -> usize {
+        self.warnings.len()
+    }
+
+    /// Consumes the parser and returns everything parsed so far.
+    pub fn finish(self) -> ParseResult {
+        (self.warnings, self.files, self.context)
+    }
+
+    fn record(&mut self, ctx: AnalysisContext) {
+        if let AnalysisContext::Warning(warning) = &ctx {
+            self.files
+                .entry(warning.file.clone())
+                .or_insert_with(|| FileWarnings::new(warning.file.clone()))
+                .add_warning(warning.clone());
+            self.warnings.push(warning.clone());
+        }
+        self.context.push(ctx);
     }
 
     fn parse_compiler_message(&mut self, msg: CompilerMessage) -> Option<AnalysisContext> {
@@ -187,24 +368,24 @@ impl WarningParser {
     fn parse_artifact_message(&self, msg: CompilerMessage) -> Option<BuildInfo> {
         let target = msg.target?;
         let package_id = msg.package_id?;
-        let profile = msg.profile?;
+        let profile = msg.profile;
         let manifest_path = msg.manifest_path?;
         let filenames = msg.filenames.unwrap_or_default();
         
         Some(BuildInfo {
-            crate_name: package_id.split('#').next()?.split('/').last()?.to_string(),
+            crate_name: package_id.split('#').next()?.split('/').next_back()?.to_string(),
             features: msg.features.unwrap_or_default(),
             manifest_path,
             filenames,
             config: BuildConfig {
                 edition: target.edition,
                 crate_types: target.crate_types,
-                opt_level: profile.opt_level.clone(),
-                debug: profile.debuginfo > 0,
+                opt_level: profile.as_ref().map(|profile| profile.opt_level.clone()).unwrap_or_default(),
+                debug: profile.as_ref().is_some_and(|profile| profile.debuginfo > 0),
                 test_mode: target.test.unwrap_or(false),
                 is_doc: target.doc.unwrap_or(false),
                 is_doctest: target.doctest.unwrap_or(false),
-                profile: Some(profile),
+                profile,
                 kind: target.kind,
                 name: target.name,
                 src_path: target.src_path,
@@ -213,12 +394,23 @@ impl WarningParser {
     }
 
     fn parse_diagnostic_message(&self, msg: CompilerMessage) -> Option<Warning> {
+        let crate_name = msg.package_id.as_deref().map(crate_name_from_package_id);
         let diagnostic = msg.message?;
-        let span = diagnostic.spans.first()?;
+        let span = diagnostic.spans.iter().find(|s| s.is_primary).or(diagnostic.spans.first())?;
+        let (span, macro_origin) = span.resolve_macro_call_site();
         
-        let clippy_code = diagnostic.code.as_ref()
+        let is_error = diagnostic.level.starts_with("error");
+        let code = diagnostic.code.as_ref()
             .map(|c| c.code.clone())
-            .unwrap_or_else(|| "unknown".to_string());
+            .unwrap_or_else(|| if is_error { "error" } else { "unknown" }.to_string());
+        let source = Source::from_code(&code);
+        let (category, category_confidence) = match source {
+            Source::Clippy => self.categorize_clippy_warning(&code),
+            Source::Rustc if is_error => (CategoryType::Uncategorized, 1.0),
+            Source::Rustc => self.categorize_rustc_diagnostic(&code),
+            Source::Rustfmt => (CategoryType::Style, 1.0),
+            Source::External => categorize_by_name(&code),
+        };
 
         let (suggestion, explanations) = if let Some(ref rendered) = diagnostic.rendered {
             self.parse_clippy_suggestion(rendered)
@@ -231,32 +423,31 @@ impl WarningParser {
             .map(|child| child.message.clone())
             .collect();
 
-        let location = format!(
-            "{}:{}-{}:{}-{}",
-            span.line_start,
-            span.column_start,
-            span.line_end,
-            span.column_end,
-            span.file_name
-        );
-
-        let message = diagnostic.message.clone();
-        
-        Some(Warning {
-            message: format!(
-                "{}\nLocation: {}\nExplanation: {}\nChild messages: {:?}", 
-                message,
-                location,
-                explanations.join("\n"),
-                child_messages
-            ),
-            id: clippy_code.clone(),
+        let mut warning = Warning {
+            lint_code: code,
+            primary_message: diagnostic.message.lines().next().unwrap_or("").to_string(),
+            span: Some(span.span),
+            explanations,
+            children: child_messages,
+            rendered: diagnostic.rendered.clone(),
             file: span.file_name.clone(),
-            line: span.line_start,
-            category: self.categorize_clippy_warning(&clippy_code),
+            line: span.span.line_start,
+            category,
             priority: self.determine_message_priority(&diagnostic),
             suggested_fix: suggestion,
-        })
+            crate_name,
+            source,
+            is_error,
+            category_confidence,
+            raw: None,
+            fingerprint: String::new(),
+            macro_origin,
+            safety_subcategory: None,
+        };
+        if is_future_incompat(&warning.children) {
+            mark_future_incompat(&mut warning);
+        }
+        Some(warning)
     }
 
     fn parse_clippy_suggestion(&self, rendered: &str) -> (Option<String>, Vec<String>) {
@@ -275,13 +466,27 @@ impl WarningParser {
         (suggestion, explanations)
     }
 
-    fn categorize_clippy_warning(&self, code: &str) -> CategoryType {
+    /// Categorizes clippy lints, returning the category and how confident
+    /// the mapping is: lints listed in `KNOWN_CLIPPY_LINTS` are certain,
+    /// name heuristics are guesses, and anything else is uncategorized.
+    fn categorize_clippy_warning(&self, code: &str) -> (CategoryType, f32) {
+        let name = code.trim_start_matches("clippy::");
+        if let Some((_, category)) = KNOWN_CLIPPY_LINTS.iter().find(|(lint, _)| *lint == name) {
+            return (*category, 1.0);
+        }
+        categorize_by_name(name)
+    }
+
+    /// Categorizes rustc lints by name. Hard errors (`E0308`, ...) are
+    /// uncategorized and never reach here.
+    fn categorize_rustc_diagnostic(&self, code: &str) -> (CategoryType, f32) {
         match code {
-            c if c.contains("use_self") || c.contains("redundant") => CategoryType::Style,
-            c if c.contains("unsafe") || c.contains("mut") => CategoryType::Safety,
-            c if c.contains("perf") || c.contains("box") => CategoryType::Performance,
-            c if c.contains("doc") || c.contains("missing") => CategoryType::Documentation,
-            _ => CategoryType::Style,
+            c if c.starts_with("unsafe") || c.contains("unsafe_op") => (CategoryType::Safety, 0.9),
+            c if c.starts_with("missing_docs") || c.starts_with("rustdoc::") => (CategoryType::Documentation, 0.9),
+            c if c.starts_with("large_") || c == "unused_allocation" => (CategoryType::Performance, 0.8),
+            c if c.starts_with("unused_") || c == "dead_code" || c.starts_with("non_") => (CategoryType::Style, 0.9),
+            "unknown" => (CategoryType::Uncategorized, 0.0),
+            _ => (CategoryType::Style, 0.4),
         }
     }
 
@@ -311,6 +516,52 @@ impl WarningParser {
     }
 }
 
+/// Guesses a lint's category from words in its name, with the confidence of
+/// the guess.
+pub(crate) fn categorize_by_name(name: &str) -> (CategoryType, f32) {
+    match name {
+        c if c.contains("use_self") || c.contains("redundant") => (CategoryType::Style, 0.7),
+        c if c.contains("unsafe") || c.contains("mut") => (CategoryType::Safety, 0.6),
+        c if c.contains("perf") || c.contains("box") => (CategoryType::Performance, 0.6),
+        c if c.contains("doc") || c.contains("missing") => (CategoryType::Documentation, 0.6),
+        c if c.starts_with("needless_") || c.starts_with("manual_") => (CategoryType::Style, 0.6),
+        _ => (CategoryType::Uncategorized, 0.0),
+    }
+}
+
+/// Pretty-prints the `message` object of a compiler message line.
+fn raw_diagnostic(line: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    serde_json::to_string_pretty(value.get("message")?).ok()
+}
+
+/// Extracts the package name from a cargo package id.
+///
+/// Handles both the legacy `name version (source)` form and package id specs
+/// such as `path+file:///work/my-crate#my-crate@0.1.0` or `.../my-crate#0.1.0`.
+fn crate_name_from_package_id(package_id: &str) -> String {
+    match package_id.split_once('#') {
+        Some((source, fragment)) => match fragment.split_once('@') {
+            Some((name, _version)) => name.to_string(),
+            None => source.rsplit('/').next().unwrap_or(source).to_string(),
+        },
+        None => package_id.split_whitespace().next().unwrap_or(package_id).to_string(),
+    }
+}
+
+/// Reads the next line of `reader` into `line` without its line ending,
+/// whatever its encoding. Returns false at the end of the input.
+pub fn read_line_lossy<R: BufRead>(reader: &mut R, line: &mut Vec<u8>) -> std::io::Result<bool> {
+    line.clear();
+    if reader.read_until(b'\n', line)? == 0 {
+        return Ok(false);
+    }
+    while line.last().is_some_and(|&b| b == b'\n' || b == b'\r') {
+        line.pop();
+    }
+    Ok(true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,6 +590,34 @@ mod tests {
         }
     }
 
+
+    #[test]
+    fn test_parse_reader_invalid_utf8() {
+        let diagnostic = r#"{"reason":"compiler-message","package_id":"demo 0.1.0","message":{"message":"unused variable: `x`","code":{"code":"unused_variables"},"level":"warning","spans":[{"file_name":"src/lib.rs","line_start":1,"line_end":1,"column_start":5,"column_end":6,"is_primary":true}],"children":[],"rendered":null}}"#;
+        let mut input = b"build script said \xff\xfe\n".to_vec();
+        input.extend_from_slice(diagnostic.as_bytes());
+        input.extend_from_slice(b"\r\n");
+        let (warnings, _, _) = WarningParser::new().parse_reader(&input[..]).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].lint_code, "unused_variables");
+    }
+
+    #[test]
+    fn test_parse_reader_read_error() {
+        // One line, then the pipe breaks
+        struct Broken(bool);
+        impl std::io::Read for Broken {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                if std::mem::replace(&mut self.0, true) {
+                    return Err(std::io::Error::other("pipe closed"));
+                }
+                buf[..3].copy_from_slice(b"{}\n");
+                Ok(3)
+            }
+        }
+        let error = WarningParser::new().parse_reader(BufReader::new(Broken(false))).unwrap_err();
+        assert_eq!(error.to_string(), "pipe closed");
+    }
     #[test]
     fn test_parse_diagnostic_message() {
         let json = r#"{
@@ -366,8 +645,122 @@ mod tests {
             assert_eq!(warning.file, "src/main.rs");
             assert_eq!(warning.line, 10);
             assert_eq!(warning.category, CategoryType::Style);
+            assert_eq!(warning.source, Source::Rustc);
+            assert!(!warning.is_error);
+            assert_eq!(warning.raw, None);
+        } else {
+            panic!("Expected Warning variant");
+        }
+    }
+
+    #[test]
+    fn test_macro_expansion_call_site() {
+        let json = r#"{
+            "reason": "compiler-message",
+            "message": {
+                "code": {"code": "clippy::unwrap_used"},
+                "level": "warning",
+                "message": "used `unwrap()` on a `Result` value",
+                "spans": [{
+                    "file_name": "/home/user/.cargo/registry/src/helpers-1.0.0/src/lib.rs",
+                    "line_start": 40, "line_end": 40, "column_start": 9, "column_end": 30,
+                    "is_primary": true,
+                    "expansion": {
+                        "span": {
+                            "file_name": "src/config.rs",
+                            "line_start": 12, "line_end": 12, "column_start": 5, "column_end": 25
+                        },
+                        "macro_decl_name": "load!",
+                        "def_site_span": {
+                            "file_name": "/home/user/.cargo/registry/src/helpers-1.0.0/src/lib.rs",
+                            "line_start": 35, "line_end": 42, "column_start": 1, "column_end": 2
+                        }
+                    }
+                }],
+                "children": [],
+                "rendered": null
+            }
+        }"#;
+
+        let msg: CompilerMessage = serde_json::from_str(json).unwrap();
+        let Some(AnalysisContext::Warning(warning)) = WarningParser::new().parse_compiler_message(msg) else {
+            panic!("Expected Warning variant");
+        };
+        assert_eq!((warning.file.as_str(), warning.line), ("src/config.rs", 12));
+        let origin = warning.macro_origin.unwrap();
+        assert_eq!(origin.name, "load!");
+        assert_eq!(origin.definition.as_deref(), Some("/home/user/.cargo/registry/src/helpers-1.0.0/src/lib.rs:35"));
+        assert!(origin.external);
+    }
+
+    #[test]
+    fn test_parse_compile_error() {
+        let json = r#"{"reason":"compiler-message","message":{"code":{"code":"E0308"},"level":"error","message":"mismatched types","spans":[{"file_name":"src/lib.rs","line_start":3,"line_end":3,"column_start":5,"column_end":9}],"children":[],"rendered":null}}"#;
+
+        let msg: CompilerMessage = serde_json::from_str(json).unwrap();
+        let mut parser = WarningParser::new();
+
+        if let Some(AnalysisContext::Warning(warning)) = parser.parse_compiler_message(msg) {
+            assert_eq!(warning.lint_code, "E0308");
+            assert_eq!(warning.source, Source::Rustc);
+            assert!(warning.is_error);
+            assert_eq!(warning.priority, Priority::Critical);
+            assert_eq!(warning.category, CategoryType::Uncategorized);
+            assert!(!warning.is_low_confidence());
         } else {
             panic!("Expected Warning variant");
         }
     }
-} 
+
+    #[test]
+    fn test_primary_span_with_byte_offsets() {
+        let json = r#"{"reason":"compiler-message","message":{"code":{"code":"clippy::len_zero"},"level":"warning","message":"length comparison to zero","spans":[{"file_name":"src/lib.rs","byte_start":10,"byte_end":14,"line_start":1,"line_end":1,"column_start":11,"column_end":15,"is_primary":false},{"file_name":"src/lib.rs","byte_start":40,"byte_end":52,"line_start":3,"line_end":4,"column_start":8,"column_end":2,"is_primary":true}],"children":[],"rendered":null}}"#;
+
+        let msg: CompilerMessage = serde_json::from_str(json).unwrap();
+        let Some(AnalysisContext::Warning(warning)) = WarningParser::new().parse_compiler_message(msg) else {
+            panic!("Expected Warning variant");
+        };
+        let span = warning.span.unwrap();
+        assert_eq!(warning.line, 3);
+        assert_eq!((span.line_start, span.column_start, span.line_end, span.column_end), (3, 8, 4, 2));
+        assert_eq!(span.byte_range(), Some(40..52));
+
+        // The offsets survive the JSON export, and are left out when unknown
+        let exported = serde_json::to_value(&warning).unwrap();
+        assert_eq!(exported["span"]["byte_start"], 40);
+        let read_back: Warning = serde_json::from_value(exported).unwrap();
+        assert_eq!(read_back.span, Some(span));
+        let unknown = serde_json::to_value(Span::at(3, 8)).unwrap();
+        assert!(unknown.get("byte_start").is_none() && unknown.get("byte_end").is_none());
+    }
+
+    #[test]
+    fn test_category_confidence() {
+        let parser = WarningParser::new();
+        assert_eq!(parser.categorize_clippy_warning("clippy::unwrap_used"), (CategoryType::Safety, 1.0));
+        assert_eq!(parser.categorize_clippy_warning("clippy::needless_range_loop"), (CategoryType::Style, 0.6));
+        assert_eq!(parser.categorize_clippy_warning("clippy::zst_offset"), (CategoryType::Uncategorized, 0.0));
+        assert_eq!(parser.categorize_rustc_diagnostic("unused_variables"), (CategoryType::Style, 0.9));
+    }
+
+    #[test]
+    fn test_parallel_parse_matches_sequential() {
+        let line = |i: usize| format!(
+            r#"{{"reason":"compiler-message","message":{{"code":{{"code":"clippy::lint_{}"}},"level":"warning","message":"warning {}","spans":[{{"file_name":"src/f{}.rs","line_start":{},"line_end":{},"column_start":1,"column_end":2}}],"children":[],"rendered":null}}}}"#,
+            i % 7, i, i % 13, i + 1, i + 1
+        );
+        let input: Vec<String> = (0..5000).map(line).collect();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("parallel_parse_test.json");
+        std::fs::write(&path, input.join("\n")).unwrap();
+        let path_str = path.to_str().unwrap();
+
+        let (sequential, seq_files, _) = WarningParser::parse_file(path_str).unwrap();
+        let (parallel, par_files, _) = WarningParser::parse_file_parallel(path_str, 4).unwrap();
+
+        assert_eq!(sequential.len(), 5000);
+        assert_eq!(seq_files.len(), par_files.len());
+        let key = |w: &Warning| (w.lint_code.clone(), w.file.clone(), w.line);
+        assert!(sequential.iter().map(key).eq(parallel.iter().map(key)));
+    }
+}
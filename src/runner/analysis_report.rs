@@ -0,0 +1,125 @@
+//! The result of a full analysis run.
+//!
+//! [`AnalysisRunner::run`] and [`AnalysisRunner::analyze`] write their
+//! reports to disk and also return what they found, so embedders can act on
+//! the warnings, statistics, and gate verdict without reading the files back.
+//! A runner built with `in_memory(true)` writes nothing at all: the reports
+//! are kept in [`AnalysisReport::artifacts`] under the paths they would have
+//! been written to, and the caller decides what to do with them.
+//!
+//! [`AnalysisRunner::run`]: super::analysis_runner::AnalysisRunner::run
+//! [`AnalysisRunner::analyze`]: super::analysis_runner::AnalysisRunner::analyze
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use crate::{
+    analysis::{alerts::Alert, gate::GateVerdict, statistics::warning::WarningStatistics},
+    types::{Completeness, Warning},
+};
+
+/// How far an analysis run got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// Warnings were analyzed and the enabled reports written.
+    Analyzed,
+    /// No warnings; the clean run reports were written.
+    Clean,
+    /// No warnings on the changed lines or in the changed files; nothing
+    /// was written.
+    NoChangedWarnings,
+    /// The statistics did not add up; nothing was written.
+    CountMismatch,
+}
+
+/// What an analysis run found and wrote.
+#[derive(Debug)]
+pub struct AnalysisReport {
+    pub outcome: RunOutcome,
+    /// Warnings left after exclusion, scoping, and ignore rules
+    pub warnings: Vec<Warning>,
+    pub stats: WarningStatistics,
+    pub completeness: Completeness,
+    /// Verdict of the configured gate; passes when no thresholds are set
+    pub verdict: GateVerdict,
+    /// Alert rules that fired
+    pub alerts: Vec<Alert>,
+    /// Files written, by report name, e.g. `("summary", path)`
+    pub files: Vec<(String, PathBuf)>,
+    /// Contents of the files of an in-memory run, by path; empty when the
+    /// reports went to disk
+    pub artifacts: BTreeMap<PathBuf, Vec<u8>>,
+    pub reports_dir: PathBuf,
+    /// One-line result, which the CLI prints in quiet mode
+    pub summary: String,
+}
+
+impl AnalysisReport {
+    /// Path of the report named `name`, if it was written.
+    pub fn file(&self, name: &str) -> Option<&Path> {
+        self.files.iter().find(|(n, _)| n == name).map(|(_, path)| path.as_path())
+    }
+
+    /// Contents of the report named `name` of an in-memory run.
+    pub fn contents(&self, name: &str) -> Option<&[u8]> {
+        self.artifacts.get(self.file(name)?).map(Vec::as_slice)
+    }
+
+    /// Whether the gate passed and no alert fired.
+    pub fn passed(&self) -> bool {
+        self.verdict.passed() && self.alerts.is_empty()
+    }
+}
+
+/// Report contents of an in-memory run, by path.
+pub(crate) type ArtifactStore = Arc<Mutex<BTreeMap<PathBuf, Vec<u8>>>>;
+
+/// A report file being written: on disk, or buffered and added to the
+/// run's artifacts when dropped.
+pub(crate) enum ReportFile {
+    Disk(File),
+    Memory { path: PathBuf, buffer: Vec<u8>, store: ArtifactStore },
+}
+
+impl ReportFile {
+    /// Creates the file at `path`, and its directory, or a buffer for it in
+    /// `store`.
+    pub(crate) fn create(path: PathBuf, store: Option<&ArtifactStore>) -> io::Result<Self> {
+        match store {
+            Some(store) => Ok(ReportFile::Memory { path, buffer: Vec::new(), store: Arc::clone(store) }),
+            None => {
+                if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(dir)?;
+                }
+                File::create(path).map(ReportFile::Disk)
+            }
+        }
+    }
+}
+
+impl Write for ReportFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            ReportFile::Disk(file) => file.write(buf),
+            ReportFile::Memory { buffer, .. } => buffer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            ReportFile::Disk(file) => file.flush(),
+            ReportFile::Memory { .. } => Ok(()),
+        }
+    }
+}
+
+impl Drop for ReportFile {
+    fn drop(&mut self) {
+        if let ReportFile::Memory { path, buffer, store } = self {
+            let contents = std::mem::take(buffer);
+            store.lock().unwrap_or_else(|e| e.into_inner()).insert(std::mem::take(path), contents);
+        }
+    }
+}
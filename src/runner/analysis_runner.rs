@@ -1,24 +1,103 @@
-use std::fs::{File, OpenOptions};
+use std::fs::File;
 use std::collections::HashMap;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use termcolor::ColorChoice;
 use crate::{
-    parser::{WarningParser, AnalysisContext},
-    types::{Warning, FileWarnings, CategoryType},
+    config::AnalyzerConfig,
+    fixes::{
+        examples::FixExample,
+        post_process::{SuggestionPipeline, SuggestionPostProcessor},
+        registry::FixExampleRegistry,
+    },
+    parser::{WarningParser, AnalysisContext, ParseResult},
+    types::{Warning, FileWarnings, CategoryType, Completeness, Priority, Source},
     analysis::{
-        trends::TrendAnalysis,
-        statistics::warning::WarningStatistics,
+        trends::{TrendAnalysis, recurring_issues},
+        charts::{ChartConfig, ChartStyle, create_enhanced_chart},
+        baseline::{Baseline, BaselineFile, BaselineMeta, BaselineSource, BaselineStore},
+        fingerprint::{Fingerprinter, diff_fingerprints, fingerprint_counts},
+        renames::RenameMap,
+        statistics::delta::{CountDelta, StatsDelta},
+        statistics::warning::{WarningStatistics, LOW_CONFIDENCE_LINTS_SHOWN},
+        alerts::{Alert, evaluate_alerts},
+        gate::{GateVerdict, evaluate_gate},
+        hotspots::rank_hotspots,
+        clusters::cluster_warnings,
+        dependencies::UnusedDependencies,
+        hidden_code::HiddenCodeReport,
+        impact::BuildImpact,
+        heatmap::HeatTable,
+        module_tree::ModuleTree,
+        unsafe_usage::GeigerReport,
+        scope::PathScope,
+        pipeline::{Analysis, AnalysisOptions, analyze},
     },
     output::{
         color::ColorWriter,
-        report::{write_warning_report, write_colored_section},
-        markdown::generate_markdown_report,
+        log::Logger,
+        report::{write_warning_report, write_colored_section, write_error_section, write_generated_appendix, write_suppressed_appendix},
+        markdown::{MarkdownWriter, generate_clean_markdown_report, generate_markdown_report},
+        html::{HtmlTheme, HtmlWriter, generate_clean_html_report, generate_html_report, write_file_sections_jsonl},
+        template::{TemplateContext, TemplateFormat, render_template},
         fix_plan::FixPlanGenerator,
+        merge_queue::{MergeQueueStatus, write_merge_queue_status},
+        playbook::{Playbook, write_playbook},
+        audit_notes::{AuditNotes, write_audit_notes},
+        binary_export::{WarningSet, write_binary_export},
+        csv_export::write_csv,
+        json_export::{write_json, write_jsonl},
+        rendered_appendix::write_rendered_appendix,
+        run_manifest::{RUN_MANIFEST_FILE, RunManifest, RunMetadata, write_manifest, write_run_manifest},
+        sarif::write_sarif,
+        svg_charts::SvgCharts,
+        badge::{BADGE_ENDPOINT_FILE, BADGE_SVG_FILE, Badge, write_badge_svg, write_shields_endpoint},
+        bundle::Artifact,
+        formats::ReportFormat,
+        notification::RunSummary,
+        pr_summary::{CommentBase, PR_COMMENT_FILE, PrComment, write_pr_comment},
+        prometheus::{PROMETHEUS_FILE, write_prometheus, write_prometheus_file},
+        baseline_report::{short_commit, write_baseline_report},
+        false_positive_report::write_false_positive_report,
+        diff::DiffLayout,
+        preview::FixPreview,
+        hyperlink::{EditorLinks, osc8},
+        snippet::{DEFAULT_CONTEXT_LINES, Snippet, SnippetReader},
+        progress::{NoProgress, ProgressReporter, Stage, default_progress_reporter},
+    },
+    runner::{
+        cargo::{cargo_command, workspace_target_dir},
+        analysis_report::{AnalysisReport, ArtifactStore, ReportFile, RunOutcome},
+        check::CheckOutcome,
+        events::{AnalysisEvents, NoEvents},
+        git,
+        import::HISTORICAL_TRENDS_FILE,
+        notify::notify_webhook,
+        repro::{REPRO_FILE, ReproInfo, cargo_version, toolchain_versions, write_repro_script},
     },
 };
 
-/// Provides the core analysis runner implementation for processing Clippy warnings.
+#[cfg(feature = "sqlite")]
+use crate::analysis::{fingerprint::{fingerprint_churn, follow_renamed}, history::{HistoryDb, RunRecord}};
+
+/// Number of previous runs loaded from the history database for trends
+pub const HISTORY_TREND_RUNS: usize = 30;
+
+/// Fix examples previewed in the terminal with `--preview-fixes`
+const MAX_FIX_PREVIEWS: usize = 3;
+
+/// Width of the longest bar in the terminal priority chart
+const PRIORITY_CHART_WIDTH: usize = 30;
+
+/// Warnings quoted in the terminal with `--snippets`
+const MAX_TERMINAL_SNIPPETS: usize = 3;
+
+/// Warnings quoted in the HTML summary
+const MAX_HTML_SNIPPETS: usize = 10;
 
+/// Provides the core analysis runner implementation for processing Clippy warnings.
+///
 /// Main struct responsible for executing the analysis workflow and generating reports.
 /// 
 /// The `AnalysisRunner` coordinates the entire analysis process, including:
@@ -29,35 +108,67 @@ use crate::{
 /// 
 /// # Example
 /// 
-/// ```rust
-/// use cargo_analyzer::runner::analysis_runner::AnalysisRunner;
+/// ```rust,no_run
+/// use cargo_analyzer::output::bundle::Artifact;
+/// use cargo_analyzer::runner::{AnalysisRunner, HistoryBackend};
 /// 
-/// let runner = AnalysisRunner::new();
-/// runner.run().expect("Analysis failed");
+/// let mut runner = AnalysisRunner::builder()
+///     .reports_dir("target/clippy-reports")
+///     .artifacts(vec![Artifact::Summary, Artifact::Json])
+///     .history(HistoryBackend::Database("history.db".into()))
+///     .ignore("clippy::module_name_repetitions")
+///     .build()
+///     .expect("Invalid runner configuration");
+/// let report = runner.run("clippy.json").expect("Analysis failed");
+/// println!("{} warnings", report.stats.total_warnings);
 /// ```
 pub struct AnalysisRunner {
     color_writer: ColorWriter,
     timestamp: String,
     reports_dir: Option<PathBuf>,
-    debug_log: std::io::BufWriter<File>,
+    /// Root of the analyzed workspace, which warning paths are relative to
+    working_dir: PathBuf,
+    log: Logger,
+    config: AnalyzerConfig,
+    jobs: usize,
+    progress: Box<dyn ProgressReporter>,
+    events: Box<dyn AnalysisEvents>,
+    completeness: Completeness,
+    repro: Option<ReproInfo>,
+    scope: Option<PathScope>,
+    post_processors: Vec<Arc<dyn SuggestionPostProcessor>>,
+    unused_deps: Option<UnusedDependencies>,
+    hidden_code: Option<HiddenCodeReport>,
+    geiger: Option<GeigerReport>,
+    /// Size and compile time measurements ranking performance warnings
+    build_impact: Option<BuildImpact>,
+    fix_examples: FixExampleRegistry,
+    /// Categories a playbook was written for, by slug
+    playbooks: Vec<String>,
+    /// Whether the HTML summary loads per-file sections from a companion file
+    lazy_summary: bool,
+    /// Whether a trend chart was written alongside the distribution charts
+    trend_chart: bool,
+    /// Fingerprint counts of the last recorded run, read with the history
+    previous_fingerprints: Option<HashMap<String, usize>>,
+    /// Whether the run is recorded in the history database and baseline
+    /// store; off when re-rendering saved warnings
+    record_run: bool,
+    /// Where the reports of an in-memory run are kept instead of on disk
+    artifacts: Option<ArtifactStore>,
+    /// One-line result of the last run, printed by the CLI in quiet mode
+    summary: Option<String>,
 }
 
 impl AnalysisRunner {
+    /// Starts configuring a runner.
+    pub fn builder() -> AnalysisRunnerBuilder {
+        AnalysisRunnerBuilder::new()
+    }
+
     /// Creates a new instance of the analysis runner with default configuration.
     pub fn new() -> std::io::Result<Self> {
-        let debug_log = std::io::BufWriter::new(
-            OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open("clippy_analyzer_debug.log")?
-        );
-
-        Ok(Self {
-            color_writer: ColorWriter::new(),
-            timestamp: chrono::Local::now().format("%Y%m%d_%H%M%S").to_string(),
-            reports_dir: None,
-            debug_log,
-        })
+        Self::builder().build()
     }
 
     /// Creates a new instance with a custom reports directory.
@@ -66,30 +177,116 @@ impl AnalysisRunner {
     /// 
     /// * `reports_dir` - Path to the directory where reports will be stored
     pub fn new_with_reports_dir(reports_dir: Option<PathBuf>) -> std::io::Result<Self> {
-        let debug_log = std::io::BufWriter::new(
-            OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open("clippy_analyzer_debug.log")?
-        );
+        let mut builder = Self::builder();
+        builder.reports_dir = reports_dir;
+        builder.build()
+    }
 
-        Ok(Self {
-            color_writer: ColorWriter::new(),
-            timestamp: chrono::Local::now().format("%Y%m%d_%H%M%S").to_string(),
-            reports_dir,
-            debug_log,
-        })
+    /// Creates a runner for embedding that writes nothing to the terminal.
+    ///
+    /// Progress, parsed warnings, and generated reports are reported through
+    /// `events` instead.
+    pub fn with_events(
+        reports_dir: Option<PathBuf>,
+        events: Box<dyn AnalysisEvents>,
+    ) -> std::io::Result<Self> {
+        let mut builder = Self::builder().events(events);
+        builder.reports_dir = reports_dir;
+        builder.build()
     }
 
     pub fn set_timestamp(&mut self, timestamp: &str) {
         self.timestamp = timestamp.to_string();
     }
 
+    /// Sets the root of the analyzed workspace (the current directory by
+    /// default), where clippy and git run and sources are read.
+    pub fn set_working_dir(&mut self, working_dir: PathBuf) {
+        self.working_dir = working_dir;
+    }
+
+    pub fn set_config(&mut self, config: AnalyzerConfig) {
+        self.config = config;
+    }
+
+    /// Sets the number of threads used to parse the clippy output.
+    pub fn set_jobs(&mut self, jobs: usize) {
+        self.jobs = jobs.max(1);
+    }
+
+    /// Replaces the progress reporter, e.g. to continue the workflow's display.
+    pub fn set_progress_reporter(&mut self, progress: Box<dyn ProgressReporter>) {
+        self.progress = progress;
+    }
+
+    /// Replaces the diagnostic logger. A quiet logger also silences terminal
+    /// output; only the [summary line](Self::summary) is left to show.
+    pub fn set_logger(&mut self, log: Logger) {
+        if log.verbosity().is_quiet() {
+            self.color_writer = ColorWriter::silent();
+        }
+        self.log = log;
+    }
+
+    /// Marks the input as partial so every report carries an incomplete notice.
+    pub fn set_completeness(&mut self, completeness: Completeness) {
+        self.completeness = completeness;
+    }
+
+    /// Emits a reproduction script for the clippy run alongside the reports.
+    pub fn set_repro_info(&mut self, repro: ReproInfo) {
+        self.repro = Some(repro);
+    }
+
+    /// Leaves the run out of the history database and baseline store, for
+    /// reports re-rendered from saved warnings.
+    pub fn set_record_run(&mut self, record: bool) {
+        self.record_run = record;
+    }
+
+    /// Restricts statistics and reports to warnings within `scope`.
+    pub fn set_scope(&mut self, scope: PathScope) {
+        self.scope = Some(scope);
+    }
+
+    /// Applies `processor` to fix suggestions after the built-ins enabled in
+    /// the config.
+    pub fn add_suggestion_post_processor(&mut self, processor: Arc<dyn SuggestionPostProcessor>) {
+        self.post_processors.push(processor);
+    }
+
+    /// Reports these unused dependencies, e.g. from cargo-udeps, instead of
+    /// those found by the `unused_crate_dependencies` lint.
+    pub fn set_unused_dependencies(&mut self, unused: UnusedDependencies) {
+        self.unused_deps = Some(unused);
+    }
+
+    /// Reports the ignored tests and inactive cfg items of the workspace.
+    pub fn set_hidden_code(&mut self, report: HiddenCodeReport) {
+        self.hidden_code = Some(report);
+    }
+
+    /// Uses cargo-geiger's unsafe counts when unsafe metrics are enabled.
+    pub fn set_geiger_report(&mut self, report: GeigerReport) {
+        self.geiger = Some(report);
+    }
+
+    /// Annotates performance warnings with the measured size and compile
+    /// time of their code.
+    pub fn set_build_impact(&mut self, impact: BuildImpact) {
+        self.build_impact = Some(impact);
+    }
+
+    /// Replaces the event callbacks.
+    pub fn set_events(&mut self, events: Box<dyn AnalysisEvents>) {
+        self.events = events;
+    }
+
     /// Executes the complete analysis workflow.
     /// 
     /// # Returns
     /// 
-    /// Returns `Result<(), Error>` indicating success or failure of the analysis
+    /// Returns what the analysis found and which reports it wrote
     /// 
     /// # Errors
     /// 
@@ -97,104 +294,800 @@ impl AnalysisRunner {
     /// - File parsing fails
     /// - Report generation fails
     /// - Output directory is not writable
-    pub fn run(&mut self, input_path: &str) -> std::io::Result<()> {
+    pub fn run(&mut self, input_path: &str) -> std::io::Result<AnalysisReport> {
         self.debug_log("Starting Clippy Analyzer")?;
         
-        // Remove screen clearing
-        // print!("\x1B[2J\x1B[1;1H");
-        
-        self.color_writer.write_header("Clippy Analyzer")?;
-        
         // Create timestamped input file in reports directory
         self.debug_log("Creating output file...")?;
         let (mut output_file, file_path) = self.create_output_file("output")?;
         
         // Run clippy and capture its output
-        let output = std::process::Command::new("cargo")
-            .current_dir(std::env::current_dir()?)
+        let output = cargo_command(&self.working_dir)
             .args(["clippy", "--message-format=json"])
             .output()?;
             
-        // Write clippy output to our file
+        // Write clippy output to our file, closed so an in-memory run keeps it
         output_file.write_all(&output.stdout)?;
+        drop(output_file);
         
         let input_path = file_path.to_str().unwrap_or(input_path);
-        self.debug_log(&format!("Analyzing input file: {}", input_path))?;
-        writeln!(self.color_writer.writer(), "\nAnalyzing {}...\n", input_path)?;
 
         // Parse warnings and context
-        let (warnings, file_warnings, context) = match WarningParser::parse_file(input_path) {
-            Ok((w, fw, ctx)) => (w, fw, ctx),
+        self.phase_started(Stage::Parsing);
+        let mut parser = WarningParser::new();
+        parser.set_keep_raw(self.config.embed_raw);
+        let parsed = if self.artifacts.is_some() {
+            Ok(parser.parse_str(&String::from_utf8_lossy(&output.stdout), self.jobs))
+        } else {
+            parser.parse_path(input_path, self.jobs)
+        };
+        let parsed = match parsed {
+            Ok(parsed) => {
+                self.phase_finished(Stage::Parsing, &format!("{} warnings", parsed.0.len()));
+                parsed
+            }
             Err(e) => {
                 self.color_writer.write_error(&format!("Failed to parse file: {}", e))?;
-                return Ok(());
+                return Err(e);
             }
         };
+        // Compile errors fail the build but are reported like warnings
+        if !output.status.success() && !parsed.0.iter().any(|w| w.is_error) {
+            self.completeness = Completeness::Partial {
+                reason: format!("clippy failed ({})", output.status),
+                lines_processed: output.stdout.split(|&b| b == b'\n').filter(|line| !line.is_empty()).count(),
+            };
+        }
+
+        self.analyze(input_path, parsed)
+    }
+
+    /// Runs statistics and report generation over already parsed clippy output.
+    ///
+    /// `input_path` is the raw clippy output the results were parsed from; it is
+    /// only used for display.
+    pub fn analyze(&mut self, input_path: &str, parsed: ParseResult) -> std::io::Result<AnalysisReport> {
+        self.color_writer.write_header("Clippy Analyzer")?;
+
+        self.debug_log(&format!("Analyzing input file: {}", input_path))?;
+        match &self.scope {
+            Some(scope) => writeln!(
+                self.color_writer.writer(),
+                "\nAnalyzing {} (scoped to {})...\n",
+                input_path,
+                scope.path()
+            )?,
+            None => writeln!(self.color_writer.writer(), "\nAnalyzing {}...\n", input_path)?,
+        }
+
+        if !self.completeness.is_complete() {
+            self.color_writer.write_warning(&format!("⚠️  {}\n", self.completeness))?;
+        }
+
+        self.phase_started(Stage::Analyzing);
+        let mut options = self.analysis_options()?;
+        options.source_root = Some(self.working_dir.clone());
+        options.generated.set_target_dir(workspace_target_dir(&self.working_dir));
+        let analysis = self.run_pipeline(parsed, &options)?;
+        let Analysis { warnings, file_warnings, context, generated, third_party, suppressed, false_positives, stats, pre_existing, untouched, .. } = analysis;
+
+        if let Some(changed) = options.changed_lines.as_ref().filter(|changed| changed.whole_files) {
+            writeln!(
+                self.color_writer.writer(),
+                "🔀 {} files changed since {}: {} warnings; {} in untouched files\n",
+                changed.file_count(),
+                changed.base,
+                warnings.len(),
+                untouched
+            )?;
+            if warnings.is_empty() {
+                self.phase_finished(Stage::Analyzing, "no warnings in changed files");
+                self.color_writer.write_success("✅ No warnings in changed files\n")?;
+                self.set_summary("no warnings in changed files");
+                return self.finish(RunOutcome::NoChangedWarnings, input_path, warnings, stats, Vec::new());
+            }
+        } else if let Some(changed) = &options.changed_lines {
+            writeln!(
+                self.color_writer.writer(),
+                "🔀 Lines changed since {} in {} files: {} warnings; {} pre-existing in touched files, {} in untouched files\n",
+                changed.base,
+                changed.file_count(),
+                warnings.len(),
+                pre_existing.len(),
+                untouched
+            )?;
+            if warnings.is_empty() {
+                self.phase_finished(Stage::Analyzing, "no warnings on changed lines");
+                self.color_writer.write_success("✅ No warnings on changed lines\n")?;
+                self.set_summary(&format!("no warnings on changed lines ({} pre-existing)", pre_existing.len()));
+                return self.finish(RunOutcome::NoChangedWarnings, input_path, warnings, stats, Vec::new());
+            }
+        }
 
         if warnings.is_empty() {
-            self.color_writer.write_error("No valid warnings were parsed from the input file!")?;
-            return Ok(());
+            self.phase_finished(Stage::Analyzing, "no warnings");
+            let written = self.write_clean_run(&stats, &context)?;
+            let files = written.into_iter().map(|(name, path)| (name.to_string(), path)).collect();
+            return self.finish(RunOutcome::Clean, input_path, warnings, stats, files);
+        }
+
+        for warning in &warnings {
+            self.events.on_warning_parsed(warning);
         }
 
-        // Generate statistics and validate
-        let stats = WarningStatistics::from_warnings(&warnings, file_warnings.len());
+        self.phase_finished(
+            Stage::Analyzing,
+            &format!("{} warnings in {} files", stats.total_warnings, stats.files_affected),
+        );
         
         // Validate warning counts
         let total_by_category: usize = stats.by_category.values().sum();
         let total_by_priority: usize = stats.by_priority.values().sum();
         if total_by_category != stats.total_warnings || total_by_priority != stats.total_warnings {
             self.color_writer.write_error("Warning count mismatch detected in analysis!\n")?;
-            return Ok(());
+            return self.finish(RunOutcome::CountMismatch, input_path, warnings, stats, Vec::new());
+        }
+
+        self.fix_examples = FixExampleRegistry::from_config(&self.config.fix_templates)?;
+        if !self.fix_examples.is_empty() {
+            self.debug_log(&format!("Loaded {} user fix templates", self.fix_examples.len()))?;
         }
 
         // Show summary immediately
         self.write_terminal_summary(&stats)?;
+        if let Some(layout) = self.config.preview_fixes {
+            self.write_fix_previews(&warnings, layout)?;
+        }
+        if self.config.snippets {
+            self.write_snippets(&warnings)?;
+        }
+
+        let mut baseline = match self.config.auto_baseline.clone() {
+            Some(branch) => Some(self.load_baseline(&branch)?),
+            None => None,
+        };
+        if let Some(Ok(baseline)) = &mut baseline {
+            let (renames, renamed) = self.renames_since(&baseline.commit, &warnings)?;
+            baseline.follow_renames(&renames, &renamed);
+        }
+        match &baseline {
+            Some(Ok(baseline)) => self.write_baseline_summary(baseline, &baseline.delta(&stats), &warnings)?,
+            Some(Err(reason)) => self.color_writer.write_warning(&format!("⚠️  {}\n\n", reason))?,
+            None => {}
+        }
+
+        let historical_trends = self.update_history(&warnings, &stats)?;
+        let alerts = evaluate_alerts(&self.config.alert_if, &stats, &historical_trends, chrono::Local::now().date_naive());
+        if !alerts.is_empty() {
+            self.write_alerts(&alerts)?;
+        }
+
+        let verdict = evaluate_gate(&self.config.gate, &stats);
+        if !self.config.gate.is_empty() {
+            self.write_gate_verdict(&verdict)?;
+        }
 
         // Generate reports silently
-        let (mut report_file, _) = self.create_output_file("report")?;
-        let (mut summary_file, _) = self.create_output_file("summary")?;
-        let (mut analysis_file, _) = self.create_output_file("analysis")?;
-        let (mut json_file, _) = self.create_output_file("warnings_json")?;
-        let (mut csv_file, _) = self.create_output_file("warnings_csv")?;
-        let (mut fix_plan_file, _) = self.create_output_file("fix_plan")?;
+        self.phase_started(Stage::GeneratingReports);
+        let mut report_file = self.create_artifact_file(Artifact::Report, "report")?;
+        let mut summary_file = self.create_artifact_file(Artifact::Summary, "summary")?;
+        let mut analysis_file = self.create_artifact_file(Artifact::Analysis, "analysis")?;
+        let mut json_file = self.create_artifact_file(Artifact::Json, "warnings_json")?;
+        let mut csv_file = self.create_artifact_file(Artifact::Csv, "warnings_csv")?;
+        let mut fix_plan_file = self.create_artifact_file(Artifact::FixPlan, "fix_plan")?;
+
+        if !self.completeness.is_complete() {
+            for (file, _) in [&mut report_file, &mut analysis_file, &mut fix_plan_file].into_iter().flatten() {
+                writeln!(file, "> ⚠️ **{}**\n", self.completeness)?;
+            }
+        }
+
+        let mut trend = TrendAnalysis::new(
+            stats.total_warnings,
+            stats.by_category.clone(),
+            stats.by_priority.clone(),
+            recurring_issues(&warnings),
+        );
+        let historical_totals: Vec<usize> = historical_trends.iter().map(|t| t.total_warnings).collect();
+        trend.calculate_improvement_rate(&historical_totals);
+        trend.calculate_weighted_improvement(&historical_trends);
+        trend.by_file = stats.by_file.clone();
+        if let Some(previous) = &self.previous_fingerprints {
+            trend.set_fingerprint_churn(previous, &fingerprint_counts(&warnings));
+        }
 
-        let historical_trends = self.load_historical_trends()?;
-        let trend_analysis = TrendAnalysis::default();
-        let trend = historical_trends.last().unwrap_or(&trend_analysis);
+        let charts = SvgCharts::new(&stats, &historical_trends);
+        let chart_files = if self.config.writes(Artifact::Charts) { self.write_svg_charts(&charts)? } else { Vec::new() };
+        self.trend_chart = chart_files.iter().any(|(name, _, _)| *name == "trend_chart");
+        let chart_images: Vec<(&str, String)> = chart_files
+            .iter()
+            .map(|(_, alt, path)| {
+                let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                (*alt, file_name)
+            })
+            .collect();
 
+        let inputs = ReportInputs {
+            warnings: &warnings,
+            file_warnings: &file_warnings,
+            stats: &stats,
+            trends: &trend,
+            historical_trends: &historical_trends,
+            context: &context,
+            charts: &charts,
+            chart_images: &chart_images,
+        };
         self.generate_reports(
-            &warnings,
-            &file_warnings,
-            &stats,
-            trend,
-            &historical_trends,
-            &context,
-            &mut report_file,
-            &mut summary_file,
-            &mut analysis_file,
-            &mut json_file,
-            &mut csv_file,
-            &mut fix_plan_file,
+            &inputs,
+            ReportFiles {
+                report: report_file.as_mut().map(|(file, _)| file),
+                summary: summary_file.as_mut().map(|(file, _)| file),
+                markdown: analysis_file.as_mut().map(|(file, _)| file),
+                json: json_file.as_mut().map(|(file, _)| file),
+                csv: csv_file.as_mut().map(|(file, _)| file),
+                fix_plan: fix_plan_file.as_mut().map(|(file, _)| file),
+            },
         )?;
 
+        if let Some((file, _)) = analysis_file.as_mut().filter(|_| !alerts.is_empty()) {
+            MarkdownWriter::new(file).write_alerts(&alerts)?;
+        }
+
+        if let Some((file, _)) = analysis_file.as_mut().filter(|_| !third_party.is_empty()) {
+            MarkdownWriter::new(file).write_third_party(&third_party)?;
+        }
+
+        if let (Some((file, _)), Some(changed)) = (analysis_file.as_mut(), &options.changed_lines) {
+            if !pre_existing.is_empty() {
+                MarkdownWriter::new(file).write_pre_existing(&changed.base, &pre_existing)?;
+            }
+        }
+
+        if let Some((file, _)) = report_file.as_mut().filter(|_| self.config.list_suppressed && !suppressed.is_empty()) {
+            write_suppressed_appendix(file, &suppressed)?;
+        }
+        if let Some((file, _)) = report_file.as_mut().filter(|_| !generated.is_empty()) {
+            write_generated_appendix(file, &generated, &options.generated)?;
+        }
+
+        let mut written: Vec<(&str, PathBuf)> = [
+            ("report", report_file),
+            ("summary", summary_file),
+            ("analysis", analysis_file),
+            ("warnings_json", json_file),
+            ("warnings_csv", csv_file),
+            ("fix_plan", fix_plan_file),
+        ]
+        .into_iter()
+        .filter_map(|(name, file)| file.map(|(_, path)| (name, path)))
+        .collect();
+        written.extend(chart_files.into_iter().map(|(name, _, path)| (name, path)));
+
+        if self.config.merge_queue_status && self.config.writes(Artifact::MergeStatus) {
+            let (status_file, status_path) = self.create_output_file("merge_status")?;
+            let status = MergeQueueStatus::new(&stats, &verdict, &warnings, git::head_commit(&self.working_dir));
+            write_merge_queue_status(status_file, &status)?;
+            written.push(("merge_status", status_path));
+        }
+
+        if let Some(baseline) = baseline.as_ref().filter(|_| self.config.writes(Artifact::Baseline)) {
+            let (mut baseline_file, baseline_path) = self.create_output_file("baseline")?;
+            match baseline {
+                Ok(baseline) => write_baseline_report(baseline_file, baseline, &baseline.delta(&stats), &warnings)?,
+                Err(reason) => writeln!(baseline_file, "# Baseline Comparison\n\n{}", reason)?,
+            }
+            written.push(("baseline", baseline_path));
+        }
+
+        if let Some(dir) = self.config.baseline_store.as_ref().filter(|_| self.records_run()) {
+            if let Some(commit) = git::head_commit(&self.working_dir) {
+                let baseline = BaselineFile::new(self.baseline_meta(&commit), warnings.clone());
+                let path = BaselineStore::new(dir).save(&baseline)?;
+                self.debug_log(&format!("Stored analysis of {} in {:?}", commit, path))?;
+            }
+        }
+
+        if self.config.false_positive_report && self.config.writes(Artifact::FalsePositives) {
+            let (fp_file, fp_path) = self.create_output_file("false_positives")?;
+            write_false_positive_report(fp_file, &false_positives)?;
+            written.push(("false_positives", fp_path));
+        }
+
+        if let Some(repro) = self.repro.clone() {
+            let repro_path = self.fixed_report_path(REPRO_FILE);
+            write_repro_script(self.create_report_file(&repro_path)?, &repro, &self.timestamp)?;
+            if self.artifacts.is_none() {
+                make_executable(&repro_path)?;
+            }
+            written.push(("repro", repro_path));
+        }
+
+        if self.config.audit_notes && self.config.writes(Artifact::AuditNotes) {
+            let (notes_file, notes_path) = self.create_output_file("audit_notes")?;
+            let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+            write_audit_notes(notes_file, &AuditNotes::from_warnings(&warnings, &date))?;
+            written.push(("audit_notes", notes_path));
+        }
+
+        let mut playbook_files = Vec::new();
+        if self.config.playbooks && self.config.writes(Artifact::Playbooks) {
+            for playbook in Playbook::from_warnings(&warnings) {
+                let name = format!("playbook_{}", playbook.slug());
+                let (playbook_file, playbook_path) = self.create_output_file(&name)?;
+                write_playbook(std::io::BufWriter::new(playbook_file), &playbook, &self.fix_examples)?;
+                self.log.info(&format!("Wrote {} report to {}", name, playbook_path.display()))?;
+                playbook_files.push((name, playbook_path));
+                self.playbooks.push(playbook.slug());
+            }
+        }
+
+        if self.config.jsonl && self.config.writes(Artifact::Jsonl) {
+            let (jsonl_file, jsonl_path) = self.create_output_file("warnings_jsonl")?;
+            write_jsonl(std::io::BufWriter::new(jsonl_file), &warnings)?;
+            written.push(("warnings_jsonl", jsonl_path));
+        }
+
+        if self.config.rendered_appendix && self.config.writes(Artifact::RenderedDiagnostics) {
+            let (rendered_file, rendered_path) = self.create_output_file("rendered_diagnostics")?;
+            write_rendered_appendix(std::io::BufWriter::new(rendered_file), &warnings)?;
+            written.push(("rendered_diagnostics", rendered_path));
+        }
+
+        if self.config.binary_export && self.config.writes(Artifact::Protobuf) {
+            let (pb_file, pb_path) = self.create_output_file("warnings_pb")?;
+            write_binary_export(pb_file, &WarningSet::from_warnings(&warnings, &self.timestamp))?;
+            written.push(("warnings_pb", pb_path));
+        }
+
+        if self.config.formats.contains(&ReportFormat::Sarif) && self.config.writes(Artifact::Sarif) {
+            let (sarif_file, sarif_path) = self.create_output_file("sarif")?;
+            write_sarif(sarif_file, &warnings)?;
+            written.push(("sarif", sarif_path));
+        }
+
+        written.extend(self.write_status_files(&warnings, &stats)?);
+
+        if self.config.formats.contains(&ReportFormat::PrComment) && self.config.writes(Artifact::PrComment) {
+            let comment = match baseline.as_ref().and_then(|b| b.as_ref().ok()) {
+                Some(baseline) => {
+                    let diff = baseline.warning_diff(&warnings);
+                    PrComment {
+                        base: CommentBase::Branch { branch: baseline.branch.clone(), commit: short_commit(&baseline.commit).to_string() },
+                        total: Some(baseline.delta(&stats).total),
+                        fixed: diff.as_ref().map(|diff| diff.fixed_count()),
+                        warnings: diff.map_or_else(|| warnings.iter().collect(), |diff| diff.new),
+                    }
+                }
+                None => match &self.previous_fingerprints {
+                    Some(previous) => {
+                        let diff = diff_fingerprints(previous, &warnings, Warning::fingerprint);
+                        PrComment {
+                            base: CommentBase::PreviousRun,
+                            total: historical_trends.last().map(|run| CountDelta::new(run.total_warnings, stats.total_warnings)),
+                            fixed: Some(diff.fixed_count()),
+                            warnings: diff.new,
+                        }
+                    }
+                    None => PrComment { base: CommentBase::None, total: None, fixed: None, warnings: warnings.iter().collect() },
+                },
+            };
+            let comment_path = self.fixed_report_path(PR_COMMENT_FILE);
+            write_pr_comment(self.create_report_file(&comment_path)?, &comment, &stats, &self.completeness, &self.config.pr_comment)?;
+            written.push(("pr_comment", comment_path));
+        }
+
+        for (name, path) in &written {
+            self.log.info(&format!("Wrote {} report to {}", name, path.display()))?;
+            self.events.on_report_written(name, path);
+        }
+        self.phase_finished(Stage::GeneratingReports, "");
+
         // Add separator before success message
         writeln!(self.color_writer.writer(), "\n{}\n", "=".repeat(50))?;
 
         // Show success message with file links (without clearing screen)
         self.write_success_message()?;
-        Ok(())
+
+        if !self.config.notify.is_empty() {
+            let run = RunSummary::new(&stats, &trend, &historical_trends, &alerts, &verdict, &self.completeness);
+            for webhook in self.config.notify.clone() {
+                match notify_webhook(&webhook, &run) {
+                    Ok(true) => self.log.info(&format!("Sent {} notification", webhook.kind))?,
+                    Ok(false) => {}
+                    Err(e) => self.color_writer.write_warning(&format!("⚠️  {}\n", e))?,
+                }
+            }
+        }
+
+        let critical = stats.by_priority.get(&Priority::Critical).copied().unwrap_or(0);
+        let high = stats.by_priority.get(&Priority::High).copied().unwrap_or(0);
+        let reports_dir = self.reports_dir.clone().unwrap_or_else(|| PathBuf::from("."));
+        let mut summary = format!(
+            "{} warnings in {} files ({} critical, {} high)",
+            stats.total_warnings, stats.files_affected, critical, high
+        );
+        if !alerts.is_empty() {
+            summary.push_str(&format!(", {} alerts", alerts.len()));
+        }
+        if !verdict.passed() {
+            summary.push_str(", gate failed");
+        }
+        if !self.completeness.is_complete() {
+            summary.push_str(", incomplete");
+        }
+        summary.push_str(&format!("; reports in {}", reports_dir.display()));
+        self.set_summary(&summary);
+
+        let files = written
+            .into_iter()
+            .map(|(name, path)| (name.to_string(), path))
+            .chain(playbook_files)
+            .collect();
+        let mut report = self.finish(RunOutcome::Analyzed, input_path, warnings, stats, files)?;
+        report.alerts = alerts;
+        Ok(report)
+    }
+
+    /// Writes the run manifest listing `files`, the reports written, and the
+    /// clippy output at `input_path` if it is in the reports directory.
+    /// Returns the result of the run, without alerts.
+    fn finish(
+        &mut self,
+        outcome: RunOutcome,
+        input_path: &str,
+        warnings: Vec<Warning>,
+        stats: WarningStatistics,
+        mut files: Vec<(String, PathBuf)>,
+    ) -> std::io::Result<AnalysisReport> {
+        let reports_dir = self.reports_dir.clone().unwrap_or_else(|| PathBuf::from("."));
+        let mut manifest = RunManifest::new(&self.timestamp, self.run_metadata(), &self.completeness, stats.total_warnings);
+        let input = Path::new(input_path);
+        let manifest_path = match &self.artifacts {
+            Some(store) => {
+                let contents = store.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(output) = contents.get(input) {
+                    manifest.add_contents("clippy_output", input, output, &reports_dir);
+                }
+                for (name, path) in &files {
+                    if let Some(file) = contents.get(path) {
+                        manifest.add_contents(name, path, file, &reports_dir);
+                    }
+                }
+                drop(contents);
+                let path = reports_dir.join(RUN_MANIFEST_FILE);
+                write_manifest(self.create_report_file(&path)?, &manifest)?;
+                path
+            }
+            None => {
+                if input.starts_with(&reports_dir) && input.is_file() {
+                    manifest.add_artifact("clippy_output", input, &reports_dir)?;
+                }
+                for (name, path) in &files {
+                    manifest.add_artifact(name, path, &reports_dir)?;
+                }
+                write_run_manifest(&reports_dir, &manifest)?
+            }
+        };
+        self.log.info(&format!("Wrote run manifest to {}", manifest_path.display()))?;
+        self.events.on_report_written("manifest", &manifest_path);
+        files.push(("manifest".to_string(), manifest_path));
+
+        let artifacts = match &self.artifacts {
+            Some(store) => std::mem::take(&mut *store.lock().unwrap_or_else(|e| e.into_inner())),
+            None => Default::default(),
+        };
+        Ok(AnalysisReport {
+            outcome,
+            verdict: evaluate_gate(&self.config.gate, &stats),
+            warnings,
+            stats,
+            completeness: self.completeness.clone(),
+            alerts: Vec::new(),
+            files,
+            artifacts,
+            reports_dir,
+            summary: self.summary.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Git and toolchain details for the run manifest, from the
+    /// reproduction info when it was captured.
+    fn run_metadata(&self) -> RunMetadata {
+        match &self.repro {
+            Some(repro) => RunMetadata {
+                git_commit: repro.git_commit.clone(),
+                git_branch: repro.git_branch.clone(),
+                rustc_version: repro.rustc_version.clone(),
+                cargo_version: cargo_version(),
+                clippy_version: repro.clippy_version.clone(),
+                args: repro.analyzer_args.iter().skip(1).cloned().collect(),
+            },
+            None => {
+                let (rustc_version, clippy_version) = toolchain_versions();
+                RunMetadata {
+                    git_commit: git::head_commit(&self.working_dir),
+                    git_branch: git::current_branch(&self.working_dir),
+                    rustc_version,
+                    cargo_version: cargo_version(),
+                    clippy_version,
+                    args: std::env::args().skip(1).collect(),
+                }
+            }
+        }
+    }
+
+    /// Reports a run without warnings. It is recorded in the history
+    /// database and baseline store like any other, so trends show the drop
+    /// to zero, and the analysis, HTML summary, JSON export, and enabled
+    /// status artifacts are written; the other reports would be empty.
+    ///
+    /// Returns the reports written.
+    fn write_clean_run(
+        &mut self,
+        stats: &WarningStatistics,
+        context: &[AnalysisContext],
+    ) -> std::io::Result<Vec<(&'static str, PathBuf)>> {
+        if self.completeness.is_complete() {
+            self.color_writer.write_success("🎉 No warnings: clippy found nothing to fix\n")?;
+        } else {
+            self.color_writer.write_success("✅ No warnings in the targets clippy checked\n")?;
+        }
+
+        let historical_trends = self.update_history(&[], stats)?;
+        let previous = historical_trends.last().map(|run| run.total_warnings);
+        if let Some(count) = previous.filter(|&count| count > 0) {
+            writeln!(self.color_writer.writer(), "Down from {} warnings in the previous run\n", count)?;
+        }
+        if let Some(dir) = self.config.baseline_store.as_ref().filter(|_| self.records_run()) {
+            if let Some(commit) = git::head_commit(&self.working_dir) {
+                let baseline = BaselineFile::new(self.baseline_meta(&commit), Vec::new());
+                let path = BaselineStore::new(dir).save(&baseline)?;
+                self.debug_log(&format!("Stored analysis of {} in {:?}", commit, path))?;
+            }
+        }
+
+        self.phase_started(Stage::GeneratingReports);
+        let mut trend = TrendAnalysis::new(0, HashMap::new(), HashMap::new(), HashMap::new());
+        let historical_totals: Vec<usize> = historical_trends.iter().map(|t| t.total_warnings).collect();
+        trend.calculate_improvement_rate(&historical_totals);
+        trend.calculate_weighted_improvement(&historical_trends);
+        let charts = SvgCharts::new(stats, &historical_trends);
+
+        let mut written: Vec<(&str, PathBuf)> = Vec::new();
+        if let Some((file, path)) = self.create_artifact_file(Artifact::Analysis, "analysis")? {
+            generate_clean_markdown_report(file, &trend, &historical_trends, context)?;
+            written.push(("analysis", path));
+        }
+        if let Some((file, path)) = self.create_artifact_file(Artifact::Summary, "summary")? {
+            let mut theme = self.config.html.clone();
+            theme.embed_logo(&self.working_dir)?;
+            let mut html_writer = HtmlWriter::new(file);
+            html_writer.set_theme(theme);
+            generate_clean_html_report(html_writer, &self.completeness, &charts, previous)?;
+            written.push(("summary", path));
+        }
+        if let Some((file, path)) = self.create_artifact_file(Artifact::Json, "warnings_json")? {
+            write_json(file, &[])?;
+            written.push(("warnings_json", path));
+        }
+        if self.config.merge_queue_status && self.config.writes(Artifact::MergeStatus) {
+            let (status_file, status_path) = self.create_output_file("merge_status")?;
+            let verdict = evaluate_gate(&self.config.gate, stats);
+            let status = MergeQueueStatus::new(stats, &verdict, &[], git::head_commit(&self.working_dir));
+            write_merge_queue_status(status_file, &status)?;
+            written.push(("merge_status", status_path));
+        }
+        written.extend(self.write_status_files(&[], stats)?);
+        let reports_dir = self.reports_dir.clone().unwrap_or_else(|| PathBuf::from("."));
+
+        for (name, path) in &written {
+            self.log.info(&format!("Wrote {} report to {}", name, path.display()))?;
+            self.events.on_report_written(name, path);
+        }
+        self.phase_finished(Stage::GeneratingReports, "");
+
+        if !written.is_empty() {
+            writeln!(self.color_writer.writer(), "\n{}\n", "=".repeat(50))?;
+            self.color_writer.write_success("📊 Generated Reports:\n")?;
+            for (_, path) in &written {
+                let path = std::path::absolute(path).unwrap_or_else(|_| path.clone());
+                writeln!(
+                    self.color_writer.writer(),
+                    "  {}",
+                    osc8(&format!("file://{}", path.display()), &path.display().to_string())
+                )?;
+            }
+            writeln!(self.color_writer.writer())?;
+        }
+        self.set_summary(&format!("no warnings; reports in {}", reports_dir.display()));
+        Ok(written)
+    }
+
+    /// Writes the enabled files that status displays poll under fixed names
+    /// in the reports directory: the badge and its shields.io endpoint, and
+    /// the Prometheus metrics. Returns the files written.
+    fn write_status_files(
+        &mut self,
+        warnings: &[Warning],
+        stats: &WarningStatistics,
+    ) -> std::io::Result<Vec<(&'static str, PathBuf)>> {
+        let mut written = Vec::new();
+        if self.config.badge && self.config.writes(Artifact::Badge) {
+            let badge = Badge::from_stats(stats, &self.completeness);
+            let svg_path = self.fixed_report_path(BADGE_SVG_FILE);
+            write_badge_svg(self.create_report_file(&svg_path)?, &badge)?;
+            written.push(("badge", svg_path));
+            let endpoint_path = self.fixed_report_path(BADGE_ENDPOINT_FILE);
+            write_shields_endpoint(self.create_report_file(&endpoint_path)?, &badge)?;
+            written.push(("badge_endpoint", endpoint_path));
+        }
+        if self.config.formats.contains(&ReportFormat::Prometheus) && self.config.writes(Artifact::Prometheus) {
+            let metrics_path = self.fixed_report_path(PROMETHEUS_FILE);
+            if self.artifacts.is_some() {
+                let file = self.create_report_file(&metrics_path)?;
+                write_prometheus(file, warnings, stats, &self.completeness, chrono::Utc::now().timestamp())?;
+            } else {
+                std::fs::create_dir_all(metrics_path.parent().unwrap_or(Path::new(".")))?;
+                write_prometheus_file(&metrics_path, warnings, stats, &self.completeness)?;
+            }
+            written.push(("prometheus", metrics_path));
+        }
+        Ok(written)
+    }
+
+    /// Classifies parsed clippy output for `--check` mode without writing
+    /// any report files.
+    pub fn check(&mut self, parsed: ParseResult) -> std::io::Result<CheckOutcome> {
+        self.phase_started(Stage::Analyzing);
+        let options = self.analysis_options()?;
+        let stats = self.run_pipeline(parsed, &options)?.stats;
+        self.phase_finished(
+            Stage::Analyzing,
+            &format!("{} warnings in {} files", stats.total_warnings, stats.files_affected),
+        );
+
+        let outcome = CheckOutcome::from_stats(&stats);
+        if stats.total_warnings > 0 {
+            self.write_terminal_summary(&stats)?;
+        }
+        let critical = stats.by_priority.get(&Priority::Critical).copied().unwrap_or(0);
+        let safety = stats.by_category.get(&CategoryType::Safety).copied().unwrap_or(0);
+        let summary = format!(
+            "{} warnings ({} critical, {} safety)",
+            stats.total_warnings, critical, safety
+        );
+        match outcome {
+            CheckOutcome::Clean => self.color_writer.write_success("✅ Check passed: no warnings\n")?,
+            CheckOutcome::Warnings => self.color_writer.write_warning(&format!("⚠️  Check failed: {}\n", summary))?,
+            CheckOutcome::Critical => self.color_writer.write_error(&format!("❌ Check failed: {}\n", summary))?,
+        }
+        self.set_summary(&summary);
+        Ok(outcome)
+    }
+
+    /// Evaluates the gate against parsed clippy output for `--gate-only`
+    /// mode, writing nothing but the verdict.
+    ///
+    /// An incomplete run still fails the gate when it already exceeds a
+    /// threshold, since its counts are lower bounds; an incomplete run that
+    /// passes is an error.
+    pub fn gate(&mut self, parsed: ParseResult) -> std::io::Result<GateVerdict> {
+        self.phase_started(Stage::Analyzing);
+        let options = self.analysis_options()?;
+        let stats = self.run_pipeline(parsed, &options)?.stats;
+        self.phase_finished(
+            Stage::Analyzing,
+            &format!("{} warnings in {} files", stats.total_warnings, stats.files_affected),
+        );
+
+        let verdict = evaluate_gate(&self.config.gate, &stats);
+        if verdict.passed() && !self.completeness.is_complete() {
+            return Err(std::io::Error::new(std::io::ErrorKind::Interrupted, self.completeness.to_string()));
+        }
+        self.write_gate_verdict(&verdict)?;
+        let summary = match verdict.violations.as_slice() {
+            [] => format!("gate passed ({} warnings)", stats.total_warnings),
+            violations => format!(
+                "gate failed: {}",
+                violations.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+            ),
+        };
+        self.set_summary(&summary);
+        Ok(verdict)
+    }
+
+    /// Analysis options from the config, the triage file, and the runner's
+    /// scope and cargo-geiger report.
+    fn analysis_options(&self) -> std::io::Result<AnalysisOptions> {
+        let mut options = AnalysisOptions::from_config(&self.config)?;
+        if let Some(path) = &self.config.false_positives_file {
+            options.false_positives.load_triage_file(path)?;
+        }
+        options.scope = self.scope.clone();
+        options.geiger = self.geiger.clone();
+        if self.config.changed_only && self.config.base.is_none() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "changed-only needs a ref to compare with: pass --changed-only REF or --base REF",
+            ));
+        }
+        if let Some(base) = &self.config.base {
+            let commit = git::branch_head(&self.working_dir, base)
+                .and_then(|head| git::merge_base(&self.working_dir, &head))
+                .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, format!("Cannot resolve base ref {}", base)))?;
+            let changed = if self.config.changed_only {
+                git::changed_files(&self.working_dir, base, &commit)
+            } else {
+                git::changed_lines(&self.working_dir, base, &commit)
+            };
+            let changed = changed
+                .ok_or_else(|| std::io::Error::other(format!("Cannot diff against {} ({})", base, short_commit(&commit))))?;
+            options.changed_lines = Some(changed);
+        }
+        Ok(options)
+    }
+
+    fn run_pipeline(&mut self, parsed: ParseResult, options: &AnalysisOptions) -> std::io::Result<Analysis> {
+        let analysis = analyze(parsed, options);
+        if let Some(scope) = &options.scope {
+            self.debug_log(&format!("Dropped {} warnings outside {}", analysis.out_of_scope, scope.path()))?;
+        }
+        if analysis.overridden > 0 {
+            self.debug_log(&format!("Overrode the priority of {} warnings", analysis.overridden))?;
+        }
+        if !analysis.generated.is_empty() {
+            self.debug_log(&format!("Excluded {} warnings in generated or vendored files", analysis.generated.len()))?;
+        }
+        if !analysis.third_party.is_empty() {
+            self.debug_log(&format!("Set aside {} warnings in third-party code", analysis.third_party.len()))?;
+        }
+        if !analysis.suppressed.is_empty() {
+            self.debug_log(&format!("Suppressed {} warnings via ignore rules", analysis.suppressed.len()))?;
+        }
+        if !analysis.false_positives.is_empty() {
+            self.debug_log(&format!("Set aside {} suspected false positives", analysis.false_positives.len()))?;
+        }
+        Ok(analysis)
+    }
+
+    fn phase_started(&mut self, stage: Stage) {
+        self.progress.stage_started(stage);
+        self.events.on_phase_start(stage);
+    }
+
+    fn phase_finished(&mut self, stage: Stage, summary: &str) {
+        self.progress.stage_finished(stage, summary);
+        self.events.on_phase_end(stage);
     }
 
     fn debug_log(&mut self, message: &str) -> std::io::Result<()> {
-        let timestamp = chrono::Local::now().format("%H:%M:%S%.3f");
-        writeln!(self.debug_log, "[{}] {}", timestamp, message)?;
-        self.debug_log.flush()?;
-        Ok(())
+        self.log.debug(message)
     }
 
-    fn create_output_file(&mut self, name: &str) -> std::io::Result<(File, PathBuf)> {
-        self.debug_log(&format!("\n=== Creating Output File: {} ===", name))?;
-        
+    /// Keeps the one-line result the CLI shows in quiet mode.
+    fn set_summary(&mut self, summary: &str) {
+        self.summary = Some(summary.to_string());
+    }
+
+    /// One-line result of the last run, check, or gate evaluation, e.g.
+    /// `12 warnings in 3 files (0 critical, 2 high); reports in reports`.
+    pub fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    /// Path of a report with a fixed name in the reports directory.
+    fn fixed_report_path(&self, file_name: &str) -> PathBuf {
+        self.reports_dir.as_deref().unwrap_or(Path::new(".")).join(file_name)
+    }
+
+    /// Creates the report file at `path`, or its buffer in an in-memory run.
+    fn create_report_file(&self, path: &Path) -> std::io::Result<ReportFile> {
+        ReportFile::create(path.to_path_buf(), self.artifacts.as_ref())
+    }
+
+    fn create_output_file(&mut self, name: &str) -> std::io::Result<(ReportFile, PathBuf)> {
+        self.debug_log(&format!("Creating output file: {}", name))?;
+
         let reports_dir = match &self.reports_dir {
             Some(dir) => {
                 let dir_clone = dir.clone();
@@ -207,33 +1100,131 @@ impl AnalysisRunner {
             }
         };
 
-        self.debug_log(&format!("📁 Ensuring directory exists: {:?}", reports_dir))?;
-        std::fs::create_dir_all(&reports_dir)?;
-
         let filename = format!("clippy_{}_{}.{}", name, self.timestamp, self.get_extension(name));
         let file_path = reports_dir.join(&filename);
         
         self.debug_log(&format!("📝 Creating file: {:?}", file_path))?;
-        let file = File::create(&file_path)?;
-        self.debug_log("✅ File created successfully")?;
-        self.debug_log("=== File Creation Complete ===\n")?;
+        let file = self.create_report_file(&file_path)?;
 
         Ok((file, file_path))
     }
 
+    /// Creates the output file of `artifact`, unless the artifact selection
+    /// leaves it out.
+    fn create_artifact_file(&mut self, artifact: Artifact, name: &str) -> std::io::Result<Option<(ReportFile, PathBuf)>> {
+        if !self.config.writes(artifact) {
+            return Ok(None);
+        }
+        self.create_output_file(name).map(Some)
+    }
+
+    /// Writes each chart to its own SVG file, returning the report name, alt
+    /// text, and path of every file written.
+    fn write_svg_charts(
+        &mut self,
+        charts: &SvgCharts,
+    ) -> std::io::Result<Vec<(&'static str, &'static str, PathBuf)>> {
+        let mut svgs = vec![
+            ("category_chart", "Category distribution", &charts.category),
+            ("severity_chart", "Severity distribution", &charts.severity),
+        ];
+        if let Some(trend) = &charts.trend {
+            svgs.push(("trend_chart", "Warning trend", trend));
+        }
+
+        let mut written = Vec::with_capacity(svgs.len());
+        for (name, alt, svg) in svgs {
+            let (mut file, path) = self.create_output_file(name)?;
+            file.write_all(svg.as_bytes())?;
+            written.push((name, alt, path));
+        }
+        Ok(written)
+    }
+
     fn get_extension(&self, name: &str) -> &str {
         match name {
             "output" => "json",
-            "analysis" | "fix_plan" | "report" => "md",
+            "analysis" | "fix_plan" | "report" | "baseline" | "false_positives" | "rendered_diagnostics" => "md",
             "summary" => "html",
-            "warnings_json" => "json",
+            "warnings_json" | "merge_status" => "json",
             "warnings_csv" => "csv",
+            "warnings_jsonl" | "summary_files" => "jsonl",
+            name if name.starts_with("playbook_") => "md",
+            "audit_notes" => "toml",
+            "warnings_pb" => "pb",
+            "sarif" => "sarif",
+            "category_chart" | "severity_chart" | "trend_chart" => "svg",
             _ => "txt",
         }
     }
 
+    /// Records this run in the history database, if configured, and returns
+    /// the trends of earlier runs.
+    #[cfg(feature = "sqlite")]
+    fn update_history(
+        &mut self,
+        warnings: &[Warning],
+        stats: &WarningStatistics,
+    ) -> std::io::Result<Vec<TrendAnalysis>> {
+        // Opening a missing database would create it
+        let Some(path) = self.config.history_db.clone().filter(|path| self.artifacts.is_none() || path.exists()) else {
+            return self.load_historical_trends();
+        };
+
+        self.debug_log(&format!("Recording run in history database {:?}", path))?;
+        let mut db = HistoryDb::open(&path)?;
+        let trends = db.recent_trends(HISTORY_TREND_RUNS)?;
+        let mut previous = db.latest_fingerprints()?;
+        if let Some(commit) = db.latest_commit()? {
+            let (_, renamed) = self.renames_since(&commit, warnings)?;
+            previous = follow_renamed(&previous, &renamed);
+        }
+        if !previous.is_empty() || trends.last().is_some_and(|t| t.total_warnings == 0) {
+            self.previous_fingerprints = Some(previous);
+        }
+        if self.records_run() {
+            let mut run = RunRecord::new(warnings, stats);
+            run.churn = self.previous_fingerprints
+                .as_ref()
+                .map(|previous| fingerprint_churn(previous, &run.fingerprints));
+            run.git_commit = git::head_commit(&self.working_dir);
+            run.branch = git::current_branch(&self.working_dir);
+            db.record_run(&run)?;
+        }
+        Ok(trends)
+    }
+
+    /// The trends of the last `runs` recorded runs, oldest first, from the
+    /// history database if configured.
+    #[cfg(feature = "sqlite")]
+    pub fn recorded_trends(&self, runs: usize) -> std::io::Result<Vec<TrendAnalysis>> {
+        match &self.config.history_db {
+            Some(path) => HistoryDb::open(path)?.recent_trends(runs),
+            None => self.load_historical_trends().map(|trends| last_runs(trends, runs)),
+        }
+    }
+
+    #[cfg(not(feature = "sqlite"))]
+    pub fn recorded_trends(&self, runs: usize) -> std::io::Result<Vec<TrendAnalysis>> {
+        self.load_historical_trends().map(|trends| last_runs(trends, runs))
+    }
+
+    #[cfg(not(feature = "sqlite"))]
+    fn update_history(
+        &mut self,
+        _warnings: &[Warning],
+        _stats: &WarningStatistics,
+    ) -> std::io::Result<Vec<TrendAnalysis>> {
+        if self.config.history_db.is_some() {
+            self.color_writer.write_warning(
+                "⚠️  history-db is set but this build lacks the `sqlite` feature; using clippy_historical.json\n",
+            )?;
+        }
+        self.load_historical_trends()
+    }
+
     fn load_historical_trends(&self) -> std::io::Result<Vec<TrendAnalysis>> {
-        let path = "clippy_historical.json";
+        let path = self.working_dir.join(HISTORICAL_TRENDS_FILE);
         if let Ok(file) = File::open(path) {
             let reader = std::io::BufReader::new(file);
             Ok(serde_json::from_reader(reader).unwrap_or_default())
@@ -242,57 +1233,137 @@ impl AnalysisRunner {
         }
     }
 
-    fn generate_reports(
-        &mut self,
-        warnings: &[Warning],
-        file_warnings: &HashMap<String, FileWarnings>,
-        stats: &WarningStatistics,
-        trends: &TrendAnalysis,
-        historical_trends: &[TrendAnalysis],
-        context: &[AnalysisContext],
-        report_file: &mut File,
-        summary_file: &mut File,
-        markdown_file: &mut File,
-        json_file: &mut File,
-        csv_file: &mut File,
-        fix_plan_file: &mut File,
-    ) -> std::io::Result<()> {
-        // Write CSV header
-        writeln!(csv_file, "File,Line,Category,Message,Priority,Suggested Fix")?;
-
-        // Write warnings to CSV
-        for warning in warnings {
-            writeln!(
-                csv_file,
-                "{},{},{:?},{},{:?},{}",
-                warning.file,
-                warning.line,
-                warning.category,
-                warning.message.replace(",", ";"),  // Escape commas
-                warning.priority,
-                warning.suggested_fix.as_ref().unwrap_or(&String::new()).replace(",", ";")
-            )?;
+    fn generate_reports(&mut self, inputs: &ReportInputs<'_>, files: ReportFiles<'_>) -> std::io::Result<()> {
+        let ReportInputs { warnings, file_warnings, stats, trends, historical_trends, context, charts, chart_images } = *inputs;
+        let ReportFiles {
+            report: report_file,
+            summary: summary_file,
+            markdown: markdown_file,
+            json: json_file,
+            csv: csv_file,
+            fix_plan: fix_plan_file,
+        } = files;
+        // Write CSV
+        self.progress.stage_progress(Stage::GeneratingReports, "warnings CSV and JSON");
+        if let Some(csv_file) = csv_file {
+            write_csv(csv_file, warnings, &self.config.csv_columns)?;
         }
 
         // Write JSON output
-        serde_json::to_writer_pretty(json_file, &warnings)?;
+        if let Some(json_file) = json_file {
+            write_json(json_file, warnings)?;
+        }
+
+        let unused_deps = self.unused_deps
+            .clone()
+            .unwrap_or_else(|| UnusedDependencies::from_lints(warnings));
+        let unused_deps = (self.config.udeps || !unused_deps.is_empty()).then_some(unused_deps);
+        let impacts = self.build_impact.as_ref().map(|impact| impact.annotate(warnings, &self.working_dir));
 
         // Write markdown report
-        generate_markdown_report(
-            markdown_file,
-            stats,
-            trends,
-            &historical_trends,
-            context,
-        )?;
+        if let Some(markdown_file) = markdown_file {
+            self.progress.stage_progress(Stage::GeneratingReports, "markdown analysis");
+            let clusters = cluster_warnings(warnings);
+            if let Some(path) = &self.config.templates.markdown {
+                let mut ctx = TemplateContext::new("Clippy Analysis Report", stats, warnings, &self.completeness);
+                ctx.add_section("summary", |out| MarkdownWriter::new(out).write_summary(stats, &self.timestamp))?;
+                ctx.add_section("charts", |out| MarkdownWriter::new(out).write_chart_images(chart_images))?;
+                ctx.add_section("heat_table", |out| MarkdownWriter::new(out).write_heat_table(&HeatTable::from_stats(stats)))?;
+                ctx.add_section("module_heatmap", |out| MarkdownWriter::new(out).write_module_heatmap(&ModuleTree::from_stats(stats)))?;
+                ctx.add_section("module_breakdown", |out| MarkdownWriter::new(out).write_module_breakdown(&stats.by_directory, stats))?;
+                ctx.add_section("recurring_patterns", |out| MarkdownWriter::new(out).write_recurring_patterns(&clusters))?;
+                ctx.add_section("unsafe_usage", |out| MarkdownWriter::new(out).write_unsafe_usage(&stats.safety_details.unsafe_details))?;
+                ctx.add_section("build_info", |out| MarkdownWriter::new(out).write_build_info(context))?;
+                ctx.add_section("trends", |out| MarkdownWriter::new(out).write_trend_analysis(trends, historical_trends))?;
+                ctx.add_section("unused_dependencies", |out| {
+                    unused_deps.as_ref().map_or(Ok(()), |unused| MarkdownWriter::new(out).write_unused_dependencies(unused))
+                })?;
+                ctx.add_section("hidden_code", |out| {
+                    self.hidden_code.as_ref().map_or(Ok(()), |hidden| MarkdownWriter::new(out).write_hidden_code(hidden))
+                })?;
+                ctx.add_section("performance_impact", |out| {
+                    impacts.as_ref().map_or(Ok(()), |impacts| MarkdownWriter::new(out).write_performance_impact(impacts))
+                })?;
+                markdown_file.write_all(render_template(path, TemplateFormat::Markdown, &ctx)?.as_bytes())?;
+            } else {
+                generate_markdown_report(&mut *markdown_file, stats, trends, historical_trends, context, chart_images, &clusters, unused_deps.as_ref())?;
+                if let Some(hidden) = &self.hidden_code {
+                    MarkdownWriter::new(&mut *markdown_file).write_hidden_code(hidden)?;
+                }
+                if let Some(impacts) = &impacts {
+                    MarkdownWriter::new(&mut *markdown_file).write_performance_impact(impacts)?;
+                }
+            }
+        }
 
-        // Write summary statistics
-        self.write_summary(summary_file, stats)?;
+        // Write HTML summary
+        if let Some(summary_file) = summary_file {
+            self.progress.stage_progress(Stage::GeneratingReports, "HTML summary");
+            let snippets = self.top_snippets(warnings, MAX_HTML_SNIPPETS);
+            let mut theme = self.config.html.clone();
+            theme.embed_logo(&self.working_dir)?;
+            if let Some(path) = &self.config.templates.html {
+                let mut ctx = TemplateContext::new("Clippy Analysis Summary", stats, warnings, &self.completeness);
+                fn themed<'a>(out: &'a mut Vec<u8>, theme: &HtmlTheme) -> HtmlWriter<&'a mut Vec<u8>> {
+                    let mut writer = HtmlWriter::new(out);
+                    writer.set_theme(theme.clone());
+                    writer
+                }
+                ctx.sections.insert("style", theme.style());
+                ctx.add_section("notice", |out| {
+                    if self.completeness.is_complete() { Ok(()) } else { themed(out, &theme).write_notice(&self.completeness.to_string()) }
+                })?;
+                ctx.add_section("summary", |out| themed(out, &theme).write_summary(stats))?;
+                ctx.add_section("charts", |out| themed(out, &theme).write_charts(charts))?;
+                ctx.add_section("heat_table", |out| themed(out, &theme).write_heat_table(&HeatTable::from_stats(stats)))?;
+                ctx.add_section("module_breakdown", |out| themed(out, &theme).write_module_breakdown(&stats.by_directory, stats))?;
+                ctx.add_section("snippets", |out| themed(out, &theme).write_snippets(&snippets))?;
+                ctx.add_section("file_sections", |out| {
+                    let mut writer = themed(out, &theme);
+                    writer.set_file_history(historical_trends);
+                    writer.write_file_sections(warnings)
+                })?;
+                ctx.add_section("raw_diagnostics", |out| themed(out, &theme).write_raw_diagnostics(warnings))?;
+                summary_file.write_all(render_template(path, TemplateFormat::Html, &ctx)?.as_bytes())?;
+            } else {
+                let mut html_writer = HtmlWriter::new(summary_file);
+                html_writer.set_theme(theme.clone());
+                html_writer.set_file_history(historical_trends);
+                if theme.is_lazy(warnings.len()) {
+                    let (sections_file, sections_path) = self.create_output_file("summary_files")?;
+                    let sections = write_file_sections_jsonl(std::io::BufWriter::new(sections_file), warnings)?;
+                    let href = sections_path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+                    html_writer.set_lazy_sections(href, sections);
+                    self.lazy_summary = true;
+                }
+                generate_html_report(html_writer, stats, warnings, &self.completeness, charts, &snippets)?;
+            }
+        }
 
         // Write detailed report
-        self.write_detailed_report(report_file, warnings, file_warnings, stats, trends)?;
+        if let Some(report_file) = report_file {
+            self.progress.stage_progress(Stage::GeneratingReports, "detailed report");
+            self.write_detailed_report(report_file, warnings, file_warnings, stats, trends)?;
+        }
 
+        let Some(fix_plan_file) = fix_plan_file else { return Ok(()) };
+        self.progress.stage_progress(Stage::GeneratingReports, "fix plan");
         let mut fix_plan_generator = FixPlanGenerator::new(fix_plan_file);
+        fix_plan_generator.set_risk_levels(&self.config.risk_levels);
+        let churn = git::file_churn(&self.working_dir, self.config.hotspots.months);
+        fix_plan_generator.set_hotspots(rank_hotspots(warnings, &churn, &self.config.hotspots));
+        fix_plan_generator.set_worst_functions(&stats.by_function);
+        if let Some(unused) = unused_deps {
+            fix_plan_generator.set_unused_dependencies(unused);
+        }
+        let mut pipeline = SuggestionPipeline::from_config(&self.config.suggestions);
+        for processor in &self.post_processors {
+            pipeline.register(Arc::clone(processor));
+        }
+        self.debug_log(&format!("Suggestion post-processors: {:?}", pipeline.names()))?;
+        fix_plan_generator.set_suggestion_pipeline(pipeline);
+        fix_plan_generator.set_fix_examples(self.fix_examples.clone());
+        fix_plan_generator.set_snippet_reader(self.snippet_reader());
         fix_plan_generator.generate_plan(warnings)?;
 
         Ok(())
@@ -307,7 +1378,7 @@ impl AnalysisRunner {
 
         // Get absolute path
         let base_dir = if base_dir.is_relative() {
-            std::env::current_dir()?.join(base_dir)
+            self.working_dir.join(base_dir)
         } else {
             base_dir
         };
@@ -319,7 +1390,7 @@ impl AnalysisRunner {
         self.color_writer.write_success("📊 Generated Reports:\n")?;
         
         // Define file groups with icons and descriptions
-        let file_groups = [
+        let mut file_groups = vec![
             ("📋", "Analysis", vec![
                 ("analysis", "md", "Detailed analysis with charts"),
                 ("fix_plan", "md", "Fix suggestions and priorities"),
@@ -327,31 +1398,88 @@ impl AnalysisRunner {
             ("📝", "Reports", vec![
                 ("report", "md", "File-by-file analysis"),
                 ("summary", "html", "Interactive overview"),
+                ("category_chart", "svg", "Category distribution chart"),
+                ("severity_chart", "svg", "Severity distribution chart"),
+                ("trend_chart", "svg", "Warning trend chart"),
             ]),
             ("📦", "Data", vec![
                 ("warnings_csv", "csv", "CSV format"),
                 ("warnings_json", "json", "JSON format"),
             ]),
         ];
+        for (_, _, files) in &mut file_groups {
+            files.retain(|(name, _, _)| {
+                let artifact = match *name {
+                    "analysis" => Artifact::Analysis,
+                    "fix_plan" => Artifact::FixPlan,
+                    "report" => Artifact::Report,
+                    "summary" => Artifact::Summary,
+                    "warnings_csv" => Artifact::Csv,
+                    "warnings_json" => Artifact::Json,
+                    "trend_chart" => return self.trend_chart,
+                    _ => Artifact::Charts,
+                };
+                self.config.writes(artifact)
+            });
+        }
+        let playbooks: Vec<(String, String)> = self.playbooks
+            .iter()
+            .map(|slug| (format!("playbook_{}", slug), format!("Fix playbook for {} warnings", slug)))
+            .collect();
+        for (name, desc) in &playbooks {
+            file_groups[0].2.push((name.as_str(), "md", desc.as_str()));
+        }
+        if self.config.auto_baseline.is_some() && self.config.writes(Artifact::Baseline) {
+            file_groups[0].2.push(("baseline", "md", "Comparison with the baseline branch"));
+        }
+        if self.lazy_summary {
+            file_groups[1].2.push(("summary_files", "jsonl", "Warnings by file for the HTML summary"));
+        }
+        if self.config.rendered_appendix && self.config.writes(Artifact::RenderedDiagnostics) {
+            file_groups[1].2.push(("rendered_diagnostics", "md", "Diagnostics as clippy rendered them"));
+        }
+        if self.config.false_positive_report && self.config.writes(Artifact::FalsePositives) {
+            file_groups[1].2.push(("false_positives", "md", "Suspected false positives to report upstream"));
+        }
+        if self.config.merge_queue_status && self.config.writes(Artifact::MergeStatus) {
+            file_groups[2].2.push(("merge_status", "json", "Merge queue check status"));
+        }
+        if self.repro.is_some() {
+            file_groups[2].2.push(("repro", "sh", "Reproduction script"));
+        }
+        if self.config.audit_notes && self.config.writes(Artifact::AuditNotes) {
+            file_groups[2].2.push(("audit_notes", "toml", "Supply-chain audit notes"));
+        }
+        if self.config.jsonl && self.config.writes(Artifact::Jsonl) {
+            file_groups[2].2.push(("warnings_jsonl", "jsonl", "JSONL format, one warning per line"));
+        }
+        if self.config.binary_export && self.config.writes(Artifact::Protobuf) {
+            file_groups[2].2.push(("warnings_pb", "pb", "Compact protobuf format"));
+        }
+        if self.config.formats.contains(&ReportFormat::Sarif) && self.config.writes(Artifact::Sarif) {
+            file_groups[2].2.push(("sarif", "sarif", "SARIF log for code scanning"));
+        }
 
         // Write each group
         for (icon, group_name, files) in file_groups {
             writeln!(self.color_writer.writer(), "\n{} {}:", icon, group_name)?;
             
             for (name, ext, desc) in files {
-                let filename = format!("clippy_{}_{}.{}", name, self.timestamp, ext);
+                let filename = match name {
+                    "repro" => REPRO_FILE.to_string(),
+                    _ => format!("clippy_{}_{}.{}", name, self.timestamp, ext),
+                };
                 let filepath = format!("{}/{}", base_dir, filename);
                 
+                let label = if filename.len() >= 40 {
+                    format!("{}...", &filename[..37])
+                } else {
+                    format!("{:.<40}", filename)
+                };
                 writeln!(
                     self.color_writer.writer(),
-                    "  \x1B]8;;file://{}\x1B\\{}⎯⎯▶ {}\x1B]8;;\x1B\\ {}",
-                    filepath,
-                    filename,
-                    if filename.len() >= 40 {
-                        format!("{}...", &filename[..37])
-                    } else {
-                        format!("{:.<40}", filename)
-                    },
+                    "  {} {}",
+                    osc8(&format!("file://{}", filepath), &format!("{}⎯⎯▶ {}", filename, label)),
                     desc
                 )?;
             }
@@ -362,32 +1490,9 @@ impl AnalysisRunner {
         Ok(())
     }
 
-    fn write_summary(&self, file: &mut File, stats: &WarningStatistics) -> std::io::Result<()> {
-        writeln!(file, "Total Warnings: {}", stats.total_warnings)?;
-        writeln!(file, "Files Affected: {}", stats.files_affected)?;
-        writeln!(file, "\nCategory Distribution:")?;
-        
-        for (category, count) in &stats.by_category {
-            let percentage = (*count as f64 / stats.total_warnings as f64) * 100.0;
-            writeln!(file, "{:?}: {} ({:.1}%)", category, count, percentage)?;
-        }
-
-        writeln!(file, "\nPriority Distribution:")?;
-        for (priority, count) in &stats.by_priority {
-            writeln!(file, "{:?}: {}", priority, count)?;
-        }
-
-        writeln!(file, "\nSubcategory Distribution:")?;
-        for (subcategory, count) in &stats.by_subcategory {
-            writeln!(file, "{}: {}", subcategory, count)?;
-        }
-
-        Ok(())
-    }
-
     fn write_detailed_report(
         &self,
-        file: &mut File,
+        file: &mut ReportFile,
         warnings: &[Warning],
         file_warnings: &HashMap<String, FileWarnings>,
         stats: &WarningStatistics,
@@ -395,6 +1500,8 @@ impl AnalysisRunner {
     ) -> std::io::Result<()> {
         let (safety, perf, style, docs) = stats.get_detailed_stats();
 
+        write_error_section(file, warnings)?;
+
         // Write file-by-file analysis
         writeln!(file, "File-by-File Analysis\n")?;
         writeln!(file, "===================\n")?;
@@ -421,8 +1528,219 @@ impl AnalysisRunner {
 
         // Write all warnings with their full details
         writeln!(file, "\nDetailed Warning List\n")?;
-        write_warning_report(file, warnings, true)?;
+        write_warning_report(file, warnings, true, Some(&mut self.snippet_reader()))?;
+
+        Ok(())
+    }
+
+    /// Files renamed since `commit`, and the fingerprints the warnings in
+    /// them had under their earlier paths.
+    fn renames_since(&mut self, commit: &str, warnings: &[Warning]) -> std::io::Result<(RenameMap, HashMap<String, String>)> {
+        let renames = git::renames_since(&self.working_dir, commit);
+        if renames.is_empty() {
+            return Ok((renames, HashMap::new()));
+        }
+        self.debug_log(&format!("Following {} files renamed since {}", renames.len(), short_commit(commit)))?;
+        let renamed = Fingerprinter::new(Some(&self.working_dir)).renamed_fingerprints(warnings, &renames);
+        Ok((renames, renamed))
+    }
+
+    /// Whether the run goes into the history database and baseline store.
+    /// Runs limited to a path or to the changes since a base ref never do:
+    /// their counts would show up as drops in the history. Neither do
+    /// in-memory runs, which write nothing.
+    fn records_run(&self) -> bool {
+        self.record_run && self.scope.is_none() && self.config.base.is_none() && self.artifacts.is_none()
+    }
+
+    /// Describes this run's analysis of `commit` for the baseline store.
+    fn baseline_meta(&self, commit: &str) -> BaselineMeta {
+        let (rustc_version, clippy_version) = match &self.repro {
+            Some(repro) => (repro.rustc_version.clone(), repro.clippy_version.clone()),
+            None => toolchain_versions(),
+        };
+        BaselineMeta {
+            branch: git::current_branch(&self.working_dir),
+            rustc_version,
+            clippy_version,
+            root: Some(self.working_dir.to_string_lossy().into_owned()),
+            ..BaselineMeta::new(commit)
+        }
+    }
+
+    /// Finds the stored analysis of `branch`'s head commit, in the baseline
+    /// store and then the history database. A missing baseline does not fail
+    /// the run; the reason the comparison was skipped is returned instead.
+    fn load_baseline(&mut self, branch: &str) -> std::io::Result<Result<Baseline, String>> {
+        let Some(commit) = git::branch_head(&self.working_dir, branch) else {
+            return Ok(Err(format!("Cannot resolve baseline branch {}; skipped the comparison.", branch)));
+        };
+
+        let mut found = None;
+        if let Some(dir) = &self.config.baseline_store {
+            let store = BaselineStore::new(dir);
+            if let Some(mut stored) = store.load(&commit)? {
+                stored.remap_paths();
+                let source = BaselineSource::Store(store.path_for(&commit));
+                found = Some(Baseline::from_warnings(branch, &commit, source, stored.warnings));
+            }
+        }
+        if found.is_none() {
+            found = self.history_baseline(branch, &commit)?;
+        }
+
+        Ok(match found {
+            Some(baseline) => Ok(baseline),
+            None => Err(format!(
+                "No stored analysis for {} at {}; skipped the comparison.",
+                branch,
+                short_commit(&commit)
+            )),
+        })
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn history_baseline(&self, branch: &str, commit: &str) -> std::io::Result<Option<Baseline>> {
+        let Some(path) = &self.config.history_db else { return Ok(None) };
+        let db = HistoryDb::open(path)?;
+        let Some(stats) = db.stats_for_commit(commit)? else { return Ok(None) };
+        Ok(Some(Baseline {
+            branch: branch.to_string(),
+            commit: commit.to_string(),
+            source: BaselineSource::History,
+            stats,
+            fingerprints: db.fingerprints_for_commit(commit)?,
+            warnings: Vec::new(),
+            by_message: false,
+        }))
+    }
 
+    #[cfg(not(feature = "sqlite"))]
+    fn history_baseline(&self, _branch: &str, _commit: &str) -> std::io::Result<Option<Baseline>> {
+        Ok(None)
+    }
+
+    fn write_baseline_summary(
+        &mut self,
+        baseline: &Baseline,
+        delta: &StatsDelta,
+        warnings: &[Warning],
+    ) -> std::io::Result<()> {
+        let mut summary = format!(
+            "📐 Compared with {} ({}): {:+} warnings ({} → {}), {} new lints, {} fixed",
+            baseline.branch,
+            short_commit(&baseline.commit),
+            delta.total.change(),
+            delta.total.previous,
+            delta.total.current,
+            delta.new_lints().len(),
+            delta.fixed_lints().len()
+        );
+        if let Some(diff) = baseline.warning_diff(warnings) {
+            summary.push_str(&format!("; {} new warnings, {} fixed", diff.new.len(), diff.fixed_count()));
+        }
+        summary.push('\n');
+        if delta.total.change() > 0 {
+            self.color_writer.write_warning(&summary)?;
+        } else {
+            self.color_writer.write_success(&summary)?;
+        }
+        writeln!(self.color_writer.writer())
+    }
+
+    /// Reads snippets relative to the working directory with the configured
+    /// context.
+    fn snippet_reader(&self) -> SnippetReader {
+        SnippetReader::new(&self.working_dir, self.config.snippet_context.unwrap_or(DEFAULT_CONTEXT_LINES))
+    }
+
+    /// Snippets of the `count` highest-priority warnings whose code can be read.
+    fn top_snippets<'a>(&self, warnings: &'a [Warning], count: usize) -> Vec<(&'a Warning, Snippet)> {
+        let mut by_priority: Vec<&Warning> = warnings.iter().collect();
+        by_priority.sort_by_key(|w| std::cmp::Reverse(w.priority.severity_score()));
+        let mut reader = self.snippet_reader();
+        by_priority
+            .into_iter()
+            .filter_map(|w| Some((w, reader.read(w)?)))
+            .take(count)
+            .collect()
+    }
+
+    /// Shows the code of the highest-priority warnings, highlighted.
+    fn write_snippets(&mut self, warnings: &[Warning]) -> std::io::Result<()> {
+        let snippets = self.top_snippets(warnings, MAX_TERMINAL_SNIPPETS);
+        if snippets.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.color_writer.writer(), "🔎 Top Warnings:\n")?;
+        let preview = FixPreview::new(DiffLayout::Unified, self.color_writer.writer().supports_color());
+        let links = EditorLinks::for_stdout(self.config.editor_link.as_deref(), &self.working_dir)?;
+        for (warning, snippet) in snippets {
+            let location = links.link(&format!("{}:{}", warning.file, warning.line), warning);
+            self.color_writer.write_colored(
+                &format!("{} at {} ({})\n", warning.lint_code, location, warning.priority),
+                termcolor::Color::Cyan,
+            )?;
+            writeln!(self.color_writer.writer(), "{}", warning.primary_message)?;
+            writeln!(self.color_writer.writer(), "{}", preview.render_snippet(&snippet))?;
+        }
+        Ok(())
+    }
+
+    /// Shows the fix examples matching the highest-priority warnings as
+    /// highlighted diffs.
+    fn write_fix_previews(&mut self, warnings: &[Warning], layout: DiffLayout) -> std::io::Result<()> {
+        let mut by_priority: Vec<&Warning> = warnings.iter().collect();
+        by_priority.sort_by_key(|w| std::cmp::Reverse(w.priority.severity_score()));
+
+        let mut examples: Vec<(FixExample, usize)> = Vec::new();
+        for warning in by_priority {
+            let Some(example) = self.fix_examples.get(warning) else { continue };
+            if let Some((_, count)) = examples.iter_mut().find(|(seen, _)| seen.description == example.description) {
+                *count += 1;
+            } else if examples.len() < MAX_FIX_PREVIEWS {
+                examples.push((example, 1));
+            }
+        }
+
+        writeln!(self.color_writer.writer(), "🔧 Fix Previews:\n")?;
+        if examples.is_empty() {
+            writeln!(self.color_writer.writer(), "No fix examples match these warnings.\n")?;
+            return Ok(());
+        }
+
+        let preview = FixPreview::new(layout, self.color_writer.writer().supports_color());
+        for (example, count) in examples {
+            self.color_writer.write_colored(
+                &format!("{} ({} warning{})\n", example.description, count, if count == 1 { "" } else { "s" }),
+                termcolor::Color::Cyan,
+            )?;
+            write!(self.color_writer.writer(), "{}", preview.render(&example.before, &example.after))?;
+            writeln!(self.color_writer.writer(), "{}\n", example.explanation)?;
+        }
+        Ok(())
+    }
+
+    fn write_alerts(&mut self, alerts: &[Alert]) -> std::io::Result<()> {
+        self.color_writer.write_warning("🔔 Alerts:\n")?;
+        for alert in alerts {
+            self.color_writer.write_colored(&format!("- {}\n", alert), termcolor::Color::Yellow)?;
+            self.events.on_alert(alert);
+        }
+        writeln!(self.color_writer.writer())?;
+        Ok(())
+    }
+
+    fn write_gate_verdict(&mut self, verdict: &GateVerdict) -> std::io::Result<()> {
+        if verdict.passed() {
+            self.color_writer.write_success("🚦 Gate: PASSED\n")?;
+        } else {
+            self.color_writer.write_error("🚦 Gate: FAILED\n")?;
+            for violation in &verdict.violations {
+                self.color_writer.write_colored(&format!("- {}\n", violation), termcolor::Color::Red)?;
+            }
+        }
+        writeln!(self.color_writer.writer())?;
         Ok(())
     }
 
@@ -435,6 +1753,67 @@ impl AnalysisRunner {
             &format!("- Raw warnings from clippy: {} warnings\n", stats.total_input_warnings),
             termcolor::Color::White,
         )?;
+        if stats.duplicates_removed > 0 {
+            self.color_writer.write_colored(
+                &format!("- Duplicates removed: {} warnings reported by more than one target\n", stats.duplicates_removed),
+                termcolor::Color::White,
+            )?;
+        }
+        if stats.generated_excluded > 0 {
+            self.color_writer.write_colored(
+                &format!("- Generated and vendored code: {} warnings excluded\n", stats.generated_excluded),
+                termcolor::Color::White,
+            )?;
+        }
+        if stats.third_party_warnings > 0 {
+            self.color_writer.write_colored(
+                &format!("- Third-party code: {} warnings (reported separately, excluded from totals and gates)\n", stats.third_party_warnings),
+                termcolor::Color::White,
+            )?;
+        }
+        if stats.suppressed_warnings > 0 {
+            self.color_writer.write_colored(
+                &format!("- Suppressed by ignore rules: {} warnings\n", stats.suppressed_warnings),
+                termcolor::Color::White,
+            )?;
+        }
+        if stats.false_positives > 0 {
+            self.color_writer.write_colored(
+                &format!("- Suspected false positives: {} warnings (excluded from totals and gates)\n", stats.false_positives),
+                termcolor::Color::White,
+            )?;
+        }
+        let by_source = |source| stats.by_source.get(&source).copied().unwrap_or(0);
+        self.color_writer.write_colored(
+            &format!("- From clippy: {}, from rustc: {}\n", by_source(Source::Clippy), by_source(Source::Rustc)),
+            termcolor::Color::White,
+        )?;
+        if by_source(Source::Rustfmt) > 0 {
+            self.color_writer.write_colored(
+                &format!("- Formatting deviations from rustfmt: {}\n", by_source(Source::Rustfmt)),
+                termcolor::Color::White,
+            )?;
+        }
+        if by_source(Source::External) > 0 {
+            self.color_writer.write_colored(
+                &format!("- From external tools: {}\n", by_source(Source::External)),
+                termcolor::Color::White,
+            )?;
+        }
+        if stats.errors > 0 {
+            self.color_writer.write_error(&format!("- Compile errors: {} (the build failed)\n", stats.errors))?;
+        }
+        if stats.low_confidence > 0 {
+            let lints: Vec<String> = stats.top_low_confidence_lints(LOW_CONFIDENCE_LINTS_SHOWN)
+                .iter()
+                .map(|(lint, count)| format!("{} ({})", lint, count))
+                .collect();
+            self.color_writer.write_warning(&format!(
+                "- Low-confidence categorizations: {} ({})\n",
+                stats.low_confidence,
+                lints.join(", ")
+            ))?;
+        }
         writeln!(self.color_writer.writer())?;
 
         // Validation stats
@@ -473,6 +1852,7 @@ impl AnalysisRunner {
             (CategoryType::Performance, "🟡", termcolor::Color::Yellow),
             (CategoryType::Documentation, "🟢", termcolor::Color::Cyan),
             (CategoryType::Style, "⚪", termcolor::Color::Blue),
+            (CategoryType::Uncategorized, "⚫", termcolor::Color::White),
         ];
 
         for (category, icon, color) in ordered_categories {
@@ -486,6 +1866,8 @@ impl AnalysisRunner {
             }
         }
 
+        self.write_priority_chart(stats)?;
+
         // Top subcategories
         writeln!(self.color_writer.writer(), "\n🔍 Top Warning Types:")?;
         let mut subcategories: Vec<_> = stats.by_subcategory.iter().collect();
@@ -502,5 +1884,358 @@ impl AnalysisRunner {
         Ok(())
     }
 
+    /// Bar chart of the priorities from Critical to Trivial, each bar in its
+    /// severity's color.
+    fn write_priority_chart(&mut self, stats: &WarningStatistics) -> std::io::Result<()> {
+        if stats.total_warnings == 0 {
+            return Ok(());
+        }
+        writeln!(self.color_writer.writer(), "\n🚨 Priority Distribution:")?;
+        let priorities = [
+            (Priority::Critical, termcolor::Color::Red),
+            (Priority::High, termcolor::Color::Magenta),
+            (Priority::Medium, termcolor::Color::Yellow),
+            (Priority::Low, termcolor::Color::Blue),
+            (Priority::Trivial, termcolor::Color::White),
+        ];
+        let data: Vec<(String, usize)> = priorities
+            .iter()
+            .map(|(priority, _)| {
+                let count = stats.by_priority.get(priority).copied().unwrap_or(0);
+                (format!("{} ({})", priority, count), count)
+            })
+            .collect();
+        let chart = create_enhanced_chart(&data, ChartConfig::new(ChartStyle::Basic, PRIORITY_CHART_WIDTH, true));
+        for (line, (_, color)) in chart.lines().zip(priorities) {
+            self.color_writer.write_colored(&format!("{}\n", line), color)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where a runner reads the trends of earlier runs and records new ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryBackend {
+    /// `clippy_historical.json` in the working directory, which is read but
+    /// not written
+    TrendsFile,
+    /// A history database of runs; needs the `sqlite` feature
+    Database(PathBuf),
+}
+
+/// Configures an [`AnalysisRunner`], created with [`AnalysisRunner::builder`].
+///
+/// Settings given here take precedence over the same settings of
+/// [`config`](Self::config), whatever the order of the calls. Inputs that
+/// depend on the run, like the completeness of the clippy output or a
+/// cargo-udeps report, are still passed with the runner's setters.
+pub struct AnalysisRunnerBuilder {
+    reports_dir: Option<PathBuf>,
+    working_dir: Option<PathBuf>,
+    timestamp: Option<String>,
+    config: AnalyzerConfig,
+    artifacts: Option<Vec<Artifact>>,
+    color: ColorChoice,
+    history: Option<HistoryBackend>,
+    record_run: bool,
+    ignore: Vec<String>,
+    generated: Vec<String>,
+    scope: Option<PathScope>,
+    jobs: usize,
+    progress: Option<Box<dyn ProgressReporter>>,
+    log: Option<Logger>,
+    events: Option<Box<dyn AnalysisEvents>>,
+    in_memory: bool,
+}
+
+impl AnalysisRunnerBuilder {
+    fn new() -> Self {
+        Self {
+            reports_dir: None,
+            working_dir: None,
+            timestamp: None,
+            config: AnalyzerConfig::default(),
+            artifacts: None,
+            color: ColorChoice::Auto,
+            history: None,
+            record_run: true,
+            ignore: Vec::new(),
+            generated: Vec::new(),
+            scope: None,
+            jobs: 1,
+            progress: None,
+            log: None,
+            events: None,
+            in_memory: false,
+        }
+    }
+
+    /// Directory the reports are written to; the current directory by
+    /// default.
+    pub fn reports_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.reports_dir = Some(dir.into());
+        self
+    }
+
+    /// Root of the analyzed workspace; the current directory by default.
+    pub fn working_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.working_dir = Some(dir.into());
+        self
+    }
+
+    /// Timestamp in the names of the report files; the current local time
+    /// by default.
+    pub fn timestamp(mut self, timestamp: impl Into<String>) -> Self {
+        self.timestamp = Some(timestamp.into());
+        self
+    }
+
+    pub fn config(mut self, config: AnalyzerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Writes only these reports, and enables those that are off by default.
+    pub fn artifacts(mut self, artifacts: Vec<Artifact>) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+
+    /// Whether terminal output is colored; detected from the terminal by
+    /// default.
+    pub fn color(mut self, choice: ColorChoice) -> Self {
+        self.color = choice;
+        self
+    }
+
+    pub fn history(mut self, backend: HistoryBackend) -> Self {
+        self.history = Some(backend);
+        self
+    }
+
+    /// Whether the run is recorded in the history database and baseline
+    /// store; on by default.
+    pub fn record_run(mut self, record: bool) -> Self {
+        self.record_run = record;
+        self
+    }
+
+    /// Adds an ignore rule, in the syntax of the `ignore` config key.
+    pub fn ignore(mut self, rule: impl Into<String>) -> Self {
+        self.ignore.push(rule.into());
+        self
+    }
+
+    /// Excludes files matching `glob` as generated code.
+    pub fn generated(mut self, glob: impl Into<String>) -> Self {
+        self.generated.push(glob.into());
+        self
+    }
+
+    /// Restricts statistics and reports to warnings within `scope`.
+    pub fn scope(mut self, scope: PathScope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Number of threads used to parse the clippy output.
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    pub fn progress_reporter(mut self, progress: Box<dyn ProgressReporter>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Diagnostic logger. A quiet logger also silences terminal output;
+    /// only the [summary line](AnalysisRunner::summary) is left to show.
+    pub fn logger(mut self, log: Logger) -> Self {
+        self.log = Some(log);
+        self
+    }
+
+    /// Reports progress, parsed warnings, and generated reports through
+    /// `events` and writes nothing to the terminal, whatever the color
+    /// choice. Progress is only shown if a reporter is set explicitly.
+    pub fn events(mut self, events: Box<dyn AnalysisEvents>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Keeps the reports in [`AnalysisReport::artifacts`] instead of writing
+    /// them, and leaves the history database and baseline store alone; off
+    /// by default. The reports directory only names the files.
+    pub fn in_memory(mut self, in_memory: bool) -> Self {
+        self.in_memory = in_memory;
+        self
+    }
+
+    /// Creates the runner, failing on invalid ignore rules, generated code
+    /// globs, or severity overrides.
+    pub fn build(self) -> std::io::Result<AnalysisRunner> {
+        let mut config = self.config;
+        if let Some(artifacts) = self.artifacts {
+            config.artifacts = Some(artifacts);
+            config.enable_selected_artifacts();
+        }
+        match self.history {
+            Some(HistoryBackend::TrendsFile) => config.history_db = None,
+            Some(HistoryBackend::Database(path)) => config.history_db = Some(path),
+            None => {}
+        }
+        config.ignore.extend(self.ignore);
+        config.generated.extend(self.generated);
+        AnalysisOptions::from_config(&config)?;
+
+        let silent = self.events.is_some() || self.log.as_ref().is_some_and(|log| log.verbosity().is_quiet());
+        let progress = match self.progress {
+            Some(progress) => progress,
+            None if self.events.is_some() => Box::new(NoProgress),
+            None => default_progress_reporter(),
+        };
+        Ok(AnalysisRunner {
+            color_writer: if silent { ColorWriter::silent() } else { ColorWriter::with_choice(self.color) },
+            timestamp: self.timestamp.unwrap_or_else(|| chrono::Local::now().format("%Y%m%d_%H%M%S").to_string()),
+            reports_dir: self.reports_dir,
+            working_dir: match self.working_dir {
+                Some(dir) => dir,
+                None => std::env::current_dir()?,
+            },
+            log: self.log.unwrap_or_default(),
+            config,
+            jobs: self.jobs,
+            progress,
+            events: self.events.unwrap_or_else(|| Box::new(NoEvents)),
+            completeness: Completeness::Complete,
+            repro: None,
+            scope: self.scope,
+            post_processors: Vec::new(),
+            unused_deps: None,
+            hidden_code: None,
+            geiger: None,
+            build_impact: None,
+            fix_examples: FixExampleRegistry::default(),
+            playbooks: Vec::new(),
+            lazy_summary: false,
+            trend_chart: false,
+            previous_fingerprints: None,
+            record_run: self.record_run,
+            artifacts: self.in_memory.then(ArtifactStore::default),
+            summary: None,
+        })
+    }
+}
+
+/// What the reports of a run are generated from.
+struct ReportInputs<'a> {
+    warnings: &'a [Warning],
+    file_warnings: &'a HashMap<String, FileWarnings>,
+    stats: &'a WarningStatistics,
+    trends: &'a TrendAnalysis,
+    historical_trends: &'a [TrendAnalysis],
+    context: &'a [AnalysisContext],
+    charts: &'a SvgCharts,
+    /// Alt text and file name of each chart written
+    chart_images: &'a [(&'a str, String)],
+}
+
+/// The report files `generate_reports` fills in; `None` for reports left
+/// out of the artifact selection.
+struct ReportFiles<'a> {
+    report: Option<&'a mut ReportFile>,
+    summary: Option<&'a mut ReportFile>,
+    markdown: Option<&'a mut ReportFile>,
+    json: Option<&'a mut ReportFile>,
+    csv: Option<&'a mut ReportFile>,
+    fix_plan: Option<&'a mut ReportFile>,
+}
 
-} 
\ No newline at end of file
+/// The last `runs` of `trends`.
+fn last_runs(mut trends: Vec<TrendAnalysis>, runs: usize) -> Vec<TrendAnalysis> {
+    trends.drain(..trends.len().saturating_sub(runs));
+    trends
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o111);
+    std::fs::set_permissions(path, permissions)
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_settings() {
+        let runner = AnalysisRunner::builder()
+            .reports_dir("reports")
+            .working_dir("/work/demo")
+            .timestamp("20240101_120000")
+            .artifacts(vec![Artifact::Summary, Artifact::Jsonl])
+            .history(HistoryBackend::Database(PathBuf::from("history.db")))
+            .ignore("clippy::module_name_repetitions")
+            .generated("src/bindings/**")
+            .record_run(false)
+            .build()
+            .unwrap();
+        assert_eq!(runner.reports_dir, Some(PathBuf::from("reports")));
+        assert_eq!(runner.working_dir, PathBuf::from("/work/demo"));
+        assert_eq!(runner.timestamp, "20240101_120000");
+        assert!(runner.config.jsonl);
+        assert!(runner.config.writes(Artifact::Summary));
+        assert!(!runner.config.writes(Artifact::Report));
+        assert_eq!(runner.config.history_db, Some(PathBuf::from("history.db")));
+        assert_eq!(runner.config.ignore, ["clippy::module_name_repetitions"]);
+        assert_eq!(runner.config.generated, ["src/bindings/**"]);
+        assert!(!runner.record_run);
+
+        let config = AnalyzerConfig { base: Some("main".to_string()), ..AnalyzerConfig::default() };
+        let runner = AnalysisRunner::builder().config(config).build().unwrap();
+        assert!(runner.record_run && !runner.records_run());
+
+        assert!(AnalysisRunner::builder().ignore("category:nonsense").build().is_err());
+    }
+
+    #[test]
+    fn test_in_memory_run() {
+        let temp = tempfile::tempdir().unwrap();
+        let reports_dir = temp.path().join("reports");
+        let config = AnalyzerConfig { badge: true, ..AnalyzerConfig::default() };
+        let mut runner = AnalysisRunner::builder()
+            .reports_dir(&reports_dir)
+            .working_dir(temp.path())
+            .timestamp("20240101_120000")
+            .config(config)
+            .artifacts(vec![Artifact::Json, Artifact::Csv, Artifact::Badge])
+            .color(ColorChoice::Never)
+            .progress_reporter(Box::new(NoProgress))
+            .in_memory(true)
+            .build()
+            .unwrap();
+        let output = r#"{"reason":"compiler-message","message":{"code":{"code":"clippy::len_zero"},"level":"warning","message":"length comparison to zero","spans":[{"file_name":"src/lib.rs","line_start":3,"line_end":3,"column_start":8,"column_end":20,"is_primary":true}],"children":[],"rendered":null}}"#;
+        let report = runner.analyze("clippy.json", WarningParser::new().parse_str(output, 1)).unwrap();
+
+        assert!(!reports_dir.exists());
+        assert_eq!(std::fs::read_dir(temp.path()).unwrap().count(), 0);
+        assert_eq!(report.file("warnings_json"), Some(reports_dir.join("clippy_warnings_json_20240101_120000.json").as_path()));
+        let json: serde_json::Value = serde_json::from_slice(report.contents("warnings_json").unwrap()).unwrap();
+        assert_eq!(json[0]["id"], "clippy::len_zero");
+        assert!(report.contents("badge").is_some_and(|svg| svg.starts_with(b"<svg")));
+
+        let manifest: serde_json::Value = serde_json::from_slice(report.contents("manifest").unwrap()).unwrap();
+        let listed: Vec<&str> = manifest["artifacts"].as_array().unwrap().iter().map(|a| a["type"].as_str().unwrap()).collect();
+        assert_eq!(listed, ["warnings_json", "warnings_csv", "badge", "badge_endpoint"]);
+        assert_eq!(report.summary, format!("1 warnings in 1 files (0 critical, 0 high); reports in {}", reports_dir.display()));
+        assert_eq!(runner.summary(), Some(report.summary.as_str()));
+    }
+}
@@ -0,0 +1,180 @@
+//! Local performance benchmark.
+//!
+//! `cargo analyzer bench` times the analysis on synthetic clippy output of
+//! a given number of warnings: parsing, the in-memory pipeline, and writing
+//! the JSON, markdown, HTML, and fix plan reports (to a sink, so disk speed
+//! does not count). Nothing is collected or sent anywhere; results are only
+//! printed.
+
+use std::fmt::Write as _;
+use std::io;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use crate::analysis::{
+    clusters::cluster_warnings,
+    pipeline::{AnalysisOptions, analyze},
+    trends::{TrendAnalysis, recurring_issues},
+};
+use crate::output::{
+    fix_plan::FixPlanGenerator,
+    html::{HtmlWriter, generate_html_report},
+    json_export::write_json,
+    markdown::generate_markdown_report,
+    svg_charts::SvgCharts,
+};
+use crate::parser::WarningParser;
+use crate::types::Completeness;
+
+/// Workload sizes benchmarked when none are given
+pub const DEFAULT_SIZES: [&str; 2] = ["10k", "100k"];
+
+const LINTS: [&str; 6] = [
+    "clippy::needless_borrow",
+    "clippy::redundant_clone",
+    "clippy::unwrap_used",
+    "clippy::missing_errors_doc",
+    "clippy::box_collection",
+    "clippy::use_self",
+];
+
+/// Number of synthetic warnings, written as a count with an optional `k`
+/// or `m` suffix (`10k`, `1m`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkloadSize(pub usize);
+
+impl FromStr for WorkloadSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        let (digits, factor) = match lower.strip_suffix('k') {
+            Some(digits) => (digits, 1_000),
+            None => match lower.strip_suffix('m') {
+                Some(digits) => (digits, 1_000_000),
+                None => (lower.as_str(), 1),
+            },
+        };
+        match digits.parse::<usize>().ok().and_then(|count| count.checked_mul(factor)) {
+            Some(count) if count > 0 => Ok(WorkloadSize(count)),
+            _ => Err(format!("Invalid workload size '{}', expected e.g. 10k or 1m", s)),
+        }
+    }
+}
+
+/// Synthetic clippy JSON output with `warnings` diagnostics spread over
+/// 97 files and six lints.
+pub fn synthetic_workload(warnings: usize) -> String {
+    let mut output = String::new();
+    for i in 0..warnings {
+        let line = i % 500 + 1;
+        let _ = writeln!(
+            output,
+            r#"{{"reason":"compiler-message","package_id":"bench 0.1.0","message":{{"code":{{"code":"{lint}"}},"level":"warning","message":"synthetic warning {i}","spans":[{{"file_name":"src/module_{file}.rs","line_start":{line},"line_end":{line},"column_start":5,"column_end":20}}],"children":[{{"code":null,"level":"help","message":"for further information visit https://rust-lang.github.io/rust-clippy","spans":[],"children":[],"rendered":null}}],"rendered":"warning: synthetic warning\n  = help: consider fixing this\n"}}}}"#,
+            lint = LINTS[i % LINTS.len()],
+            i = i,
+            file = i % 97,
+            line = line,
+        );
+    }
+    output
+}
+
+/// Timings of one workload.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchResult {
+    pub warnings: usize,
+    pub parse: Duration,
+    pub analyze: Duration,
+    pub reports: Duration,
+}
+
+impl BenchResult {
+    pub fn total(&self) -> Duration {
+        self.parse + self.analyze + self.reports
+    }
+
+    /// Parsed warnings per second.
+    pub fn parse_throughput(&self) -> f64 {
+        self.warnings as f64 / self.parse.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Generates and analyzes a workload of `size` warnings, parsing on `jobs`
+/// threads.
+pub fn run_bench(size: WorkloadSize, jobs: usize) -> io::Result<BenchResult> {
+    let output = synthetic_workload(size.0);
+
+    let start = Instant::now();
+    let parsed = WarningParser::new().parse_str(&output, jobs);
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let analysis = analyze(parsed, &AnalysisOptions::new());
+    let analyze = start.elapsed();
+
+    let start = Instant::now();
+    let stats = &analysis.stats;
+    let trend = TrendAnalysis::new(
+        stats.total_warnings,
+        stats.by_category.clone(),
+        stats.by_priority.clone(),
+        recurring_issues(&analysis.warnings),
+    );
+    write_json(io::sink(), &analysis.warnings)?;
+    generate_markdown_report(
+        io::sink(),
+        stats,
+        &trend,
+        &[],
+        &analysis.context,
+        &[],
+        &cluster_warnings(&analysis.warnings),
+        None,
+    )?;
+    let charts = SvgCharts::new(stats, &[]);
+    generate_html_report(HtmlWriter::new(io::sink()), stats, &analysis.warnings, &Completeness::Complete, &charts, &[])?;
+    FixPlanGenerator::new(io::sink()).generate_plan(&analysis.warnings)?;
+    let reports = start.elapsed();
+
+    Ok(BenchResult { warnings: analysis.warnings.len(), parse, analyze, reports })
+}
+
+/// Formats results as an aligned table.
+pub fn format_results(results: &[BenchResult]) -> String {
+    let mut table = format!(
+        "{:>10}  {:>10}  {:>14}  {:>10}  {:>10}  {:>10}\n",
+        "warnings", "parse", "parse/s", "analyze", "reports", "total"
+    );
+    for result in results {
+        let _ = writeln!(
+            table,
+            "{:>10}  {:>10.2?}  {:>14.0}  {:>10.2?}  {:>10.2?}  {:>10.2?}",
+            result.warnings,
+            result.parse,
+            result.parse_throughput(),
+            result.analyze,
+            result.reports,
+            result.total()
+        );
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_workload() {
+        assert_eq!("10k".parse(), Ok(WorkloadSize(10_000)));
+        assert_eq!("1M".parse(), Ok(WorkloadSize(1_000_000)));
+        assert_eq!("250".parse(), Ok(WorkloadSize(250)));
+        assert!("0".parse::<WorkloadSize>().is_err());
+        assert!("ten".parse::<WorkloadSize>().is_err());
+        assert!(format!("{}m", usize::MAX).parse::<WorkloadSize>().is_err());
+
+        let result = run_bench(WorkloadSize(200), 2).unwrap();
+        assert_eq!(result.warnings, 200);
+        assert!(format_results(&[result]).lines().nth(1).unwrap().trim_start().starts_with("200"));
+    }
+}
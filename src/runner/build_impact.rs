@@ -0,0 +1,44 @@
+//! Measuring binary size and compile time for the performance impact
+//! annotations.
+
+use std::path::Path;
+use super::cargo::cargo_command;
+use crate::analysis::impact::BuildImpact;
+
+/// Runs `cargo bloat --release --message-format json -n 0` and a timed
+/// nightly build, `cargo +nightly build --timings=json`, in `root`.
+///
+/// Either measurement is skipped when cargo-bloat or the nightly toolchain
+/// is missing; returns `None` when both are. cargo-bloat measures a single
+/// binary, so `--workspace` and `--all-targets` are not passed on to it.
+///
+/// The nightly build runs in a target directory of its own, removed
+/// afterwards: cargo reports timings only for crates it compiles, so a warm
+/// target directory would leave them out, and the user's would be rebuilt
+/// with the nightly toolchain.
+pub fn measure_build_impact(root: &Path, cargo_args: &[String]) -> Option<BuildImpact> {
+    let bloat = cargo_command(root)
+        .args(["bloat", "--release", "--message-format", "json", "-n", "0"])
+        .args(cargo_args.iter().filter(|arg| *arg != "--workspace" && *arg != "--all-targets"))
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|stdout| BuildImpact::from_bloat_json(&stdout).ok());
+    let mut impact = bloat.unwrap_or_default();
+
+    let target_dir = std::env::temp_dir().join(format!("cargo-analyzer-timings-{}", std::process::id()));
+    let timings = cargo_command(root)
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .args(["+nightly", "build", "-Zunstable-options", "--timings=json", "--message-format", "json"])
+        .args(cargo_args)
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    let _ = std::fs::remove_dir_all(&target_dir);
+    if let Some(stdout) = timings {
+        impact.add_timings(&stdout);
+    }
+
+    (!impact.is_empty()).then_some(impact)
+}
@@ -0,0 +1,187 @@
+//! Cooperative cancellation of a running analysis.
+//!
+//! Ctrl-C sets a shared flag instead of killing the process, so the workflow
+//! can stop clippy and still write reports for what was processed. A second
+//! Ctrl-C exits immediately. The handler is installed once per process and
+//! cancels the token of the latest run, so a watch loop can start run after
+//! run.
+
+use std::fmt;
+use std::io;
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Exit status conventionally used after SIGINT.
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Shared flag signalling that the current run should stop early.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Token of the latest run, cancelled by the Ctrl-C handler
+static CURRENT_TOKEN: Mutex<Option<CancellationToken>> = Mutex::new(None);
+
+/// Returns a token that is cancelled on the next Ctrl-C, replacing the
+/// token of any earlier run.
+pub fn cancel_on_interrupt() -> io::Result<CancellationToken> {
+    static HANDLER: OnceLock<Result<(), String>> = OnceLock::new();
+    let token = CancellationToken::new();
+    *CURRENT_TOKEN.lock().unwrap_or_else(PoisonError::into_inner) = Some(token.clone());
+    HANDLER
+        .get_or_init(|| {
+            ctrlc::set_handler(|| {
+                let current = CURRENT_TOKEN.lock().unwrap_or_else(PoisonError::into_inner).clone();
+                match current {
+                    Some(token) if !token.is_cancelled() => token.cancel(),
+                    _ => std::process::exit(INTERRUPTED_EXIT_CODE),
+                }
+            })
+            .map_err(|e| e.to_string())
+        })
+        .clone()
+        .map_err(io::Error::other)?;
+    Ok(token)
+}
+
+/// Whether Ctrl-C cancelled the latest run.
+pub fn interrupted() -> bool {
+    CURRENT_TOKEN
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+        .is_some_and(CancellationToken::is_cancelled)
+}
+
+/// Why a child process was stopped early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    Interrupted,
+    TimedOut(Duration),
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopReason::Interrupted => write!(f, "interrupted"),
+            StopReason::TimedOut(limit) => write!(f, "timed out after {}s", limit.as_secs()),
+        }
+    }
+}
+
+/// Kills `child` when `token` is cancelled or `timeout` elapses.
+///
+/// The watcher exits once `done` is set. Joining it yields the reason the
+/// child was killed, if it was.
+pub fn watch_child(
+    child: Arc<Mutex<Child>>,
+    token: CancellationToken,
+    timeout: Option<Duration>,
+    done: Arc<AtomicBool>,
+) -> JoinHandle<Option<StopReason>> {
+    let started = Instant::now();
+    thread::spawn(move || {
+        while !done.load(Ordering::SeqCst) {
+            let reason = match timeout {
+                _ if token.is_cancelled() => Some(StopReason::Interrupted),
+                Some(limit) if started.elapsed() >= limit => Some(StopReason::TimedOut(limit)),
+                _ => None,
+            };
+            if let Some(reason) = reason {
+                if let Ok(mut child) = child.lock() {
+                    let _ = child.kill();
+                }
+                return Some(reason);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+        None
+    })
+}
+
+/// Waits for `child` without holding its lock, so a watcher can still kill it.
+pub fn wait_child(child: &Mutex<Child>) -> io::Result<std::process::ExitStatus> {
+    loop {
+        let status = child
+            .lock()
+            .map_err(|_| io::Error::other("child process lock poisoned"))?
+            .try_wait()?;
+        if let Some(status) = status {
+            return Ok(status);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn sleeping_child() -> Arc<Mutex<Child>> {
+        Arc::new(Mutex::new(Command::new("sleep").arg("30").spawn().unwrap()))
+    }
+
+    #[test]
+    fn test_token_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn test_watch_child_kills_on_cancel() {
+        let child = sleeping_child();
+        let token = CancellationToken::new();
+        let done = Arc::new(AtomicBool::new(false));
+        let watcher = watch_child(child.clone(), token.clone(), None, done);
+
+        token.cancel();
+        assert_eq!(watcher.join().unwrap(), Some(StopReason::Interrupted));
+        assert!(!wait_child(&child).unwrap().success());
+    }
+
+    #[test]
+    fn test_watch_child_kills_on_timeout() {
+        let child = sleeping_child();
+        let limit = Duration::from_millis(100);
+        let done = Arc::new(AtomicBool::new(false));
+        let watcher = watch_child(child.clone(), CancellationToken::new(), Some(limit), done);
+
+        let reason = watcher.join().unwrap();
+        assert_eq!(reason, Some(StopReason::TimedOut(limit)));
+        assert!(!wait_child(&child).unwrap().success());
+        assert_eq!(StopReason::TimedOut(Duration::from_secs(90)).to_string(), "timed out after 90s");
+    }
+
+    #[test]
+    fn test_watch_child_stops_when_done() {
+        let child = Arc::new(Mutex::new(Command::new("true").spawn().unwrap()));
+        let done = Arc::new(AtomicBool::new(false));
+        let watcher = watch_child(child.clone(), CancellationToken::new(), None, done.clone());
+
+        assert!(wait_child(&child).unwrap().success());
+        done.store(true, Ordering::SeqCst);
+        assert_eq!(watcher.join().unwrap(), None);
+    }
+}
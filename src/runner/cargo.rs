@@ -0,0 +1,73 @@
+//! Cargo invocations against the analyzed workspace.
+//!
+//! Cargo runs in the workspace root, which need not be the directory the
+//! analyzer was started in (`--working-dir`, `--manifest-path`). A relative
+//! `CARGO_TARGET_DIR` is resolved against the starting directory first, so
+//! builds land where the user's own cargo commands put them instead of
+//! rebuilding from scratch in a second target directory.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A `cargo` command running in `root`.
+pub fn cargo_command(root: &Path) -> Command {
+    let mut command = Command::new("cargo");
+    command.current_dir(root);
+    if let Some(target_dir) = target_dir() {
+        command.env("CARGO_TARGET_DIR", target_dir);
+    }
+    command
+}
+
+/// `CARGO_TARGET_DIR` as an absolute path, if set.
+pub fn target_dir() -> Option<PathBuf> {
+    let dir = PathBuf::from(std::env::var_os("CARGO_TARGET_DIR").filter(|dir| !dir.is_empty())?);
+    if dir.is_absolute() {
+        return Some(dir);
+    }
+    std::env::current_dir().ok().map(|cwd| cwd.join(dir))
+}
+
+/// Target directory of the workspace at `root`: `CARGO_TARGET_DIR`, or
+/// `target` in the root.
+pub fn workspace_target_dir(root: &Path) -> PathBuf {
+    target_dir().unwrap_or_else(|| root.join("target"))
+}
+
+/// Root directory of the workspace `manifest_path` belongs to.
+pub fn workspace_root(manifest_path: &Path) -> io::Result<PathBuf> {
+    let output = Command::new("cargo")
+        .args(["locate-project", "--workspace", "--message-format", "plain", "--manifest-path"])
+        .arg(manifest_path)
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "{} is not part of a cargo workspace: {}",
+                manifest_path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ));
+    }
+    let manifest = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    manifest
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| io::Error::other(format!("cargo located the workspace at {}", manifest.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_root_of_manifest() {
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let root = workspace_root(&crate_dir.join("Cargo.toml")).unwrap();
+        assert!(crate_dir.starts_with(&root));
+        assert!(root.join("Cargo.toml").exists());
+        assert!(workspace_root(&crate_dir.join("missing/Cargo.toml")).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+//! Exit code semantics for `--check` mode.
+//!
+//! Check mode writes no report files; the result is conveyed entirely through
+//! the process exit code so it can drive pre-commit hooks and scripts.
+
+use crate::{
+    analysis::statistics::warning::WarningStatistics,
+    types::{CategoryType, Priority},
+};
+
+/// Exit code for failures of the analyzer itself (clippy could not run,
+/// output was incomplete, invalid configuration, ...).
+pub const TOOL_ERROR_EXIT_CODE: i32 = 3;
+
+/// Result of a `--check` run, ordered from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckOutcome {
+    /// No warnings after ignore rules were applied.
+    Clean,
+    /// Warnings present, none critical or safety related.
+    Warnings,
+    /// At least one critical priority or safety category warning.
+    Critical,
+}
+
+impl CheckOutcome {
+    pub fn from_stats(stats: &WarningStatistics) -> Self {
+        let critical = stats.by_priority.get(&Priority::Critical).copied().unwrap_or(0);
+        let safety = stats.by_category.get(&CategoryType::Safety).copied().unwrap_or(0);
+        if critical > 0 || safety > 0 {
+            CheckOutcome::Critical
+        } else if stats.total_warnings > 0 {
+            CheckOutcome::Warnings
+        } else {
+            CheckOutcome::Clean
+        }
+    }
+
+    pub fn exit_code(self) -> i32 {
+        match self {
+            CheckOutcome::Clean => 0,
+            CheckOutcome::Warnings => 1,
+            CheckOutcome::Critical => 2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(total: usize, critical: usize, safety: usize) -> WarningStatistics {
+        let mut stats = WarningStatistics { total_warnings: total, ..Default::default() };
+        stats.by_priority.insert(Priority::Critical, critical);
+        stats.by_category.insert(CategoryType::Safety, safety);
+        stats
+    }
+
+    #[test]
+    fn test_outcome_from_stats() {
+        assert_eq!(CheckOutcome::from_stats(&WarningStatistics::default()), CheckOutcome::Clean);
+        assert_eq!(CheckOutcome::from_stats(&stats(3, 0, 0)), CheckOutcome::Warnings);
+        assert_eq!(CheckOutcome::from_stats(&stats(3, 1, 0)), CheckOutcome::Critical);
+        assert_eq!(CheckOutcome::from_stats(&stats(3, 0, 1)), CheckOutcome::Critical);
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(CheckOutcome::Clean.exit_code(), 0);
+        assert_eq!(CheckOutcome::Warnings.exit_code(), 1);
+        assert_eq!(CheckOutcome::Critical.exit_code(), 2);
+        // Tool failures never look like an analysis result
+        assert_eq!(TOOL_ERROR_EXIT_CODE, 3);
+        assert!(CheckOutcome::Clean < CheckOutcome::Warnings && CheckOutcome::Warnings < CheckOutcome::Critical);
+    }
+}
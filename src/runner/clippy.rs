@@ -0,0 +1,236 @@
+//! Clippy as a warning source.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{ChildStdout, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use super::cargo::cargo_command;
+use super::cancel::{cancel_on_interrupt, wait_child, watch_child};
+use crate::analysis::plugin::{SourceContext, WarningSource};
+use crate::output::progress::{ProgressReporter, Stage};
+use crate::parser::{AnalysisContext, ParseResult, WarningParser, read_line_lossy};
+use crate::types::{Completeness, Warning};
+
+/// Runs `cargo clippy --message-format=json` and parses its diagnostics.
+///
+/// Ctrl-C and the timeout stop clippy early; the run is then marked partial
+/// instead of failing, so reports can still be written. A build that fails
+/// with compile errors is complete: the errors are its result.
+pub struct ClippySource {
+    output_path: Option<PathBuf>,
+    jobs: usize,
+    timeout: Option<Duration>,
+    quiet: bool,
+    clippy_args: Vec<String>,
+    conf_dir: Option<PathBuf>,
+}
+
+impl ClippySource {
+    pub fn new() -> Self {
+        Self { output_path: None, jobs: 1, timeout: None, quiet: false, clippy_args: Vec::new(), conf_dir: None }
+    }
+
+    /// Saves clippy's raw output to `path`.
+    pub fn set_output_path(&mut self, path: PathBuf) {
+        self.output_path = Some(path);
+    }
+
+    /// Parses saved output with `jobs` threads. Streamed output is always
+    /// parsed on one thread.
+    pub fn set_jobs(&mut self, jobs: usize) {
+        self.jobs = jobs;
+    }
+
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Discards clippy's stderr.
+    pub fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+
+    /// Arguments for clippy itself, passed after `--`, e.g. `-D warnings`.
+    pub fn set_clippy_args(&mut self, clippy_args: Vec<String>) {
+        self.clippy_args = clippy_args;
+    }
+
+    /// Reads `clippy.toml` from `dir` instead of the workspace, through
+    /// `CLIPPY_CONF_DIR`.
+    pub fn set_conf_dir(&mut self, dir: PathBuf) {
+        self.conf_dir = Some(dir);
+    }
+
+    /// The clippy invocation in `root`, the workspace root.
+    pub fn command(&self, root: &Path, cargo_args: &[String]) -> Command {
+        let mut command = cargo_command(root);
+        command
+            .args(["clippy"])
+            .args(cargo_args)
+            .args(["--message-format=json"]);
+        if !self.clippy_args.is_empty() {
+            command.arg("--").args(&self.clippy_args);
+        }
+        if let Some(dir) = &self.conf_dir {
+            command.env("CLIPPY_CONF_DIR", dir);
+        }
+        if self.quiet {
+            command.stderr(Stdio::null());
+        }
+        command
+    }
+
+    /// Runs clippy and parses its output, saving the raw output to
+    /// `output_path` if set.
+    fn run(&self, ctx: &mut SourceContext<'_>) -> io::Result<(ParseResult, Completeness)> {
+        let output_path = self.output_path.as_deref();
+        let cancel = cancel_on_interrupt()?;
+        let stdout = match output_path {
+            Some(output_path) if self.jobs > 1 => Stdio::from(std::fs::File::create(output_path)?),
+            _ => Stdio::piped(),
+        };
+
+        ctx.progress.stage_started(Stage::Compiling);
+        let mut child = self.command(ctx.root, ctx.cargo_args).stdout(stdout).spawn()?;
+        let child_stdout = child.stdout.take();
+        let child = Arc::new(Mutex::new(child));
+        let done = Arc::new(AtomicBool::new(false));
+        let watcher = watch_child(Arc::clone(&child), cancel, self.timeout, Arc::clone(&done));
+
+        let streamed = match child_stdout {
+            Some(child_stdout) => self
+                .stream(child_stdout, ctx.keep_raw, &mut *ctx.progress)
+                .map(Some),
+            None => Ok(None),
+        };
+        if streamed.is_err() {
+            if let Ok(mut child) = child.lock() {
+                let _ = child.kill();
+            }
+        }
+        let status = wait_child(&child);
+        done.store(true, Ordering::SeqCst);
+        let stopped = watcher.join().unwrap_or(None);
+        let status = status?;
+        let streamed = streamed?;
+
+        let (parsed, lines_processed) = match (streamed, output_path) {
+            (Some(streamed), _) => streamed,
+            (None, Some(output_path)) => {
+                ctx.progress.stage_finished(Stage::Compiling, "");
+                ctx.progress.stage_started(Stage::Parsing);
+                let mut parser = WarningParser::new();
+                parser.set_keep_raw(ctx.keep_raw);
+                let parsed = parser.parse_path(path_str(output_path)?, self.jobs)?;
+                ctx.progress.stage_finished(Stage::Parsing, &format!("{} warnings", parsed.0.len()));
+                (parsed, count_lines(output_path)?)
+            }
+            (None, None) => unreachable!("clippy output is piped when not saved to a file"),
+        };
+
+        let completeness = match stopped {
+            Some(reason) => Completeness::Partial { reason: reason.to_string(), lines_processed },
+            None if status.success() => Completeness::Complete,
+            // Clippy fails on code that does not compile, and on lints
+            // denied with `-D`, after reporting them as errors; only a
+            // failure without errors stopped it early
+            None if parsed.0.iter().any(|w| w.is_error) => Completeness::Complete,
+            None if lines_processed == 0 => {
+                return Err(io::Error::other("Clippy command failed"));
+            }
+            None => Completeness::Partial {
+                reason: format!("clippy failed ({})", status),
+                lines_processed,
+            },
+        };
+        Ok((parsed, completeness))
+    }
+
+    /// Parses clippy's output line by line as it is produced, optionally
+    /// saving the raw output to `output_path`. Returns the parse result and the
+    /// number of lines read, which stops short if clippy is killed. A failed
+    /// read is returned as an error, and clippy is then killed.
+    fn stream(
+        &self,
+        stdout: ChildStdout,
+        keep_raw: bool,
+        progress: &mut dyn ProgressReporter,
+    ) -> io::Result<(ParseResult, usize)> {
+        let mut raw_output = match &self.output_path {
+            Some(path) => Some(io::BufWriter::new(std::fs::File::create(path)?)),
+            None => None,
+        };
+        let mut parser = WarningParser::new();
+        parser.set_keep_raw(keep_raw);
+        let mut latest_crate = String::from("-");
+        let mut lines_processed = 0;
+
+        let mut reader = BufReader::new(stdout);
+        let mut bytes = Vec::new();
+        while read_line_lossy(&mut reader, &mut bytes)? {
+            let line = String::from_utf8_lossy(&bytes);
+            lines_processed += 1;
+            if let Some(raw_output) = &mut raw_output {
+                writeln!(raw_output, "{}", line)?;
+            }
+            match parser.feed_line(&line) {
+                Some(AnalysisContext::BuildInfo { crate_name, .. }) => {
+                    latest_crate = crate_name.clone();
+                }
+                Some(AnalysisContext::Warning(warning)) => {
+                    if let Some(crate_name) = &warning.crate_name {
+                        latest_crate = crate_name.clone();
+                    }
+                }
+                _ => continue,
+            }
+            progress.stage_progress(
+                Stage::Compiling,
+                &format!("{} warnings so far · latest crate: {}", parser.warning_count(), latest_crate),
+            );
+        }
+        if let Some(raw_output) = &mut raw_output {
+            raw_output.flush()?;
+        }
+
+        progress.stage_finished(Stage::Compiling, &format!("{} warnings", parser.warning_count()));
+        Ok((parser.finish(), lines_processed))
+    }
+}
+
+impl Default for ClippySource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WarningSource for ClippySource {
+    fn name(&self) -> &str {
+        "clippy"
+    }
+
+    fn collect(&self, ctx: &mut SourceContext<'_>) -> io::Result<Vec<Warning>> {
+        let ((warnings, _, context), completeness) = self.run(ctx)?;
+        ctx.build_context.extend(context.into_iter().filter(|c| !matches!(c, AnalysisContext::Warning(_))));
+        if !completeness.is_complete() {
+            ctx.completeness = completeness;
+        }
+        Ok(warnings)
+    }
+
+    fn is_required(&self) -> bool {
+        true
+    }
+}
+
+pub(crate) fn path_str(path: &Path) -> io::Result<&str> {
+    path.to_str()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Output path is not valid UTF-8"))
+}
+
+fn count_lines(path: &Path) -> io::Result<usize> {
+    let file = std::fs::File::open(path)?;
+    Ok(BufReader::new(file).split(b'\n').count())
+}
@@ -0,0 +1,88 @@
+//! Event callbacks for embedding the analyzer in other applications.
+//!
+//! A runner created with [`AnalysisRunner::with_events`] writes nothing to the
+//! terminal; instead it reports phases, parsed warnings, and generated report
+//! files through an [`AnalysisEvents`] implementation supplied by the caller.
+//!
+//! [`AnalysisRunner::with_events`]: super::analysis_runner::AnalysisRunner::with_events
+
+use std::path::Path;
+use crate::{analysis::alerts::Alert, output::progress::Stage, types::Warning};
+
+/// Callbacks invoked while an analysis runs. All methods default to no-ops.
+pub trait AnalysisEvents {
+    /// A phase of the analysis has started.
+    fn on_phase_start(&mut self, _phase: Stage) {}
+
+    /// A phase of the analysis has finished.
+    fn on_phase_end(&mut self, _phase: Stage) {}
+
+    /// A warning survived the ignore rules and will be included in the reports.
+    fn on_warning_parsed(&mut self, _warning: &Warning) {}
+
+    /// A report file has been written. `name` is the report kind, e.g. `fix_plan`.
+    fn on_report_written(&mut self, _name: &str, _path: &Path) {}
+
+    /// An `alert-if` rule matched this run.
+    fn on_alert(&mut self, _alert: &Alert) {}
+}
+
+/// Event sink that ignores every callback.
+#[derive(Debug, Default)]
+pub struct NoEvents;
+
+impl AnalysisEvents for NoEvents {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::{output::bundle::Artifact, parser::WarningParser, runner::AnalysisRunner};
+
+    struct Recorder(Rc<RefCell<Vec<String>>>);
+
+    impl AnalysisEvents for Recorder {
+        fn on_phase_start(&mut self, phase: Stage) {
+            self.0.borrow_mut().push(format!("start {:?}", phase));
+        }
+
+        fn on_phase_end(&mut self, phase: Stage) {
+            self.0.borrow_mut().push(format!("end {:?}", phase));
+        }
+
+        fn on_warning_parsed(&mut self, warning: &Warning) {
+            self.0.borrow_mut().push(format!("warning {}", warning.lint_code));
+        }
+
+        fn on_report_written(&mut self, name: &str, path: &Path) {
+            assert!(path.exists());
+            self.0.borrow_mut().push(format!("report {}", name));
+        }
+    }
+
+    #[test]
+    fn test_runner_reports_events() {
+        let temp = tempfile::tempdir().unwrap();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut runner = AnalysisRunner::builder()
+            .reports_dir(temp.path())
+            .working_dir(temp.path())
+            .timestamp("20240101_120000")
+            .artifacts(vec![Artifact::Jsonl])
+            .record_run(false)
+            .events(Box::new(Recorder(events.clone())))
+            .build()
+            .unwrap();
+        let output = r#"{"reason":"compiler-message","message":{"code":{"code":"clippy::len_zero"},"level":"warning","message":"length comparison to zero","spans":[{"file_name":"src/lib.rs","line_start":3,"line_end":3,"column_start":8,"column_end":20,"is_primary":true}],"children":[],"rendered":null}}"#;
+        runner.analyze("clippy.json", WarningParser::new().parse_str(output, 1)).unwrap();
+
+        let events = events.borrow();
+        let position = |event: &str| {
+            events.iter().position(|e| e == event).unwrap_or_else(|| panic!("no {:?} in {:?}", event, events))
+        };
+        assert!(position("start Analyzing") < position("warning clippy::len_zero"));
+        assert!(position("end Analyzing") < position("start GeneratingReports"));
+        assert!(position("report warnings_jsonl") < position("end GeneratingReports"));
+    }
+}
@@ -0,0 +1,76 @@
+//! External tools as warning sources.
+//!
+//! Tools are declared in the config by name and command line:
+//!
+//! ```toml
+//! [sources]
+//! mytool = "mytool --json"
+//! ```
+//!
+//! Each command runs in the workspace root and reports warnings with the
+//! line-JSON protocol described in [`parse_external`].
+
+use std::io;
+use std::process::{Command, Stdio};
+use crate::analysis::plugin::{SourceContext, WarningSource};
+use crate::parser::parse_external;
+use crate::types::Warning;
+
+pub struct ExternalSource {
+    name: String,
+    program: String,
+    args: Vec<String>,
+}
+
+impl ExternalSource {
+    /// Creates a source running `command_line`, split into words the way a
+    /// POSIX shell would, so arguments may be quoted.
+    pub fn new(name: &str, command_line: &str) -> io::Result<Self> {
+        let words = shlex::split(command_line).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("Source {} has unbalanced quotes in its command", name))
+        })?;
+        let mut words = words.into_iter();
+        let program = words.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("Source {} has an empty command", name))
+        })?;
+        Ok(Self { name: name.to_string(), program, args: words.collect() })
+    }
+}
+
+impl WarningSource for ExternalSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Runs the tool and parses its output. Linters commonly exit with a
+    /// failure status when they find something, so only a failure without
+    /// output is an error.
+    fn collect(&self, ctx: &mut SourceContext<'_>) -> io::Result<Vec<Warning>> {
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .current_dir(ctx.root)
+            .stderr(Stdio::inherit())
+            .output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !output.status.success() && stdout.trim().is_empty() {
+            return Err(io::Error::other(format!("{} failed ({})", self.program, output.status)));
+        }
+        parse_external(&self.name, &stdout, ctx.root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_splits_quoted_arguments() {
+        let source = ExternalSource::new("mytool", r#"mytool --json --config "my tool.toml""#).unwrap();
+        assert_eq!(source.program, "mytool");
+        assert_eq!(source.args, ["--json", "--config", "my tool.toml"]);
+
+        let err = ExternalSource::new("mytool", r#"mytool "--json"#).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(ExternalSource::new("mytool", "  ").is_err());
+    }
+}
@@ -0,0 +1,255 @@
+//! Reviewing and applying suggested fixes.
+//!
+//! `cargo analyzer fix --interactive` steps through the warnings that come
+//! with a clippy suggestion, previews each change, and lets the user apply,
+//! skip, or suppress it. Without `--interactive`, every pending fix is
+//! applied. Decisions are recorded so the next session only asks about new
+//! warnings.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use crate::fixes::apply::{FixDecision, FixDecisions, SuggestedEdit};
+use crate::output::diff::DiffLayout;
+use crate::output::hyperlink::EditorLinks;
+use crate::output::preview::FixPreview;
+use crate::types::Warning;
+
+/// Unchanged lines shown around each change
+const CONTEXT_LINES: usize = 2;
+
+/// Counts of a session's outcomes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FixSummary {
+    pub applied: usize,
+    pub skipped: usize,
+    pub suppressed: usize,
+    /// Fixes that could not be applied because the code changed
+    pub failed: usize,
+}
+
+impl FixSummary {
+    fn count(&mut self, decision: FixDecision) {
+        match decision {
+            FixDecision::Applied => self.applied += 1,
+            FixDecision::Skipped => self.skipped += 1,
+            FixDecision::Suppressed => self.suppressed += 1,
+        }
+    }
+}
+
+pub struct FixSession {
+    root: PathBuf,
+    decisions: FixDecisions,
+    preview: FixPreview,
+    interactive: bool,
+    links: EditorLinks,
+}
+
+impl FixSession {
+    pub fn new(root: &Path, decisions: FixDecisions, interactive: bool, color: bool) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            decisions,
+            preview: FixPreview::new(DiffLayout::Unified, color),
+            interactive,
+            links: EditorLinks::disabled(),
+        }
+    }
+
+    /// Links each fix's location to an editor.
+    pub fn set_editor_links(&mut self, links: EditorLinks) {
+        self.links = links;
+    }
+
+    /// Warnings with a suggested edit and no recorded decision, ordered so
+    /// that applying one edit leaves the byte offsets of the next valid:
+    /// by file, from the end of the file to the start.
+    fn pending<'a>(&self, warnings: &'a [Warning]) -> Vec<(&'a Warning, SuggestedEdit)> {
+        let mut seen = HashSet::new();
+        let mut pending: Vec<(&Warning, SuggestedEdit)> = warnings
+            .iter()
+            .filter(|w| self.decisions.get(w).is_none() && seen.insert(w.site_key()))
+            .filter_map(|w| Some((w, SuggestedEdit::from_warning(w)?)))
+            .collect();
+        pending.sort_by(|(a, a_edit), (b, b_edit)| {
+            a.file.cmp(&b.file).then(b_edit.first_byte().cmp(&a_edit.first_byte()))
+        });
+        pending
+    }
+
+    /// Reviews the pending fixes, reading answers from `input` in
+    /// interactive mode. Stops early when the user quits or `input` ends.
+    pub fn run<R: BufRead, W: Write>(
+        &mut self,
+        warnings: &[Warning],
+        mut input: R,
+        output: &mut W,
+    ) -> io::Result<FixSummary> {
+        let pending = self.pending(warnings);
+        let mut summary = FixSummary::default();
+        // Lowest byte changed so far per file; edits reaching past it are stale
+        let mut applied_from: HashMap<String, usize> = HashMap::new();
+
+        for (index, (warning, edit)) in pending.iter().enumerate() {
+            let stale = edit.replacements.iter().any(|r| {
+                applied_from.get(&r.file).is_some_and(|&start| r.span.byte_end.is_none_or(|end| end > start))
+            });
+            if stale {
+                summary.failed += 1;
+                continue;
+            }
+
+            let location = self.links.link(&format!("{}:{}", warning.file, warning.line), warning);
+            writeln!(output, "\n[{}/{}] {} at {}", index + 1, pending.len(), warning.lint_code, location)?;
+            writeln!(output, "{}", warning.primary_message)?;
+            writeln!(output, "Applicability: {}\n", edit.applicability)?;
+            for file in edit.files() {
+                let source = std::fs::read_to_string(self.root.join(file))?;
+                if let Some((before, after)) = snippet(edit, file, &source) {
+                    if edit.files().len() > 1 {
+                        writeln!(output, "{}:", file)?;
+                    }
+                    write!(output, "{}", self.preview.render(&before, &after))?;
+                }
+            }
+
+            let decision = if self.interactive {
+                match prompt(&mut input, output)? {
+                    Some(decision) => decision,
+                    None => break,
+                }
+            } else {
+                FixDecision::Applied
+            };
+            if decision == FixDecision::Applied {
+                if let Err(e) = edit.apply(&self.root) {
+                    writeln!(output, "Could not apply fix: {}", e)?;
+                    summary.failed += 1;
+                    continue;
+                }
+                for replacement in &edit.replacements {
+                    let start = applied_from.entry(replacement.file.clone()).or_insert(usize::MAX);
+                    *start = (*start).min(replacement.span.byte_start.unwrap_or(0));
+                }
+            }
+            self.decisions.record(warning, decision);
+            summary.count(decision);
+        }
+        Ok(summary)
+    }
+
+    pub fn into_decisions(self) -> FixDecisions {
+        self.decisions
+    }
+}
+
+/// Asks for a decision; `None` means quit.
+fn prompt<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> io::Result<Option<FixDecision>> {
+    loop {
+        write!(output, "[a]pply, [s]kip, suppre[x], [q]uit? ")?;
+        output.flush()?;
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            return Ok(None);
+        }
+        match answer.trim() {
+            "a" | "y" => return Ok(Some(FixDecision::Applied)),
+            "s" | "n" | "" => return Ok(Some(FixDecision::Skipped)),
+            "x" => return Ok(Some(FixDecision::Suppressed)),
+            "q" => return Ok(None),
+            _ => writeln!(output, "Please answer a, s, x, or q.")?,
+        }
+    }
+}
+
+/// The lines an edit changes in `file`, with context, before and after.
+fn snippet(edit: &SuggestedEdit, file: &str, source: &str) -> Option<(String, String)> {
+    let fixed = edit.apply_to(file, source)?;
+    let lines: Vec<&str> = source.lines().collect();
+    let fixed_lines: Vec<&str> = fixed.lines().collect();
+    let replacements = edit.replacements.iter().filter(|r| r.file == file);
+    let first = replacements.clone().map(|r| r.span.line_start).min()? as usize;
+    let last = replacements.map(|r| r.span.line_end).max()? as usize;
+
+    let start = first.saturating_sub(1 + CONTEXT_LINES);
+    let end = (last + CONTEXT_LINES).min(lines.len());
+    let fixed_end = (end + fixed_lines.len()).saturating_sub(lines.len()).min(fixed_lines.len());
+    Some((
+        lines.get(start..end)?.join("\n"),
+        fixed_lines.get(start..fixed_end.max(start))?.join("\n"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `len_zero` warning whose suggestion replaces `start..end` of `file`.
+    fn warning(file: &str, line: u32, start: usize, end: usize) -> Warning {
+        let raw = serde_json::json!({
+            "message": "length comparison to zero",
+            "children": [{ "message": "using `is_empty` is clearer", "spans": [{
+                "file_name": file,
+                "byte_start": start,
+                "byte_end": end,
+                "line_start": line,
+                "line_end": line,
+                "column_start": 1,
+                "column_end": 1 + end - start,
+                "suggested_replacement": "v.is_empty()",
+                "suggestion_applicability": "MachineApplicable"
+            }]}]
+        });
+        Warning {
+            primary_message: format!("length comparison to zero at {}", start),
+            raw: Some(raw.to_string()),
+            ..Warning::for_test("clippy::len_zero", file, line)
+        }
+    }
+
+    #[test]
+    fn test_pending_order() {
+        let mut decisions = FixDecisions::new();
+        let decided = warning("src/a.rs", 9, 90, 102);
+        decisions.record(&decided, FixDecision::Skipped);
+        let session = FixSession::new(Path::new("."), decisions, false, false);
+        let no_fix = Warning { raw: None, ..warning("src/a.rs", 4, 40, 52) };
+        let warnings = [
+            warning("src/b.rs", 1, 0, 12),
+            warning("src/a.rs", 2, 10, 12),
+            decided,
+            warning("src/a.rs", 5, 50, 62),
+            no_fix,
+            warning("src/a.rs", 5, 50, 62),
+        ];
+
+        let pending: Vec<(&str, u32)> =
+            session.pending(&warnings).iter().map(|(w, _)| (w.file.as_str(), w.line)).collect();
+        assert_eq!(pending, [("src/a.rs", 5), ("src/a.rs", 2), ("src/b.rs", 1)]);
+    }
+
+    #[test]
+    fn test_decisions_survive_a_failed_session() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let source = "fn f(v: Vec<u8>) -> bool {\n    v.len() == 0\n}\n";
+        std::fs::write(root.join("src/a.rs"), source).unwrap();
+        let start = source.find("v.len() == 0").unwrap();
+        // src/b.rs is missing, so previewing its fix fails after src/a.rs
+        let warnings = [warning("src/a.rs", 2, start, start + 12), warning("src/b.rs", 1, 0, 12)];
+
+        let mut session = FixSession::new(root, FixDecisions::new(), true, false);
+        let mut output = Vec::new();
+        assert!(session.run(&warnings, "s\n".as_bytes(), &mut output).is_err());
+        let path = root.join("decisions.json");
+        session.into_decisions().save(&path).unwrap();
+
+        let decisions = FixDecisions::load(&path).unwrap();
+        assert_eq!(decisions.get(&warnings[0]), Some(FixDecision::Skipped));
+        assert_eq!(decisions.get(&warnings[1]), None);
+        let session = FixSession::new(root, decisions, true, false);
+        assert_eq!(session.pending(&warnings).len(), 1);
+    }
+}
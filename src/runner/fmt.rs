@@ -0,0 +1,63 @@
+//! Running rustfmt in check mode against the analyzed workspace.
+
+use std::io;
+use std::path::Path;
+use super::cargo::cargo_command;
+use crate::analysis::plugin::{SourceContext, WarningSource};
+use crate::parser::{parse_fmt_check, parse_fmt_json};
+use crate::types::Warning;
+
+/// Finds code rustfmt would reformat in the workspace at `root`.
+///
+/// Nightly rustfmt's JSON output is tried first; stable rustfmt rejects it,
+/// and its `--check` diff is parsed instead. Returns `None` when rustfmt is
+/// not installed or fails, e.g. on a syntax error. `--workspace` becomes
+/// `cargo fmt --all` and `--manifest-path` is passed on; no other cargo flags
+/// apply to rustfmt.
+pub fn run_fmt_check(cargo_args: &[String], root: &Path) -> Option<Vec<Warning>> {
+    let mut fmt_args = Vec::new();
+    if cargo_args.iter().any(|arg| arg == "--workspace") {
+        fmt_args.push("--all");
+    }
+    if let Some(i) = cargo_args.iter().position(|arg| arg == "--manifest-path") {
+        fmt_args.extend(cargo_args.get(i..=i + 1).into_iter().flatten().map(String::as_str));
+    }
+    if let Some(stdout) = run_cargo_fmt(root, &fmt_args, &["--emit", "json", "--unstable-features"]) {
+        if let Ok(warnings) = parse_fmt_json(&stdout, root) {
+            return Some(warnings);
+        }
+    }
+    run_cargo_fmt(root, &fmt_args, &["--check", "--color", "never"]).map(|stdout| parse_fmt_check(&stdout, root))
+}
+
+/// Runs `cargo fmt` with `rustfmt_args`, returning its output if it ran.
+/// `--check` exits with status 1 when it prints a diff, so only a failure
+/// without output counts.
+fn run_cargo_fmt(root: &Path, fmt_args: &[&str], rustfmt_args: &[&str]) -> Option<String> {
+    let output = cargo_command(root)
+        .arg("fmt")
+        .args(fmt_args)
+        .arg("--")
+        .args(rustfmt_args)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    if !output.status.success() && stdout.trim().is_empty() {
+        return None;
+    }
+    Some(stdout)
+}
+
+/// rustfmt's check mode as a warning source.
+pub struct RustfmtSource;
+
+impl WarningSource for RustfmtSource {
+    fn name(&self) -> &str {
+        "rustfmt"
+    }
+
+    fn collect(&self, ctx: &mut SourceContext<'_>) -> io::Result<Vec<Warning>> {
+        run_fmt_check(ctx.cargo_args, ctx.root)
+            .ok_or_else(|| io::Error::other("rustfmt is unavailable or failed"))
+    }
+}
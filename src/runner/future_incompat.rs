@@ -0,0 +1,37 @@
+//! Reading cargo's future-incompatibility report for the last build.
+
+use std::io;
+use super::cargo::cargo_command;
+use crate::analysis::plugin::{SourceContext, WarningSource};
+use crate::parser::parse_future_incompat_report;
+use crate::types::Warning;
+
+/// The future incompatibilities cargo recorded for dependencies during the
+/// clippy build, from `cargo report future-incompatibilities`.
+///
+/// Those in the workspace itself were already reported by clippy and are
+/// left out. A build without future incompatibilities has no report, which
+/// cargo treats as an error; it yields no warnings here.
+pub struct FutureIncompatSource;
+
+impl WarningSource for FutureIncompatSource {
+    fn name(&self) -> &str {
+        "future-incompat report"
+    }
+
+    fn collect(&self, ctx: &mut SourceContext<'_>) -> io::Result<Vec<Warning>> {
+        let output = cargo_command(ctx.root)
+            .args(["report", "future-incompatibilities", "--color", "never"])
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("no reports are currently available") {
+                return Ok(Vec::new());
+            }
+            return Err(io::Error::other(format!("cargo report failed: {}", stderr.trim())));
+        }
+        let mut warnings = parse_future_incompat_report(&String::from_utf8_lossy(&output.stdout), ctx.root);
+        warnings.retain(|warning| std::path::Path::new(&warning.file).is_absolute());
+        Ok(warnings)
+    }
+}
@@ -0,0 +1,21 @@
+//! Running cargo-geiger against the analyzed workspace.
+
+use std::path::Path;
+use super::cargo::cargo_command;
+use crate::analysis::unsafe_usage::GeigerReport;
+
+/// Runs `cargo geiger --output-format Json` in `root`.
+///
+/// Returns `None` when cargo-geiger is missing or its output cannot be
+/// parsed. Geiger exits non-zero when it finds unsafe code, so the exit
+/// status is not checked. Geiger has no `--workspace` flag, so that one is
+/// not passed on.
+pub fn run_geiger(root: &Path, cargo_args: &[String]) -> Option<GeigerReport> {
+    let output = cargo_command(root)
+        .args(["geiger", "--output-format", "Json"])
+        .args(cargo_args.iter().filter(|arg| *arg != "--workspace"))
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    GeigerReport::from_json(&stdout).ok()
+}
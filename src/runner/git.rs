@@ -0,0 +1,208 @@
+//! Best-effort queries against the git repository being analyzed.
+//!
+//! Every helper runs git in the directory it is given, the analyzed
+//! workspace. All return `None` when git is unavailable or the directory is
+//! not in a repository, so callers can treat git metadata as optional.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use crate::analysis::{changed_lines::ChangedLines, renames::RenameMap};
+
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").current_dir(dir).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Full hash of the checked out commit.
+pub fn head_commit(dir: &Path) -> Option<String> {
+    git_output(dir, &["rev-parse", "HEAD"])
+}
+
+/// Name of the checked out branch, `None` on a detached HEAD.
+pub fn current_branch(dir: &Path) -> Option<String> {
+    git_output(dir, &["rev-parse", "--abbrev-ref", "HEAD"]).filter(|branch| branch != "HEAD")
+}
+
+/// Head commit of `branch`, trying the local branch, then `origin/<branch>`,
+/// then fetching it from origin (CI checkouts are often shallow and carry
+/// only the branch under test).
+pub fn branch_head(dir: &Path, branch: &str) -> Option<String> {
+    local_commit(dir, branch).or_else(|| {
+        git_output(dir, &["fetch", "--quiet", "origin", branch]);
+        resolve_commit(dir, "FETCH_HEAD")
+    })
+}
+
+/// Full hash of `rev` as a local revision or `origin/<rev>`, without
+/// fetching.
+pub fn local_commit(dir: &Path, rev: &str) -> Option<String> {
+    resolve_commit(dir, rev).or_else(|| resolve_commit(dir, &format!("origin/{}", rev)))
+}
+
+fn resolve_commit(dir: &Path, rev: &str) -> Option<String> {
+    git_output(dir, &["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", rev)])
+}
+
+/// The best common ancestor of `rev` and HEAD.
+pub fn merge_base(dir: &Path, rev: &str) -> Option<String> {
+    git_output(dir, &["merge-base", rev, "HEAD"])
+}
+
+/// Lines of the working tree added or modified since `commit`, with paths
+/// relative to `dir`; untracked files count as entirely changed. `base`
+/// names the ref compared against.
+pub fn changed_lines(dir: &Path, base: &str, commit: &str) -> Option<ChangedLines> {
+    // An empty diff is not an error, unlike git_output's empty output
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["diff", "--unified=0", "--no-color", "--no-ext-diff", "--relative", commit])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut changed = ChangedLines::from_diff(base, &String::from_utf8_lossy(&output.stdout));
+    let untracked = git_output(dir, &["ls-files", "--others", "--exclude-standard"]).unwrap_or_default();
+    for file in untracked.lines() {
+        changed.add_file(file);
+    }
+    Some(changed)
+}
+
+/// Files of the working tree changed since `commit`, as by `git diff
+/// --name-only`, with paths relative to `dir`; untracked files count as
+/// changed. `base` names the ref compared against.
+pub fn changed_files(dir: &Path, base: &str, commit: &str) -> Option<ChangedLines> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["diff", "--name-only", "--no-ext-diff", "--relative", commit])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let untracked = git_output(dir, &["ls-files", "--others", "--exclude-standard"]).unwrap_or_default();
+    let diff = String::from_utf8_lossy(&output.stdout);
+    Some(ChangedLines::from_files(base, diff.lines().chain(untracked.lines())))
+}
+
+/// Files renamed between `commit` and the working tree, with paths
+/// relative to `dir`. Empty outside a git repository or when the commit is
+/// unknown.
+pub fn renames_since(dir: &Path, commit: &str) -> RenameMap {
+    let diff = git_output(dir, &["diff", "-M", "--name-status", "--diff-filter=R", "--relative", commit]);
+    RenameMap::from_renames(diff.as_deref().map(parse_renames).unwrap_or_default())
+}
+
+/// Number of commits touching each file in the last `months` months, with
+/// paths relative to `dir` and commits to earlier paths of
+/// renamed files counted for their current path. Empty outside a git
+/// repository.
+pub fn file_churn(dir: &Path, months: u32) -> HashMap<String, usize> {
+    let since = format!("--since={} months ago", months);
+    let mut churn = HashMap::new();
+    let Some(log) = git_output(dir, &["log", &since, "-M", "--name-status", "--format=", "--relative"]) else {
+        return churn;
+    };
+    for line in log.lines() {
+        // The new path of a rename is last
+        if let Some(file) = line.split('\t').skip(1).last() {
+            *churn.entry(file.to_string()).or_insert(0) += 1;
+        }
+    }
+    // The log lists the newest commit first
+    let mut renames = parse_renames(&log);
+    renames.reverse();
+    RenameMap::from_renames(renames).follow(&churn)
+}
+
+/// The `(old, new)` pairs of the rename lines (`R<similarity>\told\tnew`)
+/// of `--name-status` output.
+fn parse_renames(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| match line.split('\t').collect::<Vec<_>>().as_slice() {
+            [status, old, new] if status.starts_with('R') => Some((old.to_string(), new.to_string())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Warning;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com", "-c", "commit.gpgsign=false"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_parse_renames() {
+        let output = "M\tsrc/lib.rs\nR100\tsrc/old.rs\tsrc/new.rs\nR087\ta.rs\tb.rs\n";
+        assert_eq!(
+            parse_renames(output),
+            [
+                ("src/old.rs".to_string(), "src/new.rs".to_string()),
+                ("a.rs".to_string(), "b.rs".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_queries_outside_a_repository() {
+        let temp = tempfile::tempdir().unwrap();
+        assert_eq!(head_commit(temp.path()), None);
+        assert!(changed_files(temp.path(), "main", "HEAD").is_none());
+        assert!(renames_since(temp.path(), "HEAD").is_empty());
+        assert!(file_churn(temp.path(), 6).is_empty());
+    }
+
+    #[test]
+    fn test_queries_in_a_repository() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        git(dir, &["init", "--quiet", "--initial-branch=main"]);
+        std::fs::write(dir.join("old.rs"), "fn a() {}\nfn b() {}\nfn c() {}\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "--quiet", "-m", "initial"]);
+
+        let commit = head_commit(dir).unwrap();
+        assert_eq!(commit.len(), 40);
+        assert_eq!(current_branch(dir).as_deref(), Some("main"));
+        assert_eq!(local_commit(dir, "main").as_deref(), Some(commit.as_str()));
+        assert_eq!(merge_base(dir, "main").as_deref(), Some(commit.as_str()));
+
+        git(dir, &["mv", "old.rs", "new.rs"]);
+        std::fs::write(dir.join("new.rs"), "fn a() {}\nfn b() { todo!() }\nfn c() {}\n").unwrap();
+        std::fs::write(dir.join("untracked.rs"), "").unwrap();
+
+        let renames = renames_since(dir, &commit);
+        assert_eq!(renames.previous_path("new.rs"), Some("old.rs"));
+
+        let changed = changed_lines(dir, "main", &commit).unwrap();
+        assert!(changed.contains(&Warning::for_test("clippy::todo", "new.rs", 2)));
+        assert!(!changed.contains(&Warning::for_test("clippy::todo", "new.rs", 3)));
+        assert!(changed.touches("untracked.rs"));
+
+        let files = changed_files(dir, "main", &commit).unwrap();
+        assert!(files.touches("new.rs"));
+        assert!(files.touches("untracked.rs"));
+
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "--quiet", "-m", "rename"]);
+        assert_eq!(file_churn(dir, 1).get("new.rs"), Some(&2));
+    }
+}
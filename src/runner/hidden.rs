@@ -0,0 +1,71 @@
+//! Scanning the analyzed workspace for code clippy cannot see.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use super::cargo::cargo_command;
+use crate::analysis::hidden_code::{FeatureSelection, FeatureSet, HiddenCodeReport, scan_hidden_code};
+
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    manifest_path: PathBuf,
+    features: BTreeMap<String, Vec<String>>,
+}
+
+/// Scans every `.rs` file of the workspace packages at `root` for ignored
+/// tests and items behind features the `cargo_args` feature selection
+/// leaves off. `target` and hidden directories are skipped, as are nested
+/// packages, which are scanned with their own features.
+pub fn scan_workspace(root: &Path, cargo_args: &[String]) -> io::Result<HiddenCodeReport> {
+    let mut command = cargo_command(root);
+    command.args(["metadata", "--no-deps", "--format-version", "1"]);
+    if let Some(i) = cargo_args.iter().position(|arg| arg == "--manifest-path") {
+        command.args(cargo_args.get(i..=i + 1).into_iter().flatten());
+    }
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "cargo metadata failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let metadata: Metadata = serde_json::from_slice(&output.stdout)?;
+
+    let selection = FeatureSelection::from_cargo_args(cargo_args);
+    let package_dirs: Vec<&Path> = metadata.packages.iter().filter_map(|p| p.manifest_path.parent()).collect();
+    let mut report = HiddenCodeReport::default();
+    for (package, dir) in metadata.packages.iter().zip(&package_dirs) {
+        let features = FeatureSet::resolve(&package.features, &selection);
+        let mut files = Vec::new();
+        collect_sources(dir, &package_dirs, &mut files)?;
+        files.sort();
+        for file in files {
+            let Ok(source) = std::fs::read_to_string(&file) else { continue };
+            let relative = file.strip_prefix(root).unwrap_or(&file).to_string_lossy().into_owned();
+            report.items.extend(scan_hidden_code(&source, &relative, &features));
+            report.files_scanned += 1;
+        }
+    }
+    Ok(report)
+}
+
+fn collect_sources(dir: &Path, package_dirs: &[&Path], files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        if path.is_dir() {
+            if name != "target" && !name.starts_with('.') && !package_dirs.contains(&path.as_path()) {
+                collect_sources(&path, package_dirs, files)?;
+            }
+        } else if name.ends_with(".rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,319 @@
+//! Backfilling the run history from earlier output.
+//!
+//! `import-history DIR` records a run for every raw clippy dump (the JSON
+//! messages of `cargo clippy --message-format=json`, like the
+//! `clippy_output_<timestamp>.json` files the analyzer keeps) and every
+//! JSON or JSONL warnings export in `DIR`, oldest first, so trends have
+//! history from the first run on. Each run is dated by the
+//! `_YYYYMMDD_HHMMSS` timestamp in its file name, or else by the file's
+//! modification time; when a dump and an export share a timestamp, only
+//! the export is imported.
+//!
+//! Runs go to `history-db` (with the `sqlite` feature) or, when it is not
+//! set, to `clippy_historical.json`. Runs already recorded are skipped, so
+//! a directory can be imported again as it grows; the trends file only
+//! keeps dates, so there a run counts as recorded when one of the same day
+//! has the same total.
+//! The current ignore rules and severity overrides apply. Exports keep
+//! their fingerprints; warnings of raw dumps are fingerprinted against the
+//! current source like those of a live run, so the new and fixed counts of
+//! old dumps are approximate.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use super::cargo::workspace_target_dir;
+use super::clippy::path_str;
+use crate::{
+    analysis::{
+        fingerprint::fingerprint_counts,
+        pipeline::{AnalysisOptions, analyze},
+        statistics::warning::WarningStatistics,
+        trends::{TrendAnalysis, recurring_issues},
+    },
+    config::AnalyzerConfig,
+    output::json_export::read_warnings,
+    parser::{ParseResult, WarningParser},
+    types::{FileWarnings, Warning},
+};
+#[cfg(feature = "sqlite")]
+use crate::analysis::{
+    fingerprint::fingerprint_churn,
+    history::{HistoryDb, RunRecord},
+};
+
+/// Trends file read when no history database is configured
+pub const HISTORICAL_TRENDS_FILE: &str = "clippy_historical.json";
+
+/// What an imported file holds; exports sort first so that they win over
+/// a dump of the same run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ImportFormat {
+    /// A JSON or JSONL warnings export
+    WarningsExport,
+    /// Cargo JSON messages
+    ClippyOutput,
+}
+
+/// A file of earlier output to record as a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryImport {
+    pub path: PathBuf,
+    pub timestamp: DateTime<Utc>,
+    pub format: ImportFormat,
+}
+
+impl HistoryImport {
+    /// The importable files in `dir`, oldest first, one per timestamp.
+    pub fn scan(dir: &Path) -> io::Result<Vec<HistoryImport>> {
+        let mut imports = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let json = matches!(path.extension().and_then(|ext| ext.to_str()), Some("json" | "jsonl"));
+            if !json || !path.is_file() || path.file_name().is_some_and(|name| name == HISTORICAL_TRENDS_FILE) {
+                continue;
+            }
+            let Some(format) = detect_format(&path)? else { continue };
+            let timestamp = match path.file_stem().and_then(|stem| stem.to_str()).and_then(file_timestamp) {
+                Some(timestamp) => timestamp,
+                None => DateTime::<Utc>::from(std::fs::metadata(&path)?.modified()?),
+            };
+            imports.push(HistoryImport { path, timestamp, format });
+        }
+        imports.sort_by(|a, b| (a.timestamp, a.format, &a.path).cmp(&(b.timestamp, b.format, &b.path)));
+        imports.dedup_by_key(|import| import.timestamp);
+        Ok(imports)
+    }
+
+    /// The warnings of this file, grouped by file.
+    pub fn read(&self) -> io::Result<ParseResult> {
+        match self.format {
+            ImportFormat::ClippyOutput => WarningParser::parse_file(path_str(&self.path)?),
+            ImportFormat::WarningsExport => {
+                let warnings = read_warnings(&self.path)?;
+                let mut files: HashMap<String, FileWarnings> = HashMap::new();
+                for warning in &warnings {
+                    files
+                        .entry(warning.file.clone())
+                        .or_insert_with(|| FileWarnings::new(warning.file.clone()))
+                        .add_warning(warning.clone());
+                }
+                Ok((warnings, files, Vec::new()))
+            }
+        }
+    }
+}
+
+/// Recognizes a dump or an export by its first line.
+fn detect_format(path: &Path) -> io::Result<Option<ImportFormat>> {
+    let reader = BufReader::new(File::open(path)?);
+    let Some(Ok(line)) = reader.lines().find(|line| line.as_ref().map_or(true, |line| !line.trim().is_empty())) else {
+        return Ok(None);
+    };
+    if line.trim_start().starts_with('[') {
+        return Ok(Some(ImportFormat::WarningsExport));
+    }
+    let Ok(serde_json::Value::Object(message)) = serde_json::from_str(&line) else { return Ok(None) };
+    Ok(if message.contains_key("reason") {
+        Some(ImportFormat::ClippyOutput)
+    } else if message.contains_key("id") || message.contains_key("lint_code") {
+        Some(ImportFormat::WarningsExport)
+    } else {
+        None
+    })
+}
+
+/// The local time of a `_YYYYMMDD_HHMMSS` suffix, as in report file names.
+fn file_timestamp(stem: &str) -> Option<DateTime<Utc>> {
+    let parts: Vec<&str> = stem.split('_').collect();
+    parts.windows(2).rev().find_map(|pair| {
+        let digits = |part: &str, len| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+        if !digits(pair[0], 8) || !digits(pair[1], 6) {
+            return None;
+        }
+        let naive = NaiveDateTime::parse_from_str(&format!("{}_{}", pair[0], pair[1]), "%Y%m%d_%H%M%S").ok()?;
+        Local.from_local_datetime(&naive).earliest().map(|local| local.with_timezone(&Utc))
+    })
+}
+
+/// Runs recorded and files skipped by an import.
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    /// Where the runs were recorded
+    pub target: PathBuf,
+    pub imported: usize,
+    /// Runs recorded at the same time before
+    pub already_recorded: usize,
+    /// Files that could not be read, with the reason
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// Where imported runs are recorded.
+enum HistoryTarget {
+    #[cfg(feature = "sqlite")]
+    Database(HistoryDb),
+    Trends(Vec<TrendAnalysis>),
+}
+
+impl HistoryTarget {
+    /// Records a run at `timestamp`, unless one is already recorded then.
+    fn record(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        warnings: &[Warning],
+        stats: &WarningStatistics,
+        previous: Option<&HashMap<String, usize>>,
+    ) -> io::Result<bool> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            HistoryTarget::Database(db) => {
+                if db.has_run_at(timestamp)? {
+                    return Ok(false);
+                }
+                let mut run = RunRecord::new(warnings, stats);
+                run.timestamp = timestamp;
+                run.churn = previous.map(|previous| fingerprint_churn(previous, &run.fingerprints));
+                db.record_run(&run)?;
+                Ok(true)
+            }
+            HistoryTarget::Trends(trends) => {
+                let date = timestamp.with_timezone(&Local).format("%Y-%m-%d").to_string();
+                let recorded = |t: &TrendAnalysis| t.dates.first() == Some(&date) && t.total_warnings == stats.total_warnings;
+                if trends.iter().any(recorded) {
+                    return Ok(false);
+                }
+                let earlier: Vec<TrendAnalysis> =
+                    trends.iter().filter(|t| t.dates.first().is_some_and(|d| *d <= date)).cloned().collect();
+                let mut trend = TrendAnalysis::new(
+                    stats.total_warnings,
+                    stats.by_category.clone(),
+                    stats.by_priority.clone(),
+                    recurring_issues(warnings),
+                );
+                trend.dates = vec![date];
+                let totals: Vec<usize> = earlier.iter().map(|t| t.total_warnings).collect();
+                trend.calculate_improvement_rate(&totals);
+                trend.calculate_weighted_improvement(&earlier);
+                trend.by_file = stats.by_file.clone();
+                if let Some(previous) = previous {
+                    trend.set_fingerprint_churn(previous, &fingerprint_counts(warnings));
+                }
+                let at = trends.partition_point(|t| t.dates.first() <= trend.dates.first());
+                trends.insert(at, trend);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Writes the trends file; runs are stored in the database as recorded.
+    fn save(&self, path: &Path) -> io::Result<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            HistoryTarget::Database(_) => Ok(()),
+            HistoryTarget::Trends(trends) => Ok(serde_json::to_writer_pretty(File::create(path)?, trends)?),
+        }
+    }
+}
+
+/// Analyzes each of `imports` with `config`'s rules and records it in the
+/// history of the workspace at `root`.
+pub fn import_history(imports: &[HistoryImport], root: &Path, config: &AnalyzerConfig) -> io::Result<ImportSummary> {
+    let mut summary = ImportSummary::default();
+    let mut target = match &config.history_db {
+        #[cfg(feature = "sqlite")]
+        Some(path) => {
+            summary.target = path.clone();
+            HistoryTarget::Database(HistoryDb::open(path)?)
+        }
+        #[cfg(not(feature = "sqlite"))]
+        Some(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "history-db is set but this build lacks the `sqlite` feature",
+            ));
+        }
+        None => {
+            summary.target = root.join(HISTORICAL_TRENDS_FILE);
+            let trends = match File::open(&summary.target) {
+                Ok(file) => serde_json::from_reader(BufReader::new(file)).map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", summary.target.display(), e))
+                })?,
+                Err(_) => Vec::new(),
+            };
+            HistoryTarget::Trends(trends)
+        }
+    };
+
+    let mut options = AnalysisOptions::from_config(config)?;
+    options.source_root = Some(root.to_path_buf());
+    options.generated.set_target_dir(workspace_target_dir(root));
+    options.unsafe_metrics = false;
+    let mut previous: Option<HashMap<String, usize>> = None;
+    for import in imports {
+        let parsed = match import.read() {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                summary.failed.push((import.path.clone(), e.to_string()));
+                continue;
+            }
+        };
+        let analysis = analyze(parsed, &options);
+        if target.record(import.timestamp, &analysis.warnings, &analysis.stats, previous.as_ref())? {
+            summary.imported += 1;
+        } else {
+            summary.already_recorded += 1;
+        }
+        previous = Some(fingerprint_counts(&analysis.warnings));
+    }
+
+    if summary.imported > 0 {
+        target.save(&summary.target)?;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_history_from_dumps_and_exports() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let message = |lint: &str, line: u32| {
+            format!(
+                r#"{{"reason":"compiler-message","message":{{"code":{{"code":"clippy::{}"}},"level":"warning","message":"{} here","spans":[{{"file_name":"src/lib.rs","line_start":{},"line_end":{},"column_start":1,"column_end":2}}],"children":[],"rendered":null}}}}"#,
+                lint, lint, line, line
+            )
+        };
+        let export = |messages: &[String], name: &str| {
+            let (warnings, _, _) = WarningParser::new().parse_str(&messages.join("\n"), 1);
+            crate::output::json_export::write_json(File::create(dir.join(name)).unwrap(), &warnings).unwrap();
+        };
+        export(&[message("len_zero", 3), message("needless_borrow", 9)], "clippy_warnings_json_20260901_100000.json");
+        // A dump and an export of the same run
+        std::fs::write(dir.join("clippy_output_20260902_100000.json"), message("len_zero", 3)).unwrap();
+        export(&[message("len_zero", 3)], "clippy_warnings_json_20260902_100000.json");
+        std::fs::write(dir.join("clippy_output_20260903_100000.json"), message("len_zero", 3)).unwrap();
+        std::fs::write(dir.join("clippy_merge_status_20260902_100000.json"), "{\"state\": \"success\"}").unwrap();
+
+        let imports = HistoryImport::scan(dir).unwrap();
+        let formats: Vec<ImportFormat> = imports.iter().map(|import| import.format).collect();
+        assert_eq!(formats, [ImportFormat::WarningsExport, ImportFormat::WarningsExport, ImportFormat::ClippyOutput]);
+        assert!(imports[0].timestamp < imports[1].timestamp);
+
+        let summary = import_history(&imports, dir, &AnalyzerConfig::default()).unwrap();
+        assert_eq!((summary.imported, summary.already_recorded), (3, 0));
+        let trends: Vec<TrendAnalysis> =
+            serde_json::from_reader(File::open(dir.join(HISTORICAL_TRENDS_FILE)).unwrap()).unwrap();
+        let totals: Vec<usize> = trends.iter().map(|t| t.total_warnings).collect();
+        assert_eq!(totals, [2, 1, 1]);
+        assert_eq!((trends[1].new_warnings, trends[1].fixed_warnings), (Some(0), Some(1)));
+        assert_eq!((trends[2].new_warnings, trends[2].fixed_warnings), (Some(0), Some(0)));
+
+        let again = import_history(&imports, dir, &AnalyzerConfig::default()).unwrap();
+        assert_eq!((again.imported, again.already_recorded), (0, 3));
+    }
+}
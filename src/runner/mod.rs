@@ -1,4 +1,32 @@
+pub mod analysis_report;
 pub mod analysis_runner;
+pub mod bench;
+pub mod build_impact;
+pub mod cancel;
+pub mod cargo;
+pub mod check;
+pub mod clippy;
+pub mod events;
+pub mod external;
+pub mod fix;
+pub mod fmt;
+pub mod future_incompat;
+pub mod geiger;
+pub mod git;
+pub mod hidden;
+pub mod import;
+pub mod notify;
+pub mod pr_comment;
+pub mod repro;
+pub mod saved;
+pub mod self_analysis;
+pub mod udeps;
+pub mod upgrade;
+pub mod watch;
 pub mod workflow;
 
-pub use workflow::run_analysis; 
\ No newline at end of file
+pub use analysis_report::{AnalysisReport, RunOutcome};
+pub use analysis_runner::{AnalysisRunner, AnalysisRunnerBuilder, HistoryBackend};
+pub use check::{CheckOutcome, TOOL_ERROR_EXIT_CODE};
+pub use events::{AnalysisEvents, NoEvents};
+pub use workflow::run_analysis;
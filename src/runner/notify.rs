@@ -0,0 +1,53 @@
+//! Posting regression notifications (see `output::notification`).
+//!
+//! Requests go through `curl`, which CI images already ship, with the URL
+//! and body passed on standard input so a secret webhook URL never shows
+//! up in the process list.
+
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use crate::output::notification::{RunSummary, WebhookConfig};
+
+/// Seconds before a webhook request is abandoned
+const WEBHOOK_TIMEOUT_SECS: u32 = 15;
+
+/// Posts `summary` to `webhook` if one of its triggers fired. Returns
+/// whether a message was sent; a webhook whose URL is not set is skipped.
+pub fn notify_webhook(webhook: &WebhookConfig, summary: &RunSummary) -> io::Result<bool> {
+    let fired = summary.fired(&webhook.on);
+    if fired.is_empty() {
+        return Ok(false);
+    }
+    let Some(url) = webhook.resolve_url() else { return Ok(false) };
+    let payload = summary.payload(webhook.kind, &fired, webhook.report_url.as_deref()).to_string();
+    let config = format!(
+        "url = \"{}\"\nheader = \"Content-Type: application/json\"\ndata-binary = \"{}\"\n",
+        curl_quote(&url),
+        curl_quote(&payload)
+    );
+
+    let mut child = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--max-time", &WEBHOOK_TIMEOUT_SECS.to_string(), "--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| io::Error::new(e.kind(), format!("Cannot run curl: {}", e)))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(config.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "{} notification failed: {}",
+            webhook.kind,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(true)
+}
+
+/// Escapes `value` for a double-quoted curl config string.
+fn curl_quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
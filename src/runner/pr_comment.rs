@@ -0,0 +1,144 @@
+//! Pull request comments.
+//!
+//! `cargo analyzer comment --pr N BODY` posts a markdown file as the
+//! analyzer's comment with the GitHub CLI (`gh`, authenticated by
+//! `GH_TOKEN` in CI). The body carries a hidden marker, so the next push
+//! updates the newest marked comment instead of adding another one, and any
+//! older marked comments are collapsed as outdated. `--format pr-comment`
+//! writes a suitable body (see `output::pr_summary`).
+
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use serde::Deserialize;
+
+/// Hidden marker identifying the analyzer's comments
+pub const COMMENT_MARKER: &str = "<!-- cargo-analyzer:summary -->";
+
+/// A pull request comment as listed by the GitHub API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExistingComment {
+    pub id: u64,
+    /// GraphQL id, needed to minimize the comment
+    pub node_id: String,
+    #[serde(default)]
+    pub body: String,
+}
+
+/// What to do with the comments already on the pull request.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CommentPlan {
+    /// Marked comment to update; a new comment is posted when there is none
+    pub update: Option<u64>,
+    /// Older marked comments to collapse, by GraphQL id
+    pub minimize: Vec<String>,
+}
+
+/// Updates the newest marked comment of `existing`, oldest first as the API
+/// lists them, and collapses the other marked ones.
+pub fn plan_comment(existing: &[ExistingComment]) -> CommentPlan {
+    let mut marked: Vec<&ExistingComment> = existing.iter().filter(|c| c.body.contains(COMMENT_MARKER)).collect();
+    let Some(newest) = marked.pop() else { return CommentPlan::default() };
+    CommentPlan {
+        update: Some(newest.id),
+        minimize: marked.into_iter().map(|c| c.node_id.clone()).collect(),
+    }
+}
+
+/// `body` with the marker prepended, unless it already has it.
+pub fn marked_body(body: &str) -> String {
+    if body.contains(COMMENT_MARKER) {
+        body.to_string()
+    } else {
+        format!("{}\n{}", COMMENT_MARKER, body)
+    }
+}
+
+/// Result of posting a comment.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CommentOutcome {
+    Created,
+    Updated { id: u64, minimized: usize },
+}
+
+/// Posts `body` on pull request `pr` of `repo` (`OWNER/REPO`, by default the
+/// repository of `dir`'s git remote).
+pub fn post_pr_comment(dir: &Path, repo: Option<&str>, pr: u64, body: &str) -> io::Result<CommentOutcome> {
+    let repo = repo.unwrap_or("{owner}/{repo}");
+    let listed = gh(dir, &["api", "--paginate", "--jq", ".[] | {id, node_id, body}", &format!("repos/{}/issues/{}/comments", repo, pr)], None)?;
+    let existing = listed
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+        .collect::<io::Result<Vec<ExistingComment>>>()?;
+    let plan = plan_comment(&existing);
+
+    let payload = serde_json::json!({ "body": marked_body(body) }).to_string();
+    let Some(id) = plan.update else {
+        gh(dir, &["api", "-X", "POST", &format!("repos/{}/issues/{}/comments", repo, pr), "--input", "-"], Some(&payload))?;
+        return Ok(CommentOutcome::Created);
+    };
+    gh(dir, &["api", "-X", "PATCH", &format!("repos/{}/issues/comments/{}", repo, id), "--input", "-"], Some(&payload))?;
+    for node_id in &plan.minimize {
+        gh(
+            dir,
+            &[
+                "api",
+                "graphql",
+                "-f",
+                "query=mutation($id: ID!) { minimizeComment(input: {subjectId: $id, classifier: OUTDATED}) { clientMutationId } }",
+                "-f",
+                &format!("id={}", node_id),
+            ],
+            None,
+        )?;
+    }
+    Ok(CommentOutcome::Updated { id, minimized: plan.minimize.len() })
+}
+
+/// Runs `gh` with `args` in `dir`, feeding it `input`, and returns its
+/// standard output.
+fn gh(dir: &Path, args: &[&str], input: Option<&str>) -> io::Result<String> {
+    let mut child = Command::new("gh")
+        .args(args)
+        .current_dir(dir)
+        .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| io::Error::new(e.kind(), format!("Cannot run the GitHub CLI (gh): {}", e)))?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "gh {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_updates_newest_marked_comment() {
+        let comment = |id: u64, body: &str| ExistingComment { id, node_id: format!("IC_{}", id), body: body.to_string() };
+        let existing = [
+            comment(1, &marked_body("first run")),
+            comment(2, "LGTM"),
+            comment(3, &marked_body("second run")),
+            comment(4, &marked_body("third run")),
+        ];
+        assert_eq!(
+            plan_comment(&existing),
+            CommentPlan { update: Some(4), minimize: vec!["IC_1".to_string(), "IC_3".to_string()] }
+        );
+        assert_eq!(plan_comment(&existing[1..2]), CommentPlan::default());
+        assert_eq!(marked_body(&marked_body("body")), marked_body("body"));
+    }
+}
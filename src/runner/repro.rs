@@ -0,0 +1,202 @@
+//! Reproduction scripts for analysis runs.
+//!
+//! Each run can emit a `repro.sh` next to its reports recording the exact
+//! clippy command, toolchain, relevant environment, and git revision, so the
+//! clippy output behind a report can be regenerated elsewhere.
+
+use chrono::NaiveDate;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use super::git;
+
+/// Name of the reproduction script in the reports directory
+pub const REPRO_FILE: &str = "repro.sh";
+
+/// Environment variables that change what clippy reports
+const RELEVANT_ENV: &[&str] = &[
+    "RUSTUP_TOOLCHAIN",
+    "RUSTFLAGS",
+    "RUSTDOCFLAGS",
+    "CARGO_ENCODED_RUSTFLAGS",
+    "CARGO_BUILD_TARGET",
+    "CARGO_TARGET_DIR",
+    "CARGO_PROFILE_DEV_DEBUG_ASSERTIONS",
+    "CLIPPY_CONF_DIR",
+    "CLIPPY_DISABLE_DOCS_LINKS",
+];
+
+#[derive(Debug, Clone)]
+pub struct ReproInfo {
+    pub working_dir: PathBuf,
+    pub command: Vec<String>,
+    pub analyzer_args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    pub rustc_version: Option<String>,
+    pub clippy_version: Option<String>,
+    pub git_commit: Option<String>,
+    pub git_branch: Option<String>,
+}
+
+impl ReproInfo {
+    /// Captures the current environment for `command`, the clippy invocation.
+    pub fn capture(command: &Command) -> io::Result<Self> {
+        let working_dir = match command.get_current_dir() {
+            Some(dir) => dir.to_path_buf(),
+            None => std::env::current_dir()?,
+        };
+        let command_line = std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+
+        let mut env: Vec<(String, String)> = RELEVANT_ENV
+            .iter()
+            .filter_map(|name| std::env::var(name).ok().map(|value| (name.to_string(), value)))
+            .collect();
+        // Variables set explicitly on the command take precedence
+        for (name, value) in command.get_envs() {
+            let name = name.to_string_lossy().into_owned();
+            env.retain(|(existing, _)| *existing != name);
+            if let Some(value) = value {
+                env.push((name, value.to_string_lossy().into_owned()));
+            }
+        }
+
+        let git_commit = git::head_commit(&working_dir);
+        let git_branch = git::current_branch(&working_dir);
+        Ok(Self {
+            working_dir,
+            command: command_line,
+            analyzer_args: std::env::args().collect(),
+            env,
+            rustc_version: tool_version("rustc", &["-V"]),
+            clippy_version: tool_version("cargo", &["clippy", "-V"]),
+            git_commit,
+            git_branch,
+        })
+    }
+
+    /// Rustup toolchain name matching `rustc -V`, e.g. `1.80.0`,
+    /// `beta-2024-08-01`, or `nightly-2024-08-02`.
+    pub fn toolchain(&self) -> Option<String> {
+        // "rustc 1.82.0-nightly (abcdef123 2024-08-01)"
+        let version = self.rustc_version.as_deref()?;
+        let mut parts = version.split_whitespace().skip(1);
+        let release = parts.next()?;
+        let Some((_, channel)) = release.split_once('-') else {
+            return Some(release.to_string());
+        };
+        let date = parts.nth(1)?.trim_end_matches(')');
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+        // "beta.3" is published as plain "beta"
+        match channel.split('.').next()? {
+            // rustc reports the commit date; the nightly is published the day after
+            "nightly" => Some(format!("nightly-{}", date.succ_opt()?)),
+            channel => Some(format!("{}-{}", channel, date)),
+        }
+    }
+}
+
+/// The `cargo -V` version of the active toolchain.
+pub fn cargo_version() -> Option<String> {
+    tool_version("cargo", &["-V"])
+}
+
+/// The `rustc -V` and `cargo clippy -V` versions of the active toolchain.
+pub fn toolchain_versions() -> (Option<String>, Option<String>) {
+    (tool_version("rustc", &["-V"]), tool_version("cargo", &["clippy", "-V"]))
+}
+
+fn tool_version(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    Some(text.trim().to_string()).filter(|text| !text.is_empty())
+}
+
+/// Quotes `value` for a POSIX shell.
+fn shell_quote(value: &str) -> String {
+    if !value.is_empty()
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c))
+    {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+pub fn write_repro_script<W: Write>(mut writer: W, info: &ReproInfo, timestamp: &str) -> io::Result<()> {
+    writeln!(writer, "#!/bin/sh")?;
+    writeln!(writer, "# Reproduces the clippy output of the cargo-analyzer run {}.", timestamp)?;
+    writeln!(writer, "#")?;
+    writeln!(writer, "# rustc:  {}", info.rustc_version.as_deref().unwrap_or("unknown"))?;
+    writeln!(writer, "# clippy: {}", info.clippy_version.as_deref().unwrap_or("unknown"))?;
+    match (&info.git_commit, &info.git_branch) {
+        (Some(commit), Some(branch)) => writeln!(writer, "# commit: {} ({})", commit, branch)?,
+        (Some(commit), None) => writeln!(writer, "# commit: {}", commit)?,
+        _ => writeln!(writer, "# commit: not a git repository")?,
+    }
+    writeln!(writer, "set -eu\n")?;
+
+    writeln!(writer, "cd {}", shell_quote(&info.working_dir.to_string_lossy()))?;
+    if let Some(commit) = &info.git_commit {
+        writeln!(writer, "# Uncomment to analyze the exact revision:")?;
+        writeln!(writer, "# git checkout --detach {}", commit)?;
+    }
+    writeln!(writer)?;
+
+    let has_toolchain = info.env.iter().any(|(name, _)| name == "RUSTUP_TOOLCHAIN");
+    if let (false, Some(toolchain)) = (has_toolchain, info.toolchain()) {
+        writeln!(writer, "export RUSTUP_TOOLCHAIN={}", shell_quote(&toolchain))?;
+    }
+    for (name, value) in &info.env {
+        writeln!(writer, "export {}={}", name, shell_quote(value))?;
+    }
+    writeln!(writer)?;
+
+    let command: Vec<String> = info.command.iter().map(|arg| shell_quote(arg)).collect();
+    writeln!(writer, "{} > clippy_output.json", command.join(" "))?;
+
+    let analyzer_args: Vec<String> = info.analyzer_args.iter().map(|arg| shell_quote(arg)).collect();
+    writeln!(writer, "\n# Original analyzer invocation:")?;
+    writeln!(writer, "# {}", analyzer_args.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(rustc_version: &str) -> ReproInfo {
+        ReproInfo {
+            working_dir: PathBuf::from("/work"),
+            command: vec!["cargo".to_string(), "clippy".to_string()],
+            analyzer_args: Vec::new(),
+            env: Vec::new(),
+            rustc_version: Some(rustc_version.to_string()),
+            clippy_version: None,
+            git_commit: None,
+            git_branch: None,
+        }
+    }
+
+    #[test]
+    fn test_toolchain() {
+        assert_eq!(info("rustc 1.80.0 (051478957 2024-07-21)").toolchain().as_deref(), Some("1.80.0"));
+        assert_eq!(info("rustc 1.83.0-beta.3 (1b3e0e7d5 2024-10-22)").toolchain().as_deref(), Some("beta-2024-10-22"));
+        assert_eq!(info("rustc 1.82.0-nightly (abcdef123 2024-08-31)").toolchain().as_deref(), Some("nightly-2024-09-01"));
+        assert_eq!(info("rustc 1.82.0-nightly").toolchain(), None);
+    }
+
+    #[test]
+    fn test_repro_script_pins_toolchain() {
+        let mut out = Vec::new();
+        write_repro_script(&mut out, &info("rustc 1.83.0-beta.3 (1b3e0e7d5 2024-10-22)"), "20241022_120000").unwrap();
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("cd /work\n"));
+        assert!(script.contains("export RUSTUP_TOOLCHAIN=beta-2024-10-22\n"));
+        assert!(script.contains("cargo clippy > clippy_output.json\n"));
+    }
+}
@@ -0,0 +1,124 @@
+//! Working from saved output instead of running clippy.
+//!
+//! The `report` subcommand re-renders every report from a JSON or JSONL
+//! warnings export, `diff` compares two exports, and `trends` prints the
+//! recorded history; none of them rebuilds the workspace.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use crate::{
+    analysis::{
+        baseline::{Baseline, BaselineSource},
+        plugin::{SourceContext, WarningSource},
+        statistics::warning::WarningStatistics,
+        trends::TrendAnalysis,
+    },
+    output::{baseline_report::write_baseline_report, json_export::read_warnings},
+    types::{Priority, Warning},
+};
+
+/// The warnings of a JSON or JSONL export, as a source for re-rendering.
+pub struct SavedWarningsSource {
+    path: PathBuf,
+}
+
+impl SavedWarningsSource {
+    pub fn new(path: &Path) -> Self {
+        Self { path: path.to_path_buf() }
+    }
+}
+
+impl WarningSource for SavedWarningsSource {
+    fn name(&self) -> &str {
+        "saved warnings"
+    }
+
+    fn collect(&self, _ctx: &mut SourceContext<'_>) -> io::Result<Vec<Warning>> {
+        read_warnings(&self.path)
+    }
+
+    fn is_required(&self) -> bool {
+        true
+    }
+}
+
+/// Writes the comparison of the warnings saved in `new` with those in
+/// `old` as a markdown baseline report.
+pub fn write_saved_diff<W: Write>(writer: W, old: &Path, new: &Path) -> io::Result<()> {
+    let baseline = Baseline::from_warnings("", "", BaselineSource::File(old.to_path_buf()), read_warnings(old)?);
+    let warnings = read_warnings(new)?;
+    let files: HashSet<&str> = warnings.iter().map(|w| w.file.as_str()).collect();
+    let stats = WarningStatistics::from_warnings(&warnings, files.len());
+    write_baseline_report(writer, &baseline, &baseline.delta(&stats), &warnings)
+}
+
+/// Formats recorded runs, oldest first, as an aligned table.
+pub fn format_trends(trends: &[TrendAnalysis]) -> String {
+    let mut table = format!(
+        "{:<10}  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}\n",
+        "date", "total", "critical", "high", "change", "new", "fixed"
+    );
+    let mut previous: Option<usize> = None;
+    for trend in trends {
+        let priority = |p| trend.by_priority.get(&p).copied().unwrap_or(0);
+        let optional = |n: Option<usize>| n.map_or_else(|| "-".to_string(), |n| n.to_string());
+        let change = previous.map(|p| format!("{:+}", trend.total_warnings as i64 - p as i64));
+        let _ = writeln!(
+            table,
+            "{:<10}  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}",
+            trend.dates.first().map_or("-", String::as_str),
+            trend.total_warnings,
+            priority(Priority::Critical),
+            priority(Priority::High),
+            change.as_deref().unwrap_or("-"),
+            optional(trend.new_warnings),
+            optional(trend.fixed_warnings)
+        );
+        previous = Some(trend.total_warnings);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::fingerprint::Fingerprinter;
+    use crate::output::json_export::{write_json, write_jsonl};
+
+    #[test]
+    fn test_diff_saved_exports() {
+        let warning = |lint: &str, line| Warning {
+            primary_message: format!("{} here", lint),
+            priority: Priority::Low,
+            ..Warning::for_test(lint, "src/lib.rs", line)
+        };
+        let mut old = vec![warning("clippy::needless_borrow", 3), warning("clippy::len_zero", 9)];
+        let mut new = vec![warning("clippy::needless_borrow", 3), warning("clippy::redundant_clone", 12)];
+        Fingerprinter::new(None).assign(&mut old);
+        Fingerprinter::new(None).assign(&mut new);
+
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let (old_path, new_path) = (dir.join("old.json"), dir.join("new.jsonl"));
+        write_json(std::fs::File::create(&old_path).unwrap(), &old).unwrap();
+        write_jsonl(std::fs::File::create(&new_path).unwrap(), &new).unwrap();
+        assert_eq!(read_warnings(&new_path).unwrap()[1].fingerprint, new[1].fingerprint);
+
+        let mut output = Vec::new();
+        write_saved_diff(&mut output, &old_path, &new_path).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("the warnings saved in"));
+        let new_section = output.split("## New Warnings").nth(1).unwrap();
+        assert!(new_section.contains("clippy::redundant_clone"));
+        assert!(!new_section.split("## Fixed Warnings").next().unwrap().contains("needless_borrow"));
+        assert!(output.split("## Fixed Warnings").nth(1).unwrap().contains("clippy::len_zero"));
+
+        let runs = [
+            TrendAnalysis { dates: vec!["2026-10-01".to_string()], total_warnings: 5, ..Default::default() },
+            TrendAnalysis { dates: vec!["2026-10-02".to_string()], total_warnings: 3, ..Default::default() },
+        ];
+        assert!(format_trends(&runs).lines().nth(2).unwrap().contains("-2"));
+    }
+}
@@ -0,0 +1,345 @@
+//! Self-analysis regression suite.
+//!
+//! `cargo analyzer analyze-self` runs clippy on the analyzer's own crate with
+//! a pinned lint set and config and compares how the warnings are
+//! categorized with a committed snapshot of expected findings. It is meant
+//! for projects that vendor the analyzer: after an upgrade, findings whose
+//! category or priority changed point to a change in categorization rather
+//! than in their own code. `--bless` writes the current findings as the new
+//! snapshot. The crate ships the snapshot of its own release as
+//! `analyzer-self-snapshot.json`, taken with the clippy named in it.
+//!
+//! The workspace's `clippy.toml` is ignored. Its `[lints]` table, and the
+//! crate's, still apply: cargo passes them to clippy along with the pinned
+//! lint flags, so a workspace that enables more lints there gets more
+//! findings than the snapshot.
+//!
+//! Findings are matched by file, lint, and message, without line numbers,
+//! so that code moving within a file does not count as a change. Paths are
+//! relative to the analyzer crate, wherever it is vendored.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::{
+    analysis::{
+        pipeline::{AnalysisOptions, analyze},
+        plugin::{SourceContext, WarningSource},
+    },
+    config::AnalyzerConfig,
+    output::progress::ProgressReporter,
+    types::{CategoryType, Priority, Warning},
+};
+use super::{cargo::{workspace_root, workspace_target_dir}, clippy::ClippySource, repro::toolchain_versions};
+
+/// Snapshot written when none is given, relative to the current directory
+pub const DEFAULT_SELF_SNAPSHOT: &str = "analyzer-self-snapshot.json";
+
+/// Version of the snapshot layout
+pub const SELF_SNAPSHOT_VERSION: u32 = 1;
+
+/// Lint flags clippy runs with on the analyzer crate
+pub const SELF_ANALYSIS_CLIPPY_ARGS: &[&str] = &["-W", "clippy::all", "-W", "clippy::pedantic"];
+
+/// Source of the analyzer crate this binary was built from. Installed
+/// binaries are built in a directory that is gone or holds no crate, so
+/// the source has to be given with `--crate-dir`.
+pub fn self_crate_dir() -> io::Result<PathBuf> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    if !dir.join("Cargo.toml").is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("This analyzer was built in {}, which holds no crate; pass its source with --crate-dir", dir.display()),
+        ));
+    }
+    Ok(dir)
+}
+
+/// The config the analyzer crate is analyzed with: the defaults, with
+/// generated code included, since a vendored crate is under `vendor/`.
+pub fn pinned_config() -> AnalyzerConfig {
+    AnalyzerConfig { include_generated: true, ..AnalyzerConfig::default() }
+}
+
+/// One warning on the analyzer crate and how it was categorized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Finding {
+    /// Path relative to the analyzer crate
+    pub file: String,
+    pub lint_code: String,
+    pub message: String,
+    pub category: CategoryType,
+    pub priority: Priority,
+}
+
+impl Finding {
+    fn key(&self) -> (&str, &str, &str) {
+        (&self.file, &self.lint_code, &self.message)
+    }
+
+    fn classification(&self) -> String {
+        format!("{}/{}", self.category, self.priority)
+    }
+}
+
+/// The expected findings of the self-analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindingsSnapshot {
+    pub version: u32,
+    /// `cargo clippy -V` of the run
+    pub clippy_version: Option<String>,
+    /// Sorted by file, lint, and message
+    pub findings: Vec<Finding>,
+}
+
+impl FindingsSnapshot {
+    /// The findings of `warnings`, with paths made relative to `crate_prefix`,
+    /// the analyzer crate's path within its workspace.
+    pub fn from_warnings(warnings: &[Warning], crate_prefix: &Path, clippy_version: Option<String>) -> Self {
+        let mut findings: Vec<Finding> = warnings
+            .iter()
+            .map(|warning| {
+                let file = Path::new(&warning.file);
+                Finding {
+                    file: file.strip_prefix(crate_prefix).unwrap_or(file).to_string_lossy().replace('\\', "/"),
+                    lint_code: warning.lint_code.clone(),
+                    message: warning.primary_message.clone(),
+                    category: warning.category,
+                    priority: warning.priority,
+                }
+            })
+            .collect();
+        findings.sort_by(|a, b| {
+            a.key()
+                .cmp(&b.key())
+                .then(a.category.cmp(&b.category))
+                .then(b.priority.severity_score().cmp(&a.priority.severity_score()))
+        });
+        Self { version: SELF_SNAPSHOT_VERSION, clippy_version, findings }
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No self-analysis snapshot at {}; create it with `analyze-self --bless`", path.display()),
+            ),
+            _ => e,
+        })?;
+        let snapshot: Self = serde_json::from_str(&contents).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("Invalid snapshot {}: {}", path.display(), e))
+        })?;
+        if snapshot.version > SELF_SNAPSHOT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Snapshot {} has version {}, newer than this analyzer's", path.display(), snapshot.version),
+            ));
+        }
+        Ok(snapshot)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.write_all(b"\n")?;
+        writer.flush()
+    }
+}
+
+/// Differences between the expected and the current findings.
+#[derive(Debug, Default)]
+pub struct SnapshotDiff {
+    /// Findings whose category or priority changed, as `(expected, current)`
+    pub recategorized: Vec<(Finding, Finding)>,
+    /// Current findings the snapshot lacks
+    pub added: Vec<Finding>,
+    /// Expected findings no longer reported
+    pub removed: Vec<Finding>,
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.recategorized.is_empty() && self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Compares `current` with `expected`. Findings with the same file, lint,
+/// and message that are categorized alike are matched first; the rest are
+/// paired up as recategorized, and any left over were added or removed.
+pub fn compare_findings(expected: &FindingsSnapshot, current: &FindingsSnapshot) -> SnapshotDiff {
+    type Groups<'a> = BTreeMap<(&'a str, &'a str, &'a str), Vec<&'a Finding>>;
+    fn group(snapshot: &FindingsSnapshot) -> Groups<'_> {
+        let mut groups = Groups::new();
+        for finding in &snapshot.findings {
+            groups.entry(finding.key()).or_default().push(finding);
+        }
+        groups
+    }
+    let (mut expected, mut current) = (group(expected), group(current));
+    let mut keys: Vec<_> = expected.keys().chain(current.keys()).copied().collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    let mut diff = SnapshotDiff::default();
+    for key in keys {
+        let mut before = expected.remove(&key).unwrap_or_default();
+        let mut after = current.remove(&key).unwrap_or_default();
+        before.retain(|finding| match after.iter().position(|other| other == finding) {
+            Some(index) => {
+                after.remove(index);
+                false
+            }
+            None => true,
+        });
+        let paired = before.len().min(after.len());
+        diff.recategorized
+            .extend(before.drain(..paired).zip(after.drain(..paired)).map(|(a, b)| (a.clone(), b.clone())));
+        diff.removed.extend(before.into_iter().cloned());
+        diff.added.extend(after.into_iter().cloned());
+    }
+    diff
+}
+
+/// Formats `diff` for the terminal, noting a change of clippy version,
+/// which changes findings by itself.
+pub fn format_snapshot_diff(diff: &SnapshotDiff, expected: &FindingsSnapshot, current: &FindingsSnapshot) -> String {
+    let mut text = String::new();
+    if !diff.recategorized.is_empty() {
+        let _ = writeln!(text, "Recategorized ({}):", diff.recategorized.len());
+        for (before, after) in &diff.recategorized {
+            let _ = writeln!(
+                text,
+                "  {} {}: {} -> {} ({})",
+                before.file,
+                before.lint_code,
+                before.classification(),
+                after.classification(),
+                before.message
+            );
+        }
+    }
+    for (label, findings) in [("Added", &diff.added), ("Removed", &diff.removed)] {
+        if findings.is_empty() {
+            continue;
+        }
+        let _ = writeln!(text, "{} ({}):", label, findings.len());
+        for finding in findings {
+            let _ = writeln!(
+                text,
+                "  {} {}: {} ({})",
+                finding.file,
+                finding.lint_code,
+                finding.classification(),
+                finding.message
+            );
+        }
+    }
+    if expected.clippy_version != current.clippy_version {
+        let _ = writeln!(
+            text,
+            "Note: the snapshot was taken with {}, this run used {}; added and removed findings may come from clippy.",
+            expected.clippy_version.as_deref().unwrap_or("an unknown clippy"),
+            current.clippy_version.as_deref().unwrap_or("an unknown clippy")
+        );
+    }
+    text
+}
+
+/// Runs clippy on the analyzer crate at `crate_dir` with the pinned lint
+/// flags and config, and no `clippy.toml`, and returns its findings.
+pub fn analyze_self(crate_dir: &Path, progress: &mut dyn ProgressReporter) -> io::Result<FindingsSnapshot> {
+    let manifest = std::path::absolute(crate_dir.join("Cargo.toml"))?;
+    if !manifest.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("No analyzer crate at {}; pass its source with --crate-dir", crate_dir.display()),
+        ));
+    }
+    let root = workspace_root(&manifest)?;
+    let crate_prefix = manifest.parent().and_then(|dir| dir.strip_prefix(&root).ok()).unwrap_or(Path::new(""));
+
+    // An empty clippy.toml, so clippy stops looking for the workspace's
+    let conf_dir = std::env::temp_dir().join(format!("cargo-analyzer-self-conf-{}", std::process::id()));
+    std::fs::create_dir_all(&conf_dir)?;
+    std::fs::write(conf_dir.join("clippy.toml"), "")?;
+    let mut clippy = ClippySource::new();
+    clippy.set_quiet(true);
+    clippy.set_clippy_args(SELF_ANALYSIS_CLIPPY_ARGS.iter().map(|arg| arg.to_string()).collect());
+    clippy.set_conf_dir(conf_dir.clone());
+    let cargo_args = ["--manifest-path".to_string(), manifest.to_string_lossy().into_owned()];
+    let mut ctx = SourceContext::new(&root, &cargo_args, progress);
+    let warnings = clippy.collect(&mut ctx);
+    std::fs::remove_dir_all(&conf_dir)?;
+    let (parsed, completeness) = ctx.finish(warnings?);
+    if !completeness.is_complete() {
+        return Err(io::Error::new(io::ErrorKind::Interrupted, completeness.to_string()));
+    }
+
+    let mut options = AnalysisOptions::from_config(&pinned_config())?;
+    options.source_root = Some(root.clone());
+    options.generated.set_target_dir(workspace_target_dir(&root));
+    let warnings = analyze(parsed, &options).warnings;
+    Ok(FindingsSnapshot::from_warnings(&warnings, crate_prefix, toolchain_versions().1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_findings() {
+        let finding = |file: &str, lint: &str, category, priority| Finding {
+            file: file.to_string(),
+            lint_code: lint.to_string(),
+            message: "message".to_string(),
+            category,
+            priority,
+        };
+        let snapshot = |findings: Vec<Finding>, clippy: &str| FindingsSnapshot {
+            version: SELF_SNAPSHOT_VERSION,
+            clippy_version: Some(clippy.to_string()),
+            findings,
+        };
+        let expected = snapshot(
+            vec![
+                finding("src/lib.rs", "clippy::needless_borrow", CategoryType::Style, Priority::Low),
+                finding("src/lib.rs", "clippy::needless_borrow", CategoryType::Style, Priority::Low),
+                finding("src/main.rs", "clippy::redundant_clone", CategoryType::Style, Priority::Low),
+                finding("src/parser.rs", "clippy::unwrap_used", CategoryType::Safety, Priority::High),
+            ],
+            "clippy 0.1.80",
+        );
+        let current = snapshot(
+            vec![
+                finding("src/lib.rs", "clippy::needless_borrow", CategoryType::Style, Priority::Low),
+                finding("src/lib.rs", "clippy::needless_borrow", CategoryType::Style, Priority::Low),
+                finding("src/main.rs", "clippy::redundant_clone", CategoryType::Performance, Priority::High),
+                finding("src/report.rs", "clippy::too_many_lines", CategoryType::Style, Priority::Medium),
+            ],
+            "clippy 0.1.82",
+        );
+
+        assert!(compare_findings(&expected, &expected).is_empty());
+        let diff = compare_findings(&expected, &current);
+        assert_eq!(diff.recategorized.len(), 1);
+        assert_eq!(diff.recategorized[0].1.category, CategoryType::Performance);
+        assert_eq!(diff.added[0].file, "src/report.rs");
+        assert_eq!(diff.removed[0].lint_code, "clippy::unwrap_used");
+
+        let text = format_snapshot_diff(&diff, &expected, &current);
+        assert!(text.contains("src/main.rs clippy::redundant_clone: Style/Low -> Performance/High"));
+        assert!(text.contains("clippy 0.1.80"));
+
+        let warning = Warning {
+            primary_message: "message".to_string(),
+            priority: Priority::Low,
+            crate_name: Some("cargo_analyzer".to_string()),
+            ..Warning::for_test("clippy::redundant_clone", "vendor/cargo-analyzer/src/main.rs", 12)
+        };
+        let vendored = FindingsSnapshot::from_warnings(&[warning], Path::new("vendor/cargo-analyzer"), None);
+        assert_eq!(vendored.findings[0].file, "src/main.rs");
+    }
+}
@@ -0,0 +1,20 @@
+//! Running cargo-udeps against the analyzed workspace.
+
+use std::path::Path;
+use super::cargo::cargo_command;
+use crate::analysis::dependencies::UnusedDependencies;
+
+/// Runs `cargo +nightly udeps --output json` in `root`.
+///
+/// Returns `None` when the nightly toolchain or cargo-udeps is missing, or
+/// the output cannot be parsed. cargo-udeps exits non-zero when it finds
+/// unused dependencies, so the exit status is not checked.
+pub fn run_udeps(root: &Path, cargo_args: &[String]) -> Option<UnusedDependencies> {
+    let output = cargo_command(root)
+        .args(["+nightly", "udeps", "--output", "json"])
+        .args(cargo_args)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    UnusedDependencies::from_udeps_json(&stdout).ok()
+}
@@ -0,0 +1,361 @@
+//! Dependency upgrade checks.
+//!
+//! `cargo analyzer upgrade-check --package serde --version 2.0` copies the
+//! workspace (without `target` and `.git`) to a directory next to it, so
+//! relative path dependencies such as `../shared` still resolve, and runs
+//! the analysis there. It then requires the new version of the package in
+//! every manifest depending on it and runs the analysis again. Both builds
+//! use the workspace's target directory, so compiled dependencies are
+//! reused. Warnings are matched by fingerprint, so the comparison lists
+//! exactly the compile errors and warnings the upgrade introduces. The
+//! workspace's sources are never touched.
+//!
+//! Dependencies inherited with `workspace = true` follow the
+//! `[workspace.dependencies]` entry; path and git dependencies are left as
+//! they are.
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+use crate::analysis::fingerprint::{diff_fingerprints, fingerprint_counts};
+use crate::types::Warning;
+use super::cargo::cargo_command;
+
+/// New compile errors and warnings listed in the report
+const MAX_LISTED_WARNINGS: usize = 50;
+
+/// Directories not copied into the temporary workspace
+const SKIPPED_DIRS: [&str; 2] = ["target", ".git"];
+
+/// Dependency tables of a manifest, and of each `[target.<cfg>]` table
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// A dependency requirement to try.
+#[derive(Debug, Clone)]
+pub struct DependencyUpgrade {
+    pub package: String,
+    /// Version requirement, e.g. `2.0`
+    pub version: String,
+}
+
+impl DependencyUpgrade {
+    pub fn new(package: &str, version: &str) -> Self {
+        Self { package: package.to_string(), version: version.to_string() }
+    }
+
+    /// Requires the new version in every manifest under `root`, then lets
+    /// cargo update the lock file. Returns the manifests changed.
+    pub fn apply(&self, root: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut changed = Vec::new();
+        for manifest in find_manifests(root)? {
+            let contents = std::fs::read_to_string(&manifest)?;
+            let mut table: Table = contents.parse().map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("Invalid manifest {}: {}", manifest.display(), e))
+            })?;
+            if self.set_requirement(&mut table) > 0 {
+                std::fs::write(&manifest, table.to_string())?;
+                changed.push(manifest);
+            }
+        }
+        if changed.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("No manifest in the workspace depends on {} from a registry", self.package),
+            ));
+        }
+
+        // Picks the newest release matching a requirement the lock file
+        // already satisfied; fails harmlessly when several versions of the
+        // package are locked, as the build then resolves the requirement
+        let _ = cargo_command(root).args(["update", "--package", &self.package]).output()?;
+        Ok(changed)
+    }
+
+    /// Sets the version of the package in the dependency tables of
+    /// `manifest`, returning the entries changed.
+    pub fn set_requirement(&self, manifest: &mut Table) -> usize {
+        let mut tables: Vec<&mut Table> = Vec::new();
+        let mut targets = None;
+        let mut workspace = None;
+        for (key, value) in manifest.iter_mut() {
+            let Value::Table(table) = value else { continue };
+            match key.as_str() {
+                key if DEPENDENCY_TABLES.contains(&key) => tables.push(table),
+                "target" => targets = Some(table),
+                "workspace" => workspace = Some(table),
+                _ => {}
+            }
+        }
+        if let Some(Value::Table(table)) = workspace.and_then(|workspace| workspace.get_mut("dependencies")) {
+            tables.push(table);
+        }
+        for target in targets.into_iter().flat_map(|targets| targets.iter_mut().map(|(_, v)| v)) {
+            if let Value::Table(target) = target {
+                for (key, value) in target.iter_mut() {
+                    if let (true, Value::Table(table)) = (DEPENDENCY_TABLES.contains(&key.as_str()), value) {
+                        tables.push(table);
+                    }
+                }
+            }
+        }
+
+        let mut changed = 0;
+        for table in tables {
+            for (name, dependency) in table.iter_mut() {
+                let package = match &*dependency {
+                    Value::Table(spec) => spec.get("package").and_then(Value::as_str).unwrap_or(name.as_str()),
+                    _ => name.as_str(),
+                };
+                if package != self.package {
+                    continue;
+                }
+                match dependency {
+                    Value::String(version) => *version = self.version.clone(),
+                    Value::Table(spec) => {
+                        if ["workspace", "path", "git"].iter().any(|key| spec.contains_key(*key)) {
+                            continue;
+                        }
+                        spec.insert("version".to_string(), Value::String(self.version.clone()));
+                    }
+                    _ => continue,
+                }
+                changed += 1;
+            }
+        }
+        changed
+    }
+}
+
+/// A temporary copy of a workspace, removed when dropped.
+#[derive(Debug)]
+pub struct WorkspaceCopy {
+    pub root: PathBuf,
+}
+
+impl WorkspaceCopy {
+    /// Copies the workspace at `root` into a new directory beside it, at the
+    /// same depth, so paths leaving the workspace point at the same files.
+    pub fn create(root: &Path) -> io::Result<Self> {
+        let name = format!(
+            ".{}-upgrade-{}",
+            root.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+            std::process::id()
+        );
+        let copy = Self { root: root.parent().unwrap_or(root).join(name) };
+        if copy.root.exists() {
+            std::fs::remove_dir_all(&copy.root)?;
+        }
+        copy_dir(root, &copy.root)?;
+        Ok(copy)
+    }
+}
+
+impl Drop for WorkspaceCopy {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Copies `from` into `to`. Symbolic links are copied as links, not
+/// followed, so a link to a parent directory cannot recurse forever.
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            copy_symlink(&path, &to.join(&name))?;
+        } else if file_type.is_dir() {
+            if !SKIPPED_DIRS.iter().any(|dir| name == *dir) {
+                copy_dir(&path, &to.join(&name))?;
+            }
+        } else {
+            std::fs::copy(&path, to.join(&name))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(link: &Path, to: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(std::fs::read_link(link)?, to)
+}
+
+/// Copies what a link to a file points at; links to directories are skipped.
+#[cfg(not(unix))]
+fn copy_symlink(link: &Path, to: &Path) -> io::Result<()> {
+    match std::fs::metadata(link) {
+        Ok(metadata) if metadata.is_file() => std::fs::copy(link, to).map(|_| ()),
+        _ => Ok(()),
+    }
+}
+
+/// The `Cargo.toml` files under `root`.
+fn find_manifests(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut manifests = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                if !SKIPPED_DIRS.iter().any(|skipped| entry.file_name() == *skipped) {
+                    dirs.push(path);
+                }
+            } else if path.file_name().is_some_and(|name| name == "Cargo.toml") {
+                manifests.push(path);
+            }
+        }
+    }
+    manifests.sort();
+    Ok(manifests)
+}
+
+/// The warnings an upgrade introduces and resolves.
+#[derive(Debug)]
+pub struct UpgradeComparison<'a> {
+    pub before_warnings: usize,
+    pub before_errors: usize,
+    pub after_warnings: usize,
+    pub after_errors: usize,
+    /// Compile errors and warnings new after the upgrade, errors first
+    pub new: Vec<&'a Warning>,
+    /// Warnings gone after the upgrade
+    pub resolved: usize,
+}
+
+impl<'a> UpgradeComparison<'a> {
+    pub fn new(before: &[Warning], after: &'a [Warning]) -> Self {
+        let errors = |warnings: &[Warning]| warnings.iter().filter(|w| w.is_error).count();
+        let diff = diff_fingerprints(&fingerprint_counts(before), after, Warning::fingerprint);
+        let resolved = diff.fixed_count();
+        let mut new = diff.new;
+        new.sort_by_key(|w| !w.is_error);
+        Self {
+            before_warnings: before.len() - errors(before),
+            before_errors: errors(before),
+            after_warnings: after.len() - errors(after),
+            after_errors: errors(after),
+            new,
+            resolved,
+        }
+    }
+
+    pub fn new_errors(&self) -> usize {
+        self.new.iter().filter(|w| w.is_error).count()
+    }
+}
+
+/// Writes the comparison as markdown.
+pub fn write_upgrade_report<W: Write>(
+    mut writer: W,
+    upgrade: &DependencyUpgrade,
+    manifests: usize,
+    comparison: &UpgradeComparison,
+) -> io::Result<()> {
+    writeln!(writer, "# Upgrade Check: `{}` {}\n", upgrade.package, upgrade.version)?;
+    writeln!(
+        writer,
+        "Clippy ran on a copy of the workspace before and after requiring `{} = \"{}\"` in {} manifests.\n",
+        upgrade.package, upgrade.version, manifests
+    )?;
+    let new_errors = comparison.new_errors();
+    let new_warnings = comparison.new.len() - new_errors;
+    if new_errors > 0 {
+        writeln!(writer, "**The upgrade breaks the build: {} new compile errors.**\n", new_errors)?;
+    } else if new_warnings == 0 {
+        writeln!(writer, "The upgrade introduces no compile errors or warnings.\n")?;
+    }
+
+    writeln!(writer, "| | Before | After | Change |")?;
+    writeln!(writer, "|---|---:|---:|---:|")?;
+    for (label, before, after) in [
+        ("**Compile errors**", comparison.before_errors, comparison.after_errors),
+        ("Warnings", comparison.before_warnings, comparison.after_warnings),
+    ] {
+        writeln!(writer, "| {} | {} | {} | {:+} |", label, before, after, after as i64 - before as i64)?;
+    }
+    writeln!(writer)?;
+
+    let (errors, warnings) = comparison.new.split_at(new_errors);
+    write_new(&mut writer, "New Compile Errors", errors)?;
+    write_new(&mut writer, "New Warnings", warnings)?;
+    if comparison.resolved > 0 {
+        writeln!(writer, "## Resolved\n\n{} warnings are gone after the upgrade.\n", comparison.resolved)?;
+    }
+    Ok(())
+}
+
+fn write_new<W: Write>(writer: &mut W, title: &str, warnings: &[&Warning]) -> io::Result<()> {
+    if warnings.is_empty() {
+        return Ok(());
+    }
+    writeln!(writer, "## {}\n", title)?;
+    for warning in warnings.iter().take(MAX_LISTED_WARNINGS) {
+        writeln!(writer, "- `{}:{}` `{}` {}", warning.file, warning.line, warning.lint_code, warning.primary_message)?;
+    }
+    if warnings.len() > MAX_LISTED_WARNINGS {
+        writeln!(writer, "- ... and {} more", warnings.len() - MAX_LISTED_WARNINGS)?;
+    }
+    writeln!(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    #[test]
+    fn test_set_requirement() {
+        let mut manifest: Table = r#"
+            [workspace.dependencies]
+            serde = { version = "1.0", features = ["derive"] }
+
+            [dependencies]
+            serde = { workspace = true }
+            json = { package = "serde_json", version = "1" }
+            local = { path = "../local" }
+
+            [target.'cfg(unix)'.dev-dependencies]
+            serde = "1.0.100"
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(DependencyUpgrade::new("serde", "2.0").set_requirement(&mut manifest), 2);
+        assert_eq!(manifest["workspace"]["dependencies"]["serde"]["version"].as_str(), Some("2.0"));
+        assert_eq!(manifest["workspace"]["dependencies"]["serde"]["features"][0].as_str(), Some("derive"));
+        assert!(manifest["dependencies"]["serde"].get("version").is_none());
+        assert_eq!(manifest["target"]["cfg(unix)"]["dev-dependencies"]["serde"].as_str(), Some("2.0"));
+
+        assert_eq!(DependencyUpgrade::new("serde_json", "2").set_requirement(&mut manifest), 1);
+        assert_eq!(manifest["dependencies"]["json"]["version"].as_str(), Some("2"));
+        assert_eq!(DependencyUpgrade::new("local", "2").set_requirement(&mut manifest), 0);
+    }
+
+    #[test]
+    fn test_upgrade_report() {
+        let warning = |lint: &str, is_error| Warning {
+            primary_message: "message".to_string(),
+            priority: Priority::Low,
+            is_error,
+            fingerprint: lint.to_string(),
+            ..Warning::for_test(lint, "src/lib.rs", 3)
+        };
+        let before = [warning("clippy::len_zero", false), warning("deprecated", false)];
+        let after = [warning("clippy::len_zero", false), warning("E0599", true)];
+        let comparison = UpgradeComparison::new(&before, &after);
+        assert_eq!((comparison.new_errors(), comparison.resolved), (1, 1));
+
+        let mut out = Vec::new();
+        write_upgrade_report(&mut out, &DependencyUpgrade::new("serde", "2.0"), 1, &comparison).unwrap();
+        let report = String::from_utf8(out).unwrap();
+        assert!(report.contains("**The upgrade breaks the build: 1 new compile errors.**"));
+        assert!(report.contains("| **Compile errors** | 0 | 1 | +1 |"));
+        assert!(report.contains("## New Compile Errors\n\n- `src/lib.rs:3` `E0599` message"));
+        assert!(!report.contains("## New Warnings"));
+    }
+}
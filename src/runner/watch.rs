@@ -0,0 +1,174 @@
+//! Watch mode.
+//!
+//! The workspace's Rust sources and manifests are polled for changes; on
+//! each change clippy runs again, reusing cargo's incremental build, and
+//! only the warnings introduced or resolved since the previous run are
+//! printed. Warnings are matched by fingerprint, so edits that merely move
+//! a warning do not report it as resolved and new.
+//!
+//! For editors without a language server integration, watch mode can also
+//! keep a problems file, `.analyzer-problems` by default, holding every
+//! current warning in quickfix format:
+//!
+//! ```text
+//! src/parser.rs:42:9: warning: redundant clone [clippy::redundant_clone]
+//! ```
+//!
+//! which matches the default `errorformat` of Vim and Neovim (`:cfile`) and
+//! the problem matchers of most other editors. The file is replaced whole
+//! after each complete run, so a poller never reads it half written.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use crate::types::Warning;
+
+/// How often the sources are checked for changes, in milliseconds
+pub const POLL_INTERVAL_MS: u64 = 500;
+
+/// Problems file written when `--problems-file` is given without a path
+pub const DEFAULT_PROBLEMS_FILE: &str = ".analyzer-problems";
+
+/// Modification times of the files that affect clippy's output.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceSnapshot {
+    modified: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl SourceSnapshot {
+    /// Records the `.rs` and `.toml` files under `root`, skipping `target`
+    /// and hidden directories.
+    pub fn capture(root: &Path) -> Self {
+        let mut snapshot = Self::default();
+        snapshot.scan(root);
+        snapshot
+    }
+
+    fn scan(&mut self, dir: &Path) {
+        let Ok(entries) = std::fs::read_dir(dir) else { return };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if path.is_dir() {
+                if name != "target" && !name.starts_with('.') {
+                    self.scan(&path);
+                }
+            } else if name.ends_with(".rs") || name.ends_with(".toml") {
+                if let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) {
+                    self.modified.insert(path, modified);
+                }
+            }
+        }
+    }
+
+    /// Files added, removed, or modified since `earlier`.
+    pub fn changed_since(&self, earlier: &SourceSnapshot) -> usize {
+        let changed = self.modified.iter().filter(|(path, time)| earlier.modified.get(*path) != Some(time)).count();
+        let removed = earlier.modified.keys().filter(|path| !self.modified.contains_key(*path)).count();
+        changed + removed
+    }
+}
+
+/// The warnings of a run compared with those of the previous one.
+#[derive(Debug, Default)]
+pub struct WarningDiff<'a> {
+    pub introduced: Vec<&'a Warning>,
+    pub resolved: Vec<&'a Warning>,
+}
+
+impl WarningDiff<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.introduced.is_empty() && self.resolved.is_empty()
+    }
+}
+
+/// Matches `current` against `previous` by fingerprint, counting repeated
+/// fingerprints, so a second copy of a warning is still introduced.
+pub fn diff_warnings<'a>(previous: &'a [Warning], current: &'a [Warning]) -> WarningDiff<'a> {
+    let mut remaining: HashMap<String, usize> = HashMap::new();
+    for warning in previous {
+        *remaining.entry(warning.fingerprint()).or_insert(0) += 1;
+    }
+    let mut diff = WarningDiff::default();
+    for warning in current {
+        match remaining.get_mut(&warning.fingerprint()) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => diff.introduced.push(warning),
+        }
+    }
+    for warning in previous.iter().rev() {
+        if let Some(count) = remaining.get_mut(&warning.fingerprint()).filter(|count| **count > 0) {
+            *count -= 1;
+            diff.resolved.push(warning);
+        }
+    }
+    diff.resolved.reverse();
+    diff
+}
+
+/// `file:line lint: message`
+pub fn format_watched(warning: &Warning) -> String {
+    format!("{}:{} {}: {}", warning.file, warning.line, warning.lint_code, warning.primary_message)
+}
+
+/// `file:line:column: warning: message [lint]`, or `error:` for compile
+/// errors.
+pub fn format_quickfix(warning: &Warning) -> String {
+    let column = warning.span.as_ref().map_or(1, |span| span.column_start);
+    let level = if warning.is_error { "error" } else { "warning" };
+    format!("{}:{}:{}: {}: {} [{}]", warning.file, warning.line, column, level, warning.primary_message, warning.lint_code)
+}
+
+/// Replaces the problems file at `path` with one quickfix line per warning,
+/// through a temporary file renamed into place.
+pub fn write_problems_file(path: &Path, warnings: &[Warning]) -> io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    let mut file = BufWriter::new(File::create(&temp)?);
+    for warning in warnings {
+        writeln!(file, "{}", format_quickfix(warning))?;
+    }
+    file.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    fs::rename(&temp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Priority;
+
+    #[test]
+    fn test_diff_warnings() {
+        let warning = |lint: &str, fingerprint: &str| Warning {
+            primary_message: "message".to_string(),
+            priority: Priority::Low,
+            fingerprint: fingerprint.to_string(),
+            ..Warning::for_test(lint, "src/lib.rs", 1)
+        };
+        let previous = [warning("clippy::len_zero", "a"), warning("clippy::len_zero", "a"), warning("clippy::needless_return", "b")];
+        let current = [warning("clippy::len_zero", "a"), warning("clippy::redundant_clone", "c")];
+        let diff = diff_warnings(&previous, &current);
+        let lints = |warnings: &[&Warning]| warnings.iter().map(|w| w.lint_code.clone()).collect::<Vec<_>>();
+        assert_eq!(lints(&diff.introduced), ["clippy::redundant_clone"]);
+        assert_eq!(lints(&diff.resolved), ["clippy::len_zero", "clippy::needless_return"]);
+        assert!(diff_warnings(&current, &current).is_empty());
+
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("target")).unwrap();
+        std::fs::write(dir.join("lib.rs"), "").unwrap();
+        std::fs::write(dir.join("target/out.rs"), "").unwrap();
+        let before = SourceSnapshot::capture(dir);
+        std::fs::write(dir.join("main.rs"), "").unwrap();
+        let after = SourceSnapshot::capture(dir);
+        let problems = dir.join(DEFAULT_PROBLEMS_FILE);
+        write_problems_file(&problems, &current).unwrap();
+        let written = std::fs::read_to_string(&problems).unwrap();
+        assert_eq!(before.modified.len(), 1);
+        assert_eq!(after.changed_since(&before), 1);
+        assert_eq!(written.lines().nth(1), Some("src/lib.rs:1:1: warning: message [clippy::redundant_clone]"));
+    }
+}
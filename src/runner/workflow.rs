@@ -1,19 +1,75 @@
-use std::process::Command;
-use std::io::{self, Write};
-use std::path::PathBuf;
-use clap::{Parser, ArgAction};
-use super::analysis_runner::AnalysisRunner;
+use std::ffi::OsString;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use termcolor::Color;
+use clap::{ArgAction, ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
+use super::analysis_runner::{AnalysisRunner, HISTORY_TREND_RUNS};
+use super::bench::{DEFAULT_SIZES, WorkloadSize, format_results, run_bench};
+use super::cargo::{target_dir, workspace_root, workspace_target_dir};
+use super::cancel::{cancel_on_interrupt, interrupted};
+use super::check::{CheckOutcome, TOOL_ERROR_EXIT_CODE};
+use super::clippy::{ClippySource, path_str};
+use super::external::ExternalSource;
+use super::fix::{FixSession, FixSummary};
+use super::fmt::RustfmtSource;
+use super::future_incompat::FutureIncompatSource;
+use super::udeps::run_udeps;
+use super::upgrade::{DependencyUpgrade, UpgradeComparison, WorkspaceCopy, write_upgrade_report};
+use super::geiger::run_geiger;
+use super::build_impact::measure_build_impact;
+use super::git;
+use super::hidden::scan_workspace;
+use super::import::{HistoryImport, ImportSummary, import_history};
+use super::pr_comment::{CommentOutcome, post_pr_comment};
+use super::repro::{ReproInfo, toolchain_versions};
+use super::watch::{DEFAULT_PROBLEMS_FILE, POLL_INTERVAL_MS, SourceSnapshot, diff_warnings, format_watched, write_problems_file};
+use super::saved::{SavedWarningsSource, format_trends, write_saved_diff};
+use super::self_analysis::{DEFAULT_SELF_SNAPSHOT, FindingsSnapshot, analyze_self, compare_findings, format_snapshot_diff, self_crate_dir};
+use crate::analysis::baseline::{BASELINE_FORMAT_VERSION, BaselineFile, BaselineStore, is_commit_hash};
+use crate::analysis::false_positives::FalsePositive;
+use crate::analysis::gate::GateVerdict;
+use crate::analysis::gate_simulation::simulate_gates;
+use crate::analysis::generated::GeneratedCodeFilter;
+use crate::analysis::pipeline::{AnalysisOptions, analyze};
+use crate::analysis::plugin::{SourceContext, WarningSource};
+use crate::analysis::scope::PathScope;
+use crate::analysis::suppression::SuppressionFilter;
+use crate::analysis::third_party::partition_third_party;
+use crate::config::{AnalyzerConfig, DEFAULT_CONFIG_FILE};
+use crate::fixes::apply::FixDecisions;
+use crate::fixes::registry::FixExampleRegistry;
+use crate::output::baseline_report::short_commit;
+use crate::output::analysis_bundle::{DEFAULT_BUNDLE_DIR, write_analysis_bundle};
+use crate::output::{bundle::Artifact, csv_export::CsvColumn, diff::DiffLayout, formats::ReportFormat, hyperlink::EditorLinks, json_export::WARNING_SCHEMA};
+use crate::output::color::ColorWriter;
+#[cfg(feature = "tui")]
+use crate::output::{snippet::DEFAULT_CONTEXT_LINES, tui::run_dashboard};
+use crate::output::log::{Logger, Verbosity};
+use crate::output::progress::{NoProgress, ProgressReporter, default_progress_reporter};
+use crate::parser::ParseResult;
+use crate::types::{Completeness, Warning};
 
 #[derive(Parser)]
-#[command(name = "cargo-analyzer")]
+#[command(name = "cargo-analyzer", version)]
 #[command(about = "Analyze Clippy warnings and generate detailed reports")]
 pub struct CliArgs {
+    #[command(subcommand)]
+    command: Option<AnalyzerCommand>,
+
     #[arg(long, default_value = "clippy_output.json")]
     output_file: String,
 
+    /// Analyze the workspace in DIR instead of the current directory
     #[arg(long, value_name = "DIR")]
     working_dir: Option<PathBuf>,
 
+    /// Analyze the package or workspace of this Cargo.toml; reports and the
+    /// config file are then looked up in its workspace root
+    #[arg(long, value_name = "FILE", conflicts_with = "working_dir")]
+    manifest_path: Option<PathBuf>,
+
+    /// Write reports to DIR (default: analysis_reports in the workspace root)
     #[arg(long, value_name = "DIR")]
     reports_dir: Option<PathBuf>,
 
@@ -25,10 +81,450 @@ pub struct CliArgs {
 
     #[arg(long, action=ArgAction::SetTrue)]
     all_targets: bool,
+
+    /// Features to activate, comma-separated; may be repeated
+    #[arg(short = 'F', long, value_name = "FEATURES")]
+    features: Vec<String>,
+
+    /// Do not activate the `default` feature
+    #[arg(long, action=ArgAction::SetTrue)]
+    no_default_features: bool,
+
+    /// Check the release profile
+    #[arg(long, action=ArgAction::SetTrue)]
+    release: bool,
+
+    /// Check for the target triple
+    #[arg(long, value_name = "TRIPLE")]
+    target: Option<String>,
+
+    /// Set a lint to warn, passed on to clippy
+    #[arg(short = 'W', long = "warn", value_name = "LINT")]
+    warn: Vec<String>,
+
+    /// Set a lint to allow, passed on to clippy
+    #[arg(short = 'A', long = "allow", value_name = "LINT")]
+    allow: Vec<String>,
+
+    /// Set a lint to deny, passed on to clippy; denied lints are reported
+    /// as errors
+    #[arg(short = 'D', long = "deny", value_name = "LINT")]
+    deny: Vec<String>,
+
+    /// `-W`, `-A`, and `-D` flags in command line order
+    #[arg(skip)]
+    lint_flags: Vec<String>,
+
+    /// Extra arguments for `cargo clippy`, e.g. `-- --locked -- -W
+    /// clippy::pedantic`; those after a second `--` go to clippy itself, as
+    /// do `-W`, `-A`, and `-D` lint flags before it
+    #[arg(last = true, value_name = "ARGS")]
+    extra_args: Vec<String>,
+
+    /// Number of threads used to parse clippy output. Values above 1 parse the
+    /// saved output after clippy finishes instead of streaming it.
+    #[arg(short, long, value_name = "N", default_value_t = 1)]
+    jobs: usize,
+
+    /// Stop clippy after SECS seconds and report on the output received so far
+    #[arg(long, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Path to a config file (defaults to ./cargo-analyzer.toml if present)
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Ignore warnings by lint name, path glob, `category:<name>`, `macro:external`, or `macro:<name>`
+    #[arg(long = "ignore", value_name = "RULE")]
+    ignore: Vec<String>,
+
+    /// Mark the warning with this fingerprint as a suspected false positive
+    #[arg(long = "false-positive", value_name = "FINGERPRINT")]
+    false_positives: Vec<String>,
+
+    /// Write the suspected false positives as a report to file with clippy
+    #[arg(long, action=ArgAction::SetTrue)]
+    false_positive_report: bool,
+
+    /// Count a warning once per target reporting it, as clippy does with
+    /// `--all-targets`, instead of once
+    #[arg(long, action=ArgAction::SetTrue)]
+    no_dedupe: bool,
+
+    /// List suppressed warnings in an appendix of the detailed report
+    #[arg(long, action=ArgAction::SetTrue)]
+    list_suppressed: bool,
+
+    /// Exclude files matching this glob as generated or vendored code
+    #[arg(long = "generated", value_name = "GLOB")]
+    generated: Vec<String>,
+
+    /// Analyze build script output, `target/`, `*.pb.rs`, and `vendor/`
+    /// instead of excluding them as generated or vendored code
+    #[arg(long, action=ArgAction::SetTrue)]
+    include_generated: bool,
+
+    /// Count warnings in dependencies and files outside the workspace in
+    /// the totals and gates instead of reporting them separately
+    #[arg(long, action=ArgAction::SetTrue)]
+    include_third_party: bool,
+
+    /// Write a merge queue (check run) status payload with the gate verdict
+    #[arg(long, action=ArgAction::SetTrue)]
+    merge_queue_status: bool,
+
+    /// Write a standalone fix playbook per category, with fix templates,
+    /// affected files, and commands
+    #[arg(long, action=ArgAction::SetTrue)]
+    playbooks: bool,
+
+    /// Write per-crate safety findings as cargo-vet style audit notes
+    #[arg(long, action=ArgAction::SetTrue)]
+    audit_notes: bool,
+
+    /// Write warnings as compact protobuf, following the shipped
+    /// `proto/warnings.proto` schema
+    #[arg(long, action=ArgAction::SetTrue)]
+    binary_export: bool,
+
+    /// Include each warning's original JSON diagnostic in the reports
+    #[arg(long, action=ArgAction::SetTrue)]
+    embed_raw: bool,
+
+    /// Also write warnings as JSONL, one per line (see `--schema`)
+    #[arg(long, action=ArgAction::SetTrue)]
+    jsonl: bool,
+
+    /// Write an appendix file with every diagnostic's rendered text as
+    /// clippy printed it, grouped by file
+    #[arg(long, action=ArgAction::SetTrue)]
+    rendered_appendix: bool,
+
+    /// Columns of the CSV export, comma-separated (default: file, line,
+    /// column, end-line, end-column, category, message, priority,
+    /// suggested-fix)
+    #[arg(long, value_name = "COLUMNS", value_enum, value_delimiter = ',')]
+    csv_columns: Vec<CsvColumn>,
+
+    /// Also write the reports in these formats, comma-separated or repeated
+    /// (prometheus: metrics for the node_exporter textfile collector;
+    /// pr-comment: markdown body for a pull request comment; sarif: SARIF
+    /// log for code scanning)
+    #[arg(long = "format", value_name = "FORMAT", value_enum, value_delimiter = ',')]
+    formats: Vec<ReportFormat>,
+
+    /// Write only the artifacts of this bundle from the config's
+    /// `[bundles]` table, plus those requested by other flags
+    #[arg(long, value_name = "NAME")]
+    bundle: Option<String>,
+
+    /// Preview fix examples for the top warnings in the terminal as
+    /// highlighted diffs
+    #[arg(long, value_name = "LAYOUT", num_args = 0..=1, default_missing_value = "unified")]
+    preview_fixes: Option<DiffLayout>,
+
+    /// Show the code of the highest-priority warnings in the terminal
+    #[arg(long, action=ArgAction::SetTrue)]
+    snippets: bool,
+
+    /// Link warning locations in the terminal to SCHEME: file (default),
+    /// vscode, idea, none, or a template such as
+    /// `myeditor://open?path={path}&line={line}&col={col}`
+    #[arg(long, value_name = "SCHEME")]
+    editor_link: Option<String>,
+
+    /// Find unused dependencies with `cargo +nightly udeps`, falling back to
+    /// the `unused_crate_dependencies` lint when it is unavailable
+    #[arg(long, action=ArgAction::SetTrue)]
+    udeps: bool,
+
+    /// Report code `cargo fmt` would reformat as Style warnings
+    #[arg(long, action=ArgAction::SetTrue)]
+    fmt_check: bool,
+
+    /// Report dependencies' code a future Rust release will reject, from
+    /// `cargo report future-incompatibilities`
+    #[arg(long, action=ArgAction::SetTrue)]
+    future_incompat: bool,
+
+    /// Measure unsafe blocks, raw pointers, FFI, and mutable statics per
+    /// crate, using cargo-geiger when it is installed
+    #[arg(long, action=ArgAction::SetTrue)]
+    unsafe_metrics: bool,
+
+    /// Rank performance warnings by the binary size and compile time of
+    /// their code, measured with cargo-bloat and a nightly timed build
+    #[arg(long, action=ArgAction::SetTrue)]
+    performance_impact: bool,
+
+    /// Report `#[ignore]`d tests and items behind cfg features the analyzed
+    /// feature set never enables, which clippy cannot see
+    #[arg(long, action=ArgAction::SetTrue)]
+    hidden_code: bool,
+
+    /// Make the HTML report accessible without color: label every heat
+    /// table cell with its severity in text
+    #[arg(long, action=ArgAction::SetTrue)]
+    a11y: bool,
+
+    /// Write clippy_badge.svg and the shields.io endpoint clippy_badge.json,
+    /// showing the warning count colored by the worst severity
+    #[arg(long, action=ArgAction::SetTrue)]
+    badge: bool,
+
+    /// Compare with the stored analysis of BRANCH's head commit, found in the
+    /// baseline store or history database
+    #[arg(long, value_name = "BRANCH")]
+    auto_baseline: Option<String>,
+
+    /// Only report warnings on lines changed since the merge base with REF;
+    /// other warnings of the touched files are listed as pre-existing. The
+    /// run is not recorded in the history or baseline store
+    #[arg(long, value_name = "REF", visible_aliases = ["since", "base-branch"])]
+    base: Option<String>,
+
+    /// Only consider warnings in files changed since the merge base with
+    /// REF (default: the `--base` ref), in statistics, fix plan, and gates
+    #[arg(long, value_name = "REF", num_args = 0..=1)]
+    changed_only: Option<Option<String>>,
+
+    /// Store each run's warnings by commit in DIR, for later baseline
+    /// comparisons
+    #[arg(long, value_name = "DIR")]
+    baseline_store: Option<PathBuf>,
+
+    /// Write no reports; exit 0 when clean, 1 on warnings, 2 on critical or
+    /// safety warnings, 3 on analyzer errors
+    #[arg(long, action=ArgAction::SetTrue)]
+    check: bool,
+
+    /// Evaluate the `[gate]` thresholds and exit with the verdict as soon as
+    /// clippy finishes, writing nothing: 0 when the gate passes, 1 when it
+    /// fails, 3 on analyzer errors
+    #[arg(long, action=ArgAction::SetTrue, conflicts_with = "check")]
+    gate_only: bool,
+
+    /// Rerun clippy whenever a source file changes and print only the
+    /// warnings introduced or resolved since the previous run; stop with
+    /// Ctrl-C
+    #[arg(long, action=ArgAction::SetTrue, conflicts_with_all = ["check", "gate_only"])]
+    watch: bool,
+
+    /// With --watch, keep every current warning in FILE (default
+    /// `.analyzer-problems`) in quickfix format, for editors to poll
+    #[arg(long, value_name = "FILE", num_args = 0..=1, default_missing_value = DEFAULT_PROBLEMS_FILE, requires = "watch")]
+    problems_file: Option<PathBuf>,
+
+    /// Browse the warnings in a full-screen dashboard instead of writing
+    /// reports (requires the `tui` feature)
+    #[arg(long, action=ArgAction::SetTrue, conflicts_with_all = ["check", "gate_only", "watch"])]
+    tui: bool,
+
+    /// Record runs in and read trends from a SQLite database (requires the
+    /// `sqlite` feature)
+    #[arg(long, value_name = "FILE")]
+    history_db: Option<PathBuf>,
+
+    /// Only print the final summary line
+    #[arg(short, long, action=ArgAction::SetTrue, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Log diagnostics to stderr (-v for info, -vv for debug)
+    #[arg(short, long, action=ArgAction::Count)]
+    verbose: u8,
+
+    /// Append all diagnostic messages to FILE
+    #[arg(long, value_name = "FILE")]
+    log_file: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum AnalyzerCommand {
+    /// Run clippy and write every report (the default)
+    Analyze,
+    /// Re-render the reports from a saved JSON or JSONL warnings export
+    /// without running clippy; the run is not recorded in the history
+    Report {
+        /// Warnings export, e.g. analysis_reports/clippy_warnings_json_<timestamp>.json
+        input: PathBuf,
+    },
+    /// Compare two saved warnings exports and print the differences as
+    /// markdown
+    Diff {
+        /// The earlier export
+        old: PathBuf,
+        /// The later export
+        new: PathBuf,
+    },
+    /// Print the runs recorded in the history database or
+    /// clippy_historical.json
+    Trends {
+        /// Number of most recent runs shown
+        #[arg(long, value_name = "N", default_value_t = HISTORY_TREND_RUNS)]
+        runs: usize,
+    },
+    /// Replay the recorded runs against proposed gate thresholds and alert
+    /// rules and report how often each would have failed
+    SimulateGates {
+        /// Config file with the proposed `[gate]` table and `alert-if`
+        /// rules; defaults to the current config
+        config: Option<PathBuf>,
+        /// Number of most recent runs replayed
+        #[arg(long, value_name = "N", default_value_t = HISTORY_TREND_RUNS)]
+        runs: usize,
+    },
+    /// Backfill the history from a directory of raw clippy JSON dumps and
+    /// warnings exports, recording each as a run dated by its file name
+    ImportHistory {
+        /// Directory of earlier output, e.g. an old analysis_reports
+        dir: PathBuf,
+    },
+    /// Exchange stored baseline analyses between the baseline stores of CI
+    /// and developer machines
+    Baseline {
+        #[command(subcommand)]
+        action: BaselineAction,
+    },
+    /// Analyze and report only the warnings in one file or directory
+    File {
+        /// File or directory, relative to the workspace root
+        path: PathBuf,
+    },
+    /// Apply clippy's suggested fixes, recording each decision
+    Fix {
+        /// Review each fix: apply, skip, or suppress it
+        #[arg(long, action=ArgAction::SetTrue)]
+        interactive: bool,
+    },
+    /// Time parsing, analysis, and report generation on synthetic
+    /// workloads; nothing is collected or sent
+    Bench {
+        /// Warnings per workload, e.g. 10k, 100k, or 1m
+        #[arg(value_name = "SIZE", default_values = DEFAULT_SIZES)]
+        sizes: Vec<WorkloadSize>,
+    },
+    /// Run clippy and write the versioned analysis bundle read by editor
+    /// extensions and dashboards: warnings, statistics, and a fixes index
+    #[command(visible_alias = "export-bundle")]
+    Bundle {
+        /// Directory the bundle is written to
+        #[arg(default_value = DEFAULT_BUNDLE_DIR)]
+        dir: PathBuf,
+    },
+    /// Analyze a copy of the workspace before and after requiring a new
+    /// version of a dependency, and print the compile errors and warnings
+    /// the upgrade introduces as markdown; exits 1 when it breaks the build
+    UpgradeCheck {
+        /// Package upgraded, as named on the registry
+        #[arg(long)]
+        package: String,
+        /// Version requirement tried, e.g. 2.0
+        #[arg(long)]
+        version: String,
+    },
+    /// Run clippy on the analyzer's own crate with a pinned config and
+    /// compare how its warnings are categorized with a committed snapshot;
+    /// exits 1 when they differ
+    AnalyzeSelf {
+        /// Snapshot of the expected findings
+        #[arg(long, value_name = "FILE", default_value = DEFAULT_SELF_SNAPSHOT)]
+        snapshot: PathBuf,
+        /// Source of the analyzer crate; defaults to the one this binary was
+        /// built from, if it is still there
+        #[arg(long, value_name = "DIR")]
+        crate_dir: Option<PathBuf>,
+        /// Write the current findings to the snapshot instead of comparing
+        #[arg(long, action=ArgAction::SetTrue)]
+        bless: bool,
+    },
+    /// Print the JSON schema of the JSON and JSONL warning exports
+    #[command(long_flag = "schema")]
+    Schema,
+    /// Post a markdown file as the analyzer's pull request comment with the
+    /// GitHub CLI, updating its previous comment instead of adding another
+    /// and collapsing older ones
+    Comment {
+        /// Markdown body of the comment
+        body: PathBuf,
+        /// Pull request number
+        #[arg(long, value_name = "NUMBER")]
+        pr: u64,
+        /// Repository as OWNER/REPO; defaults to the git remote's
+        #[arg(long, value_name = "OWNER/REPO")]
+        repo: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum BaselineAction {
+    /// Write the stored analysis of a commit to a portable baseline file
+    Export {
+        /// Commit, branch, or tag whose analysis is exported
+        #[arg(default_value = "HEAD")]
+        rev: String,
+        /// Baseline file written; defaults to baseline-<commit>.json
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Add the analysis in a baseline file to the baseline store
+    Import {
+        /// Baseline file, from `baseline export` or a CI baseline store
+        file: PathBuf,
+        /// Commit the analysis is stored for, when the file does not record it
+        #[arg(long)]
+        commit: Option<String>,
+    },
 }
 
 pub struct ClippyWorkflow {
     cargo_args: Vec<String>,
+    /// Arguments after `--` of the clippy invocation
+    clippy_args: Vec<String>,
+    working_dir: Option<PathBuf>,
+    manifest_path: Option<PathBuf>,
+    reports_dir: Option<PathBuf>,
+    config_path: Option<PathBuf>,
+    ignore: Vec<String>,
+    false_positives: Vec<String>,
+    false_positive_report: bool,
+    no_dedupe: bool,
+    list_suppressed: bool,
+    generated: Vec<String>,
+    include_generated: bool,
+    include_third_party: bool,
+    merge_queue_status: bool,
+    playbooks: bool,
+    audit_notes: bool,
+    binary_export: bool,
+    embed_raw: bool,
+    jsonl: bool,
+    rendered_appendix: bool,
+    csv_columns: Vec<CsvColumn>,
+    formats: Vec<ReportFormat>,
+    bundle: Option<String>,
+    udeps: bool,
+    fmt_check: bool,
+    future_incompat: bool,
+    auto_baseline: Option<String>,
+    base: Option<String>,
+    changed_only: Option<Option<String>>,
+    baseline_store: Option<PathBuf>,
+    unsafe_metrics: bool,
+    performance_impact: bool,
+    hidden_code: bool,
+    a11y: bool,
+    badge: bool,
+    preview_fixes: Option<DiffLayout>,
+    editor_link: Option<String>,
+    snippets: bool,
+    problems_file: Option<PathBuf>,
+    jobs: usize,
+    timeout: Option<Duration>,
+    history_db: Option<PathBuf>,
+    verbosity: Verbosity,
+    log_file: Option<PathBuf>,
+    scope: Option<PathBuf>,
+    sources: Vec<Box<dyn WarningSource>>,
 }
 
 impl ClippyWorkflow {
@@ -37,89 +533,1231 @@ impl ClippyWorkflow {
         if args.workspace { cargo_args.push("--workspace".to_string()); }
         if args.all_features { cargo_args.push("--all-features".to_string()); }
         if args.all_targets { cargo_args.push("--all-targets".to_string()); }
+        for features in args.features {
+            cargo_args.extend(["--features".to_string(), features]);
+        }
+        if args.no_default_features { cargo_args.push("--no-default-features".to_string()); }
+        if args.release { cargo_args.push("--release".to_string()); }
+        if let Some(target) = args.target {
+            cargo_args.extend(["--target".to_string(), target]);
+        }
+        let mut clippy_args = if args.lint_flags.is_empty() {
+            lint_flags(&[("-W", &args.warn), ("-A", &args.allow), ("-D", &args.deny)])
+        } else {
+            args.lint_flags
+        };
+        let (extra_cargo_args, extra_clippy_args) = split_extra_args(args.extra_args);
+        cargo_args.extend(extra_cargo_args);
+        clippy_args.extend(extra_clippy_args);
+        // Cargo runs in the workspace root, so a relative path would no
+        // longer resolve
+        let manifest_path = args.manifest_path.map(|path| std::path::absolute(&path).unwrap_or(path));
+        if let Some(path) = &manifest_path {
+            cargo_args.push("--manifest-path".to_string());
+            cargo_args.push(path.to_string_lossy().into_owned());
+        }
 
-        Self { cargo_args }
+        Self {
+            cargo_args,
+            clippy_args,
+            working_dir: args.working_dir,
+            manifest_path,
+            reports_dir: args.reports_dir,
+            config_path: args.config,
+            ignore: args.ignore,
+            false_positives: args.false_positives,
+            false_positive_report: args.false_positive_report,
+            no_dedupe: args.no_dedupe,
+            list_suppressed: args.list_suppressed,
+            generated: args.generated,
+            include_generated: args.include_generated,
+            include_third_party: args.include_third_party,
+            merge_queue_status: args.merge_queue_status,
+            playbooks: args.playbooks,
+            audit_notes: args.audit_notes,
+            binary_export: args.binary_export,
+            embed_raw: args.embed_raw,
+            jsonl: args.jsonl,
+            rendered_appendix: args.rendered_appendix,
+            csv_columns: args.csv_columns,
+            formats: args.formats,
+            bundle: args.bundle,
+            udeps: args.udeps,
+            fmt_check: args.fmt_check,
+            future_incompat: args.future_incompat,
+            auto_baseline: args.auto_baseline,
+            base: args.base,
+            changed_only: args.changed_only,
+            baseline_store: args.baseline_store,
+            unsafe_metrics: args.unsafe_metrics,
+            performance_impact: args.performance_impact,
+            hidden_code: args.hidden_code,
+            a11y: args.a11y,
+            badge: args.badge,
+            preview_fixes: args.preview_fixes,
+            problems_file: args.problems_file,
+            editor_link: args.editor_link,
+            snippets: args.snippets,
+            jobs: args.jobs,
+            timeout: args.timeout.map(Duration::from_secs),
+            history_db: args.history_db,
+            verbosity: Verbosity::from_flags(args.quiet, args.verbose),
+            log_file: args.log_file,
+            scope: match args.command {
+                Some(AnalyzerCommand::File { path }) => Some(path),
+                _ => None,
+            },
+            sources: Vec::new(),
+        }
     }
 
-    pub fn run(&self) -> io::Result<()> {
-        // Create debug log file
-        let debug_log = std::fs::File::create("clippy_analyzer_debug.log")?;
-        let mut log = std::io::BufWriter::new(debug_log);
-        writeln!(log, "=== CLIPPY ANALYZER DEBUG LOG ===\n")?;
-
-        // Get current directory
-        let current_dir = std::env::current_dir()?;
-        writeln!(log, "Current directory: {:?}", current_dir)?;
-        log.flush()?;
-
-        // Create analysis_reports directory
-        let reports_dir = current_dir.join("analysis_reports");
-        writeln!(log, "\nCreating reports directory: {:?}", reports_dir)?;
+    /// Registers a source whose warnings are analyzed alongside clippy's.
+    pub fn add_source(&mut self, source: Box<dyn WarningSource>) {
+        self.sources.push(source);
+    }
+
+    /// Root of the analyzed workspace: the workspace of `--manifest-path`,
+    /// `--working-dir`, or the current directory.
+    fn root(&self) -> io::Result<PathBuf> {
+        if let Some(manifest_path) = &self.manifest_path {
+            return workspace_root(manifest_path);
+        }
+        let root = match &self.working_dir {
+            Some(dir) => std::path::absolute(dir)?,
+            None => std::env::current_dir()?,
+        };
+        if !root.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not a directory", root.display()),
+            ));
+        }
+        Ok(root)
+    }
+
+    /// `--reports-dir`, or `analysis_reports` in `root`, created if missing.
+    fn reports_dir(&self, root: &Path) -> io::Result<PathBuf> {
+        let reports_dir = self.reports_dir.clone().unwrap_or_else(|| root.join("analysis_reports"));
         std::fs::create_dir_all(&reports_dir)?;
-        writeln!(log, "Reports directory created")?;
-        log.flush()?;
+        Ok(reports_dir)
+    }
+
+    fn load_config(&self, root: &Path) -> io::Result<AnalyzerConfig> {
+        let default_path = root.join(DEFAULT_CONFIG_FILE);
+        let path = self.config_path.clone().or_else(|| default_path.exists().then_some(default_path));
+        let mut config = AnalyzerConfig::load_or_default(path.as_deref())?;
+        config.ignore.extend(self.ignore.iter().cloned());
+        config.false_positive.extend(
+            self.false_positives
+                .iter()
+                .map(|fingerprint| FalsePositive { fingerprint: fingerprint.clone(), reason: None }),
+        );
+        config.false_positive_report |= self.false_positive_report;
+        config.keep_duplicates |= self.no_dedupe;
+        config.list_suppressed |= self.list_suppressed;
+        config.generated.extend(self.generated.iter().cloned());
+        config.include_generated |= self.include_generated;
+        config.include_third_party |= self.include_third_party;
+        config.merge_queue_status |= self.merge_queue_status;
+        config.playbooks |= self.playbooks;
+        config.audit_notes |= self.audit_notes;
+        config.binary_export |= self.binary_export;
+        config.embed_raw |= self.embed_raw;
+        config.jsonl |= self.jsonl;
+        config.rendered_appendix |= self.rendered_appendix;
+        config.udeps |= self.udeps;
+        config.fmt_check |= self.fmt_check;
+        config.future_incompat |= self.future_incompat;
+        config.unsafe_metrics |= self.unsafe_metrics;
+        config.performance_impact |= self.performance_impact;
+        config.hidden_code |= self.hidden_code;
+        config.html.a11y |= self.a11y;
+        config.badge |= self.badge;
+        config.snippets |= self.snippets;
+        if !self.csv_columns.is_empty() {
+            config.csv_columns = self.csv_columns.clone();
+        }
+        for format in &self.formats {
+            if !config.formats.contains(format) {
+                config.formats.push(*format);
+            }
+        }
+        if let Some(bundle) = &self.bundle {
+            config.select_bundle(bundle)?;
+        }
+        if let Some(artifacts) = &mut config.artifacts {
+            let requested = [
+                (self.jsonl, Artifact::Jsonl),
+                (self.rendered_appendix, Artifact::RenderedDiagnostics),
+                (self.binary_export, Artifact::Protobuf),
+                (self.merge_queue_status, Artifact::MergeStatus),
+                (self.false_positive_report, Artifact::FalsePositives),
+                (self.audit_notes, Artifact::AuditNotes),
+                (self.playbooks, Artifact::Playbooks),
+                (self.badge, Artifact::Badge),
+                (self.formats.contains(&ReportFormat::Prometheus), Artifact::Prometheus),
+                (self.formats.contains(&ReportFormat::PrComment), Artifact::PrComment),
+                (self.formats.contains(&ReportFormat::Sarif), Artifact::Sarif),
+            ];
+            for (_, artifact) in requested.into_iter().filter(|(flag, _)| *flag) {
+                if !artifacts.contains(&artifact) {
+                    artifacts.push(artifact);
+                }
+            }
+        }
+        config.enable_selected_artifacts();
+        if self.problems_file.is_some() {
+            config.problems_file = self.problems_file.clone();
+        }
+        if self.preview_fixes.is_some() {
+            config.preview_fixes = self.preview_fixes;
+        }
+        if self.editor_link.is_some() {
+            config.editor_link = self.editor_link.clone();
+        }
+        if let Some(scheme) = &config.editor_link {
+            EditorLinks::new(scheme, root)?;
+        }
+        if self.auto_baseline.is_some() {
+            config.auto_baseline = self.auto_baseline.clone();
+        }
+        if self.base.is_some() {
+            config.base = self.base.clone();
+        }
+        if let Some(base) = &self.changed_only {
+            config.changed_only = true;
+            if base.is_some() {
+                config.base = base.clone();
+            }
+        }
+        if self.baseline_store.is_some() {
+            config.baseline_store = self.baseline_store.clone();
+        }
+        if self.history_db.is_some() {
+            config.history_db = self.history_db.clone();
+        }
+        config.fix_decisions = Some(root.join(config.fix_decisions_path()));
+        for template in [&mut config.templates.markdown, &mut config.templates.html].into_iter().flatten() {
+            if !cfg!(feature = "templates") {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("Report template {} requires cargo-analyzer built with the `templates` feature", template.display()),
+                ));
+            }
+            *template = root.join(&*template);
+        }
+        let decisions = FixDecisions::load(&config.fix_decisions_path())?;
+        config.ignore.extend(decisions.suppressed().map(|fingerprint| format!("fingerprint:{}", fingerprint)));
+        Ok(config)
+    }
+
+    fn path_scope(&self, root: &Path) -> io::Result<Option<PathScope>> {
+        let Some(path) = &self.scope else { return Ok(None) };
+        if !root.join(path).exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} does not exist", path.display()),
+            ));
+        }
+        Ok(Some(PathScope::new(path, root)))
+    }
+
+    fn logger(&self) -> io::Result<Logger> {
+        let log = Logger::new(self.verbosity);
+        match &self.log_file {
+            Some(path) => log.with_log_file(path),
+            None => Ok(log),
+        }
+    }
+
+    /// Prints the one-line result of a run in quiet mode, where it is the
+    /// only output.
+    fn print_summary(&self, summary: &str) {
+        if self.verbosity.is_quiet() && !summary.is_empty() {
+            println!("cargo-analyzer: {}", summary);
+        }
+    }
+
+    fn progress_reporter(&self) -> Box<dyn ProgressReporter> {
+        if self.verbosity.is_quiet() {
+            Box::new(NoProgress)
+        } else {
+            default_progress_reporter()
+        }
+    }
+
+    pub fn run(&self) -> io::Result<()> {
+        let mut log = self.logger()?;
+
+        let root = self.root()?;
+        log.debug(&format!("Workspace root: {:?}", root))?;
+        let config = self.load_config(&root)?;
+        let scope = self.path_scope(&root)?;
+        log.debug(&format!("Ignore rules: {:?}", config.ignore))?;
+
+        let reports_dir = self.reports_dir(&root)?;
+        log.debug(&format!("Reports directory: {:?}", reports_dir))?;
 
         // Generate timestamp
         let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
-        writeln!(log, "\nTimestamp: {}", timestamp)?;
-        log.flush()?;
 
         // Create clippy output path
         let output_path = reports_dir.join(format!("clippy_output_{}.json", timestamp));
-        writeln!(log, "Clippy output path: {:?}", output_path)?;
-        log.flush()?;
+        log.debug(&format!("Clippy output path: {:?}", output_path))?;
 
         // Run clippy
-        writeln!(log, "\nRunning cargo clippy")?;
-        writeln!(log, "Command: cargo clippy {} --message-format=json", self.cargo_args.join(" "))?;
-        log.flush()?;
-
-        let status = Command::new("cargo")
-            .args(["clippy"])
-            .args(&self.cargo_args)
-            .args(["--message-format=json"])
-            .stdout(std::fs::File::create(&output_path)?)
-            .status()?;
-
-        if !status.success() {
-            writeln!(log, "Clippy command failed!")?;
-            log.flush()?;
-            return Err(io::Error::new(io::ErrorKind::Other, "Clippy command failed"));
-        }
-
-        writeln!(log, "Clippy completed successfully")?;
-        writeln!(log, "Output file size: {} bytes", std::fs::metadata(&output_path)?.len())?;
-        log.flush()?;
-
-        // Run analyzer
-        writeln!(log, "\nStarting analysis")?;
-        writeln!(log, "Reports directory: {:?}", reports_dir)?;
-        writeln!(log, "Reading from: {:?}", output_path)?;
-        log.flush()?;
-
-        let mut analyzer = AnalysisRunner::new_with_reports_dir(Some(reports_dir.clone()))?;
-        analyzer.set_timestamp(&timestamp);
-        analyzer.run(output_path.to_str().unwrap())?;
-
-        // List files
-        writeln!(log, "\nFinal contents of reports directory:")?;
-        for entry in std::fs::read_dir(&reports_dir)? {
-            let entry = entry?;
-            writeln!(log, "  - {:?} ({} bytes)", 
-                entry.file_name(),
-                entry.metadata()?.len()
-            )?;
-        }
-        log.flush()?;
-
-        writeln!(log, "\nWorkflow completed")?;
-        log.flush()?;
+        log.info(&format!("Running: cargo clippy {} --message-format=json", self.cargo_args.join(" ")))?;
+
+        let output_str = path_str(&output_path)?;
+
+        let mut progress = self.progress_reporter();
+        let mut ctx = SourceContext::new(&root, &self.cargo_args, progress.as_mut());
+        ctx.keep_raw = config.embed_raw;
+        let (parsed, completeness) =
+            self.collect_warnings(ctx, Some(&output_path), &config, &mut log)?;
+
+        log.info(&format!(
+            "Clippy output saved to {:?} ({} bytes)",
+            output_path,
+            std::fs::metadata(&output_path)?.len()
+        ))?;
+
+        let unused_deps = if config.udeps {
+            log.info("Running: cargo +nightly udeps --output json")?;
+            let unused = run_udeps(&root, &self.cargo_args);
+            if unused.is_none() {
+                log.info("cargo-udeps is unavailable; reporting the unused_crate_dependencies lint instead")?;
+            }
+            unused
+        } else {
+            None
+        };
+
+        let geiger = if config.unsafe_metrics {
+            log.info("Running: cargo geiger --output-format Json")?;
+            let report = run_geiger(&root, &self.cargo_args);
+            if report.is_none() {
+                log.info("cargo-geiger is unavailable; scanning sources for unsafe code instead")?;
+            }
+            report
+        } else {
+            None
+        };
+
+        let build_impact = if config.performance_impact {
+            log.info("Measuring binary size with cargo-bloat and compile time with cargo +nightly build --timings=json")?;
+            let impact = measure_build_impact(&root, &self.cargo_args);
+            match &impact {
+                None => log.info("cargo-bloat and the nightly toolchain are unavailable; skipping performance impact")?,
+                Some(impact) if impact.compile_seconds.is_empty() => {
+                    log.info("The nightly build reported no compile times; the performance impact has binary sizes only")?
+                }
+                Some(_) => {}
+            }
+            impact
+        } else {
+            None
+        };
+
+        let hidden_code = if config.hidden_code {
+            match scan_workspace(&root, &self.cargo_args) {
+                Ok(report) => {
+                    log.info(&format!(
+                        "Found {} ignored tests and {} inactive cfg items in {} files",
+                        report.ignored_tests(),
+                        report.inactive_items(),
+                        report.files_scanned
+                    ))?;
+                    Some(report)
+                }
+                Err(e) => {
+                    log.info(&format!("Skipping the hidden code scan: {}", e))?;
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Runs limited to a path or to the changes since a base ref are
+        // partial
+        let record_run = scope.is_none() && config.base.is_none();
+        let mut builder = AnalysisRunner::builder()
+            .reports_dir(reports_dir)
+            .timestamp(&timestamp)
+            .working_dir(root.clone())
+            .record_run(record_run)
+            .config(config)
+            .jobs(self.jobs)
+            .progress_reporter(progress)
+            .logger(log);
+        if let Some(scope) = scope {
+            builder = builder.scope(scope);
+        }
+        let mut analyzer = builder.build()?;
+        analyzer.set_completeness(completeness.clone());
+        analyzer.set_repro_info(ReproInfo::capture(&self.clippy_source().command(&root, &self.cargo_args))?);
+        if let Some(unused) = unused_deps {
+            analyzer.set_unused_dependencies(unused);
+        }
+        if let Some(report) = hidden_code {
+            analyzer.set_hidden_code(report);
+        }
+        if let Some(report) = geiger {
+            analyzer.set_geiger_report(report);
+        }
+        if let Some(impact) = build_impact {
+            analyzer.set_build_impact(impact);
+        }
+        let report = analyzer.analyze(output_str, parsed)?;
+        self.print_summary(&report.summary);
+
+        match completeness {
+            Completeness::Complete => Ok(()),
+            partial => Err(io::Error::new(io::ErrorKind::Interrupted, partial.to_string())),
+        }
+    }
+
+    /// Writes the reports for the warnings saved in `input`, as `run` would
+    /// have for the run that saved them.
+    pub fn report(&self, input: &Path) -> io::Result<()> {
+        let mut log = self.logger()?;
+        let root = self.root()?;
+        let config = self.load_config(&root)?;
+        let scope = self.path_scope(&root)?;
+        let reports_dir = self.reports_dir(&root)?;
+
+        log.info(&format!("Reading warnings from {}", input.display()))?;
+        let mut progress = self.progress_reporter();
+        let mut ctx = SourceContext::new(&root, &self.cargo_args, progress.as_mut());
+        let warnings = SavedWarningsSource::new(input).collect(&mut ctx)?;
+        let (parsed, _) = ctx.finish(warnings);
+
+        let mut builder = AnalysisRunner::builder()
+            .reports_dir(reports_dir)
+            .working_dir(root)
+            .config(config)
+            .progress_reporter(progress)
+            .logger(log)
+            .record_run(false);
+        if let Some(scope) = scope {
+            builder = builder.scope(scope);
+        }
+        let report = builder.build()?.analyze(path_str(input)?, parsed)?;
+        self.print_summary(&report.summary);
+        Ok(())
+    }
+
+    /// Prints the last `runs` recorded runs.
+    pub fn trends(&self, runs: usize) -> io::Result<()> {
+        let root = self.root()?;
+        let analyzer = AnalysisRunner::builder().config(self.load_config(&root)?).working_dir(root).build()?;
+        let trends = analyzer.recorded_trends(runs)?;
+        if trends.is_empty() {
+            println!(
+                "No recorded runs; set history-db or keep clippy_historical.json to track trends, \
+                 or backfill them with `cargo analyzer import-history DIR`."
+            );
+        } else {
+            print!("{}", format_trends(&trends));
+        }
+        Ok(())
+    }
+
+    /// Prints how often the gate and alert rules of `proposed`, or of the
+    /// current config, would have failed over the last `runs` recorded runs.
+    pub fn simulate_gates(&self, proposed: Option<&Path>, runs: usize) -> io::Result<()> {
+        let root = self.root()?;
+        let config = self.load_config(&root)?;
+        let rules = match proposed {
+            Some(path) => AnalyzerConfig::load(path)?,
+            None => config.clone(),
+        };
+        if rules.gate.is_empty() && rules.alert_if.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "simulate-gates needs thresholds in the [gate] config table or alert-if rules",
+            ));
+        }
+
+        let analyzer = AnalysisRunner::builder().config(config).working_dir(root).build()?;
+        let trends = analyzer.recorded_trends(runs)?;
+        if trends.is_empty() {
+            println!(
+                "No recorded runs to replay; set history-db or keep clippy_historical.json to track trends, \
+                 or backfill them with `cargo analyzer import-history DIR`."
+            );
+        } else {
+            print!("{}", simulate_gates(&rules.gate, &rules.alert_if, &trends));
+        }
+        Ok(())
+    }
+
+    /// Analyzes the analyzer's own crate at `crate_dir`, or the one it was
+    /// built from, and prints how its findings differ from the snapshot at
+    /// `snapshot`; with `bless`, replaces the snapshot instead. Returns
+    /// whether the findings match.
+    pub fn analyze_self(&self, snapshot: &Path, crate_dir: Option<&Path>, bless: bool) -> io::Result<bool> {
+        let expected = if bless { None } else { Some(FindingsSnapshot::load(snapshot)?) };
+        let crate_dir = crate_dir.map_or_else(self_crate_dir, |dir| Ok(dir.to_path_buf()))?;
+        let mut progress = self.progress_reporter();
+        let current = analyze_self(&crate_dir, progress.as_mut())?;
+
+        let Some(expected) = expected else {
+            current.save(snapshot)?;
+            println!("Wrote {} findings to {}", current.findings.len(), snapshot.display());
+            return Ok(true);
+        };
+        let diff = compare_findings(&expected, &current);
+        if diff.is_empty() {
+            println!("Self-analysis matches {}: {} findings", snapshot.display(), current.findings.len());
+        } else {
+            print!("{}", format_snapshot_diff(&diff, &expected, &current));
+        }
+        Ok(diff.is_empty())
+    }
+
+    /// Records the earlier output in `dir` as runs in the history.
+    pub fn import_history(&self, dir: &Path) -> io::Result<ImportSummary> {
+        let root = self.root()?;
+        let config = self.load_config(&root)?;
+        let imports = HistoryImport::scan(dir)?;
+        if imports.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No clippy JSON output or warnings exports in {}", dir.display()),
+            ));
+        }
+        import_history(&imports, &root, &config)
+    }
+
+    /// The configured baseline store.
+    fn baseline_store(&self, root: &Path) -> io::Result<BaselineStore> {
+        let config = self.load_config(root)?;
+        let dir = config.baseline_store.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "No baseline store; set baseline-store or pass --baseline-store")
+        })?;
+        Ok(BaselineStore::new(&dir))
+    }
+
+    /// Writes the stored analysis of `rev` to a portable baseline file.
+    pub fn export_baseline(&self, rev: &str, output: Option<&Path>) -> io::Result<()> {
+        let root = self.root()?;
+        let store = self.baseline_store(&root)?;
+        let commit = git::branch_head(&root, rev)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("Cannot resolve {}", rev)))?;
+        let mut baseline = store.load(&commit)?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No stored analysis of {} ({}); analyze it with the baseline store set", rev, short_commit(&commit)),
+            )
+        })?;
+        if baseline.meta.format_version == 0 {
+            // Stored before baselines described themselves, by this workspace
+            baseline.meta.format_version = BASELINE_FORMAT_VERSION;
+            baseline.meta.root = Some(root.to_string_lossy().into_owned());
+        }
+        let path = output
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from(format!("baseline-{}.json", short_commit(&commit))));
+        baseline.write(&path)?;
+        println!(
+            "Exported the analysis of {} ({} warnings) to {}",
+            short_commit(&commit),
+            baseline.warnings.len(),
+            path.display()
+        );
         Ok(())
     }
+
+    /// Adds the analysis in the baseline `file` to the baseline store, as
+    /// the analysis of `commit` if given.
+    pub fn import_baseline(&self, file: &Path, commit: Option<&str>) -> io::Result<()> {
+        let root = self.root()?;
+        let store = self.baseline_store(&root)?;
+        let mut baseline = BaselineFile::read(file, None)?;
+        if let Some(commit) = commit {
+            baseline.meta.commit = commit.to_string();
+        }
+        if baseline.meta.commit.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} does not record its commit; pass --commit", file.display()),
+            ));
+        }
+        match git::local_commit(&root, &baseline.meta.commit) {
+            Some(commit) => baseline.meta.commit = commit,
+            None if !is_commit_hash(&baseline.meta.commit) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("'{}' is neither a local revision nor a commit hash", baseline.meta.commit),
+                ));
+            }
+            None => println!(
+                "⚠️  {} is not in the local repository; the baseline applies once it is fetched",
+                short_commit(&baseline.meta.commit)
+            ),
+        }
+        let remapped = baseline.remap_paths();
+        baseline.meta.root = Some(root.to_string_lossy().into_owned());
+        let (rustc, clippy) = toolchain_versions();
+        for mismatch in baseline.toolchain_mismatches(rustc.as_deref(), clippy.as_deref()) {
+            println!("⚠️  {}; lints may differ", mismatch);
+        }
+        let path = store.save(&baseline)?;
+        println!(
+            "Imported the analysis of {} ({} warnings, {} paths remapped) into {}",
+            short_commit(&baseline.meta.commit),
+            baseline.warnings.len(),
+            remapped,
+            path.display()
+        );
+        Ok(())
+    }
+
+    /// Runs clippy without writing any files and classifies the result.
+    ///
+    /// An incomplete run is an error, since its outcome cannot be trusted.
+    /// Code that does not compile is not: its errors are critical findings.
+    pub fn check(&self) -> io::Result<CheckOutcome> {
+        let mut log = self.logger()?;
+        let root = self.root()?;
+        let config = self.load_config(&root)?;
+        let scope = self.path_scope(&root)?;
+
+        let mut progress = self.progress_reporter();
+        let ctx = SourceContext::new(&root, &self.cargo_args, progress.as_mut());
+        let (parsed, completeness) = self.collect_warnings(ctx, None, &config, &mut log)?;
+        if !completeness.is_complete() {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, completeness.to_string()));
+        }
+
+        let mut builder = AnalysisRunner::builder()
+            .working_dir(root)
+            .config(config)
+            .progress_reporter(progress)
+            .logger(log);
+        if let Some(scope) = scope {
+            builder = builder.scope(scope);
+        }
+        let mut analyzer = builder.build()?;
+        let outcome = analyzer.check(parsed)?;
+        self.print_summary(analyzer.summary().unwrap_or_default());
+        Ok(outcome)
+    }
+
+    /// Runs clippy, then evaluates the gate without analyzing further.
+    pub fn gate(&self) -> io::Result<GateVerdict> {
+        let mut log = self.logger()?;
+        let root = self.root()?;
+        let config = self.load_config(&root)?;
+        if config.gate.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--gate-only needs thresholds in the [gate] config table",
+            ));
+        }
+        let scope = self.path_scope(&root)?;
+
+        let mut progress = self.progress_reporter();
+        let ctx = SourceContext::new(&root, &self.cargo_args, progress.as_mut());
+        let (parsed, completeness) = self.collect_warnings(ctx, None, &config, &mut log)?;
+
+        let mut builder = AnalysisRunner::builder()
+            .working_dir(root)
+            .config(config)
+            .progress_reporter(progress)
+            .logger(log);
+        if let Some(scope) = scope {
+            builder = builder.scope(scope);
+        }
+        let mut analyzer = builder.build()?;
+        analyzer.set_completeness(completeness);
+        let verdict = analyzer.gate(parsed)?;
+        self.print_summary(analyzer.summary().unwrap_or_default());
+        Ok(verdict)
+    }
+
+    /// Runs clippy and writes the analysis bundle into `dir`.
+    pub fn export_bundle(&self, dir: &Path) -> io::Result<()> {
+        let mut log = self.logger()?;
+        let root = self.root()?;
+        let config = self.load_config(&root)?;
+        let options = self.analysis_options(&root, &config)?;
+        let examples = FixExampleRegistry::from_config(&config.fix_templates)?;
+
+        let mut progress = self.progress_reporter();
+        let ctx = SourceContext::new(&root, &self.cargo_args, progress.as_mut());
+        let (parsed, completeness) = self.collect_warnings(ctx, None, &config, &mut log)?;
+        let analysis = analyze(parsed, &options);
+        let manifest = write_analysis_bundle(dir, &analysis.warnings, &analysis.stats, &completeness, &examples)?;
+        println!(
+            "Wrote {} warnings and {} fixes to {} (bundle version {})",
+            manifest.total_warnings,
+            manifest.total_fixes,
+            dir.display(),
+            manifest.version
+        );
+        if !completeness.is_complete() {
+            log.info(&format!("The bundle is incomplete: {}", completeness))?;
+        }
+        Ok(())
+    }
+
+    /// Analyzes a copy of the workspace before and after `upgrade` and
+    /// prints the comparison, returning the compile errors it introduces.
+    pub fn upgrade_check(&self, upgrade: &DependencyUpgrade) -> io::Result<usize> {
+        let mut log = self.logger()?;
+        let root = self.root()?;
+        let config = self.load_config(&root)?;
+        let copy = WorkspaceCopy::create(&root)?;
+        log.info(&format!("Copied the workspace to {}", copy.root.display()))?;
+        let mut options = self.analysis_options(&root, &config)?;
+        options.source_root = Some(copy.root.clone());
+
+        // `--manifest-path` points into the workspace; use the copy's
+        let mut cargo_args = Vec::with_capacity(self.cargo_args.len());
+        let mut args = self.cargo_args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--manifest-path" {
+                let path = PathBuf::from(args.next().map(String::as_str).unwrap_or_default());
+                let relative = path.strip_prefix(&root).unwrap_or(&path);
+                cargo_args.extend([arg.clone(), copy.root.join(relative).to_string_lossy().into_owned()]);
+            } else {
+                cargo_args.push(arg.clone());
+            }
+        }
+        // Builds into the workspace's target directory, so the copy reuses
+        // the dependencies it already compiled
+        if target_dir().is_none() && !self.cargo_args.iter().any(|arg| arg.starts_with("--target-dir")) {
+            cargo_args.extend(["--target-dir".to_string(), root.join("target").to_string_lossy().into_owned()]);
+        }
+
+        let analyze_copy = |log: &mut Logger| -> io::Result<Vec<Warning>> {
+            let mut progress = self.progress_reporter();
+            let ctx = SourceContext::new(&copy.root, &cargo_args, progress.as_mut());
+            let (parsed, completeness) = self.collect_warnings(ctx, None, &config, log)?;
+            // Clippy fails when the code does not compile; the errors are
+            // what the comparison is after
+            if !completeness.is_complete() && !parsed.0.iter().any(|w| w.is_error) {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, completeness.to_string()));
+            }
+            Ok(analyze(parsed, &options).warnings)
+        };
+
+        log.info(&format!("Analyzing with the current version of {}", upgrade.package))?;
+        let before = analyze_copy(&mut log)?;
+        let manifests = upgrade.apply(&copy.root)?;
+        log.info(&format!("Analyzing with {} {}", upgrade.package, upgrade.version))?;
+        let after = analyze_copy(&mut log)?;
+
+        let comparison = UpgradeComparison::new(&before, &after);
+        write_upgrade_report(io::stdout().lock(), upgrade, manifests.len(), &comparison)?;
+        Ok(comparison.new_errors())
+    }
+
+    /// Options for analyzing without an [`AnalysisRunner`]: the config's
+    /// filters, the triage file, and the path scope.
+    fn analysis_options(&self, root: &Path, config: &AnalyzerConfig) -> io::Result<AnalysisOptions> {
+        let mut options = AnalysisOptions::from_config(config)?;
+        if let Some(path) = &config.false_positives_file {
+            options.false_positives.load_triage_file(path)?;
+        }
+        options.scope = self.path_scope(root)?;
+        options.source_root = Some(root.to_path_buf());
+        options.generated.set_target_dir(workspace_target_dir(root));
+        Ok(options)
+    }
+
+    /// Runs clippy and opens the dashboard on its warnings.
+    #[cfg(feature = "tui")]
+    pub fn tui(&self) -> io::Result<()> {
+        let mut log = self.logger()?;
+        let root = self.root()?;
+        let config = self.load_config(&root)?;
+        let options = self.analysis_options(&root, &config)?;
+
+        let mut progress = self.progress_reporter();
+        let ctx = SourceContext::new(&root, &self.cargo_args, progress.as_mut());
+        let (parsed, completeness) = self.collect_warnings(ctx, None, &config, &mut log)?;
+        if !completeness.is_complete() {
+            log.info(&format!("Showing an incomplete run: {}", completeness))?;
+        }
+        let warnings = analyze(parsed, &options).warnings;
+        run_dashboard(warnings, &root, config.snippet_context.unwrap_or(DEFAULT_CONTEXT_LINES))
+    }
+
+    #[cfg(not(feature = "tui"))]
+    pub fn tui(&self) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "--tui requires cargo-analyzer to be built with the `tui` feature",
+        ))
+    }
+
+    /// Watches the workspace, printing the warnings each change introduces
+    /// or resolves, until Ctrl-C.
+    pub fn watch(&self) -> io::Result<()> {
+        let mut log = self.logger()?;
+        let root = self.root()?;
+        let config = self.load_config(&root)?;
+        let options = self.analysis_options(&root, &config)?;
+        let mut color = ColorWriter::new();
+
+        let links = EditorLinks::for_stdout(config.editor_link.as_deref(), &root)?;
+        let problems_file = config.problems_file.as_ref().map(|path| root.join(path));
+        let mut snapshot = SourceSnapshot::capture(&root);
+        let mut previous: Option<Vec<Warning>> = None;
+        loop {
+            let mut progress = self.progress_reporter();
+            let ctx = SourceContext::new(&root, &self.cargo_args, progress.as_mut());
+            let (parsed, completeness) =
+                self.collect_warnings(ctx, None, &config, &mut log)?;
+            if interrupted() {
+                return Ok(());
+            }
+            let warnings = analyze(parsed, &options).warnings;
+            let time = chrono::Local::now().format("%H:%M:%S");
+            match &previous {
+                None => writeln!(color.writer(), "[{}] {} warnings", time, warnings.len())?,
+                Some(previous) => {
+                    let mut diff = diff_warnings(previous, &warnings);
+                    // A partial run would report its missing warnings as resolved
+                    if !completeness.is_complete() {
+                        diff.resolved.clear();
+                    }
+                    writeln!(
+                        color.writer(),
+                        "[{}] {} warnings ({} new, {} resolved)",
+                        time,
+                        warnings.len(),
+                        diff.introduced.len(),
+                        diff.resolved.len()
+                    )?;
+                    for warning in &diff.introduced {
+                        color.write_colored(&format!("  + {}\n", links.link(&format_watched(warning), warning)), Color::Red)?;
+                    }
+                    for warning in &diff.resolved {
+                        color.write_colored(&format!("  - {}\n", links.link(&format_watched(warning), warning)), Color::Green)?;
+                    }
+                }
+            }
+            if !completeness.is_complete() {
+                color.write_warning(&format!("⚠️  {}\n", completeness))?;
+            }
+            // Written after every run, so editors show what the last build
+            // reported even when it was cut short
+            if let Some(path) = &problems_file {
+                write_problems_file(path, &warnings)?;
+            }
+            if completeness.is_complete() || previous.is_none() {
+                previous = Some(warnings);
+            }
+
+            log.info("Watching for changes; press Ctrl-C to stop")?;
+            let idle = cancel_on_interrupt()?;
+            loop {
+                std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+                if idle.is_cancelled() {
+                    return Ok(());
+                }
+                let current = SourceSnapshot::capture(&root);
+                if current.changed_since(&snapshot) > 0 {
+                    // Let editors finish saving before rerunning
+                    std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+                    snapshot = SourceSnapshot::capture(&root);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Runs clippy and applies its suggested fixes, asking about each one
+    /// when `interactive` is set, then records the decisions.
+    pub fn fix(&self, interactive: bool) -> io::Result<FixSummary> {
+        let mut log = self.logger()?;
+        let root = self.root()?;
+        let config = self.load_config(&root)?;
+
+        let mut progress = self.progress_reporter();
+        let mut ctx = SourceContext::new(&root, &self.cargo_args, progress.as_mut());
+        ctx.keep_raw = true;
+        let ((warnings, _, _), completeness) = self.collect_warnings(ctx, None, &AnalyzerConfig::default(), &mut log)?;
+        if !completeness.is_complete() {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, completeness.to_string()));
+        }
+        let mut generated = GeneratedCodeFilter::new(&config.generated, config.include_generated)?;
+        generated.set_target_dir(workspace_target_dir(&root));
+        let (warnings, _) = generated.partition(warnings);
+        let (warnings, _) = partition_third_party(warnings, Some(root.as_path()));
+        let (warnings, _) = SuppressionFilter::from_specs(&config.ignore)?.partition(warnings);
+
+        let decisions_path = config.fix_decisions_path();
+        let decisions = FixDecisions::load(&decisions_path)?;
+        let color = io::stdout().is_terminal();
+        let mut session = FixSession::new(&root, decisions, interactive, color);
+        session.set_editor_links(EditorLinks::for_stdout(config.editor_link.as_deref(), &root)?);
+        // Decisions made before a failure are kept, too
+        let summary = session.run(&warnings, io::stdin().lock(), &mut io::stdout());
+        session.into_decisions().save(&decisions_path)?;
+        log.info(&format!("Decisions saved to {}", decisions_path.display()))?;
+        summary
+    }
+
+    /// Collects warnings in the workspace of `ctx` from clippy, rustfmt and
+    /// cargo's future-incompatibility report when enabled in `config`, its
+    /// external tools, and every added source, saving clippy's raw output
+    /// to `output_path` if given.
+    fn collect_warnings(
+        &self,
+        mut ctx: SourceContext<'_>,
+        output_path: Option<&Path>,
+        config: &AnalyzerConfig,
+        log: &mut Logger,
+    ) -> io::Result<(ParseResult, Completeness)> {
+        let mut clippy = self.clippy_source();
+        if let Some(output_path) = output_path {
+            clippy.set_output_path(output_path.to_path_buf());
+        }
+        let mut sources: Vec<&dyn WarningSource> = vec![&clippy];
+        if config.fmt_check {
+            sources.push(&RustfmtSource);
+        }
+        if config.future_incompat {
+            sources.push(&FutureIncompatSource);
+        }
+        let external = config
+            .sources
+            .iter()
+            .map(|(name, command)| ExternalSource::new(name, command))
+            .collect::<io::Result<Vec<_>>>()?;
+        sources.extend(external.iter().map(|source| source as &dyn WarningSource));
+        sources.extend(self.sources.iter().map(Box::as_ref));
+
+        let mut warnings = Vec::new();
+        for source in sources {
+            log.debug(&format!("Collecting warnings from {}", source.name()))?;
+            match source.collect(&mut ctx) {
+                Ok(collected) => warnings.extend(collected),
+                Err(e) if source.is_required() => return Err(e),
+                Err(e) => log.info(&format!("Skipping {}: {}", source.name(), e))?,
+            }
+        }
+        Ok(ctx.finish(warnings))
+    }
+
+    fn clippy_source(&self) -> ClippySource {
+        let mut clippy = ClippySource::new();
+        clippy.set_jobs(self.jobs);
+        clippy.set_timeout(self.timeout);
+        clippy.set_quiet(self.verbosity.is_quiet());
+        clippy.set_clippy_args(self.clippy_args.clone());
+        clippy
+    }
+}
+
+/// Clippy lint flags, each flag followed by its lint.
+fn lint_flags(flags: &[(&str, &Vec<String>)]) -> Vec<String> {
+    flags
+        .iter()
+        .flat_map(|(flag, lints)| lints.iter().flat_map(move |lint| [flag.to_string(), lint.clone()]))
+        .collect()
+}
+
+/// Splits the arguments after `--` into those for cargo and those for
+/// clippy: everything after a second `--`, and the lint flags before it,
+/// which cargo would reject.
+fn split_extra_args(extra_args: Vec<String>) -> (Vec<String>, Vec<String>) {
+    let (mut cargo_args, mut clippy_args) = (Vec::new(), Vec::new());
+    let mut extra_args = extra_args.into_iter();
+    while let Some(arg) = extra_args.next() {
+        match arg.as_str() {
+            "--" => {
+                clippy_args.extend(extra_args);
+                break;
+            }
+            "-W" | "-A" | "-D" | "--warn" | "--allow" | "--deny" => {
+                clippy_args.push(arg);
+                clippy_args.extend(extra_args.next());
+            }
+            _ if arg.len() > 2 && ["-W", "-A", "-D"].iter().any(|flag| arg.starts_with(flag)) => clippy_args.push(arg),
+            _ => cargo_args.push(arg),
+        }
+    }
+    (cargo_args, clippy_args)
+}
+
+/// Argument cargo inserts after the program name when it runs the binary
+/// as `cargo analyzer`
+const CARGO_SUBCOMMAND: &str = "analyzer";
+
+/// Parses the command line of either `cargo analyzer ARGS`, which cargo
+/// runs as `cargo-analyzer analyzer ARGS`, or `cargo-analyzer ARGS`.
+fn parse_args(args: impl IntoIterator<Item = OsString>) -> clap::error::Result<(CliArgs, ArgMatches)> {
+    let mut args: Vec<OsString> = args.into_iter().collect();
+    let mut command = CliArgs::command();
+    if args.get(1).is_some_and(|arg| arg == CARGO_SUBCOMMAND) {
+        args.remove(1);
+        command = command.bin_name("cargo analyzer");
+    }
+    let matches = command.try_get_matches_from(args)?;
+    let mut cli = CliArgs::from_arg_matches(&matches)?;
+    cli.lint_flags = ordered_lint_flags(&matches);
+    Ok((cli, matches))
+}
+
+/// The `-W`, `-A`, and `-D` lint flags in command line order, since later
+/// flags override earlier ones for the lints they share.
+fn ordered_lint_flags(matches: &ArgMatches) -> Vec<String> {
+    let mut flags: Vec<(usize, &str, String)> = Vec::new();
+    for (id, flag) in [("warn", "-W"), ("allow", "-A"), ("deny", "-D")] {
+        let (Some(indices), Some(lints)) = (matches.indices_of(id), matches.get_many::<String>(id)) else { continue };
+        flags.extend(indices.zip(lints).map(|(index, lint)| (index, flag, lint.clone())));
+    }
+    flags.sort_by_key(|(index, _, _)| *index);
+    flags.into_iter().flat_map(|(_, flag, lint)| [flag.to_string(), lint]).collect()
 }
 
-pub fn run_analysis() -> io::Result<()> {
-    // Skip "cargo" and "analyzer" from args when run as cargo subcommand
-    let args = CliArgs::parse_from(std::env::args().skip(2));
-    ClippyWorkflow::new(args).run()
-} 
\ No newline at end of file
+/// Runs the benchmark workloads and prints a table of the results.
+fn bench(sizes: &[WorkloadSize], jobs: usize) -> i32 {
+    let mut results = Vec::new();
+    for &size in sizes {
+        eprintln!("Benchmarking {} warnings...", size.0);
+        match run_bench(size, jobs) {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return 1;
+            }
+        }
+    }
+    print!("{}", format_results(&results));
+    0
+}
+
+/// 0 on success; otherwise prints the error and returns 1.
+fn exit_code(result: io::Result<()>) -> i32 {
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+/// Posts the comment in `body` on pull request `pr`.
+fn comment(dir: &Path, body: &Path, pr: u64, repo: Option<&str>) -> io::Result<()> {
+    let body = std::fs::read_to_string(body)
+        .map_err(|e| io::Error::new(e.kind(), format!("Cannot read {}: {}", body.display(), e)))?;
+    match post_pr_comment(dir, repo, pr, &body)? {
+        CommentOutcome::Created => println!("Posted the analysis comment on #{}", pr),
+        CommentOutcome::Updated { id, minimized } => {
+            println!("Updated the analysis comment {} on #{}", id, pr);
+            if minimized > 0 {
+                println!("Collapsed {} outdated comments", minimized);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses the command line, runs the workflow, and returns the process exit code.
+pub fn run_analysis() -> i32 {
+    let (args, _) = parse_args(std::env::args_os()).unwrap_or_else(|e| e.exit());
+    let check = args.check;
+    let gate_only = args.gate_only;
+    let watch = args.watch;
+    let tui = args.tui;
+    let fix = match args.command {
+        Some(AnalyzerCommand::Fix { interactive }) => Some(interactive),
+        _ => None,
+    };
+    match &args.command {
+        Some(AnalyzerCommand::Bench { sizes }) => return bench(sizes, args.jobs),
+        Some(AnalyzerCommand::Diff { old, new }) => return exit_code(write_saved_diff(io::stdout().lock(), old, new)),
+        Some(AnalyzerCommand::Schema) => {
+            print!("{}", WARNING_SCHEMA);
+            return 0;
+        }
+        Some(AnalyzerCommand::Comment { body, pr, repo }) => {
+            let dir = args.working_dir.clone().unwrap_or_else(|| PathBuf::from("."));
+            return exit_code(comment(&dir, body, *pr, repo.as_deref()));
+        }
+        _ => {}
+    }
+    let report = match &args.command {
+        Some(AnalyzerCommand::Report { input }) => Some(input.clone()),
+        _ => None,
+    };
+    let trends = match &args.command {
+        Some(AnalyzerCommand::Trends { runs }) => Some(*runs),
+        _ => None,
+    };
+    let simulation = match &args.command {
+        Some(AnalyzerCommand::SimulateGates { config, runs }) => Some((config.clone(), *runs)),
+        _ => None,
+    };
+    let baseline = match &args.command {
+        Some(AnalyzerCommand::Baseline { action }) => Some(action.clone()),
+        _ => None,
+    };
+    let bundle_dir = match &args.command {
+        Some(AnalyzerCommand::Bundle { dir }) => Some(dir.clone()),
+        _ => None,
+    };
+    let upgrade = match &args.command {
+        Some(AnalyzerCommand::UpgradeCheck { package, version }) => Some(DependencyUpgrade::new(package, version)),
+        _ => None,
+    };
+    let import_dir = match &args.command {
+        Some(AnalyzerCommand::ImportHistory { dir }) => Some(dir.clone()),
+        _ => None,
+    };
+    let self_analysis = match &args.command {
+        Some(AnalyzerCommand::AnalyzeSelf { snapshot, crate_dir, bless }) => {
+            Some((snapshot.clone(), crate_dir.clone(), *bless))
+        }
+        _ => None,
+    };
+    let workflow = ClippyWorkflow::new(args);
+
+    if let Some(input) = report {
+        exit_code(workflow.report(&input))
+    } else if let Some(runs) = trends {
+        exit_code(workflow.trends(runs))
+    } else if let Some((config, runs)) = simulation {
+        exit_code(workflow.simulate_gates(config.as_deref(), runs))
+    } else if let Some(action) = baseline {
+        exit_code(match action {
+            BaselineAction::Export { rev, output } => workflow.export_baseline(&rev, output.as_deref()),
+            BaselineAction::Import { file, commit } => workflow.import_baseline(&file, commit.as_deref()),
+        })
+    } else if let Some(dir) = bundle_dir {
+        exit_code(workflow.export_bundle(&dir))
+    } else if let Some(upgrade) = upgrade {
+        match workflow.upgrade_check(&upgrade) {
+            Ok(new_errors) => i32::from(new_errors > 0),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                TOOL_ERROR_EXIT_CODE
+            }
+        }
+    } else if let Some((snapshot, crate_dir, bless)) = self_analysis {
+        match workflow.analyze_self(&snapshot, crate_dir.as_deref(), bless) {
+            Ok(matches) => i32::from(!matches),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                TOOL_ERROR_EXIT_CODE
+            }
+        }
+    } else if let Some(dir) = import_dir {
+        match workflow.import_history(&dir) {
+            Ok(summary) => {
+                for (path, reason) in &summary.failed {
+                    eprintln!("Skipped {}: {}", path.display(), reason);
+                }
+                println!(
+                    "Imported {} runs into {}; {} were already recorded",
+                    summary.imported,
+                    summary.target.display(),
+                    summary.already_recorded
+                );
+                0
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                1
+            }
+        }
+    } else if let Some(interactive) = fix {
+        match workflow.fix(interactive) {
+            Ok(summary) => {
+                println!(
+                    "Applied {}, skipped {}, suppressed {} fixes",
+                    summary.applied, summary.skipped, summary.suppressed
+                );
+                if summary.failed > 0 {
+                    println!("{} fixes no longer matched the code; rerun to review them", summary.failed);
+                }
+                0
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                1
+            }
+        }
+    } else if tui {
+        exit_code(workflow.tui())
+    } else if watch {
+        exit_code(workflow.watch())
+    } else if gate_only {
+        match workflow.gate() {
+            Ok(verdict) if verdict.passed() => 0,
+            Ok(_) => 1,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                TOOL_ERROR_EXIT_CODE
+            }
+        }
+    } else if check {
+        workflow.check().map(CheckOutcome::exit_code).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            TOOL_ERROR_EXIT_CODE
+        })
+    } else {
+        exit_code(workflow.run())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clippy_arg_passthrough() {
+        let matches = CliArgs::command().get_matches_from([
+            "cargo-analyzer", "-D", "warnings", "--features", "json", "-W", "clippy::pedantic", "--release",
+            "-A", "clippy::module_name_repetitions", "--", "--locked", "--", "-Zunstable-options",
+        ]);
+        let mut args = CliArgs::from_arg_matches(&matches).unwrap();
+        args.lint_flags = ordered_lint_flags(&matches);
+        let workflow = ClippyWorkflow::new(args);
+        assert_eq!(workflow.cargo_args, ["--features", "json", "--release", "--locked"]);
+        assert_eq!(
+            workflow.clippy_args,
+            ["-D", "warnings", "-W", "clippy::pedantic", "-A", "clippy::module_name_repetitions", "-Zunstable-options"]
+        );
+        let command = workflow.clippy_source().command(Path::new("."), &workflow.cargo_args);
+        let command_line: Vec<_> = command.get_args().map(|arg| arg.to_string_lossy().into_owned()).collect();
+        assert_eq!(command_line[5..7], ["--message-format=json", "--"]);
+    }
+
+    #[test]
+    fn test_cargo_subcommand_invocation() {
+        let parse = |args: &[&str]| {
+            let (args, _) = parse_args(args.iter().map(OsString::from)).unwrap();
+            ClippyWorkflow::new(args)
+        };
+        for args in [&["cargo-analyzer", "analyzer", "--release"][..], &["cargo-analyzer", "--release"]] {
+            assert_eq!(parse(args).cargo_args, ["--release"]);
+        }
+        let workflow = parse(&["/usr/bin/cargo-analyzer", "--", "--locked", "-W", "clippy::pedantic", "-Dwarnings"]);
+        assert_eq!(workflow.cargo_args, ["--locked"]);
+        assert_eq!(workflow.clippy_args, ["-W", "clippy::pedantic", "-Dwarnings"]);
+
+        let help = parse_args(["cargo-analyzer", "analyzer", "--help"].map(OsString::from)).err().unwrap();
+        assert_eq!(help.kind(), clap::error::ErrorKind::DisplayHelp);
+        assert!(help.to_string().contains("Usage: cargo analyzer"));
+        let version = parse_args(["cargo-analyzer", "--version"].map(OsString::from)).err().unwrap();
+        assert_eq!(version.kind(), clap::error::ErrorKind::DisplayVersion);
+    }
+
+    #[test]
+    fn test_bundle_subcommand() {
+        for name in ["bundle", "export-bundle"] {
+            let (args, _) = parse_args(["cargo-analyzer", "analyzer", name, "out"].map(OsString::from)).unwrap();
+            assert!(matches!(args.command, Some(AnalyzerCommand::Bundle { dir }) if dir == Path::new("out")));
+        }
+    }
+}
@@ -1,5 +1,10 @@
 use serde::{Serialize, Deserialize};
 use std::fmt;
+use std::str::FromStr;
+
+/// Categorizations with a confidence below this are reported as likely
+/// mapping gaps.
+pub const LOW_CONFIDENCE: f32 = 0.5;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum CategoryType {
@@ -7,6 +12,8 @@ pub enum CategoryType {
     Performance,
     Style,
     Documentation,
+    /// Diagnostics the categorizer has no mapping for
+    Uncategorized,
 }
 
 impl CategoryType {
@@ -16,6 +23,7 @@ impl CategoryType {
             CategoryType::Performance => "Performance optimizations",
             CategoryType::Style => "Code style and maintainability",
             CategoryType::Documentation => "Documentation completeness",
+            CategoryType::Uncategorized => "Not yet mapped to a category",
         }
     }
 
@@ -25,6 +33,7 @@ impl CategoryType {
             CategoryType::Performance => 3,
             CategoryType::Style => 2,
             CategoryType::Documentation => 1,
+            CategoryType::Uncategorized => 0,
         }
     }
 }
@@ -36,6 +45,22 @@ impl fmt::Display for CategoryType {
             CategoryType::Performance => write!(f, "Performance"),
             CategoryType::Style => write!(f, "Style"),
             CategoryType::Documentation => write!(f, "Documentation"),
+            CategoryType::Uncategorized => write!(f, "Uncategorized"),
+        }
+    }
+}
+
+impl FromStr for CategoryType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "safety" => Ok(CategoryType::Safety),
+            "performance" | "perf" => Ok(CategoryType::Performance),
+            "style" => Ok(CategoryType::Style),
+            "documentation" | "docs" => Ok(CategoryType::Documentation),
+            "uncategorized" => Ok(CategoryType::Uncategorized),
+            other => Err(format!("Unknown category: {}", other)),
         }
     }
 }
@@ -0,0 +1,50 @@
+use std::fmt;
+
+/// Whether an analysis saw all of clippy's output.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Completeness {
+    #[default]
+    Complete,
+    /// The run was cut short; reports only cover what was processed.
+    Partial {
+        reason: String,
+        lines_processed: usize,
+    },
+}
+
+impl Completeness {
+    pub fn is_complete(&self) -> bool {
+        matches!(self, Completeness::Complete)
+    }
+}
+
+impl fmt::Display for Completeness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Completeness::Complete => write!(f, "complete"),
+            Completeness::Partial { reason, lines_processed } => write!(
+                f,
+                "INCOMPLETE ({}): only {} lines of clippy output were processed; counts are lower bounds",
+                reason, lines_processed
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completeness() {
+        assert!(Completeness::default().is_complete());
+        assert_eq!(Completeness::Complete.to_string(), "complete");
+
+        let partial = Completeness::Partial { reason: "interrupted".to_string(), lines_processed: 42 };
+        assert!(!partial.is_complete());
+        assert_eq!(
+            partial.to_string(),
+            "INCOMPLETE (interrupted): only 42 lines of clippy output were processed; counts are lower bounds"
+        );
+    }
+}
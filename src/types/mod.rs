@@ -1,9 +1,11 @@
 pub mod categories;
+pub mod completeness;
 pub mod priorities;
 pub mod subcategories;
 pub mod warnings;
 
 pub use categories::*;
+pub use completeness::*;
 pub use priorities::*;
 pub use subcategories::*;
 pub use warnings::*; 
\ No newline at end of file
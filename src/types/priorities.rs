@@ -1,5 +1,6 @@
 use serde::{Serialize, Deserialize};
 use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Clone, Copy)]
 pub enum Priority {
@@ -42,4 +43,77 @@ impl fmt::Display for Priority {
             Priority::Trivial => write!(f, "Trivial"),
         }
     }
-} 
\ No newline at end of file
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "critical" => Ok(Priority::Critical),
+            "high" => Ok(Priority::High),
+            "medium" => Ok(Priority::Medium),
+            "low" => Ok(Priority::Low),
+            "trivial" => Ok(Priority::Trivial),
+            other => Err(format!("Unknown priority: {}", other)),
+        }
+    }
+}
+
+/// Fix plan wording for one priority: its numeric risk score, the label shown
+/// in headings, and a description of the expected response.
+///
+/// Configured under `[[risk-levels]]` so the plan can follow an organization's
+/// own severity taxonomy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RiskLevel {
+    #[serde(deserialize_with = "deserialize_priority")]
+    pub priority: Priority,
+    pub score: u8,
+    pub label: String,
+    pub description: String,
+}
+
+impl RiskLevel {
+    pub fn new(priority: Priority, score: u8, label: &str, description: &str) -> Self {
+        Self {
+            priority,
+            score,
+            label: label.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    /// The built-in levels, one per priority.
+    pub fn defaults() -> Vec<RiskLevel> {
+        vec![
+            RiskLevel::new(Priority::Critical, 5, "Critical", "Immediate action required (safety issues, potential bugs)"),
+            RiskLevel::new(Priority::High, 4, "High", "Should be fixed soon (correctness issues, performance problems)"),
+            RiskLevel::new(Priority::Medium, 3, "Medium", "Plan to fix (maintainability issues)"),
+            RiskLevel::new(Priority::Low, 2, "Low", "Fix when convenient (style issues)"),
+            RiskLevel::new(Priority::Trivial, 1, "Trivial", "Optional fixes"),
+        ]
+    }
+
+    /// Completes `configured` with the default level of every priority it
+    /// does not mention, ordered from highest to lowest score.
+    pub fn merge_with_defaults(configured: &[RiskLevel]) -> Vec<RiskLevel> {
+        let mut levels = configured.to_vec();
+        for default in RiskLevel::defaults() {
+            if !levels.iter().any(|level| level.priority == default.priority) {
+                levels.push(default);
+            }
+        }
+        levels.sort_by_key(|level| std::cmp::Reverse(level.score));
+        levels
+    }
+}
+
+fn deserialize_priority<'de, D>(deserializer: D) -> Result<Priority, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let name = String::deserialize(deserializer)?;
+    name.parse().map_err(serde::de::Error::custom)
+}
@@ -7,6 +7,8 @@ pub enum SafetySubcategory {
     UnsafeCode,
     ResourceLeak,
     ConcurrencyIssue,
+    /// Code a future Rust release will reject
+    FutureIncompat,
     Other,
 }
 
@@ -17,6 +19,7 @@ impl SafetySubcategory {
             SafetySubcategory::UnsafeCode => "Usage of unsafe blocks",
             SafetySubcategory::ResourceLeak => "Potential resource leaks",
             SafetySubcategory::ConcurrencyIssue => "Thread safety concerns",
+            SafetySubcategory::FutureIncompat => "Future incompatibility",
             SafetySubcategory::Other => "Other safety issues",
         }
     }
@@ -3,20 +3,146 @@
 //! This module defines the fundamental types used to represent and process
 //! Clippy warnings throughout the analysis process.
 
+use std::fmt;
+use std::ops::Range;
 use std::path::PathBuf;
 use serde::{Serialize, Deserialize};
-use super::categories::CategoryType;
+use super::categories::{CategoryType, LOW_CONFIDENCE};
 use super::priorities::Priority;
+use super::subcategories::SafetySubcategory;
 
 /// Represents the analysis result of a warning: (severity score, impact description)
 pub type WarningAnalysis = (u8, String);
 
+/// The tool that reported a diagnostic.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Source {
+    #[default]
+    Clippy,
+    Rustc,
+    Rustfmt,
+    /// A tool run through the external source protocol
+    External,
+}
+
+impl Source {
+    /// Clippy lints are reported as `clippy::<name>` and formatting
+    /// deviations as `rustfmt::<name>`; everything else, including hard
+    /// errors, comes from rustc.
+    pub fn from_code(code: &str) -> Self {
+        if code.starts_with("clippy::") {
+            Source::Clippy
+        } else if code.starts_with("rustfmt::") {
+            Source::Rustfmt
+        } else {
+            Source::Rustc
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Clippy => write!(f, "clippy"),
+            Source::Rustc => write!(f, "rustc"),
+            Source::Rustfmt => write!(f, "rustfmt"),
+            Source::External => write!(f, "external"),
+        }
+    }
+}
+
+/// Lines and columns a warning points at, 1-based and inclusive, with the
+/// byte offsets rustc reports when known. Field names follow rustc's JSON
+/// spans, so those deserialize directly.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub line_start: u32,
+    pub line_end: u32,
+    pub column_start: u32,
+    pub column_end: u32,
+    /// Byte offset of the start in the file, 0-based
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byte_start: Option<usize>,
+    /// Byte offset just past the end
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byte_end: Option<usize>,
+}
+
+impl Span {
+    pub fn new(line_start: u32, column_start: u32, line_end: u32, column_end: u32) -> Self {
+        Self { line_start, line_end, column_start, column_end, byte_start: None, byte_end: None }
+    }
+
+    /// A span covering a single position.
+    pub fn at(line: u32, column: u32) -> Self {
+        Self::new(line, column, line, column)
+    }
+
+    /// The byte offsets, when both are known and in order.
+    pub fn byte_range(&self) -> Option<Range<usize>> {
+        match (self.byte_start, self.byte_end) {
+            (Some(start), Some(end)) if start <= end => Some(start..end),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}-{}:{}", self.line_start, self.column_start, self.line_end, self.column_end)
+    }
+}
+
+/// The macro whose expansion produced a warning's code.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MacroOrigin {
+    /// The macro as rustc names it, e.g. `vec!` or `#[derive(Debug)]`
+    pub name: String,
+    /// `file:line` of the macro definition, when rustc reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+    /// Whether the macro is defined outside the workspace: in a dependency,
+    /// the standard library, or a proc macro
+    pub external: bool,
+}
+
+impl fmt::Display for MacroOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "in an expansion of `{}`", self.name)?;
+        match &self.definition {
+            Some(definition) => write!(f, ", defined at {}", definition),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A diagnostic reported for one location.
+///
+/// `lint_code` and `primary_message` are serialized as `id` and `message`,
+/// the names exported warnings have always had. Warnings serialized before
+/// the message was split into fields, with a `message` holding the
+/// location, explanations, and child messages, still deserialize:
+/// `primary_message` keeps only its first line.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Warning {
-    /// Unique identifier for the warning
-    pub id: String,
-    /// The specific warning message
-    pub message: String,
+    /// Lint or error code, e.g. `clippy::unwrap_used` or `E0308`
+    #[serde(rename = "id", alias = "lint_code")]
+    pub lint_code: String,
+    /// The diagnostic's main message, one line
+    #[serde(rename = "message", alias = "primary_message", deserialize_with = "first_line")]
+    pub primary_message: String,
+    /// Where the warning points; `None` for warnings stored without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+    /// `= help:` notes from the rendered diagnostic
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub explanations: Vec<String>,
+    /// Messages of the diagnostic's children (notes, help, suggestions)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<String>,
+    /// The diagnostic as rustc printed it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rendered: Option<String>,
     /// Category of the warning (style, safety, etc.)
     pub category: CategoryType,
     /// Priority level of the warning
@@ -27,9 +153,118 @@ pub struct Warning {
     pub line: u32,
     /// Suggested fix for the warning
     pub suggested_fix: Option<String>,
+    /// Name of the package the warning was reported for
+    #[serde(default)]
+    pub crate_name: Option<String>,
+    /// Tool that reported the diagnostic
+    #[serde(default)]
+    pub source: Source,
+    /// Whether the diagnostic is a hard error rather than a lint
+    #[serde(default)]
+    pub is_error: bool,
+    /// How sure the categorizer is of `category`, from 0.0 to 1.0
+    #[serde(default = "full_confidence")]
+    pub category_confidence: f32,
+    /// Original JSON diagnostic, kept with `--embed-raw`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+    /// Identity across runs, assigned during analysis (see
+    /// `analysis::fingerprint`); empty until then
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub fingerprint: String,
+    /// The macro the flagged code came from; the location is then the
+    /// macro's call site in the workspace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub macro_origin: Option<MacroOrigin>,
+    /// Safety subcategory known from the diagnostic itself rather than
+    /// guessed from its message, e.g. a future incompatibility
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safety_subcategory: Option<SafetySubcategory>,
+}
+
+/// 64-bit FNV-1a hash of `parts`, as 16 hex digits.
+pub(crate) fn fingerprint_hash(parts: &[&str]) -> String {
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+    let mut hash = FNV_OFFSET;
+    for part in parts {
+        // NUL separates the parts so ("ab", "c") and ("a", "bc") differ
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+fn full_confidence() -> f32 {
+    1.0
+}
+
+fn first_line<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let message = String::deserialize(deserializer)?;
+    Ok(message.lines().next().unwrap_or("").to_string())
 }
 
 impl Warning {
+    /// Whether the category is likely a guess; see [`LOW_CONFIDENCE`].
+    pub fn is_low_confidence(&self) -> bool {
+        self.category_confidence < LOW_CONFIDENCE
+    }
+
+    /// Identifies the warning across runs: the assigned fingerprint, or the
+    /// message fingerprint for warnings that were never assigned one.
+    pub fn fingerprint(&self) -> String {
+        if self.fingerprint.is_empty() {
+            self.message_fingerprint()
+        } else {
+            self.fingerprint.clone()
+        }
+    }
+
+    /// Hash of the lint, file, and first message line. Fingerprints recorded
+    /// before they were based on code (triage files, fix decisions) are
+    /// message fingerprints.
+    pub fn message_fingerprint(&self) -> String {
+        fingerprint_hash(&[&self.lint_code, &self.file, &self.primary_message])
+    }
+
+    /// Whether `fingerprint` identifies this warning, as either its
+    /// fingerprint or its message fingerprint.
+    pub fn matches_fingerprint(&self, fingerprint: &str) -> bool {
+        fingerprint == self.fingerprint() || fingerprint == self.message_fingerprint()
+    }
+
+    /// Identifies the warning's site: the lint, file, and start position.
+    /// Unlike the fingerprint, two warnings with the same message in one
+    /// file get different keys.
+    pub fn site_key(&self) -> (&str, &str, u32, u32) {
+        let (line, column) = self.span.map_or((self.line, 0), |span| (span.line_start, span.column_start));
+        (&self.lint_code, &self.file, line, column)
+    }
+
+    /// The primary message followed by the location, explanations, and
+    /// child messages, one per line, for detailed reports.
+    pub fn details(&self) -> String {
+        let mut details = self.primary_message.clone();
+        if let Some(span) = &self.span {
+            details.push_str(&format!("\nLocation: {}-{}", span, self.file));
+        }
+        if let Some(origin) = &self.macro_origin {
+            details.push_str(&format!("\nMacro: {}", origin));
+        }
+        for explanation in &self.explanations {
+            details.push_str(&format!("\nExplanation: {}", explanation));
+        }
+        for child in &self.children {
+            details.push_str(&format!("\nNote: {}", child));
+        }
+        details
+    }
+
     /// Analyzes the warning to extract additional insights.
     /// 
     /// This method processes the warning's contents to determine:
@@ -50,12 +285,42 @@ impl Warning {
             CategoryType::Performance => format!("Performance bottleneck in {} (line {})", self.file, self.line),
             CategoryType::Style => format!("Style improvement needed in {} (line {})", self.file, self.line),
             CategoryType::Documentation => format!("Documentation needed in {} (line {})", self.file, self.line),
+            CategoryType::Uncategorized => format!("Uncategorized issue in {} (line {})", self.file, self.line),
         };
 
         (severity_score, impact_description)
     }
 }
 
+#[cfg(test)]
+impl Warning {
+    /// A clippy style warning of `lint_code` at `file:line` with no message
+    /// or span, for tests to override with struct update syntax.
+    pub(crate) fn for_test(lint_code: &str, file: &str, line: u32) -> Self {
+        Warning {
+            lint_code: lint_code.to_string(),
+            primary_message: String::new(),
+            span: None,
+            explanations: Vec::new(),
+            children: Vec::new(),
+            rendered: None,
+            category: CategoryType::Style,
+            priority: Priority::Medium,
+            file: file.to_string(),
+            line,
+            suggested_fix: None,
+            crate_name: None,
+            source: Source::Clippy,
+            is_error: false,
+            category_confidence: 1.0,
+            raw: None,
+            fingerprint: String::new(),
+            macro_origin: None,
+            safety_subcategory: None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct FileWarnings {
     pub file_path: PathBuf,